@@ -0,0 +1,312 @@
+//! 应用的多语言名称
+//!
+//! 索引时为每个应用收集其他语言的名称（存入 AppInfo::alt_names），任一语言输入都能命中，
+//! 结果仍显示当前系统语言的名称。来源：
+//! - 开始菜单目录 desktop.ini 的 [LocalizedFileNames]（快捷方式文件名通常是英文，显示名为本地化资源）
+//! - exe 版本信息中各语言的 FileDescription
+//! - UWP 包的 DisplayName 资源（ms-resource），按多种界面语言解析
+
+use std::collections::HashMap;
+use std::path::Path;
+
+/// 除当前系统语言外额外解析的界面语言
+const EXTRA_LANGUAGES: &[&str] = &["en-US", "zh-CN"];
+
+/// 读取目录下 desktop.ini 的 [LocalizedFileNames]：文件名（小写）-> 资源字符串（如 "@%SystemRoot%\system32\shell32.dll,-22051"）
+pub fn desktop_ini_names(dir: &Path) -> HashMap<String, String> {
+    let Ok(bytes) = std::fs::read(dir.join("desktop.ini")) else {
+        return HashMap::new();
+    };
+    // desktop.ini 通常为带 BOM 的 UTF-16LE
+    let text = if bytes.starts_with(&[0xFF, 0xFE]) {
+        let units: Vec<u16> = bytes[2..]
+            .chunks_exact(2)
+            .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+            .collect();
+        String::from_utf16_lossy(&units)
+    } else {
+        String::from_utf8_lossy(&bytes).into_owned()
+    };
+
+    let mut names = HashMap::new();
+    let mut in_section = false;
+    for line in text.lines() {
+        let line = line.trim();
+        if line.starts_with('[') {
+            in_section = line.eq_ignore_ascii_case("[LocalizedFileNames]");
+        } else if in_section {
+            if let Some((file, source)) = line.split_once('=') {
+                let (file, source) = (file.trim(), source.trim());
+                if !file.is_empty() && !source.is_empty() {
+                    names.insert(file.to_lowercase(), source.to_string());
+                }
+            }
+        }
+    }
+    names
+}
+
+/// 解析资源字符串：第一个为当前系统语言的结果，其余为其他界面语言下不同的结果
+///
+/// 不以 "@" 开头的视为字面名称原样返回
+pub fn resolve_names(source: &str) -> Vec<String> {
+    if !source.starts_with('@') {
+        return vec![source.to_string()];
+    }
+    let mut names: Vec<String> = Vec::new();
+    if let Some(name) = self::windows::load_indirect_string(source, None) {
+        names.push(name);
+    }
+    for language in EXTRA_LANGUAGES {
+        if let Some(name) = self::windows::load_indirect_string(source, Some(language)) {
+            if !names.contains(&name) {
+                names.push(name);
+            }
+        }
+    }
+    names
+}
+
+/// exe 版本信息中所有语言的 FileDescription
+pub fn file_descriptions(path: &Path) -> Vec<String> {
+    self::windows::file_descriptions(path)
+}
+
+/// 已安装 UWP 包的 DisplayName 资源字符串：包家族名（小写）-> 资源字符串
+pub fn uwp_display_sources() -> HashMap<String, String> {
+    self::windows::uwp_display_sources()
+}
+
+/// UWP AppID（"PackageFamilyName!App"）对应包的其他语言名称
+pub fn uwp_alt_names(app_id: &str, display_sources: &HashMap<String, String>) -> Vec<String> {
+    let family = app_id.split('!').next().unwrap_or(app_id).to_lowercase();
+    display_sources
+        .get(&family)
+        .map(|source| resolve_names(source))
+        .unwrap_or_default()
+}
+
+/// 去重并去掉与主名称相同（忽略大小写）或过短的名称
+pub fn collect_alt_names(
+    primary: &str,
+    candidates: impl IntoIterator<Item = String>,
+) -> Vec<String> {
+    let primary = primary.trim().to_lowercase();
+    let mut seen = vec![primary];
+    let mut names = Vec::new();
+    for name in candidates {
+        let name = name.trim().to_string();
+        let lower = name.to_lowercase();
+        if name.chars().count() < 2 || seen.contains(&lower) {
+            continue;
+        }
+        seen.push(lower);
+        names.push(name);
+    }
+    names
+}
+
+#[cfg(target_os = "windows")]
+mod windows {
+    use std::collections::HashMap;
+    use std::ffi::OsStr;
+    use std::os::windows::ffi::OsStrExt;
+    use std::path::Path;
+    use windows_sys::Win32::Foundation::FILETIME;
+    use windows_sys::Win32::Globalization::{SetThreadPreferredUILanguages, MUI_LANGUAGE_NAME};
+    use windows_sys::Win32::Storage::FileSystem::{
+        GetFileVersionInfoSizeW, GetFileVersionInfoW, VerQueryValueW,
+    };
+    use windows_sys::Win32::System::Registry::{
+        RegCloseKey, RegEnumKeyExW, RegGetValueW, RegOpenKeyExW, HKEY, HKEY_CURRENT_USER, KEY_READ,
+        RRF_RT_REG_SZ,
+    };
+    use windows_sys::Win32::UI::Shell::SHLoadIndirectString;
+
+    const PACKAGES_KEY: &str = "Software\\Classes\\Local Settings\\Software\\Microsoft\\Windows\\CurrentVersion\\AppModel\\Repository\\Packages";
+
+    fn to_wide(s: &str) -> Vec<u16> {
+        OsStr::new(s)
+            .encode_wide()
+            .chain(std::iter::once(0))
+            .collect()
+    }
+
+    fn from_wide(buffer: &[u16]) -> String {
+        let len = buffer.iter().position(|&c| c == 0).unwrap_or(buffer.len());
+        String::from_utf16_lossy(&buffer[..len])
+    }
+
+    /// 用 SHLoadIndirectString 解析资源字符串，language 指定时临时切换线程界面语言
+    pub fn load_indirect_string(source: &str, language: Option<&str>) -> Option<String> {
+        let source_wide = to_wide(source);
+        let mut buffer = vec![0u16; 512];
+        unsafe {
+            if let Some(language) = language {
+                // 语言列表以两个 0 结尾
+                let mut languages = to_wide(language);
+                languages.push(0);
+                if SetThreadPreferredUILanguages(
+                    MUI_LANGUAGE_NAME,
+                    languages.as_ptr(),
+                    std::ptr::null_mut(),
+                ) == 0
+                {
+                    return None;
+                }
+            }
+            let hr = SHLoadIndirectString(
+                source_wide.as_ptr(),
+                buffer.as_mut_ptr(),
+                buffer.len() as u32,
+                std::ptr::null(),
+            );
+            if language.is_some() {
+                // 传入空列表恢复默认语言
+                SetThreadPreferredUILanguages(0, std::ptr::null(), std::ptr::null_mut());
+            }
+            if hr < 0 {
+                return None;
+            }
+        }
+        let name = from_wide(&buffer).trim().to_string();
+        (!name.is_empty()).then_some(name)
+    }
+
+    pub fn file_descriptions(path: &Path) -> Vec<String> {
+        let path_wide: Vec<u16> = path
+            .as_os_str()
+            .encode_wide()
+            .chain(std::iter::once(0))
+            .collect();
+        let mut descriptions = Vec::new();
+        unsafe {
+            let size = GetFileVersionInfoSizeW(path_wide.as_ptr(), std::ptr::null_mut());
+            if size == 0 {
+                return descriptions;
+            }
+            let mut data = vec![0u8; size as usize];
+            if GetFileVersionInfoW(path_wide.as_ptr(), 0, size, data.as_mut_ptr().cast()) == 0 {
+                return descriptions;
+            }
+
+            // 每个翻译为 (语言 ID, 代码页)
+            let mut translations: *mut std::ffi::c_void = std::ptr::null_mut();
+            let mut len = 0u32;
+            let query = to_wide("\\VarFileInfo\\Translation");
+            if VerQueryValueW(
+                data.as_ptr().cast(),
+                query.as_ptr(),
+                &mut translations,
+                &mut len,
+            ) == 0
+                || translations.is_null()
+            {
+                return descriptions;
+            }
+            let pairs = std::slice::from_raw_parts(translations as *const u16, len as usize / 2);
+            for pair in pairs.chunks_exact(2) {
+                let query = to_wide(&format!(
+                    "\\StringFileInfo\\{:04x}{:04x}\\FileDescription",
+                    pair[0], pair[1]
+                ));
+                let mut value: *mut std::ffi::c_void = std::ptr::null_mut();
+                let mut chars = 0u32;
+                if VerQueryValueW(data.as_ptr().cast(), query.as_ptr(), &mut value, &mut chars) != 0
+                    && !value.is_null()
+                    && chars > 0
+                {
+                    let text = from_wide(std::slice::from_raw_parts(
+                        value as *const u16,
+                        chars as usize,
+                    ));
+                    let text = text.trim().to_string();
+                    if !text.is_empty() && !descriptions.contains(&text) {
+                        descriptions.push(text);
+                    }
+                }
+            }
+        }
+        descriptions
+    }
+
+    /// 枚举 AppModel 仓库中的包（键名为包全名 Name_Version_Arch_ResourceId_PublisherId），
+    /// 读取 DisplayName 值，按包家族名 Name_PublisherId 归类
+    pub fn uwp_display_sources() -> HashMap<String, String> {
+        let mut sources = HashMap::new();
+        unsafe {
+            let mut key: HKEY = 0;
+            let path = to_wide(PACKAGES_KEY);
+            if RegOpenKeyExW(HKEY_CURRENT_USER, path.as_ptr(), 0, KEY_READ, &mut key) != 0 {
+                return sources;
+            }
+            let value_name = to_wide("DisplayName");
+            let mut index = 0u32;
+            loop {
+                let mut name = [0u16; 256];
+                let mut name_len = name.len() as u32;
+                let mut last_write: FILETIME = std::mem::zeroed();
+                if RegEnumKeyExW(
+                    key,
+                    index,
+                    name.as_mut_ptr(),
+                    &mut name_len,
+                    std::ptr::null(),
+                    std::ptr::null_mut(),
+                    std::ptr::null_mut(),
+                    &mut last_write,
+                ) != 0
+                {
+                    break;
+                }
+                index += 1;
+
+                let full_name = String::from_utf16_lossy(&name[..name_len as usize]);
+                let parts: Vec<&str> = full_name.split('_').collect();
+                if parts.len() < 5 {
+                    continue;
+                }
+                let family = format!("{}_{}", parts[0], parts[parts.len() - 1]).to_lowercase();
+
+                let mut buffer = [0u16; 1024];
+                let mut size = std::mem::size_of_val(&buffer) as u32;
+                let sub_key = to_wide(&full_name);
+                if RegGetValueW(
+                    key,
+                    sub_key.as_ptr(),
+                    value_name.as_ptr(),
+                    RRF_RT_REG_SZ,
+                    std::ptr::null_mut(),
+                    buffer.as_mut_ptr().cast(),
+                    &mut size,
+                ) == 0
+                {
+                    let source = from_wide(&buffer);
+                    if !source.is_empty() {
+                        sources.insert(family, source);
+                    }
+                }
+            }
+            RegCloseKey(key);
+        }
+        sources
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+mod windows {
+    use std::collections::HashMap;
+    use std::path::Path;
+
+    pub fn load_indirect_string(_source: &str, _language: Option<&str>) -> Option<String> {
+        None
+    }
+
+    pub fn file_descriptions(_path: &Path) -> Vec<String> {
+        Vec::new()
+    }
+
+    pub fn uwp_display_sources() -> HashMap<String, String> {
+        HashMap::new()
+    }
+}
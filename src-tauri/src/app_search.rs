@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::process::Command;
@@ -13,6 +14,9 @@ pub struct AppInfo {
     pub name_pinyin: Option<String>, // Cached pinyin for faster search
     #[serde(skip_serializing_if = "Option::is_none")]
     pub name_pinyin_initials: Option<String>, // Cached pinyin initials for faster search
+    /// 其他语言的名称（英文快捷方式名、exe 描述、UWP 多语言 DisplayName），用于搜索
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub alt_names: Vec<String>,
 }
 
 #[cfg(target_os = "windows")]
@@ -301,6 +305,7 @@ pub mod windows {
                 description: Some("Windows 系统设置".to_string()),
                 name_pinyin: Some("shezhi".to_string()),
                 name_pinyin_initials: Some("sz".to_string()),
+                alt_names: Vec::new(),
             });
         }
         
@@ -319,6 +324,7 @@ pub mod windows {
                 description: Some("Windows 计算器".to_string()),
                 name_pinyin: Some("jisuanqi".to_string()),
                 name_pinyin_initials: Some("jsq".to_string()),
+                alt_names: Vec::new(),
             });
         }
         
@@ -890,6 +896,8 @@ pub mod windows {
         // #endregion
 
         crate::log!("AppScan", "[UWP] 开始处理 {} 个应用条目...", entries.len());
+        // 包的 DisplayName 资源，用于收集其他语言的名称
+        let display_sources = crate::app_names::uwp_display_sources();
         let mut apps = Vec::with_capacity(entries.len());
         let mut chinese_app_count = 0;
         let processing_start = std::time::Instant::now();
@@ -952,6 +960,15 @@ pub mod windows {
                 (None, None)
             };
 
+            let alt_names = if app_id.contains('!') {
+                crate::app_names::collect_alt_names(
+                    name,
+                    crate::app_names::uwp_alt_names(app_id, &display_sources),
+                )
+            } else {
+                Vec::new()
+            };
+
             apps.push(AppInfo {
                 name: name_string,
                 path,
@@ -959,6 +976,7 @@ pub mod windows {
                 description: None,
                 name_pinyin,
                 name_pinyin_initials,
+                alt_names,
             });
         }
 
@@ -993,6 +1011,8 @@ pub mod windows {
             Ok(entries) => entries,
             Err(_) => return Ok(()), // Skip directories we can't read
         };
+        // 开始菜单中快捷方式的本地化显示名（文件名 -> 资源字符串）
+        let localized_names = crate::app_names::desktop_ini_names(dir);
 
         for entry in entries {
             if apps.len() >= MAX_APPS {
@@ -1026,7 +1046,36 @@ pub mod windows {
                     // Fast path: use filename directly without parsing
                     // Don't extract icon during scan to keep it fast - extract in background later
                     if let Some(name) = path.file_stem().and_then(|s| s.to_str()) {
-                        let name_str = name.to_string();
+                        // 有本地化显示名时以当前系统语言的名称显示，文件名与其他语言名称用于搜索
+                        let file_name = path
+                            .file_name()
+                            .map(|s| s.to_string_lossy().to_lowercase())
+                            .unwrap_or_default();
+                        let mut names = localized_names
+                            .get(&file_name)
+                            .map(|source| crate::app_names::resolve_names(source))
+                            .unwrap_or_default();
+                        let name_str = if names.is_empty() {
+                            name.to_string()
+                        } else {
+                            names.remove(0)
+                        };
+                        names.push(name.to_string());
+                        // exe 版本信息中各语言的描述
+                        let exe_path = if ext.as_deref() == Some("exe") {
+                            Some(path.clone())
+                        } else {
+                            get_lnk_all_paths(&path)
+                                .and_then(|(_, target)| target)
+                                .map(|target| PathBuf::from(expand_env_path(&target)))
+                                .filter(|target| {
+                                    target.extension().is_some_and(|e| e.eq_ignore_ascii_case("exe"))
+                                })
+                        };
+                        if let Some(exe_path) = exe_path {
+                            names.extend(crate::app_names::file_descriptions(&exe_path));
+                        }
+                        let alt_names = crate::app_names::collect_alt_names(&name_str, names);
                         // Pre-compute pinyin for faster search (only for Chinese names)
                         let (name_pinyin, name_pinyin_initials) = if contains_chinese(&name_str) {
                             (
@@ -1043,6 +1092,7 @@ pub mod windows {
                             description: None,
                             name_pinyin,
                             name_pinyin_initials,
+                            alt_names,
                         });
                     }
                 }
@@ -1972,7 +2022,7 @@ try {
     }
     
     /// 获取 .lnk 文件的所有路径信息（IconLocation 和 TargetPath）
-    fn get_lnk_all_paths(lnk_path: &Path) -> Option<(Option<(PathBuf, i32)>, Option<String>)> {
+    pub(crate) fn get_lnk_all_paths(lnk_path: &Path) -> Option<(Option<(PathBuf, i32)>, Option<String>)> {
         use std::fs::File;
         use std::io::{Read, Seek, SeekFrom};
         
@@ -3389,6 +3439,7 @@ public class IconExtractor {
             description: None,
             name_pinyin,
             name_pinyin_initials,
+            alt_names: Vec::new(),
         })
     }
 
@@ -3423,13 +3474,105 @@ public class IconExtractor {
         })
     }
 
+    // Optimal string alignment distance (Levenshtein + adjacent transposition), char based
+    fn edit_distance(a: &[char], b: &[char]) -> usize {
+        let mut prev_prev = vec![0usize; b.len() + 1];
+        let mut prev: Vec<usize> = (0..=b.len()).collect();
+        let mut curr = vec![0usize; b.len() + 1];
+        for i in 1..=a.len() {
+            curr[0] = i;
+            for j in 1..=b.len() {
+                let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+                curr[j] = (prev[j] + 1).min(curr[j - 1] + 1).min(prev[j - 1] + cost);
+                if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                    curr[j] = curr[j].min(prev_prev[j - 2] + 1);
+                }
+            }
+            std::mem::swap(&mut prev_prev, &mut prev);
+            std::mem::swap(&mut prev, &mut curr);
+        }
+        prev[b.len()]
+    }
+
+    // Typo-tolerant score; always lower than any direct/pinyin/description match
+    fn fuzzy_score(query_chars: &[char], query_pinyin: Option<&str>, name_lower: &str, app: &AppInfo) -> i32 {
+        // Chinese homophones, e.g. "微新" -> "微信"
+        if let (Some(query_pinyin), Some(name_pinyin)) = (query_pinyin, app.name_pinyin.as_deref()) {
+            if !query_pinyin.is_empty() {
+                if name_pinyin == query_pinyin {
+                    return 90;
+                } else if name_pinyin.starts_with(query_pinyin) {
+                    return 70;
+                }
+            }
+        }
+
+        let max_distance = match query_chars.len() {
+            0..=2 => return 0,
+            3..=5 => 1,
+            _ => 2,
+        };
+
+        let mut best = usize::MAX;
+        let mut candidates: Vec<&str> = vec![name_lower];
+        if let Some(ref name_pinyin) = app.name_pinyin {
+            candidates.push(name_pinyin.as_str());
+        }
+        for candidate in candidates {
+            let candidate_chars: Vec<char> = candidate.chars().collect();
+            // Compare against the whole name and against a prefix of similar length (partial typing)
+            best = best.min(edit_distance(query_chars, &candidate_chars));
+            for len in [query_chars.len().saturating_sub(1), query_chars.len(), query_chars.len() + 1] {
+                if len > 0 && len < candidate_chars.len() {
+                    best = best.min(edit_distance(query_chars, &candidate_chars[..len]));
+                }
+            }
+        }
+
+        if best <= max_distance {
+            80 - 20 * best as i32
+        } else {
+            0
+        }
+    }
+
+    // Expand the query with user-defined synonyms; every group is bidirectional
+    fn synonym_targets(query_lower: &str, synonyms: &HashMap<String, Vec<String>>) -> Vec<String> {
+        let mut targets = Vec::new();
+        for (key, values) in synonyms {
+            let key_lower = key.to_lowercase();
+            let values_lower: Vec<String> = values.iter().map(|v| v.to_lowercase()).collect();
+            if key_lower == query_lower || values_lower.iter().any(|v| v == query_lower) {
+                targets.extend(
+                    std::iter::once(key_lower)
+                        .chain(values_lower)
+                        .filter(|t| !t.is_empty() && t != query_lower),
+                );
+            }
+        }
+        targets.sort();
+        targets.dedup();
+        targets
+    }
+
     pub fn search_apps(query: &str, apps: &[AppInfo]) -> Vec<AppInfo> {
+        search_apps_with_synonyms(query, apps, &HashMap::new())
+    }
+
+    pub fn search_apps_with_synonyms(
+        query: &str,
+        apps: &[AppInfo],
+        synonyms: &HashMap<String, Vec<String>>,
+    ) -> Vec<AppInfo> {
         if query.is_empty() {
             return apps.iter().take(10).cloned().collect();
         }
 
         let query_lower = query.to_lowercase();
         let query_is_pinyin = !contains_chinese(&query_lower);
+        let query_chars: Vec<char> = query_lower.chars().collect();
+        let query_pinyin = if query_is_pinyin { None } else { Some(to_pinyin(&query_lower)) };
+        let synonym_targets = synonym_targets(&query_lower, synonyms);
 
         // Pre-allocate with capacity estimate to reduce allocations
         let mut results: Vec<(usize, i32)> = Vec::with_capacity(MAX_SEARCH_RESULTS);
@@ -3458,6 +3601,20 @@ public class IconExtractor {
                 score += 100;
             }
 
+            // 其他语言名称（如中文系统下的英文名），分数略低于主名称命中
+            if score == 0 {
+                for alt_name in &app.alt_names {
+                    let alt_lower = alt_name.to_lowercase();
+                    if alt_lower == query_lower {
+                        score = score.max(950);
+                    } else if alt_lower.starts_with(&query_lower) {
+                        score = score.max(450);
+                    } else if alt_lower.contains(&query_lower) {
+                        score = score.max(90);
+                    }
+                }
+            }
+
             // Pinyin matching (if query is pinyin) - use cached pinyin if available
             if query_is_pinyin {
                 // Use cached pinyin if available (much faster than computing on the fly)
@@ -3508,6 +3665,22 @@ public class IconExtractor {
                 }
             }
 
+            // Synonym match (user-defined aliases, e.g. "gc" -> "Chrome")
+            for target in &synonym_targets {
+                if name_lower == *target {
+                    score = score.max(700);
+                } else if name_lower.starts_with(target.as_str()) {
+                    score = score.max(350);
+                } else if name_lower.contains(target.as_str()) {
+                    score = score.max(100);
+                }
+            }
+
+            // Typo-tolerant match gets the lowest score so corrected hits rank last
+            if score == 0 {
+                score = fuzzy_score(&query_chars, query_pinyin.as_deref(), &name_lower, app);
+            }
+
             if score > 0 {
                 results.push((idx, score));
             }
@@ -3535,6 +3708,11 @@ public class IconExtractor {
     }
 
     pub fn launch_app(app: &AppInfo) -> Result<(), String> {
+        launch_app_with_code(app).map_err(|(message, _)| message)
+    }
+
+    /// Launch an app, returning the OS error code alongside the message on failure
+    pub fn launch_app_with_code(app: &AppInfo) -> Result<(), (String, Option<i32>)> {
         use std::process::Command;
         use std::os::windows::process::CommandExt;
 
@@ -3547,7 +3725,7 @@ public class IconExtractor {
                 .args(&["/c", "start", "", path_str])
                 .creation_flags(0x08000000) // CREATE_NO_WINDOW - 不显示控制台窗口
                 .spawn()
-                .map_err(|e| format!("Failed to open Windows Settings: {}", e))?;
+                .map_err(|e| (format!("Failed to open Windows Settings: {}", e), e.raw_os_error()))?;
             
             return Ok(());
         }
@@ -3569,11 +3747,11 @@ public class IconExtractor {
                             .args(&["/c", "start", "", "ms-settings:"])
                             .creation_flags(0x08000000)
                             .spawn()
-                            .map_err(|e| format!("Failed to open Windows Settings (fallback): {}", e))?;
+                            .map_err(|e| (format!("Failed to open Windows Settings (fallback): {}", e), e.raw_os_error()))?;
                         
                         return Ok(());
                     } else {
-                        return Err(format!("Failed to launch application: {}", app.path));
+                        return Err((format!("Failed to launch application: {}", app.path), None));
                     }
                 }
             }
@@ -3591,7 +3769,7 @@ public class IconExtractor {
         if is_lnk {
             // 检查快捷方式文件是否存在
             if !path.exists() {
-                return Err(format!("快捷方式文件不存在: {}", app.path));
+                return Err((format!("快捷方式文件不存在: {}", app.path), Some(2)));
             }
             
             // 解析快捷方式，检查目标是否存在
@@ -3599,9 +3777,12 @@ public class IconExtractor {
                 Ok(target_info) => {
                     let target_path = Path::new(&target_info.path);
                     if !target_path.exists() {
-                        return Err(format!(
-                            "快捷方式目标不存在: 快捷方式 '{}' 指向的目标 '{}' 已移动或删除。请更新或重新创建该快捷方式。",
-                            app.path, target_info.path
+                        return Err((
+                            format!(
+                                "快捷方式目标不存在: 快捷方式 '{}' 指向的目标 '{}' 已移动或删除。请更新或重新创建该快捷方式。",
+                                app.path, target_info.path
+                            ),
+                            Some(2),
                         ));
                     }
                 }
@@ -3611,7 +3792,7 @@ public class IconExtractor {
                 }
             }
         } else if !path.exists() {
-            return Err(format!("应用程序未找到: {}", app.path));
+            return Err((format!("应用程序未找到: {}", app.path), Some(2)));
         }
 
         // Use cmd /c start to launch application with proper environment variables
@@ -3647,7 +3828,7 @@ public class IconExtractor {
                     format!("启动应用程序失败: {} - {}", app.path, e)
                 };
                 
-                Err(error_msg)
+                Err((error_msg, e.raw_os_error()))
             }
         }
     }
@@ -3665,9 +3846,21 @@ pub mod windows {
         vec![]
     }
 
+    pub fn search_apps_with_synonyms(
+        _query: &str,
+        _apps: &[AppInfo],
+        _synonyms: &HashMap<String, Vec<String>>,
+    ) -> Vec<AppInfo> {
+        vec![]
+    }
+
     pub fn launch_app(_app: &AppInfo) -> Result<(), String> {
         Err("App launch is only supported on Windows".to_string())
     }
+
+    pub fn launch_app_with_code(_app: &AppInfo) -> Result<(), (String, Option<i32>)> {
+        Err(("App launch is only supported on Windows".to_string(), None))
+    }
 }
 
 #[cfg(test)]
@@ -3692,6 +3885,7 @@ mod tests {
                 description: None,
                 name_pinyin: Some("weixin".to_string()),
                 name_pinyin_initials: Some("wx".to_string()),
+                alt_names: Vec::new(),
             },
             AppInfo {
                 name: "QQ".to_string(),
@@ -3700,6 +3894,7 @@ mod tests {
                 description: None,
                 name_pinyin: None,
                 name_pinyin_initials: None,
+                alt_names: Vec::new(),
             },
         ];
 
@@ -3718,6 +3913,7 @@ mod tests {
                 description: None,
                 name_pinyin: Some("weixin".to_string()),
                 name_pinyin_initials: Some("wx".to_string()),
+                alt_names: Vec::new(),
             },
         ];
 
@@ -3736,6 +3932,7 @@ mod tests {
                 description: None,
                 name_pinyin: Some("weixin".to_string()),
                 name_pinyin_initials: Some("wx".to_string()),
+                alt_names: Vec::new(),
             },
         ];
 
@@ -3754,6 +3951,7 @@ mod tests {
                 description: None,
                 name_pinyin: None,
                 name_pinyin_initials: None,
+                alt_names: Vec::new(),
             },
         ];
 
@@ -3772,6 +3970,7 @@ mod tests {
                 description: None,
                 name_pinyin: None,
                 name_pinyin_initials: None,
+                alt_names: Vec::new(),
             },
             AppInfo {
                 name: "App2".to_string(),
@@ -3780,6 +3979,7 @@ mod tests {
                 description: None,
                 name_pinyin: None,
                 name_pinyin_initials: None,
+                alt_names: Vec::new(),
             },
         ];
 
@@ -3797,6 +3997,7 @@ mod tests {
                 description: None,
                 name_pinyin: None,
                 name_pinyin_initials: None,
+                alt_names: Vec::new(),
             },
         ];
 
@@ -3814,6 +4015,7 @@ mod tests {
                 description: None,
                 name_pinyin: None,
                 name_pinyin_initials: None,
+                alt_names: Vec::new(),
             },
             AppInfo {
                 name: "Chrome".to_string(),
@@ -3822,6 +4024,7 @@ mod tests {
                 description: None,
                 name_pinyin: None,
                 name_pinyin_initials: None,
+                alt_names: Vec::new(),
             },
         ];
 
@@ -3830,4 +4033,88 @@ mod tests {
         // Exact match should be prioritized
         assert_eq!(results[0].name, "Chrome");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_search_apps_typo_match_ranks_last() {
+        let apps = vec![
+            AppInfo {
+                name: "Chrome".to_string(),
+                path: "C:\\Chrome.exe".to_string(),
+                icon: None,
+                description: None,
+                name_pinyin: None,
+                name_pinyin_initials: None,
+                alt_names: Vec::new(),
+            },
+            AppInfo {
+                name: "Chrom Tools".to_string(),
+                path: "C:\\ChromTools.exe".to_string(),
+                icon: None,
+                description: None,
+                name_pinyin: None,
+                name_pinyin_initials: None,
+                alt_names: Vec::new(),
+            },
+        ];
+
+        let results = windows::search_apps("chorme", &apps);
+        assert!(!results.is_empty());
+        assert_eq!(results[0].name, "Chrome");
+
+        let results = windows::search_apps("chrom", &apps);
+        assert_eq!(results.len(), 2);
+    }
+
+    #[test]
+    fn test_search_apps_homophone_and_synonym_match() {
+        let apps = vec![AppInfo {
+            name: "微信".to_string(),
+            path: "C:\\WeChat.exe".to_string(),
+            icon: None,
+            description: None,
+            name_pinyin: Some("weixin".to_string()),
+            name_pinyin_initials: Some("wx".to_string()),
+            alt_names: Vec::new(),
+        }];
+
+        let results = windows::search_apps("微新", &apps);
+        assert_eq!(results.len(), 1);
+
+        let mut synonyms = HashMap::new();
+        synonyms.insert("微信".to_string(), vec!["wechat".to_string()]);
+        let results = windows::search_apps_with_synonyms("WeChat", &apps, &synonyms);
+        assert_eq!(results.len(), 1);
+    }
+
+    #[test]
+    fn test_search_apps_alt_name_match() {
+        let apps = vec![
+            AppInfo {
+                name: "记事本".to_string(),
+                path: "C:\\Windows\\notepad.exe".to_string(),
+                icon: None,
+                description: None,
+                name_pinyin: Some("jishiben".to_string()),
+                name_pinyin_initials: Some("jsb".to_string()),
+                alt_names: vec!["Notepad".to_string()],
+            },
+            AppInfo {
+                name: "Notepad++".to_string(),
+                path: "C:\\Notepad++\\notepad++.exe".to_string(),
+                icon: None,
+                description: None,
+                name_pinyin: None,
+                name_pinyin_initials: None,
+                alt_names: Vec::new(),
+            },
+        ];
+
+        // 英文名命中中文名的应用，显示的仍是主名称
+        let results = windows::search_apps("notepad", &apps);
+        assert_eq!(results[0].name, "记事本");
+        assert_eq!(results.len(), 2);
+        // 中文名与拼音仍然有效
+        assert_eq!(windows::search_apps("记事本", &apps)[0].name, "记事本");
+        assert_eq!(windows::search_apps("jsb", &apps)[0].name, "记事本");
+    }
+}
@@ -0,0 +1,149 @@
+//! 受限的本地资源协议
+//!
+//! 贴图、预览等窗口通过 `refast-asset://localhost/<目录>/<文件>`（Windows 上为
+//! `http://refast-asset.localhost/<目录>/<文件>`）加载用户模板资源。
+//! 只允许读取 app_data 下白名单目录中的文件，并根据扩展名推断 MIME。
+
+use percent_encoding::percent_decode_str;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use tauri::http::{Request, Response, StatusCode};
+use tauri::{Manager, Runtime, UriSchemeContext};
+
+pub const SCHEME: &str = "refast-asset";
+
+/// 允许通过协议访问的 app_data 子目录（不包含剪贴板图片等用户隐私数据）
+pub const ALLOWED_DIRS: &[&str] = &["templates", "assets"];
+
+/// 允许跨源读取资源的应用自身来源，其他页面只能嵌入不能读取内容
+const APP_ORIGINS: &[&str] = &["tauri://localhost", "http://tauri.localhost", "https://tauri.localhost"];
+
+/// 开发模式下前端由 devUrl 提供
+#[cfg(debug_assertions)]
+const DEV_ORIGIN: &str = "http://localhost:1420";
+
+fn is_app_origin(origin: &str) -> bool {
+    #[cfg(debug_assertions)]
+    if origin == DEV_ORIGIN {
+        return true;
+    }
+    APP_ORIGINS.contains(&origin)
+}
+
+/// 根据扩展名推断 MIME 类型
+pub fn mime_for_path(path: &Path) -> &'static str {
+    let ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_lowercase())
+        .unwrap_or_default();
+    match ext.as_str() {
+        "html" | "htm" => "text/html; charset=utf-8",
+        "css" => "text/css; charset=utf-8",
+        "js" | "mjs" => "text/javascript; charset=utf-8",
+        "json" => "application/json; charset=utf-8",
+        "txt" | "md" => "text/plain; charset=utf-8",
+        "svg" => "image/svg+xml",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "bmp" => "image/bmp",
+        "ico" => "image/x-icon",
+        "woff" => "font/woff",
+        "woff2" => "font/woff2",
+        "ttf" => "font/ttf",
+        "otf" => "font/otf",
+        _ => "application/octet-stream",
+    }
+}
+
+/// 把请求路径解析为白名单目录内的真实文件路径
+pub fn resolve_asset_path(app_data_dir: &Path, request_path: &str) -> Result<PathBuf, StatusCode> {
+    let decoded = percent_decode_str(request_path.trim_start_matches('/'))
+        .decode_utf8()
+        .map_err(|_| StatusCode::BAD_REQUEST)?;
+    let relative = Path::new(decoded.as_ref());
+
+    // 只接受普通路径片段，拒绝 ..、盘符、根目录等
+    let mut components = Vec::new();
+    for component in relative.components() {
+        match component {
+            Component::Normal(part) => components.push(part),
+            _ => return Err(StatusCode::FORBIDDEN),
+        }
+    }
+    let (first, rest) = components.split_first().ok_or(StatusCode::NOT_FOUND)?;
+    let dir_name = first.to_str().ok_or(StatusCode::FORBIDDEN)?;
+    if !ALLOWED_DIRS.contains(&dir_name) || rest.is_empty() {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    // 规范化后再次确认仍在白名单目录内（防止符号链接逃逸）
+    let allowed_root = app_data_dir
+        .join(dir_name)
+        .canonicalize()
+        .map_err(|_| StatusCode::NOT_FOUND)?;
+    let full_path = rest
+        .iter()
+        .fold(allowed_root.clone(), |path, part| path.join(part))
+        .canonicalize()
+        .map_err(|_| StatusCode::NOT_FOUND)?;
+    if !full_path.starts_with(&allowed_root) || !full_path.is_file() {
+        return Err(StatusCode::FORBIDDEN);
+    }
+    Ok(full_path)
+}
+
+fn error_response(status: StatusCode) -> Response<Vec<u8>> {
+    Response::builder()
+        .status(status)
+        .header("Content-Type", "text/plain; charset=utf-8")
+        .body(status.canonical_reason().unwrap_or("Error").as_bytes().to_vec())
+        .unwrap_or_default()
+}
+
+/// 协议处理器，注册到 Builder::register_uri_scheme_protocol
+pub fn handle<R: Runtime>(ctx: UriSchemeContext<'_, R>, request: Request<Vec<u8>>) -> Response<Vec<u8>> {
+    if request.method() != tauri::http::Method::GET {
+        return error_response(StatusCode::METHOD_NOT_ALLOWED);
+    }
+
+    let app_data_dir = match ctx.app_handle().path().app_data_dir() {
+        Ok(dir) => crate::profile::apply(dir),
+        Err(_) => return error_response(StatusCode::INTERNAL_SERVER_ERROR),
+    };
+
+    let path = match resolve_asset_path(&app_data_dir, request.uri().path()) {
+        Ok(path) => path,
+        Err(status) => {
+            eprintln!("[AssetProtocol] Rejected {} ({})", request.uri(), status);
+            return error_response(status);
+        }
+    };
+
+    // 只对应用自身来源返回 CORS 头
+    let app_origin = request
+        .headers()
+        .get("Origin")
+        .and_then(|origin| origin.to_str().ok())
+        .filter(|origin| is_app_origin(origin));
+
+    match fs::read(&path) {
+        Ok(data) => {
+            let mut builder = Response::builder()
+                .status(StatusCode::OK)
+                .header("Content-Type", mime_for_path(&path))
+                .header("Cache-Control", "no-cache")
+                .header("Vary", "Origin");
+            if let Some(origin) = app_origin {
+                builder = builder.header("Access-Control-Allow-Origin", origin);
+            }
+            builder.body(data).unwrap_or_default()
+        }
+        Err(e) => {
+            eprintln!("[AssetProtocol] Failed to read {}: {}", path.display(), e);
+            error_response(StatusCode::NOT_FOUND)
+        }
+    }
+}
@@ -0,0 +1,170 @@
+//! 命令行参数支持
+//!
+//! 支持 `--play <recording-id> [--speed 2]` 与 `--search "xxx"`。
+//! 已有实例运行时，新进程通过本地回环端口把命令转发给已运行进程执行，
+//! 方便任务计划程序等外部工具联动。
+
+use serde::{Deserialize, Serialize};
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+
+/// 命令行可触发的操作
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum CliCommand {
+    /// 回放指定录制
+    Play { recording: String, speed: f32 },
+    /// 打开启动器并填入搜索词
+    Search { query: String },
+}
+
+/// 转发请求（带上端点文件里的令牌，防止其他本地进程伪造请求）
+#[derive(Serialize, Deserialize)]
+struct ForwardRequest {
+    token: String,
+    command: CliCommand,
+}
+
+/// 解析命令行参数（不含程序路径），无可识别命令时返回 None
+pub fn parse<I: IntoIterator<Item = String>>(args: I) -> Result<Option<CliCommand>, String> {
+    let mut play: Option<String> = None;
+    let mut speed: Option<f32> = None;
+    let mut search: Option<String> = None;
+
+    let mut iter = args.into_iter();
+    while let Some(arg) = iter.next() {
+        // 同时支持 `--key value` 与 `--key=value`
+        let (key, inline_value) = match arg.split_once('=') {
+            Some((k, v)) if k.starts_with("--") => (k.to_string(), Some(v.to_string())),
+            _ => (arg.clone(), None),
+        };
+        let mut value = |name: &str| -> Result<String, String> {
+            inline_value
+                .clone()
+                .or_else(|| iter.next())
+                .ok_or_else(|| format!("Missing value for {}", name))
+        };
+        match key.as_str() {
+            "--play" => play = Some(value("--play")?),
+            "--speed" => {
+                let raw = value("--speed")?;
+                speed = Some(
+                    raw.parse::<f32>()
+                        .map_err(|_| format!("Invalid speed: {}", raw))?,
+                );
+            }
+            "--search" => search = Some(value("--search")?),
+            _ => {}
+        }
+    }
+
+    if let Some(recording) = play {
+        return Ok(Some(CliCommand::Play {
+            recording: normalize_recording_path(&recording),
+            speed: speed.unwrap_or(1.0),
+        }));
+    }
+    Ok(search.map(|query| CliCommand::Search { query }))
+}
+
+/// 录制 ID 转换为 play_recording 接受的相对路径
+fn normalize_recording_path(id: &str) -> String {
+    let id = id.trim().replace('\\', "/");
+    let name = id.strip_prefix("recordings/").unwrap_or(&id);
+    if name.to_lowercase().ends_with(".json") {
+        name.to_string()
+    } else {
+        format!("{}.json", name)
+    }
+}
+
+/// 端点文件路径（与单实例锁文件放在同一目录）
+pub fn endpoint_file_path(lock_file_path: &Path) -> PathBuf {
+    lock_file_path.with_file_name("re-fast.cli")
+}
+
+/// 将命令转发给已运行的实例
+pub fn forward(endpoint_file: &Path, command: CliCommand) -> Result<(), String> {
+    let contents = std::fs::read_to_string(endpoint_file)
+        .map_err(|e| format!("Failed to read CLI endpoint file: {}", e))?;
+    let (port, token) = contents
+        .trim()
+        .split_once(' ')
+        .ok_or_else(|| "Invalid CLI endpoint file".to_string())?;
+    let port: u16 = port
+        .parse()
+        .map_err(|e| format!("Invalid CLI endpoint port: {}", e))?;
+
+    let addr = std::net::SocketAddr::from(([127, 0, 0, 1], port));
+    let mut stream = TcpStream::connect_timeout(&addr, Duration::from_secs(2))
+        .map_err(|e| format!("Failed to connect to running instance: {}", e))?;
+    let request = ForwardRequest {
+        token: token.to_string(),
+        command,
+    };
+    let line = serde_json::to_string(&request)
+        .map_err(|e| format!("Failed to serialize CLI command: {}", e))?;
+    stream
+        .write_all(format!("{}\n", line).as_bytes())
+        .map_err(|e| format!("Failed to send CLI command: {}", e))?;
+    Ok(())
+}
+
+/// 启动转发监听（仅主实例调用），端口与令牌写入端点文件
+pub fn start_server(app: AppHandle, endpoint_file: PathBuf) -> Result<(), String> {
+    let listener = TcpListener::bind(("127.0.0.1", 0))
+        .map_err(|e| format!("Failed to bind CLI listener: {}", e))?;
+    let port = listener
+        .local_addr()
+        .map_err(|e| format!("Failed to get CLI listener address: {}", e))?
+        .port();
+    let token = format!("{:032x}", rand::random::<u128>());
+    std::fs::write(&endpoint_file, format!("{} {}", port, token))
+        .map_err(|e| format!("Failed to write CLI endpoint file: {}", e))?;
+
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let Ok(stream) = stream else { continue };
+            let _ = stream.set_read_timeout(Some(Duration::from_secs(2)));
+            let mut line = String::new();
+            if BufReader::new(stream).read_line(&mut line).is_err() {
+                continue;
+            }
+            match serde_json::from_str::<ForwardRequest>(line.trim()) {
+                Ok(request) if request.token == token => execute(&app, request.command),
+                Ok(_) => eprintln!("[CLI] Rejected forwarded command with invalid token"),
+                Err(e) => eprintln!("[CLI] Invalid forwarded command: {}", e),
+            }
+        }
+    });
+    Ok(())
+}
+
+/// 删除端点文件
+pub fn cleanup(endpoint_file: &Path) {
+    let _ = std::fs::remove_file(endpoint_file);
+}
+
+/// 在当前实例中执行命令
+pub fn execute(app: &AppHandle, command: CliCommand) {
+    eprintln!("[CLI] Executing {:?}", command);
+    match command {
+        CliCommand::Play { recording, speed } => {
+            if let Err(e) = crate::commands::play_recording(app.clone(), recording, speed, None, None, None) {
+                eprintln!("[CLI] Failed to play recording: {}", e);
+            }
+        }
+        CliCommand::Search { query } => {
+            if let Some(window) = app.get_webview_window("launcher") {
+                if !window.is_visible().unwrap_or(false) {
+                    let _ = crate::commands::toggle_launcher(app.clone());
+                }
+                let _ = window.set_focus();
+            }
+            let _ = app.emit("cli-search", query);
+        }
+    }
+}
@@ -1,18 +1,388 @@
+use crate::clipboard_crypto as crypto;
 use crate::db;
 use crate::settings;
-use rusqlite::{params, OptionalExtension};
+use rusqlite::{params, Connection, OptionalExtension};
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
 use std::path::PathBuf;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use sha2::{Sha256, Digest};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ClipboardItem {
     pub id: String,
     pub content: String,
-    pub content_type: String, // "text", "image", "file"
+    pub content_type: String, // "text", "image", "file", "color"
     pub created_at: u64,
     pub is_favorite: bool,
+    /// 内容超过大小阈值，仅保存了前 N KB 摘要
+    #[serde(default)]
+    pub truncated: bool,
+    /// 颜色条目对应的 "#rrggbb"，用于在历史中渲染色块
+    #[serde(default)]
+    pub color: Option<String>,
+    /// 同时保存了 HTML / RTF 格式，可按原格式粘贴
+    #[serde(default)]
+    pub has_rich_format: bool,
+    /// 完整内容保存在 clipboard_blobs 目录下，content 只是预览，需通过 get_full_content 读取
+    #[serde(default)]
+    pub external: bool,
+    /// 来源应用的可执行文件名（如 "chrome.exe"）
+    #[serde(default)]
+    pub source_app: Option<String>,
+    /// 复制时来源应用的窗口标题
+    #[serde(default)]
+    pub source_title: Option<String>,
+    /// 标签（按名称排序）
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// 图片条目后台 OCR 识别出的文字（尚未识别或没有文字时为空）
+    #[serde(default)]
+    pub ocr_text: Option<String>,
+    /// 片段缩写（仅收藏条目），输入缩写后自动展开为条目内容
+    #[serde(default)]
+    pub abbreviation: Option<String>,
+    /// 定时清除的到期时间（Unix 秒），到期后自动删除
+    #[serde(default)]
+    pub expires_at: Option<u64>,
+}
+
+/// 剪贴板内容的来源应用，由监控线程在收到更新时获取
+#[derive(Debug, Clone, Default)]
+pub struct ClipboardSource {
+    pub app: Option<String>,
+    pub title: Option<String>,
+}
+
+/// 与纯文本一起保存的富文本格式：CF_HTML 原始数据（含头部）与 RTF
+#[derive(Debug, Clone, Default)]
+pub struct RichFormats {
+    pub html: Option<String>,
+    pub rtf: Option<String>,
+}
+
+impl RichFormats {
+    pub fn is_empty(&self) -> bool {
+        self.html.is_none() && self.rtf.is_none()
+    }
+}
+
+/// 颜色条目的色块值，非颜色条目返回 None
+fn swatch_for(content: &str, content_type: &str) -> Option<String> {
+    if content_type != "color" {
+        return None;
+    }
+    crate::color::parse_color_text(content).map(|(r, g, b)| crate::color::format_color(r, g, b, "hex"))
+}
+
+/// 查询剪切板项时使用的列，顺序与 row_to_item 一致
+const ITEM_COLUMNS: &str = "id, content, content_type, created_at, is_favorite, truncated, (html_content IS NOT NULL OR rtf_content IS NOT NULL), content_file IS NOT NULL, source_app, source_title, \
+    (SELECT group_concat(tag, char(31)) FROM (SELECT tag FROM clipboard_tags WHERE item_id = clipboard_history.id ORDER BY tag)), ocr_text, abbreviation, expires_at";
+
+/// 标签列表在查询结果中的分隔符（group_concat 使用的 char(31)）
+pub(crate) const TAG_SEPARATOR: char = '\u{1f}';
+
+/// 按规范化规则处理文本，规则全部关闭时原样返回
+pub fn normalize_text(text: String, rules: &settings::ClipboardNormalizeSettings) -> String {
+    let mut text = text;
+    if rules.unify_line_endings {
+        text = text.replace("\r\n", "\n").replace('\r', "\n");
+    }
+    if rules.tab_width > 0 {
+        text = text.replace('\t', &" ".repeat(rules.tab_width.min(16) as usize));
+    }
+    if rules.trim_line_ends {
+        // 保留原有换行符，只去掉其前面的空白
+        text = text
+            .split_inclusive('\n')
+            .map(|line| {
+                let body = line.trim_end_matches(['\r', '\n']);
+                let ending = &line[body.len()..];
+                format!("{}{}", body.trim_end(), ending)
+            })
+            .collect();
+    }
+    if rules.trim {
+        let trimmed = text.trim();
+        if trimmed.len() != text.len() {
+            text = trimmed.to_string();
+        }
+    }
+    text
+}
+
+/// 外部存储条目在数据库中保留的预览大小（字节）
+const EXTERNAL_PREVIEW_BYTES: usize = 4096;
+
+/// 外部存储的大文本所在目录（相对于应用数据目录）
+const BLOBS_DIR: &str = "clipboard_blobs";
+
+fn row_to_item(row: &rusqlite::Row) -> rusqlite::Result<ClipboardItem> {
+    let content = crypto::open_or_placeholder(row.get(1)?);
+    let content_type: String = row.get(2)?;
+    Ok(ClipboardItem {
+        id: row.get(0)?,
+        color: swatch_for(&content, &content_type),
+        content,
+        content_type,
+        created_at: row.get::<_, i64>(3)? as u64,
+        is_favorite: row.get::<_, i64>(4)? != 0,
+        truncated: row.get::<_, i64>(5)? != 0,
+        has_rich_format: row.get::<_, i64>(6)? != 0,
+        external: row.get::<_, i64>(7)? != 0,
+        source_app: row.get(8)?,
+        source_title: row.get(9)?,
+        tags: row
+            .get::<_, Option<String>>(10)?
+            .map(|tags| tags.split(TAG_SEPARATOR).map(str::to_string).collect())
+            .unwrap_or_default(),
+        ocr_text: row
+            .get::<_, Option<String>>(11)?
+            .map(crypto::open_or_placeholder)
+            .filter(|text| !text.is_empty()),
+        abbreviation: row.get(12)?,
+        expires_at: row.get::<_, Option<i64>>(13)?.map(|ts| ts.max(0) as u64),
+    })
+}
+
+/// 文本超过阈值（KB，0 表示不启用）时把完整内容写入以 SHA-256 命名的文件，返回预览与文件路径
+pub(crate) fn store_external_content(
+    content: String,
+    content_type: &str,
+    threshold_kb: u32,
+    app_data_dir: &PathBuf,
+) -> Result<(String, Option<String>), String> {
+    if threshold_kb == 0 || content_type != "text" || content.len() <= threshold_kb as usize * 1024 {
+        return Ok((content, None));
+    }
+
+    let blobs_dir = app_data_dir.join(BLOBS_DIR);
+    std::fs::create_dir_all(&blobs_dir)
+        .map_err(|e| format!("Failed to create clipboard blobs directory: {}", e))?;
+    let hash = format!("{:x}", Sha256::digest(content.as_bytes()));
+    let file_path = blobs_dir.join(format!("{}.txt", hash));
+    // 同一内容的文件名相同，已存在时无需重复写入
+    if !file_path.exists() {
+        std::fs::write(&file_path, crypto::seal(content.clone())?)
+            .map_err(|e| format!("Failed to write clipboard blob: {}", e))?;
+    }
+
+    let mut end = EXTERNAL_PREVIEW_BYTES.min(content.len());
+    while !content.is_char_boundary(end) {
+        end -= 1;
+    }
+    Ok((content[..end].to_string(), Some(file_path.to_string_lossy().to_string())))
+}
+
+/// 删除已没有记录引用的外部存储文件
+fn remove_orphan_blobs(app_data_dir: &PathBuf) {
+    let Ok(entries) = std::fs::read_dir(app_data_dir.join(BLOBS_DIR)) else {
+        return;
+    };
+    let referenced: std::collections::HashSet<String> = match db::get_readonly_connection(app_data_dir) {
+        Ok(conn) => {
+            let Ok(mut stmt) = conn.prepare("SELECT content_file FROM clipboard_history WHERE content_file IS NOT NULL") else {
+                return;
+            };
+            let Ok(rows) = stmt.query_map([], |row| row.get::<_, String>(0)) else {
+                return;
+            };
+            rows.filter_map(|r| r.ok()).collect()
+        }
+        Err(e) => {
+            eprintln!("[Clipboard] Failed to check clipboard blobs: {}", e);
+            return;
+        }
+    };
+    for entry in entries.flatten() {
+        let path = entry.path().to_string_lossy().to_string();
+        if !referenced.contains(&path) {
+            if let Err(e) = std::fs::remove_file(entry.path()) {
+                eprintln!("[Clipboard] Failed to delete clipboard blob {}: {}", path, e);
+            }
+        }
+    }
+}
+
+/// 按 ID 获取剪切板项
+pub fn get_clipboard_item(id: &str, app_data_dir: &PathBuf) -> Result<ClipboardItem, String> {
+    let conn = db::get_readonly_connection(app_data_dir)?;
+    conn.query_row(
+        &format!("SELECT {} FROM clipboard_history WHERE id = ?1", ITEM_COLUMNS),
+        params![id],
+        row_to_item,
+    )
+    .optional()
+    .map_err(|e| format!("Failed to load clipboard item: {}", e))?
+    .ok_or_else(|| format!("Clipboard item {} not found", id))
+}
+
+/// 读取条目的完整文本（外部存储的条目从文件加载）
+pub fn get_full_content(id: &str, app_data_dir: &PathBuf) -> Result<String, String> {
+    let conn = db::get_readonly_connection(app_data_dir)?;
+    let (content, content_file): (String, Option<String>) = conn
+        .query_row(
+            "SELECT content, content_file FROM clipboard_history WHERE id = ?1",
+            params![id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .optional()
+        .map_err(|e| format!("Failed to load clipboard item: {}", e))?
+        .ok_or_else(|| format!("Clipboard item {} not found", id))?;
+    load_content(content, content_file)
+}
+
+pub(crate) fn load_content(content: String, content_file: Option<String>) -> Result<String, String> {
+    match content_file {
+        Some(path) => std::fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read clipboard blob {}: {}", path, e))
+            .and_then(crypto::open),
+        None => crypto::open(content),
+    }
+}
+
+/// 条目内容哈希，用于去重：文本类按完整明文计算；
+/// 图片文件以像素哈希命名，按文件名（不含扩展名）计算，重新压缩后保持不变。
+/// 启用加密时使用由数据密钥派生的 HMAC，未解锁时返回错误
+pub(crate) fn content_hash(content: &str, content_type: &str) -> Result<String, String> {
+    let key = crypto::content_hash_key()?;
+    Ok(content_hash_with(key.as_ref(), content, content_type))
+}
+
+/// 按给定的哈希密钥计算内容哈希，key 为 None 时为普通 SHA-256
+pub(crate) fn content_hash_with(key: Option<&[u8; 32]>, content: &str, content_type: &str) -> String {
+    let name = if content_type == "image" {
+        std::path::Path::new(content)
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_else(|| content.to_string())
+    } else {
+        content.to_string()
+    };
+    let mut data = Vec::with_capacity(content_type.len() + 1 + name.len());
+    data.extend_from_slice(content_type.as_bytes());
+    data.push(0);
+    data.extend_from_slice(name.as_bytes());
+    match key {
+        Some(key) => crypto::hmac_sha256(key, &data)
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect(),
+        None => format!("{:x}", Sha256::digest(&data)),
+    }
+}
+
+/// content_hash 回填完成前，去重仍需兼容没有哈希的旧条目
+static CONTENT_HASH_BACKFILLED: AtomicBool = AtomicBool::new(false);
+
+/// 查找内容相同的条目。stored_content / content_file 为入库形式，仅用于匹配尚未回填哈希的旧条目
+pub(crate) fn find_duplicate(
+    conn: &Connection,
+    hash: &str,
+    stored_content: &str,
+    content_type: &str,
+    content_file: Option<&str>,
+) -> Result<Option<String>, String> {
+    let existing: Option<String> = conn
+        .query_row(
+            "SELECT id FROM clipboard_history WHERE content_hash = ?1 LIMIT 1",
+            params![hash],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(|e| format!("Failed to check existing clipboard: {}", e))?;
+    if existing.is_some() || CONTENT_HASH_BACKFILLED.load(Ordering::SeqCst) {
+        return Ok(existing);
+    }
+
+    match content_file {
+        Some(file) => conn.query_row(
+            "SELECT id FROM clipboard_history WHERE content_file = ?1 AND content_hash IS NULL",
+            params![file],
+            |row| row.get(0),
+        ),
+        None => conn.query_row(
+            "SELECT id FROM clipboard_history WHERE content = ?1 AND content_type = ?2 AND content_file IS NULL AND content_hash IS NULL",
+            params![stored_content, content_type],
+            |row| row.get(0),
+        ),
+    }
+    .optional()
+    .map_err(|e| format!("Failed to check existing clipboard: {}", e))
+}
+
+/// 为没有 content_hash 的旧条目计算哈希，返回回填的条目数
+///
+/// 启用加密但尚未解锁时无法计算哈希，直接跳过，解锁后再回填
+pub(crate) fn backfill_content_hashes(app_data_dir: &PathBuf) -> Result<usize, String> {
+    const BATCH_SIZE: i64 = 500;
+    let Ok(hash_key) = crypto::content_hash_key() else {
+        eprintln!("[Clipboard] Clipboard history is locked, skipping content hash backfill");
+        return Ok(0);
+    };
+    let mut total = 0;
+    loop {
+        let conn = db::get_readonly_connection(app_data_dir)?;
+        let mut stmt = conn
+            .prepare("SELECT id, content, content_type, content_file FROM clipboard_history WHERE content_hash IS NULL LIMIT ?1")
+            .map_err(|e| format!("Failed to prepare content hash query: {}", e))?;
+        let rows = stmt
+            .query_map(params![BATCH_SIZE], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, Option<String>>(3)?,
+                ))
+            })
+            .map_err(|e| format!("Failed to query clipboard items: {}", e))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Failed to read clipboard items: {}", e))?;
+        drop(stmt);
+        drop(conn);
+        if rows.is_empty() {
+            break;
+        }
+
+        // 外部文件丢失时按入库内容计算，保证每条都能完成回填
+        let hashes: Vec<(String, String)> = rows
+            .into_iter()
+            .map(|(id, content, content_type, content_file)| {
+                let plain = if content_type == "image" {
+                    content
+                } else {
+                    load_content(content.clone(), content_file).unwrap_or(content)
+                };
+                (id, content_hash_with(hash_key.as_ref(), &plain, &content_type))
+            })
+            .collect();
+        total += hashes.len();
+        db::execute_write(app_data_dir, move |conn| {
+            for (id, hash) in &hashes {
+                conn.execute(
+                    "UPDATE clipboard_history SET content_hash = ?1 WHERE id = ?2",
+                    params![hash, id],
+                )
+                .map_err(|e| format!("Failed to update content hash: {}", e))?;
+            }
+            Ok(())
+        })?;
+    }
+    CONTENT_HASH_BACKFILLED.store(true, Ordering::SeqCst);
+    Ok(total)
+}
+
+/// 同一毫秒内新增多条时用递增序号区分 id
+static ITEM_ID_SEQ: AtomicU64 = AtomicU64::new(0);
+
+fn new_item_id() -> String {
+    let millis = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis();
+    format!("clipboard-{}-{}", millis, ITEM_ID_SEQ.fetch_add(1, Ordering::Relaxed))
 }
 
 fn now_ts() -> u64 {
@@ -23,23 +393,16 @@ fn now_ts() -> u64 {
 }
 
 /// 获取所有剪切板历史
+/// 一次性读取全部历史，条目很多时会阻塞界面，请改用 list_clipboard_items 分页读取
 pub fn get_all_clipboard_items(app_data_dir: &PathBuf) -> Result<Vec<ClipboardItem>, String> {
     let conn = db::get_readonly_connection(app_data_dir)?;
 
     let mut stmt = conn
-        .prepare("SELECT id, content, content_type, created_at, is_favorite FROM clipboard_history ORDER BY created_at DESC")
+        .prepare(&format!("SELECT {} FROM clipboard_history ORDER BY created_at DESC", ITEM_COLUMNS))
         .map_err(|e| format!("Failed to prepare clipboard query: {}", e))?;
 
     let rows = stmt
-        .query_map([], |row| {
-            Ok(ClipboardItem {
-                id: row.get(0)?,
-                content: row.get(1)?,
-                content_type: row.get(2)?,
-                created_at: row.get::<_, i64>(3)? as u64,
-                is_favorite: row.get::<_, i64>(4)? != 0,
-            })
-        })
+        .query_map([], row_to_item)
         .map_err(|e| format!("Failed to iterate clipboard items: {}", e))?;
 
     let mut items = Vec::new();
@@ -49,14 +412,179 @@ pub fn get_all_clipboard_items(app_data_dir: &PathBuf) -> Result<Vec<ClipboardIt
     Ok(items)
 }
 
+/// 分页查询的单页条目上限
+const MAX_PAGE_SIZE: usize = 500;
+
+/// 分页查询的过滤条件，字段均为空时返回全部条目
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ClipboardListFilter {
+    /// 搜索文本（与 search_clipboard_items 相同的匹配规则）
+    #[serde(default)]
+    pub query: Option<String>,
+    /// 条目类型："text" / "image" / "file" / "color"
+    #[serde(default)]
+    pub content_type: Option<String>,
+    #[serde(default)]
+    pub favorites_only: bool,
+    /// 来源应用名或窗口标题（模糊匹配）
+    #[serde(default)]
+    pub source: Option<String>,
+    #[serde(default)]
+    pub tag: Option<String>,
+}
+
+/// 一页剪切板条目，total 为满足过滤条件的条目总数
+#[derive(Debug, Clone, Serialize)]
+pub struct ClipboardPage {
+    pub items: Vec<ClipboardItem>,
+    pub total: usize,
+    pub offset: usize,
+    pub limit: usize,
+}
+
+/// 分页读取剪切板历史（按时间降序），limit 最大为 500
+pub fn list_clipboard_items(
+    offset: usize,
+    limit: usize,
+    filter: ClipboardListFilter,
+    app_data_dir: &PathBuf,
+) -> Result<ClipboardPage, String> {
+    let limit = limit.clamp(1, MAX_PAGE_SIZE);
+    let non_empty = |value: Option<String>| {
+        value
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty())
+    };
+    let content_type = non_empty(filter.content_type);
+    let source = non_empty(filter.source);
+    let tag = non_empty(filter.tag);
+
+    // 有搜索词时复用搜索逻辑（FTS / 加密时的逐条匹配），再在内存中分页
+    if let Some(query) = non_empty(filter.query) {
+        let items: Vec<ClipboardItem> =
+            search_clipboard_items(&query, source.as_deref(), tag.as_deref(), app_data_dir)?
+                .into_iter()
+                .filter(|item| content_type.as_deref().map_or(true, |t| item.content_type == t))
+                .filter(|item| !filter.favorites_only || item.is_favorite)
+                .collect();
+        let total = items.len();
+        return Ok(ClipboardPage {
+            items: items.into_iter().skip(offset).take(limit).collect(),
+            total,
+            offset,
+            limit,
+        });
+    }
+
+    let conn = db::get_readonly_connection(app_data_dir)?;
+    let source = source.map(|s| format!("%{}%", s.to_lowercase()));
+    let condition = "(?1 IS NULL OR content_type = ?1)
+         AND (?2 = 0 OR is_favorite = 1)
+         AND (?3 IS NULL OR lower(source_app) LIKE ?3 OR lower(source_title) LIKE ?3)
+         AND (?4 IS NULL OR id IN (SELECT item_id FROM clipboard_tags WHERE tag = ?4))";
+    let total: i64 = conn
+        .query_row(
+            &format!("SELECT COUNT(*) FROM clipboard_history WHERE {}", condition),
+            params![content_type, filter.favorites_only as i64, source, tag],
+            |row| row.get(0),
+        )
+        .map_err(|e| format!("Failed to count clipboard items: {}", e))?;
+
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} FROM clipboard_history WHERE {} ORDER BY created_at DESC LIMIT ?5 OFFSET ?6",
+            ITEM_COLUMNS, condition
+        ))
+        .map_err(|e| format!("Failed to prepare clipboard query: {}", e))?;
+    let items = stmt
+        .query_map(
+            params![
+                content_type,
+                filter.favorites_only as i64,
+                source,
+                tag,
+                limit as i64,
+                offset as i64
+            ],
+            row_to_item,
+        )
+        .map_err(|e| format!("Failed to iterate clipboard items: {}", e))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to read clipboard row: {}", e))?;
+
+    Ok(ClipboardPage {
+        items,
+        total: total as usize,
+        offset,
+        limit,
+    })
+}
+
+/// 获取最新的 limit 条剪切板历史
+pub fn get_recent_clipboard_items(limit: usize, app_data_dir: &PathBuf) -> Result<Vec<ClipboardItem>, String> {
+    let conn = db::get_readonly_connection(app_data_dir)?;
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} FROM clipboard_history ORDER BY created_at DESC LIMIT ?1",
+            ITEM_COLUMNS
+        ))
+        .map_err(|e| format!("Failed to prepare clipboard query: {}", e))?;
+    let items = stmt
+        .query_map(params![limit as i64], row_to_item)
+        .map_err(|e| format!("Failed to iterate clipboard items: {}", e))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to read clipboard row: {}", e));
+    items
+}
+
 /// 添加剪切板项
 pub fn add_clipboard_item(
     content: String,
     content_type: String,
     app_data_dir: &PathBuf,
+) -> Result<ClipboardItem, String> {
+    add_clipboard_item_ex(content, content_type, false, app_data_dir)
+}
+
+/// 添加剪切板项，可标记内容是否被截断
+pub fn add_clipboard_item_ex(
+    content: String,
+    content_type: String,
+    truncated: bool,
+    app_data_dir: &PathBuf,
+) -> Result<ClipboardItem, String> {
+    add_clipboard_item_rich(
+        content,
+        content_type,
+        truncated,
+        RichFormats::default(),
+        ClipboardSource::default(),
+        app_data_dir,
+    )
+}
+
+/// 添加剪切板项并保存 HTML / RTF 格式；内容已存在时以新的富文本格式覆盖
+pub fn add_clipboard_item_rich(
+    content: String,
+    content_type: String,
+    truncated: bool,
+    rich: RichFormats,
+    source: ClipboardSource,
+    app_data_dir: &PathBuf,
 ) -> Result<ClipboardItem, String> {
     let now = now_ts();
-    let id = format!("clipboard-{}", now);
+    let id = new_item_id();
+
+    // 在写线程外读取设置并写入外部文件，避免写任务内做耗时 IO
+    let settings = settings::load_settings(app_data_dir).unwrap_or_default();
+    let max_items = settings.clipboard_max_items;
+    let hash = content_hash(&content, &content_type)?;
+    let (content, content_file) = store_external_content(
+        content,
+        &content_type,
+        settings.clipboard_external_threshold_kb,
+        app_data_dir,
+    )?;
 
     let item = ClipboardItem {
         id: id.clone(),
@@ -64,64 +592,89 @@ pub fn add_clipboard_item(
         content_type: content_type.clone(),
         created_at: now,
         is_favorite: false,
+        truncated,
+        color: swatch_for(&content, &content_type),
+        has_rich_format: !rich.is_empty(),
+        external: content_file.is_some(),
+        source_app: source.app,
+        source_title: source.title,
+        tags: Vec::new(),
+        ocr_text: None,
+        abbreviation: None,
+        expires_at: None,
     };
 
-    let conn = db::get_connection(app_data_dir)?;
-    
-    // 检查是否已存在相同内容（避免重复）
-    let existing: Option<String> = conn
-        .query_row(
-            "SELECT id FROM clipboard_history WHERE content = ?1 AND content_type = ?2",
-            params![content, content_type],
-            |row| row.get(0),
-        )
-        .optional()
-        .map_err(|e| format!("Failed to check existing clipboard: {}", e))?;
-    
-    if let Some(existing_id) = existing {
-        // 如果已存在，更新时间戳
+    // 启用加密时数据库中只保存密文，返回的条目仍是明文
+    let content = crypto::seal_content(content, &content_type)?;
+    let rich = RichFormats {
+        html: crypto::seal_opt(rich.html)?,
+        rtf: crypto::seal_opt(rich.rtf)?,
+    };
+
+    let (item, orphan_images) = db::execute_write(app_data_dir, move |conn| {
+        // 按内容哈希检查是否已存在相同内容（避免重复）
+        let existing = find_duplicate(conn, &hash, &content, &content_type, content_file.as_deref())?;
+
+        if let Some(existing_id) = existing {
+            // 如果已存在，更新时间戳（匹配到的旧条目同时补上哈希）
+            conn.execute(
+                "UPDATE clipboard_history SET created_at = ?1, content_hash = ?2 WHERE id = ?3",
+                params![now as i64, hash, existing_id],
+            )
+            .map_err(|e| format!("Failed to update clipboard timestamp: {}", e))?;
+
+            if !rich.is_empty() {
+                conn.execute(
+                    "UPDATE clipboard_history SET html_content = ?1, rtf_content = ?2 WHERE id = ?3",
+                    params![rich.html, rich.rtf, existing_id],
+                )
+                .map_err(|e| format!("Failed to update clipboard rich formats: {}", e))?;
+            }
+
+            // 记录最近一次复制的来源
+            if item.source_app.is_some() {
+                conn.execute(
+                    "UPDATE clipboard_history SET source_app = ?1, source_title = ?2 WHERE id = ?3",
+                    params![item.source_app, item.source_title, existing_id],
+                )
+                .map_err(|e| format!("Failed to update clipboard source: {}", e))?;
+            }
+
+            return Ok((
+                ClipboardItem {
+                    id: existing_id,
+                    ..item
+                },
+                Vec::new(),
+            ));
+        }
+
         conn.execute(
-            "UPDATE clipboard_history SET created_at = ?1 WHERE id = ?2",
-            params![now as i64, existing_id],
+            "INSERT INTO clipboard_history (id, content, content_type, created_at, is_favorite, truncated, html_content, rtf_content, content_file, source_app, source_title, content_hash)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+            params![item.id, content, item.content_type, item.created_at as i64, 0, item.truncated as i64, rich.html, rich.rtf, content_file, item.source_app, item.source_title, hash],
         )
-        .map_err(|e| format!("Failed to update clipboard timestamp: {}", e))?;
-        
-        return Ok(ClipboardItem {
-            id: existing_id,
-            content,
-            content_type,
-            created_at: now,
-            is_favorite: false,
-        });
-    }
-
-    conn.execute(
-        "INSERT INTO clipboard_history (id, content, content_type, created_at, is_favorite)
-         VALUES (?1, ?2, ?3, ?4, ?5)",
-        params![item.id, item.content, item.content_type, item.created_at as i64, 0],
-    )
-    .map_err(|e| format!("Failed to insert clipboard item: {}", e))?;
+        .map_err(|e| format!("Failed to insert clipboard item: {}", e))?;
 
-    // 检查并限制最大数量
-    enforce_max_items(app_data_dir)?;
+        // 检查并限制最大数量
+        let orphan_images = enforce_max_items(conn, max_items)?;
+        Ok((item, orphan_images))
+    })?;
 
+    remove_image_files(&orphan_images);
+    if item.content_type == "image" {
+        crate::clipboard_ocr::request_ocr();
+    }
     Ok(item)
 }
 
-/// 限制剪切板历史的最大数量，删除超出部分的记录
-fn enforce_max_items(app_data_dir: &PathBuf) -> Result<(), String> {
-    // 获取设置中的最大数量
-    let settings = settings::load_settings(app_data_dir)
-        .unwrap_or_default();
-    let max_items = settings.clipboard_max_items;
-    
+/// 限制剪切板历史的最大数量，删除超出部分的记录，返回不再被引用的图片路径
+fn enforce_max_items(conn: &Connection, max_items: u32) -> Result<Vec<String>, String> {
     if max_items == 0 {
         // 0 表示不限制
-        return Ok(());
+        return Ok(Vec::new());
     }
 
-    let conn = db::get_connection(app_data_dir)?;
-    
     // 统计非收藏项的数量
     let non_favorite_count: i64 = conn
         .query_row(
@@ -133,7 +686,7 @@ fn enforce_max_items(app_data_dir: &PathBuf) -> Result<(), String> {
     
     if non_favorite_count <= max_items as i64 {
         // 未超过最大数量，不需要删除
-        return Ok(());
+        return Ok(Vec::new());
     }
     
     // 计算需要删除的数量
@@ -160,300 +713,1711 @@ fn enforce_max_items(app_data_dir: &PathBuf) -> Result<(), String> {
     
     drop(stmt);
     
-    // 删除对应的图片文件
-    for (id, content, content_type) in &items_to_delete {
-        if content_type == "image" {
-            let image_path = std::path::Path::new(content);
-            if image_path.exists() {
-                // 检查是否还有其他记录引用这个图片
-                let ref_count: i64 = conn
-                    .query_row(
-                        "SELECT COUNT(*) FROM clipboard_history WHERE content = ?1 AND content_type = 'image'",
-                        params![content],
-                        |row| row.get(0),
-                    )
-                    .unwrap_or(0);
-                
-                // 只有当没有其他记录引用时才删除文件
-                if ref_count <= 1 {
-                    if let Err(e) = std::fs::remove_file(image_path) {
-                        eprintln!("[Clipboard] Failed to delete image file {}: {}", content, e);
+    println!("[Clipboard] Deleted {} old clipboard items (max_items: {})", to_delete, max_items);
+    delete_items(conn, &items_to_delete)
+}
+
+/// 过滤出数据库中已没有记录引用的图片路径
+fn unreferenced_images(conn: &Connection, image_paths: Vec<String>) -> Vec<String> {
+    image_paths
+        .into_iter()
+        .filter(|content| {
+            let ref_count: i64 = conn
+                .query_row(
+                    "SELECT COUNT(*) FROM clipboard_history WHERE content = ?1 AND content_type = 'image'",
+                    params![content],
+                    |row| row.get(0),
+                )
+                .unwrap_or(0);
+            if ref_count > 0 {
+                println!("[Clipboard] Skipping {} (still referenced by {} item(s))", content, ref_count);
+            }
+            ref_count == 0
+        })
+        .collect()
+}
+
+/// 删除图片文件（每个文件最多重试 3 次），返回成功删除的数量
+fn remove_image_files(image_paths: &[String]) -> usize {
+    let mut deleted_count = 0;
+    for image_path in image_paths {
+        let path = std::path::Path::new(image_path);
+        println!("[Clipboard] Attempting to delete: {}", image_path);
+        
+        if !path.exists() {
+            eprintln!("[Clipboard] Image file not found: {}", image_path);
+            continue;
+        }
+
+        let max_retries = 3;
+        for retry in 1..=max_retries {
+            match std::fs::remove_file(path) {
+                Ok(_) => {
+                    deleted_count += 1;
+                    println!("[Clipboard] Successfully deleted: {}", image_path);
+                    break;
+                }
+                Err(e) => {
+                    eprintln!("[Clipboard] Failed to delete image file {} (attempt {}): {}", image_path, retry, e);
+                    if retry < max_retries {
+                        std::thread::sleep(std::time::Duration::from_millis(100));
                     } else {
-                        println!("[Clipboard] Deleted image file: {}", content);
+                        eprintln!("[Clipboard] Gave up deleting {} after {} attempts", image_path, max_retries);
                     }
                 }
             }
         }
     }
-    
-    // 删除数据库记录（逐个删除更安全）
-    for (id, _, _) in &items_to_delete {
-        conn.execute(
-            "DELETE FROM clipboard_history WHERE id = ?1",
-            params![id],
-        )
-        .map_err(|e| format!("Failed to delete clipboard item {}: {}", id, e))?;
-    }
-    
-    println!("[Clipboard] Deleted {} old clipboard items (max_items: {})", to_delete, max_items);
-    
-    Ok(())
+    deleted_count
 }
 
-/// 更新剪切板项内容
-pub fn update_clipboard_item(
-    id: String,
-    content: String,
-    app_data_dir: &PathBuf,
-) -> Result<ClipboardItem, String> {
-    let conn = db::get_connection(app_data_dir)?;
-
-    let existing: Option<ClipboardItem> = conn
-        .query_row(
-            "SELECT id, content, content_type, created_at, is_favorite FROM clipboard_history WHERE id = ?1",
-            params![id],
-            |row| {
-                Ok(ClipboardItem {
-                    id: row.get(0)?,
-                    content: row.get(1)?,
-                    content_type: row.get(2)?,
-                    created_at: row.get::<_, i64>(3)? as u64,
-                    is_favorite: row.get::<_, i64>(4)? != 0,
-                })
-            },
-        )
-        .optional()
-        .map_err(|e| format!("Failed to load clipboard item: {}", e))?;
+/// 后台保留策略清理的执行间隔
+const RETENTION_INTERVAL: Duration = Duration::from_secs(60 * 60);
 
-    let mut item = existing.ok_or_else(|| format!("Clipboard item {} not found", id))?;
-    item.content = content;
+/// 已删除条目的记录保留时长，超过后不再阻止同步带回相同内容
+const DELETION_RETENTION_SECS: u64 = 90 * 24 * 60 * 60;
 
+/// 删除前按内容哈希记下删除时间，同步合并时据此跳过本机已删除的条目
+fn record_deletion(conn: &Connection, id: &str) -> Result<(), String> {
     conn.execute(
-        "UPDATE clipboard_history SET content = ?1 WHERE id = ?2",
-        params![item.content, item.id],
+        "INSERT OR REPLACE INTO clipboard_deleted (content_hash, deleted_at)
+         SELECT content_hash, ?2 FROM clipboard_history WHERE id = ?1 AND content_hash IS NOT NULL",
+        params![id, now_ts() as i64],
     )
-    .map_err(|e| format!("Failed to update clipboard item: {}", e))?;
-
-    Ok(item)
+    .map_err(|e| format!("Failed to record deleted clipboard item: {}", e))?;
+    Ok(())
 }
 
-/// 切换收藏状态
-pub fn toggle_favorite_clipboard_item(
-    id: String,
-    app_data_dir: &PathBuf,
-) -> Result<ClipboardItem, String> {
-    let conn = db::get_connection(app_data_dir)?;
+/// 内容在创建时间之后被本机删除过（删除后重新复制的内容不算）
+pub(crate) fn deleted_since(conn: &Connection, hash: &str, created_at: u64) -> Result<bool, String> {
+    conn.query_row(
+        "SELECT 1 FROM clipboard_deleted WHERE content_hash = ?1 AND deleted_at >= ?2",
+        params![hash, created_at as i64],
+        |_| Ok(()),
+    )
+    .optional()
+    .map(|row| row.is_some())
+    .map_err(|e| format!("Failed to query deleted clipboard items: {}", e))
+}
 
-    let existing: Option<ClipboardItem> = conn
-        .query_row(
-            "SELECT id, content, content_type, created_at, is_favorite FROM clipboard_history WHERE id = ?1",
-            params![id],
-            |row| {
-                Ok(ClipboardItem {
-                    id: row.get(0)?,
-                    content: row.get(1)?,
-                    content_type: row.get(2)?,
-                    created_at: row.get::<_, i64>(3)? as u64,
-                    is_favorite: row.get::<_, i64>(4)? != 0,
-                })
-            },
-        )
-        .optional()
-        .map_err(|e| format!("Failed to load clipboard item: {}", e))?;
+/// 清理过期的删除记录
+fn prune_deletions(conn: &Connection) -> Result<(), String> {
+    let cutoff = now_ts().saturating_sub(DELETION_RETENTION_SECS) as i64;
+    conn.execute("DELETE FROM clipboard_deleted WHERE deleted_at < ?1", params![cutoff])
+        .map_err(|e| format!("Failed to prune deleted clipboard items: {}", e))?;
+    Ok(())
+}
 
-    let mut item = existing.ok_or_else(|| format!("Clipboard item {} not found", id))?;
-    item.is_favorite = !item.is_favorite;
+/// 删除一组条目，返回不再被引用的图片路径
+fn delete_items(conn: &Connection, items: &[(String, String, String)]) -> Result<Vec<String>, String> {
+    for (id, _, _) in items {
+        record_deletion(conn, id)?;
+        conn.execute("DELETE FROM clipboard_history WHERE id = ?1", params![id])
+            .map_err(|e| format!("Failed to delete clipboard item {}: {}", id, e))?;
+    }
+    let image_paths: Vec<String> = items
+        .iter()
+        .filter(|(_, _, content_type)| content_type == "image")
+        .map(|(_, content, _)| content.clone())
+        .collect();
+    Ok(unreferenced_images(conn, image_paths))
+}
 
-    conn.execute(
-        "UPDATE clipboard_history SET is_favorite = ?1 WHERE id = ?2",
-        params![if item.is_favorite { 1 } else { 0 }, item.id],
-    )
-    .map_err(|e| format!("Failed to toggle favorite: {}", e))?;
+/// 删除早于 max_age_days 天的非收藏条目（0 表示不限制）
+fn enforce_max_age(conn: &Connection, max_age_days: u32) -> Result<Vec<String>, String> {
+    if max_age_days == 0 {
+        return Ok(Vec::new());
+    }
+    let cutoff = now_ts().saturating_sub(max_age_days as u64 * 24 * 60 * 60) as i64;
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, content, content_type FROM clipboard_history
+             WHERE is_favorite = 0 AND created_at < ?1",
+        )
+        .map_err(|e| format!("Failed to prepare expired items query: {}", e))?;
+    let expired: Vec<(String, String, String)> = stmt
+        .query_map(params![cutoff], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))
+        .map_err(|e| format!("Failed to query expired items: {}", e))?
+        .filter_map(|r| r.ok())
+        .collect();
+    drop(stmt);
 
-    Ok(item)
+    if !expired.is_empty() {
+        println!("[Clipboard] Deleted {} items older than {} days", expired.len(), max_age_days);
+    }
+    delete_items(conn, &expired)
 }
 
-/// 删除剪切板项
-pub fn delete_clipboard_item(id: String, app_data_dir: &PathBuf) -> Result<(), String> {
-    let conn = db::get_connection(app_data_dir)?;
-    
-    // 先查询该项的内容和类型，如果是图片则需要删除文件
-    let item: Option<(String, String)> = conn
-        .query_row(
-            "SELECT content, content_type FROM clipboard_history WHERE id = ?1",
-            params![id],
-            |row| Ok((row.get(0)?, row.get(1)?)),
+/// 总大小（文本、富文本格式与图片文件）超过 max_total_mb 时从最旧的非收藏条目开始删除（0 表示不限制）
+fn enforce_max_total_size(conn: &Connection, max_total_mb: u32) -> Result<Vec<String>, String> {
+    if max_total_mb == 0 {
+        return Ok(Vec::new());
+    }
+    let limit = max_total_mb as u64 * 1024 * 1024;
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, content, content_type, is_favorite, content_file,
+                    LENGTH(CAST(content AS BLOB)) + IFNULL(LENGTH(CAST(html_content AS BLOB)), 0) + IFNULL(LENGTH(CAST(rtf_content AS BLOB)), 0)
+             FROM clipboard_history
+             ORDER BY created_at ASC",
+        )
+        .map_err(|e| format!("Failed to prepare size query: {}", e))?;
+    let rows: Vec<(String, String, String, bool, Option<String>, u64)> = stmt
+        .query_map([], |row| {
+            Ok((
+                row.get(0)?,
+                row.get(1)?,
+                row.get(2)?,
+                row.get::<_, i64>(3)? != 0,
+                row.get(4)?,
+                row.get::<_, i64>(5)?.max(0) as u64,
+            ))
+        })
+        .map_err(|e| format!("Failed to query item sizes: {}", e))?
+        .filter_map(|r| r.ok())
+        .collect();
+    drop(stmt);
+
+    // 图片条目的 content 是文件路径，外部存储的文本另加文件大小，均按文件实际大小计算
+    let file_size = |path: &str| std::fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+    let sized: Vec<((String, String, String), bool, u64)> = rows
+        .into_iter()
+        .map(|(id, content, content_type, is_favorite, content_file, text_size)| {
+            let size = if content_type == "image" {
+                file_size(&content)
+            } else {
+                text_size + content_file.as_deref().map_or(0, file_size)
+            };
+            ((id, content, content_type), is_favorite, size)
+        })
+        .collect();
+    let mut total: u64 = sized.iter().map(|(_, _, size)| size).sum();
+    if total <= limit {
+        return Ok(Vec::new());
+    }
+
+    let mut to_delete = Vec::new();
+    for (item, is_favorite, size) in sized {
+        if total <= limit {
+            break;
+        }
+        if !is_favorite {
+            total = total.saturating_sub(size);
+            to_delete.push(item);
+        }
+    }
+    println!(
+        "[Clipboard] Deleted {} items to keep history under {} MB",
+        to_delete.len(),
+        max_total_mb
+    );
+    delete_items(conn, &to_delete)
+}
+
+/// 按设置中的保留策略（最大数量、最长保留天数、最大总大小）清理历史，收藏项始终保留
+pub fn enforce_retention(app_data_dir: &PathBuf) -> Result<(), String> {
+    let settings = settings::load_settings(app_data_dir).unwrap_or_default();
+    let orphan_images = db::execute_write(app_data_dir, move |conn| {
+        let mut orphans = enforce_max_age(conn, settings.clipboard_max_age_days)?;
+        orphans.extend(enforce_max_items(conn, settings.clipboard_max_items)?);
+        orphans.extend(enforce_max_total_size(conn, settings.clipboard_max_total_mb)?);
+        prune_deletions(conn)?;
+        Ok(orphans)
+    })?;
+    remove_image_files(&orphan_images);
+    remove_orphan_blobs(app_data_dir);
+    Ok(())
+}
+
+/// 启动后台保留策略清理线程，启动时执行一次，之后每小时执行一次
+pub fn start_retention_task(app_data_dir: PathBuf) {
+    std::thread::spawn(move || {
+        match backfill_content_hashes(&app_data_dir) {
+            Ok(0) => {}
+            Ok(count) => eprintln!("[Clipboard] Backfilled content hash for {} item(s)", count),
+            Err(e) => eprintln!("[Clipboard] Content hash backfill failed: {}", e),
+        }
+        loop {
+            if let Err(e) = enforce_retention(&app_data_dir) {
+                eprintln!("[Clipboard] Retention cleanup failed: {}", e);
+            }
+            std::thread::sleep(RETENTION_INTERVAL);
+        }
+    });
+}
+
+/// 读取条目的纯文本与保存的 HTML / RTF 格式
+pub fn get_item_formats(id: &str, app_data_dir: &PathBuf) -> Result<(String, RichFormats), String> {
+    let conn = db::get_readonly_connection(app_data_dir)?;
+    let (content, content_file, rich) = conn
+        .query_row(
+            "SELECT content, content_file, html_content, rtf_content FROM clipboard_history WHERE id = ?1",
+            params![id],
+            |row| {
+                Ok((
+                    row.get(0)?,
+                    row.get(1)?,
+                    RichFormats {
+                        html: row.get(2)?,
+                        rtf: row.get(3)?,
+                    },
+                ))
+            },
         )
         .optional()
-        .map_err(|e| format!("Failed to query clipboard item: {}", e))?;
-    
-    // 先删除数据库记录
-    let affected = conn
-        .execute("DELETE FROM clipboard_history WHERE id = ?1", params![id])
-        .map_err(|e| format!("Failed to delete clipboard item: {}", e))?;
-    if affected == 0 {
-        return Err("Clipboard item not found".to_string());
+        .map_err(|e| format!("Failed to load clipboard item: {}", e))?
+        .ok_or_else(|| format!("Clipboard item {} not found", id))?;
+    let rich = RichFormats {
+        html: crypto::open_opt(rich.html)?,
+        rtf: crypto::open_opt(rich.rtf)?,
+    };
+    Ok((load_content(content, content_file)?, rich))
+}
+
+/// 更新剪切板项内容
+pub fn update_clipboard_item(
+    id: String,
+    content: String,
+    app_data_dir: &PathBuf,
+) -> Result<ClipboardItem, String> {
+    db::execute_write(app_data_dir, move |conn| {
+        let existing: Option<ClipboardItem> = conn
+            .query_row(
+                &format!("SELECT {} FROM clipboard_history WHERE id = ?1", ITEM_COLUMNS),
+                params![id],
+                row_to_item,
+            )
+            .optional()
+            .map_err(|e| format!("Failed to load clipboard item: {}", e))?;
+
+        let mut item = existing.ok_or_else(|| format!("Clipboard item {} not found", id))?;
+        let stored = crypto::seal_content(content.clone(), &item.content_type)?;
+        let hash = content_hash(&content, &item.content_type)?;
+        item.content = content;
+        // 编辑后的内容直接保存在表中，原外部文件由清理任务回收
+        item.external = false;
+
+        conn.execute(
+            "UPDATE clipboard_history SET content = ?1, content_file = NULL, content_hash = ?2 WHERE id = ?3",
+            params![stored, hash, item.id],
+        )
+        .map_err(|e| format!("Failed to update clipboard item: {}", e))?;
+
+        Ok(item)
+    })
+}
+
+/// 切换收藏状态
+pub fn toggle_favorite_clipboard_item(
+    id: String,
+    app_data_dir: &PathBuf,
+) -> Result<ClipboardItem, String> {
+    db::execute_write(app_data_dir, move |conn| {
+        let existing: Option<ClipboardItem> = conn
+            .query_row(
+                &format!("SELECT {} FROM clipboard_history WHERE id = ?1", ITEM_COLUMNS),
+                params![id],
+                row_to_item,
+            )
+            .optional()
+            .map_err(|e| format!("Failed to load clipboard item: {}", e))?;
+
+        let mut item = existing.ok_or_else(|| format!("Clipboard item {} not found", id))?;
+        item.is_favorite = !item.is_favorite;
+        // 取消收藏时一并移除片段缩写
+        if !item.is_favorite {
+            item.abbreviation = None;
+        }
+
+        conn.execute(
+            "UPDATE clipboard_history SET is_favorite = ?1, abbreviation = ?2 WHERE id = ?3",
+            params![if item.is_favorite { 1 } else { 0 }, item.abbreviation, item.id],
+        )
+        .map_err(|e| format!("Failed to toggle favorite: {}", e))?;
+
+        Ok(item)
+    })
+}
+
+/// 片段缩写最大长度（字符数）
+const MAX_ABBREVIATION_CHARS: usize = 32;
+
+/// 设置收藏条目的片段缩写（None 或空字符串表示移除），缩写不能包含空白且不能与其他条目重复
+pub fn set_clipboard_abbreviation(
+    id: String,
+    abbreviation: Option<String>,
+    app_data_dir: &PathBuf,
+) -> Result<ClipboardItem, String> {
+    let abbreviation = abbreviation
+        .map(|a| a.trim().to_string())
+        .filter(|a| !a.is_empty());
+    if let Some(abbreviation) = &abbreviation {
+        let len = abbreviation.chars().count();
+        if !(2..=MAX_ABBREVIATION_CHARS).contains(&len) {
+            return Err(format!("缩写长度需在 2 到 {} 个字符之间", MAX_ABBREVIATION_CHARS));
+        }
+        if abbreviation.chars().any(char::is_whitespace) {
+            return Err("缩写不能包含空白字符".to_string());
+        }
     }
-    
-    if let Some((content, content_type)) = item {
-        // 如果是图片类型，检查是否还有其他记录引用这个文件
-        if content_type == "image" {
-            let ref_count: i64 = conn
+
+    db::execute_write(app_data_dir, move |conn| {
+        let mut item = conn
+            .query_row(
+                &format!("SELECT {} FROM clipboard_history WHERE id = ?1", ITEM_COLUMNS),
+                params![id],
+                row_to_item,
+            )
+            .optional()
+            .map_err(|e| format!("Failed to load clipboard item: {}", e))?
+            .ok_or_else(|| format!("Clipboard item {} not found", id))?;
+        if abbreviation.is_some() {
+            if !item.is_favorite {
+                return Err("只有收藏的条目可以设置缩写".to_string());
+            }
+            if item.content_type == "image" {
+                return Err("图片条目不支持片段展开".to_string());
+            }
+            let taken: Option<String> = conn
                 .query_row(
-                    "SELECT COUNT(*) FROM clipboard_history WHERE content = ?1 AND content_type = 'image'",
-                    params![content],
+                    "SELECT id FROM clipboard_history WHERE abbreviation = ?1 AND id != ?2",
+                    params![abbreviation, id],
                     |row| row.get(0),
                 )
-                .unwrap_or(0);
-            
-            println!("[Clipboard] Image {} has {} remaining references", content, ref_count);
-            
-            // 只有当没有其他记录引用时才删除文件
-            if ref_count == 0 {
-                let image_path = std::path::Path::new(&content);
-                println!("[Clipboard] Deleting image: {}", content);
-                
-                if image_path.exists() {
-                    // 重试删除最多3次
-                    let mut retry = 0;
-                    let max_retries = 3;
-                    
-                    while retry < max_retries {
-                        match std::fs::remove_file(image_path) {
-                            Ok(_) => {
-                                println!("[Clipboard] Successfully deleted image file: {}", content);
-                                break;
-                            }
-                            Err(e) => {
-                                retry += 1;
-                                eprintln!("[Clipboard] Failed to delete image file {} (attempt {}): {}", content, retry, e);
-                                if retry < max_retries {
-                                    std::thread::sleep(std::time::Duration::from_millis(100));
-                                }
-                            }
-                        }
-                    }
-                } else {
-                    eprintln!("[Clipboard] Image file not found: {}", content);
-                }
+                .optional()
+                .map_err(|e| format!("Failed to check abbreviation: {}", e))?;
+            if taken.is_some() {
+                return Err("缩写已被其他条目使用".to_string());
             }
         }
+
+        conn.execute(
+            "UPDATE clipboard_history SET abbreviation = ?1 WHERE id = ?2",
+            params![abbreviation, id],
+        )
+        .map_err(|e| format!("Failed to set abbreviation: {}", e))?;
+        item.abbreviation = abbreviation;
+        Ok(item)
+    })
+}
+
+/// 所有片段缩写及对应的条目 ID
+pub fn list_abbreviations(app_data_dir: &PathBuf) -> Result<Vec<(String, String)>, String> {
+    let conn = db::get_readonly_connection(app_data_dir)?;
+    let mut stmt = conn
+        .prepare(
+            "SELECT abbreviation, id FROM clipboard_history
+             WHERE abbreviation IS NOT NULL AND is_favorite = 1",
+        )
+        .map_err(|e| format!("Failed to prepare abbreviation query: {}", e))?;
+    let rows = stmt
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+        .map_err(|e| format!("Failed to query abbreviations: {}", e))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to read abbreviations: {}", e));
+    rows
+}
+
+/// 标签名最大长度（字符数）
+const MAX_TAG_CHARS: usize = 64;
+
+/// 标签及使用该标签的条目数
+#[derive(Debug, Clone, Serialize)]
+pub struct TagCount {
+    pub tag: String,
+    pub count: u64,
+}
+
+pub(crate) fn normalize_tag(tag: &str) -> Result<String, String> {
+    let tag = tag.trim();
+    if tag.is_empty() {
+        return Err("Tag cannot be empty".to_string());
     }
-    
-    Ok(())
+    if tag.chars().count() > MAX_TAG_CHARS || tag.contains(TAG_SEPARATOR) {
+        return Err(format!("Invalid tag: {}", tag));
+    }
+    Ok(tag.to_string())
 }
 
-/// 清空剪切板历史
-pub fn clear_clipboard_history(app_data_dir: &PathBuf) -> Result<(), String> {
-    let conn = db::get_connection(app_data_dir)?;
-    
-    // 先查询所有要删除的图片项（去重）
+/// 给条目添加标签（已存在时忽略）
+pub fn add_tag(id: String, tag: &str, app_data_dir: &PathBuf) -> Result<(), String> {
+    let tag = normalize_tag(tag)?;
+    db::execute_write(app_data_dir, move |conn| {
+        let exists: bool = conn
+            .query_row(
+                "SELECT COUNT(*) FROM clipboard_history WHERE id = ?1",
+                params![id],
+                |row| row.get::<_, i64>(0),
+            )
+            .map(|count| count > 0)
+            .map_err(|e| format!("Failed to query clipboard item: {}", e))?;
+        if !exists {
+            return Err(format!("Clipboard item {} not found", id));
+        }
+        conn.execute(
+            "INSERT OR IGNORE INTO clipboard_tags (item_id, tag) VALUES (?1, ?2)",
+            params![id, tag],
+        )
+        .map_err(|e| format!("Failed to add clipboard tag: {}", e))?;
+        Ok(())
+    })
+}
+
+/// 移除条目的标签
+pub fn remove_tag(id: String, tag: &str, app_data_dir: &PathBuf) -> Result<(), String> {
+    let tag = tag.trim().to_string();
+    db::execute_write(app_data_dir, move |conn| {
+        conn.execute(
+            "DELETE FROM clipboard_tags WHERE item_id = ?1 AND tag = ?2",
+            params![id, tag],
+        )
+        .map_err(|e| format!("Failed to remove clipboard tag: {}", e))?;
+        Ok(())
+    })
+}
+
+/// 列出所有标签，按使用次数降序
+pub fn list_tags(app_data_dir: &PathBuf) -> Result<Vec<TagCount>, String> {
+    let conn = db::get_readonly_connection(app_data_dir)?;
     let mut stmt = conn
-        .prepare("SELECT DISTINCT content FROM clipboard_history WHERE is_favorite = 0 AND content_type = 'image'")
-        .map_err(|e| format!("Failed to prepare query: {}", e))?;
-    
-    let image_paths: Vec<String> = stmt
-        .query_map([], |row| row.get(0))
-        .map_err(|e| format!("Failed to query image paths: {}", e))?
-        .filter_map(|r| r.ok())
-        .collect();
-    
-    // 释放语句，避免持有数据库连接
-    drop(stmt);
-    
-    println!("[Clipboard] Found {} unique image files to check", image_paths.len());
-    
-    // 先删除数据库记录
-    conn.execute("DELETE FROM clipboard_history WHERE is_favorite = 0", [])
-        .map_err(|e| format!("Failed to clear clipboard history: {}", e))?;
-    
-    // 然后检查并删除图片文件
-    let mut deleted_count = 0;
-    for image_path in image_paths {
-        // 检查是否还有收藏记录引用这个图片
-        let ref_count: i64 = conn
+        .prepare(
+            "SELECT tag, COUNT(*) FROM clipboard_tags
+             GROUP BY tag
+             ORDER BY COUNT(*) DESC, tag ASC",
+        )
+        .map_err(|e| format!("Failed to prepare tag query: {}", e))?;
+    let tags = stmt
+        .query_map([], |row| {
+            Ok(TagCount {
+                tag: row.get(0)?,
+                count: row.get::<_, i64>(1)? as u64,
+            })
+        })
+        .map_err(|e| format!("Failed to query clipboard tags: {}", e))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to read clipboard tags: {}", e))?;
+    Ok(tags)
+}
+
+/// 看板名称最大长度（字符数）
+const MAX_BOARD_NAME_CHARS: usize = 64;
+
+/// 剪贴板看板：用于分组整理常用片段，看板内的条目按 position 排序
+#[derive(Debug, Clone, Serialize)]
+pub struct ClipboardBoard {
+    pub id: String,
+    pub name: String,
+    pub sort_order: i64,
+    pub created_at: u64,
+    pub item_count: u64,
+}
+
+fn normalize_board_name(name: &str) -> Result<String, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Board name cannot be empty".to_string());
+    }
+    if name.chars().count() > MAX_BOARD_NAME_CHARS {
+        return Err(format!("Board name is too long: {}", name));
+    }
+    Ok(name.to_string())
+}
+
+fn board_exists(conn: &Connection, board_id: &str) -> Result<bool, String> {
+    conn.query_row(
+        "SELECT COUNT(*) FROM clipboard_boards WHERE id = ?1",
+        params![board_id],
+        |row| row.get::<_, i64>(0),
+    )
+    .map(|count| count > 0)
+    .map_err(|e| format!("Failed to query clipboard board: {}", e))
+}
+
+fn map_board_name_error(e: rusqlite::Error, name: &str) -> String {
+    match e {
+        rusqlite::Error::SqliteFailure(err, _) if err.code == rusqlite::ErrorCode::ConstraintViolation => {
+            format!("Board {} already exists", name)
+        }
+        e => format!("Failed to save clipboard board: {}", e),
+    }
+}
+
+/// 列出所有看板及其条目数
+pub fn list_boards(app_data_dir: &PathBuf) -> Result<Vec<ClipboardBoard>, String> {
+    let conn = db::get_readonly_connection(app_data_dir)?;
+    let mut stmt = conn
+        .prepare(
+            "SELECT b.id, b.name, b.sort_order, b.created_at,
+                    (SELECT COUNT(*) FROM clipboard_board_items i WHERE i.board_id = b.id)
+             FROM clipboard_boards b
+             ORDER BY b.sort_order ASC, b.created_at ASC",
+        )
+        .map_err(|e| format!("Failed to prepare board query: {}", e))?;
+    let boards = stmt
+        .query_map([], |row| {
+            Ok(ClipboardBoard {
+                id: row.get(0)?,
+                name: row.get(1)?,
+                sort_order: row.get(2)?,
+                created_at: row.get::<_, i64>(3)? as u64,
+                item_count: row.get::<_, i64>(4)? as u64,
+            })
+        })
+        .map_err(|e| format!("Failed to query clipboard boards: {}", e))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to read clipboard boards: {}", e))?;
+    Ok(boards)
+}
+
+/// 新建看板，排在已有看板之后
+pub fn create_board(name: &str, app_data_dir: &PathBuf) -> Result<ClipboardBoard, String> {
+    let name = normalize_board_name(name)?;
+    db::execute_write(app_data_dir, move |conn| {
+        let created_at = now_ts();
+        let millis = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis();
+        let id = format!("board-{}", millis);
+        let sort_order: i64 = conn
             .query_row(
-                "SELECT COUNT(*) FROM clipboard_history WHERE content = ?1 AND content_type = 'image'",
-                params![image_path],
+                "SELECT COALESCE(MAX(sort_order) + 1, 0) FROM clipboard_boards",
+                [],
                 |row| row.get(0),
             )
-            .unwrap_or(0);
-        
-        if ref_count > 0 {
-            println!("[Clipboard] Skipping {} (still referenced by {} favorite(s))", image_path, ref_count);
-            continue;
+            .map_err(|e| format!("Failed to query board order: {}", e))?;
+        conn.execute(
+            "INSERT INTO clipboard_boards (id, name, sort_order, created_at) VALUES (?1, ?2, ?3, ?4)",
+            params![id, name, sort_order, created_at as i64],
+        )
+        .map_err(|e| map_board_name_error(e, &name))?;
+        Ok(ClipboardBoard {
+            id,
+            name,
+            sort_order,
+            created_at,
+            item_count: 0,
+        })
+    })
+}
+
+/// 重命名看板
+pub fn rename_board(board_id: String, name: &str, app_data_dir: &PathBuf) -> Result<(), String> {
+    let name = normalize_board_name(name)?;
+    db::execute_write(app_data_dir, move |conn| {
+        let affected = conn
+            .execute(
+                "UPDATE clipboard_boards SET name = ?1 WHERE id = ?2",
+                params![name, board_id],
+            )
+            .map_err(|e| map_board_name_error(e, &name))?;
+        if affected == 0 {
+            return Err(format!("Clipboard board {} not found", board_id));
         }
-        
-        let path = std::path::Path::new(&image_path);
-        println!("[Clipboard] Attempting to delete: {}", image_path);
-        
-        if path.exists() {
-            // 重试删除最多3次
-            let mut retry = 0;
-            let max_retries = 3;
-            let mut success = false;
-            
-            while retry < max_retries {
-                match std::fs::remove_file(path) {
-                    Ok(_) => {
-                        deleted_count += 1;
-                        println!("[Clipboard] Successfully deleted: {}", image_path);
-                        success = true;
-                        break;
-                    }
-                    Err(e) => {
-                        retry += 1;
-                        eprintln!("[Clipboard] Failed to delete image file {} (attempt {}): {}", image_path, retry, e);
-                        if retry < max_retries {
-                            std::thread::sleep(std::time::Duration::from_millis(100));
-                        }
-                    }
-                }
+        Ok(())
+    })
+}
+
+/// 删除看板，看板内的条目保留在历史中（仍为收藏状态）
+pub fn delete_board(board_id: String, app_data_dir: &PathBuf) -> Result<(), String> {
+    db::execute_write(app_data_dir, move |conn| {
+        let affected = conn
+            .execute("DELETE FROM clipboard_boards WHERE id = ?1", params![board_id])
+            .map_err(|e| format!("Failed to delete clipboard board: {}", e))?;
+        if affected == 0 {
+            return Err(format!("Clipboard board {} not found", board_id));
+        }
+        Ok(())
+    })
+}
+
+/// 按给定顺序重排看板
+pub fn reorder_boards(board_ids: Vec<String>, app_data_dir: &PathBuf) -> Result<(), String> {
+    db::execute_write(app_data_dir, move |conn| {
+        for (index, id) in board_ids.iter().enumerate() {
+            conn.execute(
+                "UPDATE clipboard_boards SET sort_order = ?1 WHERE id = ?2",
+                params![index as i64, id],
+            )
+            .map_err(|e| format!("Failed to reorder clipboard boards: {}", e))?;
+        }
+        Ok(())
+    })
+}
+
+/// 把条目移动到看板的指定位置（None 表示末尾）；board_id 为 None 时从所在看板移出。
+/// 加入看板的条目会被标记为收藏，避免被历史清理删除
+pub fn move_item_to_board(
+    item_id: String,
+    board_id: Option<String>,
+    position: Option<u32>,
+    app_data_dir: &PathBuf,
+) -> Result<(), String> {
+    db::execute_write(app_data_dir, move |conn| {
+        conn.execute("DELETE FROM clipboard_board_items WHERE item_id = ?1", params![item_id])
+            .map_err(|e| format!("Failed to remove item from board: {}", e))?;
+
+        if let Some(board_id) = board_id {
+            if !board_exists(conn, &board_id)? {
+                return Err(format!("Clipboard board {} not found", board_id));
             }
-            
-            if !success {
-                eprintln!("[Clipboard] Gave up deleting {} after {} attempts", image_path, max_retries);
+            let marked = conn
+                .execute(
+                    "UPDATE clipboard_history SET is_favorite = 1 WHERE id = ?1",
+                    params![item_id],
+                )
+                .map_err(|e| format!("Failed to pin clipboard item: {}", e))?;
+            if marked == 0 {
+                return Err(format!("Clipboard item {} not found", item_id));
             }
-        } else {
-            eprintln!("[Clipboard] Image file not found: {}", image_path);
+
+            let mut ids = board_item_ids(conn, &board_id)?;
+            let index = position.map(|p| p as usize).unwrap_or(ids.len()).min(ids.len());
+            ids.insert(index, item_id.clone());
+            write_board_positions(conn, &board_id, &ids)?;
         }
+
+        Ok(())
+    })
+}
+
+/// 按给定顺序重排看板内的条目，未列出的条目保持原有相对顺序排在后面
+pub fn reorder_board_items(
+    board_id: String,
+    item_ids: Vec<String>,
+    app_data_dir: &PathBuf,
+) -> Result<(), String> {
+    db::execute_write(app_data_dir, move |conn| {
+        let current = board_item_ids(conn, &board_id)?;
+        let mut ordered: Vec<String> = Vec::new();
+        for id in item_ids {
+            if current.contains(&id) && !ordered.contains(&id) {
+                ordered.push(id);
+            }
+        }
+        for id in current {
+            if !ordered.contains(&id) {
+                ordered.push(id);
+            }
+        }
+        write_board_positions(conn, &board_id, &ordered)?;
+        Ok(())
+    })
+}
+
+fn board_item_ids(conn: &Connection, board_id: &str) -> Result<Vec<String>, String> {
+    let mut stmt = conn
+        .prepare("SELECT item_id FROM clipboard_board_items WHERE board_id = ?1 ORDER BY position ASC")
+        .map_err(|e| format!("Failed to prepare board items query: {}", e))?;
+    let ids = stmt
+        .query_map(params![board_id], |row| row.get(0))
+        .map_err(|e| format!("Failed to query board items: {}", e))?
+        .collect::<Result<Vec<String>, _>>()
+        .map_err(|e| format!("Failed to read board items: {}", e))?;
+    Ok(ids)
+}
+
+fn write_board_positions(conn: &Connection, board_id: &str, item_ids: &[String]) -> Result<(), String> {
+    for (index, id) in item_ids.iter().enumerate() {
+        conn.execute(
+            "INSERT OR REPLACE INTO clipboard_board_items (item_id, board_id, position) VALUES (?1, ?2, ?3)",
+            params![id, board_id, index as i64],
+        )
+        .map_err(|e| format!("Failed to save board item order: {}", e))?;
     }
+    Ok(())
+}
+
+/// 获取看板内的条目（按看板内顺序）
+pub fn get_board_items(board_id: &str, app_data_dir: &PathBuf) -> Result<Vec<ClipboardItem>, String> {
+    let conn = db::get_readonly_connection(app_data_dir)?;
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} FROM clipboard_history
+             JOIN clipboard_board_items b ON b.item_id = clipboard_history.id
+             WHERE b.board_id = ?1
+             ORDER BY b.position ASC",
+            ITEM_COLUMNS
+        ))
+        .map_err(|e| format!("Failed to prepare board items query: {}", e))?;
+    let items = stmt
+        .query_map(params![board_id], row_to_item)
+        .map_err(|e| format!("Failed to query board items: {}", e))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to read board items: {}", e))?;
+    Ok(items)
+}
+
+/// 删除剪切板项
+pub fn delete_clipboard_item(id: String, app_data_dir: &PathBuf) -> Result<(), String> {
+    let orphan_images = db::execute_write(app_data_dir, move |conn| {
+        // 先查询该项的内容和类型，如果是图片则需要删除文件
+        let item: Option<(String, String)> = conn
+            .query_row(
+                "SELECT content, content_type FROM clipboard_history WHERE id = ?1",
+                params![id],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()
+            .map_err(|e| format!("Failed to query clipboard item: {}", e))?;
+        
+        // 先删除数据库记录
+        record_deletion(conn, &id)?;
+        let affected = conn
+            .execute("DELETE FROM clipboard_history WHERE id = ?1", params![id])
+            .map_err(|e| format!("Failed to delete clipboard item: {}", e))?;
+        if affected == 0 {
+            return Err("Clipboard item not found".to_string());
+        }
+
+        // 如果是图片类型，检查是否还有其他记录引用这个文件
+        let image_paths = match item {
+            Some((content, content_type)) if content_type == "image" => vec![content],
+            _ => Vec::new(),
+        };
+        Ok(unreferenced_images(conn, image_paths))
+    })?;
+
+    remove_image_files(&orphan_images);
+    remove_orphan_blobs(app_data_dir);
+    Ok(())
+}
+
+/// 批量删除条目（含收藏），在单个事务内完成，返回实际删除的条目数
+pub fn batch_delete_items(ids: Vec<String>, app_data_dir: &PathBuf) -> Result<usize, String> {
+    let (deleted, orphan_images) = db::execute_write(app_data_dir, move |conn| {
+        let mut items = Vec::with_capacity(ids.len());
+        for id in ids {
+            let item: Option<(String, String)> = conn
+                .query_row(
+                    "SELECT content, content_type FROM clipboard_history WHERE id = ?1",
+                    params![id],
+                    |row| Ok((row.get(0)?, row.get(1)?)),
+                )
+                .optional()
+                .map_err(|e| format!("Failed to query clipboard item: {}", e))?;
+            if let Some((content, content_type)) = item {
+                items.push((id, content, content_type));
+            }
+        }
+        let orphan_images = delete_items(conn, &items)?;
+        Ok((items.len(), orphan_images))
+    })?;
+
+    remove_image_files(&orphan_images);
+    remove_orphan_blobs(app_data_dir);
+    Ok(deleted)
+}
+
+/// 批量设置收藏状态，取消收藏时一并移除片段缩写，返回实际更新的条目数
+pub fn batch_set_favorite(ids: Vec<String>, favorite: bool, app_data_dir: &PathBuf) -> Result<usize, String> {
+    db::execute_write(app_data_dir, move |conn| {
+        let mut updated = 0;
+        for id in &ids {
+            let sql = if favorite {
+                "UPDATE clipboard_history SET is_favorite = 1 WHERE id = ?1"
+            } else {
+                "UPDATE clipboard_history SET is_favorite = 0, abbreviation = NULL WHERE id = ?1"
+            };
+            updated += conn
+                .execute(sql, params![id])
+                .map_err(|e| format!("Failed to update favorite: {}", e))?;
+        }
+        Ok(updated)
+    })
+}
+
+/// 批量给条目添加标签（已有该标签的条目忽略），返回新添加标签的条目数
+pub fn batch_add_tag(ids: Vec<String>, tag: &str, app_data_dir: &PathBuf) -> Result<usize, String> {
+    let tag = normalize_tag(tag)?;
+    db::execute_write(app_data_dir, move |conn| {
+        let mut added = 0;
+        for id in &ids {
+            // 只给存在的条目添加，避免留下悬空标签
+            added += conn
+                .execute(
+                    "INSERT OR IGNORE INTO clipboard_tags (item_id, tag)
+                     SELECT id, ?2 FROM clipboard_history WHERE id = ?1",
+                    params![id, tag],
+                )
+                .map_err(|e| format!("Failed to add clipboard tag: {}", e))?;
+        }
+        Ok(added)
+    })
+}
+
+/// 清空历史但保留最近 keep 条非收藏条目（收藏项始终保留），在单个事务内完成，返回删除的条目数
+pub fn clear_except_recent(keep: usize, app_data_dir: &PathBuf) -> Result<usize, String> {
+    let (deleted, orphan_images) = db::execute_write(app_data_dir, move |conn| {
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, content, content_type FROM clipboard_history
+                 WHERE is_favorite = 0
+                 ORDER BY created_at DESC
+                 LIMIT -1 OFFSET ?1",
+            )
+            .map_err(|e| format!("Failed to prepare clear query: {}", e))?;
+        let items: Vec<(String, String, String)> = stmt
+            .query_map(params![keep as i64], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))
+            .map_err(|e| format!("Failed to query clipboard items: {}", e))?
+            .filter_map(|r| r.ok())
+            .collect();
+        drop(stmt);
+        let orphan_images = delete_items(conn, &items)?;
+        Ok((items.len(), orphan_images))
+    })?;
+
+    remove_image_files(&orphan_images);
+    remove_orphan_blobs(app_data_dir);
+    Ok(deleted)
+}
+
+/// 清空剪切板历史
+pub fn clear_clipboard_history(app_data_dir: &PathBuf) -> Result<(), String> {
+    let orphan_images = db::execute_write(app_data_dir, |conn| {
+        // 先查询所有要删除的图片项（去重）
+        let mut stmt = conn
+            .prepare("SELECT DISTINCT content FROM clipboard_history WHERE is_favorite = 0 AND content_type = 'image'")
+            .map_err(|e| format!("Failed to prepare query: {}", e))?;
+        
+        let image_paths: Vec<String> = stmt
+            .query_map([], |row| row.get(0))
+            .map_err(|e| format!("Failed to query image paths: {}", e))?
+            .filter_map(|r| r.ok())
+            .collect();
+        
+        drop(stmt);
+        
+        println!("[Clipboard] Found {} unique image files to check", image_paths.len());
+        
+        // 先删除数据库记录，再检查是否还有收藏记录引用这些图片
+        conn.execute(
+            "INSERT OR REPLACE INTO clipboard_deleted (content_hash, deleted_at)
+             SELECT content_hash, ?1 FROM clipboard_history WHERE is_favorite = 0 AND content_hash IS NOT NULL",
+            params![now_ts() as i64],
+        )
+        .map_err(|e| format!("Failed to record deleted clipboard items: {}", e))?;
+        conn.execute("DELETE FROM clipboard_history WHERE is_favorite = 0", [])
+            .map_err(|e| format!("Failed to clear clipboard history: {}", e))?;
+        
+        Ok(unreferenced_images(conn, image_paths))
+    })?;
     
+    let deleted_count = remove_image_files(&orphan_images);
     println!("[Clipboard] Successfully deleted {} image files", deleted_count);
+    remove_orphan_blobs(app_data_dir);
     Ok(())
 }
 
-/// 搜索剪切板历史
-pub fn search_clipboard_items(query: &str, app_data_dir: &PathBuf) -> Result<Vec<ClipboardItem>, String> {
+/// trigram 索引能匹配的最短查询长度（字符数）
+const FTS_MIN_QUERY_CHARS: usize = 3;
+
+/// 搜索剪切板历史，source 不为空时只返回来源应用名或窗口标题包含该文本的条目，
+/// tag 不为空时只返回带有该标签的条目
+///
+/// 优先走 FTS5 trigram 索引；查询不足 3 个字符或索引不可用时回退到 LIKE。
+/// 启用加密后正文在库中是密文，改为解密后在内存中匹配
+pub fn search_clipboard_items(
+    query: &str,
+    source: Option<&str>,
+    tag: Option<&str>,
+    app_data_dir: &PathBuf,
+) -> Result<Vec<ClipboardItem>, String> {
     let conn = db::get_readonly_connection(app_data_dir)?;
-
+    let source = source
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(|s| format!("%{}%", s.to_lowercase()));
+    let source = source.as_deref();
     let like = format!("%{}%", query.to_lowercase());
+
+    if crypto::is_enabled() {
+        let filter = ItemFilter {
+            source,
+            tag: tag.map(str::trim).filter(|t| !t.is_empty()),
+            ocr: None,
+        };
+        let query = query.to_lowercase();
+        let matches = |text: &str| text.to_lowercase().contains(&query);
+        let items = query_items(&conn, "?1 IS NOT NULL", "", &filter)?;
+        return Ok(items
+            .into_iter()
+            .filter(|item| matches(&item.content) || item.ocr_text.as_deref().is_some_and(matches))
+            .collect());
+    }
+
+    let filter = ItemFilter {
+        source,
+        tag: tag.map(str::trim).filter(|t| !t.is_empty()),
+        ocr: Some(&like),
+    };
+
+    if query.chars().count() >= FTS_MIN_QUERY_CHARS {
+        // 整体作为短语匹配，双引号需转义
+        let phrase = format!("\"{}\"", query.replace('"', "\"\""));
+        match query_items(
+            &conn,
+            "rowid IN (SELECT rowid FROM clipboard_fts WHERE clipboard_fts MATCH ?1)",
+            &phrase,
+            &filter,
+        ) {
+            Ok(items) => return Ok(items),
+            Err(e) => eprintln!("[Clipboard] FTS search failed, falling back to LIKE: {}", e),
+        }
+    }
+
+    query_items(&conn, "lower(content) LIKE ?1", &like, &filter)
+}
+
+/// 搜索时附加的过滤条件
+struct ItemFilter<'a> {
+    /// 来源应用名或窗口标题的 LIKE 模式
+    source: Option<&'a str>,
+    tag: Option<&'a str>,
+    /// 图片 OCR 文字的 LIKE 模式，与正文条件为“或”关系
+    ocr: Option<&'a str>,
+}
+
+/// 按条件查询条目，并应用来源与标签过滤；filter.ocr 不为空时 OCR 文字匹配的图片条目也会返回
+fn query_items(
+    conn: &Connection,
+    condition: &str,
+    param: &str,
+    filter: &ItemFilter,
+) -> Result<Vec<ClipboardItem>, String> {
     let mut stmt = conn
         .prepare(
-            "SELECT id, content, content_type, created_at, is_favorite
-             FROM clipboard_history
-             WHERE lower(content) LIKE ?1
-             ORDER BY is_favorite DESC, created_at DESC",
+            &format!(
+                "SELECT {} FROM clipboard_history
+                 WHERE ({} OR (?4 IS NOT NULL AND lower(ocr_text) LIKE ?4))
+                   AND (?2 IS NULL OR lower(source_app) LIKE ?2 OR lower(source_title) LIKE ?2)
+                   AND (?3 IS NULL OR id IN (SELECT item_id FROM clipboard_tags WHERE tag = ?3))
+                 ORDER BY is_favorite DESC, created_at DESC",
+                ITEM_COLUMNS, condition
+            ),
         )
         .map_err(|e| format!("Failed to prepare clipboard search: {}", e))?;
 
-    let rows = stmt
-        .query_map(params![like], |row| {
-            Ok(ClipboardItem {
-                id: row.get(0)?,
-                content: row.get(1)?,
-                content_type: row.get(2)?,
-                created_at: row.get::<_, i64>(3)? as u64,
-                is_favorite: row.get::<_, i64>(4)? != 0,
-            })
-        })
-        .map_err(|e| format!("Failed to iterate clipboard search: {}", e))?;
+    let rows = stmt
+        .query_map(params![param, filter.source, filter.tag, filter.ocr], row_to_item)
+        .map_err(|e| format!("Failed to iterate clipboard search: {}", e))?;
+
+    let mut items = Vec::new();
+    for row in rows {
+        items.push(row.map_err(|e| format!("Failed to read clipboard row: {}", e))?);
+    }
+    Ok(items)
+}
+
+/// 按正则表达式搜索剪切板历史（跳过图片条目），在 Rust 端逐行过滤
+pub fn search_clipboard_items_regex(
+    pattern: &str,
+    case_insensitive: bool,
+    app_data_dir: &PathBuf,
+) -> Result<Vec<ClipboardItem>, String> {
+    let re = regex::RegexBuilder::new(pattern)
+        .case_insensitive(case_insensitive)
+        .size_limit(1 << 20)
+        .build()
+        .map_err(|e| format!("Invalid regex: {}", e))?;
+
+    let conn = db::get_readonly_connection(app_data_dir)?;
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} FROM clipboard_history
+             WHERE content_type != 'image'
+             ORDER BY is_favorite DESC, created_at DESC",
+            ITEM_COLUMNS
+        ))
+        .map_err(|e| format!("Failed to prepare clipboard regex search: {}", e))?;
+
+    let rows = stmt
+        .query_map([], row_to_item)
+        .map_err(|e| format!("Failed to iterate clipboard regex search: {}", e))?;
+
+    let mut items = Vec::new();
+    for row in rows {
+        let item = row.map_err(|e| format!("Failed to read clipboard row: {}", e))?;
+        if re.is_match(&item.content) {
+            items.push(item);
+        }
+    }
+    Ok(items)
+}
+
+/// 合并导出格式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MergeFormat {
+    #[default]
+    Markdown,
+    Text,
+}
+
+/// 合并导出选项
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MergeOptions {
+    #[serde(default)]
+    pub format: MergeFormat,
+    /// 条目间分隔符，未指定时 Markdown 使用水平线、纯文本使用空行
+    #[serde(default)]
+    pub separator: Option<String>,
+    /// 是否为每个条目附带时间戳与来源
+    #[serde(default)]
+    pub include_meta: bool,
+}
+
+/// 按给定 ID 顺序读取剪切板条目，同时返回来源应用
+fn get_items_by_ids(
+    conn: &Connection,
+    ids: &[String],
+) -> Result<Vec<(ClipboardItem, Option<String>)>, String> {
+    let mut stmt = conn
+        .prepare(&format!("SELECT {} FROM clipboard_history WHERE id = ?1", ITEM_COLUMNS))
+        .map_err(|e| format!("Failed to prepare clipboard query: {}", e))?;
+
+    let mut items = Vec::new();
+    for id in ids {
+        let item = stmt
+            .query_row(params![id], row_to_item)
+            .optional()
+            .map_err(|e| format!("Failed to read clipboard item: {}", e))?;
+        if let Some(item) = item {
+            let source = item.source_app.clone();
+            items.push((item, source));
+        }
+    }
+    Ok(items)
+}
+
+fn format_meta(item: &ClipboardItem, source: Option<&str>) -> String {
+    use chrono::{Local, TimeZone};
+    let time = Local
+        .timestamp_opt(item.created_at as i64, 0)
+        .single()
+        .map(|t| t.format("%Y-%m-%d %H:%M:%S").to_string())
+        .unwrap_or_default();
+    match source {
+        Some(source) if !source.is_empty() => format!("{} · {}", time, source),
+        _ => time,
+    }
+}
+
+/// 将多条剪切板历史合并为 Markdown 或纯文本
+pub fn merge_clipboard_items(
+    ids: &[String],
+    options: &MergeOptions,
+    app_data_dir: &PathBuf,
+) -> Result<String, String> {
+    let conn = db::get_readonly_connection(app_data_dir)?;
+    let items = get_items_by_ids(&conn, ids)?;
+    if items.is_empty() {
+        return Err("No clipboard items to merge".to_string());
+    }
+
+    let separator = options.separator.clone().unwrap_or_else(|| match options.format {
+        MergeFormat::Markdown => "\n\n---\n\n".to_string(),
+        MergeFormat::Text => "\n\n".to_string(),
+    });
+
+    let blocks: Vec<String> = items
+        .iter()
+        .map(|(item, source)| {
+            let body = match (options.format, item.content_type.as_str()) {
+                (MergeFormat::Markdown, "image") => {
+                    format!("![]({})", item.content.replace('\\', "/").replace(' ', "%20"))
+                }
+                (MergeFormat::Markdown, "file") => item
+                    .content
+                    .lines()
+                    .filter(|line| !line.trim().is_empty())
+                    .map(|line| format!("- `{}`", line.trim()))
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+                _ if item.external => get_full_content(&item.id, app_data_dir)
+                    .unwrap_or_else(|_| item.content.clone()),
+                _ => item.content.clone(),
+            };
+            if !options.include_meta {
+                return body;
+            }
+            let meta = format_meta(item, source.as_deref());
+            match options.format {
+                MergeFormat::Markdown => format!("### {}\n\n{}", meta, body),
+                MergeFormat::Text => format!("[{}]\n{}", meta, body),
+            }
+        })
+        .collect();
+
+    Ok(blocks.join(&separator))
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DailyCount {
+    pub date: String, // YYYY-MM-DD (本地时间)
+    pub count: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TypeShare {
+    pub content_type: String,
+    pub count: u64,
+    pub ratio: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SourceAppCount {
+    pub source_app: String,
+    pub count: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ClipboardStats {
+    pub total: u64,
+    pub daily: Vec<DailyCount>,
+    pub types: Vec<TypeShare>,
+    pub top_sources: Vec<SourceAppCount>,
+    /// 文本类条目的平均字符数
+    pub average_text_length: f64,
+}
+
+/// 剪切板历史统计：按天新增、类型占比、来源应用 Top、平均长度
+pub fn get_clipboard_stats(days: u32, app_data_dir: &PathBuf) -> Result<ClipboardStats, String> {
+    let conn = db::get_readonly_connection(app_data_dir)?;
+    let since = now_ts().saturating_sub(days as u64 * 24 * 60 * 60) as i64;
+
+    let total: i64 = conn
+        .query_row("SELECT COUNT(*) FROM clipboard_history", [], |row| row.get(0))
+        .map_err(|e| format!("Failed to count clipboard items: {}", e))?;
+
+    let mut stmt = conn
+        .prepare(
+            "SELECT date(created_at, 'unixepoch', 'localtime') AS day, COUNT(*)
+             FROM clipboard_history
+             WHERE created_at >= ?1
+             GROUP BY day
+             ORDER BY day ASC",
+        )
+        .map_err(|e| format!("Failed to prepare daily stats query: {}", e))?;
+    let daily = stmt
+        .query_map(params![since], |row| {
+            Ok(DailyCount {
+                date: row.get(0)?,
+                count: row.get::<_, i64>(1)? as u64,
+            })
+        })
+        .map_err(|e| format!("Failed to query daily stats: {}", e))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to read daily stats: {}", e))?;
+
+    let mut stmt = conn
+        .prepare(
+            "SELECT content_type, COUNT(*) FROM clipboard_history
+             GROUP BY content_type
+             ORDER BY COUNT(*) DESC",
+        )
+        .map_err(|e| format!("Failed to prepare type stats query: {}", e))?;
+    let types = stmt
+        .query_map([], |row| {
+            let count = row.get::<_, i64>(1)? as u64;
+            Ok(TypeShare {
+                content_type: row.get(0)?,
+                count,
+                ratio: if total > 0 { count as f64 / total as f64 } else { 0.0 },
+            })
+        })
+        .map_err(|e| format!("Failed to query type stats: {}", e))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to read type stats: {}", e))?;
+
+    // 来源统计只包含监控记录了 source_app 的条目
+    let top_sources = match conn.prepare(
+        "SELECT source_app, COUNT(*) FROM clipboard_history
+         WHERE source_app IS NOT NULL AND source_app != ''
+         GROUP BY source_app
+         ORDER BY COUNT(*) DESC
+         LIMIT 10",
+    ) {
+        Ok(mut stmt) => stmt
+            .query_map([], |row| {
+                Ok(SourceAppCount {
+                    source_app: row.get(0)?,
+                    count: row.get::<_, i64>(1)? as u64,
+                })
+            })
+            .map_err(|e| format!("Failed to query source stats: {}", e))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Failed to read source stats: {}", e))?,
+        Err(_) => Vec::new(),
+    };
+
+    let average_text_length: f64 = conn
+        .query_row(
+            "SELECT COALESCE(AVG(length(content)), 0) FROM clipboard_history WHERE content_type = 'text'",
+            [],
+            |row| row.get(0),
+        )
+        .map_err(|e| format!("Failed to query average length: {}", e))?;
+
+    Ok(ClipboardStats {
+        total: total as u64,
+        daily,
+        types,
+        top_sources,
+        average_text_length,
+    })
+}
+
+/// 监控读取剪贴板时 OpenClipboard 的成功/失败统计
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ClipboardOpenStats {
+    pub attempts: u64,
+    /// 重试总次数（不含首次尝试）
+    pub retries: u64,
+    /// 重试耗尽仍未打开的次数
+    pub failures: u64,
+    pub failure_rate: f64,
+    /// 最近一次失败时占用剪贴板的进程
+    pub last_blocker: Option<String>,
+    /// 同一进程连续占用导致失败的次数
+    pub blocker_streak: u32,
+}
+
+static OPEN_STATS: LazyLock<Mutex<ClipboardOpenStats>> =
+    LazyLock::new(|| Mutex::new(ClipboardOpenStats::default()));
+
+/// 同一进程连续导致失败达到该次数时提示用户
+const BLOCKER_ALERT_STREAK: u32 = 5;
+/// 每隔多少次打开写一次统计日志
+const STATS_LOG_INTERVAL: u64 = 200;
+
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+fn record_open(retries: u32, blocker: Option<Option<String>>) -> Option<String> {
+    let mut stats = OPEN_STATS.lock().ok()?;
+    stats.attempts += 1;
+    stats.retries += retries as u64;
+
+    let mut alert = None;
+    match blocker {
+        None => stats.blocker_streak = 0,
+        Some(process) => {
+            stats.failures += 1;
+            if process.is_some() && process == stats.last_blocker {
+                stats.blocker_streak += 1;
+            } else {
+                stats.blocker_streak = 1;
+            }
+            eprintln!(
+                "[Clipboard Monitor] OpenClipboard failed after {} retries, locked by {}",
+                retries,
+                process.as_deref().unwrap_or("unknown")
+            );
+            if stats.blocker_streak == BLOCKER_ALERT_STREAK {
+                alert = process.clone();
+            }
+            stats.last_blocker = process;
+        }
+    }
+    stats.failure_rate = stats.failures as f64 / stats.attempts as f64;
+
+    if stats.attempts % STATS_LOG_INTERVAL == 0 {
+        eprintln!(
+            "[Clipboard Monitor] Open stats: attempts={}, retries={}, failures={}, failure_rate={:.2}%",
+            stats.attempts,
+            stats.retries,
+            stats.failures,
+            stats.failure_rate * 100.0
+        );
+    }
+    alert
+}
+
+/// 获取剪贴板打开统计
+pub fn get_open_stats() -> ClipboardOpenStats {
+    OPEN_STATS.lock().map(|s| s.clone()).unwrap_or_default()
+}
+
+/// 因高频写入被临时屏蔽的剪贴板来源
+#[derive(Debug, Clone, Serialize)]
+pub struct BlockedClipboardSource {
+    pub source: String,
+    pub remaining_secs: u64,
+}
+
+/// 单一来源高频写入检测：记录各来源最近的写入时间，超过阈值后屏蔽一段时间
+#[derive(Default)]
+struct SpamGuard {
+    writes: HashMap<String, VecDeque<Instant>>,
+    blocked_until: HashMap<String, Instant>,
+}
+
+/// 一次写入的检测结果
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+#[derive(Debug, PartialEq)]
+enum SpamVerdict {
+    Allow,
+    Blocked,
+    /// 本次写入触发屏蔽
+    NewlyBlocked,
+}
+
+static SPAM_GUARD: LazyLock<Mutex<SpamGuard>> = LazyLock::new(|| Mutex::new(SpamGuard::default()));
+
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+fn check_spam(source: &str, settings: &settings::Settings) -> SpamVerdict {
+    let Ok(mut guard) = SPAM_GUARD.lock() else {
+        return SpamVerdict::Allow;
+    };
+    let now = Instant::now();
+
+    if let Some(until) = guard.blocked_until.get(source) {
+        if *until > now {
+            return SpamVerdict::Blocked;
+        }
+        guard.blocked_until.remove(source);
+    }
+    if settings.clipboard_spam_threshold == 0 {
+        return SpamVerdict::Allow;
+    }
+
+    let window = Duration::from_secs(settings.clipboard_spam_window_secs.max(1));
+    let writes = guard.writes.entry(source.to_string()).or_default();
+    writes.push_back(now);
+    while writes.front().is_some_and(|t| now.duration_since(*t) > window) {
+        writes.pop_front();
+    }
+    if writes.len() < settings.clipboard_spam_threshold as usize {
+        return SpamVerdict::Allow;
+    }
+
+    let count = writes.len();
+    guard.writes.remove(source);
+    guard.blocked_until.insert(
+        source.to_string(),
+        now + Duration::from_secs(settings.clipboard_spam_block_secs),
+    );
+    eprintln!(
+        "[Clipboard Monitor] {} wrote the clipboard {} times within {:?}, ignoring it for {}s",
+        source, count, window, settings.clipboard_spam_block_secs
+    );
+    SpamVerdict::NewlyBlocked
+}
+
+/// 获取当前被屏蔽的剪贴板来源
+pub fn get_blocked_sources() -> Vec<BlockedClipboardSource> {
+    let Ok(guard) = SPAM_GUARD.lock() else {
+        return Vec::new();
+    };
+    let now = Instant::now();
+    guard
+        .blocked_until
+        .iter()
+        .filter(|(_, until)| **until > now)
+        .map(|(source, until)| BlockedClipboardSource {
+            source: source.clone(),
+            remaining_secs: until.duration_since(now).as_secs(),
+        })
+        .collect()
+}
+
+/// 提前解除对某个来源的屏蔽
+pub fn unblock_source(source: &str) {
+    if let Ok(mut guard) = SPAM_GUARD.lock() {
+        guard.blocked_until.remove(source);
+        guard.writes.remove(source);
+    }
+}
+
+/// 连续粘贴栈：依次把选中的条目放入剪贴板，每次粘贴（Ctrl+V 或专用快捷键）后自动切换到下一条
+#[derive(Default)]
+struct PasteStack {
+    entries: VecDeque<(String, RichFormats)>,
+    total: usize,
+}
+
+/// 连续粘贴栈的当前状态
+#[derive(Debug, Clone, Serialize)]
+pub struct PasteStackStatus {
+    pub active: bool,
+    /// 含当前已放入剪贴板的条目
+    pub remaining: usize,
+    pub total: usize,
+    /// 当前剪贴板中条目的预览
+    pub current: Option<String>,
+}
+
+static PASTE_STACK: LazyLock<Mutex<PasteStack>> = LazyLock::new(|| Mutex::new(PasteStack::default()));
+/// 键盘钩子中快速判断是否需要处理 Ctrl+V
+static PASTE_STACK_ACTIVE: AtomicBool = AtomicBool::new(false);
+/// 正在等待切换到下一条，忽略按住 V 时的自动重复
+static PASTE_STACK_ADVANCING: AtomicBool = AtomicBool::new(false);
+
+/// 粘贴后等待目标程序读取剪贴板再切换下一条
+const PASTE_STACK_ADVANCE_DELAY: Duration = Duration::from_millis(200);
+const PASTE_STACK_PREVIEW_CHARS: usize = 80;
+
+impl PasteStack {
+    fn status(&self) -> PasteStackStatus {
+        PasteStackStatus {
+            active: !self.entries.is_empty(),
+            remaining: self.entries.len(),
+            total: self.total,
+            current: self
+                .entries
+                .front()
+                .map(|(text, _)| text.chars().take(PASTE_STACK_PREVIEW_CHARS).collect()),
+        }
+    }
+
+    /// 把栈顶条目写入剪贴板
+    fn put_current(&self) -> Result<(), String> {
+        let Some((text, rich)) = self.entries.front() else {
+            return Ok(());
+        };
+        #[cfg(target_os = "windows")]
+        {
+            monitor::set_clipboard_rich(text, rich)
+        }
+        #[cfg(not(target_os = "windows"))]
+        {
+            let _ = (text, rich);
+            Err("Paste stack is only supported on Windows".to_string())
+        }
+    }
+}
+
+/// 按给定顺序建立连续粘贴栈并把第一条放入剪贴板（图片条目不支持）
+pub fn start_paste_stack(ids: Vec<String>, app_data_dir: &PathBuf) -> Result<PasteStackStatus, String> {
+    if ids.is_empty() {
+        return Err("No clipboard items selected".to_string());
+    }
+    let conn = db::get_readonly_connection(app_data_dir)?;
+    let mut entries = VecDeque::with_capacity(ids.len());
+    for id in &ids {
+        let content_type: String = conn
+            .query_row(
+                "SELECT content_type FROM clipboard_history WHERE id = ?1",
+                params![id],
+                |row| row.get(0),
+            )
+            .optional()
+            .map_err(|e| format!("Failed to load clipboard item: {}", e))?
+            .ok_or_else(|| format!("Clipboard item {} not found", id))?;
+        if content_type == "image" {
+            return Err(format!("Image item {} cannot be added to the paste stack", id));
+        }
+        entries.push_back(get_item_formats(id, app_data_dir)?);
+    }
+
+    let mut stack = PASTE_STACK.lock().map_err(|e| e.to_string())?;
+    *stack = PasteStack {
+        total: entries.len(),
+        entries,
+    };
+    stack.put_current()?;
+    PASTE_STACK_ACTIVE.store(true, Ordering::SeqCst);
+    Ok(stack.status())
+}
+
+/// 弹出已粘贴的条目并把下一条放入剪贴板，栈空时结束
+pub fn advance_paste_stack() -> Result<PasteStackStatus, String> {
+    let mut stack = PASTE_STACK.lock().map_err(|e| e.to_string())?;
+    stack.entries.pop_front();
+    if stack.entries.is_empty() {
+        PASTE_STACK_ACTIVE.store(false, Ordering::SeqCst);
+    } else {
+        stack.put_current()?;
+    }
+    Ok(stack.status())
+}
+
+/// 取消连续粘贴，剪贴板保留当前内容
+pub fn cancel_paste_stack() -> PasteStackStatus {
+    PASTE_STACK_ACTIVE.store(false, Ordering::SeqCst);
+    match PASTE_STACK.lock() {
+        Ok(mut stack) => {
+            *stack = PasteStack::default();
+            stack.status()
+        }
+        Err(_) => PasteStack::default().status(),
+    }
+}
+
+pub fn paste_stack_status() -> PasteStackStatus {
+    PASTE_STACK
+        .lock()
+        .map(|stack| stack.status())
+        .unwrap_or_else(|_| PasteStack::default().status())
+}
+
+pub fn is_paste_stack_active() -> bool {
+    PASTE_STACK_ACTIVE.load(Ordering::SeqCst)
+}
+
+/// 键盘钩子检测到粘贴时调用：稍等目标程序读完剪贴板后切换到下一条（钩子回调中不能阻塞）
+pub fn on_paste_detected() {
+    if !is_paste_stack_active() || PASTE_STACK_ADVANCING.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    std::thread::spawn(|| {
+        std::thread::sleep(PASTE_STACK_ADVANCE_DELAY);
+        if let Err(e) = advance_paste_stack() {
+            eprintln!("[Clipboard] Failed to advance paste stack: {}", e);
+        }
+        PASTE_STACK_ADVANCING.store(false, Ordering::SeqCst);
+    });
+}
+
+/// 把 CF_DIB 数据（BITMAPINFOHEADER / V4 / V5 头 + 像素）解码为自上而下的 RGBA
+///
+/// 支持 8 位调色板、24 位与 32 位（含 BI_BITFIELDS），32 位时 alpha 全为 0 视为不透明
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+fn decode_dib(data: &[u8]) -> Option<(u32, u32, Vec<u8>)> {
+    const BI_RGB: u32 = 0;
+    const BI_BITFIELDS: u32 = 3;
+
+    let u32_at = |offset: usize| data.get(offset..offset + 4).map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]));
+    let header_size = u32_at(0)? as usize;
+    let width = u32_at(4)? as i32;
+    let raw_height = u32_at(8)? as i32;
+    let bit_count = u16::from_le_bytes([*data.get(14)?, *data.get(15)?]);
+    let compression = u32_at(16)?;
+    let colors_used = u32_at(32)? as usize;
+
+    if header_size < 40 || width <= 0 || raw_height == 0 {
+        return None;
+    }
+    if compression != BI_RGB && compression != BI_BITFIELDS {
+        return None;
+    }
+    let width = width as usize;
+    let height = raw_height.unsigned_abs() as usize;
+    let top_down = raw_height < 0;
+
+    // 旧版头部的 BI_BITFIELDS 掩码跟在头部之后；V4/V5 头部已包含掩码
+    let masks_size = if compression == BI_BITFIELDS && header_size == 40 { 12 } else { 0 };
+    let palette_len = match bit_count {
+        8 => if colors_used == 0 { 256 } else { colors_used },
+        24 | 32 => colors_used,
+        _ => return None,
+    };
+    let palette_offset = header_size + masks_size;
+    let pixel_offset = palette_offset + palette_len * 4;
+
+    let row_size = (width * bit_count as usize).div_ceil(32) * 4;
+    let pixels = data.get(pixel_offset..pixel_offset + row_size * height)?;
+
+    let mut rgba = Vec::with_capacity(width * height * 4);
+    let mut has_alpha = false;
+    for y in 0..height {
+        let src_row = if top_down { y } else { height - 1 - y };
+        let row = &pixels[src_row * row_size..(src_row + 1) * row_size];
+        for x in 0..width {
+            let (b, g, r, a) = match bit_count {
+                8 => {
+                    let entry = palette_offset + row[x] as usize * 4;
+                    let color = data.get(entry..entry + 3)?;
+                    (color[0], color[1], color[2], 255)
+                }
+                24 => (row[x * 3], row[x * 3 + 1], row[x * 3 + 2], 255),
+                _ => {
+                    let a = row[x * 4 + 3];
+                    has_alpha |= a != 0;
+                    (row[x * 4], row[x * 4 + 1], row[x * 4 + 2], a)
+                }
+            };
+            rgba.extend_from_slice(&[r, g, b, a]);
+        }
+    }
+
+    if bit_count == 32 && !has_alpha {
+        for pixel in rgba.chunks_exact_mut(4) {
+            pixel[3] = 255;
+        }
+    }
+    Some((width as u32, height as u32, rgba))
+}
+
+/// 是否记录剪贴板内容（暂停时监控线程仍在运行，只是不再入库）
+static MONITORING_ENABLED: AtomicBool = AtomicBool::new(true);
+
+/// 当前是否在记录剪贴板内容
+pub fn is_monitoring_enabled() -> bool {
+    MONITORING_ENABLED.load(Ordering::SeqCst)
+}
+
+/// 更新记录状态（启动或保存设置时调用），状态变化时通知前端与托盘
+pub fn apply_monitoring_enabled(app: &tauri::AppHandle, enabled: bool) {
+    use tauri::Emitter;
+    if MONITORING_ENABLED.swap(enabled, Ordering::SeqCst) != enabled {
+        eprintln!("[Clipboard] Monitoring {}", if enabled { "resumed" } else { "paused" });
+        let _ = app.emit("clipboard-monitoring-changed", enabled);
+    }
+}
+
+/// 开启 / 暂停剪贴板记录并写入设置，重启后保持
+pub fn set_monitoring_enabled(
+    app: &tauri::AppHandle,
+    enabled: bool,
+    app_data_dir: &PathBuf,
+) -> Result<(), String> {
+    let mut settings = settings::load_settings(app_data_dir)?;
+    settings.clipboard_monitoring = enabled;
+    settings::save_settings(app_data_dir, &settings)?;
+    apply_monitoring_enabled(app, enabled);
+    Ok(())
+}
+
+/// 捕获提示的最小间隔，连续复制时间隔内的条目不再提示
+const CAPTURE_TOAST_MIN_INTERVAL: Duration = Duration::from_secs(5);
+/// 捕获提示显示的摘要字符数
+const CAPTURE_TOAST_PREVIEW_CHARS: usize = 50;
+
+static LAST_CAPTURE_TOAST: Mutex<Option<Instant>> = Mutex::new(None);
 
-    let mut items = Vec::new();
-    for row in rows {
-        items.push(row.map_err(|e| format!("Failed to read clipboard row: {}", e))?);
+/// 新条目入库后：敏感条目按设置定时清除，并按需显示捕获提示
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+fn on_captured(app: &tauri::AppHandle, item: &ClipboardItem, app_data_dir: &PathBuf) {
+    crate::clipboard_expiry::schedule_if_sensitive(item, app_data_dir);
+    notify_captured(app, item, app_data_dir);
+}
+
+/// 开启捕获提示时在通知区域显示新条目摘要，确认内容已被记录
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+fn notify_captured(app: &tauri::AppHandle, item: &ClipboardItem, app_data_dir: &PathBuf) {
+    let enabled = settings::load_settings(app_data_dir)
+        .map(|s| s.clipboard_capture_toast)
+        .unwrap_or(false);
+    if !enabled {
+        return;
     }
-    Ok(items)
+    if item.content_type != "image" && crate::clipboard_sensitive::looks_sensitive(&item.content) {
+        return;
+    }
+    {
+        let mut last = LAST_CAPTURE_TOAST.lock().unwrap();
+        if last.is_some_and(|t| t.elapsed() < CAPTURE_TOAST_MIN_INTERVAL) {
+            return;
+        }
+        *last = Some(Instant::now());
+    }
+
+    let summary = match item.content_type.as_str() {
+        "image" => "[图片]".to_string(),
+        "file" => item
+            .content
+            .lines()
+            .filter_map(|path| std::path::Path::new(path).file_name())
+            .map(|name| name.to_string_lossy().to_string())
+            .collect::<Vec<_>>()
+            .join(", "),
+        _ => item.content.split_whitespace().collect::<Vec<_>>().join(" "),
+    };
+    let mut preview: String = summary.chars().take(CAPTURE_TOAST_PREVIEW_CHARS).collect();
+    if summary.chars().count() > CAPTURE_TOAST_PREVIEW_CHARS {
+        preview.push('…');
+    }
+    let _ = crate::toast::show_toast(app, "已记录剪贴板内容", &preview);
 }
 
 #[cfg(target_os = "windows")]
@@ -465,7 +2429,8 @@ pub mod monitor {
     use std::ptr;
     use windows_sys::Win32::System::DataExchange::{
         GetClipboardData, IsClipboardFormatAvailable, OpenClipboard, CloseClipboard,
-        AddClipboardFormatListener, RemoveClipboardFormatListener,
+        AddClipboardFormatListener, RemoveClipboardFormatListener, GetOpenClipboardWindow,
+        GetClipboardOwner,
     };
     use windows_sys::Win32::System::Memory::{GlobalLock, GlobalUnlock, GlobalSize};
     use windows_sys::Win32::Foundation::{HWND, HINSTANCE, LPARAM, WPARAM};
@@ -475,22 +2440,194 @@ pub mod monitor {
         WS_OVERLAPPED, CS_HREDRAW, CS_VREDRAW,
     };
     use windows_sys::Win32::Graphics::Gdi::{
-        GetDIBits, BITMAPINFO, BITMAPINFOHEADER, BI_RGB, DIB_RGB_COLORS,
+        GetDC, GetDIBits, ReleaseDC, BITMAPINFO, BITMAPINFOHEADER, BI_RGB, DIB_RGB_COLORS,
     };
 
     const CF_TEXT: u32 = 1;
     const CF_UNICODETEXT: u32 = 13;
     const CF_DIB: u32 = 8;
     const CF_BITMAP: u32 = 2;
+    const CF_HDROP: u32 = 15;
+
+    /// OpenClipboard 最多尝试次数
+    const OPEN_MAX_ATTEMPTS: u32 = 5;
+    /// 首次重试前等待时间，之后每次翻倍（最长累计约 150ms）
+    const OPEN_BASE_BACKOFF_MS: u64 = 10;
+
+    /// 同一进程长期锁定剪贴板的待提示进程名
+    static PENDING_BLOCKER_ALERT: Mutex<Option<String>> = Mutex::new(None);
+
+    /// 打开剪贴板，被占用时指数退避重试并记录统计
+    ///
+    /// 只在收到 WM_CLIPBOARDUPDATE 后于监控线程调用，重试不会阻塞用户的复制操作
+    fn open_clipboard_with_retry() -> bool {
+        for attempt in 0..OPEN_MAX_ATTEMPTS {
+            if unsafe { OpenClipboard(0 as HWND) } != 0 {
+                record_open(attempt, None);
+                return true;
+            }
+            if attempt + 1 < OPEN_MAX_ATTEMPTS {
+                thread::sleep(std::time::Duration::from_millis(OPEN_BASE_BACKOFF_MS << attempt));
+            }
+        }
+
+        let blocker = crate::process_info::windows::get_window_process_name(unsafe { GetOpenClipboardWindow() });
+        if let Some(process) = record_open(OPEN_MAX_ATTEMPTS - 1, Some(blocker)) {
+            if let Ok(mut pending) = PENDING_BLOCKER_ALERT.lock() {
+                *pending = Some(process);
+            }
+        }
+        false
+    }
+
+    /// 有进程持续锁定剪贴板时提示用户（每次连续失败只提示一次）
+    fn notify_blocker(app: &tauri::AppHandle) {
+        let Some(process) = PENDING_BLOCKER_ALERT.lock().ok().and_then(|mut p| p.take()) else {
+            return;
+        };
+        eprintln!("[Clipboard Monitor] {} keeps locking the clipboard", process);
+        let _ = crate::toast::show_toast(
+            app,
+            "剪贴板记录可能遗漏",
+            &format!("{} 长时间占用剪贴板，部分复制内容未能记录", process),
+        );
+    }
+
+    /// 当前剪贴板内容的来源进程名（剪贴板所有者窗口所属进程）
+    fn clipboard_source() -> Option<String> {
+        crate::process_info::windows::get_window_process_name(unsafe { GetClipboardOwner() })
+    }
+
+    /// 当前剪贴板内容的来源应用与窗口标题
+    ///
+    /// 剪贴板所有者常是隐藏窗口，标题优先取同一进程的前台窗口；没有所有者时以前台窗口为来源
+    fn clipboard_source_info() -> ClipboardSource {
+        use crate::process_info::windows::{get_foreground_window, get_window_process_id, get_window_process_name, get_window_title};
+
+        let owner = unsafe { GetClipboardOwner() };
+        let foreground = get_foreground_window();
+        let app = get_window_process_name(owner).or_else(|| get_window_process_name(foreground));
+        let title_hwnd = if owner == 0 || get_window_process_id(owner) == get_window_process_id(foreground) {
+            foreground
+        } else {
+            owner
+        };
+        ClipboardSource {
+            app,
+            title: get_window_title(title_hwnd),
+        }
+    }
+
+    /// 本次更新是否应按来源忽略：内容带有 ExcludeClipboardContentFromMonitorProcessing 标记，
+    /// 或来源进程在排除列表中（剪贴板无所有者窗口时以前台进程作为来源）
+    fn is_ignored_source(app_data_dir: &PathBuf) -> bool {
+        let excluded = registered_format("ExcludeClipboardContentFromMonitorProcessing");
+        if unsafe { IsClipboardFormatAvailable(excluded) } != 0 {
+            return true;
+        }
+
+        let ignored = settings::load_settings(app_data_dir)
+            .map(|s| s.clipboard_ignored_processes)
+            .unwrap_or_default();
+        if ignored.is_empty() {
+            return false;
+        }
+        let Some(source) = clipboard_source().or_else(crate::process_info::windows::get_foreground_process_name) else {
+            return false;
+        };
+        let source_stem = source.strip_suffix(".exe").or_else(|| source.strip_suffix(".EXE")).unwrap_or(&source);
+        let matched = ignored.iter().any(|name| {
+            let name = name.trim();
+            name.eq_ignore_ascii_case(&source) || name.eq_ignore_ascii_case(source_stem)
+        });
+        if matched {
+            eprintln!("[Clipboard Monitor] Ignored clipboard update from {}", source);
+        }
+        matched
+    }
+
+    /// 本次更新是否来自被屏蔽的高频写入来源，新触发屏蔽时通知前端并弹出提示
+    fn is_spam_blocked(app: &tauri::AppHandle, app_data_dir: &PathBuf) -> bool {
+        use tauri::Emitter;
+
+        let Some(source) = clipboard_source() else {
+            return false;
+        };
+        let settings = settings::load_settings(app_data_dir).unwrap_or_default();
+        match check_spam(&source, &settings) {
+            SpamVerdict::Allow => false,
+            SpamVerdict::Blocked => true,
+            SpamVerdict::NewlyBlocked => {
+                let _ = app.emit(
+                    "clipboard-source-blocked",
+                    BlockedClipboardSource {
+                        source: source.clone(),
+                        remaining_secs: settings.clipboard_spam_block_secs,
+                    },
+                );
+                let _ = crate::toast::show_toast(
+                    app,
+                    "已暂停记录剪贴板来源",
+                    &format!(
+                        "{} 频繁写入剪贴板，{} 分钟内不再记录",
+                        source,
+                        settings.clipboard_spam_block_secs.div_ceil(60)
+                    ),
+                );
+                true
+            }
+        }
+    }
+
+    /// 按敏感词规则检查内容，命中时提醒；返回 true 表示不写入历史
+    fn is_alert_blocked(app: &tauri::AppHandle, content: &str, app_data_dir: &PathBuf) -> bool {
+        let alert = settings::load_settings(app_data_dir)
+            .map(|s| s.clipboard_alert)
+            .unwrap_or_default();
+        crate::clipboard_alert::check(app, content, &alert)
+    }
+
+    /// 监控线程中上次记录的内容，用于跳过重复通知
+    #[derive(Default)]
+    struct MonitorState {
+        last_text_content: String,
+        last_image_hash: String,
+        last_files_content: String,
+    }
+
+    /// 轮询回退模式下检查剪贴板序列号的间隔
+    const POLL_INTERVAL_MS: u64 = 500;
+
+    /// 启动剪切板监控线程
+    ///
+    /// 优先使用 AddClipboardFormatListener + 隐藏消息窗口即时接收更新通知；
+    /// 创建窗口或注册监听失败时回退为按 GetClipboardSequenceNumber 轮询
+    /// 监控线程的消息窗口，退出时向其投递停止消息
+    static MONITOR_HWND: std::sync::atomic::AtomicIsize = std::sync::atomic::AtomicIsize::new(0);
+    static MONITOR_STOPPED: AtomicBool = AtomicBool::new(false);
+    const WM_STOP_MONITOR: u32 = windows_sys::Win32::UI::WindowsAndMessaging::WM_APP + 1;
+
+    /// 停止剪贴板监控线程（退出流程调用），不再写入新的历史
+    pub fn stop_clipboard_monitor() {
+        MONITOR_STOPPED.store(true, Ordering::SeqCst);
+        let hwnd = MONITOR_HWND.swap(0, Ordering::SeqCst);
+        if hwnd != 0 {
+            unsafe {
+                windows_sys::Win32::UI::WindowsAndMessaging::PostMessageW(hwnd, WM_STOP_MONITOR, 0, 0);
+            }
+        }
+    }
 
-    /// 启动剪切板监控线程（使用 Windows 消息机制，完全避免冲突）
-    pub fn start_clipboard_monitor(app_data_dir: PathBuf) -> Result<(), String> {
+    pub fn start_clipboard_monitor(app: tauri::AppHandle, app_data_dir: PathBuf) -> Result<(), String> {
         thread::spawn(move || {
+            let mut state = MonitorState::default();
+
             // 创建隐藏的消息窗口来接收剪贴板更新通知
             let hwnd = match create_message_window() {
                 Ok(hwnd) => hwnd,
                 Err(e) => {
-                    eprintln!("[Clipboard Monitor] Failed to create message window: {}", e);
+                    eprintln!("[Clipboard Monitor] Failed to create message window: {}, falling back to polling", e);
+                    poll_clipboard(&app, &app_data_dir, &mut state);
                     return;
                 }
             };
@@ -498,50 +2635,28 @@ pub mod monitor {
             // 注册剪贴板格式监听器（不需要打开剪贴板，完全避免冲突）
             unsafe {
                 if AddClipboardFormatListener(hwnd) == 0 {
-                    eprintln!("[Clipboard Monitor] Failed to add clipboard format listener");
+                    eprintln!("[Clipboard Monitor] Failed to add clipboard format listener, falling back to polling");
+                    poll_clipboard(&app, &app_data_dir, &mut state);
                     return;
                 }
             }
 
-            let mut last_text_content = String::new();
-            let mut last_image_hash = String::new();
+            MONITOR_HWND.store(hwnd, Ordering::SeqCst);
 
             // 消息循环：只在收到剪贴板更新通知时才读取剪贴板
             let mut msg: MSG = unsafe { std::mem::zeroed() };
             loop {
                 unsafe {
                     let result = GetMessageW(&mut msg, hwnd, 0, 0);
-                    if result == 0 || result == -1 {
-                        // WM_QUIT 或错误
+                    if result == 0 || result == -1 || msg.message == WM_STOP_MONITOR {
+                        // WM_QUIT、错误或退出流程要求停止
                         break;
                     }
 
                     if msg.message == WM_CLIPBOARDUPDATE {
                         // 剪贴板内容已改变，现在可以安全地读取
                         // 因为这是系统通知，说明剪贴板操作已完成
-                        
-                        // 检查文本内容
-                        if let Ok(content) = get_clipboard_text() {
-                            if !content.is_empty() && content != last_text_content {
-                                if let Err(e) = add_clipboard_item(content.clone(), "text".to_string(), &app_data_dir) {
-                                    eprintln!("[Clipboard Monitor] Failed to add text clipboard item: {}", e);
-                                }
-                                last_text_content = content;
-                            }
-                        }
-                        
-                        // 检查图片内容
-                        if let Ok(image_path) = get_clipboard_image(&app_data_dir) {
-                            if !image_path.is_empty() {
-                                let image_hash = format!("{}", image_path);
-                                if image_hash != last_image_hash {
-                                    if let Err(e) = add_clipboard_item(image_path.clone(), "image".to_string(), &app_data_dir) {
-                                        eprintln!("[Clipboard Monitor] Failed to add image clipboard item: {}", e);
-                                    }
-                                    last_image_hash = image_hash;
-                                }
-                            }
-                        }
+                        handle_clipboard_update(&app, &app_data_dir, &mut state);
                     }
 
                     TranslateMessage(&msg);
@@ -558,6 +2673,92 @@ pub mod monitor {
         Ok(())
     }
 
+    /// 轮询回退：剪贴板序列号变化时按更新处理
+    fn poll_clipboard(app: &tauri::AppHandle, app_data_dir: &PathBuf, state: &mut MonitorState) {
+        use windows_sys::Win32::System::DataExchange::GetClipboardSequenceNumber;
+
+        let mut last_sequence = unsafe { GetClipboardSequenceNumber() };
+        while !MONITOR_STOPPED.load(Ordering::SeqCst) {
+            thread::sleep(std::time::Duration::from_millis(POLL_INTERVAL_MS));
+            let sequence = unsafe { GetClipboardSequenceNumber() };
+            if sequence != last_sequence {
+                last_sequence = sequence;
+                handle_clipboard_update(app, app_data_dir, state);
+            }
+        }
+    }
+
+    /// 处理一次剪贴板更新：读取文件 / 文本 / 图片并写入历史
+    fn handle_clipboard_update(app: &tauri::AppHandle, app_data_dir: &PathBuf, state: &mut MonitorState) {
+        if crate::stealth::is_enabled() || !is_monitoring_enabled() || is_ignored_source(app_data_dir) || is_spam_blocked(app, app_data_dir) {
+            return;
+        }
+
+        let source = clipboard_source_info();
+
+        // 复制文件时（CF_HDROP）记录路径列表，不再读取同时附带的文本/图片
+        let files = get_clipboard_files().unwrap_or_default();
+        if !files.is_empty() {
+            let content = files.join("\n");
+            if content != state.last_files_content {
+                if !is_alert_blocked(app, &content, app_data_dir) {
+                    match add_clipboard_item_rich(content.clone(), "file".to_string(), false, RichFormats::default(), source, app_data_dir) {
+                        Ok(item) => on_captured(app, &item, app_data_dir),
+                        Err(e) => eprintln!("[Clipboard Monitor] Failed to add file clipboard item: {}", e),
+                    }
+                }
+                state.last_files_content = content;
+            }
+        } else {
+            // 检查文本内容（超大文本只读取摘要）
+            let (max_bytes, keep_bytes) = text_size_limits(app_data_dir);
+            if let Ok((content, truncated)) = get_clipboard_text(max_bytes, keep_bytes) {
+                if !content.is_empty() && content != state.last_text_content {
+                    if truncated {
+                        eprintln!("[Clipboard Monitor] Clipboard text exceeds {} bytes, keeping first {} bytes", max_bytes, keep_bytes);
+                    }
+                    state.last_text_content = content.clone();
+                    // 按规范化规则处理后入库，避免仅空白 / 换行不同的重复条目
+                    let rules = settings::load_settings(app_data_dir)
+                        .map(|s| s.clipboard_normalize)
+                        .unwrap_or_default();
+                    let content = normalize_text(content, &rules);
+                    // 规范化后为空（如纯空白）或命中敏感词且设置为阻止时不入库
+                    if !content.is_empty() && !is_alert_blocked(app, &content, app_data_dir) {
+                        let content_type = if !truncated && detect_colors(app_data_dir) && crate::color::parse_color_text(&content).is_some() {
+                            "color"
+                        } else {
+                            "text"
+                        };
+                        // 截断的文本不保存富文本格式，避免与摘要内容不一致
+                        let rich = if truncated {
+                            RichFormats::default()
+                        } else {
+                            get_clipboard_rich_formats(max_bytes)
+                        };
+                        match add_clipboard_item_rich(content, content_type.to_string(), truncated, rich, source.clone(), app_data_dir) {
+                            Ok(item) => on_captured(app, &item, app_data_dir),
+                            Err(e) => eprintln!("[Clipboard Monitor] Failed to add text clipboard item: {}", e),
+                        }
+                    }
+                }
+            }
+
+            // 检查图片内容
+            if let Ok(image_path) = get_clipboard_image(app_data_dir) {
+                if !image_path.is_empty() && image_path != state.last_image_hash {
+                    match add_clipboard_item_rich(image_path.clone(), "image".to_string(), false, RichFormats::default(), source, app_data_dir) {
+                        Ok(item) => on_captured(app, &item, app_data_dir),
+                        Err(e) => eprintln!("[Clipboard Monitor] Failed to add image clipboard item: {}", e),
+                    }
+                    state.last_image_hash = image_path;
+                }
+            }
+        }
+
+        notify_blocker(app);
+    }
+
     /// 创建隐藏的消息窗口
     fn create_message_window() -> Result<HWND, String> {
         unsafe {
@@ -614,14 +2815,85 @@ pub mod monitor {
         DefWindowProcW(hwnd, msg, wparam, lparam)
     }
 
-    /// 获取剪切板文本内容
-    pub fn get_clipboard_text() -> Result<String, String> {
+    /// 清空系统剪贴板
+    pub fn clear_clipboard() -> Result<(), String> {
+        use windows_sys::Win32::System::DataExchange::EmptyClipboard;
+
+        unsafe {
+            if !open_clipboard_with_retry() {
+                return Err("Clipboard is busy or unavailable".to_string());
+            }
+            let cleared = EmptyClipboard() != 0;
+            CloseClipboard();
+            if cleared {
+                Ok(())
+            } else {
+                Err("Failed to empty clipboard".to_string())
+            }
+        }
+    }
+
+    /// 写入文本到剪切板（CF_UNICODETEXT）
+    pub fn set_clipboard_text(text: &str) -> Result<(), String> {
+        use windows_sys::Win32::System::DataExchange::{EmptyClipboard, SetClipboardData};
+        use windows_sys::Win32::System::Memory::{GlobalAlloc, GlobalFree, GMEM_MOVEABLE};
+
         unsafe {
-            // 尝试打开剪贴板，如果失败（可能被其他程序占用），立即返回错误
-            // 不重试，避免阻塞用户的复制操作
             if OpenClipboard(0 as HWND) == 0 {
                 return Err("Clipboard is busy or unavailable".to_string());
             }
+            EmptyClipboard();
+
+            let text_wide: Vec<u16> = text.encode_utf16().chain(std::iter::once(0)).collect();
+            let h_mem = GlobalAlloc(GMEM_MOVEABLE, text_wide.len() * 2);
+            if h_mem.is_null() {
+                CloseClipboard();
+                return Err("Failed to allocate clipboard memory".to_string());
+            }
+            let p_mem = GlobalLock(h_mem) as *mut u16;
+            if p_mem.is_null() {
+                GlobalFree(h_mem);
+                CloseClipboard();
+                return Err("Failed to lock clipboard memory".to_string());
+            }
+            std::ptr::copy_nonoverlapping(text_wide.as_ptr(), p_mem, text_wide.len());
+            GlobalUnlock(h_mem);
+
+            if SetClipboardData(CF_UNICODETEXT, h_mem as isize) == 0 {
+                GlobalFree(h_mem);
+                CloseClipboard();
+                return Err("Failed to set clipboard data".to_string());
+            }
+            CloseClipboard();
+            Ok(())
+        }
+    }
+
+    /// 是否开启了颜色值自动识别
+    fn detect_colors(app_data_dir: &PathBuf) -> bool {
+        settings::load_settings(app_data_dir)
+            .map(|s| s.clipboard_detect_colors)
+            .unwrap_or(false)
+    }
+
+    /// 读取设置中的文本大小阈值与摘要大小（字节）
+    fn text_size_limits(app_data_dir: &PathBuf) -> (usize, usize) {
+        let settings = settings::load_settings(app_data_dir).unwrap_or_default();
+        let max_bytes = settings.clipboard_max_text_kb.max(1) as usize * 1024;
+        let keep_bytes = (settings.clipboard_truncated_keep_kb.max(1) as usize * 1024).min(max_bytes);
+        (max_bytes, keep_bytes)
+    }
+
+    /// 获取剪切板文本内容
+    ///
+    /// 读取前先通过 GlobalSize 检查数据大小，超过 max_bytes 时只读取前 keep_bytes 字节，
+    /// 返回值第二项表示是否被截断
+    pub fn get_clipboard_text(max_bytes: usize, keep_bytes: usize) -> Result<(String, bool), String> {
+        unsafe {
+            // 剪贴板可能被其他程序短暂占用，退避重试后仍失败则返回错误
+            if !open_clipboard_with_retry() {
+                return Err("Clipboard is busy or unavailable".to_string());
+            }
 
             let result = if IsClipboardFormatAvailable(CF_UNICODETEXT) != 0 {
                 let h_data = GetClipboardData(CF_UNICODETEXT);
@@ -636,16 +2908,20 @@ pub mod monitor {
                     return Err("Failed to lock clipboard data".to_string());
                 }
 
+                let data_size = GlobalSize(h_data as *mut std::ffi::c_void);
+                let truncated = data_size > max_bytes;
+                let limit = if truncated { keep_bytes } else { data_size } / 2;
+
                 let text = std::ffi::OsString::from_wide(
                     std::slice::from_raw_parts(
                         p_data as *const u16,
-                        (0..).take_while(|&i| *((p_data as *const u16).add(i)) != 0).count(),
+                        (0..limit).take_while(|&i| *((p_data as *const u16).add(i)) != 0).count(),
                     ),
                 );
                 
                 GlobalUnlock(h_data as *mut std::ffi::c_void);
                 
-                text.to_string_lossy().to_string()
+                (text.to_string_lossy().to_string(), truncated)
             } else if IsClipboardFormatAvailable(CF_TEXT) != 0 {
                 let h_data = GetClipboardData(CF_TEXT);
                 if h_data == 0 {
@@ -659,14 +2935,21 @@ pub mod monitor {
                     return Err("Failed to lock clipboard data".to_string());
                 }
 
-                let c_str = std::ffi::CStr::from_ptr(p_data as *const i8);
-                let text = c_str.to_string_lossy().to_string();
+                let data_size = GlobalSize(h_data as *mut std::ffi::c_void);
+                let truncated = data_size > max_bytes;
+                let limit = if truncated { keep_bytes } else { data_size };
+
+                let bytes = std::slice::from_raw_parts(
+                    p_data as *const u8,
+                    (0..limit).take_while(|&i| *((p_data as *const u8).add(i)) != 0).count(),
+                );
+                let text = String::from_utf8_lossy(bytes).to_string();
                 
                 GlobalUnlock(h_data as *mut std::ffi::c_void);
                 
-                text
+                (text, truncated)
             } else {
-                String::new()
+                (String::new(), false)
             };
 
             CloseClipboard();
@@ -674,129 +2957,448 @@ pub mod monitor {
         }
     }
 
-    /// 获取剪切板图片并保存到本地
-    pub fn get_clipboard_image(app_data_dir: &PathBuf) -> Result<String, String> {
+    /// 注册（或获取已注册的）自定义剪贴板格式 ID
+    fn registered_format(name: &str) -> u32 {
+        use windows_sys::Win32::System::DataExchange::RegisterClipboardFormatW;
+
+        let wide: Vec<u16> = OsStr::new(name).encode_wide().chain(std::iter::once(0)).collect();
+        unsafe { RegisterClipboardFormatW(wide.as_ptr()) }
+    }
+
+    fn html_format() -> u32 {
+        registered_format("HTML Format")
+    }
+
+    fn rtf_format() -> u32 {
+        registered_format("Rich Text Format")
+    }
+
+    /// 读取剪贴板中某格式的原始字节（去掉结尾的 \0），超过 max_bytes 时放弃，调用前需已打开剪贴板
+    unsafe fn read_format_bytes(format: u32, max_bytes: usize) -> Option<Vec<u8>> {
+        if format == 0 || IsClipboardFormatAvailable(format) == 0 {
+            return None;
+        }
+        let h_data = GetClipboardData(format);
+        if h_data == 0 {
+            return None;
+        }
+        let size = GlobalSize(h_data as *mut std::ffi::c_void);
+        if size == 0 || size > max_bytes {
+            return None;
+        }
+        let p_data = GlobalLock(h_data as *mut std::ffi::c_void);
+        if p_data.is_null() {
+            return None;
+        }
+        let mut bytes = std::slice::from_raw_parts(p_data as *const u8, size).to_vec();
+        GlobalUnlock(h_data as *mut std::ffi::c_void);
+
+        while bytes.last() == Some(&0) {
+            bytes.pop();
+        }
+        (!bytes.is_empty()).then_some(bytes)
+    }
+
+    /// 读取剪贴板中的 CF_HTML 与 RTF 格式（单个格式超过 max_bytes 时不保存）
+    pub fn get_clipboard_rich_formats(max_bytes: usize) -> RichFormats {
+        let (html_format, rtf_format) = (html_format(), rtf_format());
+        unsafe {
+            if IsClipboardFormatAvailable(html_format) == 0 && IsClipboardFormatAvailable(rtf_format) == 0 {
+                return RichFormats::default();
+            }
+            if !open_clipboard_with_retry() {
+                return RichFormats::default();
+            }
+            let rich = RichFormats {
+                // CF_HTML 规定为 UTF-8；RTF 为 7 位 ASCII（非 ASCII 字符已转义）
+                html: read_format_bytes(html_format, max_bytes).map(|b| String::from_utf8_lossy(&b).to_string()),
+                rtf: read_format_bytes(rtf_format, max_bytes).map(|b| String::from_utf8_lossy(&b).to_string()),
+            };
+            CloseClipboard();
+            rich
+        }
+    }
+
+    /// 把数据复制到新分配的全局内存并放入剪贴板，调用前需已打开剪贴板
+    unsafe fn put_clipboard_bytes(format: u32, bytes: &[u8]) -> Result<(), String> {
+        use windows_sys::Win32::System::DataExchange::SetClipboardData;
+        use windows_sys::Win32::System::Memory::{GlobalAlloc, GlobalFree, GMEM_MOVEABLE};
+
+        let h_mem = GlobalAlloc(GMEM_MOVEABLE, bytes.len());
+        if h_mem.is_null() {
+            return Err("Failed to allocate clipboard memory".to_string());
+        }
+        let p_mem = GlobalLock(h_mem) as *mut u8;
+        if p_mem.is_null() {
+            GlobalFree(h_mem);
+            return Err("Failed to lock clipboard memory".to_string());
+        }
+        std::ptr::copy_nonoverlapping(bytes.as_ptr(), p_mem, bytes.len());
+        GlobalUnlock(h_mem);
+
+        if SetClipboardData(format, h_mem as isize) == 0 {
+            GlobalFree(h_mem);
+            return Err("Failed to set clipboard data".to_string());
+        }
+        Ok(())
+    }
+
+    /// 同时写入纯文本与保存的 HTML / RTF 格式，目标程序可按原格式粘贴
+    pub fn set_clipboard_rich(text: &str, rich: &RichFormats) -> Result<(), String> {
+        use windows_sys::Win32::System::DataExchange::EmptyClipboard;
+
+        let text_bytes: Vec<u8> = text
+            .encode_utf16()
+            .chain(std::iter::once(0))
+            .flat_map(|unit| unit.to_le_bytes())
+            .collect();
+
         unsafe {
-            // 尝试打开剪贴板，如果失败（可能被其他程序占用），立即返回错误
-            // 不重试，避免阻塞用户的复制操作
             if OpenClipboard(0 as HWND) == 0 {
                 return Err("Clipboard is busy or unavailable".to_string());
             }
-
-            let result = if IsClipboardFormatAvailable(CF_DIB) != 0 {
-                let h_data = GetClipboardData(CF_DIB);
-                if h_data == 0 {
-                    CloseClipboard();
-                    return Err("Failed to get clipboard DIB data".to_string());
+            EmptyClipboard();
+
+            let result = (|| -> Result<(), String> {
+                put_clipboard_bytes(CF_UNICODETEXT, &text_bytes)?;
+                if let Some(html) = &rich.html {
+                    let mut bytes = html.as_bytes().to_vec();
+                    bytes.push(0);
+                    put_clipboard_bytes(html_format(), &bytes)?;
                 }
-
-                let p_data = GlobalLock(h_data as *mut std::ffi::c_void);
-                if p_data.is_null() {
-                    CloseClipboard();
-                    return Err("Failed to lock clipboard data".to_string());
+                if let Some(rtf) = &rich.rtf {
+                    let mut bytes = rtf.as_bytes().to_vec();
+                    bytes.push(0);
+                    put_clipboard_bytes(rtf_format(), &bytes)?;
                 }
+                Ok(())
+            })();
 
-                let data_size = GlobalSize(h_data as *mut std::ffi::c_void);
-                if data_size == 0 {
-                    GlobalUnlock(h_data as *mut std::ffi::c_void);
-                    CloseClipboard();
-                    return Err("Invalid clipboard data size".to_string());
-                }
+            CloseClipboard();
+            result
+        }
+    }
 
-                // 读取 BITMAPINFOHEADER
-                let bmi = p_data as *const BITMAPINFOHEADER;
-                let width = (*bmi).biWidth;
-                let height = (*bmi).biHeight.abs();
-                let bit_count = (*bmi).biBitCount;
+    /// 读取剪贴板中复制的文件路径（CF_HDROP），没有文件时返回空列表
+    pub fn get_clipboard_files() -> Result<Vec<String>, String> {
+        use windows_sys::Win32::UI::Shell::DragQueryFileW;
 
-                // 创建保存目录
-                let clipboard_images_dir = app_data_dir.join("clipboard_images");
-                if let Err(e) = std::fs::create_dir_all(&clipboard_images_dir) {
-                    GlobalUnlock(h_data as *mut std::ffi::c_void);
-                    CloseClipboard();
-                    return Err(format!("Failed to create clipboard images directory: {}", e));
-                }
+        unsafe {
+            if IsClipboardFormatAvailable(CF_HDROP) == 0 {
+                return Ok(Vec::new());
+            }
+            if !open_clipboard_with_retry() {
+                return Err("Clipboard is busy or unavailable".to_string());
+            }
 
-                // 计算图片数据大小
-                let bytes_per_pixel = (bit_count / 8) as usize;
-                let row_size = ((width * bit_count as i32 + 31) / 32 * 4) as usize;
-                let image_data_size = row_size * height as usize;
-
-                // 获取图片数据指针（跳过 BITMAPINFOHEADER）
-                let image_data_ptr = (p_data as *const u8).add(std::mem::size_of::<BITMAPINFOHEADER>());
-                let image_data = std::slice::from_raw_parts(image_data_ptr, image_data_size.min(data_size - std::mem::size_of::<BITMAPINFOHEADER>()));
-
-                // 转换 BGR 到 RGB 并保存为 PNG
-                let mut rgba_data = Vec::with_capacity((width * height * 4) as usize);
-                for y in (0..height).rev() {
-                    for x in 0..width {
-                        let offset = (y as usize * row_size + x as usize * bytes_per_pixel) as usize;
-                        if offset + bytes_per_pixel <= image_data.len() {
-                            let b = image_data[offset];
-                            let g = image_data[offset + 1];
-                            let r = image_data[offset + 2];
-                            rgba_data.push(r);
-                            rgba_data.push(g);
-                            rgba_data.push(b);
-                            rgba_data.push(255); // Alpha
-                        }
+            let hdrop = GetClipboardData(CF_HDROP);
+            let mut paths = Vec::new();
+            if hdrop != 0 {
+                let count = DragQueryFileW(hdrop, 0xFFFFFFFF, ptr::null_mut(), 0);
+                for index in 0..count {
+                    let len = DragQueryFileW(hdrop, index, ptr::null_mut(), 0);
+                    if len == 0 {
+                        continue;
                     }
+                    let mut buffer = vec![0u16; len as usize + 1];
+                    let copied = DragQueryFileW(hdrop, index, buffer.as_mut_ptr(), buffer.len() as u32);
+                    buffer.truncate(copied as usize);
+                    paths.push(std::ffi::OsString::from_wide(&buffer).to_string_lossy().to_string());
                 }
+            }
+            CloseClipboard();
+            Ok(paths)
+        }
+    }
 
-                // 计算图片内容的哈希值（用于去重）
-                let mut hasher = Sha256::new();
-                hasher.update(&rgba_data);
-                let hash_result = hasher.finalize();
-                let hash_str = format!("{:x}", hash_result);
-                
-                // 使用哈希值作为文件名（取前16个字符）
-                let filename = format!("clipboard_{}.png", &hash_str[..16]);
-                let file_path = clipboard_images_dir.join(&filename);
+    /// 把文件列表以 CF_HDROP 写入剪贴板，可在资源管理器中直接粘贴
+    pub fn set_clipboard_files(paths: &[String]) -> Result<(), String> {
+        use windows_sys::Win32::Foundation::POINT;
+        use windows_sys::Win32::System::DataExchange::{EmptyClipboard, SetClipboardData};
+        use windows_sys::Win32::System::Memory::{GlobalAlloc, GlobalFree, GMEM_MOVEABLE, GMEM_ZEROINIT};
+        use windows_sys::Win32::UI::Shell::DROPFILES;
+
+        // DROPFILES 之后是以 \0 分隔、以双 \0 结尾的宽字符路径列表
+        let mut file_list: Vec<u16> = Vec::new();
+        for path in paths {
+            file_list.extend(OsStr::new(path).encode_wide());
+            file_list.push(0);
+        }
+        file_list.push(0);
 
-                // 如果文件已存在（说明是重复的图片），直接返回路径
-                if file_path.exists() {
-                    GlobalUnlock(h_data as *mut std::ffi::c_void);
-                    CloseClipboard();
-                    return Ok(file_path.to_string_lossy().to_string());
-                }
+        let header_size = std::mem::size_of::<DROPFILES>();
+        let total_size = header_size + file_list.len() * 2;
 
-                // 保存为 PNG
-                let save_result = save_png(&file_path, &rgba_data, width as u32, height as u32);
+        unsafe {
+            if OpenClipboard(0 as HWND) == 0 {
+                return Err("Clipboard is busy or unavailable".to_string());
+            }
+            EmptyClipboard();
 
-                GlobalUnlock(h_data as *mut std::ffi::c_void);
-                
-                match save_result {
-                    Ok(_) => Ok(file_path.to_string_lossy().to_string()),
-                    Err(e) => Err(format!("Failed to save PNG: {}", e)),
-                }
+            let h_mem = GlobalAlloc(GMEM_MOVEABLE | GMEM_ZEROINIT, total_size);
+            if h_mem.is_null() {
+                CloseClipboard();
+                return Err("Failed to allocate clipboard memory".to_string());
+            }
+            let p_mem = GlobalLock(h_mem);
+            if p_mem.is_null() {
+                GlobalFree(h_mem);
+                CloseClipboard();
+                return Err("Failed to lock clipboard memory".to_string());
+            }
+            *(p_mem as *mut DROPFILES) = DROPFILES {
+                pFiles: header_size as u32,
+                pt: POINT { x: 0, y: 0 },
+                fNC: 0,
+                fWide: 1,
+            };
+            std::ptr::copy_nonoverlapping(
+                file_list.as_ptr(),
+                (p_mem as *mut u8).add(header_size) as *mut u16,
+                file_list.len(),
+            );
+            GlobalUnlock(h_mem);
+
+            if SetClipboardData(CF_HDROP, h_mem as isize) == 0 {
+                GlobalFree(h_mem);
+                CloseClipboard();
+                return Err("Failed to set clipboard data".to_string());
+            }
+            CloseClipboard();
+            Ok(())
+        }
+    }
+
+    /// 获取剪切板图片并保存到本地
+    pub fn get_clipboard_image(app_data_dir: &PathBuf) -> Result<String, String> {
+        unsafe {
+            // 剪贴板可能被其他程序短暂占用，退避重试后仍失败则返回错误
+            if !open_clipboard_with_retry() {
+                return Err("Clipboard is busy or unavailable".to_string());
+            }
+
+            // 优先读取 CF_DIB（系统会从 CF_BITMAP 自动合成），不可用时直接读取 CF_BITMAP
+            let decoded = if IsClipboardFormatAvailable(CF_DIB) != 0 {
+                read_dib()
+            } else if IsClipboardFormatAvailable(CF_BITMAP) != 0 {
+                read_bitmap()
             } else {
                 Err("No image in clipboard".to_string())
             };
-
             CloseClipboard();
-            result
+
+            let (width, height, rgba_data) = decoded?;
+            save_clipboard_image(app_data_dir, width, height, &rgba_data)
         }
     }
 
-    /// 保存图片为 PNG 格式
-    fn save_png(path: &std::path::Path, data: &[u8], width: u32, height: u32) -> Result<(), String> {
-        use std::fs::File;
-        use std::io::BufWriter;
-        
-        let file = File::create(path)
-            .map_err(|e| format!("Failed to create file: {}", e))?;
-        let writer = BufWriter::new(file);
+    /// 读取 CF_DIB 数据并解码为 RGBA，调用前需已打开剪贴板
+    unsafe fn read_dib() -> Result<(u32, u32, Vec<u8>), String> {
+        let h_data = GetClipboardData(CF_DIB);
+        if h_data == 0 {
+            return Err("Failed to get clipboard DIB data".to_string());
+        }
+
+        let p_data = GlobalLock(h_data as *mut std::ffi::c_void);
+        if p_data.is_null() {
+            return Err("Failed to lock clipboard data".to_string());
+        }
+
+        let data_size = GlobalSize(h_data as *mut std::ffi::c_void);
+        let decoded = if data_size == 0 {
+            Err("Invalid clipboard data size".to_string())
+        } else {
+            let data = std::slice::from_raw_parts(p_data as *const u8, data_size);
+            decode_dib(data).ok_or_else(|| "Unsupported clipboard DIB format".to_string())
+        };
 
-        let mut encoder = png::Encoder::new(writer, width, height);
-        encoder.set_color(png::ColorType::Rgba);
-        encoder.set_depth(png::BitDepth::Eight);
+        GlobalUnlock(h_data as *mut std::ffi::c_void);
+        decoded
+    }
 
-        let mut writer = encoder.write_header()
-            .map_err(|e| format!("Failed to write PNG header: {}", e))?;
+    /// 通过 GetDIBits 读取 CF_BITMAP 为 32 位自上而下的 RGBA，调用前需已打开剪贴板
+    unsafe fn read_bitmap() -> Result<(u32, u32, Vec<u8>), String> {
+        let h_bitmap = GetClipboardData(CF_BITMAP);
+        if h_bitmap == 0 {
+            return Err("Failed to get clipboard bitmap".to_string());
+        }
 
-        writer.write_image_data(data)
-            .map_err(|e| format!("Failed to write PNG data: {}", e))?;
+        let screen_dc = GetDC(0);
+        let mut bmi: BITMAPINFO = std::mem::zeroed();
+        bmi.bmiHeader.biSize = std::mem::size_of::<BITMAPINFOHEADER>() as u32;
 
-        Ok(())
+        // 第一次调用只填充尺寸信息
+        if GetDIBits(screen_dc, h_bitmap, 0, 0, ptr::null_mut(), &mut bmi, DIB_RGB_COLORS) == 0 {
+            ReleaseDC(0, screen_dc);
+            return Err("Failed to query clipboard bitmap size".to_string());
+        }
+
+        let width = bmi.bmiHeader.biWidth.unsigned_abs();
+        let height = bmi.bmiHeader.biHeight.unsigned_abs();
+        bmi.bmiHeader.biBitCount = 32;
+        bmi.bmiHeader.biCompression = BI_RGB;
+        bmi.bmiHeader.biHeight = -(height as i32); // 自上而下
+
+        let mut pixels = vec![0u8; width as usize * height as usize * 4];
+        let lines = GetDIBits(
+            screen_dc,
+            h_bitmap,
+            0,
+            height,
+            pixels.as_mut_ptr() as *mut std::ffi::c_void,
+            &mut bmi,
+            DIB_RGB_COLORS,
+        );
+        ReleaseDC(0, screen_dc);
+        if lines == 0 {
+            return Err("Failed to read clipboard bitmap".to_string());
+        }
+
+        // DDB 没有有效的 alpha 通道
+        for pixel in pixels.chunks_exact_mut(4) {
+            pixel.swap(0, 2);
+            pixel[3] = 255;
+        }
+        Ok((width, height, pixels))
+    }
+
+    /// 按图片存储设置（格式、质量、分辨率上限）保存，相同图片复用已有文件，返回文件路径
+    fn save_clipboard_image(app_data_dir: &PathBuf, width: u32, height: u32, rgba_data: &[u8]) -> Result<String, String> {
+        let image_settings = settings::load_settings(app_data_dir)
+            .map(|s| s.clipboard_image)
+            .unwrap_or_default();
+        crate::clipboard_image::save_image(app_data_dir, width, height, rgba_data, &image_settings)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_data_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("refast-clipboard-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn insert_item(app_data_dir: &PathBuf, id: &str, content: &str, created_at: u64) {
+        let (id, content) = (id.to_string(), content.to_string());
+        db::execute_write(app_data_dir, move |conn| {
+            conn.execute(
+                "INSERT INTO clipboard_history (id, content, content_type, created_at, is_favorite)
+                 VALUES (?1, ?2, 'text', ?3, 0)",
+                params![id, content, created_at as i64],
+            )
+            .map_err(|e| e.to_string())?;
+            Ok(())
+        })
+        .unwrap();
+    }
+
+    #[test]
+    fn test_items_added_in_the_same_second_get_distinct_ids() {
+        let dir = test_data_dir("same-second");
+        let first = add_clipboard_item("alpha".to_string(), "text".to_string(), &dir).unwrap();
+        let second = add_clipboard_item("beta".to_string(), "text".to_string(), &dir).unwrap();
+        assert_ne!(first.id, second.id);
+        assert_eq!(get_clipboard_item(&first.id, &dir).unwrap().content, "alpha");
+        assert_eq!(get_clipboard_item(&second.id, &dir).unwrap().content, "beta");
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_board_changes_are_persisted() {
+        let dir = test_data_dir("boards");
+        insert_item(&dir, "a", "alpha", 1);
+        insert_item(&dir, "b", "beta", 2);
+        let board = create_board("Snippets", &dir).unwrap();
+
+        move_item_to_board("a".to_string(), Some(board.id.clone()), None, &dir).unwrap();
+        move_item_to_board("b".to_string(), Some(board.id.clone()), Some(0), &dir).unwrap();
+        let ids = |dir: &PathBuf| -> Vec<String> {
+            get_board_items(&board.id, dir).unwrap().into_iter().map(|item| item.id).collect()
+        };
+        assert_eq!(ids(&dir), vec!["b", "a"]);
+        assert!(get_board_items(&board.id, &dir).unwrap().iter().all(|item| item.is_favorite));
+
+        reorder_board_items(board.id.clone(), vec!["a".to_string()], &dir).unwrap();
+        assert_eq!(ids(&dir), vec!["a", "b"]);
+
+        reorder_boards(vec![board.id.clone()], &dir).unwrap();
+        assert_eq!(list_boards(&dir).unwrap()[0].item_count, 2);
+
+        move_item_to_board("a".to_string(), None, None, &dir).unwrap();
+        assert_eq!(ids(&dir), vec!["b"]);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_backfill_content_hashes() {
+        let dir = test_data_dir("backfill");
+        insert_item(&dir, "a", "alpha", 1);
+        assert_eq!(backfill_content_hashes(&dir).unwrap(), 1);
+
+        let conn = db::get_readonly_connection(&dir).unwrap();
+        let hash: Option<String> = conn
+            .query_row("SELECT content_hash FROM clipboard_history WHERE id = 'a'", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(hash, Some(content_hash("alpha", "text").unwrap()));
+        drop(conn);
+        assert_eq!(backfill_content_hashes(&dir).unwrap(), 0);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_keyed_content_hash_differs_from_plain_hash() {
+        let key = [7u8; 32];
+        let plain = content_hash_with(None, "alpha", "text");
+        let keyed = content_hash_with(Some(&key), "alpha", "text");
+        assert_eq!(plain.len(), 64);
+        assert_eq!(keyed.len(), 64);
+        assert_ne!(plain, keyed);
+        assert_eq!(keyed, content_hash_with(Some(&key), "alpha", "text"));
+        assert_ne!(keyed, content_hash_with(Some(&[8u8; 32]), "alpha", "text"));
+    }
+
+    #[test]
+    fn test_batch_operations_through_writer() {
+        let dir = test_data_dir("batch");
+        insert_item(&dir, "a", "alpha", 1);
+        insert_item(&dir, "b", "beta", 2);
+        insert_item(&dir, "c", "gamma", 3);
+
+        let ids = vec!["a".to_string(), "b".to_string(), "missing".to_string()];
+        assert_eq!(batch_set_favorite(ids.clone(), true, &dir).unwrap(), 2);
+        assert!(get_clipboard_item("a", &dir).unwrap().is_favorite);
+        assert!(!get_clipboard_item("c", &dir).unwrap().is_favorite);
+
+        assert_eq!(batch_add_tag(ids.clone(), "work", &dir).unwrap(), 2);
+        assert_eq!(batch_add_tag(ids.clone(), "work", &dir).unwrap(), 0);
+        assert_eq!(get_clipboard_item("b", &dir).unwrap().tags, vec!["work".to_string()]);
+
+        assert_eq!(batch_delete_items(ids, &dir).unwrap(), 2);
+        assert!(get_clipboard_item("a", &dir).is_err());
+        assert!(get_clipboard_item("b", &dir).is_err());
+        assert!(get_clipboard_item("c", &dir).is_ok());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_clear_except_recent_keeps_newest() {
+        let dir = test_data_dir("clear-recent");
+        insert_item(&dir, "a", "alpha", 1);
+        insert_item(&dir, "b", "beta", 2);
+        insert_item(&dir, "c", "gamma", 3);
+
+        assert_eq!(clear_except_recent(1, &dir).unwrap(), 2);
+        assert!(get_clipboard_item("a", &dir).is_err());
+        assert!(get_clipboard_item("b", &dir).is_err());
+        assert!(get_clipboard_item("c", &dir).is_ok());
+
+        let _ = std::fs::remove_dir_all(&dir);
     }
 }
@@ -0,0 +1,108 @@
+//! 剪贴板条目自定义动作：内容匹配正则时调用外部程序，条目内容作为参数或 STDIN 传入
+
+use crate::settings::ClipboardAction;
+use regex::{Captures, Regex};
+use serde::Serialize;
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::sync::LazyLock;
+
+static PLACEHOLDER: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\{(content|match|[1-9])\}").unwrap());
+
+/// 右键菜单中展示的动作
+#[derive(Debug, Clone, Serialize)]
+pub struct MatchedAction {
+    pub id: String,
+    pub name: String,
+    /// 正则匹配到的文本
+    pub matched: String,
+}
+
+fn compile(action: &ClipboardAction) -> Option<Regex> {
+    match Regex::new(&action.pattern) {
+        Ok(re) => Some(re),
+        Err(e) => {
+            eprintln!(
+                "[ClipboardActions] Invalid pattern for action {}: {}",
+                action.name, e
+            );
+            None
+        }
+    }
+}
+
+/// 返回与条目内容匹配的已启用动作
+pub fn matching_actions(content: &str, actions: &[ClipboardAction]) -> Vec<MatchedAction> {
+    actions
+        .iter()
+        .filter(|action| action.enabled)
+        .filter_map(|action| {
+            let matched = compile(action)?.find(content)?;
+            Some(MatchedAction {
+                id: action.id.clone(),
+                name: action.name.clone(),
+                matched: matched.as_str().to_string(),
+            })
+        })
+        .collect()
+}
+
+fn expand_arg(arg: &str, content: &str, caps: &Captures) -> String {
+    PLACEHOLDER
+        .replace_all(arg, |placeholder: &Captures| match &placeholder[1] {
+            "content" => content.to_string(),
+            "match" => caps[0].to_string(),
+            group => group
+                .parse::<usize>()
+                .ok()
+                .and_then(|i| caps.get(i))
+                .map(|m| m.as_str().to_string())
+                .unwrap_or_default(),
+        })
+        .into_owned()
+}
+
+/// 对条目内容执行动作，不等待外部程序结束
+pub fn run_action(action: &ClipboardAction, content: &str) -> Result<(), String> {
+    let re = compile(action).ok_or_else(|| format!("Invalid pattern: {}", action.pattern))?;
+    let caps = re
+        .captures(content)
+        .ok_or_else(|| format!("Content does not match action {}", action.name))?;
+    let args: Vec<String> = action
+        .args
+        .iter()
+        .map(|arg| expand_arg(arg, content, &caps))
+        .collect();
+
+    let mut command = Command::new(&action.program);
+    command.args(&args);
+    if action.use_stdin {
+        command.stdin(Stdio::piped());
+    }
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        command.creation_flags(0x08000000); // CREATE_NO_WINDOW
+    }
+
+    let mut child = command
+        .spawn()
+        .map_err(|e| format!("Failed to start {}: {}", action.program, e))?;
+    if action.use_stdin {
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin
+                .write_all(content.as_bytes())
+                .map_err(|e| format!("Failed to write action stdin: {}", e))?;
+        }
+    }
+    // 在后台回收子进程，写入的 STDIN 已随 drop 关闭
+    std::thread::spawn(move || {
+        let _ = child.wait();
+    });
+    eprintln!(
+        "[ClipboardActions] Started action {} ({} {:?})",
+        action.name, action.program, args
+    );
+    Ok(())
+}
@@ -0,0 +1,61 @@
+//! 剪贴板敏感词告警
+//!
+//! 按用户配置的敏感词 / 正则（如内部项目代号）检查新捕获的文本与文件路径，
+//! 命中时弹出提醒，可选不写入历史。规则只保存在本地设置中。
+
+use crate::settings::{ClipboardAlertRule, ClipboardAlertSettings};
+use regex::Regex;
+
+/// 返回第一条命中的规则；敏感词忽略大小写，无效的正则会被跳过
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+pub fn matched_rule<'a>(
+    text: &str,
+    settings: &'a ClipboardAlertSettings,
+) -> Option<&'a ClipboardAlertRule> {
+    if !settings.enabled || text.is_empty() {
+        return None;
+    }
+    let lower = text.to_lowercase();
+    settings.rules.iter().find(|rule| {
+        let pattern = rule.pattern.trim();
+        if pattern.is_empty() {
+            return false;
+        }
+        if rule.regex {
+            match Regex::new(pattern) {
+                Ok(re) => re.is_match(text),
+                Err(e) => {
+                    eprintln!("[ClipboardAlert] Invalid pattern {:?}: {}", pattern, e);
+                    false
+                }
+            }
+        } else {
+            lower.contains(&pattern.to_lowercase())
+        }
+    })
+}
+
+/// 检查捕获的内容，命中时弹出提醒；返回 true 表示不应写入历史
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+pub fn check(app: &tauri::AppHandle, content: &str, settings: &ClipboardAlertSettings) -> bool {
+    let Some(rule) = matched_rule(content, settings) else {
+        return false;
+    };
+    eprintln!(
+        "[ClipboardAlert] Clipboard content matched rule {:?}{}",
+        rule.pattern,
+        if settings.block_storage {
+            ", not saved"
+        } else {
+            ""
+        }
+    );
+    // 提醒中只显示规则，不回显内容本身
+    let body = if settings.block_storage {
+        format!("命中规则「{}」，该内容未记录到剪贴板历史", rule.pattern)
+    } else {
+        format!("命中规则「{}」", rule.pattern)
+    };
+    let _ = crate::toast::show_toast(app, "你复制了敏感内容", &body);
+    settings.block_storage
+}
@@ -0,0 +1,412 @@
+//! 剪贴板历史的导出与导入
+//!
+//! 导出为 JSON 归档（路径以 .gz 结尾时使用 gzip 压缩），包含收藏状态、标签、HTML / RTF 格式与来源；
+//! 图片条目以 base64 原始图片文件内嵌，外部存储的大文本导出完整内容。导入时按策略处理与现有条目的冲突。
+
+use crate::clipboard::{self, TAG_SEPARATOR};
+use crate::clipboard_crypto;
+use crate::db;
+use crate::settings;
+use base64::{engine::general_purpose, Engine as _};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use rusqlite::{params, params_from_iter, Connection};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+/// 当前归档格式版本
+const ARCHIVE_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchiveItem {
+    pub id: String,
+    /// 完整文本；图片条目为原文件名
+    pub content: String,
+    pub content_type: String,
+    pub created_at: u64,
+    #[serde(default)]
+    pub is_favorite: bool,
+    #[serde(default)]
+    pub truncated: bool,
+    #[serde(default)]
+    pub html: Option<String>,
+    #[serde(default)]
+    pub rtf: Option<String>,
+    #[serde(default)]
+    pub source_app: Option<String>,
+    #[serde(default)]
+    pub source_title: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// 图片条目的文件数据（base64）
+    #[serde(default)]
+    pub image_data: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClipboardArchive {
+    #[serde(default)]
+    pub version: u32,
+    #[serde(default)]
+    pub exported_at: u64,
+    pub items: Vec<ArchiveItem>,
+}
+
+/// 导入条目与现有条目内容相同时的处理方式
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportConflict {
+    /// 保留现有条目，跳过导入项
+    #[default]
+    Skip,
+    /// 合并到现有条目：收藏取并集、合并标签、时间取较新者
+    Merge,
+    /// 同时保留两条
+    KeepBoth,
+}
+
+#[derive(Debug, Clone, Serialize, Default)]
+pub struct ImportResult {
+    pub imported: usize,
+    pub merged: usize,
+    pub skipped: usize,
+    /// 无法导入的条目（如图片数据损坏）
+    pub failed: Vec<String>,
+}
+
+fn is_gzip_path(path: &Path) -> bool {
+    path.extension()
+        .map(|e| e.eq_ignore_ascii_case("gz"))
+        .unwrap_or(false)
+}
+
+/// 导出剪贴板历史，ids 为空时导出全部，返回导出的条目数
+pub fn export_history(
+    ids: Option<Vec<String>>,
+    path: &Path,
+    app_data_dir: &PathBuf,
+) -> Result<usize, String> {
+    let archive = build_archive(ids, app_data_dir)?;
+    let data = encode_archive(&archive, is_gzip_path(path))?;
+    std::fs::write(path, data).map_err(|e| format!("Failed to write clipboard archive: {}", e))?;
+    Ok(archive.items.len())
+}
+
+/// 读取条目生成归档（文本为明文完整内容），ids 为空时包含全部条目
+pub fn build_archive(
+    ids: Option<Vec<String>>,
+    app_data_dir: &PathBuf,
+) -> Result<ClipboardArchive, String> {
+    let conn = db::get_readonly_connection(app_data_dir)?;
+    let mut sql = String::from(
+        "SELECT id, content, content_type, created_at, is_favorite, truncated, html_content, rtf_content, content_file, source_app, source_title,
+                (SELECT group_concat(tag, char(31)) FROM clipboard_tags WHERE item_id = clipboard_history.id)
+         FROM clipboard_history",
+    );
+    let ids = ids.filter(|ids| !ids.is_empty());
+    if let Some(ids) = &ids {
+        sql.push_str(&format!(" WHERE id IN ({})", vec!["?"; ids.len()].join(", ")));
+    }
+    sql.push_str(" ORDER BY created_at ASC");
+
+    let mut stmt = conn
+        .prepare(&sql)
+        .map_err(|e| format!("Failed to prepare export query: {}", e))?;
+    let rows = stmt
+        .query_map(params_from_iter(ids.iter().flatten()), |row| {
+            Ok((
+                ArchiveItem {
+                    id: row.get(0)?,
+                    content: row.get(1)?,
+                    content_type: row.get(2)?,
+                    created_at: row.get::<_, i64>(3)? as u64,
+                    is_favorite: row.get::<_, i64>(4)? != 0,
+                    truncated: row.get::<_, i64>(5)? != 0,
+                    html: row.get(6)?,
+                    rtf: row.get(7)?,
+                    source_app: row.get(9)?,
+                    source_title: row.get(10)?,
+                    tags: row
+                        .get::<_, Option<String>>(11)?
+                        .map(|tags| tags.split(TAG_SEPARATOR).map(str::to_string).collect())
+                        .unwrap_or_default(),
+                    image_data: None,
+                },
+                row.get::<_, Option<String>>(8)?,
+            ))
+        })
+        .map_err(|e| format!("Failed to query clipboard items: {}", e))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to read clipboard items: {}", e))?;
+    drop(stmt);
+
+    let mut items = Vec::with_capacity(rows.len());
+    for (mut item, content_file) in rows {
+        if item.content_type == "image" {
+            // 图片文件已丢失的条目无法恢复，直接跳过
+            let Ok(bytes) = std::fs::read(&item.content) else {
+                eprintln!("[ClipboardArchive] Skipped missing image: {}", item.content);
+                continue;
+            };
+            item.image_data = Some(general_purpose::STANDARD.encode(bytes));
+            item.content = Path::new(&item.content)
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default();
+        } else {
+            item.content = clipboard::load_content(item.content, content_file)?;
+        }
+        item.html = clipboard_crypto::open_opt(item.html)?;
+        item.rtf = clipboard_crypto::open_opt(item.rtf)?;
+        items.push(item);
+    }
+
+    Ok(ClipboardArchive {
+        version: ARCHIVE_VERSION,
+        exported_at: chrono::Utc::now().timestamp() as u64,
+        items,
+    })
+}
+
+/// 序列化归档，gzip 为 true 时压缩
+pub fn encode_archive(archive: &ClipboardArchive, gzip: bool) -> Result<Vec<u8>, String> {
+    let json = serde_json::to_vec_pretty(archive)
+        .map_err(|e| format!("Failed to serialize clipboard archive: {}", e))?;
+    if !gzip {
+        return Ok(json);
+    }
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder
+        .write_all(&json)
+        .and_then(|_| encoder.finish())
+        .map_err(|e| format!("Failed to compress clipboard archive: {}", e))
+}
+
+fn read_archive(path: &Path) -> Result<ClipboardArchive, String> {
+    let bytes = std::fs::read(path).map_err(|e| format!("Failed to read clipboard archive: {}", e))?;
+    decode_archive(&bytes)
+}
+
+/// 解析归档数据，按 gzip 魔数识别压缩归档，不依赖扩展名
+pub fn decode_archive(bytes: &[u8]) -> Result<ClipboardArchive, String> {
+    let json = if bytes.starts_with(&[0x1f, 0x8b]) {
+        let mut json = Vec::new();
+        GzDecoder::new(bytes)
+            .read_to_end(&mut json)
+            .map_err(|e| format!("Failed to decompress clipboard archive: {}", e))?;
+        json
+    } else {
+        bytes.to_vec()
+    };
+    let archive: ClipboardArchive =
+        serde_json::from_slice(&json).map_err(|e| format!("Invalid clipboard archive: {}", e))?;
+    if archive.version > ARCHIVE_VERSION {
+        return Err(format!("Unsupported clipboard archive version: {}", archive.version));
+    }
+    Ok(archive)
+}
+
+/// 写入导入的图片，按文件内容哈希命名并保留原扩展名，相同图片复用已有文件
+fn restore_image(data: &str, file_name: &str, app_data_dir: &Path) -> Result<String, String> {
+    let bytes = general_purpose::STANDARD
+        .decode(data.trim())
+        .map_err(|e| format!("Invalid image data: {}", e))?;
+    let images_dir = app_data_dir.join("clipboard_images");
+    std::fs::create_dir_all(&images_dir)
+        .map_err(|e| format!("Failed to create clipboard images directory: {}", e))?;
+    let hash = format!("{:x}", Sha256::digest(&bytes));
+    let extension = Path::new(file_name)
+        .extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .filter(|e| matches!(e.as_str(), "png" | "jpg" | "jpeg" | "webp"))
+        .unwrap_or_else(|| "png".to_string());
+    let file_path = images_dir.join(format!("clipboard_import_{}.{}", &hash[..16], extension));
+    if !file_path.exists() {
+        std::fs::write(&file_path, &bytes).map_err(|e| format!("Failed to write image: {}", e))?;
+    }
+    Ok(file_path.to_string_lossy().to_string())
+}
+
+/// 导入前已写好文件的条目：content 为入库内容（图片路径或大文本预览）
+struct PreparedItem {
+    item: ArchiveItem,
+    content_file: Option<String>,
+    content_hash: String,
+}
+
+fn find_existing(conn: &Connection, item: &PreparedItem) -> Result<Option<String>, String> {
+    clipboard::find_duplicate(
+        conn,
+        &item.content_hash,
+        &item.item.content,
+        &item.item.content_type,
+        item.content_file.as_deref(),
+    )
+}
+
+fn insert_tags(conn: &Connection, id: &str, tags: &[String]) -> Result<(), String> {
+    for tag in tags {
+        let Ok(tag) = clipboard::normalize_tag(tag) else {
+            continue;
+        };
+        conn.execute(
+            "INSERT OR IGNORE INTO clipboard_tags (item_id, tag) VALUES (?1, ?2)",
+            params![id, tag],
+        )
+        .map_err(|e| format!("Failed to import clipboard tag: {}", e))?;
+    }
+    Ok(())
+}
+
+/// 为导入项分配 ID：原 ID 未被占用时沿用，否则追加序号
+fn available_id(conn: &Connection, id: &str) -> Result<String, String> {
+    let taken = |candidate: &str| -> Result<bool, String> {
+        conn.query_row(
+            "SELECT COUNT(*) FROM clipboard_history WHERE id = ?1",
+            params![candidate],
+            |row| row.get::<_, i64>(0),
+        )
+        .map(|count| count > 0)
+        .map_err(|e| format!("Failed to check clipboard id: {}", e))
+    };
+    if !id.is_empty() && !taken(id)? {
+        return Ok(id.to_string());
+    }
+    let base = if id.is_empty() { "clipboard-import" } else { id };
+    let mut suffix = 1;
+    loop {
+        let candidate = format!("{}-{}", base, suffix);
+        if !taken(&candidate)? {
+            return Ok(candidate);
+        }
+        suffix += 1;
+    }
+}
+
+/// 从归档导入剪贴板历史
+pub fn import_history(
+    path: &Path,
+    conflict: ImportConflict,
+    app_data_dir: &PathBuf,
+) -> Result<ImportResult, String> {
+    import_archive(read_archive(path)?, conflict, app_data_dir)
+}
+
+/// 导入已解析的归档
+pub fn import_archive(
+    archive: ClipboardArchive,
+    conflict: ImportConflict,
+    app_data_dir: &PathBuf,
+) -> Result<ImportResult, String> {
+    let threshold_kb = settings::load_settings(app_data_dir)
+        .map(|s| s.clipboard_external_threshold_kb)
+        .unwrap_or_default();
+
+    // 先在写线程外落盘图片与大文本，写任务内只做数据库操作
+    let mut result = ImportResult::default();
+    let mut prepared = Vec::with_capacity(archive.items.len());
+    for mut item in archive.items {
+        let content_hash;
+        let content_file = if item.content_type == "image" {
+            match item.image_data.take().map(|data| restore_image(&data, &item.content, app_data_dir)) {
+                Some(Ok(image_path)) => {
+                    content_hash = clipboard::content_hash(&image_path, &item.content_type)?;
+                    item.content = image_path;
+                    None
+                }
+                Some(Err(e)) => {
+                    result.failed.push(format!("{}: {}", item.id, e));
+                    continue;
+                }
+                None => {
+                    result.failed.push(format!("{}: missing image data", item.id));
+                    continue;
+                }
+            }
+        } else {
+            content_hash = clipboard::content_hash(&item.content, &item.content_type)?;
+            let (content, content_file) =
+                clipboard::store_external_content(item.content, &item.content_type, threshold_kb, app_data_dir)?;
+            item.content = clipboard_crypto::seal(content)?;
+            content_file
+        };
+        item.html = clipboard_crypto::seal_opt(item.html)?;
+        item.rtf = clipboard_crypto::seal_opt(item.rtf)?;
+        prepared.push(PreparedItem {
+            item,
+            content_file,
+            content_hash,
+        });
+    }
+
+    let result = db::execute_write(app_data_dir, move |conn| {
+        for entry in &prepared {
+            let item = &entry.item;
+            let existing = match conflict {
+                ImportConflict::KeepBoth => None,
+                _ => find_existing(conn, entry)?,
+            };
+
+            if let Some(existing_id) = existing {
+                if conflict == ImportConflict::Skip {
+                    result.skipped += 1;
+                    continue;
+                }
+                conn.execute(
+                    "UPDATE clipboard_history SET
+                        created_at = MAX(created_at, ?1),
+                        is_favorite = MAX(is_favorite, ?2),
+                        html_content = COALESCE(html_content, ?3),
+                        rtf_content = COALESCE(rtf_content, ?4),
+                        source_app = COALESCE(source_app, ?5),
+                        source_title = COALESCE(source_title, ?6)
+                     WHERE id = ?7",
+                    params![
+                        item.created_at as i64,
+                        item.is_favorite as i64,
+                        item.html,
+                        item.rtf,
+                        item.source_app,
+                        item.source_title,
+                        existing_id
+                    ],
+                )
+                .map_err(|e| format!("Failed to merge clipboard item: {}", e))?;
+                insert_tags(conn, &existing_id, &item.tags)?;
+                result.merged += 1;
+                continue;
+            }
+
+            let id = available_id(conn, &item.id)?;
+            conn.execute(
+                "INSERT INTO clipboard_history (id, content, content_type, created_at, is_favorite, truncated, html_content, rtf_content, content_file, source_app, source_title, content_hash)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+                params![
+                    id,
+                    item.content,
+                    item.content_type,
+                    item.created_at as i64,
+                    item.is_favorite as i64,
+                    item.truncated as i64,
+                    item.html,
+                    item.rtf,
+                    entry.content_file,
+                    item.source_app,
+                    item.source_title,
+                    entry.content_hash
+                ],
+            )
+            .map_err(|e| format!("Failed to import clipboard item: {}", e))?;
+            insert_tags(conn, &id, &item.tags)?;
+            result.imported += 1;
+        }
+        Ok(result)
+    })?;
+
+    // 导入的图片由后台任务识别文字
+    crate::clipboard_ocr::request_ocr();
+    Ok(result)
+}
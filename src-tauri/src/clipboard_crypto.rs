@@ -0,0 +1,654 @@
+//! 剪贴板历史的静态加密（可选）
+//!
+//! 启用后随机生成 256 位数据密钥，经 Windows DPAPI 保护（可附加用户口令作为熵）后写入
+//! clipboard_key.json；文本条目的 content / html_content / rtf_content、图片的 ocr_text 与外部存储文件以
+//! AES-256-GCM 加密，密文格式为 `enc1:` + base64(nonce || 密文 || tag)。
+//!
+//! nonce 由内容的 HMAC-SHA256 派生，相同明文得到相同密文，数据库中按内容去重仍然有效
+//! （代价是可以看出两条记录是否相同）。图片条目的 content 是文件路径，不加密。
+//! 加密后无法在 SQL 中匹配正文，搜索会解密后在内存中过滤。
+
+use crate::db;
+use base64::{engine::general_purpose, Engine as _};
+use rusqlite::params;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+/// 密文前缀，区分加密值与明文（未迁移的旧数据）
+const SEALED_PREFIX: &str = "enc1:";
+
+/// 同步数据的密文前缀，格式为 `encsync1:` + base64(salt) + `:` + `enc1:` 密文
+const SYNC_SEALED_PREFIX: &str = "encsync1:";
+
+/// 未解锁时展示的占位文本
+pub const LOCKED_PLACEHOLDER: &str = "🔒 已加密（请先解锁剪贴板历史）";
+
+const KEY_FILE: &str = "clipboard_key.json";
+const KEY_FILE_VERSION: u32 = 1;
+const NONCE_LEN: usize = 12;
+const TAG_LEN: usize = 16;
+/// 口令派生熵时的迭代次数
+const PASSPHRASE_ROUNDS: u32 = 100_000;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static KEY: Mutex<Option<[u8; 32]>> = Mutex::new(None);
+
+#[derive(Serialize, Deserialize)]
+struct KeyFile {
+    version: u32,
+    /// 解锁是否需要口令
+    passphrase: bool,
+    salt: String,
+    /// DPAPI 保护后的数据密钥
+    wrapped_key: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct EncryptionStatus {
+    pub enabled: bool,
+    pub passphrase_protected: bool,
+    pub unlocked: bool,
+}
+
+fn key_file_path(app_data_dir: &PathBuf) -> PathBuf {
+    app_data_dir.join(KEY_FILE)
+}
+
+fn read_key_file(app_data_dir: &PathBuf) -> Result<Option<KeyFile>, String> {
+    let path = key_file_path(app_data_dir);
+    if !path.exists() {
+        return Ok(None);
+    }
+    let data = std::fs::read_to_string(&path).map_err(|e| format!("Failed to read clipboard key: {}", e))?;
+    serde_json::from_str(&data)
+        .map(Some)
+        .map_err(|e| format!("Failed to parse clipboard key: {}", e))
+}
+
+fn current_key() -> Option<[u8; 32]> {
+    *KEY.lock().unwrap()
+}
+
+/// 是否已启用加密（新写入的内容会被加密）
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::SeqCst)
+}
+
+pub fn is_sealed(value: &str) -> bool {
+    value.starts_with(SEALED_PREFIX)
+}
+
+/// 启动时加载密钥：未设置口令时直接用 DPAPI 解开，设置了口令则保持锁定直到 unlock
+pub fn init(app_data_dir: &PathBuf) {
+    match read_key_file(app_data_dir) {
+        Ok(Some(key_file)) => {
+            ENABLED.store(true, Ordering::SeqCst);
+            if !key_file.passphrase {
+                match unwrap_key(&key_file, None) {
+                    Ok(key) => *KEY.lock().unwrap() = Some(key),
+                    Err(e) => eprintln!("[ClipboardCrypto] Failed to unlock clipboard key: {}", e),
+                }
+            }
+        }
+        Ok(None) => {}
+        Err(e) => eprintln!("[ClipboardCrypto] {}", e),
+    }
+}
+
+pub fn status(app_data_dir: &PathBuf) -> Result<EncryptionStatus, String> {
+    let key_file = read_key_file(app_data_dir)?;
+    Ok(EncryptionStatus {
+        enabled: key_file.is_some(),
+        passphrase_protected: key_file.map(|k| k.passphrase).unwrap_or(false),
+        unlocked: current_key().is_some(),
+    })
+}
+
+/// 加密单个值；未启用时原样返回，已是密文时不重复加密
+pub fn seal(value: String) -> Result<String, String> {
+    if !is_enabled() || is_sealed(&value) {
+        return Ok(value);
+    }
+    let key = current_key().ok_or_else(|| "剪贴板历史已加密，请先输入口令解锁".to_string())?;
+    seal_with(&key, &value)
+}
+
+pub fn seal_opt(value: Option<String>) -> Result<Option<String>, String> {
+    value.map(seal).transpose()
+}
+
+/// 加密条目正文，图片条目保存的是文件路径，不加密
+pub fn seal_content(content: String, content_type: &str) -> Result<String, String> {
+    if content_type == "image" {
+        Ok(content)
+    } else {
+        seal(content)
+    }
+}
+
+/// 内容去重哈希使用的密钥：未启用加密时为 None（普通 SHA-256）；启用后由数据密钥派生，
+/// 避免明文哈希泄露已加密的内容。未解锁时无法计算，返回错误
+pub fn content_hash_key() -> Result<Option<[u8; 32]>, String> {
+    if !is_enabled() {
+        return Ok(None);
+    }
+    let key = current_key().ok_or_else(|| "剪贴板历史已加密，请先输入口令解锁".to_string())?;
+    Ok(Some(derive_hash_key(&key)))
+}
+
+/// 解密单个值；明文原样返回
+pub fn open(value: String) -> Result<String, String> {
+    if !is_sealed(&value) {
+        return Ok(value);
+    }
+    let key = current_key().ok_or_else(|| "剪贴板历史已加密，请先输入口令解锁".to_string())?;
+    open_with(&key, &value)
+}
+
+pub fn open_opt(value: Option<String>) -> Result<Option<String>, String> {
+    value.map(open).transpose()
+}
+
+/// 用于列表展示的解密：无法解密时返回占位文本而不是报错
+pub fn open_or_placeholder(value: String) -> String {
+    open(value).unwrap_or_else(|_| LOCKED_PLACEHOLDER.to_string())
+}
+
+/// 用同步口令加密上传的数据。各设备的数据密钥互不相同，跨设备传输改用共享口令派生的密钥
+pub fn seal_for_sync(passphrase: &str, data: &[u8]) -> Result<Vec<u8>, String> {
+    let salt: [u8; 16] = rand::random();
+    let key = derive_entropy(passphrase, &salt);
+    let sealed = seal_with(&key, &general_purpose::STANDARD.encode(data))?;
+    Ok(format!(
+        "{}{}:{}",
+        SYNC_SEALED_PREFIX,
+        general_purpose::STANDARD.encode(salt),
+        sealed
+    )
+    .into_bytes())
+}
+
+pub fn is_sealed_for_sync(data: &[u8]) -> bool {
+    data.starts_with(SYNC_SEALED_PREFIX.as_bytes())
+}
+
+/// 用同步口令解密其他设备上传的数据
+pub fn open_from_sync(passphrase: &str, data: &[u8]) -> Result<Vec<u8>, String> {
+    let invalid = || "Invalid encrypted sync data".to_string();
+    let text = std::str::from_utf8(data).map_err(|_| invalid())?;
+    let (salt, sealed) = text
+        .strip_prefix(SYNC_SEALED_PREFIX)
+        .and_then(|rest| rest.split_once(':'))
+        .filter(|(_, sealed)| is_sealed(sealed))
+        .ok_or_else(invalid)?;
+    let salt = general_purpose::STANDARD.decode(salt).map_err(|_| invalid())?;
+    let key = derive_entropy(passphrase, &salt);
+    let encoded = open_with(&key, sealed).map_err(|_| "同步口令错误或数据已损坏".to_string())?;
+    general_purpose::STANDARD.decode(encoded).map_err(|_| invalid())
+}
+
+/// 启用加密并加密存量数据，passphrase 为空时仅依赖 DPAPI（绑定当前 Windows 用户）
+pub fn enable(passphrase: Option<&str>, app_data_dir: &PathBuf) -> Result<usize, String> {
+    if read_key_file(app_data_dir)?.is_some() {
+        return Err("剪贴板加密已启用".to_string());
+    }
+    let passphrase = passphrase.filter(|p| !p.is_empty());
+    let key: [u8; 32] = rand::random();
+    let salt: [u8; 16] = rand::random();
+    let entropy = passphrase.map(|p| derive_entropy(p, &salt));
+    let wrapped = dpapi_protect(&key, entropy.as_ref().map(|e| e.as_slice()))?;
+
+    let key_file = KeyFile {
+        version: KEY_FILE_VERSION,
+        passphrase: passphrase.is_some(),
+        salt: general_purpose::STANDARD.encode(salt),
+        wrapped_key: general_purpose::STANDARD.encode(wrapped),
+    };
+    let json = serde_json::to_string_pretty(&key_file)
+        .map_err(|e| format!("Failed to serialize clipboard key: {}", e))?;
+    // 先落盘密钥再迁移数据，迁移中途退出也不会丢失密钥；迁移失败则撤回密钥文件
+    let key_path = key_file_path(app_data_dir);
+    std::fs::write(&key_path, json).map_err(|e| format!("Failed to write clipboard key: {}", e))?;
+
+    let hash_key = derive_hash_key(&key);
+    let (count, files) = match migrate_rows(app_data_dir, Some(hash_key), move |value| seal_with(&key, &value)) {
+        Ok(result) => result,
+        Err(e) => {
+            if let Err(remove_err) = std::fs::remove_file(&key_path) {
+                eprintln!("[ClipboardCrypto] Failed to roll back clipboard key: {}", remove_err);
+            }
+            return Err(e);
+        }
+    };
+
+    // 存量数据已加密，此后新写入的内容同样加密
+    *KEY.lock().unwrap() = Some(key);
+    ENABLED.store(true, Ordering::SeqCst);
+    eprintln!("[ClipboardCrypto] Encryption enabled");
+
+    migrate_files(files, |value| seal_with(&key, &value))?;
+    compact(app_data_dir)?;
+    Ok(count)
+}
+
+/// 输入口令解锁
+pub fn unlock(passphrase: &str, app_data_dir: &PathBuf) -> Result<(), String> {
+    let key_file = read_key_file(app_data_dir)?.ok_or_else(|| "剪贴板加密未启用".to_string())?;
+    let key = unwrap_key(&key_file, Some(passphrase)).map_err(|_| "口令错误".to_string())?;
+    *KEY.lock().unwrap() = Some(key);
+    Ok(())
+}
+
+/// 关闭加密：解密存量数据后删除密钥，需要已解锁
+pub fn disable(app_data_dir: &PathBuf) -> Result<usize, String> {
+    if read_key_file(app_data_dir)?.is_none() {
+        return Err("剪贴板加密未启用".to_string());
+    }
+    let key = current_key().ok_or_else(|| "请先解锁剪贴板历史".to_string())?;
+    // 先停止加密新内容，迁移期间写入的条目保持明文
+    ENABLED.store(false, Ordering::SeqCst);
+    let convert = move |value: String| {
+        if is_sealed(&value) {
+            open_with(&key, &value)
+        } else {
+            Ok(value)
+        }
+    };
+    let (count, files) = match migrate_rows(app_data_dir, None, convert) {
+        Ok(result) => result,
+        Err(e) => {
+            ENABLED.store(true, Ordering::SeqCst);
+            return Err(e);
+        }
+    };
+    migrate_files(files, convert)?;
+    compact(app_data_dir)?;
+
+    std::fs::remove_file(key_file_path(app_data_dir))
+        .map_err(|e| format!("Failed to remove clipboard key: {}", e))?;
+    *KEY.lock().unwrap() = None;
+    eprintln!("[ClipboardCrypto] Encryption disabled");
+    Ok(count)
+}
+
+/// 用 convert 重写文本条目的正文、富文本格式与图片的 OCR 文字，并按 hash_key 重新计算内容哈希，
+/// 返回修改的条目数和外部存储文件列表
+///
+/// 写入线程的 savepoint 保证整批更新要么全部生效要么全部回滚
+fn migrate_rows<F>(
+    app_data_dir: &PathBuf,
+    hash_key: Option<[u8; 32]>,
+    convert: F,
+) -> Result<(usize, Vec<String>), String>
+where
+    F: Fn(String) -> Result<String, String> + Send + 'static,
+{
+    // 迁移前后总有一侧是明文，哈希按明文计算
+    let plain = move |value: String, converted: &String| {
+        if is_sealed(&value) {
+            converted.clone()
+        } else {
+            value
+        }
+    };
+    let (count, mut files) = db::execute_write(app_data_dir, move |conn| {
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, content, content_type, html_content, rtf_content, content_file, ocr_text, content_hash
+                 FROM clipboard_history",
+            )
+            .map_err(|e| format!("Failed to prepare clipboard query: {}", e))?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, Option<String>>(3)?,
+                    row.get::<_, Option<String>>(4)?,
+                    row.get::<_, Option<String>>(5)?,
+                    row.get::<_, Option<String>>(6)?,
+                    row.get::<_, Option<String>>(7)?,
+                ))
+            })
+            .map_err(|e| format!("Failed to query clipboard items: {}", e))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Failed to read clipboard items: {}", e))?;
+        drop(stmt);
+
+        let mut count = 0;
+        let mut files = Vec::new();
+        for (id, content, content_type, html, rtf, content_file, ocr_text, hash) in rows {
+            // 图片条目的 content 是文件路径，只处理其 OCR 文字
+            let new_content = if content_type == "image" {
+                content.clone()
+            } else {
+                convert(content.clone())?
+            };
+            let new_html = html.clone().map(&convert).transpose()?;
+            let new_rtf = rtf.clone().map(&convert).transpose()?;
+            let new_ocr_text = ocr_text
+                .clone()
+                .filter(|text| !text.is_empty())
+                .map(&convert)
+                .transpose()?
+                .or(ocr_text.clone());
+            // 外部存储的大文本按文件中的完整内容计算哈希，文件不可读时保留原哈希
+            let hashed = match &content_file {
+                Some(file) => match std::fs::read_to_string(file) {
+                    Ok(data) => {
+                        let converted = convert(data.clone())?;
+                        Some(plain(data, &converted))
+                    }
+                    Err(_) => None,
+                },
+                None => Some(plain(content.clone(), &new_content)),
+            };
+            let new_hash = hashed
+                .map(|text| crate::clipboard::content_hash_with(hash_key.as_ref(), &text, &content_type))
+                .or(hash.clone());
+            let changed = new_content != content || new_html != html || new_rtf != rtf || new_ocr_text != ocr_text;
+            if changed || new_hash != hash {
+                conn.execute(
+                    "UPDATE clipboard_history SET content = ?1, html_content = ?2, rtf_content = ?3, ocr_text = ?4, content_hash = ?5 WHERE id = ?6",
+                    params![new_content, new_html, new_rtf, new_ocr_text, new_hash, id],
+                )
+                .map_err(|e| format!("Failed to update clipboard item: {}", e))?;
+            }
+            if changed {
+                count += 1;
+            }
+            files.extend(content_file);
+        }
+        Ok((count, files))
+    })?;
+
+    files.sort();
+    files.dedup();
+    eprintln!("[ClipboardCrypto] Rewrote {} clipboard item(s)", count);
+    Ok((count, files))
+}
+
+/// 用 convert 重写外部存储的大文本文件
+fn migrate_files<F>(files: Vec<String>, convert: F) -> Result<(), String>
+where
+    F: Fn(String) -> Result<String, String>,
+{
+    for file in files {
+        let Ok(data) = std::fs::read_to_string(&file) else {
+            continue;
+        };
+        let converted = convert(data.clone())?;
+        if converted != data {
+            std::fs::write(&file, converted)
+                .map_err(|e| format!("Failed to rewrite clipboard blob {}: {}", file, e))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// 回收空闲页，避免旧明文残留在数据库文件中；VACUUM 可能重排 rowid，随后重建 FTS 索引
+///
+/// VACUUM 不能在事务中执行，所以等写入队列清空后在独立连接上运行，而不是交给写入线程
+fn compact(app_data_dir: &PathBuf) -> Result<(), String> {
+    db::flush_writes(app_data_dir)?;
+    let conn = db::get_connection(app_data_dir)?;
+    conn.execute_batch("VACUUM")
+        .map_err(|e| format!("Failed to vacuum database: {}", e))?;
+    db::rebuild_clipboard_fts(&conn);
+    Ok(())
+}
+
+fn derive_entropy(passphrase: &str, salt: &[u8]) -> [u8; 32] {
+    let mut digest: [u8; 32] = Sha256::new()
+        .chain_update(salt)
+        .chain_update(passphrase.as_bytes())
+        .finalize()
+        .into();
+    for _ in 1..PASSPHRASE_ROUNDS {
+        digest = Sha256::new().chain_update(digest).chain_update(salt).finalize().into();
+    }
+    digest
+}
+
+fn unwrap_key(key_file: &KeyFile, passphrase: Option<&str>) -> Result<[u8; 32], String> {
+    let decode = |s: &str| {
+        general_purpose::STANDARD
+            .decode(s)
+            .map_err(|e| format!("Invalid clipboard key file: {}", e))
+    };
+    let salt = decode(&key_file.salt)?;
+    let wrapped = decode(&key_file.wrapped_key)?;
+    let entropy = passphrase.map(|p| derive_entropy(p, &salt));
+    let key = dpapi_unprotect(&wrapped, entropy.as_ref().map(|e| e.as_slice()))?;
+    key.try_into().map_err(|_| "Invalid clipboard key length".to_string())
+}
+
+pub(crate) fn hmac_sha256(key: &[u8; 32], data: &[u8]) -> [u8; 32] {
+    let mut inner_pad = [0x36u8; 64];
+    let mut outer_pad = [0x5cu8; 64];
+    for (i, b) in key.iter().enumerate() {
+        inner_pad[i] ^= b;
+        outer_pad[i] ^= b;
+    }
+    let inner = Sha256::new().chain_update(inner_pad).chain_update(data).finalize();
+    Sha256::new().chain_update(outer_pad).chain_update(inner).finalize().into()
+}
+
+/// 内容哈希的密钥与派生 nonce 的数据密钥分开，两者互不泄露
+fn derive_hash_key(key: &[u8; 32]) -> [u8; 32] {
+    hmac_sha256(key, b"refast-clipboard-content-hash")
+}
+
+fn seal_with(key: &[u8; 32], value: &str) -> Result<String, String> {
+    if is_sealed(value) {
+        return Ok(value.to_string());
+    }
+    let nonce = &hmac_sha256(key, value.as_bytes())[..NONCE_LEN];
+    let (ciphertext, tag) = aes_gcm_encrypt(key, nonce, value.as_bytes())?;
+    let mut sealed = Vec::with_capacity(NONCE_LEN + ciphertext.len() + TAG_LEN);
+    sealed.extend_from_slice(nonce);
+    sealed.extend_from_slice(&ciphertext);
+    sealed.extend_from_slice(&tag);
+    Ok(format!("{}{}", SEALED_PREFIX, general_purpose::STANDARD.encode(sealed)))
+}
+
+fn open_with(key: &[u8; 32], value: &str) -> Result<String, String> {
+    let data = general_purpose::STANDARD
+        .decode(&value[SEALED_PREFIX.len()..])
+        .map_err(|e| format!("Invalid encrypted clipboard value: {}", e))?;
+    if data.len() < NONCE_LEN + TAG_LEN {
+        return Err("Invalid encrypted clipboard value".to_string());
+    }
+    let (nonce, rest) = data.split_at(NONCE_LEN);
+    let (ciphertext, tag) = rest.split_at(rest.len() - TAG_LEN);
+    let plain = aes_gcm_decrypt(key, nonce, ciphertext, tag)?;
+    String::from_utf8(plain).map_err(|e| format!("Invalid decrypted clipboard value: {}", e))
+}
+
+#[cfg(target_os = "windows")]
+use self::windows::{aes_gcm_decrypt, aes_gcm_encrypt, dpapi_protect, dpapi_unprotect};
+
+#[cfg(not(target_os = "windows"))]
+fn aes_gcm_encrypt(_key: &[u8; 32], _nonce: &[u8], _data: &[u8]) -> Result<(Vec<u8>, [u8; TAG_LEN]), String> {
+    Err("剪贴板加密目前仅支持 Windows".to_string())
+}
+
+#[cfg(not(target_os = "windows"))]
+fn aes_gcm_decrypt(_key: &[u8; 32], _nonce: &[u8], _data: &[u8], _tag: &[u8]) -> Result<Vec<u8>, String> {
+    Err("剪贴板加密目前仅支持 Windows".to_string())
+}
+
+#[cfg(not(target_os = "windows"))]
+fn dpapi_protect(_data: &[u8], _entropy: Option<&[u8]>) -> Result<Vec<u8>, String> {
+    Err("剪贴板加密目前仅支持 Windows".to_string())
+}
+
+#[cfg(not(target_os = "windows"))]
+fn dpapi_unprotect(_data: &[u8], _entropy: Option<&[u8]>) -> Result<Vec<u8>, String> {
+    Err("剪贴板加密目前仅支持 Windows".to_string())
+}
+
+#[cfg(target_os = "windows")]
+mod windows {
+    use super::TAG_LEN;
+    use windows_sys::Win32::Foundation::LocalFree;
+    use windows_sys::Win32::Security::Cryptography::{
+        BCryptCloseAlgorithmProvider, BCryptDecrypt, BCryptDestroyKey, BCryptEncrypt,
+        BCryptGenerateSymmetricKey, BCryptOpenAlgorithmProvider, BCryptSetProperty, CryptProtectData,
+        CryptUnprotectData, BCRYPT_AES_ALGORITHM, BCRYPT_ALG_HANDLE, BCRYPT_AUTHENTICATED_CIPHER_MODE_INFO,
+        BCRYPT_AUTHENTICATED_CIPHER_MODE_INFO_VERSION, BCRYPT_CHAINING_MODE, BCRYPT_KEY_HANDLE,
+        CRYPTPROTECT_UI_FORBIDDEN, CRYPT_INTEGER_BLOB,
+    };
+
+    fn blob(data: &[u8]) -> CRYPT_INTEGER_BLOB {
+        CRYPT_INTEGER_BLOB {
+            cbData: data.len() as u32,
+            pbData: data.as_ptr() as *mut u8,
+        }
+    }
+
+    pub fn dpapi_protect(data: &[u8], entropy: Option<&[u8]>) -> Result<Vec<u8>, String> {
+        dpapi(data, entropy, true)
+    }
+
+    pub fn dpapi_unprotect(data: &[u8], entropy: Option<&[u8]>) -> Result<Vec<u8>, String> {
+        dpapi(data, entropy, false)
+    }
+
+    fn dpapi(data: &[u8], entropy: Option<&[u8]>, protect: bool) -> Result<Vec<u8>, String> {
+        let input = blob(data);
+        let entropy = entropy.map(blob);
+        let entropy_ptr = entropy
+            .as_ref()
+            .map_or(std::ptr::null(), |e| e as *const CRYPT_INTEGER_BLOB);
+        unsafe {
+            let mut output: CRYPT_INTEGER_BLOB = std::mem::zeroed();
+            let ok = if protect {
+                CryptProtectData(
+                    &input,
+                    std::ptr::null(),
+                    entropy_ptr,
+                    std::ptr::null(),
+                    std::ptr::null(),
+                    CRYPTPROTECT_UI_FORBIDDEN,
+                    &mut output,
+                )
+            } else {
+                CryptUnprotectData(
+                    &input,
+                    std::ptr::null_mut(),
+                    entropy_ptr,
+                    std::ptr::null(),
+                    std::ptr::null(),
+                    CRYPTPROTECT_UI_FORBIDDEN,
+                    &mut output,
+                )
+            };
+            if ok == 0 {
+                return Err(format!("DPAPI failed: {}", std::io::Error::last_os_error()));
+            }
+            let bytes = std::slice::from_raw_parts(output.pbData, output.cbData as usize).to_vec();
+            LocalFree(output.pbData as _);
+            Ok(bytes)
+        }
+    }
+
+    pub fn aes_gcm_encrypt(key: &[u8; 32], nonce: &[u8], data: &[u8]) -> Result<(Vec<u8>, [u8; TAG_LEN]), String> {
+        let mut tag = [0u8; TAG_LEN];
+        let output = aes_gcm(key, nonce, data, &mut tag, true)?;
+        Ok((output, tag))
+    }
+
+    pub fn aes_gcm_decrypt(key: &[u8; 32], nonce: &[u8], data: &[u8], tag: &[u8]) -> Result<Vec<u8>, String> {
+        let mut tag: [u8; TAG_LEN] = tag.try_into().map_err(|_| "Invalid tag length".to_string())?;
+        aes_gcm(key, nonce, data, &mut tag, false)
+    }
+
+    fn aes_gcm(key: &[u8; 32], nonce: &[u8], data: &[u8], tag: &mut [u8; TAG_LEN], encrypt: bool) -> Result<Vec<u8>, String> {
+        unsafe {
+            let mut alg: BCRYPT_ALG_HANDLE = std::mem::zeroed();
+            let status = BCryptOpenAlgorithmProvider(&mut alg, BCRYPT_AES_ALGORITHM, std::ptr::null(), 0);
+            if status < 0 {
+                return Err(format!("Failed to open AES provider: 0x{:08X}", status));
+            }
+            let result = aes_gcm_with(alg, key, nonce, data, tag, encrypt);
+            BCryptCloseAlgorithmProvider(alg, 0);
+            result
+        }
+    }
+
+    unsafe fn aes_gcm_with(
+        alg: BCRYPT_ALG_HANDLE,
+        key: &[u8; 32],
+        nonce: &[u8],
+        data: &[u8],
+        tag: &mut [u8; TAG_LEN],
+        encrypt: bool,
+    ) -> Result<Vec<u8>, String> {
+        let mode: Vec<u16> = "ChainingModeGCM\0".encode_utf16().collect();
+        let status = BCryptSetProperty(alg as _, BCRYPT_CHAINING_MODE, mode.as_ptr() as *const u8, (mode.len() * 2) as u32, 0);
+        if status < 0 {
+            return Err(format!("Failed to set GCM mode: 0x{:08X}", status));
+        }
+
+        let mut key_handle: BCRYPT_KEY_HANDLE = std::mem::zeroed();
+        let status = BCryptGenerateSymmetricKey(alg, &mut key_handle, std::ptr::null_mut(), 0, key.as_ptr(), key.len() as u32, 0);
+        if status < 0 {
+            return Err(format!("Failed to create AES key: 0x{:08X}", status));
+        }
+
+        let mut info: BCRYPT_AUTHENTICATED_CIPHER_MODE_INFO = std::mem::zeroed();
+        info.cbSize = std::mem::size_of::<BCRYPT_AUTHENTICATED_CIPHER_MODE_INFO>() as u32;
+        info.dwInfoVersion = BCRYPT_AUTHENTICATED_CIPHER_MODE_INFO_VERSION;
+        info.pbNonce = nonce.as_ptr() as *mut u8;
+        info.cbNonce = nonce.len() as u32;
+        info.pbTag = tag.as_mut_ptr();
+        info.cbTag = TAG_LEN as u32;
+
+        let mut output = vec![0u8; data.len()];
+        let mut written = 0u32;
+        let status = if encrypt {
+            BCryptEncrypt(
+                key_handle,
+                data.as_ptr(),
+                data.len() as u32,
+                &info as *const _ as *const _,
+                std::ptr::null_mut(),
+                0,
+                output.as_mut_ptr(),
+                output.len() as u32,
+                &mut written,
+                0,
+            )
+        } else {
+            BCryptDecrypt(
+                key_handle,
+                data.as_ptr(),
+                data.len() as u32,
+                &info as *const _ as *const _,
+                std::ptr::null_mut(),
+                0,
+                output.as_mut_ptr(),
+                output.len() as u32,
+                &mut written,
+                0,
+            )
+        };
+        BCryptDestroyKey(key_handle);
+
+        if status < 0 {
+            return Err(if encrypt {
+                format!("AES-GCM encryption failed: 0x{:08X}", status)
+            } else {
+                "解密失败，数据可能已损坏或密钥不匹配".to_string()
+            });
+        }
+        output.truncate(written as usize);
+        Ok(output)
+    }
+}
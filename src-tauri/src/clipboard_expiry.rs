@@ -0,0 +1,220 @@
+//! 剪贴板条目定时清除
+//!
+//! 敏感条目（像密码 / 密钥，或来自密码管理器）入库时按设置写入到期时间（expires_at 列），
+//! 也可以手动为任意条目设置到期时间。由单个调度线程统一管理：按最早的到期时间休眠，
+//! 到期后删除条目，系统剪贴板中仍是该内容时一并清空。到期时间保存在数据库中，重启后继续生效。
+//! 收藏条目不会被定时清除。
+
+use crate::clipboard::ClipboardItem;
+use crate::db;
+use crate::settings;
+use rusqlite::params;
+use std::path::PathBuf;
+use std::sync::{Condvar, LazyLock, Mutex, OnceLock};
+use std::time::Duration;
+
+/// 没有待清除条目时的最长休眠时间
+const IDLE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+/// 清除失败后的重试间隔
+const RETRY_INTERVAL: Duration = Duration::from_secs(60);
+
+static APP_DATA_DIR: OnceLock<PathBuf> = OnceLock::new();
+/// 到期时间变化时唤醒调度线程重新计算休眠时长
+static WAKE: LazyLock<(Mutex<bool>, Condvar)> =
+    LazyLock::new(|| (Mutex::new(false), Condvar::new()));
+
+fn now_ts() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+fn wake() {
+    let (lock, cvar) = &*WAKE;
+    if let Ok(mut woken) = lock.lock() {
+        *woken = true;
+        cvar.notify_one();
+    }
+}
+
+/// 启动调度线程（启动时调用一次），先清除离线期间已到期的条目
+pub fn start(app_data_dir: PathBuf) {
+    if APP_DATA_DIR.set(app_data_dir.clone()).is_err() {
+        return;
+    }
+    std::thread::spawn(move || {
+        let (lock, cvar) = &*WAKE;
+        loop {
+            let timeout = match expire_due(&app_data_dir) {
+                Ok(Some(next)) => {
+                    Duration::from_secs(next.saturating_sub(now_ts()).max(1)).min(IDLE_INTERVAL)
+                }
+                Ok(None) => IDLE_INTERVAL,
+                Err(e) => {
+                    eprintln!("[ClipboardExpiry] Failed to remove expired items: {}", e);
+                    RETRY_INTERVAL
+                }
+            };
+            let Ok(mut woken) = lock.lock() else {
+                return;
+            };
+            if !*woken {
+                woken = match cvar.wait_timeout(woken, timeout) {
+                    Ok((guard, _)) => guard,
+                    Err(_) => return,
+                };
+            }
+            *woken = false;
+        }
+    });
+}
+
+/// 设置条目的到期时间，minutes 为 None 时取消定时清除，返回新的到期时间
+pub fn set_expiry(
+    id: String,
+    minutes: Option<u32>,
+    app_data_dir: &PathBuf,
+) -> Result<Option<u64>, String> {
+    let expires_at = minutes.map(|m| now_ts() + m as u64 * 60);
+    let updated = db::execute_write(app_data_dir, move |conn| {
+        conn.execute(
+            "UPDATE clipboard_history SET expires_at = ?1 WHERE id = ?2",
+            params![expires_at.map(|ts| ts as i64), id],
+        )
+        .map_err(|e| format!("Failed to update clipboard expiry: {}", e))
+    })?;
+    if updated == 0 {
+        return Err("Clipboard item not found".to_string());
+    }
+    wake();
+    Ok(expires_at)
+}
+
+/// 监控新捕获的条目：开启定时清除且内容敏感（或来自密码管理器）时设置到期时间
+pub fn schedule_if_sensitive(item: &ClipboardItem, app_data_dir: &PathBuf) {
+    if item.content_type == "image" || item.content_type == "file" {
+        return;
+    }
+    let settings = settings::load_settings(app_data_dir).unwrap_or_default();
+    let minutes = settings.clipboard_sensitive_expire_minutes;
+    if minutes == 0 {
+        return;
+    }
+    let from_password_manager = item.source_app.as_deref().is_some_and(|source| {
+        settings
+            .clipboard_password_managers
+            .iter()
+            .any(|name| name.trim().eq_ignore_ascii_case(source))
+    });
+    if !from_password_manager && !crate::clipboard_sensitive::looks_sensitive(&item.content) {
+        return;
+    }
+    match set_expiry(item.id.clone(), Some(minutes), app_data_dir) {
+        Ok(_) => eprintln!(
+            "[ClipboardExpiry] Sensitive item {} will be removed in {} minute(s)",
+            item.id, minutes
+        ),
+        Err(e) => eprintln!("[ClipboardExpiry] Failed to schedule {}: {}", item.id, e),
+    }
+}
+
+/// 删除已到期的非收藏条目，返回下一个到期时间
+fn expire_due(app_data_dir: &PathBuf) -> Result<Option<u64>, String> {
+    let now = now_ts() as i64;
+    let conn = db::get_readonly_connection(app_data_dir)?;
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, content_type, content_hash FROM clipboard_history
+             WHERE expires_at IS NOT NULL AND expires_at <= ?1 AND is_favorite = 0",
+        )
+        .map_err(|e| format!("Failed to prepare expired items query: {}", e))?;
+    let due: Vec<(String, String, Option<String>)> = stmt
+        .query_map(params![now], |row| {
+            Ok((row.get(0)?, row.get(1)?, row.get(2)?))
+        })
+        .map_err(|e| format!("Failed to query expired items: {}", e))?
+        .filter_map(|r| r.ok())
+        .collect();
+    drop(stmt);
+
+    if !due.is_empty() {
+        // 先删除条目，删除成功后再清空系统剪贴板，避免删除失败重试时反复清空用户的剪贴板
+        let ids = due.iter().map(|(id, _, _)| id.clone()).collect::<Vec<_>>();
+        let deleted = crate::clipboard::batch_delete_items(ids, app_data_dir)?;
+        eprintln!("[ClipboardExpiry] Removed {} expired item(s)", deleted);
+        if deleted > 0 {
+            clear_system_clipboard_if_matches(&due, app_data_dir);
+        }
+    }
+
+    let next: Option<i64> = conn
+        .query_row(
+            "SELECT MIN(expires_at) FROM clipboard_history WHERE expires_at IS NOT NULL AND is_favorite = 0",
+            [],
+            |row| row.get(0),
+        )
+        .map_err(|e| format!("Failed to query next expiry: {}", e))?;
+    Ok(next.map(|ts| ts.max(0) as u64))
+}
+
+/// 系统剪贴板当前内容属于到期条目时清空，避免覆盖用户之后复制的其他内容
+#[cfg(target_os = "windows")]
+fn clear_system_clipboard_if_matches(
+    due: &[(String, String, Option<String>)],
+    app_data_dir: &PathBuf,
+) {
+    use crate::clipboard::monitor;
+
+    let Ok((text, false)) = monitor::get_clipboard_text(1024 * 1024, 1024 * 1024) else {
+        return;
+    };
+    // 入库前经过规范化，比较前按相同规则处理
+    let rules = settings::load_settings(app_data_dir)
+        .map(|s| s.clipboard_normalize)
+        .unwrap_or_default();
+    let text = crate::clipboard::normalize_text(text, &rules);
+    let matches = due.iter().any(|(_, content_type, hash)| {
+        hash.is_some() && *hash == crate::clipboard::content_hash(&text, content_type).ok()
+    });
+    if matches {
+        match monitor::clear_clipboard() {
+            Ok(()) => eprintln!("[ClipboardExpiry] Cleared system clipboard"),
+            Err(e) => eprintln!("[ClipboardExpiry] Failed to clear system clipboard: {}", e),
+        }
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+fn clear_system_clipboard_if_matches(
+    _due: &[(String, String, Option<String>)],
+    _app_data_dir: &PathBuf,
+) {
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expire_due_removes_item() {
+        let dir = std::env::temp_dir().join(format!("refast-clipboard-expiry-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        db::execute_write(&dir, |conn| {
+            conn.execute_batch(
+                "INSERT INTO clipboard_history (id, content, content_type, created_at, is_favorite)
+                 VALUES ('due', 'secret', 'text', 1, 0), ('later', 'other', 'text', 2, 0)",
+            )
+            .map_err(|e| e.to_string())
+        })
+        .unwrap();
+        set_expiry("due".to_string(), Some(0), &dir).unwrap();
+        let later = set_expiry("later".to_string(), Some(10), &dir).unwrap();
+
+        assert_eq!(expire_due(&dir).unwrap(), later);
+        assert!(crate::clipboard::get_clipboard_item("due", &dir).is_err());
+        assert!(crate::clipboard::get_clipboard_item("later", &dir).is_ok());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
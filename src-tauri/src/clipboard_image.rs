@@ -0,0 +1,410 @@
+//! 剪贴板图片的存储编码
+//!
+//! 按设置把截获的图片编码为 PNG / JPEG / WebP，宽高超过上限时先按比例缩小；
+//! JPEG 与 WebP 通过 WIC 编解码。也支持按当前设置批量重新压缩已有的图片。
+
+use crate::color::RgbaImage;
+use crate::db;
+use crate::settings::{self, ClipboardImageFormat, ClipboardImageSettings};
+use rusqlite::params;
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+
+const IMAGES_DIR: &str = "clipboard_images";
+
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct RecompressResult {
+    /// 重新压缩并替换的图片数
+    pub processed: usize,
+    /// 已是目标格式且无需缩小、或压缩后没有变小的图片数
+    pub skipped: usize,
+    pub failed: Vec<String>,
+    /// 节省的磁盘空间（字节）
+    pub saved_bytes: u64,
+}
+
+fn extension(format: ClipboardImageFormat) -> &'static str {
+    match format {
+        ClipboardImageFormat::Png => "png",
+        ClipboardImageFormat::Jpeg => "jpg",
+        ClipboardImageFormat::Webp => "webp",
+    }
+}
+
+/// 实际使用的格式：系统没有 WebP 编码器时回退为 PNG
+fn effective_format(format: ClipboardImageFormat) -> ClipboardImageFormat {
+    #[cfg(target_os = "windows")]
+    {
+        if format == ClipboardImageFormat::Webp && !windows::can_encode(format) {
+            eprintln!("[ClipboardImage] WebP encoder unavailable, falling back to PNG");
+            return ClipboardImageFormat::Png;
+        }
+        format
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        let _ = format;
+        ClipboardImageFormat::Png
+    }
+}
+
+/// 按比例缩小到宽高均不超过 max_dimension（区域平均采样），无需缩小时返回 None
+pub fn downscale(pixels: &[u8], width: u32, height: u32, max_dimension: u32) -> Option<(Vec<u8>, u32, u32)> {
+    if max_dimension == 0 || (width <= max_dimension && height <= max_dimension) {
+        return None;
+    }
+    let scale = max_dimension as f64 / width.max(height) as f64;
+    let new_width = ((width as f64 * scale).round() as u32).clamp(1, max_dimension);
+    let new_height = ((height as f64 * scale).round() as u32).clamp(1, max_dimension);
+    let (width, height) = (width as usize, height as usize);
+    let (dst_width, dst_height) = (new_width as usize, new_height as usize);
+
+    let mut output = vec![0u8; dst_width * dst_height * 4];
+    for y in 0..dst_height {
+        let y0 = y * height / dst_height;
+        let y1 = ((y + 1) * height / dst_height).max(y0 + 1);
+        for x in 0..dst_width {
+            let x0 = x * width / dst_width;
+            let x1 = ((x + 1) * width / dst_width).max(x0 + 1);
+            let mut sum = [0u64; 4];
+            for sy in y0..y1 {
+                for sx in x0..x1 {
+                    let offset = (sy * width + sx) * 4;
+                    for (s, &v) in sum.iter_mut().zip(&pixels[offset..offset + 4]) {
+                        *s += v as u64;
+                    }
+                }
+            }
+            let count = ((y1 - y0) * (x1 - x0)) as u64;
+            let offset = (y * dst_width + x) * 4;
+            for (o, s) in output[offset..offset + 4].iter_mut().zip(sum) {
+                *o = ((s + count / 2) / count) as u8;
+            }
+        }
+    }
+    Some((output, new_width, new_height))
+}
+
+fn write_png(path: &Path, pixels: &[u8], width: u32, height: u32) -> Result<(), String> {
+    let file = std::fs::File::create(path).map_err(|e| format!("Failed to create file: {}", e))?;
+    let mut encoder = png::Encoder::new(std::io::BufWriter::new(file), width, height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder.set_adaptive_filter(png::AdaptiveFilterType::Adaptive);
+    let mut writer = encoder
+        .write_header()
+        .map_err(|e| format!("Failed to write PNG header: {}", e))?;
+    writer
+        .write_image_data(pixels)
+        .map_err(|e| format!("Failed to write PNG data: {}", e))
+}
+
+fn write_image(
+    path: &Path,
+    pixels: &[u8],
+    width: u32,
+    height: u32,
+    format: ClipboardImageFormat,
+    quality: u8,
+) -> Result<(), String> {
+    match format {
+        ClipboardImageFormat::Png => write_png(path, pixels, width, height),
+        #[cfg(target_os = "windows")]
+        _ => windows::encode(path, pixels, width, height, format, quality.clamp(1, 100)),
+        #[cfg(not(target_os = "windows"))]
+        _ => {
+            let _ = quality;
+            Err("JPEG / WebP 编码目前仅支持 Windows".to_string())
+        }
+    }
+}
+
+/// 解码图片文件为 RGBA（PNG 直接解码，其他格式通过 WIC）
+pub fn decode_image_file(path: &Path) -> Result<RgbaImage, String> {
+    let bytes = std::fs::read(path).map_err(|e| format!("Failed to read image: {}", e))?;
+    if bytes.starts_with(b"\x89PNG") {
+        return crate::color::decode_png(&bytes);
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        windows::decode(path)
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        Err("仅支持解码 PNG 图片".to_string())
+    }
+}
+
+/// 按图片设置保存剪贴板图片，以原始像素哈希命名，相同图片复用已有文件，返回文件路径
+pub fn save_image(
+    app_data_dir: &Path,
+    width: u32,
+    height: u32,
+    rgba: &[u8],
+    image_settings: &ClipboardImageSettings,
+) -> Result<String, String> {
+    let images_dir = app_data_dir.join(IMAGES_DIR);
+    std::fs::create_dir_all(&images_dir)
+        .map_err(|e| format!("Failed to create clipboard images directory: {}", e))?;
+
+    let format = effective_format(image_settings.format);
+    let hash = format!("{:x}", Sha256::digest(rgba));
+    let file_path = images_dir.join(format!("clipboard_{}.{}", &hash[..16], extension(format)));
+    if file_path.exists() {
+        return Ok(file_path.to_string_lossy().to_string());
+    }
+
+    let resized = downscale(rgba, width, height, image_settings.max_dimension);
+    let (pixels, width, height) = match &resized {
+        Some((pixels, w, h)) => (pixels.as_slice(), *w, *h),
+        None => (rgba, width, height),
+    };
+    // 先写临时文件再改名，避免中途失败留下损坏的图片被当作已存在复用
+    let temp_path = file_path.with_extension("tmp");
+    if let Err(e) = write_image(&temp_path, pixels, width, height, format, image_settings.quality) {
+        let _ = std::fs::remove_file(&temp_path);
+        return Err(e);
+    }
+    std::fs::rename(&temp_path, &file_path).map_err(|e| format!("Failed to save image: {}", e))?;
+    Ok(file_path.to_string_lossy().to_string())
+}
+
+/// 按当前设置重新压缩单张图片，返回新路径与节省的字节数；无需处理时返回 None
+fn recompress_file(path: &Path, image_settings: &ClipboardImageSettings) -> Result<Option<(PathBuf, u64)>, String> {
+    let format = effective_format(image_settings.format);
+    let same_format = path
+        .extension()
+        .map(|e| e.eq_ignore_ascii_case(extension(format)))
+        .unwrap_or(false);
+
+    let image = decode_image_file(path)?;
+    let resized = downscale(&image.pixels, image.width, image.height, image_settings.max_dimension);
+    if same_format && resized.is_none() {
+        return Ok(None);
+    }
+    let (pixels, width, height) = match &resized {
+        Some((pixels, w, h)) => (pixels.as_slice(), *w, *h),
+        None => (image.pixels.as_slice(), image.width, image.height),
+    };
+
+    let old_size = std::fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+    let new_path = path.with_extension(extension(format));
+    let temp_path = path.with_extension("tmp");
+    if let Err(e) = write_image(&temp_path, pixels, width, height, format, image_settings.quality) {
+        let _ = std::fs::remove_file(&temp_path);
+        return Err(e);
+    }
+    let new_size = std::fs::metadata(&temp_path).map(|m| m.len()).unwrap_or(0);
+    // 仅转换格式且没有变小时保留原图
+    if resized.is_none() && new_size >= old_size {
+        let _ = std::fs::remove_file(&temp_path);
+        return Ok(None);
+    }
+    std::fs::rename(&temp_path, &new_path).map_err(|e| format!("Failed to replace image: {}", e))?;
+    Ok(Some((new_path, old_size.saturating_sub(new_size))))
+}
+
+/// 按当前图片设置批量重新压缩剪贴板历史中的图片，并更新条目引用的路径
+pub fn recompress_all(app_data_dir: &PathBuf) -> Result<RecompressResult, String> {
+    let image_settings = settings::load_settings(app_data_dir)
+        .map(|s| s.clipboard_image)
+        .unwrap_or_default();
+
+    let conn = db::get_readonly_connection(app_data_dir)?;
+    let mut stmt = conn
+        .prepare("SELECT DISTINCT content FROM clipboard_history WHERE content_type = 'image'")
+        .map_err(|e| format!("Failed to prepare clipboard image query: {}", e))?;
+    let paths: Vec<String> = stmt
+        .query_map([], |row| row.get(0))
+        .map_err(|e| format!("Failed to query clipboard images: {}", e))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to read clipboard images: {}", e))?;
+    drop(stmt);
+    drop(conn);
+
+    let mut result = RecompressResult::default();
+    for old_path in paths {
+        let path = Path::new(&old_path);
+        if !path.exists() {
+            result.skipped += 1;
+            continue;
+        }
+        let (new_path, saved) = match recompress_file(path, &image_settings) {
+            Ok(Some(replaced)) => replaced,
+            Ok(None) => {
+                result.skipped += 1;
+                continue;
+            }
+            Err(e) => {
+                eprintln!("[ClipboardImage] Failed to recompress {}: {}", old_path, e);
+                result.failed.push(format!("{}: {}", old_path, e));
+                continue;
+            }
+        };
+
+        let new_path = new_path.to_string_lossy().to_string();
+        if new_path != old_path {
+            let (from, to) = (old_path.clone(), new_path.clone());
+            db::execute_write(app_data_dir, move |conn| {
+                conn.execute(
+                    "UPDATE clipboard_history SET content = ?1 WHERE content = ?2 AND content_type = 'image'",
+                    params![to, from],
+                )
+                .map_err(|e| format!("Failed to update clipboard image path: {}", e))
+            })?;
+            if let Err(e) = std::fs::remove_file(path) {
+                eprintln!("[ClipboardImage] Failed to delete {}: {}", old_path, e);
+            }
+        }
+        result.processed += 1;
+        result.saved_bytes += saved;
+    }
+
+    eprintln!(
+        "[ClipboardImage] Recompressed {} image(s), skipped {}, failed {}, saved {} bytes",
+        result.processed,
+        result.skipped,
+        result.failed.len(),
+        result.saved_bytes
+    );
+    Ok(result)
+}
+
+#[cfg(target_os = "windows")]
+mod windows {
+    use crate::color::RgbaImage;
+    use crate::settings::ClipboardImageFormat;
+    use ::windows::core::{GUID, PCWSTR, PWSTR};
+    use ::windows::Win32::Foundation::{GENERIC_READ, GENERIC_WRITE};
+    use ::windows::Win32::Graphics::Imaging::{
+        CLSID_WICImagingFactory, GUID_ContainerFormatJpeg, GUID_ContainerFormatPng, GUID_ContainerFormatWebp,
+        GUID_WICPixelFormat24bppBGR, GUID_WICPixelFormat32bppRGBA, IWICBitmapFrameEncode, IWICImagingFactory,
+        IWICPalette, WICBitmapDitherTypeNone, WICBitmapEncoderNoCache, WICBitmapPaletteTypeCustom,
+        WICDecodeMetadataCacheOnDemand,
+    };
+    use ::windows::Win32::System::Com::StructuredStorage::PROPBAG2;
+    use ::windows::Win32::System::Com::{CoCreateInstance, CoInitializeEx, CLSCTX_INPROC_SERVER, COINIT_MULTITHREADED};
+    use ::windows::Win32::System::Variant::VARIANT;
+    use std::os::windows::ffi::OsStrExt;
+    use std::path::Path;
+
+    fn err(e: ::windows::core::Error) -> String {
+        format!("WIC error: {}", e)
+    }
+
+    fn factory() -> Result<IWICImagingFactory, String> {
+        unsafe {
+            // 可能已被初始化为其他套间模式，失败时忽略
+            let _ = CoInitializeEx(None, COINIT_MULTITHREADED);
+            CoCreateInstance(&CLSID_WICImagingFactory, None, CLSCTX_INPROC_SERVER).map_err(err)
+        }
+    }
+
+    fn wide(path: &Path) -> Vec<u16> {
+        path.as_os_str().encode_wide().chain(std::iter::once(0)).collect()
+    }
+
+    fn container(format: ClipboardImageFormat) -> GUID {
+        match format {
+            ClipboardImageFormat::Png => GUID_ContainerFormatPng,
+            ClipboardImageFormat::Jpeg => GUID_ContainerFormatJpeg,
+            ClipboardImageFormat::Webp => GUID_ContainerFormatWebp,
+        }
+    }
+
+    /// 系统是否有该格式的编码器（WebP 编码器并非所有系统都有）
+    pub fn can_encode(format: ClipboardImageFormat) -> bool {
+        factory()
+            .and_then(|factory| unsafe { factory.CreateEncoder(&container(format), None).map_err(err) })
+            .is_ok()
+    }
+
+    /// RGBA 与白色背景混合后转为 BGR（JPEG 不支持透明通道）
+    fn to_bgr(rgba: &[u8]) -> Vec<u8> {
+        let mut bgr = Vec::with_capacity(rgba.len() / 4 * 3);
+        for pixel in rgba.chunks_exact(4) {
+            let alpha = pixel[3] as u32;
+            for &c in [pixel[2], pixel[1], pixel[0]].iter() {
+                bgr.push(((c as u32 * alpha + 255 * (255 - alpha) + 127) / 255) as u8);
+            }
+        }
+        bgr
+    }
+
+    pub fn encode(
+        path: &Path,
+        rgba: &[u8],
+        width: u32,
+        height: u32,
+        format: ClipboardImageFormat,
+        quality: u8,
+    ) -> Result<(), String> {
+        let factory = factory()?;
+        let path = wide(path);
+        unsafe {
+            let stream = factory.CreateStream().map_err(err)?;
+            stream
+                .InitializeFromFilename(PCWSTR(path.as_ptr()), GENERIC_WRITE.0)
+                .map_err(err)?;
+            let encoder = factory.CreateEncoder(&container(format), None).map_err(err)?;
+            encoder.Initialize(&stream, WICBitmapEncoderNoCache).map_err(err)?;
+
+            let mut frame: Option<IWICBitmapFrameEncode> = None;
+            let mut options = None;
+            encoder.CreateNewFrame(&mut frame, &mut options).map_err(err)?;
+            let frame = frame.ok_or_else(|| "Failed to create image frame".to_string())?;
+            if let Some(options) = &options {
+                let mut name: Vec<u16> = "ImageQuality\0".encode_utf16().collect();
+                let property = PROPBAG2 {
+                    pstrName: PWSTR(name.as_mut_ptr()),
+                    ..Default::default()
+                };
+                let value = VARIANT::from(quality as f32 / 100.0);
+                options.Write(1, &property, &value).map_err(err)?;
+            }
+            frame.Initialize(options.as_ref()).map_err(err)?;
+            frame.SetSize(width, height).map_err(err)?;
+            let mut pixel_format = GUID_WICPixelFormat24bppBGR;
+            frame.SetPixelFormat(&mut pixel_format).map_err(err)?;
+            if pixel_format != GUID_WICPixelFormat24bppBGR {
+                return Err("Encoder does not support 24bpp BGR".to_string());
+            }
+            frame.WritePixels(height, width * 3, &to_bgr(rgba)).map_err(err)?;
+            frame.Commit().map_err(err)?;
+            encoder.Commit().map_err(err)?;
+        }
+        Ok(())
+    }
+
+    pub fn decode(path: &Path) -> Result<RgbaImage, String> {
+        let factory = factory()?;
+        let path = wide(path);
+        unsafe {
+            let decoder = factory
+                .CreateDecoderFromFilename(PCWSTR(path.as_ptr()), None, GENERIC_READ, WICDecodeMetadataCacheOnDemand)
+                .map_err(err)?;
+            let frame = decoder.GetFrame(0).map_err(err)?;
+            let converter = factory.CreateFormatConverter().map_err(err)?;
+            converter
+                .Initialize(
+                    &frame,
+                    &GUID_WICPixelFormat32bppRGBA,
+                    WICBitmapDitherTypeNone,
+                    None::<&IWICPalette>,
+                    0.0,
+                    WICBitmapPaletteTypeCustom,
+                )
+                .map_err(err)?;
+            let (mut width, mut height) = (0u32, 0u32);
+            converter.GetSize(&mut width, &mut height).map_err(err)?;
+            let mut pixels = vec![0u8; width as usize * height as usize * 4];
+            converter
+                .CopyPixels(std::ptr::null(), width * 4, &mut pixels)
+                .map_err(err)?;
+            Ok(RgbaImage { width, height, pixels })
+        }
+    }
+}
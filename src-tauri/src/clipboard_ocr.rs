@@ -0,0 +1,127 @@
+//! 图片剪贴板条目的后台 OCR
+//!
+//! 后台线程识别尚未处理的图片条目（ocr_text 为 NULL），结果写回 ocr_text 供搜索使用。
+//! 新图片入库或导入后调用 request_ocr 唤醒；系统没有可用的 OCR 语言时保留待处理状态，下次唤醒再试。
+
+use crate::clipboard_crypto as crypto;
+use crate::db;
+use crate::settings;
+use rusqlite::params;
+use std::path::{Path, PathBuf};
+use std::sync::{Condvar, LazyLock, Mutex};
+use std::time::Duration;
+
+/// 每批处理的图片数
+const BATCH_SIZE: usize = 20;
+/// 没有唤醒时的轮询间隔
+const IDLE_INTERVAL: Duration = Duration::from_secs(30 * 60);
+
+static WAKE: LazyLock<(Mutex<bool>, Condvar)> = LazyLock::new(|| (Mutex::new(false), Condvar::new()));
+
+/// 唤醒后台任务处理待识别的图片
+pub fn request_ocr() {
+    let (pending, condvar) = &*WAKE;
+    *pending.lock().unwrap() = true;
+    condvar.notify_one();
+}
+
+/// 启动后台 OCR 任务（启动时先处理一次存量图片）
+pub fn start_ocr_task(app_data_dir: PathBuf) {
+    std::thread::spawn(move || loop {
+        let enabled = settings::load_settings(&app_data_dir)
+            .map(|s| s.clipboard_image_ocr)
+            .unwrap_or(true);
+        if enabled {
+            if let Err(e) = process_pending(&app_data_dir) {
+                eprintln!("[ClipboardOcr] {}", e);
+            }
+        }
+
+        let (pending, condvar) = &*WAKE;
+        let guard = pending.lock().unwrap();
+        let (mut guard, _) = condvar
+            .wait_timeout_while(guard, IDLE_INTERVAL, |pending| !*pending)
+            .unwrap();
+        *guard = false;
+    });
+}
+
+/// 识别所有待处理的图片，返回处理的条目数
+fn process_pending(app_data_dir: &PathBuf) -> Result<usize, String> {
+    let mut processed = 0;
+    loop {
+        let batch = pending_images(app_data_dir)?;
+        if batch.is_empty() {
+            break;
+        }
+        let batch_len = batch.len();
+        for (id, path) in batch {
+            let text = match recognize_file(Path::new(&path)) {
+                Ok(text) => text,
+                Err(OcrError::Image(e)) => {
+                    // 图片丢失或无法解码，记为无文字，避免反复重试
+                    eprintln!("[ClipboardOcr] Skipped {}: {}", path, e);
+                    String::new()
+                }
+                Err(OcrError::Engine(e)) => return Err(format!("OCR unavailable: {}", e)),
+            };
+            // 加密已启用但未解锁时无法保存，留到解锁后处理
+            let text = crypto::seal(text)?;
+            db::execute_write(app_data_dir, move |conn| {
+                conn.execute(
+                    "UPDATE clipboard_history SET ocr_text = ?1 WHERE id = ?2",
+                    params![text, id],
+                )
+                .map_err(|e| format!("Failed to save OCR text: {}", e))
+            })?;
+            processed += 1;
+        }
+        if batch_len < BATCH_SIZE {
+            break;
+        }
+    }
+    if processed > 0 {
+        eprintln!("[ClipboardOcr] Recognized {} image(s)", processed);
+    }
+    Ok(processed)
+}
+
+fn pending_images(app_data_dir: &PathBuf) -> Result<Vec<(String, String)>, String> {
+    let conn = db::get_readonly_connection(app_data_dir)?;
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, content FROM clipboard_history
+             WHERE content_type = 'image' AND ocr_text IS NULL
+             ORDER BY created_at DESC LIMIT ?1",
+        )
+        .map_err(|e| format!("Failed to prepare OCR query: {}", e))?;
+    let rows = stmt
+        .query_map(params![BATCH_SIZE as i64], |row| Ok((row.get(0)?, row.get(1)?)))
+        .map_err(|e| format!("Failed to query pending images: {}", e))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to read pending images: {}", e));
+    rows
+}
+
+enum OcrError {
+    /// 图片本身无法读取或解码
+    Image(String),
+    /// OCR 引擎不可用或识别失败
+    Engine(String),
+}
+
+fn recognize_file(path: &Path) -> Result<String, OcrError> {
+    let image = crate::clipboard_image::decode_image_file(path).map_err(OcrError::Image)?;
+
+    #[cfg(target_os = "windows")]
+    {
+        crate::commands::screen_ocr::recognize_rgba(&image.pixels, image.width, image.height)
+            .map_err(OcrError::Engine)
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        let _ = image;
+        Err(OcrError::Engine("OCR 目前仅支持 Windows".to_string()))
+    }
+}
@@ -0,0 +1,113 @@
+//! 剪贴板敏感内容识别
+//!
+//! 基于启发式规则判断文本是否像密码、密钥或银行卡号，用于通知摘要等场景隐藏内容。
+//! 只做本地判断，宁可漏判也尽量避免把普通文本误判为敏感。
+
+/// 已知服务的令牌前缀
+const TOKEN_PREFIXES: &[&str] = &[
+    "sk-",
+    "ghp_",
+    "gho_",
+    "ghu_",
+    "ghs_",
+    "github_pat_",
+    "glpat-",
+    "xoxb-",
+    "xoxp-",
+    "AKIA",
+    "AIza",
+];
+
+/// 文本是否像敏感内容
+pub fn looks_sensitive(text: &str) -> bool {
+    let text = text.trim();
+    if text.is_empty() {
+        return false;
+    }
+    if text.contains("-----BEGIN") && text.contains("PRIVATE KEY") {
+        return true;
+    }
+    // 以下规则只针对单个词（无空白）的短文本
+    if text.chars().count() > 256
+        || (text.chars().any(char::is_whitespace) && !is_card_number(text))
+    {
+        return false;
+    }
+    is_card_number(text) || is_token(text) || is_jwt(text) || is_password_like(text)
+}
+
+/// 13-19 位数字（允许空格 / 连字符分隔）且通过 Luhn 校验
+fn is_card_number(text: &str) -> bool {
+    if !text
+        .chars()
+        .all(|c| c.is_ascii_digit() || c == ' ' || c == '-')
+    {
+        return false;
+    }
+    let digits: Vec<u32> = text.chars().filter_map(|c| c.to_digit(10)).collect();
+    if !(13..=19).contains(&digits.len()) {
+        return false;
+    }
+    let sum: u32 = digits
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, &d)| {
+            if i % 2 == 1 {
+                let doubled = d * 2;
+                if doubled > 9 {
+                    doubled - 9
+                } else {
+                    doubled
+                }
+            } else {
+                d
+            }
+        })
+        .sum();
+    sum % 10 == 0
+}
+
+fn is_token(text: &str) -> bool {
+    text.len() >= 20
+        && TOKEN_PREFIXES.iter().any(|prefix| text.starts_with(prefix))
+        && text
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// JWT：三段 base64url，以 "eyJ"（`{"` 的编码）开头
+fn is_jwt(text: &str) -> bool {
+    let parts: Vec<&str> = text.split('.').collect();
+    parts.len() == 3
+        && text.starts_with("eyJ")
+        && parts.iter().all(|part| {
+            !part.is_empty()
+                && part
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        })
+}
+
+/// 8-64 个字符，含数字且至少包含小写、大写、数字、符号中的三类，且不是网址、路径或邮箱
+fn is_password_like(text: &str) -> bool {
+    let len = text.chars().count();
+    if !(8..=64).contains(&len) || !text.is_ascii() {
+        return false;
+    }
+    if text.contains("://")
+        || text.contains('\\')
+        || text.contains('/')
+        || (text.contains('@') && text.contains('.'))
+    {
+        return false;
+    }
+    let has_digit = text.chars().any(|c| c.is_ascii_digit());
+    let classes = [
+        text.chars().any(|c| c.is_ascii_lowercase()),
+        text.chars().any(|c| c.is_ascii_uppercase()),
+        has_digit,
+        text.chars().any(|c| c.is_ascii_punctuation()),
+    ];
+    has_digit && classes.iter().filter(|&&present| present).count() >= 3
+}
@@ -0,0 +1,188 @@
+//! 剪贴板条目文本转换：大小写、去首尾空白、URL / Base64 编解码、JSON 格式化、去除 Markdown 标记
+//!
+//! 转换结果作为新条目写入历史，原条目保持不变。
+
+use crate::clipboard::{self, ClipboardItem};
+use base64::{engine::general_purpose, Engine as _};
+use percent_encoding::{percent_decode_str, utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+use regex::Regex;
+use serde::Deserialize;
+use std::path::PathBuf;
+use std::sync::LazyLock;
+
+/// 与 JavaScript encodeURIComponent 一致：保留字母数字与 -_.!~*'()
+const URI_COMPONENT: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'_')
+    .remove(b'.')
+    .remove(b'!')
+    .remove(b'~')
+    .remove(b'*')
+    .remove(b'\'')
+    .remove(b'(')
+    .remove(b')');
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TransformOp {
+    Uppercase,
+    Lowercase,
+    Trim,
+    UrlEncode,
+    UrlDecode,
+    Base64Encode,
+    Base64Decode,
+    JsonPretty,
+    StripMarkdown,
+}
+
+/// 对文本执行转换
+pub fn apply(op: TransformOp, text: &str) -> Result<String, String> {
+    match op {
+        TransformOp::Uppercase => Ok(text.to_uppercase()),
+        TransformOp::Lowercase => Ok(text.to_lowercase()),
+        TransformOp::Trim => Ok(text
+            .lines()
+            .map(str::trim_end)
+            .collect::<Vec<_>>()
+            .join("\n")
+            .trim()
+            .to_string()),
+        TransformOp::UrlEncode => Ok(utf8_percent_encode(text, URI_COMPONENT).to_string()),
+        TransformOp::UrlDecode => percent_decode_str(&text.replace('+', " "))
+            .decode_utf8()
+            .map(|s| s.into_owned())
+            .map_err(|e| format!("Decoded URL is not valid UTF-8: {}", e)),
+        TransformOp::Base64Encode => Ok(general_purpose::STANDARD.encode(text.as_bytes())),
+        TransformOp::Base64Decode => base64_decode(text),
+        TransformOp::JsonPretty => json_pretty(text),
+        TransformOp::StripMarkdown => Ok(strip_markdown(text)),
+    }
+}
+
+/// 转换条目内容并作为新条目写入历史
+pub fn transform_item(
+    id: &str,
+    op: TransformOp,
+    app_data_dir: &PathBuf,
+) -> Result<ClipboardItem, String> {
+    let item = clipboard::get_clipboard_item(id, app_data_dir)?;
+    if item.content_type == "image" || item.content_type == "file" {
+        return Err(format!(
+            "Cannot transform a {} clipboard item",
+            item.content_type
+        ));
+    }
+    let content = clipboard::get_full_content(id, app_data_dir)?;
+    let result = apply(op, &content)?;
+    if result.is_empty() {
+        return Err("Transform result is empty".to_string());
+    }
+    clipboard::add_clipboard_item(result, "text".to_string(), app_data_dir)
+}
+
+/// 支持标准与 URL 安全字母表，允许省略填充与夹杂换行
+fn base64_decode(text: &str) -> Result<String, String> {
+    let compact: String = text.chars().filter(|c| !c.is_whitespace()).collect();
+    let trimmed = compact.trim_end_matches('=');
+    let bytes = if trimmed.contains(['-', '_']) {
+        general_purpose::URL_SAFE_NO_PAD.decode(trimmed)
+    } else {
+        general_purpose::STANDARD_NO_PAD.decode(trimmed)
+    }
+    .map_err(|e| format!("Invalid base64: {}", e))?;
+    String::from_utf8(bytes).map_err(|_| "Decoded base64 is not valid UTF-8 text".to_string())
+}
+
+/// 按 2 空格缩进重排 JSON，保留原有的键顺序与数字写法
+fn json_pretty(text: &str) -> Result<String, String> {
+    serde_json::from_str::<serde_json::Value>(text).map_err(|e| format!("Invalid JSON: {}", e))?;
+
+    let mut out = String::with_capacity(text.len() * 2);
+    let mut indent = 0usize;
+    let mut chars = text.trim().chars().peekable();
+    let newline = |out: &mut String, indent: usize| {
+        out.push('\n');
+        out.push_str(&"  ".repeat(indent));
+    };
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                out.push(c);
+                while let Some(c) = chars.next() {
+                    out.push(c);
+                    match c {
+                        '\\' => {
+                            if let Some(escaped) = chars.next() {
+                                out.push(escaped);
+                            }
+                        }
+                        '"' => break,
+                        _ => {}
+                    }
+                }
+            }
+            '{' | '[' => {
+                out.push(c);
+                // 空对象 / 数组保持在同一行
+                while chars.peek().is_some_and(|c| c.is_whitespace()) {
+                    chars.next();
+                }
+                if matches!(chars.peek(), Some('}' | ']')) {
+                    out.push(chars.next().unwrap_or_default());
+                } else {
+                    indent += 1;
+                    newline(&mut out, indent);
+                }
+            }
+            '}' | ']' => {
+                indent = indent.saturating_sub(1);
+                newline(&mut out, indent);
+                out.push(c);
+            }
+            ',' => {
+                out.push(c);
+                newline(&mut out, indent);
+            }
+            ':' => out.push_str(": "),
+            c if c.is_whitespace() => {}
+            c => out.push(c),
+        }
+    }
+    Ok(out)
+}
+
+static MD_FENCE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?m)^[ \t]*(```|~~~).*$\n?").unwrap());
+static MD_RULE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?m)^[ \t]*([-*_])([ \t]*[-*_]){2,}[ \t]*$").unwrap());
+static MD_BLOCK_PREFIX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?m)^[ \t]{0,3}(#{1,6}[ \t]+|>[ \t]?|[-*+][ \t]+(\[[ xX]\][ \t]+)?|\d+[.)][ \t]+)")
+        .unwrap()
+});
+static MD_IMAGE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"!\[([^\]]*)\]\([^)]*\)").unwrap());
+static MD_LINK: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\[([^\]]+)\]\([^)]*\)").unwrap());
+static MD_EMPHASIS: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\*\*([^*\n]+)\*\*|\*([^*\n]+)\*|~~([^~\n]+)~~").unwrap());
+/// 下划线强调只在单词边界处识别，避免误改 snake_case 标识符
+static MD_UNDERSCORE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(^|\W)__?([^_\n]+?)__?(\W|$)").unwrap());
+static MD_CODE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"`([^`\n]+)`").unwrap());
+
+/// 去除常见 Markdown 标记，保留文字内容
+fn strip_markdown(text: &str) -> String {
+    let text = MD_FENCE.replace_all(text, "");
+    let text = MD_RULE.replace_all(&text, "");
+    let text = MD_BLOCK_PREFIX.replace_all(&text, "");
+    let text = MD_IMAGE.replace_all(&text, "$1");
+    let text = MD_LINK.replace_all(&text, "$1");
+    let text = MD_CODE.replace_all(&text, "$1");
+    let text = MD_EMPHASIS.replace_all(&text, |caps: &regex::Captures| {
+        (1..=3)
+            .find_map(|i| caps.get(i))
+            .map(|m| m.as_str().to_string())
+            .unwrap_or_default()
+    });
+    let text = MD_UNDERSCORE.replace_all(&text, "$1$2$3");
+    text.trim().to_string()
+}
@@ -0,0 +1,360 @@
+//! 颜色格式化工具
+//!
+//! 取色结果在复制、展示时按用户配置的格式输出
+
+/// 解析 "#rrggbb" / "rrggbb" / "#rgb" 形式的颜色
+pub fn parse_hex(hex: &str) -> Option<(u8, u8, u8)> {
+    let hex = hex.trim().trim_start_matches('#');
+    let expanded: String = match hex.len() {
+        3 => hex.chars().flat_map(|c| [c, c]).collect(),
+        6 => hex.to_string(),
+        _ => return None,
+    };
+    let value = u32::from_str_radix(&expanded, 16).ok()?;
+    Some(((value >> 16) as u8, (value >> 8) as u8, value as u8))
+}
+
+/// 解析 "rgb(r, g, b)" / "rgba(r, g, b, a)" 形式的颜色（忽略 alpha）
+fn parse_rgb_function(text: &str) -> Option<(u8, u8, u8)> {
+    let lower = text.to_ascii_lowercase();
+    let inner = lower
+        .strip_prefix("rgba(")
+        .or_else(|| lower.strip_prefix("rgb("))?
+        .strip_suffix(')')?;
+    let parts: Vec<&str> = inner
+        .split(|c: char| c == ',' || c == '/' || c.is_whitespace())
+        .filter(|p| !p.is_empty())
+        .collect();
+    if parts.len() != 3 && parts.len() != 4 {
+        return None;
+    }
+    let channel = |s: &str| s.parse::<u16>().ok().filter(|v| *v <= 255).map(|v| v as u8);
+    Some((channel(parts[0])?, channel(parts[1])?, channel(parts[2])?))
+}
+
+/// 识别整段文本是否为颜色值（"#rrggbb" / "#rgb" / rgb() / rgba()），用于剪贴板自动建色卡
+///
+/// hex 必须带 # 前缀，避免把普通数字或单词误判为颜色
+pub fn parse_color_text(text: &str) -> Option<(u8, u8, u8)> {
+    let text = text.trim();
+    if text.len() > 64 {
+        return None;
+    }
+    if text.starts_with('#') {
+        return parse_hex(text);
+    }
+    parse_rgb_function(text)
+}
+
+/// 取色放大镜中 Shift 依次切换的格式
+pub const PICKER_FORMATS: &[&str] = &["hex_upper", "rgb", "hsl", "hsv", "cmyk", "css_var"];
+
+/// 按格式输出颜色文本，未知格式按 hex 处理
+pub fn format_color(r: u8, g: u8, b: u8, format: &str) -> String {
+    match format {
+        "hex_upper" => format!("#{:02X}{:02X}{:02X}", r, g, b),
+        "rgb" => format!("rgb({}, {}, {})", r, g, b),
+        "hsl" => {
+            let (h, s, l) = rgb_to_hsl(r, g, b);
+            format!("hsl({}, {}%, {}%)", h, s, l)
+        }
+        "hsv" => {
+            let (h, s, v) = rgb_to_hsv(r, g, b);
+            format!("hsv({}, {}%, {}%)", h, s, v)
+        }
+        "cmyk" => {
+            let (c, m, y, k) = rgb_to_cmyk(r, g, b);
+            format!("cmyk({}%, {}%, {}%, {}%)", c, m, y, k)
+        }
+        "css_var" => format!("--color: #{:02x}{:02x}{:02x};", r, g, b),
+        _ => format!("#{:02x}{:02x}{:02x}", r, g, b),
+    }
+}
+
+/// 色相（0-359）与饱和度、亮度百分比
+pub fn rgb_to_hsl(r: u8, g: u8, b: u8) -> (u16, u8, u8) {
+    let (r, g, b) = (r as f64 / 255.0, g as f64 / 255.0, b as f64 / 255.0);
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let l = (max + min) / 2.0;
+    let d = max - min;
+    let s = if d == 0.0 {
+        0.0
+    } else {
+        d / (1.0 - (2.0 * l - 1.0).abs())
+    };
+    (hue(r, g, b, max, d), percent(s), percent(l))
+}
+
+/// 色相（0-359）与饱和度、明度百分比
+pub fn rgb_to_hsv(r: u8, g: u8, b: u8) -> (u16, u8, u8) {
+    let (r, g, b) = (r as f64 / 255.0, g as f64 / 255.0, b as f64 / 255.0);
+    let max = r.max(g).max(b);
+    let d = max - r.min(g).min(b);
+    let s = if max == 0.0 { 0.0 } else { d / max };
+    (hue(r, g, b, max, d), percent(s), percent(max))
+}
+
+/// 朴素的 CMYK 百分比换算（不涉及色彩配置文件）
+pub fn rgb_to_cmyk(r: u8, g: u8, b: u8) -> (u8, u8, u8, u8) {
+    let (r, g, b) = (r as f64 / 255.0, g as f64 / 255.0, b as f64 / 255.0);
+    let k = 1.0 - r.max(g).max(b);
+    if k >= 1.0 {
+        return (0, 0, 0, 100);
+    }
+    let channel = |c: f64| percent((1.0 - c - k) / (1.0 - k));
+    (channel(r), channel(g), channel(b), percent(k))
+}
+
+fn hue(r: f64, g: f64, b: f64, max: f64, d: f64) -> u16 {
+    if d == 0.0 {
+        return 0;
+    }
+    let h = if max == r {
+        ((g - b) / d).rem_euclid(6.0)
+    } else if max == g {
+        (b - r) / d + 2.0
+    } else {
+        (r - g) / d + 4.0
+    };
+    ((h * 60.0).round() as u16) % 360
+}
+
+fn percent(v: f64) -> u8 {
+    (v.clamp(0.0, 1.0) * 100.0).round() as u8
+}
+
+/// 屏幕取色结果的各种常用表示
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct PickedColor {
+    /// #rrggbb
+    pub hex: String,
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub rgb: String,
+    pub hsl: String,
+    pub hsv: String,
+    pub cmyk: String,
+    pub css_var: String,
+}
+
+impl PickedColor {
+    pub fn new(r: u8, g: u8, b: u8) -> Self {
+        Self {
+            hex: format_color(r, g, b, "hex"),
+            r,
+            g,
+            b,
+            rgb: format_color(r, g, b, "rgb"),
+            hsl: format_color(r, g, b, "hsl"),
+            hsv: format_color(r, g, b, "hsv"),
+            cmyk: format_color(r, g, b, "cmyk"),
+            css_var: format_color(r, g, b, "css_var"),
+        }
+    }
+}
+
+/// 色觉缺陷类型：红色盲、绿色盲（均为红绿色盲）与蓝色盲（蓝黄色盲）
+#[derive(Debug, Clone, Copy)]
+pub enum ColorVision {
+    Protanopia,
+    Deuteranopia,
+    Tritanopia,
+}
+
+fn srgb_to_linear(c: u8) -> f64 {
+    let c = c as f64 / 255.0;
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn linear_to_srgb(c: f64) -> u8 {
+    let c = c.clamp(0.0, 1.0);
+    let c = if c <= 0.0031308 {
+        c * 12.92
+    } else {
+        1.055 * c.powf(1.0 / 2.4) - 0.055
+    };
+    (c * 255.0).round() as u8
+}
+
+/// 模拟色觉缺陷者看到的颜色（Machado 2009 完全缺失矩阵，在线性 RGB 中计算）
+pub fn simulate_color_vision(r: u8, g: u8, b: u8, vision: ColorVision) -> (u8, u8, u8) {
+    let m: [[f64; 3]; 3] = match vision {
+        ColorVision::Protanopia => [
+            [0.152286, 1.052583, -0.204868],
+            [0.114503, 0.786281, 0.099216],
+            [-0.003882, -0.048116, 1.051998],
+        ],
+        ColorVision::Deuteranopia => [
+            [0.367322, 0.860646, -0.227968],
+            [0.280085, 0.672501, 0.047413],
+            [-0.011820, 0.042940, 0.968881],
+        ],
+        ColorVision::Tritanopia => [
+            [1.255528, -0.076749, -0.178779],
+            [-0.078411, 0.930809, 0.147602],
+            [0.004733, 0.691367, 0.303900],
+        ],
+    };
+    let rgb = [srgb_to_linear(r), srgb_to_linear(g), srgb_to_linear(b)];
+    let channel = |row: &[f64; 3]| linear_to_srgb(row[0] * rgb[0] + row[1] * rgb[1] + row[2] * rgb[2]);
+    (channel(&m[0]), channel(&m[1]), channel(&m[2]))
+}
+
+/// 解码后的 RGBA8 图像
+pub struct RgbaImage {
+    pub width: u32,
+    pub height: u32,
+    pub pixels: Vec<u8>,
+}
+
+impl RgbaImage {
+    /// 读取指定坐标的颜色，越界返回 None
+    pub fn pixel(&self, x: u32, y: u32) -> Option<(u8, u8, u8)> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        let offset = (y as usize * self.width as usize + x as usize) * 4;
+        let p = self.pixels.get(offset..offset + 4)?;
+        Some((p[0], p[1], p[2]))
+    }
+}
+
+/// 解码 PNG 为 RGBA8（灰度、调色板、16 位等格式统一转换）
+pub fn decode_png(bytes: &[u8]) -> Result<RgbaImage, String> {
+    let mut decoder = png::Decoder::new(bytes);
+    decoder.set_transformations(png::Transformations::normalize_to_color8());
+    let mut reader = decoder
+        .read_info()
+        .map_err(|e| format!("Failed to decode PNG: {}", e))?;
+    let mut buf = vec![0; reader.output_buffer_size()];
+    let info = reader
+        .next_frame(&mut buf)
+        .map_err(|e| format!("Failed to read PNG frame: {}", e))?;
+    buf.truncate(info.buffer_size());
+
+    let pixels = match info.color_type {
+        png::ColorType::Rgba => buf,
+        png::ColorType::Rgb => buf
+            .chunks_exact(3)
+            .flat_map(|p| [p[0], p[1], p[2], 255])
+            .collect(),
+        png::ColorType::Grayscale => buf.iter().flat_map(|&v| [v, v, v, 255]).collect(),
+        png::ColorType::GrayscaleAlpha => buf
+            .chunks_exact(2)
+            .flat_map(|p| [p[0], p[0], p[0], p[1]])
+            .collect(),
+        png::ColorType::Indexed => return Err("Unsupported indexed PNG".to_string()),
+    };
+
+    Ok(RgbaImage {
+        width: info.width,
+        height: info.height,
+        pixels,
+    })
+}
+
+/// 主色调及其像素占比
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct DominantColor {
+    pub hex: String,
+    pub ratio: f64,
+}
+
+/// 用 k-means 聚类提取图像主色调，按占比降序返回
+///
+/// 最多采样约 10000 个不透明像素，初始中心取 4 位量化后出现最多的颜色，保证结果稳定
+pub fn dominant_colors(image: &RgbaImage, count: usize) -> Vec<DominantColor> {
+    const MAX_SAMPLES: usize = 10_000;
+    const ITERATIONS: usize = 10;
+
+    let total_pixels = image.pixels.len() / 4;
+    let step = (total_pixels / MAX_SAMPLES).max(1);
+    let samples: Vec<[f64; 3]> = image
+        .pixels
+        .chunks_exact(4)
+        .step_by(step)
+        .filter(|p| p[3] >= 128)
+        .map(|p| [p[0] as f64, p[1] as f64, p[2] as f64])
+        .collect();
+    if samples.is_empty() || count == 0 {
+        return Vec::new();
+    }
+
+    // 初始中心：量化直方图中出现最多的若干颜色
+    let mut buckets: std::collections::HashMap<(u8, u8, u8), usize> = std::collections::HashMap::new();
+    for s in &samples {
+        let key = ((s[0] as u8) >> 4, (s[1] as u8) >> 4, (s[2] as u8) >> 4);
+        *buckets.entry(key).or_insert(0) += 1;
+    }
+    let mut initial: Vec<((u8, u8, u8), usize)> = buckets.into_iter().collect();
+    initial.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    let mut centers: Vec<[f64; 3]> = initial
+        .iter()
+        .take(count)
+        .map(|((r, g, b), _)| {
+            [
+                (*r as f64) * 16.0 + 8.0,
+                (*g as f64) * 16.0 + 8.0,
+                (*b as f64) * 16.0 + 8.0,
+            ]
+        })
+        .collect();
+
+    let distance = |a: &[f64; 3], b: &[f64; 3]| -> f64 {
+        (a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2) + (a[2] - b[2]).powi(2)
+    };
+    let nearest = |centers: &[[f64; 3]], s: &[f64; 3]| -> usize {
+        centers
+            .iter()
+            .enumerate()
+            .min_by(|a, b| distance(a.1, s).total_cmp(&distance(b.1, s)))
+            .map(|(i, _)| i)
+            .unwrap_or(0)
+    };
+
+    let mut sizes = vec![0usize; centers.len()];
+    for _ in 0..ITERATIONS {
+        let mut sums = vec![[0.0f64; 3]; centers.len()];
+        sizes = vec![0usize; centers.len()];
+        for s in &samples {
+            let i = nearest(&centers, s);
+            sums[i][0] += s[0];
+            sums[i][1] += s[1];
+            sums[i][2] += s[2];
+            sizes[i] += 1;
+        }
+        let mut moved = false;
+        for (i, center) in centers.iter_mut().enumerate() {
+            if sizes[i] == 0 {
+                continue;
+            }
+            let n = sizes[i] as f64;
+            let updated = [sums[i][0] / n, sums[i][1] / n, sums[i][2] / n];
+            if distance(center, &updated) > 0.25 {
+                moved = true;
+            }
+            *center = updated;
+        }
+        if !moved {
+            break;
+        }
+    }
+
+    let mut result: Vec<DominantColor> = centers
+        .iter()
+        .zip(sizes.iter())
+        .filter(|(_, &size)| size > 0)
+        .map(|(c, &size)| DominantColor {
+            hex: format_color(c[0].round() as u8, c[1].round() as u8, c[2].round() as u8, "hex"),
+            ratio: size as f64 / samples.len() as f64,
+        })
+        .collect();
+    result.sort_by(|a, b| b.ratio.total_cmp(&a.ratio));
+    result
+}
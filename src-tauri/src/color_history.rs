@@ -0,0 +1,159 @@
+//! 取色历史
+//!
+//! 每次屏幕取色的结果持久化到 SQLite，同一颜色只保留一条（再次取到时刷新时间）。
+//! 未收藏的记录超过 MAX_ENTRIES 条时按时间淘汰最旧的。
+
+use crate::db;
+use rusqlite::{params, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// 未收藏记录的保留上限
+const MAX_ENTRIES: i64 = 200;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ColorHistoryEntry {
+    pub id: i64,
+    /// #RRGGBB
+    pub hex: String,
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    #[serde(default)]
+    pub note: Option<String>,
+    #[serde(default)]
+    pub is_favorite: bool,
+    /// 毫秒时间戳
+    pub created_at: i64,
+}
+
+const SELECT_COLUMNS: &str =
+    "SELECT id, hex, r, g, b, note, is_favorite, created_at FROM color_history";
+
+fn row_to_entry(row: &rusqlite::Row) -> rusqlite::Result<ColorHistoryEntry> {
+    Ok(ColorHistoryEntry {
+        id: row.get(0)?,
+        hex: row.get(1)?,
+        r: row.get(2)?,
+        g: row.get(3)?,
+        b: row.get(4)?,
+        note: row.get(5)?,
+        is_favorite: row.get::<_, i64>(6)? != 0,
+        created_at: row.get(7)?,
+    })
+}
+
+/// 获取取色历史：收藏在前，其余按时间倒序
+pub fn list_colors(
+    limit: Option<u32>,
+    app_data_dir: &Path,
+) -> Result<Vec<ColorHistoryEntry>, String> {
+    let conn = db::get_connection(app_data_dir)?;
+    let mut stmt = conn
+        .prepare(&format!(
+            "{} ORDER BY is_favorite DESC, created_at DESC LIMIT ?1",
+            SELECT_COLUMNS
+        ))
+        .map_err(|e| format!("Failed to prepare color history query: {}", e))?;
+
+    let limit = limit.map(i64::from).unwrap_or(-1);
+    let rows = stmt
+        .query_map(params![limit], row_to_entry)
+        .map_err(|e| format!("Failed to iterate color history: {}", e))?;
+
+    let mut items = Vec::new();
+    for row in rows {
+        items.push(row.map_err(|e| format!("Failed to read color history row: {}", e))?);
+    }
+    Ok(items)
+}
+
+/// 记录一次取色；颜色已存在时刷新时间，note 非空时覆盖备注
+pub fn add_color(
+    hex: &str,
+    note: Option<String>,
+    app_data_dir: &Path,
+) -> Result<ColorHistoryEntry, String> {
+    let (r, g, b) =
+        crate::color::parse_hex(hex).ok_or_else(|| format!("Invalid color: {}", hex))?;
+    let hex = format!("#{:02X}{:02X}{:02X}", r, g, b);
+    let note = note.filter(|n| !n.trim().is_empty());
+    let now = chrono::Utc::now().timestamp_millis();
+
+    let conn = db::get_connection(app_data_dir)?;
+    conn.execute(
+        "INSERT INTO color_history (hex, r, g, b, note, is_favorite, created_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, 0, ?6)
+         ON CONFLICT(hex) DO UPDATE SET
+             created_at = excluded.created_at,
+             note = COALESCE(excluded.note, color_history.note)",
+        params![hex, r, g, b, note, now],
+    )
+    .map_err(|e| format!("Failed to save color: {}", e))?;
+
+    conn.execute(
+        "DELETE FROM color_history WHERE is_favorite = 0 AND id NOT IN (
+             SELECT id FROM color_history WHERE is_favorite = 0
+             ORDER BY created_at DESC LIMIT ?1
+         )",
+        params![MAX_ENTRIES],
+    )
+    .map_err(|e| format!("Failed to trim color history: {}", e))?;
+
+    conn.query_row(
+        &format!("{} WHERE hex = ?1", SELECT_COLUMNS),
+        params![hex],
+        row_to_entry,
+    )
+    .map_err(|e| format!("Failed to load saved color: {}", e))
+}
+
+/// 删除一条取色记录
+pub fn delete_color(id: i64, app_data_dir: &Path) -> Result<(), String> {
+    let conn = db::get_connection(app_data_dir)?;
+    conn.execute("DELETE FROM color_history WHERE id = ?1", params![id])
+        .map_err(|e| format!("Failed to delete color: {}", e))?;
+    Ok(())
+}
+
+/// 设置或取消收藏（收藏的颜色不会被自动淘汰）
+pub fn set_favorite(
+    id: i64,
+    favorite: bool,
+    app_data_dir: &Path,
+) -> Result<ColorHistoryEntry, String> {
+    let conn = db::get_connection(app_data_dir)?;
+    conn.execute(
+        "UPDATE color_history SET is_favorite = ?1 WHERE id = ?2",
+        params![favorite as i64, id],
+    )
+    .map_err(|e| format!("Failed to update color favorite: {}", e))?;
+    load_color(&conn, id)
+}
+
+/// 修改备注，空字符串清除备注
+pub fn set_note(
+    id: i64,
+    note: Option<String>,
+    app_data_dir: &Path,
+) -> Result<ColorHistoryEntry, String> {
+    let note = note.filter(|n| !n.trim().is_empty());
+    let conn = db::get_connection(app_data_dir)?;
+    conn.execute(
+        "UPDATE color_history SET note = ?1 WHERE id = ?2",
+        params![note, id],
+    )
+    .map_err(|e| format!("Failed to update color note: {}", e))?;
+    load_color(&conn, id)
+}
+
+fn load_color(conn: &rusqlite::Connection, id: i64) -> Result<ColorHistoryEntry, String> {
+    conn.query_row(
+        &format!("{} WHERE id = ?1", SELECT_COLUMNS),
+        params![id],
+        row_to_entry,
+    )
+    .optional()
+    .map_err(|e| format!("Failed to load color: {}", e))?
+    .ok_or_else(|| format!("Color {} not found", id))
+}
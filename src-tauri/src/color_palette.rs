@@ -0,0 +1,352 @@
+//! 颜色历史与调色板的导入导出
+//!
+//! 导出为 JSON；导入支持 JSON 以及 Adobe .aco / .ase、GIMP .gpl 调色板文件。
+//! 无法转换为 RGB 的颜色（如专色、未知色彩空间）会被跳过。
+
+use crate::color::format_color;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// 当前导出文件格式版本
+const EXPORT_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaletteColor {
+    /// #rrggbb
+    pub hex: String,
+    #[serde(default)]
+    pub name: Option<String>,
+    /// 加入历史的时间（毫秒时间戳），调色板中的颜色可为空
+    #[serde(default)]
+    pub timestamp: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Palette {
+    pub name: String,
+    pub colors: Vec<PaletteColor>,
+}
+
+/// 导出 / 导入的颜色数据
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ColorExport {
+    #[serde(default)]
+    pub version: u32,
+    #[serde(default)]
+    pub history: Vec<PaletteColor>,
+    #[serde(default)]
+    pub palettes: Vec<Palette>,
+}
+
+/// 以 JSON 写出颜色历史与调色板
+pub fn export_json(
+    path: &Path,
+    history: Vec<PaletteColor>,
+    palettes: Vec<Palette>,
+) -> Result<(), String> {
+    let data = ColorExport {
+        version: EXPORT_VERSION,
+        history,
+        palettes,
+    };
+    let json = serde_json::to_string_pretty(&data)
+        .map_err(|e| format!("Failed to serialize colors: {}", e))?;
+    std::fs::write(path, json).map_err(|e| format!("Failed to write color file: {}", e))
+}
+
+/// 按扩展名导入颜色文件；调色板文件（.aco/.ase/.gpl）以文件名作为调色板名称
+pub fn import_file(path: &Path) -> Result<ColorExport, String> {
+    let bytes = std::fs::read(path).map_err(|e| format!("Failed to read color file: {}", e))?;
+    let extension = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("")
+        .to_ascii_lowercase();
+    let file_name = path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("Imported")
+        .to_string();
+
+    let (name, colors) = match extension.as_str() {
+        "json" => {
+            return serde_json::from_slice(&bytes)
+                .map_err(|e| format!("Failed to parse color JSON: {}", e))
+        }
+        "aco" => (file_name, parse_aco(&bytes)?),
+        "ase" => (file_name, parse_ase(&bytes)?),
+        "gpl" => {
+            let text = String::from_utf8_lossy(&bytes);
+            let (name, colors) = parse_gpl(&text)?;
+            (name.unwrap_or(file_name), colors)
+        }
+        _ => return Err(format!("Unsupported color file type: {}", extension)),
+    };
+    if colors.is_empty() {
+        return Err("No importable colors found".to_string());
+    }
+    Ok(ColorExport {
+        version: EXPORT_VERSION,
+        history: Vec::new(),
+        palettes: vec![Palette { name, colors }],
+    })
+}
+
+fn color(rgb: (u8, u8, u8), name: Option<String>) -> PaletteColor {
+    PaletteColor {
+        hex: format_color(rgb.0, rgb.1, rgb.2, "hex"),
+        name: name.filter(|n| !n.is_empty()),
+        timestamp: None,
+    }
+}
+
+/// 大端二进制读取器
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data, pos: 0 }
+    }
+
+    fn take(&mut self, len: usize) -> Option<&'a [u8]> {
+        let bytes = self.data.get(self.pos..self.pos.checked_add(len)?)?;
+        self.pos += len;
+        Some(bytes)
+    }
+
+    fn u16(&mut self) -> Option<u16> {
+        self.take(2).map(|b| u16::from_be_bytes([b[0], b[1]]))
+    }
+
+    fn u32(&mut self) -> Option<u32> {
+        self.take(4)
+            .map(|b| u32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+    }
+
+    fn f32(&mut self) -> Option<f32> {
+        self.u32().map(f32::from_bits)
+    }
+
+    /// 读取 len 个 UTF-16BE 字符，去掉结尾的 NUL
+    fn utf16(&mut self, len: usize) -> Option<String> {
+        let units: Vec<u16> = self
+            .take(len.checked_mul(2)?)?
+            .chunks_exact(2)
+            .map(|b| u16::from_be_bytes([b[0], b[1]]))
+            .collect();
+        Some(
+            String::from_utf16_lossy(&units)
+                .trim_end_matches('\0')
+                .to_string(),
+        )
+    }
+}
+
+fn to_byte(value: f64) -> u8 {
+    (value.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+fn hsb_to_rgb(h: f64, s: f64, v: f64) -> (u8, u8, u8) {
+    let h = (h.rem_euclid(360.0)) / 60.0;
+    let c = v * s;
+    let x = c * (1.0 - (h % 2.0 - 1.0).abs());
+    let (r, g, b) = match h as u32 {
+        0 => (c, x, 0.0),
+        1 => (x, c, 0.0),
+        2 => (0.0, c, x),
+        3 => (0.0, x, c),
+        4 => (x, 0.0, c),
+        _ => (c, 0.0, x),
+    };
+    let m = v - c;
+    (to_byte(r + m), to_byte(g + m), to_byte(b + m))
+}
+
+/// CMYK 各分量为 0~1 的油墨比例
+fn cmyk_to_rgb(c: f64, m: f64, y: f64, k: f64) -> (u8, u8, u8) {
+    (
+        to_byte((1.0 - c) * (1.0 - k)),
+        to_byte((1.0 - m) * (1.0 - k)),
+        to_byte((1.0 - y) * (1.0 - k)),
+    )
+}
+
+/// CIE Lab（D50 白点，Adobe 调色板使用）转 sRGB
+fn lab_to_rgb(l: f64, a: f64, b: f64) -> (u8, u8, u8) {
+    let fy = (l + 16.0) / 116.0;
+    let fx = fy + a / 500.0;
+    let fz = fy - b / 200.0;
+    let inverse = |t: f64| {
+        if t.powi(3) > 0.008856 {
+            t.powi(3)
+        } else {
+            (t - 16.0 / 116.0) / 7.787
+        }
+    };
+    let (x, y, z) = (inverse(fx) * 0.96422, inverse(fy), inverse(fz) * 0.82521);
+
+    // Bradford 适配到 D65 后的 XYZ -> 线性 sRGB 矩阵
+    let r = 3.1338561 * x - 1.6168667 * y - 0.4906146 * z;
+    let g = -0.9787684 * x + 1.9161415 * y + 0.0334540 * z;
+    let bl = 0.0719453 * x - 0.2289914 * y + 1.4052427 * z;
+    let gamma = |v: f64| {
+        if v <= 0.0031308 {
+            12.92 * v
+        } else {
+            1.055 * v.powf(1.0 / 2.4) - 0.055
+        }
+    };
+    (to_byte(gamma(r)), to_byte(gamma(g)), to_byte(gamma(bl)))
+}
+
+/// 读取 .aco 的一段颜色表（版本 2 段每个颜色后附带名称）
+fn read_aco_section(reader: &mut Reader, with_names: bool) -> Option<Vec<PaletteColor>> {
+    let count = reader.u16()?;
+    let mut colors = Vec::new();
+    for _ in 0..count {
+        let space = reader.u16()?;
+        let (w, x, y, z) = (reader.u16()?, reader.u16()?, reader.u16()?, reader.u16()?);
+        let name = if with_names {
+            let _ = reader.u16()?; // 固定为 0 的填充
+            let len = reader.u16()? as usize;
+            Some(reader.utf16(len)?)
+        } else {
+            None
+        };
+        let rgb = match space {
+            0 => Some(((w >> 8) as u8, (x >> 8) as u8, (y >> 8) as u8)),
+            1 => Some(hsb_to_rgb(
+                w as f64 / 65535.0 * 360.0,
+                x as f64 / 65535.0,
+                y as f64 / 65535.0,
+            )),
+            // .aco 中 CMYK 以 0 表示满墨
+            2 => Some(cmyk_to_rgb(
+                1.0 - w as f64 / 65535.0,
+                1.0 - x as f64 / 65535.0,
+                1.0 - y as f64 / 65535.0,
+                1.0 - z as f64 / 65535.0,
+            )),
+            7 => Some(lab_to_rgb(
+                w as f64 / 100.0,
+                x as i16 as f64 / 100.0,
+                y as i16 as f64 / 100.0,
+            )),
+            8 => {
+                let v = to_byte(1.0 - w as f64 / 10000.0);
+                Some((v, v, v))
+            }
+            _ => None,
+        };
+        if let Some(rgb) = rgb {
+            colors.push(color(rgb, name));
+        }
+    }
+    Some(colors)
+}
+
+/// 解析 Photoshop .aco 色板：优先使用带名称的版本 2 段，否则读取版本 1 段
+fn parse_aco(bytes: &[u8]) -> Result<Vec<PaletteColor>, String> {
+    let invalid = || "Invalid .aco file".to_string();
+    let mut reader = Reader::new(bytes);
+
+    match reader.u16().ok_or_else(invalid)? {
+        1 => {
+            let v1 = read_aco_section(&mut reader, false).ok_or_else(invalid)?;
+            if reader.u16() == Some(2) {
+                if let Some(v2) = read_aco_section(&mut reader, true) {
+                    return Ok(v2);
+                }
+            }
+            Ok(v1)
+        }
+        2 => read_aco_section(&mut reader, true).ok_or_else(invalid),
+        version => Err(format!("Unsupported .aco version: {}", version)),
+    }
+}
+
+/// 解析 Adobe Swatch Exchange (.ase)，分组展开为同一调色板
+fn parse_ase(bytes: &[u8]) -> Result<Vec<PaletteColor>, String> {
+    const BLOCK_COLOR: u16 = 0x0001;
+    let invalid = || "Invalid .ase file".to_string();
+    let mut reader = Reader::new(bytes);
+    if reader.take(4) != Some(b"ASEF".as_slice()) {
+        return Err(invalid());
+    }
+    reader.take(4).ok_or_else(invalid)?; // 版本号
+    let block_count = reader.u32().ok_or_else(invalid)?;
+
+    let mut colors = Vec::new();
+    for _ in 0..block_count {
+        let block_type = reader.u16().ok_or_else(invalid)?;
+        let length = reader.u32().ok_or_else(invalid)? as usize;
+        let block = reader.take(length).ok_or_else(invalid)?;
+        if block_type != BLOCK_COLOR {
+            continue;
+        }
+
+        let mut block = Reader::new(block);
+        let parsed = (|| {
+            let name_len = block.u16()? as usize;
+            let name = block.utf16(name_len)?;
+            let model = block.take(4)?;
+            let rgb = match model {
+                b"RGB " => {
+                    let (r, g, b) = (block.f32()?, block.f32()?, block.f32()?);
+                    (to_byte(r as f64), to_byte(g as f64), to_byte(b as f64))
+                }
+                b"CMYK" => {
+                    let (c, m, y, k) = (block.f32()?, block.f32()?, block.f32()?, block.f32()?);
+                    cmyk_to_rgb(c as f64, m as f64, y as f64, k as f64)
+                }
+                // ASE 中 L 为 0~1，需换算为 0~100
+                b"LAB " => {
+                    let (l, a, b) = (block.f32()?, block.f32()?, block.f32()?);
+                    lab_to_rgb(l as f64 * 100.0, a as f64, b as f64)
+                }
+                b"Gray" => {
+                    let v = to_byte(block.f32()? as f64);
+                    (v, v, v)
+                }
+                _ => return None,
+            };
+            Some(color(rgb, Some(name)))
+        })();
+        if let Some(parsed) = parsed {
+            colors.push(parsed);
+        }
+    }
+    Ok(colors)
+}
+
+/// 解析 GIMP .gpl 调色板，返回文件内的 Name 与颜色
+fn parse_gpl(text: &str) -> Result<(Option<String>, Vec<PaletteColor>), String> {
+    let mut lines = text.lines();
+    if lines.next().map(|l| l.trim()) != Some("GIMP Palette") {
+        return Err("Invalid .gpl file".to_string());
+    }
+
+    let mut name = None;
+    let mut colors = Vec::new();
+    for line in lines {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with("Columns:") {
+            continue;
+        }
+        if let Some(value) = line.strip_prefix("Name:") {
+            name = Some(value.trim().to_string()).filter(|n| !n.is_empty());
+            continue;
+        }
+        let mut parts = line.split_whitespace();
+        let mut channel = || parts.next()?.parse::<u8>().ok();
+        let (Some(r), Some(g), Some(b)) = (channel(), channel(), channel()) else {
+            continue;
+        };
+        let label = parts.collect::<Vec<_>>().join(" ");
+        colors.push(color((r, g, b), Some(label)));
+    }
+    Ok((name, colors))
+}
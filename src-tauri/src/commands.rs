@@ -2,11 +2,50 @@ pub mod recording;
 pub use recording::*;
 pub mod color_picker;
 pub mod memos;
+pub mod notes;
+pub mod screen_ruler;
+pub mod screen_ocr;
 pub mod word_records;
+pub mod file_actions;
+pub mod content_search;
+pub mod package_install;
 
 // 重新导出子模块中的所有命令
-pub use color_picker::{show_color_picker_window, pick_color_from_screen};
+pub use color_picker::{
+    show_color_picker_window,
+    pick_color_from_screen,
+    pick_color_and_copy,
+    get_color_pick_hotkey,
+    save_color_pick_hotkey,
+    load_image_for_picking,
+    pick_color_from_image,
+    extract_dominant_colors,
+    simulate_color_blindness,
+    export_colors,
+    import_colors,
+    list_color_history,
+    add_color_to_history,
+    delete_color,
+    favorite_color,
+    set_color_note,
+};
+pub use screen_ruler::{
+    measure_screen,
+    measure_screen_and_copy,
+    get_screen_ruler_hotkey,
+    save_screen_ruler_hotkey,
+};
+pub use screen_ocr::ocr_screen_region;
 pub use memos::{get_all_memos, add_memo, update_memo, delete_memo, search_memos};
+pub use notes::{
+    get_all_notes,
+    save_note,
+    delete_note,
+    search_notes,
+    show_notes_window,
+    get_notes_hotkey,
+    save_notes_hotkey,
+};
 pub use word_records::{
     get_all_word_records,
     add_word_record,
@@ -14,12 +53,23 @@ pub use word_records::{
     delete_word_record,
     search_word_records,
 };
+pub use file_actions::{
+    compress_to_zip,
+    copy_to,
+    move_to,
+    get_favorite_target_dirs,
+    add_favorite_target_dir,
+    remove_favorite_target_dir,
+};
+pub use content_search::{search_file_contents, cancel_file_content_search};
+pub use package_install::{search_packages, install_package, cancel_package_install};
 
 use crate::app_search;
 use crate::db;
 use crate::everything_search;
 use crate::everything_filters;
 use crate::file_history;
+use crate::launch_diagnostics;
 use crate::open_history;
 use crate::plugin_usage;
 
@@ -105,6 +155,11 @@ fn lock_app_cache_safe(cache: &Arc<Mutex<Option<Arc<Vec<app_search::AppInfo>>>>>
 }
 
 pub fn get_app_data_dir(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+    // 按 --profile 参数切换到独立数据目录
+    base_app_data_dir(app).map(crate::profile::apply)
+}
+
+fn base_app_data_dir(app: &tauri::AppHandle) -> Result<PathBuf, String> {
     // Try to use Tauri's path API first
     if let Ok(path) = app.path().app_data_dir() {
         return Ok(path);
@@ -125,6 +180,18 @@ pub fn get_app_data_dir(app: &tauri::AppHandle) -> Result<PathBuf, String> {
         .join("recordings"))
 }
 
+/// 获取钩子/热键线程的健康状态（重启次数、是否降级）
+#[tauri::command]
+pub fn get_hook_health() -> Vec<crate::hook_supervisor::HookHealth> {
+    crate::hook_supervisor::get_health()
+}
+
+/// 获取当前 Profile 名称（默认 Profile 返回 None）
+#[tauri::command]
+pub fn get_current_profile() -> Option<String> {
+    crate::profile::current().map(|name| name.to_string())
+}
+
 /// 统一的窗口显示辅助函数
 /// 
 /// 处理窗口的显示、取消最小化和聚焦逻辑，确保窗口正确显示在最前面
@@ -434,7 +501,11 @@ pub async fn search_applications(
         
         // 步骤2: 先执行搜索（避免预先检查计算器，节省时间）
         let search_start = std::time::Instant::now();
-        let mut results = app_search::windows::search_apps(&query_clone, apps.as_slice());
+        let synonyms = get_app_data_dir(&app_handle_for_scan)
+            .and_then(|dir| settings::load_settings(&dir))
+            .map(|s| s.synonyms)
+            .unwrap_or_default();
+        let mut results = app_search::windows::search_apps_with_synonyms(&query_clone, apps.as_slice(), &synonyms);
         let search_time = search_start.elapsed();
         
         // #region agent log
@@ -493,6 +564,7 @@ pub async fn search_applications(
                         description: Some("Windows 计算器".to_string()),
                         name_pinyin: Some("jisuanqi".to_string()),
                         name_pinyin_initials: Some("jsq".to_string()),
+                        alt_names: Vec::new(),
                     };
                     // 插入到结果开头（最高优先级）
                     results.insert(0, builtin_calculator);
@@ -866,9 +938,74 @@ pub async fn populate_app_icons(
     .map_err(|e| format!("populate_app_icons join error: {}", e))?
 }
 
+/// 启动应用，失败时返回结构化诊断（路径状态、权限、杀软拦截、错误码翻译与建议动作）
+#[tauri::command]
+pub fn launch_application(app: app_search::AppInfo) -> Result<(), launch_diagnostics::LaunchDiagnostics> {
+    app_search::windows::launch_app_with_code(&app)
+        .map_err(|(message, code)| launch_diagnostics::diagnose(&app.path, &message, code))
+}
+
+/// 输入补全建议（历史查询、别名、关键字），只读内存，不触发搜索
+#[tauri::command]
+pub fn suggest(
+    input: String,
+    limit: Option<usize>,
+    app: tauri::AppHandle,
+) -> Result<Vec<crate::suggest::Suggestion>, String> {
+    let app_data_dir = get_app_data_dir(&app)?;
+    crate::suggest::suggest(&input, limit.unwrap_or(8), &app_data_dir)
+}
+
+/// 启动器日期计算（"今天+45天"、"2024-01-01 到 2024-06-01"），无法解析时返回 None
+#[tauri::command]
+pub fn evaluate_date_expression(query: String) -> Option<crate::date_calc::DateCalcResult> {
+    crate::date_calc::evaluate(&query)
+}
+
+/// 执行启动器小工具命令（"pwd 16"、"uuid"、"roll 2d6"），结果直接复制到剪贴板
+///
+/// 输入不是工具命令时返回 None
+#[tauri::command]
+pub fn run_tool_query(
+    query: String,
+    app: tauri::AppHandle,
+) -> Result<Option<crate::tool_providers::ToolResult>, String> {
+    let app_data_dir = get_app_data_dir(&app)?;
+    let settings = settings::load_settings(&app_data_dir).unwrap_or_default();
+    let Some(result) = crate::tool_providers::run(&query, &settings.password_generator) else {
+        return Ok(None);
+    };
+    let result = result?;
+
+    #[cfg(target_os = "windows")]
+    crate::clipboard::monitor::set_clipboard_text(&result.value)?;
+
+    Ok(Some(result))
+}
+
+/// 记录一次实际执行的查询，用于补全建议
+#[tauri::command]
+pub async fn record_search_query(query: String, app: tauri::AppHandle) -> Result<(), String> {
+    let app_data_dir = get_app_data_dir(&app)?;
+    db::run_blocking(move || crate::suggest::record_query(&query, &app_data_dir)).await
+}
+
+/// 查询一组应用的运行状态，只返回正在运行的条目（键为应用路径）
+#[tauri::command]
+pub async fn get_running_apps(
+    paths: Vec<String>,
+) -> Result<std::collections::HashMap<String, crate::running_apps::RunningApp>, String> {
+    async_runtime::spawn_blocking(move || crate::running_apps::windows::get_running(&paths))
+        .await
+        .map_err(|e| format!("get_running_apps join error: {}", e))
+}
+
+/// 切换到已运行应用的窗口，应用未运行时返回 false（前端应回退为启动应用）
 #[tauri::command]
-pub fn launch_application(app: app_search::AppInfo) -> Result<(), String> {
-    app_search::windows::launch_app(&app)
+pub async fn activate_running_app(path: String) -> Result<bool, String> {
+    async_runtime::spawn_blocking(move || crate::running_apps::windows::activate(&path))
+        .await
+        .map_err(|e| format!("activate_running_app join error: {}", e))
 }
 
 /// 从应用索引中删除指定的应用
@@ -1218,6 +1355,7 @@ pub async fn extract_icon_from_path(file_path: String, app: tauri::AppHandle) ->
                 description: None,
                 name_pinyin,
                 name_pinyin_initials,
+                alt_names: Vec::new(),
             };
             
             apps.push(new_app);
@@ -1374,11 +1512,10 @@ pub fn toggle_launcher(app: tauri::AppHandle) -> Result<(), String> {
             if let Ok(position) = window.outer_position() {
                 let _ = window_config::save_launcher_position(&app_data_dir, position.x, position.y);
             }
-            let _ = window.hide();
+            crate::foreground::hide_and_restore(&window);
         } else {
             set_launcher_window_position(&window, &app_data_dir);
-            let _ = window.show();
-            let _ = window.set_focus();
+            crate::foreground::show_and_activate(&window);
         }
     } else {
         return Err("Launcher window not found".to_string());
@@ -1395,7 +1532,7 @@ pub fn hide_launcher(app: tauri::AppHandle) -> Result<(), String> {
         if let Ok(position) = window.outer_position() {
             let _ = window_config::save_launcher_position(&app_data_dir, position.x, position.y);
         }
-        let _ = window.hide();
+        crate::foreground::hide_and_restore(&window);
     }
     Ok(())
 }
@@ -1622,7 +1759,7 @@ pub fn update_open_history_remark(
 // ===== Word Record commands =====
 // 已迁移到 commands/word_records.rs
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Default, Deserialize)]
 pub struct EverythingSearchOptions {
     pub extensions: Option<Vec<String>>,
     #[serde(rename = "excludeExtensions")]
@@ -1637,11 +1774,58 @@ pub struct EverythingSearchOptions {
     pub match_folder_name_only: Option<bool>,
     #[serde(rename = "chunkSize")]
     pub chunk_size: Option<usize>,
+    #[serde(rename = "sortKey")]
+    pub sort_key: Option<String>, // "name" | "path" | "size" | "type" | "date_modified"
+    #[serde(rename = "sortOrder")]
+    pub sort_order: Option<String>, // "asc" | "desc"
+    /// 只在这些根路径下搜索；未指定时使用当前启用的作用域方案，空列表表示不限制
+    #[serde(rename = "scopePaths", default)]
+    pub scope_paths: Option<Vec<String>>,
+    /// 文件夹排在文件前面；未指定时使用设置中的默认值
+    #[serde(rename = "foldersFirst", default)]
+    pub folders_first: Option<bool>,
+}
+
+impl EverythingSearchOptions {
+    /// 透传给 Everything 的排序方式
+    fn sort(&self) -> Option<everything_search::EverythingSort> {
+        everything_search::EverythingSort::parse(self.sort_key.as_deref()?, self.sort_order.as_deref())
+    }
+}
+
+/// 是否文件夹优先：请求参数优先，否则读取设置
+#[cfg(target_os = "windows")]
+fn everything_folders_first(app: &tauri::AppHandle, requested: Option<bool>) -> bool {
+    requested.unwrap_or_else(|| {
+        get_app_data_dir(app)
+            .and_then(|dir| settings::load_settings(&dir))
+            .map(|s| s.everything_folders_first)
+            .unwrap_or(false)
+    })
+}
+
+/// 未显式指定作用域时填入当前启用方案的根路径
+fn with_active_scope(
+    app: &tauri::AppHandle,
+    options: Option<EverythingSearchOptions>,
+) -> Option<EverythingSearchOptions> {
+    if options.as_ref().is_some_and(|opts| opts.scope_paths.is_some()) {
+        return options;
+    }
+    let paths = get_app_data_dir(app)
+        .map(|dir| everything_filters::active_scope_paths(&dir))
+        .unwrap_or_default();
+    if paths.is_empty() {
+        return options;
+    }
+    let mut options = options.unwrap_or_default();
+    options.scope_paths = Some(paths);
+    Some(options)
 }
 
 fn build_everything_query(base: &str, options: &Option<EverythingSearchOptions>) -> (String, usize) {
     let mut parts: Vec<String> = Vec::new();
-    let mut base_query = base.trim().to_string();
+    let mut base_query = everything_search::normalize_folder_shortcut(base.trim());
     let mut use_regex = false;
 
     let mut max_results = 50usize;
@@ -1746,10 +1930,34 @@ fn build_everything_query(base: &str, options: &Option<EverythingSearchOptions>)
         }
     }
 
+    // 按作用域拼接 path: 过滤（用户已手写 path: / parent: 时不再添加，只有过滤条件没有关键词时也不添加）
+    let has_path_filter = original_query
+        .split_whitespace()
+        .any(|word| word.starts_with("path:") || word.starts_with("parent:"));
+    if !has_path_filter && !parts.is_empty() {
+        if let Some(scope) = options
+            .as_ref()
+            .and_then(|opts| opts.scope_paths.as_deref())
+            .and_then(everything_filters::scope_query)
+        {
+            parts.push(scope);
+        }
+    }
+
     let combined_query = parts.join(" ").trim().to_string();
     (combined_query, max_results)
 }
 
+/// 设置中的 Everything 查询超时（默认 2s）
+#[cfg(target_os = "windows")]
+fn everything_timeout(app: &tauri::AppHandle) -> Duration {
+    let timeout_ms = get_app_data_dir(app)
+        .and_then(|dir| settings::load_settings(&dir))
+        .map(|s| s.everything_timeout_ms)
+        .unwrap_or(2000);
+    Duration::from_millis(timeout_ms.max(100))
+}
+
 #[tauri::command]
 pub async fn search_everything(
     query: String,
@@ -1758,12 +1966,16 @@ pub async fn search_everything(
 ) -> Result<everything_search::EverythingSearchResponse, String> {
     #[cfg(target_os = "windows")]
     {
+        let options = with_active_scope(&app, options);
         let (combined_query, max_results) = build_everything_query(&query, &options);
         let chunk_size = options
             .as_ref()
             .and_then(|opts| opts.chunk_size)
             .unwrap_or(5000)
             .max(1);
+        let sort = options.as_ref().and_then(|opts| opts.sort());
+        let folders_first =
+            everything_folders_first(&app, options.as_ref().and_then(|opts| opts.folders_first));
 
         // 前置兜底：若最终查询字符串为空，直接返回空结果，避免前端误触发“查询字符串不能为空”错误
         // 典型场景：仅设置过滤器但未输入关键词，或异步竞态导致空串落到后端
@@ -1778,6 +1990,10 @@ pub async fn search_everything(
             });
         }
 
+        // 本次搜索的日志关联 ID
+        let trace_id = crate::logger::new_trace_id();
+        crate::log!(trace: trace_id, "Everything", "开始搜索: {} (max_results: {})", combined_query, max_results);
+
         // 为新搜索准备取消标志，同时通知旧搜索退出
         let cancel_flag = {
             let mut manager = SEARCH_TASK_MANAGER
@@ -1789,6 +2005,7 @@ pub async fn search_everything(
                 if current_query == &combined_query {
                     // query 相同，说明是重复搜索，返回错误
                     eprintln!("[RUST] Duplicate search detected for query: {}, skipping", combined_query);
+                    crate::log!(trace: trace_id, "Everything", "重复搜索，跳过: {}", combined_query);
                     return Err(format!("搜索 '{}' 正在进行中，跳过重复调用", combined_query));
                 }
             }
@@ -1845,14 +2062,19 @@ pub async fn search_everything(
 
         // 获取异步运行时句柄，用于在阻塞线程中发送事件
         let rt_handle = tokio::runtime::Handle::current();
+        let timeout = everything_timeout(&app);
         
         tokio::task::spawn_blocking(move || {
+            let search_start = std::time::Instant::now();
             // 创建批次回调，用于实时发送进度与增量结果
             let on_batch = move |batch_results: &[everything_search::EverythingResult], total_count: u32, current_count: u32| {
                 // 在异步运行时中发送事件
                 let launcher = launcher_window.clone();
                 let everything_win = everything_window.clone();
-                let batch_results = batch_results.to_vec();
+                let mut batch_results = batch_results.to_vec();
+                if folders_first {
+                    everything_search::sort_folders_first(&mut batch_results);
+                }
                 let handle = rt_handle.clone();
                 
                 // 使用运行时句柄在阻塞线程中发送异步事件
@@ -1881,6 +2103,8 @@ pub async fn search_everything(
                 &query_clone,
                 max_results_clone,
                 chunk_size,
+                timeout,
+                sort,
                 Some(&cancel_flag),
                 Some(on_batch),
             );
@@ -1896,7 +2120,21 @@ pub async fn search_everything(
                 }
             }
 
-            let resp = result.map_err(|e| e.to_string())?;
+            let mut resp = result.map_err(|e| {
+                crate::log!(trace: trace_id, "Everything", "搜索失败: {} (耗时: {}ms)", e, search_start.elapsed().as_millis());
+                e.to_string()
+            })?;
+            if folders_first {
+                everything_search::sort_folders_first(&mut resp.results);
+            }
+            crate::log!(
+                trace: trace_id,
+                "Everything",
+                "搜索完成: {} 条结果 (total_count: {}, 耗时: {}ms)",
+                resp.results.len(),
+                resp.total_count,
+                search_start.elapsed().as_millis()
+            );
 
             // 调试：确认后端实际返回了多少条结果
             eprintln!(
@@ -1948,13 +2186,17 @@ pub struct EverythingSearchSessionOptions {
     #[serde(rename = "maxResults")]
     pub max_results: Option<usize>,
     #[serde(rename = "sortKey")]
-    pub sort_key: Option<String>, // "size" | "type" | "name"
+    pub sort_key: Option<String>, // "name" | "path" | "size" | "type" | "date_modified"
     #[serde(rename = "sortOrder")]
     pub sort_order: Option<String>, // "asc" | "desc"
     #[serde(rename = "matchFolderNameOnly")]
     pub match_folder_name_only: Option<bool>,
     #[serde(rename = "chunkSize")]
     pub chunk_size: Option<usize>,
+    #[serde(rename = "scopePaths", default)]
+    pub scope_paths: Option<Vec<String>>,
+    #[serde(rename = "foldersFirst", default)]
+    pub folders_first: Option<bool>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -2010,9 +2252,16 @@ pub async fn start_everything_search_session(
             max_results: Some(max_results),
             match_folder_name_only: Some(match_folder_name_only),
             chunk_size: Some(chunk_size),
+            sort_key: opts.and_then(|o| o.sort_key.clone()),
+            sort_order: opts.and_then(|o| o.sort_order.clone()),
+            scope_paths: opts.and_then(|o| o.scope_paths.clone()),
+            folders_first: opts.and_then(|o| o.folders_first),
         };
+        let sort = search_opts.sort();
+        let folders_first = everything_folders_first(&app, search_opts.folders_first);
+        let search_opts = with_active_scope(&app, Some(search_opts));
         
-        let (combined_query, _) = build_everything_query(&search_query, &Some(search_opts));
+        let (combined_query, _) = build_everything_query(&search_query, &search_opts);
         
         // 在移动之前克隆 combined_query，用于后续生成会话 ID
         let combined_query_for_session = combined_query.clone();
@@ -2024,6 +2273,7 @@ pub async fn start_everything_search_session(
         let rt_handle = tokio::runtime::Handle::current();
 
         // 执行搜索
+        let timeout = everything_timeout(&app);
         let cancel_flag = Arc::new(AtomicBool::new(false));
         let result = {
             // 创建批次回调，用于实时发送结果（用于进度显示）
@@ -2054,6 +2304,8 @@ pub async fn start_everything_search_session(
                     &combined_query,
                     max_results,
                     5000,
+                    timeout,
+                    sort,
                     Some(&cancel_flag),
                     Some(on_batch),
                 )
@@ -2064,56 +2316,10 @@ pub async fn start_everything_search_session(
 
         let search_response = result.map_err(|e| e.to_string())?;
 
-        // 对结果进行排序（如果需要）
+        // 排序已由 Everything 完成，文件夹优先时再稳定地把文件夹排到前面
         let mut results = search_response.results;
-        if let Some(sort_key) = opts.and_then(|o| o.sort_key.as_ref()) {
-            // 使用字符串字面量而不是临时值
-            let default_sort_order = "desc";
-            let sort_order_str = opts
-                .and_then(|o| o.sort_order.as_deref())
-                .unwrap_or(default_sort_order);
-            let ascending = sort_order_str == "asc";
-
-            match sort_key.as_str() {
-                "size" => {
-                    results.sort_by(|a, b| {
-                        let a_size = a.size.unwrap_or(0);
-                        let b_size = b.size.unwrap_or(0);
-                        if ascending {
-                            a_size.cmp(&b_size)
-                        } else {
-                            b_size.cmp(&a_size)
-                        }
-                    });
-                }
-                "type" => {
-                    results.sort_by(|a, b| {
-                        let a_ext = std::path::Path::new(&a.path)
-                            .extension()
-                            .and_then(|s| s.to_str())
-                            .unwrap_or("");
-                        let b_ext = std::path::Path::new(&b.path)
-                            .extension()
-                            .and_then(|s| s.to_str())
-                            .unwrap_or("");
-                        if ascending {
-                            a_ext.cmp(b_ext)
-                        } else {
-                            b_ext.cmp(a_ext)
-                        }
-                    });
-                }
-                "name" => {
-                    results.sort_by(|a, b| {
-                        if ascending {
-                            a.name.cmp(&b.name)
-                        } else {
-                            b.name.cmp(&a.name)
-                        }
-                    });
-                }
-                _ => {}
-            }
+        if folders_first {
+            everything_search::sort_folders_first(&mut results);
         }
 
         // 生成会话 ID（使用时间戳 + 随机数）
@@ -2236,6 +2442,23 @@ pub fn get_everything_status() -> (bool, Option<String>) {
     }
 }
 
+/// 检测 Everything 安装情况（未安装/未运行/Lite 版/IPC 被禁用），返回修复建议码
+#[tauri::command]
+pub async fn detect_everything_installation(
+) -> Result<everything_search::EverythingInstallation, String> {
+    #[cfg(target_os = "windows")]
+    {
+        // sc/tasklist 调用较慢，放到后台线程执行
+        tokio::task::spawn_blocking(everything_search::windows::detect_installation)
+            .await
+            .map_err(|e| format!("Failed to detect Everything installation: {}", e))
+    }
+    #[cfg(not(target_os = "windows"))]
+    {
+        Err("Everything 仅在 Windows 上可用".to_string())
+    }
+}
+
 #[tauri::command]
 pub fn get_everything_path() -> Result<Option<String>, String> {
     #[cfg(target_os = "windows")]
@@ -2264,6 +2487,26 @@ pub fn get_everything_version() -> Result<Option<String>, String> {
     }
 }
 
+/// 读取日志，可按关联 ID（trace_id）与模块过滤；date 为 YYYYMMDD，默认今天
+#[tauri::command]
+pub async fn read_logs(
+    trace_id: Option<String>,
+    module: Option<String>,
+    date: Option<String>,
+    limit: Option<usize>,
+) -> Result<Vec<crate::logger::LogEntry>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        crate::logger::read_logs(
+            date.as_deref(),
+            trace_id.as_deref(),
+            module.as_deref(),
+            limit.unwrap_or(1000),
+        )
+    })
+    .await
+    .map_err(|e| format!("Failed to read logs: {}", e))?
+}
+
 #[tauri::command]
 pub fn get_everything_log_file_path() -> Result<Option<String>, String> {
     #[cfg(target_os = "windows")]
@@ -3726,11 +3969,17 @@ pub fn copy_file_to_downloads(source_path: String) -> Result<String, String> {
         .map(|s| s.to_string())
 }
 
+/// 打开搜索结果等文件：与快捷指令共用启动前检查，需要确认时 confirmed = true 才会真正执行
 #[tauri::command]
-pub fn launch_file(path: String, app: tauri::AppHandle) -> Result<(), String> {
+pub fn launch_file(
+    path: String,
+    confirmed: Option<bool>,
+    app: tauri::AppHandle,
+) -> Result<shortcuts::ShortcutLaunchResult, String> {
     // 注意：历史记录更新已由统一更新逻辑处理（handleLaunch 开头），这里不再更新
-    // Launch the file (keep using file_history::launch_file as it's just a utility function)
-    file_history::launch_file(&path)
+    let app_data_dir = get_app_data_dir(&app)?;
+    shortcuts::load_shortcuts(&app_data_dir)?;
+    shortcuts::launch_checked(&path, confirmed.unwrap_or(false))
 }
 
 #[tauri::command]
@@ -3745,11 +3994,12 @@ pub fn add_shortcut(
     name: String,
     path: String,
     icon: Option<String>,
+    confirm_before_run: Option<bool>,
     app: tauri::AppHandle,
 ) -> Result<shortcuts::ShortcutItem, String> {
     let app_data_dir = get_app_data_dir(&app)?;
     shortcuts::load_shortcuts(&app_data_dir)?;
-    shortcuts::add_shortcut(name, path, icon, &app_data_dir)
+    shortcuts::add_shortcut(name, path, icon, confirm_before_run.unwrap_or(false), &app_data_dir)
 }
 
 #[tauri::command]
@@ -3758,11 +4008,29 @@ pub fn update_shortcut(
     name: Option<String>,
     path: Option<String>,
     icon: Option<String>,
+    confirm_before_run: Option<bool>,
     app: tauri::AppHandle,
 ) -> Result<shortcuts::ShortcutItem, String> {
     let app_data_dir = get_app_data_dir(&app)?;
     shortcuts::load_shortcuts(&app_data_dir)?;
-    shortcuts::update_shortcut(id, name, path, icon, &app_data_dir)
+    shortcuts::update_shortcut(id, name, path, icon, confirm_before_run, &app_data_dir)
+}
+
+/// 从文件夹或拖入的一批 .lnk / .exe / .url 文件批量生成快捷指令，已存在的目标自动跳过
+#[tauri::command]
+pub async fn import_shortcuts_from_folder(
+    paths: Vec<String>,
+    recursive: Option<bool>,
+    app: tauri::AppHandle,
+) -> Result<shortcuts::ShortcutImportResult, String> {
+    let app_data_dir = get_app_data_dir(&app)?;
+    // 图标提取可能较慢，放到阻塞线程执行
+    tauri::async_runtime::spawn_blocking(move || {
+        shortcuts::load_shortcuts(&app_data_dir)?;
+        shortcuts::import_shortcuts(paths, recursive.unwrap_or(false), &app_data_dir)
+    })
+    .await
+    .map_err(|e| format!("Shortcut import task failed: {}", e))?
 }
 
 #[tauri::command]
@@ -3772,6 +4040,19 @@ pub fn delete_shortcut(id: String, app: tauri::AppHandle) -> Result<(), String>
     shortcuts::delete_shortcut(id, &app_data_dir)
 }
 
+/// 启动快捷指令：设置了运行前确认或检测到危险命令时，需 confirmed = true 才会真正执行
+#[tauri::command]
+pub fn launch_shortcut(
+    id: String,
+    confirmed: Option<bool>,
+    app: tauri::AppHandle,
+) -> Result<shortcuts::ShortcutLaunchResult, String> {
+    let app_data_dir = get_app_data_dir(&app)?;
+    shortcuts::load_shortcuts(&app_data_dir)?;
+    let shortcut = shortcuts::get_shortcut(&id).ok_or_else(|| format!("Shortcut not found: {}", id))?;
+    shortcuts::launch_checked(&shortcut.path, confirmed.unwrap_or(false))
+}
+
 #[tauri::command]
 pub fn open_url(url: String) -> Result<(), String> {
     #[cfg(target_os = "windows")]
@@ -4182,6 +4463,27 @@ pub fn get_plugin_usage(app: tauri::AppHandle) -> Result<Vec<plugin_usage::Plugi
     plugin_usage::list_plugin_usage(&app_data_dir)
 }
 
+/// 结果执行成功后回写打开次数（适用于所有 provider，key 为结果唯一标识）
+#[tauri::command]
+pub fn record_result_action(
+    key: String,
+    result_type: String,
+    app: tauri::AppHandle,
+) -> Result<crate::result_weights::ResultWeight, String> {
+    let app_data_dir = get_app_data_dir(&app)?;
+    crate::result_weights::record_result_open(key, result_type, &app_data_dir)
+}
+
+/// 批量获取结果权重，供前端排序时加权
+#[tauri::command]
+pub fn get_result_weights(
+    keys: Vec<String>,
+    app: tauri::AppHandle,
+) -> Result<std::collections::HashMap<String, f64>, String> {
+    let app_data_dir = get_app_data_dir(&app)?;
+    crate::result_weights::get_result_weights(&keys, &app_data_dir)
+}
+
 #[tauri::command]
 pub async fn show_memo_window(app: tauri::AppHandle) -> Result<(), String> {
     use tauri::Manager;
@@ -5083,7 +5385,19 @@ pub fn get_settings(app: tauri::AppHandle) -> Result<settings::Settings, String>
 #[tauri::command]
 pub fn save_settings(app: tauri::AppHandle, settings: settings::Settings) -> Result<(), String> {
     let app_data_dir = get_app_data_dir(&app)?;
-    settings::save_settings(&app_data_dir, &settings)
+    settings::save_settings(&app_data_dir, &settings)?;
+    crate::hotkey_dispatch::set_suppress_ms(settings.hotkey_suppress_ms);
+    crate::mouse_buttons::apply(settings.mouse_side_buttons);
+    crate::clipboard::apply_monitoring_enabled(&app, settings.clipboard_monitoring);
+    crate::dnd::set_quiet_periods(settings.quiet_periods);
+    crate::dnd::refresh(&app);
+    // 保留策略可能被收紧，立即在后台清理一次
+    std::thread::spawn(move || {
+        if let Err(e) = crate::clipboard::enforce_retention(&app_data_dir) {
+            eprintln!("[Clipboard] Retention cleanup failed: {}", e);
+        }
+    });
+    Ok(())
 }
 
 // ===== Everything Filters commands =====
@@ -5103,6 +5417,35 @@ pub fn save_everything_custom_filters(
     everything_filters::save_custom_filters(&app_data_dir, &filters)
 }
 
+#[tauri::command]
+pub fn get_everything_scopes(app: tauri::AppHandle) -> Result<everything_filters::SearchScopeConfig, String> {
+    let app_data_dir = get_app_data_dir(&app)?;
+    everything_filters::load_scopes(&app_data_dir)
+}
+
+#[tauri::command]
+pub fn save_everything_scopes(
+    app: tauri::AppHandle,
+    config: everything_filters::SearchScopeConfig,
+) -> Result<(), String> {
+    let app_data_dir = get_app_data_dir(&app)?;
+    everything_filters::save_scopes(&app_data_dir, &config)
+}
+
+/// 切换启用的作用域方案（None 表示不限制）
+#[tauri::command]
+pub fn set_active_everything_scope(app: tauri::AppHandle, scope_id: Option<String>) -> Result<(), String> {
+    let app_data_dir = get_app_data_dir(&app)?;
+    let mut config = everything_filters::load_scopes(&app_data_dir)?;
+    if let Some(id) = &scope_id {
+        if !config.scopes.iter().any(|scope| &scope.id == id) {
+            return Err(format!("Search scope {} not found", id));
+        }
+    }
+    config.active_id = scope_id;
+    everything_filters::save_scopes(&app_data_dir, &config)
+}
+
 #[tauri::command]
 pub fn get_hotkey_config(app: tauri::AppHandle) -> Result<Option<settings::HotkeyConfig>, String> {
     let app_data_dir = get_app_data_dir(&app)?;
@@ -5435,32 +5778,8 @@ pub fn save_app_center_hotkey(
 
 #[tauri::command]
 pub fn restart_app(app: tauri::AppHandle) -> Result<(), String> {
-    // 清理快捷键钩子
-    #[cfg(target_os = "windows")]
-    {
-        crate::hotkey_handler::windows::cleanup_hotkeys();
-    }
-    // 清理锁文件，以便重启后新实例可以正常启动
-    use std::fs;
-    use std::env;
-    use std::path::PathBuf;
-    
-    #[cfg(target_os = "windows")]
-    {
-        if let Ok(appdata) = env::var("APPDATA") {
-            let lock_file_path = PathBuf::from(appdata).join("ReFast").join("re-fast.lock");
-            let _ = fs::remove_file(&lock_file_path);
-        }
-    }
-    
-    #[cfg(not(target_os = "windows"))]
-    {
-        let lock_file_path = env::temp_dir().join("re-fast.lock");
-        let _ = fs::remove_file(&lock_file_path);
-    }
-    
-    app.restart();
-    Ok(())
+    // 统一清理（含锁文件，以便重启后新实例可以正常启动）
+    crate::shutdown::restart(&app)
 }
 
 #[cfg(target_os = "windows")]
@@ -5975,134 +6294,1076 @@ pub fn install_update(
     Ok(())
 }
 
-/// 退出应用（用于安装更新后）
+/// 设置隐身模式（卸载所有低层钩子并暂停剪贴板监控）
 #[tauri::command]
-pub fn quit_app(app_handle: tauri::AppHandle) -> Result<(), String> {
-    // 清理快捷键钩子
-    #[cfg(target_os = "windows")]
-    {
-        crate::hotkey_handler::windows::cleanup_hotkeys();
-    }
-    app_handle.exit(0);
-    Ok(())
+pub fn set_stealth_mode(enabled: bool, app: tauri::AppHandle) -> Result<bool, String> {
+    let state = crate::stealth::set_enabled(enabled)?;
+    let _ = app.emit("stealth-mode-changed", state);
+    Ok(state)
 }
 
-// ========================================
-// Clipboard Commands
-// ========================================
+/// 获取隐身模式状态
+#[tauri::command]
+pub fn get_stealth_mode() -> bool {
+    crate::stealth::is_enabled()
+}
 
+/// 开启 / 暂停剪贴板记录（持久化到设置），返回切换后的状态
 #[tauri::command]
-pub async fn get_all_clipboard_items(app_handle: tauri::AppHandle) -> Result<Vec<crate::clipboard::ClipboardItem>, String> {
-    let app_data_dir = get_app_data_dir(&app_handle)?;
-    crate::clipboard::get_all_clipboard_items(&app_data_dir)
+pub fn set_clipboard_monitoring(enabled: bool, app: tauri::AppHandle) -> Result<bool, String> {
+    let app_data_dir = get_app_data_dir(&app)?;
+    crate::clipboard::set_monitoring_enabled(&app, enabled, &app_data_dir)?;
+    Ok(crate::clipboard::is_monitoring_enabled())
 }
 
+/// 获取剪贴板记录状态
 #[tauri::command]
-pub async fn add_clipboard_item(
-    content: String,
-    content_type: String,
-    app_handle: tauri::AppHandle,
-) -> Result<crate::clipboard::ClipboardItem, String> {
-    let app_data_dir = get_app_data_dir(&app_handle)?;
-    crate::clipboard::add_clipboard_item(content, content_type, &app_data_dir)
+pub fn get_clipboard_monitoring() -> bool {
+    crate::clipboard::is_monitoring_enabled()
 }
 
+/// 手动开关勿扰模式（勿扰时段内即使关闭手动开关仍保持勿扰）
 #[tauri::command]
-pub async fn update_clipboard_item(
-    id: String,
-    content: String,
-    app_handle: tauri::AppHandle,
-) -> Result<crate::clipboard::ClipboardItem, String> {
-    let app_data_dir = get_app_data_dir(&app_handle)?;
-    crate::clipboard::update_clipboard_item(id, content, &app_data_dir)
+pub fn set_dnd_mode(enabled: bool, app: tauri::AppHandle) -> crate::dnd::DndStatus {
+    crate::dnd::set_manual(&app, enabled)
 }
 
+/// 获取勿扰模式状态
 #[tauri::command]
-pub async fn toggle_favorite_clipboard_item(
-    id: String,
-    app_handle: tauri::AppHandle,
-) -> Result<crate::clipboard::ClipboardItem, String> {
-    let app_data_dir = get_app_data_dir(&app_handle)?;
-    crate::clipboard::toggle_favorite_clipboard_item(id, &app_data_dir)
+pub fn get_dnd_status() -> crate::dnd::DndStatus {
+    crate::dnd::status()
 }
 
+/// 退出应用（用于安装更新后）
 #[tauri::command]
-pub async fn delete_clipboard_item(
-    id: String,
-    app_handle: tauri::AppHandle,
-) -> Result<(), String> {
-    let app_data_dir = get_app_data_dir(&app_handle)?;
-    crate::clipboard::delete_clipboard_item(id, &app_data_dir)
+pub fn quit_app(app_handle: tauri::AppHandle) -> Result<(), String> {
+    crate::shutdown::exit(&app_handle);
+    Ok(())
 }
 
+// ========================================
+// Pinned Result Commands
+// ========================================
+
+/// 获取固定在默认列表顶部的结果
+/// 启动器空查询默认面板（按设置聚合固定项、最近启动、剪贴板与今日提醒）
 #[tauri::command]
-pub async fn clear_clipboard_history(app_handle: tauri::AppHandle) -> Result<(), String> {
-    let app_data_dir = get_app_data_dir(&app_handle)?;
-    crate::clipboard::clear_clipboard_history(&app_data_dir)
+pub async fn get_default_panel(app: tauri::AppHandle) -> Result<crate::default_panel::DefaultPanel, String> {
+    let app_data_dir = get_app_data_dir(&app)?;
+    db::run_blocking(move || crate::default_panel::load(&app_data_dir)).await
 }
 
 #[tauri::command]
-pub async fn search_clipboard_items(
-    query: String,
-    app_handle: tauri::AppHandle,
-) -> Result<Vec<crate::clipboard::ClipboardItem>, String> {
-    let app_data_dir = get_app_data_dir(&app_handle)?;
-    crate::clipboard::search_clipboard_items(&query, &app_data_dir)
+pub fn get_pinned_results(app: tauri::AppHandle) -> Result<Vec<crate::pinned_results::PinnedResult>, String> {
+    let app_data_dir = get_app_data_dir(&app)?;
+    crate::pinned_results::get_pinned_results(&app_data_dir)
 }
 
+/// 固定结果到默认列表顶部
 #[tauri::command]
-pub async fn show_clipboard_window(app_handle: tauri::AppHandle) -> Result<(), String> {
-    if let Some(window) = app_handle.get_webview_window("clipboard") {
-        show_and_focus_window(&window)?;
-        Ok(())
-    } else {
-        use tauri::{WebviewUrl, WebviewWindowBuilder};
-        WebviewWindowBuilder::new(
-            &app_handle,
-            "clipboard",
-            WebviewUrl::App("index.html#/clipboard".into()),
-        )
-        .title("剪切板历史")
-        .inner_size(900.0, 700.0)
-        .center()
-        .build()
-        .map_err(|e| format!("Failed to create clipboard window: {}", e))?;
-        Ok(())
-    }
+pub fn pin_result(
+    item: crate::pinned_results::PinnedResult,
+    app: tauri::AppHandle,
+) -> Result<crate::pinned_results::PinnedResult, String> {
+    let app_data_dir = get_app_data_dir(&app)?;
+    crate::pinned_results::pin_result(item, &app_data_dir)
 }
 
+/// 取消固定
 #[tauri::command]
-pub async fn get_clipboard_image_data(image_path: String) -> Result<Vec<u8>, String> {
-    use std::fs;
-    fs::read(&image_path).map_err(|e| format!("Failed to read image file: {}", e))
+pub fn unpin_result(key: String, app: tauri::AppHandle) -> Result<(), String> {
+    let app_data_dir = get_app_data_dir(&app)?;
+    crate::pinned_results::unpin_result(&key, &app_data_dir)
 }
 
+/// 调整固定项顺序
 #[tauri::command]
-pub async fn copy_image_to_clipboard(image_path: String) -> Result<(), String> {
-    #[cfg(target_os = "windows")]
-    {
-        use std::fs;
-        use windows_sys::Win32::System::DataExchange::{
-            SetClipboardData, OpenClipboard, EmptyClipboard, CloseClipboard,
-        };
-        use windows_sys::Win32::System::Memory::{GlobalAlloc, GlobalLock, GlobalUnlock, GMEM_MOVEABLE};
+pub fn reorder_pinned_results(keys: Vec<String>, app: tauri::AppHandle) -> Result<(), String> {
+    let app_data_dir = get_app_data_dir(&app)?;
+    crate::pinned_results::reorder_pinned_results(&keys, &app_data_dir)
+}
+
+// ========================================
+// Form Template Commands
+// ========================================
+
+#[tauri::command]
+pub fn get_form_templates(app: tauri::AppHandle) -> Result<Vec<crate::form_templates::FormTemplate>, String> {
+    let app_data_dir = get_app_data_dir(&app)?;
+    crate::form_templates::get_all_templates(&app_data_dir)
+}
+
+/// 新建或更新表单模板（id 为空时新建）
+#[tauri::command]
+pub fn save_form_template(
+    id: Option<String>,
+    name: String,
+    fields: Vec<crate::form_templates::FormField>,
+    submit: Option<bool>,
+    app: tauri::AppHandle,
+) -> Result<crate::form_templates::FormTemplate, String> {
+    let app_data_dir = get_app_data_dir(&app)?;
+    crate::form_templates::save_template(id, name, fields, submit.unwrap_or(false), &app_data_dir)
+}
+
+#[tauri::command]
+pub fn delete_form_template(id: String, app: tauri::AppHandle) -> Result<(), String> {
+    let app_data_dir = get_app_data_dir(&app)?;
+    crate::form_templates::delete_template(&id, &app_data_dir)
+}
+
+/// 隐藏启动器并把模板字段依次填入之前的前台窗口
+#[tauri::command]
+pub async fn fill_form_template(id: String, app: tauri::AppHandle) -> Result<(), String> {
+    let app_data_dir = get_app_data_dir(&app)?;
+    let template = crate::form_templates::get_template(&id, &app_data_dir)?
+        .ok_or_else(|| format!("Form template {} not found", id))?;
+
+    hide_launcher(app)?;
+
+    tokio::task::spawn_blocking(move || {
+        // 等待焦点回到目标窗口
+        std::thread::sleep(Duration::from_millis(200));
+        crate::form_templates::fill(&template)
+    })
+    .await
+    .map_err(|e| format!("Failed to fill form: {}", e))?
+}
+
+// ========================================
+// Reminder Commands
+// ========================================
+
+/// 创建提醒，delay_minutes 分钟后弹出带"稍后提醒/完成"按钮的通知
+#[tauri::command]
+pub fn create_reminder(
+    title: String,
+    body: Option<String>,
+    delay_minutes: u64,
+    app: tauri::AppHandle,
+) -> Result<crate::reminders::Reminder, String> {
+    if title.trim().is_empty() {
+        return Err("提醒标题不能为空".to_string());
+    }
+    Ok(crate::reminders::schedule(
+        &app,
+        title,
+        body.unwrap_or_default(),
+        delay_minutes * 60,
+    ))
+}
+
+#[tauri::command]
+pub fn list_reminders() -> Vec<crate::reminders::Reminder> {
+    crate::reminders::list()
+}
+
+#[tauri::command]
+pub fn cancel_reminder(id: String) -> Result<(), String> {
+    if crate::reminders::cancel(&id) {
+        Ok(())
+    } else {
+        Err(format!("Reminder not found: {}", id))
+    }
+}
+
+// ========================================
+// Clipboard Commands
+// ========================================
+
+/// 已废弃：一次性返回全部历史，请改用 list_clipboard_items 分页读取
+#[tauri::command]
+pub async fn get_all_clipboard_items(app_handle: tauri::AppHandle) -> Result<Vec<crate::clipboard::ClipboardItem>, String> {
+    eprintln!("[Clipboard] get_all_clipboard_items is deprecated, use list_clipboard_items instead");
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    db::run_blocking(move || crate::clipboard::get_all_clipboard_items(&app_data_dir)).await
+}
+
+/// 分页读取剪切板历史，返回当前页与满足过滤条件的总数，供前端虚拟列表使用
+#[tauri::command]
+pub async fn list_clipboard_items(
+    offset: usize,
+    limit: usize,
+    filter: Option<crate::clipboard::ClipboardListFilter>,
+    app_handle: tauri::AppHandle,
+) -> Result<crate::clipboard::ClipboardPage, String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    db::run_blocking(move || {
+        crate::clipboard::list_clipboard_items(offset, limit, filter.unwrap_or_default(), &app_data_dir)
+    })
+    .await
+}
+
+/// 读取条目完整内容（外部存储的大文本按需从文件加载）
+#[tauri::command]
+pub async fn get_clipboard_item_content(id: String, app_handle: tauri::AppHandle) -> Result<String, String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    db::run_blocking(move || crate::clipboard::get_full_content(&id, &app_data_dir)).await
+}
+
+/// 把剪贴板文本条目生成二维码，返回 PNG 的 data:image/png;base64 URL，scale 为每个模块的像素数（默认 8）
+#[tauri::command]
+pub async fn get_clipboard_item_qr_code(
+    id: String,
+    scale: Option<usize>,
+    app_handle: tauri::AppHandle,
+) -> Result<String, String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    db::run_blocking(move || {
+        let item = crate::clipboard::get_clipboard_item(&id, &app_data_dir)?;
+        if item.content_type == "image" {
+            return Err("Cannot generate a QR code from an image item".to_string());
+        }
+        let content = crate::clipboard::get_full_content(&id, &app_data_dir)?;
+        crate::qrcode::QrCode::encode_text(&content)?.to_data_url(scale.unwrap_or(8).clamp(1, 32), 4)
+    })
+    .await
+}
+
+/// 对文本条目执行转换（大小写、编解码、JSON 格式化等），结果作为新条目写入历史
+#[tauri::command]
+pub async fn transform_clipboard_item(
+    id: String,
+    op: crate::clipboard_transform::TransformOp,
+    app_handle: tauri::AppHandle,
+) -> Result<crate::clipboard::ClipboardItem, String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    db::run_blocking(move || crate::clipboard_transform::transform_item(&id, op, &app_data_dir)).await
+}
+
+/// 导出剪贴板历史到 JSON 归档（.gz 结尾时压缩），ids 为空时导出全部
+#[tauri::command]
+pub async fn export_clipboard_history(
+    path: String,
+    ids: Option<Vec<String>>,
+    app_handle: tauri::AppHandle,
+) -> Result<usize, String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    db::run_blocking(move || {
+        crate::clipboard_archive::export_history(ids, Path::new(&path), &app_data_dir)
+    })
+    .await
+}
+
+/// 获取剪贴板同步状态
+#[tauri::command]
+pub fn get_clipboard_sync_status(
+    app_handle: tauri::AppHandle,
+) -> Result<crate::clipboard_sync::ClipboardSyncStatus, String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    Ok(crate::clipboard_sync::status(&app_data_dir))
+}
+
+/// 立即与 WebDAV 同步剪贴板历史
+#[tauri::command]
+pub async fn sync_clipboard_now(
+    app_handle: tauri::AppHandle,
+) -> Result<crate::clipboard_sync::ClipboardSyncStatus, String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    crate::clipboard_sync::sync_now(&app_handle, &app_data_dir).await
+}
+
+/// 用未保存的同步设置测试 WebDAV 连接
+#[tauri::command]
+pub async fn test_clipboard_sync(
+    config: crate::settings::ClipboardSyncSettings,
+) -> Result<(), String> {
+    crate::clipboard_sync::test_connection(&config).await
+}
+
+/// 按当前图片存储设置批量重新压缩剪贴板历史中的图片
+#[tauri::command]
+pub async fn recompress_clipboard_images(
+    app_handle: tauri::AppHandle,
+) -> Result<crate::clipboard_image::RecompressResult, String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    db::run_blocking(move || crate::clipboard_image::recompress_all(&app_data_dir)).await
+}
+
+/// 获取剪贴板历史加密状态
+#[tauri::command]
+pub fn get_clipboard_encryption_status(
+    app_handle: tauri::AppHandle,
+) -> Result<crate::clipboard_crypto::EncryptionStatus, String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    crate::clipboard_crypto::status(&app_data_dir)
+}
+
+/// 启用剪贴板历史加密并加密已有条目，返回加密的条目数；passphrase 为空时只依赖当前 Windows 用户的 DPAPI
+#[tauri::command]
+pub async fn enable_clipboard_encryption(
+    passphrase: Option<String>,
+    app_handle: tauri::AppHandle,
+) -> Result<usize, String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    db::run_blocking(move || crate::clipboard_crypto::enable(passphrase.as_deref(), &app_data_dir)).await
+}
+
+/// 关闭剪贴板历史加密并解密已有条目，返回解密的条目数
+#[tauri::command]
+pub async fn disable_clipboard_encryption(app_handle: tauri::AppHandle) -> Result<usize, String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    db::run_blocking(move || crate::clipboard_crypto::disable(&app_data_dir)).await
+}
+
+/// 输入口令解锁加密的剪贴板历史
+#[tauri::command]
+pub async fn unlock_clipboard_encryption(
+    passphrase: String,
+    app_handle: tauri::AppHandle,
+) -> Result<(), String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    db::run_blocking(move || {
+        crate::clipboard_crypto::unlock(&passphrase, &app_data_dir)?;
+        // 锁定期间跳过的内容哈希回填在解锁后补上
+        if let Err(e) = crate::clipboard::backfill_content_hashes(&app_data_dir) {
+            eprintln!("[Clipboard] Content hash backfill failed: {}", e);
+        }
+        Ok(())
+    })
+    .await
+}
+
+/// 开启局域网剪贴板接收，返回配对地址与二维码（port 省略时使用随机端口）
+#[tauri::command]
+pub fn start_lan_receive(
+    port: Option<u16>,
+    app_handle: tauri::AppHandle,
+) -> Result<crate::lan_receive::LanReceiveStatus, String> {
+    crate::lan_receive::start(app_handle, port.unwrap_or(0))
+}
+
+/// 关闭局域网剪贴板接收
+#[tauri::command]
+pub fn stop_lan_receive() -> Result<(), String> {
+    crate::lan_receive::stop();
+    Ok(())
+}
+
+/// 获取局域网剪贴板接收状态
+#[tauri::command]
+pub fn get_lan_receive_status() -> Result<crate::lan_receive::LanReceiveStatus, String> {
+    crate::lan_receive::status()
+}
+
+/// 从归档导入剪贴板历史，conflict 决定与现有相同内容条目的处理方式（默认跳过）
+#[tauri::command]
+pub async fn import_clipboard_history(
+    path: String,
+    conflict: Option<crate::clipboard_archive::ImportConflict>,
+    app_handle: tauri::AppHandle,
+) -> Result<crate::clipboard_archive::ImportResult, String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    db::run_blocking(move || {
+        crate::clipboard_archive::import_history(
+            Path::new(&path),
+            conflict.unwrap_or_default(),
+            &app_data_dir,
+        )
+    })
+    .await
+}
+
+#[tauri::command]
+pub async fn add_clipboard_item(
+    content: String,
+    content_type: String,
+    app_handle: tauri::AppHandle,
+) -> Result<crate::clipboard::ClipboardItem, String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    db::run_blocking(move || crate::clipboard::add_clipboard_item(content, content_type, &app_data_dir)).await
+}
+
+#[tauri::command]
+pub async fn update_clipboard_item(
+    id: String,
+    content: String,
+    app_handle: tauri::AppHandle,
+) -> Result<crate::clipboard::ClipboardItem, String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    db::run_blocking(move || crate::clipboard::update_clipboard_item(id, content, &app_data_dir)).await
+}
+
+#[tauri::command]
+pub async fn toggle_favorite_clipboard_item(
+    id: String,
+    app_handle: tauri::AppHandle,
+) -> Result<crate::clipboard::ClipboardItem, String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    let item =
+        db::run_blocking(move || crate::clipboard::toggle_favorite_clipboard_item(id, &app_data_dir)).await?;
+    crate::snippet_expander::reload();
+    Ok(item)
+}
+
+/// 设置收藏条目的片段缩写（None 表示移除）
+#[tauri::command]
+pub async fn set_clipboard_abbreviation(
+    id: String,
+    abbreviation: Option<String>,
+    app_handle: tauri::AppHandle,
+) -> Result<crate::clipboard::ClipboardItem, String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    let item = db::run_blocking(move || {
+        crate::clipboard::set_clipboard_abbreviation(id, abbreviation, &app_data_dir)
+    })
+    .await?;
+    crate::snippet_expander::reload();
+    Ok(item)
+}
+
+#[tauri::command]
+pub async fn delete_clipboard_item(
+    id: String,
+    app_handle: tauri::AppHandle,
+) -> Result<(), String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    db::run_blocking(move || crate::clipboard::delete_clipboard_item(id, &app_data_dir)).await?;
+    crate::snippet_expander::reload();
+    Ok(())
+}
+
+/// 批量删除剪切板条目，返回删除的条目数
+#[tauri::command]
+pub async fn batch_delete_clipboard_items(
+    ids: Vec<String>,
+    app_handle: tauri::AppHandle,
+) -> Result<usize, String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    let deleted = db::run_blocking(move || crate::clipboard::batch_delete_items(ids, &app_data_dir)).await?;
+    crate::snippet_expander::reload();
+    Ok(deleted)
+}
+
+/// 设置剪切板条目 minutes 分钟后自动删除（None 取消），返回到期时间（Unix 秒）
+#[tauri::command]
+pub async fn set_clipboard_item_expiry(
+    id: String,
+    minutes: Option<u32>,
+    app_handle: tauri::AppHandle,
+) -> Result<Option<u64>, String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    db::run_blocking(move || crate::clipboard_expiry::set_expiry(id, minutes, &app_data_dir)).await
+}
+
+/// 批量收藏 / 取消收藏剪切板条目，返回更新的条目数
+#[tauri::command]
+pub async fn batch_favorite_clipboard_items(
+    ids: Vec<String>,
+    favorite: bool,
+    app_handle: tauri::AppHandle,
+) -> Result<usize, String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    let updated =
+        db::run_blocking(move || crate::clipboard::batch_set_favorite(ids, favorite, &app_data_dir)).await?;
+    crate::snippet_expander::reload();
+    Ok(updated)
+}
+
+/// 批量删除剪切板条目（batch_delete_clipboard_items 的别名）
+#[tauri::command]
+pub async fn delete_clipboard_items(
+    ids: Vec<String>,
+    app_handle: tauri::AppHandle,
+) -> Result<usize, String> {
+    batch_delete_clipboard_items(ids, app_handle).await
+}
+
+/// 批量收藏 / 取消收藏剪切板条目（batch_favorite_clipboard_items 的别名）
+#[tauri::command]
+pub async fn favorite_clipboard_items(
+    ids: Vec<String>,
+    flag: bool,
+    app_handle: tauri::AppHandle,
+) -> Result<usize, String> {
+    batch_favorite_clipboard_items(ids, flag, app_handle).await
+}
+
+#[tauri::command]
+pub async fn clear_clipboard_history(app_handle: tauri::AppHandle) -> Result<(), String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    db::run_blocking(move || crate::clipboard::clear_clipboard_history(&app_data_dir)).await
+}
+
+/// 清空剪切板历史但保留最近 keep 条（收藏项始终保留），返回删除的条目数
+#[tauri::command]
+pub async fn clear_clipboard_except_recent(
+    keep: usize,
+    app_handle: tauri::AppHandle,
+) -> Result<usize, String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    db::run_blocking(move || crate::clipboard::clear_except_recent(keep, &app_data_dir)).await
+}
+
+/// 获取因高频写入被临时屏蔽的剪贴板来源
+#[tauri::command]
+pub fn get_clipboard_blocked_sources() -> Vec<crate::clipboard::BlockedClipboardSource> {
+    crate::clipboard::get_blocked_sources()
+}
+
+/// 解除对剪贴板来源的屏蔽
+#[tauri::command]
+pub fn unblock_clipboard_source(source: String) {
+    crate::clipboard::unblock_source(&source);
+}
+
+/// 擦除剪贴板历史、日志、录制与使用统计（文件覆写后删除），需输入确认短语
+#[tauri::command]
+pub async fn wipe_all_data(
+    confirm_phrase: String,
+    app_handle: tauri::AppHandle,
+) -> Result<crate::privacy::WipeReport, String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    db::run_blocking(move || crate::privacy::wipe_all_data(&app_data_dir, &confirm_phrase)).await
+}
+
+#[tauri::command]
+pub async fn search_clipboard_items(
+    query: String,
+    source: Option<String>,
+    tag: Option<String>,
+    app_handle: tauri::AppHandle,
+) -> Result<Vec<crate::clipboard::ClipboardItem>, String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    db::run_blocking(move || {
+        crate::clipboard::search_clipboard_items(&query, source.as_deref(), tag.as_deref(), &app_data_dir)
+    })
+    .await
+}
+
+#[tauri::command]
+pub async fn add_clipboard_tag(id: String, tag: String, app_handle: tauri::AppHandle) -> Result<(), String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    db::run_blocking(move || crate::clipboard::add_tag(id, &tag, &app_data_dir)).await
+}
+
+/// 批量给剪切板条目添加标签，返回新添加标签的条目数
+#[tauri::command]
+pub async fn batch_add_clipboard_tag(
+    ids: Vec<String>,
+    tag: String,
+    app_handle: tauri::AppHandle,
+) -> Result<usize, String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    db::run_blocking(move || crate::clipboard::batch_add_tag(ids, &tag, &app_data_dir)).await
+}
+
+#[tauri::command]
+pub async fn remove_clipboard_tag(id: String, tag: String, app_handle: tauri::AppHandle) -> Result<(), String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    db::run_blocking(move || crate::clipboard::remove_tag(id, &tag, &app_data_dir)).await
+}
+
+#[tauri::command]
+pub async fn list_clipboard_tags(
+    app_handle: tauri::AppHandle,
+) -> Result<Vec<crate::clipboard::TagCount>, String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    db::run_blocking(move || crate::clipboard::list_tags(&app_data_dir)).await
+}
+
+#[tauri::command]
+pub async fn list_clipboard_boards(
+    app_handle: tauri::AppHandle,
+) -> Result<Vec<crate::clipboard::ClipboardBoard>, String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    db::run_blocking(move || crate::clipboard::list_boards(&app_data_dir)).await
+}
+
+#[tauri::command]
+pub async fn create_clipboard_board(
+    name: String,
+    app_handle: tauri::AppHandle,
+) -> Result<crate::clipboard::ClipboardBoard, String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    db::run_blocking(move || crate::clipboard::create_board(&name, &app_data_dir)).await
+}
+
+#[tauri::command]
+pub async fn rename_clipboard_board(id: String, name: String, app_handle: tauri::AppHandle) -> Result<(), String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    db::run_blocking(move || crate::clipboard::rename_board(id, &name, &app_data_dir)).await
+}
+
+#[tauri::command]
+pub async fn delete_clipboard_board(id: String, app_handle: tauri::AppHandle) -> Result<(), String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    db::run_blocking(move || crate::clipboard::delete_board(id, &app_data_dir)).await
+}
+
+#[tauri::command]
+pub async fn reorder_clipboard_boards(ids: Vec<String>, app_handle: tauri::AppHandle) -> Result<(), String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    db::run_blocking(move || crate::clipboard::reorder_boards(ids, &app_data_dir)).await
+}
+
+/// 把条目移动到看板（board_id 为空时移出看板），position 为空时追加到末尾
+#[tauri::command]
+pub async fn move_clipboard_item_to_board(
+    item_id: String,
+    board_id: Option<String>,
+    position: Option<u32>,
+    app_handle: tauri::AppHandle,
+) -> Result<(), String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    db::run_blocking(move || {
+        crate::clipboard::move_item_to_board(item_id, board_id, position, &app_data_dir)
+    })
+    .await
+}
+
+#[tauri::command]
+pub async fn reorder_clipboard_board_items(
+    board_id: String,
+    item_ids: Vec<String>,
+    app_handle: tauri::AppHandle,
+) -> Result<(), String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    db::run_blocking(move || crate::clipboard::reorder_board_items(board_id, item_ids, &app_data_dir)).await
+}
+
+#[tauri::command]
+pub async fn get_clipboard_board_items(
+    board_id: String,
+    app_handle: tauri::AppHandle,
+) -> Result<Vec<crate::clipboard::ClipboardItem>, String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    db::run_blocking(move || crate::clipboard::get_board_items(&board_id, &app_data_dir)).await
+}
+
+/// 按正则表达式搜索剪切板历史（默认不区分大小写）
+#[tauri::command]
+pub async fn search_clipboard_items_regex(
+    pattern: String,
+    case_insensitive: Option<bool>,
+    app_handle: tauri::AppHandle,
+) -> Result<Vec<crate::clipboard::ClipboardItem>, String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    db::run_blocking(move || {
+        crate::clipboard::search_clipboard_items_regex(&pattern, case_insensitive.unwrap_or(true), &app_data_dir)
+    })
+    .await
+}
+
+/// 剪切板历史统计（默认最近 30 天的按天新增）
+#[tauri::command]
+pub async fn get_clipboard_stats(
+    days: Option<u32>,
+    app_handle: tauri::AppHandle,
+) -> Result<crate::clipboard::ClipboardStats, String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    db::run_blocking(move || crate::clipboard::get_clipboard_stats(days.unwrap_or(30), &app_data_dir)).await
+}
+
+/// 剪贴板监控打开剪贴板的重试与失败统计
+#[tauri::command]
+pub fn get_clipboard_open_stats() -> crate::clipboard::ClipboardOpenStats {
+    crate::clipboard::get_open_stats()
+}
+
+/// 合并多条剪切板历史为 Markdown/纯文本
+/// 指定 output_path 时写入文件，否则写入剪切板；返回合并后的内容
+#[tauri::command]
+pub async fn merge_clipboard_items(
+    ids: Vec<String>,
+    options: Option<crate::clipboard::MergeOptions>,
+    output_path: Option<String>,
+    app_handle: tauri::AppHandle,
+) -> Result<String, String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    let merged = db::run_blocking(move || {
+        crate::clipboard::merge_clipboard_items(&ids, &options.unwrap_or_default(), &app_data_dir)
+    })
+    .await?;
+
+    match output_path {
+        Some(path) => {
+            fs::write(&path, &merged).map_err(|e| format!("Failed to write merged file: {}", e))?;
+        }
+        None => {
+            #[cfg(target_os = "windows")]
+            crate::clipboard::monitor::set_clipboard_text(&merged)?;
+            #[cfg(not(target_os = "windows"))]
+            return Err("Clipboard is only supported on Windows".to_string());
+        }
+    }
+    Ok(merged)
+}
+
+/// 剪切板弹出面板的窗口标签
+const CLIPBOARD_POPUP_LABEL: &str = "clipboard-popup";
+/// 剪切板弹出面板大小（逻辑像素）
+const CLIPBOARD_POPUP_SIZE: (f64, f64) = (360.0, 440.0);
+
+/// 剪切板弹出面板快捷键在插件快捷键表中的 ID
+pub const CLIPBOARD_POPUP_HOTKEY_ID: &str = "clipboard_popup";
+
+/// 显示剪切板历史窗口；popup 为 true 时在文本光标（或鼠标）附近切换显示置顶的小面板，失去焦点后自动隐藏
+#[tauri::command]
+pub async fn show_clipboard_window(app_handle: tauri::AppHandle, popup: Option<bool>) -> Result<(), String> {
+    if popup.unwrap_or(false) {
+        return toggle_clipboard_popup(&app_handle);
+    }
+
+    // 记录打开前的前台窗口，粘贴条目时切回该窗口
+    #[cfg(target_os = "windows")]
+    crate::foreground::windows::remember_previous();
+
+    if let Some(window) = app_handle.get_webview_window("clipboard") {
+        show_and_focus_window(&window)?;
+        Ok(())
+    } else {
+        use tauri::{WebviewUrl, WebviewWindowBuilder};
+        WebviewWindowBuilder::new(
+            &app_handle,
+            "clipboard",
+            WebviewUrl::App("index.html#/clipboard".into()),
+        )
+        .title("剪切板历史")
+        .inner_size(900.0, 700.0)
+        .center()
+        .build()
+        .map_err(|e| format!("Failed to create clipboard window: {}", e))?;
+        Ok(())
+    }
+}
+
+fn toggle_clipboard_popup(app_handle: &tauri::AppHandle) -> Result<(), String> {
+    if let Some(window) = app_handle.get_webview_window(CLIPBOARD_POPUP_LABEL) {
+        if window.is_visible().unwrap_or(false) {
+            crate::foreground::hide_and_restore(&window);
+            return Ok(());
+        }
+    }
+
+    // 面板显示前读取前台窗口的文本光标位置
+    #[cfg(target_os = "windows")]
+    let anchor = crate::foreground::windows::caret_or_cursor_position();
+
+    let window = match app_handle.get_webview_window(CLIPBOARD_POPUP_LABEL) {
+        Some(window) => window,
+        None => {
+            use tauri::{WebviewUrl, WebviewWindowBuilder};
+            let window = WebviewWindowBuilder::new(
+                app_handle,
+                CLIPBOARD_POPUP_LABEL,
+                WebviewUrl::App("index.html#/clipboard".into()),
+            )
+            .title("剪切板历史")
+            .inner_size(CLIPBOARD_POPUP_SIZE.0, CLIPBOARD_POPUP_SIZE.1)
+            .always_on_top(true)
+            .decorations(false)
+            .skip_taskbar(true)
+            .resizable(false)
+            .visible(false)
+            .build()
+            .map_err(|e| format!("Failed to create clipboard popup: {}", e))?;
+            let popup = window.clone();
+            window.on_window_event(move |event| {
+                if let tauri::WindowEvent::Focused(false) = event {
+                    let _ = popup.hide();
+                }
+            });
+            window
+        }
+    };
+
+    #[cfg(target_os = "windows")]
+    if let Some((x, y)) = anchor {
+        let size = window
+            .outer_size()
+            .map_err(|e| format!("Failed to get clipboard popup size: {}", e))?;
+        let (width, height) = (size.width as i32, size.height as i32);
+        // 默认显示在光标右下方，超出工作区时翻到上方 / 向左收回
+        let (mut left, mut top) = (x, y + 4);
+        if let Some((area_left, area_top, area_right, area_bottom)) = crate::foreground::windows::work_area_at(x, y) {
+            if top + height > area_bottom {
+                top = (y - height - 24).max(area_top);
+            }
+            left = left.min(area_right - width).max(area_left);
+        }
+        window
+            .set_position(tauri::PhysicalPosition::new(left, top))
+            .map_err(|e| format!("Failed to position clipboard popup: {}", e))?;
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    let _ = window.center();
+
+    // 显示时记录之前的前台窗口，粘贴条目时切回该窗口
+    crate::foreground::show_and_activate(&window);
+    Ok(())
+}
+
+/// 获取剪切板弹出面板快捷键
+#[tauri::command]
+pub fn get_clipboard_popup_hotkey(app: tauri::AppHandle) -> Result<Option<settings::HotkeyConfig>, String> {
+    let app_data_dir = get_app_data_dir(&app)?;
+    Ok(settings::load_settings(&app_data_dir)?.clipboard_popup_hotkey)
+}
+
+/// 保存剪切板弹出面板快捷键（None 表示取消）
+#[tauri::command]
+pub fn save_clipboard_popup_hotkey(
+    app: tauri::AppHandle,
+    config: Option<settings::HotkeyConfig>,
+) -> Result<(), String> {
+    let app_data_dir = get_app_data_dir(&app)?;
+    let mut settings = settings::load_settings(&app_data_dir)?;
+    settings.clipboard_popup_hotkey = config.clone();
+    settings::save_settings(&app_data_dir, &settings)?;
+
+    #[cfg(target_os = "windows")]
+    {
+        let hotkey_id = CLIPBOARD_POPUP_HOTKEY_ID.to_string();
+        let result = match config {
+            Some(hotkey) => crate::hotkey_handler::windows::register_plugin_hotkey(hotkey_id, hotkey),
+            None => crate::hotkey_handler::windows::unregister_plugin_hotkey(&hotkey_id),
+        };
+        if let Err(e) = result {
+            eprintln!("Failed to update clipboard popup hotkey: {}", e);
+        }
+    }
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_clipboard_image_data(image_path: String) -> Result<Vec<u8>, String> {
+    use std::fs;
+    fs::read(&image_path).map_err(|e| format!("Failed to read image file: {}", e))
+}
+
+/// 把文件条目（每行一个路径）重新以文件形式放回剪贴板，跳过已不存在的文件
+#[tauri::command]
+pub async fn copy_files_to_clipboard(content: String) -> Result<(), String> {
+    let paths: Vec<String> = content
+        .lines()
+        .map(|line| line.trim().to_string())
+        .filter(|line| !line.is_empty() && Path::new(line).exists())
+        .collect();
+    if paths.is_empty() {
+        return Err("None of the copied files exist anymore".to_string());
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        crate::clipboard::monitor::set_clipboard_files(&paths)
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        Err("Not implemented for this platform".to_string())
+    }
+}
+
+/// 获取与剪切板条目内容匹配的自定义动作（用于条目右键菜单）
+#[tauri::command]
+pub async fn get_clipboard_item_actions(
+    id: String,
+    app_handle: tauri::AppHandle,
+) -> Result<Vec<crate::clipboard_actions::MatchedAction>, String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    db::run_blocking(move || {
+        let (content, _) = crate::clipboard::get_item_formats(&id, &app_data_dir)?;
+        let settings = settings::load_settings(&app_data_dir)?;
+        Ok(crate::clipboard_actions::matching_actions(&content, &settings.clipboard_actions))
+    })
+    .await
+}
+
+/// 对剪切板条目执行自定义动作
+#[tauri::command]
+pub async fn run_clipboard_action(
+    action_id: String,
+    id: String,
+    app_handle: tauri::AppHandle,
+) -> Result<(), String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    let (content, settings) = db::run_blocking(move || {
+        let (content, _) = crate::clipboard::get_item_formats(&id, &app_data_dir)?;
+        Ok((content, settings::load_settings(&app_data_dir)?))
+    })
+    .await?;
+    let action = settings
+        .clipboard_actions
+        .iter()
+        .find(|action| action.id == action_id)
+        .ok_or_else(|| format!("Clipboard action {} not found", action_id))?;
+    crate::clipboard_actions::run_action(action, &content)
+}
+
+/// 以原格式粘贴剪切板条目：写回纯文本与保存的 HTML / RTF 后模拟 Ctrl+V
+#[tauri::command]
+pub async fn paste_clipboard_item_with_format(
+    id: String,
+    window: tauri::WebviewWindow,
+    app_handle: tauri::AppHandle,
+) -> Result<(), String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    let (content, rich) =
+        db::run_blocking(move || crate::clipboard::get_item_formats(&id, &app_data_dir)).await?;
+
+    #[cfg(target_os = "windows")]
+    {
+        crate::clipboard::monitor::set_clipboard_rich(&content, &rich)?;
+        paste_into_previous_window(&window)
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        let _ = (content, rich, window);
+        Err("Not implemented for this platform".to_string())
+    }
+}
+
+/// 以纯文本粘贴剪切板条目：只写入 CF_UNICODETEXT（去掉所有格式），
+/// 回到打开启动器/剪切板窗口前的前台窗口后模拟 Ctrl+V
+#[tauri::command]
+pub async fn paste_clipboard_item_as_plain_text(
+    id: String,
+    window: tauri::WebviewWindow,
+    app_handle: tauri::AppHandle,
+) -> Result<(), String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    let (content, _) =
+        db::run_blocking(move || crate::clipboard::get_item_formats(&id, &app_data_dir)).await?;
+
+    #[cfg(target_os = "windows")]
+    {
+        crate::clipboard::monitor::set_clipboard_text(&content)?;
+        paste_into_previous_window(&window)
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        let _ = (content, window);
+        Err("Not implemented for this platform".to_string())
+    }
+}
+
+/// 粘贴剪切板条目到打开窗口前的前台窗口：按条目类型写回剪贴板（文本含 HTML / RTF，
+/// 文件与图片按原类型），隐藏当前窗口并切回之前的前台窗口后模拟 Ctrl+V
+#[tauri::command]
+pub async fn paste_to_previous_window(
+    id: String,
+    window: tauri::WebviewWindow,
+    app_handle: tauri::AppHandle,
+) -> Result<(), String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    let (content_type, content, rich) = db::run_blocking(move || {
+        let item = crate::clipboard::get_clipboard_item(&id, &app_data_dir)?;
+        let (content, rich) = crate::clipboard::get_item_formats(&id, &app_data_dir)?;
+        Ok((item.content_type, content, rich))
+    })
+    .await?;
+
+    #[cfg(target_os = "windows")]
+    {
+        match content_type.as_str() {
+            "image" => copy_image_to_clipboard(content).await?,
+            "file" => copy_files_to_clipboard(content).await?,
+            _ => crate::clipboard::monitor::set_clipboard_rich(&content, &rich)?,
+        }
+        paste_into_previous_window(&window)
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        let _ = (content_type, content, rich, window);
+        Err("Not implemented for this platform".to_string())
+    }
+}
+
+/// 隐藏调用粘贴的窗口，把焦点还给之前的前台窗口后模拟 Ctrl+V
+#[cfg(target_os = "windows")]
+fn paste_into_previous_window(window: &tauri::WebviewWindow) -> Result<(), String> {
+    crate::foreground::hide_and_restore(window);
+    paste_text_to_cursor(String::new())
+}
+
+/// 连续粘贴专用快捷键的 ID
+pub const PASTE_STACK_HOTKEY_ID: &str = "paste_stack";
+
+/// 按选中顺序建立连续粘贴栈，第一条立即放入剪贴板
+#[tauri::command]
+pub async fn start_paste_stack(
+    ids: Vec<String>,
+    app_handle: tauri::AppHandle,
+) -> Result<crate::clipboard::PasteStackStatus, String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    db::run_blocking(move || crate::clipboard::start_paste_stack(ids, &app_data_dir)).await
+}
+
+#[tauri::command]
+pub fn cancel_paste_stack() -> crate::clipboard::PasteStackStatus {
+    crate::clipboard::cancel_paste_stack()
+}
+
+#[tauri::command]
+pub fn get_paste_stack_status() -> crate::clipboard::PasteStackStatus {
+    crate::clipboard::paste_stack_status()
+}
+
+/// 粘贴栈顶条目：模拟 Ctrl+V，由键盘钩子检测到粘贴后切换到下一条
+#[tauri::command]
+pub fn paste_next_from_stack() -> Result<(), String> {
+    if !crate::clipboard::is_paste_stack_active() {
+        return Err("Paste stack is empty".to_string());
+    }
+    paste_text_to_cursor(String::new())
+}
+
+/// 获取连续粘贴快捷键
+#[tauri::command]
+pub fn get_paste_stack_hotkey(app: tauri::AppHandle) -> Result<Option<settings::HotkeyConfig>, String> {
+    let app_data_dir = get_app_data_dir(&app)?;
+    Ok(settings::load_settings(&app_data_dir)?.paste_stack_hotkey)
+}
+
+/// 保存连续粘贴快捷键（None 表示取消）
+#[tauri::command]
+pub fn save_paste_stack_hotkey(
+    app: tauri::AppHandle,
+    config: Option<settings::HotkeyConfig>,
+) -> Result<(), String> {
+    let app_data_dir = get_app_data_dir(&app)?;
+    let mut settings = settings::load_settings(&app_data_dir)?;
+    settings.paste_stack_hotkey = config.clone();
+    settings::save_settings(&app_data_dir, &settings)?;
+
+    #[cfg(target_os = "windows")]
+    {
+        let hotkey_id = PASTE_STACK_HOTKEY_ID.to_string();
+        let result = match config {
+            Some(hotkey) => crate::hotkey_handler::windows::register_plugin_hotkey(hotkey_id, hotkey),
+            None => crate::hotkey_handler::windows::unregister_plugin_hotkey(&hotkey_id),
+        };
+        if let Err(e) = result {
+            eprintln!("Failed to update paste stack hotkey: {}", e);
+        }
+    }
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn copy_image_to_clipboard(image_path: String) -> Result<(), String> {
+    #[cfg(target_os = "windows")]
+    {
+        use windows_sys::Win32::System::DataExchange::{
+            SetClipboardData, OpenClipboard, EmptyClipboard, CloseClipboard,
+        };
+        use windows_sys::Win32::System::Memory::{GlobalAlloc, GlobalLock, GlobalUnlock, GMEM_MOVEABLE};
         use windows_sys::Win32::Foundation::HWND;
         use windows_sys::Win32::Graphics::Gdi::BITMAPINFOHEADER;
 
-        // 读取图片文件
-        let image_data = fs::read(&image_path)
-            .map_err(|e| format!("Failed to read image: {}", e))?;
-
-        // 解码 PNG
-        let decoder = png::Decoder::new(&image_data[..]);
-        let mut reader = decoder.read_info()
-            .map_err(|e| format!("Failed to decode PNG: {}", e))?;
-        let mut buf = vec![0; reader.output_buffer_size()];
-        let info = reader.next_frame(&mut buf)
-            .map_err(|e| format!("Failed to read PNG frame: {}", e))?;
+        // 读取并解码图片文件（PNG / JPEG / WebP）
+        let image = crate::clipboard_image::decode_image_file(Path::new(&image_path))?;
+        let buf = image.pixels;
 
-        let width = info.width as i32;
-        let height = info.height as i32;
+        let width = image.width as i32;
+        let height = image.height as i32;
         let bit_count = 32u16; // RGBA
 
         unsafe {
@@ -10,6 +10,9 @@ use std::sync::Once;
 
 static INIT: Once = Once::new();
 
+/// 直达取色快捷键在多快捷键监听器中的 ID
+pub const COLOR_PICK_HOTKEY_ID: &str = "color_pick";
+
 /// 预热函数：在后台预加载 Windows GDI 资源
 #[cfg(target_os = "windows")]
 pub fn warmup_color_picker() {
@@ -81,6 +84,286 @@ pub fn warmup_color_picker() {
 #[cfg(target_os = "windows")]
 static mut IS_COLOR_PICKING: bool = false;
 
+/// 左键是否被钩子拦截（屏幕标尺拖拽时避免操作到下方窗口）
+#[cfg(target_os = "windows")]
+static BLOCK_LEFT_BUTTON: AtomicBool = AtomicBool::new(false);
+
+/// 左键被拦截时由钩子记录按下状态
+#[cfg(target_os = "windows")]
+pub(super) static LEFT_BUTTON_DOWN: AtomicBool = AtomicBool::new(false);
+
+/// 取色模式下钩子在左键按下瞬间采样：(x, y, COLORREF)，取色循环读取后清空
+#[cfg(target_os = "windows")]
+static PICKED_CLICK: std::sync::Mutex<Option<(i32, i32, u32)>> = std::sync::Mutex::new(None);
+
+/// 取色时被拦截的左键按下，对应的抬起也需拦截
+#[cfg(target_os = "windows")]
+static SWALLOW_LEFT_UP: AtomicBool = AtomicBool::new(false);
+
+/// 钩子回调中采样使用的屏幕 DC
+#[cfg(target_os = "windows")]
+static SCREEN_DC: std::sync::atomic::AtomicIsize = std::sync::atomic::AtomicIsize::new(0);
+
+/// 右键是否取消取色（否则右键仅被屏蔽）
+#[cfg(target_os = "windows")]
+static RIGHT_CLICK_CANCELS: AtomicBool = AtomicBool::new(false);
+
+/// 钩子中收到右键后置位，取色循环检测到后取消
+#[cfg(target_os = "windows")]
+static RIGHT_CLICK_REQUESTED: AtomicBool = AtomicBool::new(false);
+
+/// 开始取色后等待前台窗口稳定的时间
+#[cfg(target_os = "windows")]
+pub(super) const FOCUS_BASELINE_DELAY: Duration = Duration::from_millis(300);
+
+/// 取色取消原因，仅用于日志
+#[cfg(target_os = "windows")]
+#[derive(Debug)]
+pub(super) enum CancelReason {
+    Escape,
+    RightClick,
+    FocusLost,
+    Aborted,
+}
+
+/// 取色过程中占用的钩子与 GDI 资源，Drop 时统一释放
+///
+/// ESC、右键、失焦、出错等所有退出路径都经过这里，保证资源必定释放
+#[cfg(target_os = "windows")]
+pub(super) struct PickerResources {
+    /// 进入拾取前线程的 DPI 感知上下文，退出时恢复
+    dpi_context: isize,
+    hook: isize,
+    pub(super) screen_dc: isize,
+    pub(super) hwnd: isize,
+    pub(super) window_dc: isize,
+    pub(super) mem_dc: isize,
+    bitmap: isize,
+    old_bitmap: isize,
+    font: isize,
+    old_font: isize,
+}
+
+#[cfg(target_os = "windows")]
+impl PickerResources {
+    /// 进入屏幕拾取模式：安装鼠标钩子并获取屏幕 DC
+    ///
+    /// block_left 为 true 时左键也被拦截，按下状态记录在 LEFT_BUTTON_DOWN
+    ///
+    /// 线程切换为按显示器 DPI 感知：光标、钩子坐标、屏幕 DC 与信息窗口统一使用虚拟屏幕上的物理像素，
+    /// 缩放比例不同的多显示器下也能取到光标下的像素
+    pub(super) unsafe fn acquire(block_left: bool) -> Result<Self, String> {
+        use windows_sys::Win32::Graphics::Gdi::GetDC;
+        use windows_sys::Win32::UI::HiDpi::{
+            SetThreadDpiAwarenessContext, DPI_AWARENESS_CONTEXT_PER_MONITOR_AWARE_V2,
+        };
+        use windows_sys::Win32::UI::WindowsAndMessaging::{SetWindowsHookExW, WH_MOUSE_LL};
+
+        IS_COLOR_PICKING = true;
+        RIGHT_CLICK_REQUESTED.store(false, Ordering::SeqCst);
+        BLOCK_LEFT_BUTTON.store(block_left, Ordering::SeqCst);
+        LEFT_BUTTON_DOWN.store(false, Ordering::SeqCst);
+        SWALLOW_LEFT_UP.store(false, Ordering::SeqCst);
+        if let Ok(mut click) = PICKED_CLICK.lock() {
+            *click = None;
+        }
+
+        let mut res = PickerResources {
+            dpi_context: SetThreadDpiAwarenessContext(DPI_AWARENESS_CONTEXT_PER_MONITOR_AWARE_V2),
+            hook: SetWindowsHookExW(WH_MOUSE_LL, Some(mouse_hook_proc), 0, 0),
+            screen_dc: 0,
+            hwnd: 0,
+            window_dc: 0,
+            mem_dc: 0,
+            bitmap: 0,
+            old_bitmap: 0,
+            font: 0,
+            old_font: 0,
+        };
+        if res.hook == 0 {
+            return Err("安装鼠标钩子失败".to_string());
+        }
+        res.screen_dc = GetDC(0);
+        SCREEN_DC.store(res.screen_dc, Ordering::SeqCst);
+        Ok(res)
+    }
+
+    /// 处理本线程的消息：低层鼠标钩子的回调只在安装线程取消息时执行
+    pub(super) unsafe fn pump_messages(&self) {
+        use windows_sys::Win32::UI::WindowsAndMessaging::{
+            DispatchMessageW, PeekMessageW, TranslateMessage, MSG, PM_REMOVE,
+        };
+
+        let mut msg: MSG = std::mem::zeroed();
+        while PeekMessageW(&mut msg, 0, 0, 0, PM_REMOVE) != 0 {
+            TranslateMessage(&msg);
+            DispatchMessageW(&msg);
+        }
+    }
+
+    /// 信息窗口放在光标右下方，超出光标所在显示器时翻到左侧 / 上方
+    pub(super) fn info_window_position(x: i32, y: i32, width: i32, height: i32, offset: i32) -> (i32, i32) {
+        use windows_sys::Win32::Foundation::POINT;
+        use windows_sys::Win32::Graphics::Gdi::{
+            GetMonitorInfoW, MonitorFromPoint, MONITORINFO, MONITOR_DEFAULTTONEAREST,
+        };
+
+        let (mut left, mut top) = (x + offset, y + offset);
+        unsafe {
+            let monitor = MonitorFromPoint(POINT { x, y }, MONITOR_DEFAULTTONEAREST);
+            let mut info: MONITORINFO = std::mem::zeroed();
+            info.cbSize = std::mem::size_of::<MONITORINFO>() as u32;
+            if monitor == 0 || GetMonitorInfoW(monitor, &mut info) == 0 {
+                return (left, top);
+            }
+            let area = info.rcMonitor;
+            if left + width > area.right {
+                left = x - offset - width;
+            }
+            if top + height > area.bottom {
+                top = y - offset - height;
+            }
+            (left.max(area.left), top.max(area.top))
+        }
+    }
+
+    /// 取出钩子记录的左键点击（坐标与按下瞬间的像素颜色）
+    pub(super) fn take_click(&self) -> Option<(i32, i32, u32)> {
+        PICKED_CLICK.lock().ok()?.take()
+    }
+
+    /// 创建跟随鼠标的信息窗口及其双缓冲 DC，返回是否成功
+    pub(super) unsafe fn create_info_window(&mut self, width: i32, height: i32) -> bool {
+        use windows_sys::Win32::Graphics::Gdi::{
+            CreateCompatibleBitmap, CreateCompatibleDC, CreateFontW, GetDC, SelectObject,
+            CLIP_DEFAULT_PRECIS, DEFAULT_CHARSET, DEFAULT_QUALITY, FF_DONTCARE, FW_NORMAL,
+            OUT_DEFAULT_PRECIS,
+        };
+        use windows_sys::Win32::UI::WindowsAndMessaging::{
+            CreateWindowExW, SetLayeredWindowAttributes, LWA_ALPHA, WS_EX_LAYERED,
+            WS_EX_TOOLWINDOW, WS_EX_TOPMOST, WS_POPUP,
+        };
+
+        let hwnd = CreateWindowExW(
+            WS_EX_LAYERED | WS_EX_TOPMOST | WS_EX_TOOLWINDOW,
+            "Static\0".encode_utf16().collect::<Vec<u16>>().as_ptr(),
+            std::ptr::null(),
+            WS_POPUP,
+            0, 0, width, height,
+            0,
+            0,
+            0,
+            std::ptr::null(),
+        );
+        if hwnd == 0 {
+            return false;
+        }
+
+        self.hwnd = hwnd;
+        SetLayeredWindowAttributes(hwnd, 0, 240, LWA_ALPHA);
+        self.window_dc = GetDC(hwnd);
+        self.mem_dc = CreateCompatibleDC(self.screen_dc);
+        self.bitmap = CreateCompatibleBitmap(self.screen_dc, width, height);
+        self.old_bitmap = SelectObject(self.mem_dc, self.bitmap as isize);
+
+        self.font = CreateFontW(
+            16, 0, 0, 0,
+            FW_NORMAL as i32,
+            0, 0, 0,
+            DEFAULT_CHARSET as u32,
+            OUT_DEFAULT_PRECIS as u32,
+            CLIP_DEFAULT_PRECIS as u32,
+            DEFAULT_QUALITY as u32,
+            FF_DONTCARE as u32,
+            "Segoe UI\0".encode_utf16().collect::<Vec<u16>>().as_ptr(),
+        );
+        self.old_font = SelectObject(self.mem_dc, self.font as isize);
+        true
+    }
+}
+
+/// 按设置决定右键是否取消本次拾取
+#[cfg(target_os = "windows")]
+pub(super) fn apply_right_click_setting(app: &tauri::AppHandle) {
+    let right_click_cancels = super::get_app_data_dir(app)
+        .and_then(|dir| crate::settings::load_settings(&dir))
+        .map(|s| s.color_pick_right_click_cancels)
+        .unwrap_or(false);
+    RIGHT_CLICK_CANCELS.store(right_click_cancels, Ordering::SeqCst);
+}
+
+/// ESC / 右键 / 失焦 / 外部中止四种取消路径
+#[cfg(target_os = "windows")]
+pub(super) unsafe fn cancel_reason(
+    picking: &AtomicBool,
+    baseline_foreground: Option<windows_sys::Win32::Foundation::HWND>,
+) -> Option<CancelReason> {
+    use windows_sys::Win32::UI::Input::KeyboardAndMouse::{GetAsyncKeyState, VK_ESCAPE};
+    use windows_sys::Win32::UI::WindowsAndMessaging::GetForegroundWindow;
+
+    if !picking.load(Ordering::SeqCst) {
+        Some(CancelReason::Aborted)
+    } else if GetAsyncKeyState(VK_ESCAPE as i32) as u16 & 0x8000 != 0 {
+        Some(CancelReason::Escape)
+    } else if RIGHT_CLICK_REQUESTED.load(Ordering::SeqCst) {
+        Some(CancelReason::RightClick)
+    } else if baseline_foreground.is_some_and(|hwnd| GetForegroundWindow() != hwnd) {
+        Some(CancelReason::FocusLost)
+    } else {
+        None
+    }
+}
+
+#[cfg(target_os = "windows")]
+impl Drop for PickerResources {
+    fn drop(&mut self) {
+        use windows_sys::Win32::Graphics::Gdi::{DeleteDC, DeleteObject, ReleaseDC, SelectObject};
+        use windows_sys::Win32::UI::HiDpi::SetThreadDpiAwarenessContext;
+        use windows_sys::Win32::UI::WindowsAndMessaging::{DestroyWindow, UnhookWindowsHookEx};
+
+        unsafe {
+            if self.mem_dc != 0 {
+                if self.old_font != 0 {
+                    SelectObject(self.mem_dc, self.old_font);
+                }
+                if self.old_bitmap != 0 {
+                    SelectObject(self.mem_dc, self.old_bitmap);
+                }
+            }
+            if self.font != 0 {
+                DeleteObject(self.font);
+            }
+            if self.bitmap != 0 {
+                DeleteObject(self.bitmap);
+            }
+            if self.mem_dc != 0 {
+                DeleteDC(self.mem_dc);
+            }
+            if self.window_dc != 0 {
+                ReleaseDC(self.hwnd, self.window_dc);
+            }
+            if self.hwnd != 0 {
+                DestroyWindow(self.hwnd);
+            }
+            SCREEN_DC.store(0, Ordering::SeqCst);
+            if self.screen_dc != 0 {
+                ReleaseDC(0, self.screen_dc);
+            }
+            if self.hook != 0 {
+                UnhookWindowsHookEx(self.hook);
+            }
+            if self.dpi_context != 0 {
+                SetThreadDpiAwarenessContext(self.dpi_context);
+            }
+            IS_COLOR_PICKING = false;
+        }
+        RIGHT_CLICK_REQUESTED.store(false, Ordering::SeqCst);
+        BLOCK_LEFT_BUTTON.store(false, Ordering::SeqCst);
+        LEFT_BUTTON_DOWN.store(false, Ordering::SeqCst);
+        SWALLOW_LEFT_UP.store(false, Ordering::SeqCst);
+    }
+}
+
 // 鼠标钩子回调函数
 #[cfg(target_os = "windows")]
 unsafe extern "system" fn mouse_hook_proc(
@@ -88,17 +371,39 @@ unsafe extern "system" fn mouse_hook_proc(
     w_param: usize,
     l_param: isize,
 ) -> isize {
+    use windows_sys::Win32::Graphics::Gdi::GetPixel;
     use windows_sys::Win32::UI::WindowsAndMessaging::{
         CallNextHookEx, WM_LBUTTONDOWN, WM_LBUTTONUP, WM_RBUTTONDOWN, 
-        WM_RBUTTONUP, WM_MBUTTONDOWN, WM_MBUTTONUP,
+        WM_RBUTTONUP, WM_MBUTTONDOWN, WM_MBUTTONUP, MSLLHOOKSTRUCT,
     };
     
     if n_code >= 0 && IS_COLOR_PICKING {
         let msg = w_param as u32;
         match msg {
-            // 允许左键点击（用于取色）
-            WM_LBUTTONDOWN | WM_LBUTTONUP => {
-                // 允许通过
+            // 标尺模式下拦截左键，只记录按下状态
+            WM_LBUTTONDOWN | WM_LBUTTONUP if BLOCK_LEFT_BUTTON.load(Ordering::SeqCst) => {
+                LEFT_BUTTON_DOWN.store(msg == WM_LBUTTONDOWN, Ordering::SeqCst);
+                return 1;
+            }
+            // 取色：在按下瞬间按事件自带的坐标采样，避免轮询错过快速点击或取到抬起后的位置；
+            // 按下与抬起都被拦截，点击不会穿透到下方窗口
+            WM_LBUTTONDOWN => {
+                let hook_struct = &*(l_param as *const MSLLHOOKSTRUCT);
+                let (x, y) = (hook_struct.pt.x, hook_struct.pt.y);
+                let color = GetPixel(SCREEN_DC.load(Ordering::SeqCst), x, y);
+                if let Ok(mut click) = PICKED_CLICK.lock() {
+                    click.get_or_insert((x, y, color));
+                }
+                SWALLOW_LEFT_UP.store(true, Ordering::SeqCst);
+                return 1;
+            }
+            WM_LBUTTONUP if SWALLOW_LEFT_UP.swap(false, Ordering::SeqCst) => {
+                return 1;
+            }
+            // 右键按配置取消取色，事件本身仍被屏蔽以免弹出右键菜单
+            WM_RBUTTONDOWN if RIGHT_CLICK_CANCELS.load(Ordering::SeqCst) => {
+                RIGHT_CLICK_REQUESTED.store(true, Ordering::SeqCst);
+                return 1;
             }
             // 阻止所有其他鼠标按键
             WM_RBUTTONDOWN | WM_RBUTTONUP | WM_MBUTTONDOWN | WM_MBUTTONUP => {
@@ -139,11 +444,15 @@ pub async fn show_color_picker_window(app: tauri::AppHandle) -> Result<(), Strin
     Ok(())
 }
 
-/// 从屏幕取色（Windows 实现）
+/// 从屏幕取色（Windows 实现），返回 HEX / RGB / HSL / HSV / CMYK / CSS 变量等表示
 #[tauri::command]
-pub async fn pick_color_from_screen() -> Result<Option<String>, String> {
+pub async fn pick_color_from_screen(
+    app: tauri::AppHandle,
+) -> Result<Option<crate::color::PickedColor>, String> {
     #[cfg(target_os = "windows")]
     {
+        apply_right_click_setting(&app);
+
         // 创建取消标志
         let picking = Arc::new(AtomicBool::new(true));
         let picking_clone = picking.clone();
@@ -151,39 +460,46 @@ pub async fn pick_color_from_screen() -> Result<Option<String>, String> {
         // 在后台线程中执行取色操作
         let result = tokio::task::spawn_blocking(move || {
             windows_pick_color(picking_clone)
-        }).await.map_err(|e| format!("取色任务失败: {}", e))?;
-        
-        result
+        }).await.map_err(|e| format!("取色任务失败: {}", e))??;
+
+        // 取色成功后写入历史，失败不影响本次取色
+        if let Some(hex) = result.as_ref().map(|picked| picked.hex.clone()) {
+            let app_data_dir = super::get_app_data_dir(&app)?;
+            if let Err(e) = crate::db::run_blocking(move || {
+                crate::color_history::add_color(&hex, None, &app_data_dir)
+            })
+            .await
+            {
+                eprintln!("[ColorPicker] Failed to save color history: {}", e);
+            }
+        }
+
+        Ok(result)
     }
     
     #[cfg(not(target_os = "windows"))]
     {
+        let _ = app;
         Err("屏幕取色功能目前仅支持 Windows".to_string())
     }
 }
 
 #[cfg(target_os = "windows")]
-fn windows_pick_color(picking: Arc<AtomicBool>) -> Result<Option<String>, String> {
+fn windows_pick_color(picking: Arc<AtomicBool>) -> Result<Option<crate::color::PickedColor>, String> {
     use windows_sys::Win32::UI::WindowsAndMessaging::{
-        GetCursorPos, CreateWindowExW, DestroyWindow, SetWindowPos, ShowWindow,
-        HWND_TOPMOST, SWP_NOACTIVATE, SW_SHOWNOACTIVATE, WS_EX_LAYERED, WS_EX_TOPMOST,
-        WS_EX_TOOLWINDOW, WS_POPUP, SetLayeredWindowAttributes, LWA_ALPHA,
-        SetCursor, LoadCursorW, IDC_CROSS, SetWindowsHookExW, UnhookWindowsHookEx,
-        CallNextHookEx, WH_MOUSE_LL, WM_LBUTTONDOWN, WM_LBUTTONUP,
-        WM_RBUTTONDOWN, WM_RBUTTONUP, WM_MBUTTONDOWN, WM_MBUTTONUP,
+        GetCursorPos, SetWindowPos, ShowWindow, GetForegroundWindow,
+        HWND_TOPMOST, SWP_NOACTIVATE, SW_SHOWNOACTIVATE,
+        SetCursor, LoadCursorW, IDC_CROSS,
     };
     use windows_sys::Win32::UI::Input::KeyboardAndMouse::{
-        GetAsyncKeyState, VK_LBUTTON, VK_ESCAPE, VK_SHIFT, VK_C,
+        GetAsyncKeyState, VK_SHIFT, VK_C,
     };
     use windows_sys::Win32::Graphics::Gdi::{
-        GetDC, GetPixel, ReleaseDC, CreateCompatibleDC, CreateCompatibleBitmap,
-        SelectObject, BitBlt, DeleteDC, DeleteObject, StretchBlt, SRCCOPY,
+        GetPixel, BitBlt, DeleteObject, StretchBlt, SRCCOPY,
         CreateSolidBrush, FillRect, SetBkMode, SetTextColor, TextOutW, TRANSPARENT,
-        CreateFontW, FW_NORMAL, DEFAULT_CHARSET, OUT_DEFAULT_PRECIS, CLIP_DEFAULT_PRECIS,
-        DEFAULT_QUALITY, FF_DONTCARE,
     };
     use windows_sys::Win32::Foundation::{POINT, RECT, HWND};
-    
+
     // Define RGB macro since windows_sys doesn't export it
     macro_rules! WIN_RGB {
         ($r:expr, $g:expr, $b:expr) => {
@@ -196,21 +512,8 @@ fn windows_pick_color(picking: Arc<AtomicBool>) -> Result<Option<String>, String
         let cross_cursor = LoadCursorW(0, IDC_CROSS);
         SetCursor(cross_cursor);
         
-        // 设置全局取色标志
-        IS_COLOR_PICKING = true;
-        
-        // 安装鼠标钩子以阻止点击穿透
-        let hook = SetWindowsHookExW(
-            WH_MOUSE_LL,
-            Some(mouse_hook_proc),
-            0,
-            0,
-        );
-        
-        if hook == 0 {
-            IS_COLOR_PICKING = false;
-            return Err("安装鼠标钩子失败".to_string());
-        }
+        // 安装鼠标钩子以阻止点击穿透；之后创建的资源都记录在 res 中，离开作用域时统一释放
+        let mut res = PickerResources::acquire(false)?;
         
         // 放大镜窗口相关变量（懒加载）
         let magnifier_width = 280i32;
@@ -218,20 +521,18 @@ fn windows_pick_color(picking: Arc<AtomicBool>) -> Result<Option<String>, String
         let preview_size = 200i32;
         let capture_size = 20i32;
         
-        let screen_dc = GetDC(0);
+        let screen_dc = res.screen_dc;
         
-        // 懒加载标志和资源
-        let mut hwnd: HWND = 0;
-        let mut window_dc: isize = 0;
-        let mut mem_dc: isize = 0;
-        let mut bitmap: isize = 0;
-        let mut old_bitmap: isize = 0;
-        let mut font: isize = 0;
-        let mut old_font: isize = 0;
+        // 懒加载标志
         let mut magnifier_created = false;
+        // 稳定后的前台窗口，之后发生变化视为失焦（如 Alt+Tab）
+        // 前端调用前会先隐藏拾色器窗口，前台切换有延迟，因此等待片刻再记录
+        let started_at = std::time::Instant::now();
+        let mut baseline_foreground: Option<HWND> = None;
         
-        // 格式切换状态：true = RGB, false = HEX
-        let mut show_rgb = true;
+        // 当前显示 / 复制的格式（PICKER_FORMATS 下标），Shift 依次切换
+        let formats = crate::color::PICKER_FORMATS;
+        let mut format_index = 1; // 默认 RGB
         let mut shift_pressed = false;
         let mut c_pressed = false;
         let mut frame_count = 0u32;  // 帧计数器
@@ -241,63 +542,32 @@ fn windows_pick_color(picking: Arc<AtomicBool>) -> Result<Option<String>, String
             // 持续设置十字光标（确保在整个屏幕上都显示）
             SetCursor(cross_cursor);
             
-            if !picking.load(Ordering::SeqCst) {
-                break Ok(None);
+            if baseline_foreground.is_none() && started_at.elapsed() >= FOCUS_BASELINE_DELAY {
+                baseline_foreground = Some(GetForegroundWindow());
             }
-            
-            // 检查是否按下 ESC 键
-            if GetAsyncKeyState(VK_ESCAPE as i32) as u16 & 0x8000 != 0 {
+
+            if let Some(reason) = cancel_reason(&picking, baseline_foreground) {
+                eprintln!("[ColorPicker] Picking cancelled: {:?}", reason);
                 break Ok(None);
             }
             
             // 懒加载：等待几帧后再创建放大镜窗口（减少初始卡顿）
             frame_count += 1;
             if !magnifier_created && frame_count > 3 {
-                hwnd = CreateWindowExW(
-                    WS_EX_LAYERED | WS_EX_TOPMOST | WS_EX_TOOLWINDOW,
-                    "Static\0".encode_utf16().collect::<Vec<u16>>().as_ptr(),
-                    std::ptr::null(),
-                    WS_POPUP,
-                    0, 0, magnifier_width, magnifier_height,
-                    0 as HWND,
-                    0,
-                    0,
-                    std::ptr::null(),
-                );
-                
-                if hwnd != 0 {
-                    SetLayeredWindowAttributes(hwnd, 0, 240, LWA_ALPHA);
-                    window_dc = GetDC(hwnd);
-                    mem_dc = CreateCompatibleDC(screen_dc);
-                    bitmap = CreateCompatibleBitmap(screen_dc, magnifier_width, magnifier_height);
-                    old_bitmap = SelectObject(mem_dc, bitmap as isize);
-                    
-                    font = CreateFontW(
-                        16, 0, 0, 0,
-                        FW_NORMAL as i32,
-                        0, 0, 0,
-                        DEFAULT_CHARSET as u32,
-                        OUT_DEFAULT_PRECIS as u32,
-                        CLIP_DEFAULT_PRECIS as u32,
-                        DEFAULT_QUALITY as u32,
-                        FF_DONTCARE as u32,
-                        "Segoe UI\0".encode_utf16().collect::<Vec<u16>>().as_ptr(),
-                    );
-                    old_font = SelectObject(mem_dc, font as isize);
-                    magnifier_created = true;
-                }
+                magnifier_created = res.create_info_window(magnifier_width, magnifier_height);
             }
             
             // 检查 Shift 键切换显示格式
             let shift_now = GetAsyncKeyState(VK_SHIFT as i32) as u16 & 0x8000 != 0;
             if shift_now && !shift_pressed {
-                show_rgb = !show_rgb;
+                format_index = (format_index + 1) % formats.len();
             }
             shift_pressed = shift_now;
             
             // 获取鼠标位置和颜色
             let mut point = POINT { x: 0, y: 0 };
             if GetCursorPos(&mut point) != 0 && magnifier_created {
+                let (hwnd, window_dc, mem_dc) = (res.hwnd, res.window_dc, res.mem_dc);
                 // 获取当前像素颜色
                 let color = GetPixel(screen_dc, point.x, point.y);
                 let r = (color & 0xFF) as u8;
@@ -308,11 +578,7 @@ fn windows_pick_color(picking: Arc<AtomicBool>) -> Result<Option<String>, String
                 let c_now = GetAsyncKeyState(VK_C as i32) as u16 & 0x8000 != 0;
                 if c_now && !c_pressed {
                     // 复制到剪贴板
-                    let color_text = if show_rgb {
-                        format!("rgb({}, {}, {})", r, g, b)
-                    } else {
-                        format!("#{:02x}{:02x}{:02x}", r, g, b)
-                    };
+                    let color_text = crate::color::format_color(r, g, b, formats[format_index]);
                     
                     // 使用 Windows API 复制到剪贴板
                     use windows_sys::Win32::System::DataExchange::{
@@ -337,13 +603,15 @@ fn windows_pick_color(picking: Arc<AtomicBool>) -> Result<Option<String>, String
                 }
                 c_pressed = c_now;
                 
-                // 更新放大镜窗口位置
-                let offset = 30i32;
+                // 更新放大镜窗口位置（保持在光标所在的显示器内）
+                let (window_x, window_y) = PickerResources::info_window_position(
+                    point.x, point.y, magnifier_width, magnifier_height, 30,
+                );
                 SetWindowPos(
                     hwnd,
                     HWND_TOPMOST,
-                    point.x + offset,
-                    point.y + offset,
+                    window_x,
+                    window_y,
                     magnifier_width,
                     magnifier_height,
                     SWP_NOACTIVATE,
@@ -430,18 +698,19 @@ fn windows_pick_color(picking: Arc<AtomicBool>) -> Result<Option<String>, String
                 y_pos += line_height;
                 
                 // 颜色值
-                let color_text = if show_rgb {
-                    format!("RGB: ({}, {}, {})\0", r, g, b)
-                } else {
-                    format!("HEX: #{:02X}{:02X}{:02X}\0", r, g, b)
-                };
+                // 各格式文本自带标识（#、hsl() 等），不再加前缀以免超出窗口宽度
+                let color_text = format!("{}\0", crate::color::format_color(r, g, b, formats[format_index]));
                 let color_wide: Vec<u16> = color_text.encode_utf16().collect();
                 TextOutW(mem_dc, x_pos, y_pos, color_wide.as_ptr(), color_wide.len() as i32 - 1);
                 y_pos += line_height + 5;
                 
                 // 提示信息
                 SetTextColor(mem_dc, WIN_RGB!(180, 180, 180));
-                let help1 = "左键: 确认  ESC: 取消\0";
+                let help1 = if RIGHT_CLICK_CANCELS.load(Ordering::SeqCst) {
+                    "左键: 确认  ESC/右键: 取消\0"
+                } else {
+                    "左键: 确认  ESC: 取消\0"
+                };
                 let help1_wide: Vec<u16> = help1.encode_utf16().collect();
                 TextOutW(mem_dc, x_pos, y_pos, help1_wide.as_ptr(), help1_wide.len() as i32 - 1);
                 y_pos += line_height;
@@ -463,72 +732,290 @@ fn windows_pick_color(picking: Arc<AtomicBool>) -> Result<Option<String>, String
                 ShowWindow(hwnd, SW_SHOWNOACTIVATE);
             }
             
-            // 检查是否按下鼠标左键
-            if GetAsyncKeyState(VK_LBUTTON as i32) as u16 & 0x8000 != 0 {
-                // 获取鼠标位置
-                let mut point = POINT { x: 0, y: 0 };
-                if GetCursorPos(&mut point) == 0 {
-                    break Err("获取鼠标位置失败".to_string());
-                }
-                
-                // 获取指定位置的像素颜色
-                let color = GetPixel(screen_dc, point.x, point.y);
-                
+            // 处理钩子回调，检查是否点击了左键（坐标与颜色在钩子中按下瞬间采样）
+            res.pump_messages();
+            if let Some((x, y, color)) = res.take_click() {
                 if color == 0xFFFFFFFF {
+                    eprintln!("[ColorPicker] GetPixel failed at ({}, {})", x, y);
                     break Err("获取像素颜色失败".to_string());
                 }
                 
                 // 提取 RGB 值
-                let r = color & 0xFF;
-                let g = (color >> 8) & 0xFF;
-                let b = (color >> 16) & 0xFF;
-                
-                // 转换为 HEX 字符串
-                let hex_color = format!("#{:02x}{:02x}{:02x}", r, g, b);
-                
-                // 等待鼠标释放
-                while GetAsyncKeyState(VK_LBUTTON as i32) as u16 & 0x8000 != 0 {
-                    thread::sleep(Duration::from_millis(10));
-                }
+                let r = (color & 0xFF) as u8;
+                let g = ((color >> 8) & 0xFF) as u8;
+                let b = ((color >> 16) & 0xFF) as u8;
                 
-                break Ok(Some(hex_color));
+                break Ok(Some(crate::color::PickedColor::new(r, g, b)));
             }
             
             // 短暂休眠以避免过度占用 CPU
             thread::sleep(Duration::from_millis(16)); // ~60 FPS
         };
         
-        // 清理资源（仅清理已创建的）
-        if magnifier_created {
-            if old_font != 0 {
-                SelectObject(mem_dc, old_font);
-            }
-            if font != 0 {
-                DeleteObject(font);
-            }
-            if old_bitmap != 0 {
-                SelectObject(mem_dc, old_bitmap);
-            }
-            if bitmap != 0 {
-                DeleteObject(bitmap);
-            }
-            if mem_dc != 0 {
-                DeleteDC(mem_dc);
-            }
-            if window_dc != 0 {
-                ReleaseDC(hwnd, window_dc);
+        // 钩子与 GDI 资源由 res 的 Drop 统一释放
+        drop(res);
+        result
+    }
+}
+
+/// 热键直达取色：直接进入屏幕取色，按配置格式复制到剪贴板并弹出 Toast
+#[tauri::command]
+pub async fn pick_color_and_copy(app: tauri::AppHandle) -> Result<Option<String>, String> {
+    let Some(picked) = pick_color_from_screen(app.clone()).await? else {
+        return Ok(None);
+    };
+
+    let app_data_dir = super::get_app_data_dir(&app)?;
+    let format = crate::settings::load_settings(&app_data_dir)
+        .map(|s| s.color_copy_format)
+        .unwrap_or_else(|_| "hex".to_string());
+    let color_text = crate::color::format_color(picked.r, picked.g, picked.b, &format);
+
+    #[cfg(target_os = "windows")]
+    crate::clipboard::monitor::set_clipboard_text(&color_text)?;
+
+    if let Err(e) = crate::toast::show_toast(&app, "已复制颜色", &color_text) {
+        eprintln!("[ColorPicker] Failed to show toast: {}", e);
+    }
+
+    Ok(Some(color_text))
+}
+
+/// 图像取色所需的图片信息
+#[derive(serde::Serialize)]
+pub struct ImageForPicking {
+    /// 图片文件路径（剪贴板图片会先保存到 clipboard_images）
+    pub path: String,
+    pub width: u32,
+    pub height: u32,
+    /// data:image/...;base64,... 供前端显示
+    pub data_url: String,
+    /// 主色调（聚类前 5 色）
+    pub dominant_colors: Vec<crate::color::DominantColor>,
+}
+
+fn load_image(path: &str) -> Result<(Vec<u8>, crate::color::RgbaImage), String> {
+    let bytes = std::fs::read(path).map_err(|e| format!("Failed to read image: {}", e))?;
+    let image = crate::clipboard_image::decode_image_file(std::path::Path::new(path))?;
+    Ok((bytes, image))
+}
+
+/// 加载用于取色的图片：指定路径或剪贴板中的图片（path 为空时）
+#[tauri::command]
+pub async fn load_image_for_picking(
+    app: tauri::AppHandle,
+    path: Option<String>,
+) -> Result<ImageForPicking, String> {
+    use base64::{engine::general_purpose, Engine as _};
+
+    let path = match path {
+        Some(path) => path,
+        None => {
+            #[cfg(target_os = "windows")]
+            {
+                let app_data_dir = super::get_app_data_dir(&app)?;
+                crate::clipboard::monitor::get_clipboard_image(&app_data_dir)?
             }
-            if hwnd != 0 {
-                DestroyWindow(hwnd);
+            #[cfg(not(target_os = "windows"))]
+            {
+                let _ = &app;
+                return Err("Clipboard is only supported on Windows".to_string());
             }
         }
-        
-        ReleaseDC(0, screen_dc);
-        
-        // 卸载鼠标钩子
-        UnhookWindowsHookEx(hook);
-        IS_COLOR_PICKING = false;
-        
-        result
+    };
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let (bytes, image) = load_image(&path)?;
+        Ok(ImageForPicking {
+            width: image.width,
+            height: image.height,
+            data_url: format!(
+                "data:{};base64,{}",
+                crate::asset_protocol::mime_for_path(std::path::Path::new(&path)),
+                general_purpose::STANDARD.encode(&bytes)
+            ),
+            dominant_colors: crate::color::dominant_colors(&image, 5),
+            path,
+        })
+    })
+    .await
+    .map_err(|e| format!("Failed to load image: {}", e))?
+}
+
+/// 获取图片指定像素的颜色（#rrggbb）
+#[tauri::command]
+pub async fn pick_color_from_image(path: String, x: u32, y: u32) -> Result<String, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let (_, image) = load_image(&path)?;
+        let (r, g, b) = image
+            .pixel(x, y)
+            .ok_or_else(|| format!("坐标超出图片范围: ({}, {})", x, y))?;
+        Ok(crate::color::format_color(r, g, b, "hex"))
+    })
+    .await
+    .map_err(|e| format!("Failed to pick color: {}", e))?
+}
+
+/// 提取图片主色调（默认前 5 色）
+#[tauri::command]
+pub async fn extract_dominant_colors(
+    path: String,
+    count: Option<usize>,
+) -> Result<Vec<crate::color::DominantColor>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let (_, image) = load_image(&path)?;
+        Ok(crate::color::dominant_colors(&image, count.unwrap_or(5).clamp(1, 16)))
+    })
+    .await
+    .map_err(|e| format!("Failed to extract colors: {}", e))?
+}
+
+/// 一个颜色在各类色觉缺陷下的近似值（#rrggbb）
+#[derive(serde::Serialize)]
+pub struct ColorBlindSimulation {
+    /// 原始输入
+    pub input: String,
+    pub hex: String,
+    /// 红色盲（红绿）
+    pub protanopia: String,
+    /// 绿色盲（红绿）
+    pub deuteranopia: String,
+    /// 蓝色盲（蓝黄）
+    pub tritanopia: String,
+}
+
+/// 色盲模拟：对颜色或调色板输出红绿色盲、蓝黄色盲视角下的近似值
+///
+/// 支持 #rrggbb / rrggbb / #rgb / rgb() 形式，无法解析的颜色返回错误
+#[tauri::command]
+pub fn simulate_color_blindness(colors: Vec<String>) -> Result<Vec<ColorBlindSimulation>, String> {
+    use crate::color::{format_color, simulate_color_vision, ColorVision};
+
+    colors
+        .into_iter()
+        .map(|input| {
+            let (r, g, b) = crate::color::parse_color_text(&input)
+                .or_else(|| crate::color::parse_hex(&input))
+                .ok_or_else(|| format!("无法解析颜色: {}", input))?;
+            let simulate = |vision| {
+                let (r, g, b) = simulate_color_vision(r, g, b, vision);
+                format_color(r, g, b, "hex")
+            };
+            Ok(ColorBlindSimulation {
+                hex: format_color(r, g, b, "hex"),
+                protanopia: simulate(ColorVision::Protanopia),
+                deuteranopia: simulate(ColorVision::Deuteranopia),
+                tritanopia: simulate(ColorVision::Tritanopia),
+                input,
+            })
+        })
+        .collect()
+}
+
+/// 获取直达取色快捷键
+#[tauri::command]
+pub fn get_color_pick_hotkey(app: tauri::AppHandle) -> Result<Option<crate::settings::HotkeyConfig>, String> {
+    let app_data_dir = super::get_app_data_dir(&app)?;
+    Ok(crate::settings::load_settings(&app_data_dir)?.color_pick_hotkey)
+}
+
+/// 保存直达取色快捷键（None 表示取消）
+#[tauri::command]
+pub fn save_color_pick_hotkey(
+    app: tauri::AppHandle,
+    config: Option<crate::settings::HotkeyConfig>,
+) -> Result<(), String> {
+    let app_data_dir = super::get_app_data_dir(&app)?;
+    let mut settings = crate::settings::load_settings(&app_data_dir)?;
+    settings.color_pick_hotkey = config.clone();
+    crate::settings::save_settings(&app_data_dir, &settings)?;
+
+    #[cfg(target_os = "windows")]
+    {
+        let hotkey_id = COLOR_PICK_HOTKEY_ID.to_string();
+        let result = match config {
+            Some(hotkey) => crate::hotkey_handler::windows::register_plugin_hotkey(hotkey_id, hotkey),
+            None => crate::hotkey_handler::windows::unregister_plugin_hotkey(&hotkey_id),
+        };
+        if let Err(e) = result {
+            eprintln!("Failed to update color pick hotkey: {}", e);
+        }
     }
+
+    Ok(())
+}
+
+/// 导出颜色历史与调色板为 JSON 文件
+#[tauri::command]
+pub async fn export_colors(
+    path: String,
+    history: Vec<crate::color_palette::PaletteColor>,
+    palettes: Vec<crate::color_palette::Palette>,
+) -> Result<(), String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        crate::color_palette::export_json(std::path::Path::new(&path), history, palettes)
+    })
+    .await
+    .map_err(|e| format!("Failed to export colors: {}", e))?
+}
+
+/// 导入颜色文件（JSON、.aco、.ase、.gpl），由前端合并到历史与调色板
+#[tauri::command]
+pub async fn import_colors(path: String) -> Result<crate::color_palette::ColorExport, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        crate::color_palette::import_file(std::path::Path::new(&path))
+    })
+    .await
+    .map_err(|e| format!("Failed to import colors: {}", e))?
+}
+
+/// 获取取色历史（收藏在前，其余按时间倒序）
+#[tauri::command]
+pub async fn list_color_history(
+    limit: Option<u32>,
+    app: tauri::AppHandle,
+) -> Result<Vec<crate::color_history::ColorHistoryEntry>, String> {
+    let app_data_dir = super::get_app_data_dir(&app)?;
+    crate::db::run_blocking(move || crate::color_history::list_colors(limit, &app_data_dir)).await
+}
+
+/// 手动加入取色历史（如在拾色器窗口中输入的颜色）
+#[tauri::command]
+pub async fn add_color_to_history(
+    hex: String,
+    note: Option<String>,
+    app: tauri::AppHandle,
+) -> Result<crate::color_history::ColorHistoryEntry, String> {
+    let app_data_dir = super::get_app_data_dir(&app)?;
+    crate::db::run_blocking(move || crate::color_history::add_color(&hex, note, &app_data_dir)).await
+}
+
+/// 删除一条取色记录
+#[tauri::command]
+pub async fn delete_color(id: i64, app: tauri::AppHandle) -> Result<(), String> {
+    let app_data_dir = super::get_app_data_dir(&app)?;
+    crate::db::run_blocking(move || crate::color_history::delete_color(id, &app_data_dir)).await
+}
+
+/// 收藏或取消收藏颜色
+#[tauri::command]
+pub async fn favorite_color(
+    id: i64,
+    favorite: bool,
+    app: tauri::AppHandle,
+) -> Result<crate::color_history::ColorHistoryEntry, String> {
+    let app_data_dir = super::get_app_data_dir(&app)?;
+    crate::db::run_blocking(move || crate::color_history::set_favorite(id, favorite, &app_data_dir))
+        .await
+}
+
+/// 修改颜色备注
+#[tauri::command]
+pub async fn set_color_note(
+    id: i64,
+    note: Option<String>,
+    app: tauri::AppHandle,
+) -> Result<crate::color_history::ColorHistoryEntry, String> {
+    let app_data_dir = super::get_app_data_dir(&app)?;
+    crate::db::run_blocking(move || crate::color_history::set_note(id, note, &app_data_dir)).await
 }
@@ -0,0 +1,79 @@
+//! 文件内容搜索相关命令模块
+//!
+//! 命中结果通过 "content-search-match" 事件流式推送，结束时推送 "content-search-done"
+
+use crate::content_search::{self, ContentMatch, ContentSearchOptions, ContentSearchSummary};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, LazyLock, Mutex};
+use tauri::{async_runtime, AppHandle, Emitter};
+
+/// 进行中的搜索及其取消标志
+static SEARCHES: LazyLock<Mutex<HashMap<String, Arc<AtomicBool>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+#[derive(Serialize, Clone)]
+struct MatchEvent<'a> {
+    search_id: &'a str,
+    #[serde(flatten)]
+    item: ContentMatch,
+}
+
+#[derive(Serialize, Clone)]
+struct DoneEvent<'a> {
+    search_id: &'a str,
+    #[serde(flatten)]
+    summary: &'a ContentSearchSummary,
+}
+
+/// 在目录中搜索文件内容，返回搜索摘要
+#[tauri::command]
+pub async fn search_file_contents(
+    search_id: String,
+    options: ContentSearchOptions,
+    app: AppHandle,
+) -> Result<ContentSearchSummary, String> {
+    let cancel = Arc::new(AtomicBool::new(false));
+    if let Ok(mut searches) = SEARCHES.lock() {
+        // 同 ID 的旧搜索直接取消
+        if let Some(previous) = searches.insert(search_id.clone(), cancel.clone()) {
+            previous.store(true, Ordering::Relaxed);
+        }
+    }
+
+    let id = search_id.clone();
+    let cancel_flag = cancel.clone();
+    let result = async_runtime::spawn_blocking(move || {
+        let summary = content_search::search(&options, &cancel, |item| {
+            let _ = app.emit("content-search-match", MatchEvent { search_id: &id, item });
+        })?;
+        let _ = app.emit(
+            "content-search-done",
+            DoneEvent {
+                search_id: &id,
+                summary: &summary,
+            },
+        );
+        Ok(summary)
+    })
+    .await
+    .map_err(|e| format!("Failed to run content search: {}", e))?;
+
+    if let Ok(mut searches) = SEARCHES.lock() {
+        // 只移除本次搜索，避免误删同 ID 的新搜索
+        if searches.get(&search_id).is_some_and(|c| Arc::ptr_eq(c, &cancel_flag)) {
+            searches.remove(&search_id);
+        }
+    }
+    result
+}
+
+/// 取消进行中的内容搜索
+#[tauri::command]
+pub fn cancel_file_content_search(search_id: String) -> Result<(), String> {
+    if let Some(cancel) = SEARCHES.lock().map_err(|e| e.to_string())?.get(&search_id) {
+        cancel.store(true, Ordering::Relaxed);
+    }
+    Ok(())
+}
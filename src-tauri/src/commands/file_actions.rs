@@ -0,0 +1,93 @@
+//! 文件快速操作相关命令模块
+//!
+//! 提供压缩、复制、移动文件结果的操作，以及常用目标目录的收藏管理
+
+use crate::file_actions::{self, ConflictStrategy, FileActionProgress, FileActionResult};
+use crate::settings;
+use super::get_app_data_dir;
+use tauri::{async_runtime, AppHandle, Emitter};
+
+fn emit_progress(app: &AppHandle) -> impl Fn(FileActionProgress) + '_ {
+    move |progress| {
+        let _ = app.emit("file-action-progress", progress);
+    }
+}
+
+/// 将文件/文件夹压缩为 zip，返回生成的 zip 路径（跳过时返回 None）
+#[tauri::command]
+pub async fn compress_to_zip(
+    paths: Vec<String>,
+    zip_path: String,
+    conflict: Option<ConflictStrategy>,
+    app: AppHandle,
+) -> Result<Option<String>, String> {
+    async_runtime::spawn_blocking(move || {
+        file_actions::compress_to_zip(
+            &paths,
+            &zip_path,
+            conflict.unwrap_or_default(),
+            &emit_progress(&app),
+        )
+    })
+    .await
+    .map_err(|e| format!("Failed to run compress task: {}", e))?
+}
+
+/// 复制文件/文件夹到目标目录
+#[tauri::command]
+pub async fn copy_to(
+    paths: Vec<String>,
+    target: String,
+    conflict: Option<ConflictStrategy>,
+    app: AppHandle,
+) -> Result<FileActionResult, String> {
+    async_runtime::spawn_blocking(move || {
+        file_actions::copy_to(&paths, &target, conflict.unwrap_or_default(), &emit_progress(&app))
+    })
+    .await
+    .map_err(|e| format!("Failed to run copy task: {}", e))?
+}
+
+/// 移动文件/文件夹到目标目录
+#[tauri::command]
+pub async fn move_to(
+    paths: Vec<String>,
+    target: String,
+    conflict: Option<ConflictStrategy>,
+    app: AppHandle,
+) -> Result<FileActionResult, String> {
+    async_runtime::spawn_blocking(move || {
+        file_actions::move_to(&paths, &target, conflict.unwrap_or_default(), &emit_progress(&app))
+    })
+    .await
+    .map_err(|e| format!("Failed to run move task: {}", e))?
+}
+
+/// 获取收藏的常用目标目录
+#[tauri::command]
+pub fn get_favorite_target_dirs(app: AppHandle) -> Result<Vec<String>, String> {
+    let app_data_dir = get_app_data_dir(&app)?;
+    Ok(settings::load_settings(&app_data_dir)?.file_action_favorite_dirs)
+}
+
+/// 收藏一个常用目标目录
+#[tauri::command]
+pub fn add_favorite_target_dir(dir: String, app: AppHandle) -> Result<Vec<String>, String> {
+    let app_data_dir = get_app_data_dir(&app)?;
+    let mut current = settings::load_settings(&app_data_dir)?;
+    if !current.file_action_favorite_dirs.iter().any(|d| d.eq_ignore_ascii_case(&dir)) {
+        current.file_action_favorite_dirs.push(dir);
+        settings::save_settings(&app_data_dir, &current)?;
+    }
+    Ok(current.file_action_favorite_dirs)
+}
+
+/// 取消收藏常用目标目录
+#[tauri::command]
+pub fn remove_favorite_target_dir(dir: String, app: AppHandle) -> Result<Vec<String>, String> {
+    let app_data_dir = get_app_data_dir(&app)?;
+    let mut current = settings::load_settings(&app_data_dir)?;
+    current.file_action_favorite_dirs.retain(|d| !d.eq_ignore_ascii_case(&dir));
+    settings::save_settings(&app_data_dir, &current)?;
+    Ok(current.file_action_favorite_dirs)
+}
@@ -0,0 +1,102 @@
+//! 快速笔记相关命令模块
+//!
+//! 提供笔记的保存、搜索、便签窗口与唤出快捷键
+
+use super::{get_app_data_dir, show_and_focus_window};
+use crate::notes::{self, NoteItem, NoteSearchHit};
+use tauri::{AppHandle, Manager};
+
+/// 便签窗口快捷键在插件快捷键表中的 ID
+pub const NOTES_HOTKEY_ID: &str = "notes";
+
+#[tauri::command]
+pub fn get_all_notes(app: AppHandle) -> Result<Vec<NoteItem>, String> {
+    let app_data_dir = get_app_data_dir(&app)?;
+    notes::get_all_notes(&app_data_dir)
+}
+
+/// 保存笔记（id 为空时新建，title 为空时取内容首行）
+#[tauri::command]
+pub fn save_note(
+    id: Option<String>,
+    title: Option<String>,
+    content: String,
+    app: AppHandle,
+) -> Result<NoteItem, String> {
+    let app_data_dir = get_app_data_dir(&app)?;
+    notes::save_note(id, title, content, &app_data_dir)
+}
+
+#[tauri::command]
+pub fn delete_note(id: String, app: AppHandle) -> Result<(), String> {
+    let app_data_dir = get_app_data_dir(&app)?;
+    notes::delete_note(&id, &app_data_dir)
+}
+
+/// 全文搜索笔记，供启动器与便签窗口使用
+#[tauri::command]
+pub fn search_notes(query: String, limit: Option<usize>, app: AppHandle) -> Result<Vec<NoteSearchHit>, String> {
+    let app_data_dir = get_app_data_dir(&app)?;
+    notes::search_notes(&query, limit.unwrap_or(20), &app_data_dir)
+}
+
+/// 显示便签窗口；note_id 指定时打开对应笔记（通过 "notes-open" 事件通知前端）
+#[tauri::command]
+pub async fn show_notes_window(app: AppHandle, note_id: Option<String>) -> Result<(), String> {
+    use tauri::Emitter;
+
+    let window = match app.get_webview_window("notes-window") {
+        Some(window) => window,
+        None => tauri::WebviewWindowBuilder::new(
+            &app,
+            "notes-window",
+            tauri::WebviewUrl::App("index.html".into()),
+        )
+        .title("快速笔记")
+        .inner_size(420.0, 480.0)
+        .min_inner_size(300.0, 240.0)
+        .resizable(true)
+        .always_on_top(true)
+        .center()
+        .build()
+        .map_err(|e| format!("创建笔记窗口失败: {}", e))?,
+    };
+    show_and_focus_window(&window)?;
+
+    if let Some(id) = note_id {
+        let _ = window.emit("notes-open", id);
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_notes_hotkey(app: AppHandle) -> Result<Option<crate::settings::HotkeyConfig>, String> {
+    let app_data_dir = get_app_data_dir(&app)?;
+    Ok(crate::settings::load_settings(&app_data_dir)?.notes_hotkey)
+}
+
+/// 保存便签窗口快捷键（None 表示取消）
+#[tauri::command]
+pub fn save_notes_hotkey(
+    app: AppHandle,
+    config: Option<crate::settings::HotkeyConfig>,
+) -> Result<(), String> {
+    let app_data_dir = get_app_data_dir(&app)?;
+    let mut settings = crate::settings::load_settings(&app_data_dir)?;
+    settings.notes_hotkey = config.clone();
+    crate::settings::save_settings(&app_data_dir, &settings)?;
+
+    #[cfg(target_os = "windows")]
+    {
+        let hotkey_id = NOTES_HOTKEY_ID.to_string();
+        let result = match config {
+            Some(hotkey) => crate::hotkey_handler::windows::register_plugin_hotkey(hotkey_id, hotkey),
+            None => crate::hotkey_handler::windows::unregister_plugin_hotkey(&hotkey_id),
+        };
+        if let Err(e) = result {
+            eprintln!("Failed to update notes hotkey: {}", e);
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,166 @@
+//! 软件安装（winget / scoop）相关命令模块
+//!
+//! 安装在后台进程中执行，每行输出通过 "package-install-progress" 事件推送，
+//! 结束时推送 "package-install-done" 并弹出通知
+
+use crate::package_manager::{self, InstallProgress, PackageInfo, PackageSource};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex};
+use tauri::{async_runtime, AppHandle, Emitter};
+
+/// 进行中的安装：安装 ID -> (来源, 包标识, 进程 ID)
+static INSTALLS: LazyLock<Mutex<HashMap<String, (PackageSource, String, u32)>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+#[derive(Serialize, Clone)]
+struct ProgressEvent<'a> {
+    install_id: &'a str,
+    source: PackageSource,
+    id: &'a str,
+    #[serde(flatten)]
+    progress: InstallProgress,
+}
+
+#[derive(Serialize, Clone)]
+struct DoneEvent<'a> {
+    install_id: &'a str,
+    source: PackageSource,
+    id: &'a str,
+    success: bool,
+    /// 失败原因或最后一行输出
+    message: String,
+}
+
+/// 启动器输入 "install xxx" 时搜索 winget / scoop，输入不是安装命令时返回 None
+#[tauri::command]
+pub async fn search_packages(query: String) -> Result<Option<Vec<PackageInfo>>, String> {
+    let Some(keyword) = package_manager::parse_install_query(&query).map(str::to_string) else {
+        return Ok(None);
+    };
+    async_runtime::spawn_blocking(move || package_manager::search(&keyword).map(Some))
+        .await
+        .map_err(|e| format!("Failed to run package search: {}", e))?
+}
+
+/// 在后台安装软件包，立即返回安装 ID（用于匹配进度事件与取消）
+#[tauri::command]
+pub fn install_package(
+    source: PackageSource,
+    id: String,
+    app: AppHandle,
+) -> Result<String, String> {
+    let mut installs = INSTALLS.lock().map_err(|e| e.to_string())?;
+    if installs.values().any(|(s, i, _)| *s == source && *i == id) {
+        return Err(format!("{} is already being installed", id));
+    }
+
+    let mut child = package_manager::install_command(source, &id)?
+        .spawn()
+        .map_err(|e| format!("Failed to start {} install: {}", source.label(), e))?;
+    let install_id = format!(
+        "install-{}-{:04x}",
+        chrono::Utc::now().timestamp(),
+        rand::random::<u16>()
+    );
+    installs.insert(install_id.clone(), (source, id.clone(), child.id()));
+    drop(installs);
+    eprintln!("[PackageInstall] Installing {} via {}", id, source.label());
+
+    let stdout = child.stdout.take();
+    let stderr = child.stderr.take();
+    let install_id_for_thread = install_id.clone();
+    std::thread::spawn(move || {
+        let install_id = install_id_for_thread;
+        // stderr 单独读取，避免管道写满阻塞子进程
+        let stderr_reader = std::thread::spawn(move || {
+            let mut lines = Vec::new();
+            if let Some(stderr) = stderr {
+                package_manager::read_progress(stderr, |p| lines.push(p.line));
+            }
+            lines
+        });
+        let mut last_line = String::new();
+        if let Some(stdout) = stdout {
+            package_manager::read_progress(stdout, |progress| {
+                last_line = progress.line.clone();
+                let _ = app.emit(
+                    "package-install-progress",
+                    ProgressEvent {
+                        install_id: &install_id,
+                        source,
+                        id: &id,
+                        progress,
+                    },
+                );
+            });
+        }
+        let status = child.wait();
+        let errors = stderr_reader.join().unwrap_or_default();
+        let cancelled = INSTALLS
+            .lock()
+            .map(|mut installs| installs.remove(&install_id).is_none())
+            .unwrap_or(false);
+
+        let (success, message) = match status {
+            Ok(status) if status.success() => (true, last_line),
+            _ if cancelled => (false, "Installation cancelled".to_string()),
+            Ok(status) => {
+                let reason = errors.last().cloned().unwrap_or(last_line);
+                (false, format!("Exit code {:?}: {}", status.code(), reason))
+            }
+            Err(e) => (false, format!("Failed to wait for installer: {}", e)),
+        };
+        eprintln!(
+            "[PackageInstall] {} {}: {}",
+            id,
+            if success { "installed" } else { "failed" },
+            message
+        );
+        let (title, body) = if success {
+            ("安装完成", format!("{} ({})", id, source.label()))
+        } else {
+            ("安装失败", format!("{}: {}", id, message))
+        };
+        let _ = app.emit(
+            "package-install-done",
+            DoneEvent {
+                install_id: &install_id,
+                source,
+                id: &id,
+                success,
+                message,
+            },
+        );
+        let _ = crate::toast::show_toast(&app, title, &body);
+    });
+
+    Ok(install_id)
+}
+
+/// 取消进行中的安装（结束安装进程及其子进程）
+#[tauri::command]
+pub fn cancel_package_install(install_id: String) -> Result<(), String> {
+    // 先移除记录，安装线程据此判断是被取消
+    let Some((_, id, pid)) = INSTALLS
+        .lock()
+        .map_err(|e| e.to_string())?
+        .remove(&install_id)
+    else {
+        return Ok(());
+    };
+    eprintln!("[PackageInstall] Cancelling install of {}", id);
+
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        std::process::Command::new("taskkill")
+            .args(["/PID", &pid.to_string(), "/T", "/F"])
+            .creation_flags(0x08000000) // CREATE_NO_WINDOW
+            .output()
+            .map_err(|e| format!("Failed to cancel install: {}", e))?;
+    }
+    #[cfg(not(target_os = "windows"))]
+    let _ = pid;
+    Ok(())
+}
@@ -1,12 +1,16 @@
 use crate::commands::get_app_data_dir;
+use crate::db;
 use crate::hooks;
-use crate::recording::{RecordingMeta, RecordingState};
-use crate::replay::ReplayState;
+use crate::playlists::{self, OnError, Playlist, PlaylistItem};
+use crate::recording::{RecordingMeta, RecordingState, ScreenInfo};
+use crate::replay::{self, HumanizeOptions, ReplayBreakpoint, ReplayState, StepNote};
+use serde::Serialize;
 use std::fs;
 use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, LazyLock, Mutex};
 use std::time::Duration;
-use tauri::Manager;
+use tauri::{Emitter, Manager};
 
 static RECORDING_STATE: LazyLock<Arc<Mutex<RecordingState>>> =
     LazyLock::new(|| Arc::new(Mutex::new(RecordingState::new())));
@@ -44,6 +48,10 @@ pub fn start_recording() -> Result<(), String> {
             state_guard = state.lock().map_err(|e| e.to_string())?;
         }
 
+        if crate::stealth::is_enabled() {
+            return Err("隐身模式下无法开始录制".to_string());
+        }
+
         // Start fresh recording
         state_guard.start();
         drop(state_guard);
@@ -73,6 +81,7 @@ pub fn stop_recording(app: tauri::AppHandle) -> Result<String, String> {
 
         // Get events before stopping
         let events = state_guard.events.clone();
+        let target_apps = state_guard.target_apps.clone();
         let duration_ms = state_guard.get_time_offset_ms().unwrap_or(0);
 
         state_guard.stop();
@@ -99,6 +108,8 @@ pub fn stop_recording(app: tauri::AppHandle) -> Result<String, String> {
             "events": events,
             "duration_ms": duration_ms,
             "created_at": chrono::Local::now().to_rfc3339(),
+            "target_apps": target_apps,
+            "screen": ScreenInfo::current(),
         });
 
         // Write to file
@@ -112,9 +123,69 @@ pub fn stop_recording(app: tauri::AppHandle) -> Result<String, String> {
     }
 }
 
+/// 暂停录制，暂停期间的输入不被记录，时间轴自动扣除暂停时长
+#[tauri::command]
+pub fn pause_recording() -> Result<(), String> {
+    let mut state = RECORDING_STATE.lock().map_err(|e| e.to_string())?;
+    if !state.is_recording {
+        return Err("Not currently recording".to_string());
+    }
+    state.pause();
+    Ok(())
+}
+
+/// 继续已暂停的录制
+#[tauri::command]
+pub fn resume_recording() -> Result<(), String> {
+    let mut state = RECORDING_STATE.lock().map_err(|e| e.to_string())?;
+    if !state.is_recording {
+        return Err("Not currently recording".to_string());
+    }
+    state.resume();
+    Ok(())
+}
+
+/// 录制是否处于暂停状态
+#[tauri::command]
+pub fn get_recording_paused() -> Result<bool, String> {
+    let state = RECORDING_STATE.lock().map_err(|e| e.to_string())?;
+    Ok(state.is_recording && state.is_paused)
+}
+
+/// 隐身模式下卸载录制钩子，退出隐身模式后若仍在录制则重新安装
+pub(crate) fn set_recording_hooks_suspended(suspended: bool) -> Result<(), String> {
+    #[cfg(target_os = "windows")]
+    {
+        if suspended {
+            return hooks::windows::uninstall_hooks();
+        }
+        let is_recording = RECORDING_STATE
+            .lock()
+            .map_err(|e| e.to_string())?
+            .is_recording;
+        if is_recording {
+            hooks::windows::install_hooks(RECORDING_STATE.clone())?;
+        }
+        Ok(())
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        let _ = suspended;
+        Ok(())
+    }
+}
+
 #[tauri::command]
-pub fn list_recordings(app: tauri::AppHandle) -> Result<Vec<RecordingMeta>, String> {
+pub async fn list_recordings(
+    app: tauri::AppHandle,
+    target_app: Option<String>,
+) -> Result<Vec<RecordingMeta>, String> {
     let app_data_dir = get_app_data_dir(&app)?;
+    db::run_blocking(move || scan_recordings(&app_data_dir, target_app)).await
+}
+
+fn scan_recordings(app_data_dir: &Path, target_app: Option<String>) -> Result<Vec<RecordingMeta>, String> {
     let recordings_dir = app_data_dir.join("recordings");
 
     // Create directory if it doesn't exist
@@ -137,7 +208,13 @@ pub fn list_recordings(app: tauri::AppHandle) -> Result<Vec<RecordingMeta>, Stri
         // Only process JSON files
         if path.extension().and_then(|s| s.to_str()) == Some("json") {
             if let Ok(meta) = extract_recording_meta(&path, &recordings_dir) {
-                recordings.push(meta);
+                // 按目标应用筛选（忽略大小写）
+                let matches_target = target_app.as_ref().map_or(true, |target| {
+                    meta.target_apps.iter().any(|app| app.eq_ignore_ascii_case(target))
+                });
+                if matches_target {
+                    recordings.push(meta);
+                }
             }
         }
     }
@@ -149,8 +226,12 @@ pub fn list_recordings(app: tauri::AppHandle) -> Result<Vec<RecordingMeta>, Stri
 }
 
 #[tauri::command]
-pub fn delete_recording(app: tauri::AppHandle, path: String) -> Result<(), String> {
+pub async fn delete_recording(app: tauri::AppHandle, path: String) -> Result<(), String> {
     let app_data_dir = get_app_data_dir(&app)?;
+    db::run_blocking(move || remove_recording_file(&app_data_dir, &path)).await
+}
+
+fn remove_recording_file(app_data_dir: &Path, path: &str) -> Result<(), String> {
     let recordings_dir = app_data_dir.join("recordings");
 
     // Remove "recordings/" prefix if present
@@ -160,7 +241,7 @@ pub fn delete_recording(app: tauri::AppHandle, path: String) -> Result<(), Strin
             .ok_or_else(|| format!("Invalid path format: {}", path))?;
         recordings_dir.join(filename)
     } else {
-        recordings_dir.join(&path)
+        recordings_dir.join(path)
     };
 
     // Validate that the file exists and is within the recordings directory
@@ -175,6 +256,7 @@ pub fn delete_recording(app: tauri::AppHandle, path: String) -> Result<(), Strin
 
     // Delete the file
     fs::remove_file(&file_path).map_err(|e| format!("Failed to delete recording file: {}", e))?;
+    replay::clear_breakpoint(app_data_dir, Some(path.strip_prefix("recordings/").unwrap_or(path)));
 
     Ok(())
 }
@@ -198,6 +280,19 @@ fn extract_recording_meta(
 
     let event_count = json["events"].as_array().map(|arr| arr.len()).unwrap_or(0);
 
+    let target_apps = json["target_apps"]
+        .as_array()
+        .map(|apps| {
+            apps.iter()
+                .filter_map(|app| app.as_str().map(|s| s.to_string()))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let screen = serde_json::from_value::<Option<ScreenInfo>>(json["screen"].clone())
+        .ok()
+        .flatten();
+
     let created_at = json["created_at"]
         .as_str()
         .ok_or_else(|| format!("Missing or invalid created_at in {}", file_path.display()))?
@@ -223,11 +318,101 @@ fn extract_recording_meta(
         duration_ms,
         event_count,
         created_at,
+        target_apps,
+        screen,
+    })
+}
+
+/// 回放前的屏幕检查结果
+#[derive(Debug, Clone, Serialize)]
+pub struct ScreenCheck {
+    /// 录制与当前的分辨率、DPI 是否一致（录制文件没有屏幕信息时视为一致）
+    pub matches: bool,
+    pub recorded: Option<ScreenInfo>,
+    pub current: Option<ScreenInfo>,
+    /// 开启自动缩放时录制坐标乘以的系数
+    pub scale_x: f64,
+    pub scale_y: f64,
+    pub warning: Option<String>,
+}
+
+fn check_screen(recorded: Option<ScreenInfo>) -> ScreenCheck {
+    let current = ScreenInfo::current();
+    let (matches, scale_x, scale_y) = match (&recorded, &current) {
+        (Some(recorded), Some(current)) => {
+            let (scale_x, scale_y) = recorded.scale_to(current);
+            (recorded == current, scale_x, scale_y)
+        }
+        _ => (true, 1.0, 1.0),
+    };
+    let warning = match (&recorded, &current) {
+        (Some(r), Some(c)) if !matches => Some(format!(
+            "录制时屏幕为 {}x{} ({} DPI)，当前为 {}x{} ({} DPI)，回放可能点偏，可开启自动坐标缩放",
+            r.width, r.height, r.dpi, c.width, c.height, c.dpi
+        )),
+        _ => None,
+    };
+    ScreenCheck {
+        matches,
+        recorded,
+        current,
+        scale_x,
+        scale_y,
+        warning,
+    }
+}
+
+/// 回放前比较录制时与当前的分辨率 / DPI
+#[tauri::command]
+pub async fn check_recording_screen(
+    app: tauri::AppHandle,
+    path: String,
+) -> Result<ScreenCheck, String> {
+    let app_data_dir = get_app_data_dir(&app)?;
+    let file_path = app_data_dir
+        .join("recordings")
+        .join(path.strip_prefix("recordings/").unwrap_or(&path));
+    db::run_blocking(move || {
+        let mut state = ReplayState::new();
+        state.load_recording(&file_path)?;
+        Ok(check_screen(state.recorded_screen))
     })
+    .await
 }
 
+/// 获取录制文件格式的 JSON Schema
 #[tauri::command]
-pub fn play_recording(app: tauri::AppHandle, path: String, speed: f32) -> Result<(), String> {
+pub fn get_recording_schema() -> serde_json::Value {
+    crate::recording_schema::schema()
+}
+
+/// 按内置 schema 校验录制文件（录制目录下的相对路径或任意绝对路径），返回带行号的错误列表
+#[tauri::command]
+pub async fn validate_recording(
+    app: tauri::AppHandle,
+    path: String,
+) -> Result<Vec<crate::recording_schema::ValidationIssue>, String> {
+    let app_data_dir = get_app_data_dir(&app)?;
+    let file_path = app_data_dir
+        .join("recordings")
+        .join(path.strip_prefix("recordings/").unwrap_or(&path));
+    db::run_blocking(move || {
+        let content = fs::read_to_string(&file_path)
+            .map_err(|e| format!("Failed to read file {}: {}", file_path.display(), e))?;
+        Ok(crate::recording_schema::validate(&content))
+    })
+    .await
+}
+
+#[tauri::command]
+pub fn play_recording(
+    app: tauri::AppHandle,
+    path: String,
+    speed: f32,
+    humanize: Option<HumanizeOptions>,
+    auto_scale: Option<bool>,
+    resume_from_last: Option<bool>,
+) -> Result<(), String> {
     #[cfg(not(target_os = "windows"))]
     {
         return Err("Replay is only supported on Windows".to_string());
@@ -275,21 +460,60 @@ pub fn play_recording(app: tauri::AppHandle, path: String, speed: f32) -> Result
             ));
         }
 
-        state.start(speed);
+        // 屏幕与录制时不一致：开启自动缩放时按比例换算坐标，否则只记录警告
+        let screen = check_screen(state.recorded_screen);
+        let scale = if !screen.matches && auto_scale.unwrap_or(false) {
+            Some((screen.scale_x, screen.scale_y))
+        } else {
+            if let Some(warning) = &screen.warning {
+                eprintln!("[Replay] {}", warning);
+            }
+            None
+        };
+
+        // 从上次中断的步骤继续：断点属于本录制且事件数未变化时才生效
+        let recording_name = path.strip_prefix("recordings/").unwrap_or(&path).to_string();
+        let start_index = if resume_from_last.unwrap_or(false) {
+            replay::load_breakpoint(&app_data_dir)
+                .filter(|b| b.recording == recording_name && b.total == state.current_events.len())
+                .map(|b| b.index)
+                .unwrap_or(0)
+        } else {
+            0
+        };
+        state.start_at(speed, start_index);
+
+        // 本次回放的日志关联 ID
+        let trace_id = crate::logger::new_trace_id();
+        crate::log!(
+            trace: trace_id,
+            "Replay",
+            "开始回放: {} ({} 个事件, 从第 {} 步开始, 速度 {}x)",
+            path,
+            state.current_events.len(),
+            start_index,
+            speed
+        );
 
         // Start replay task in a separate thread (not async) since Windows API calls
         // should be done in a blocking context
         let replay_state = Arc::clone(&REPLAY_STATE);
         let speed_multiplier = speed.max(0.1).min(10.0); // Ensure speed is between 0.1 and 10.0
+        let humanize = humanize.unwrap_or_default();
+        let app_handle = app.clone();
 
         std::thread::spawn(move || {
             let mut last_time = 0u64;
             let mut last_mouse_move_time = 0u64;
             let mut event_count = 0u64;
+            // 按绝对时间轴调度（QPC 微秒），避免逐次 sleep 的误差累积
+            let mut next_due_us = 0u64;
             const MAX_EVENTS: u64 = 100000; // Safety limit
                                             // Minimum interval between mouse move events in the recording (based on event time offset)
                                             // This helps prevent system overload from too many rapid mouse moves
-            const MIN_MOUSE_MOVE_INTERVAL_MS: u64 = 5; // 5ms minimum between recorded mouse moves
+            const MIN_MOUSE_MOVE_INTERVAL_US: u64 = 5_000; // 5ms minimum between recorded mouse moves
+            // 落后时间轴超过该值时重新对齐，防止注入卡顿后连发事件
+            const MAX_LAG_US: u64 = 50_000;
 
             loop {
                 // Check if Esc key is pressed to stop playback
@@ -303,6 +527,7 @@ pub fn play_recording(app: tauri::AppHandle, path: String, speed: f32) -> Result
                         let key_state = GetAsyncKeyState(VK_ESCAPE) as u16;
                         if key_state & 0x8000 != 0 {
                             eprintln!("Esc key pressed, stopping playback");
+                            crate::log!(trace: trace_id, "Replay", "按 Esc 中止回放");
                             if let Ok(mut state) = replay_state.lock() {
                                 state.stop();
                             }
@@ -322,7 +547,7 @@ pub fn play_recording(app: tauri::AppHandle, path: String, speed: f32) -> Result
                 }
 
                 // Get event while holding lock briefly
-                let (event_opt, is_playing) = {
+                let (event_opt, is_playing, step_note) = {
                     let mut state = match replay_state.lock() {
                         Ok(s) => s,
                         Err(_) => break,
@@ -334,68 +559,146 @@ pub fn play_recording(app: tauri::AppHandle, path: String, speed: f32) -> Result
 
                     let event = state.get_next_event();
                     let is_playing = state.is_playing;
-                    (event, is_playing)
+                    // 本步骤带注释时推送给悬浮状态条
+                    let step_note = event
+                        .as_ref()
+                        .and_then(|e| e.note.as_ref())
+                        .and(state.current_note.clone());
+                    (event, is_playing, step_note)
                 };
 
                 if !is_playing {
                     break;
                 }
 
-                if let Some(event) = event_opt {
+                if let Some(step_note) = &step_note {
+                    let _ = app_handle.emit("replay-step-note", step_note);
+                }
+
+                if let Some(mut event) = event_opt {
+                    if let Some((scale_x, scale_y)) = scale {
+                        event.x = event.x.map(|x| (x as f64 * scale_x).round() as i32);
+                        event.y = event.y.map(|y| (y as f64 * scale_y).round() as i32);
+                    }
                     // For mouse move events, only skip if the time difference from last mouse move
                     // is too small (based on recorded event times, not system time)
+                    let event_time = event.offset_us();
                     if matches!(event.event_type, crate::recording::EventType::MouseMove) {
                         if last_mouse_move_time > 0 {
-                            let time_diff = event.time_offset_ms.saturating_sub(last_mouse_move_time);
+                            let time_diff = event_time.saturating_sub(last_mouse_move_time);
                             // Skip only if the recorded interval is less than minimum
-                            if time_diff < MIN_MOUSE_MOVE_INTERVAL_MS && time_diff > 0 {
+                            if time_diff < MIN_MOUSE_MOVE_INTERVAL_US && time_diff > 0 {
                                 // Update last_time but skip execution
-                                last_time = event.time_offset_ms;
+                                last_time = event_time;
                                 continue;
                             }
                         }
-                        last_mouse_move_time = event.time_offset_ms;
+                        last_mouse_move_time = event_time;
                     }
 
                     // Calculate delay based on time offset
-                    let delay_ms = if last_time == 0 {
+                    let delay_us = if last_time == 0 {
                         // First event, add a small delay to let system stabilize
-                        50
+                        50_000
+                    } else {
+                        let diff = event_time.saturating_sub(last_time);
+                        // Use saturating cast to prevent overflow
+                        let calculated = (diff as f64 / speed_multiplier as f64) as u64;
+                        calculated.min(60_000_000) // At most 60 seconds
+                    };
+
+                    let is_key_event = matches!(
+                        event.event_type,
+                        crate::recording::EventType::KeyDown { .. }
+                            | crate::recording::EventType::KeyUp { .. }
+                    );
+                    // 键盘事件按拟人化配置随机化间隔
+                    let delay_us = if is_key_event && last_time != 0 {
+                        (humanize.key_delay_ms(delay_us / 1000) * 1000).max(delay_us)
                     } else {
-                        let diff = event.time_offset_ms.saturating_sub(last_time);
-                        // Use saturating cast to prevent overflow, ensure minimum delay
-                        let calculated = (diff as f32 / speed_multiplier) as u64;
-                        calculated.max(1).min(60000) // Between 1ms and 60 seconds
+                        delay_us
                     };
+                    let retries = if is_key_event { humanize.retries() } else { 0 };
 
-                    if delay_ms > 0 {
-                        std::thread::sleep(Duration::from_millis(delay_ms));
+                    let now_us = crate::hires_clock::now_us();
+                    if last_time == 0 || now_us.saturating_sub(next_due_us) > MAX_LAG_US {
+                        next_due_us = now_us;
+                    }
+                    next_due_us += delay_us;
+
+                    // 轨迹平滑：提前出发，沿插值轨迹在事件的预定时间到达目标点
+                    let glide = match (&event.event_type, event.x, event.y) {
+                        (
+                            crate::recording::EventType::MouseMove
+                            | crate::recording::EventType::MouseDown { .. },
+                            Some(x),
+                            Some(y),
+                        ) if humanize.smooth_move.enabled => ReplayState::cursor_pos()
+                            .map(|from| (from, (x, y), humanize.smooth_move.duration_us(from, (x, y))))
+                            .filter(|(_, _, duration_us)| *duration_us > 0),
+                        _ => None,
+                    };
+                    let glide_us = glide.map_or(0, |(_, _, duration_us)| duration_us);
+                    crate::hires_clock::sleep_until_us(next_due_us.saturating_sub(glide_us));
+                    if let Some((from, to, duration_us)) = glide {
+                        ReplayState::smooth_move(from, to, duration_us, &humanize.smooth_move);
+                        next_due_us = next_due_us.max(crate::hires_clock::now_us());
                     }
 
                     // Execute the event with error handling
-                    match crate::replay::ReplayState::execute_event(&event) {
+                    match crate::replay::ReplayState::execute_event_with_retry(&event, retries) {
                         Ok(_) => {}
                         Err(e) => {
-                            eprintln!("Failed to execute event: {}", e);
+                            let note = replay_state
+                                .lock()
+                                .ok()
+                                .and_then(|s| s.current_note.clone())
+                                .map(|n| format!(" [step {}: {}]", n.index, n.note))
+                                .unwrap_or_default();
+                            eprintln!("Failed to execute event{}: {}", note, e);
+                            crate::log!(trace: trace_id, "Replay", "事件执行失败{}: {}", note, e);
                             // Continue with next event instead of crashing
                         }
                     }
 
-                    last_time = event.time_offset_ms;
+                    last_time = event_time;
                 } else {
                     // No more events, stop playback
+                    crate::log!(trace: trace_id, "Replay", "回放完成");
                     if let Ok(mut state) = replay_state.lock() {
+                        state.finished = true;
                         state.stop();
                     }
                     break;
                 }
             }
+
+            // 中途停止时记录断点，完整播放后清除
+            let stopped = replay_state
+                .lock()
+                .ok()
+                .map(|s| (s.finished, s.stopped_index, s.current_events.len()));
+            match stopped {
+                Some((false, index, total)) if index > 0 && index < total => {
+                    if let Err(e) = replay::save_breakpoint(&app_data_dir, &recording_name, index, total) {
+                        eprintln!("[Replay] {}", e);
+                    }
+                }
+                _ => replay::clear_breakpoint(&app_data_dir, Some(&recording_name)),
+            }
         });
 
         Ok(())
     }
 }
 
+/// 最近一次中途停止的回放断点（录制文件名 + 步骤序号）
+#[tauri::command]
+pub fn get_replay_breakpoint(app: tauri::AppHandle) -> Result<Option<ReplayBreakpoint>, String> {
+    let app_data_dir = get_app_data_dir(&app)?;
+    Ok(replay::load_breakpoint(&app_data_dir))
+}
+
 #[tauri::command]
 pub fn stop_playback() -> Result<(), String> {
     let mut state = REPLAY_STATE.lock().map_err(|e| e.to_string())?;
@@ -419,3 +722,236 @@ pub fn get_playback_progress() -> Result<f32, String> {
     let state = REPLAY_STATE.lock().map_err(|e| e.to_string())?;
     Ok(state.get_progress())
 }
+
+/// 当前回放步骤的注释（最近一次执行到的带注释步骤）
+#[tauri::command]
+pub fn get_playback_step_note() -> Result<Option<StepNote>, String> {
+    let state = REPLAY_STATE.lock().map_err(|e| e.to_string())?;
+    Ok(state.current_note.clone())
+}
+
+/// 设置录制中某个步骤（事件序号）的注释，note 为空时清除
+#[tauri::command]
+pub async fn set_recording_step_note(
+    app: tauri::AppHandle,
+    path: String,
+    index: usize,
+    note: Option<String>,
+) -> Result<(), String> {
+    let app_data_dir = get_app_data_dir(&app)?;
+    db::run_blocking(move || {
+        let recordings_dir = app_data_dir.join("recordings");
+        let file_path = recordings_dir.join(path.strip_prefix("recordings/").unwrap_or(&path));
+        if !file_path.starts_with(&recordings_dir) || !file_path.exists() {
+            return Err(format!("Recording file not found: {}", path));
+        }
+
+        let content = fs::read_to_string(&file_path)
+            .map_err(|e| format!("Failed to read recording: {}", e))?;
+        let mut json: serde_json::Value = serde_json::from_str(&content)
+            .map_err(|e| format!("Failed to parse recording: {}", e))?;
+        let event = json["events"]
+            .get_mut(index)
+            .and_then(|e| e.as_object_mut())
+            .ok_or_else(|| format!("Step {} not found in recording", index))?;
+        match note.map(|n| n.trim().to_string()).filter(|n| !n.is_empty()) {
+            Some(note) => {
+                event.insert("note".to_string(), serde_json::Value::String(note));
+            }
+            None => {
+                event.remove("note");
+            }
+        }
+
+        let content = serde_json::to_string_pretty(&json)
+            .map_err(|e| format!("Failed to serialize recording: {}", e))?;
+        fs::write(&file_path, content).map_err(|e| format!("Failed to write recording: {}", e))
+    })
+    .await
+}
+
+// ========================================
+// Playlist Commands
+// ========================================
+
+static PLAYLIST_RUNNING: AtomicBool = AtomicBool::new(false);
+static PLAYLIST_CANCEL: AtomicBool = AtomicBool::new(false);
+
+/// 播放列表执行进度，通过 "playlist-progress" 事件推送
+#[derive(Serialize, Clone)]
+struct PlaylistProgress {
+    playlist_id: String,
+    item_index: usize,
+    repeat_index: u32,
+    /// playing / failed / skipped / aborted / finished
+    status: &'static str,
+    error: Option<String>,
+    /// 失败或中止时所在步骤的注释
+    note: Option<StepNote>,
+}
+
+enum StepError {
+    Failed(String),
+    Aborted,
+}
+
+/// 等待当前回放结束，返回是否完整播放（被用户或播放列表取消时为 false）
+fn wait_for_playback() -> bool {
+    loop {
+        std::thread::sleep(Duration::from_millis(50));
+        let Ok(mut state) = REPLAY_STATE.lock() else {
+            return false;
+        };
+        if PLAYLIST_CANCEL.load(Ordering::SeqCst) {
+            state.stop();
+            return false;
+        }
+        if !state.is_playing {
+            return state.finished;
+        }
+    }
+}
+
+/// 可被 stop_playlist 打断的等待，返回是否被取消
+fn sleep_cancellable(duration: Duration) -> bool {
+    let deadline = std::time::Instant::now() + duration;
+    while std::time::Instant::now() < deadline {
+        if PLAYLIST_CANCEL.load(Ordering::SeqCst) {
+            return true;
+        }
+        std::thread::sleep(Duration::from_millis(50));
+    }
+    PLAYLIST_CANCEL.load(Ordering::SeqCst)
+}
+
+fn play_item_once(app: &tauri::AppHandle, item: &PlaylistItem) -> Result<(), StepError> {
+    play_recording(app.clone(), item.recording.clone(), item.speed, None, None, None).map_err(StepError::Failed)?;
+    if wait_for_playback() {
+        Ok(())
+    } else {
+        Err(StepError::Aborted)
+    }
+}
+
+fn run_playlist(app: &tauri::AppHandle, playlist: &Playlist) {
+    let emit = |item_index: usize, repeat_index: u32, status: &'static str, error: Option<String>| {
+        let note = match status {
+            "failed" | "skipped" | "aborted" => REPLAY_STATE.lock().ok().and_then(|s| s.current_note.clone()),
+            _ => None,
+        };
+        let _ = app.emit(
+            "playlist-progress",
+            PlaylistProgress {
+                playlist_id: playlist.id.clone(),
+                item_index,
+                repeat_index,
+                status,
+                error,
+                note,
+            },
+        );
+    };
+
+    for (item_index, item) in playlist.items.iter().enumerate() {
+        'repeat: for repeat_index in 0..item.repeat.max(1) {
+            emit(item_index, repeat_index, "playing", None);
+
+            let mut attempts = 0;
+            loop {
+                match play_item_once(app, item) {
+                    Ok(()) => break,
+                    Err(StepError::Aborted) => {
+                        emit(item_index, repeat_index, "aborted", None);
+                        return;
+                    }
+                    Err(StepError::Failed(e)) if attempts < playlist.max_retries => {
+                        eprintln!("[Playlist] {} failed, retrying: {}", item.recording, e);
+                        attempts += 1;
+                    }
+                    Err(StepError::Failed(e)) => match playlist.on_error {
+                        OnError::Skip => {
+                            emit(item_index, repeat_index, "skipped", Some(e));
+                            break 'repeat;
+                        }
+                        OnError::Stop => {
+                            emit(item_index, repeat_index, "failed", Some(e));
+                            return;
+                        }
+                    },
+                }
+            }
+
+            if item.interval_ms > 0 && sleep_cancellable(Duration::from_millis(item.interval_ms)) {
+                emit(item_index, repeat_index, "aborted", None);
+                return;
+            }
+        }
+    }
+
+    emit(playlist.items.len(), 0, "finished", None);
+}
+
+/// 新建或更新播放列表
+#[tauri::command]
+pub async fn create_playlist(
+    app: tauri::AppHandle,
+    id: Option<String>,
+    name: String,
+    items: Vec<PlaylistItem>,
+    on_error: Option<OnError>,
+    max_retries: Option<u32>,
+) -> Result<Playlist, String> {
+    let app_data_dir = get_app_data_dir(&app)?;
+    db::run_blocking(move || {
+        playlists::save_playlist(
+            id,
+            name,
+            items,
+            on_error.unwrap_or_default(),
+            max_retries.unwrap_or(0),
+            &app_data_dir,
+        )
+    })
+    .await
+}
+
+#[tauri::command]
+pub async fn list_playlists(app: tauri::AppHandle) -> Result<Vec<Playlist>, String> {
+    let app_data_dir = get_app_data_dir(&app)?;
+    db::run_blocking(move || playlists::get_all_playlists(&app_data_dir)).await
+}
+
+#[tauri::command]
+pub async fn delete_playlist(app: tauri::AppHandle, id: String) -> Result<(), String> {
+    let app_data_dir = get_app_data_dir(&app)?;
+    db::run_blocking(move || playlists::delete_playlist(&id, &app_data_dir)).await
+}
+
+/// 按顺序执行播放列表，进度通过 "playlist-progress" 事件推送
+#[tauri::command]
+pub fn play_playlist(app: tauri::AppHandle, id: String) -> Result<(), String> {
+    let app_data_dir = get_app_data_dir(&app)?;
+    let playlist = playlists::get_playlist(&id, &app_data_dir)?
+        .ok_or_else(|| format!("Playlist {} not found", id))?;
+
+    if PLAYLIST_RUNNING.swap(true, Ordering::SeqCst) {
+        return Err("Playlist already playing".to_string());
+    }
+    PLAYLIST_CANCEL.store(false, Ordering::SeqCst);
+
+    std::thread::spawn(move || {
+        run_playlist(&app, &playlist);
+        PLAYLIST_RUNNING.store(false, Ordering::SeqCst);
+    });
+    Ok(())
+}
+
+/// 停止正在执行的播放列表（同时停止当前回放）
+#[tauri::command]
+pub fn stop_playlist() -> Result<(), String> {
+    if !PLAYLIST_RUNNING.load(Ordering::SeqCst) {
+        return Err("No playlist is playing".to_string());
+    }
+    PLAYLIST_CANCEL.store(true, Ordering::SeqCst);
+    Ok(())
+}
@@ -0,0 +1,269 @@
+//! 屏幕取字模块
+//!
+//! 复用屏幕标尺的拖拽框选得到区域，截图后交给 Windows.Media.Ocr 识别，
+//! 按行位置整理阅读顺序后写入剪贴板，可同时写入剪贴板历史。
+
+/// 识别结果中的一行文本及其位置（物理像素，相对截图区域）
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct OcrLine {
+    pub text: String,
+    pub x: f64,
+    pub y: f64,
+    pub height: f64,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct OcrResult {
+    /// 按版面顺序拼接后的文本
+    pub text: String,
+    pub lines: Vec<OcrLine>,
+}
+
+fn is_cjk(c: char) -> bool {
+    matches!(c as u32,
+        0x3000..=0x303F // CJK 标点
+        | 0x3040..=0x30FF // 假名
+        | 0x3400..=0x4DBF
+        | 0x4E00..=0x9FFF
+        | 0xF900..=0xFAFF
+        | 0xFF00..=0xFFEF // 全角字符
+    )
+}
+
+/// OCR 会在中文字符之间插入空格，去掉两侧都是 CJK 字符的空格，保留英文单词间的空格
+fn join_cjk_spaces(text: &str) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut result = String::with_capacity(text.len());
+    for (i, &c) in chars.iter().enumerate() {
+        if c == ' ' {
+            let prev = result.chars().last();
+            let next = chars.get(i + 1).copied();
+            if let (Some(prev), Some(next)) = (prev, next) {
+                if is_cjk(prev) && is_cjk(next) {
+                    continue;
+                }
+            }
+        }
+        result.push(c);
+    }
+    result
+}
+
+/// 按版面整理阅读顺序：纵向位置重叠超过半行高的行视为同一行，同行内从左到右以空格连接
+fn layout_text(lines: &[OcrLine]) -> String {
+    let mut sorted: Vec<&OcrLine> = lines.iter().collect();
+    sorted.sort_by(|a, b| a.y.total_cmp(&b.y).then(a.x.total_cmp(&b.x)));
+
+    let mut rows: Vec<Vec<&OcrLine>> = Vec::new();
+    for line in sorted {
+        let center = line.y + line.height / 2.0;
+        match rows.last_mut() {
+            Some(row) if (center - (row[0].y + row[0].height / 2.0)).abs() < row[0].height.max(line.height) / 2.0 => {
+                row.push(line)
+            }
+            _ => rows.push(vec![line]),
+        }
+    }
+
+    rows.into_iter()
+        .map(|mut row| {
+            row.sort_by(|a, b| a.x.total_cmp(&b.x));
+            let joined = row.iter().map(|l| l.text.as_str()).collect::<Vec<_>>().join(" ");
+            join_cjk_spaces(&joined)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(target_os = "windows")]
+mod windows {
+    use super::OcrLine;
+
+    /// 截取屏幕区域为自上而下的 BGRA 像素
+    pub fn capture_region(left: i32, top: i32, width: i32, height: i32) -> Result<Vec<u8>, String> {
+        use windows_sys::Win32::Graphics::Gdi::{
+            BitBlt, CreateCompatibleBitmap, CreateCompatibleDC, DeleteDC, DeleteObject, GetDC, GetDIBits,
+            ReleaseDC, SelectObject, BITMAPINFO, BITMAPINFOHEADER, BI_RGB, DIB_RGB_COLORS, SRCCOPY,
+        };
+
+        unsafe {
+            let screen_dc = GetDC(0);
+            if screen_dc == 0 {
+                return Err("获取屏幕 DC 失败".to_string());
+            }
+            let mem_dc = CreateCompatibleDC(screen_dc);
+            let bitmap = CreateCompatibleBitmap(screen_dc, width, height);
+            let old = SelectObject(mem_dc, bitmap);
+            let copied = BitBlt(mem_dc, 0, 0, width, height, screen_dc, left, top, SRCCOPY);
+
+            let mut info: BITMAPINFO = std::mem::zeroed();
+            info.bmiHeader.biSize = std::mem::size_of::<BITMAPINFOHEADER>() as u32;
+            info.bmiHeader.biWidth = width;
+            info.bmiHeader.biHeight = -height; // 负值表示自上而下
+            info.bmiHeader.biPlanes = 1;
+            info.bmiHeader.biBitCount = 32;
+            info.bmiHeader.biCompression = BI_RGB;
+            let mut pixels = vec![0u8; width as usize * height as usize * 4];
+            let lines = GetDIBits(
+                mem_dc,
+                bitmap,
+                0,
+                height as u32,
+                pixels.as_mut_ptr() as *mut _,
+                &mut info,
+                DIB_RGB_COLORS,
+            );
+
+            SelectObject(mem_dc, old);
+            DeleteObject(bitmap);
+            DeleteDC(mem_dc);
+            ReleaseDC(0, screen_dc);
+
+            if copied == 0 || lines == 0 {
+                return Err("截取屏幕区域失败".to_string());
+            }
+            // GDI 截图的 alpha 通道未定义，统一设为不透明
+            for pixel in pixels.chunks_exact_mut(4) {
+                pixel[3] = 255;
+            }
+            Ok(pixels)
+        }
+    }
+
+    /// 系统 OCR 支持的最大图像宽高
+    pub fn max_dimension() -> Result<u32, String> {
+        ::windows::Media::Ocr::OcrEngine::MaxImageDimension().map_err(|e| format!("OCR 识别失败: {}", e))
+    }
+
+    /// 使用系统 OCR（用户语言配置中已安装的识别语言）识别 BGRA 图像
+    pub fn recognize(pixels: &[u8], width: i32, height: i32) -> Result<Vec<OcrLine>, String> {
+        use ::windows::Graphics::Imaging::{BitmapAlphaMode, BitmapPixelFormat, SoftwareBitmap};
+        use ::windows::Media::Ocr::OcrEngine;
+        use ::windows::Storage::Streams::DataWriter;
+        use ::windows::Win32::System::Com::{CoInitializeEx, COINIT_MULTITHREADED};
+
+        let err = |e: ::windows::core::Error| format!("OCR 识别失败: {}", e);
+
+        unsafe {
+            // 可能已被初始化为其他套间模式，失败时忽略
+            let _ = CoInitializeEx(None, COINIT_MULTITHREADED);
+        }
+
+        let max = OcrEngine::MaxImageDimension().map_err(err)? as i32;
+        if width > max || height > max {
+            return Err(format!("选区过大，宽高不能超过 {} 像素", max));
+        }
+
+        let writer = DataWriter::new().map_err(err)?;
+        writer.WriteBytes(pixels).map_err(err)?;
+        let buffer = writer.DetachBuffer().map_err(err)?;
+        let bitmap = SoftwareBitmap::CreateCopyWithAlphaFromBuffer(
+            &buffer,
+            BitmapPixelFormat::Bgra8,
+            width,
+            height,
+            BitmapAlphaMode::Premultiplied,
+        )
+        .map_err(err)?;
+
+        let engine = OcrEngine::TryCreateFromUserProfileLanguages()
+            .map_err(|_| "系统未安装可用的 OCR 语言包".to_string())?;
+        let result = engine.RecognizeAsync(&bitmap).map_err(err)?.get().map_err(err)?;
+
+        let lines = result.Lines().map_err(err)?;
+        let mut output = Vec::new();
+        for i in 0..lines.Size().map_err(err)? {
+            let line = lines.GetAt(i).map_err(err)?;
+            let text = line.Text().map_err(err)?.to_string();
+            let words = line.Words().map_err(err)?;
+            let (mut x, mut y, mut height) = (f64::MAX, f64::MAX, 0f64);
+            for j in 0..words.Size().map_err(err)? {
+                let rect = words.GetAt(j).map_err(err)?.BoundingRect().map_err(err)?;
+                x = x.min(rect.X as f64);
+                y = y.min(rect.Y as f64);
+                height = height.max(rect.Height as f64);
+            }
+            if text.trim().is_empty() || x == f64::MAX {
+                continue;
+            }
+            output.push(OcrLine { text, x, y, height });
+        }
+        Ok(output)
+    }
+}
+
+/// 识别 RGBA 图片中的文字并按版面整理，超过 OCR 尺寸上限时先按比例缩小（剪贴板图片后台识别使用）
+#[cfg(target_os = "windows")]
+pub(crate) fn recognize_rgba(rgba: &[u8], width: u32, height: u32) -> Result<String, String> {
+    let max = windows::max_dimension()?;
+    let resized = crate::clipboard_image::downscale(rgba, width, height, max);
+    let (pixels, width, height) = match &resized {
+        Some((pixels, w, h)) => (pixels.as_slice(), *w, *h),
+        None => (rgba, width, height),
+    };
+    let bgra: Vec<u8> = pixels
+        .chunks_exact(4)
+        .flat_map(|p| [p[2], p[1], p[0], 255])
+        .collect();
+    let lines = windows::recognize(&bgra, width as i32, height as i32)?;
+    Ok(layout_text(&lines))
+}
+
+/// 框选屏幕区域并识别文字，识别结果写入剪贴板；save_to_history 为 true（默认）时同时写入剪贴板历史
+#[tauri::command]
+pub async fn ocr_screen_region(
+    app: tauri::AppHandle,
+    save_to_history: Option<bool>,
+) -> Result<Option<OcrResult>, String> {
+    let Some(area) = super::screen_ruler::measure_screen(app.clone()).await? else {
+        return Ok(None);
+    };
+    if area.width < 4 || area.height < 4 {
+        return Err("选区太小，请拖拽框选要识别的区域".to_string());
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        let left = area.start_x.min(area.end_x);
+        let top = area.start_y.min(area.end_y);
+        let (width, height) = (area.width as i32, area.height as i32);
+
+        let result = tokio::task::spawn_blocking(move || {
+            // 等待选区边框与信息窗口消失后再截图
+            std::thread::sleep(std::time::Duration::from_millis(80));
+            let pixels = windows::capture_region(left, top, width, height)?;
+            let lines = windows::recognize(&pixels, width, height)?;
+            Ok::<_, String>(OcrResult {
+                text: layout_text(&lines),
+                lines,
+            })
+        })
+        .await
+        .map_err(|e| format!("OCR 任务失败: {}", e))??;
+
+        if result.text.is_empty() {
+            let _ = crate::toast::show_toast(&app, "屏幕取字", "未识别到文字");
+            return Ok(Some(result));
+        }
+
+        crate::clipboard::monitor::set_clipboard_text(&result.text)?;
+        if save_to_history.unwrap_or(true) {
+            let app_data_dir = super::get_app_data_dir(&app)?;
+            let text = result.text.clone();
+            crate::db::run_blocking(move || {
+                crate::clipboard::add_clipboard_item(text, "text".to_string(), &app_data_dir)
+            })
+            .await?;
+        }
+        if let Err(e) = crate::toast::show_toast(&app, "已复制识别文字", &result.text) {
+            eprintln!("[ScreenOcr] Failed to show toast: {}", e);
+        }
+        Ok(Some(result))
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        let _ = (area, save_to_history);
+        Err("屏幕取字功能目前仅支持 Windows".to_string())
+    }
+}
@@ -0,0 +1,353 @@
+//! 屏幕标尺模块
+//!
+//! 热键进入后拖拽测量两点间距离与矩形宽高，单击则取点坐标。
+//! 复用取色器的鼠标钩子、取消路径与信息窗口基础设施。
+
+#[cfg(target_os = "windows")]
+use super::color_picker::{self, PickerResources};
+#[cfg(target_os = "windows")]
+use std::sync::atomic::{AtomicBool, Ordering};
+#[cfg(target_os = "windows")]
+use std::thread;
+#[cfg(target_os = "windows")]
+use std::time::Duration;
+
+/// 屏幕标尺快捷键在多快捷键监听器中的 ID
+pub const SCREEN_RULER_HOTKEY_ID: &str = "screen_ruler";
+
+/// 一次测量的结果，坐标与尺寸均为物理像素，logical_* 为按 DPI 换算后的值
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct RulerMeasurement {
+    pub start_x: i32,
+    pub start_y: i32,
+    pub end_x: i32,
+    pub end_y: i32,
+    pub width: u32,
+    pub height: u32,
+    pub distance: f64,
+    pub dpi: u32,
+    /// 缩放比例（dpi / 96）
+    pub scale: f64,
+    pub logical_width: f64,
+    pub logical_height: f64,
+    pub logical_distance: f64,
+    /// 用于复制的文本
+    pub text: String,
+}
+
+impl RulerMeasurement {
+    pub fn new(start: (i32, i32), end: (i32, i32), dpi: u32) -> Self {
+        let dpi = if dpi == 0 { 96 } else { dpi };
+        let scale = dpi as f64 / 96.0;
+        let width = (end.0 - start.0).unsigned_abs();
+        let height = (end.1 - start.1).unsigned_abs();
+        let distance = (width as f64).hypot(height as f64);
+        let round = |v: f64| (v * 10.0).round() / 10.0;
+
+        let text = if width == 0 && height == 0 {
+            format!("({}, {})", start.0, start.1)
+        } else {
+            format!("{} × {} px, {:.1} px", width, height, distance)
+        };
+
+        Self {
+            start_x: start.0,
+            start_y: start.1,
+            end_x: end.0,
+            end_y: end.1,
+            width,
+            height,
+            distance: round(distance),
+            dpi,
+            scale,
+            logical_width: round(width as f64 / scale),
+            logical_height: round(height as f64 / scale),
+            logical_distance: round(distance / scale),
+            text,
+        }
+    }
+
+    /// 信息窗口中显示的各行文本
+    #[cfg(target_os = "windows")]
+    fn info_lines(&self) -> Vec<String> {
+        vec![
+            format!("起点: ({}, {})", self.start_x, self.start_y),
+            format!("终点: ({}, {})", self.end_x, self.end_y),
+            format!("宽×高: {} × {} px", self.width, self.height),
+            format!("距离: {:.1} px", self.distance),
+            format!(
+                "逻辑: {} × {}, {} ({}%)",
+                self.logical_width,
+                self.logical_height,
+                self.logical_distance,
+                (self.scale * 100.0).round()
+            ),
+        ]
+    }
+}
+
+/// 测量矩形的四条边框窗口（点击穿透），Drop 时销毁
+#[cfg(target_os = "windows")]
+struct RulerEdges {
+    windows: [isize; 4],
+}
+
+#[cfg(target_os = "windows")]
+impl RulerEdges {
+    const THICKNESS: i32 = 2;
+
+    unsafe fn create() -> Self {
+        use windows_sys::Win32::UI::WindowsAndMessaging::{
+            CreateWindowExW, WS_EX_LAYERED, WS_EX_NOACTIVATE, WS_EX_TOOLWINDOW, WS_EX_TOPMOST,
+            WS_EX_TRANSPARENT, WS_POPUP, SetLayeredWindowAttributes, LWA_ALPHA,
+        };
+
+        let class: Vec<u16> = "Static\0".encode_utf16().collect();
+        let mut windows = [0isize; 4];
+        for hwnd in windows.iter_mut() {
+            *hwnd = CreateWindowExW(
+                WS_EX_LAYERED | WS_EX_TOPMOST | WS_EX_TOOLWINDOW | WS_EX_TRANSPARENT | WS_EX_NOACTIVATE,
+                class.as_ptr(),
+                std::ptr::null(),
+                WS_POPUP,
+                0, 0, 0, 0,
+                0,
+                0,
+                0,
+                std::ptr::null(),
+            );
+            if *hwnd != 0 {
+                SetLayeredWindowAttributes(*hwnd, 0, 220, LWA_ALPHA);
+            }
+        }
+        Self { windows }
+    }
+
+    /// 把四条边移动到两点围成的矩形上并着色
+    unsafe fn update(&self, start: (i32, i32), end: (i32, i32)) {
+        use windows_sys::Win32::Foundation::RECT;
+        use windows_sys::Win32::Graphics::Gdi::{CreateSolidBrush, DeleteObject, FillRect, GetDC, ReleaseDC};
+        use windows_sys::Win32::UI::WindowsAndMessaging::{
+            SetWindowPos, HWND_TOPMOST, SWP_NOACTIVATE, SWP_SHOWWINDOW,
+        };
+
+        let t = Self::THICKNESS;
+        let (left, right) = (start.0.min(end.0), start.0.max(end.0));
+        let (top, bottom) = (start.1.min(end.1), start.1.max(end.1));
+        let width = right - left + t;
+        let height = bottom - top + t;
+        let bounds = [
+            (left, top, width, t),
+            (left, bottom, width, t),
+            (left, top, t, height),
+            (right, top, t, height),
+        ];
+
+        let brush = CreateSolidBrush(0x00FF_FF00); // 青色（BGR）
+        for (hwnd, (x, y, w, h)) in self.windows.iter().zip(bounds) {
+            if *hwnd == 0 {
+                continue;
+            }
+            SetWindowPos(*hwnd, HWND_TOPMOST, x, y, w, h, SWP_NOACTIVATE | SWP_SHOWWINDOW);
+            let dc = GetDC(*hwnd);
+            if dc != 0 {
+                FillRect(dc, &RECT { left: 0, top: 0, right: w, bottom: h }, brush);
+                ReleaseDC(*hwnd, dc);
+            }
+        }
+        DeleteObject(brush as isize);
+    }
+}
+
+#[cfg(target_os = "windows")]
+impl Drop for RulerEdges {
+    fn drop(&mut self) {
+        use windows_sys::Win32::UI::WindowsAndMessaging::DestroyWindow;
+        for hwnd in self.windows {
+            if hwnd != 0 {
+                unsafe {
+                    DestroyWindow(hwnd);
+                }
+            }
+        }
+    }
+}
+
+/// 进入屏幕标尺：拖拽测量，单击取点，取消时返回 None
+#[tauri::command]
+pub async fn measure_screen(app: tauri::AppHandle) -> Result<Option<RulerMeasurement>, String> {
+    #[cfg(target_os = "windows")]
+    {
+        color_picker::apply_right_click_setting(&app);
+
+        let measuring = AtomicBool::new(true);
+        tokio::task::spawn_blocking(move || windows_measure(&measuring))
+            .await
+            .map_err(|e| format!("测量任务失败: {}", e))?
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        let _ = app;
+        Err("屏幕标尺功能目前仅支持 Windows".to_string())
+    }
+}
+
+/// 热键直达标尺：测量完成后复制结果并弹出 Toast
+#[tauri::command]
+pub async fn measure_screen_and_copy(app: tauri::AppHandle) -> Result<Option<RulerMeasurement>, String> {
+    let Some(measurement) = measure_screen(app.clone()).await? else {
+        return Ok(None);
+    };
+
+    #[cfg(target_os = "windows")]
+    crate::clipboard::monitor::set_clipboard_text(&measurement.text)?;
+
+    if let Err(e) = crate::toast::show_toast(&app, "已复制测量结果", &measurement.text) {
+        eprintln!("[ScreenRuler] Failed to show toast: {}", e);
+    }
+
+    Ok(Some(measurement))
+}
+
+#[cfg(target_os = "windows")]
+fn windows_measure(measuring: &AtomicBool) -> Result<Option<RulerMeasurement>, String> {
+    use windows_sys::Win32::Foundation::{HWND, POINT, RECT};
+    use windows_sys::Win32::Graphics::Gdi::{
+        BitBlt, CreateSolidBrush, DeleteObject, FillRect, GetDeviceCaps, SetBkMode, SetTextColor,
+        TextOutW, LOGPIXELSX, SRCCOPY, TRANSPARENT,
+    };
+    use windows_sys::Win32::UI::WindowsAndMessaging::{
+        GetCursorPos, GetForegroundWindow, LoadCursorW, SetCursor, SetWindowPos, ShowWindow,
+        HWND_TOPMOST, IDC_CROSS, SWP_NOACTIVATE, SW_SHOWNOACTIVATE,
+    };
+
+    let info_width = 260i32;
+    let info_height = 150i32;
+    let line_height = 22i32;
+
+    unsafe {
+        let cross_cursor = LoadCursorW(0, IDC_CROSS);
+        SetCursor(cross_cursor);
+
+        // 拦截左键，避免拖拽操作到下方窗口；资源离开作用域时统一释放
+        let mut res = PickerResources::acquire(true)?;
+        let dpi = GetDeviceCaps(res.screen_dc, LOGPIXELSX).max(0) as u32;
+        let edges = RulerEdges::create();
+        let info_created = res.create_info_window(info_width, info_height);
+
+        let started_at = std::time::Instant::now();
+        let mut baseline_foreground: Option<HWND> = None;
+        let mut start: Option<(i32, i32)> = None;
+
+        let result = loop {
+            SetCursor(cross_cursor);
+
+            if baseline_foreground.is_none() && started_at.elapsed() >= color_picker::FOCUS_BASELINE_DELAY {
+                baseline_foreground = Some(GetForegroundWindow());
+            }
+            if let Some(reason) = color_picker::cancel_reason(measuring, baseline_foreground) {
+                eprintln!("[ScreenRuler] Measuring cancelled: {:?}", reason);
+                break Ok(None);
+            }
+
+            let mut point = POINT { x: 0, y: 0 };
+            if GetCursorPos(&mut point) == 0 {
+                break Err("获取鼠标位置失败".to_string());
+            }
+            let cursor = (point.x, point.y);
+            res.pump_messages();
+            let pressed = color_picker::LEFT_BUTTON_DOWN.load(Ordering::SeqCst);
+
+            match (start, pressed) {
+                (None, true) => start = Some(cursor),
+                // 松开左键即完成测量；未拖动时为取点
+                (Some(origin), false) => break Ok(Some(RulerMeasurement::new(origin, cursor, dpi))),
+                _ => {}
+            }
+
+            if let Some(origin) = start {
+                edges.update(origin, cursor);
+            }
+
+            if info_created {
+                let (hwnd, window_dc, mem_dc) = (res.hwnd, res.window_dc, res.mem_dc);
+                let (window_x, window_y) = PickerResources::info_window_position(
+                    point.x, point.y, info_width, info_height, 24,
+                );
+                SetWindowPos(
+                    hwnd,
+                    HWND_TOPMOST,
+                    window_x,
+                    window_y,
+                    info_width,
+                    info_height,
+                    SWP_NOACTIVATE,
+                );
+
+                let bg_brush = CreateSolidBrush(0x0030_2D2D);
+                FillRect(mem_dc, &RECT { left: 0, top: 0, right: info_width, bottom: info_height }, bg_brush);
+                DeleteObject(bg_brush as isize);
+
+                SetBkMode(mem_dc, TRANSPARENT as i32);
+                SetTextColor(mem_dc, 0x00FF_FFFF);
+                let mut y_pos = 10i32;
+                let lines = match start {
+                    Some(origin) => RulerMeasurement::new(origin, cursor, dpi).info_lines(),
+                    None => vec![
+                        format!("坐标: ({}, {})", point.x, point.y),
+                        "拖拽: 测量  单击: 取点".to_string(),
+                        "ESC: 取消".to_string(),
+                    ],
+                };
+                for line in lines {
+                    let wide: Vec<u16> = line.encode_utf16().collect();
+                    TextOutW(mem_dc, 12, y_pos, wide.as_ptr(), wide.len() as i32);
+                    y_pos += line_height;
+                }
+
+                BitBlt(window_dc, 0, 0, info_width, info_height, mem_dc, 0, 0, SRCCOPY);
+                ShowWindow(hwnd, SW_SHOWNOACTIVATE);
+            }
+
+            thread::sleep(Duration::from_millis(16));
+        };
+
+        drop(edges);
+        drop(res);
+        result
+    }
+}
+
+/// 获取屏幕标尺快捷键
+#[tauri::command]
+pub fn get_screen_ruler_hotkey(app: tauri::AppHandle) -> Result<Option<crate::settings::HotkeyConfig>, String> {
+    let app_data_dir = super::get_app_data_dir(&app)?;
+    Ok(crate::settings::load_settings(&app_data_dir)?.screen_ruler_hotkey)
+}
+
+/// 保存屏幕标尺快捷键（None 表示取消）
+#[tauri::command]
+pub fn save_screen_ruler_hotkey(
+    app: tauri::AppHandle,
+    config: Option<crate::settings::HotkeyConfig>,
+) -> Result<(), String> {
+    let app_data_dir = super::get_app_data_dir(&app)?;
+    let mut settings = crate::settings::load_settings(&app_data_dir)?;
+    settings.screen_ruler_hotkey = config.clone();
+    crate::settings::save_settings(&app_data_dir, &settings)?;
+
+    #[cfg(target_os = "windows")]
+    {
+        let hotkey_id = SCREEN_RULER_HOTKEY_ID.to_string();
+        let result = match config {
+            Some(hotkey) => crate::hotkey_handler::windows::register_plugin_hotkey(hotkey_id, hotkey),
+            None => crate::hotkey_handler::windows::unregister_plugin_hotkey(&hotkey_id),
+        };
+        if let Err(e) = result {
+            eprintln!("Failed to update screen ruler hotkey: {}", e);
+        }
+    }
+
+    Ok(())
+}
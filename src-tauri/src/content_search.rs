@@ -0,0 +1,321 @@
+//! 文件内容搜索（grep）
+//!
+//! 在指定目录下按关键字或正则搜索文本文件内容：多线程读取、遵循 .gitignore、
+//! 跳过二进制与超大文件，命中行连同上下文通过回调流式返回。
+
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+/// 超过该大小的文件不搜索
+const MAX_FILE_SIZE: u64 = 10 * 1024 * 1024;
+/// 判断二进制时检查的头部字节数
+const BINARY_CHECK_BYTES: usize = 8 * 1024;
+
+fn default_context_lines() -> usize {
+    2
+}
+
+fn default_max_results() -> usize {
+    1000
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContentSearchOptions {
+    pub root: String,
+    pub query: String,
+    #[serde(default)]
+    pub is_regex: bool,
+    #[serde(default)]
+    pub case_sensitive: bool,
+    #[serde(default = "default_context_lines")]
+    pub context_lines: usize,
+    #[serde(default = "default_max_results")]
+    pub max_results: usize,
+    /// 是否搜索隐藏文件（以 . 开头）
+    #[serde(default)]
+    pub include_hidden: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ContentMatch {
+    pub path: String,
+    /// 行号（从 1 开始）
+    pub line_number: usize,
+    pub line: String,
+    pub before: Vec<String>,
+    pub after: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ContentSearchSummary {
+    pub files_scanned: usize,
+    pub matches: usize,
+    /// 命中数达到 max_results 后提前结束
+    pub truncated: bool,
+    pub cancelled: bool,
+}
+
+/// 单条 .gitignore 规则
+struct IgnoreRule {
+    regex: Regex,
+    negate: bool,
+    dir_only: bool,
+}
+
+/// 某个目录下的 .gitignore，规则相对该目录匹配
+struct GitIgnore {
+    base: PathBuf,
+    rules: Vec<IgnoreRule>,
+}
+
+/// 将 gitignore 通配符转换为正则
+fn glob_to_regex(pattern: &str) -> String {
+    let mut out = String::new();
+    let chars: Vec<char> = pattern.chars().collect();
+    let mut i = 0;
+    while i < chars.len() {
+        match chars[i] {
+            '*' if chars.get(i + 1) == Some(&'*') => {
+                if chars.get(i + 2) == Some(&'/') {
+                    out.push_str("(?:.*/)?");
+                    i += 3;
+                } else {
+                    out.push_str(".*");
+                    i += 2;
+                }
+                continue;
+            }
+            '*' => out.push_str("[^/]*"),
+            '?' => out.push_str("[^/]"),
+            '[' => {
+                // 字符集原样保留，[!...] 转为 [^...]
+                if let Some(end) = chars[i..].iter().position(|&c| c == ']') {
+                    let class: String = chars[i + 1..i + end].iter().collect();
+                    let class = class.strip_prefix('!').map(|c| format!("^{}", c)).unwrap_or(class);
+                    out.push('[');
+                    out.push_str(&class.replace('\\', "\\\\"));
+                    out.push(']');
+                    i += end + 1;
+                    continue;
+                }
+                out.push_str("\\[");
+            }
+            c => out.push_str(&regex::escape(&c.to_string())),
+        }
+        i += 1;
+    }
+    out
+}
+
+impl GitIgnore {
+    fn load(dir: &Path) -> Option<Self> {
+        let contents = fs::read_to_string(dir.join(".gitignore")).ok()?;
+        let rules = contents
+            .lines()
+            .filter_map(|line| {
+                let line = line.trim_end();
+                if line.is_empty() || line.starts_with('#') {
+                    return None;
+                }
+                let (negate, line) = match line.strip_prefix('!') {
+                    Some(rest) => (true, rest),
+                    None => (false, line),
+                };
+                let (dir_only, line) = match line.strip_suffix('/') {
+                    Some(rest) => (true, rest),
+                    None => (false, line),
+                };
+                // 含有 / 的规则相对 .gitignore 所在目录，否则匹配任意层级的名称
+                let anchored = line.contains('/');
+                let body = glob_to_regex(line.trim_start_matches('/'));
+                let pattern = if anchored {
+                    format!("^{}$", body)
+                } else {
+                    format!("^(?:.*/)?{}$", body)
+                };
+                Regex::new(&pattern).ok().map(|regex| IgnoreRule {
+                    regex,
+                    negate,
+                    dir_only,
+                })
+            })
+            .collect::<Vec<_>>();
+        if rules.is_empty() {
+            None
+        } else {
+            Some(Self {
+                base: dir.to_path_buf(),
+                rules,
+            })
+        }
+    }
+
+    /// 返回 Some(true) 表示忽略，Some(false) 表示被 ! 规则重新包含，None 表示无规则命中
+    fn matched(&self, path: &Path, is_dir: bool) -> Option<bool> {
+        let relative = path.strip_prefix(&self.base).ok()?;
+        let relative = relative.to_string_lossy().replace('\\', "/");
+        self.rules
+            .iter()
+            .rev()
+            .find(|rule| (!rule.dir_only || is_dir) && rule.regex.is_match(&relative))
+            .map(|rule| !rule.negate)
+    }
+}
+
+fn is_ignored(ignores: &[GitIgnore], path: &Path, is_dir: bool) -> bool {
+    // 越深层的 .gitignore 优先级越高
+    ignores
+        .iter()
+        .rev()
+        .find_map(|ignore| ignore.matched(path, is_dir))
+        .unwrap_or(false)
+}
+
+/// 递归收集待搜索文件，遵循各级 .gitignore
+fn collect_files(
+    dir: &Path,
+    ignores: &mut Vec<GitIgnore>,
+    include_hidden: bool,
+    cancel: &AtomicBool,
+    files: &mut Vec<PathBuf>,
+) {
+    if cancel.load(Ordering::Relaxed) {
+        return;
+    }
+    let pushed = match GitIgnore::load(dir) {
+        Some(ignore) => {
+            ignores.push(ignore);
+            true
+        }
+        None => false,
+    };
+
+    if let Ok(entries) = fs::read_dir(dir) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let name = entry.file_name().to_string_lossy().to_string();
+            if name == ".git" || (!include_hidden && name.starts_with('.')) {
+                continue;
+            }
+            let Ok(file_type) = entry.file_type() else { continue };
+            // 不跟随符号链接，避免循环
+            if file_type.is_symlink() {
+                continue;
+            }
+            let is_dir = file_type.is_dir();
+            if is_ignored(ignores, &path, is_dir) {
+                continue;
+            }
+            if is_dir {
+                collect_files(&path, ignores, include_hidden, cancel, files);
+            } else if file_type.is_file() {
+                files.push(path);
+            }
+        }
+    }
+
+    if pushed {
+        ignores.pop();
+    }
+}
+
+/// 读取文本文件，二进制或超大文件返回 None
+fn read_text_file(path: &Path) -> Option<String> {
+    let metadata = fs::metadata(path).ok()?;
+    if metadata.len() > MAX_FILE_SIZE {
+        return None;
+    }
+    let mut bytes = Vec::with_capacity(metadata.len() as usize);
+    fs::File::open(path).ok()?.read_to_end(&mut bytes).ok()?;
+    if bytes[..bytes.len().min(BINARY_CHECK_BYTES)].contains(&0) {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+/// 执行内容搜索，每个命中通过 on_match 回调返回
+pub fn search<F>(
+    options: &ContentSearchOptions,
+    cancel: &AtomicBool,
+    on_match: F,
+) -> Result<ContentSearchSummary, String>
+where
+    F: Fn(ContentMatch) + Sync,
+{
+    if options.query.is_empty() {
+        return Err("Search query is empty".to_string());
+    }
+    let root = PathBuf::from(&options.root);
+    if !root.is_dir() {
+        return Err(format!("Directory not found: {}", options.root));
+    }
+
+    let pattern = if options.is_regex {
+        options.query.clone()
+    } else {
+        regex::escape(&options.query)
+    };
+    let matcher = RegexBuilder::new(&pattern)
+        .case_insensitive(!options.case_sensitive)
+        .build()
+        .map_err(|e| format!("Invalid regex: {}", e))?;
+
+    let mut files = Vec::new();
+    collect_files(&root, &mut Vec::new(), options.include_hidden, cancel, &mut files);
+
+    let files = Mutex::new(files.into_iter());
+    let files_scanned = AtomicUsize::new(0);
+    let matches = AtomicUsize::new(0);
+    let truncated = AtomicBool::new(false);
+    let threads = std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(4)
+        .min(8);
+
+    std::thread::scope(|scope| {
+        for _ in 0..threads {
+            scope.spawn(|| loop {
+                if cancel.load(Ordering::Relaxed) || truncated.load(Ordering::Relaxed) {
+                    return;
+                }
+                let Some(path) = files.lock().ok().and_then(|mut f| f.next()) else {
+                    return;
+                };
+                let Some(text) = read_text_file(&path) else { continue };
+                files_scanned.fetch_add(1, Ordering::Relaxed);
+
+                let lines: Vec<&str> = text.lines().collect();
+                for (index, line) in lines.iter().enumerate() {
+                    if !matcher.is_match(line) {
+                        continue;
+                    }
+                    if matches.fetch_add(1, Ordering::SeqCst) >= options.max_results {
+                        truncated.store(true, Ordering::Relaxed);
+                        return;
+                    }
+                    let start = index.saturating_sub(options.context_lines);
+                    let end = (index + 1 + options.context_lines).min(lines.len());
+                    on_match(ContentMatch {
+                        path: path.to_string_lossy().to_string(),
+                        line_number: index + 1,
+                        line: line.to_string(),
+                        before: lines[start..index].iter().map(|l| l.to_string()).collect(),
+                        after: lines[index + 1..end].iter().map(|l| l.to_string()).collect(),
+                    });
+                }
+            });
+        }
+    });
+
+    Ok(ContentSearchSummary {
+        files_scanned: files_scanned.load(Ordering::Relaxed),
+        matches: matches.load(Ordering::Relaxed).min(options.max_results),
+        truncated: truncated.load(Ordering::Relaxed),
+        cancelled: cancel.load(Ordering::Relaxed),
+    })
+}
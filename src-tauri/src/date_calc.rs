@@ -0,0 +1,161 @@
+//! 启动器日期计算（解析型 provider，离线）
+//!
+//! 支持：
+//! - 日期加减："今天+45天"、"2024-01-01 - 3周"、"明天+10工作日"
+//! - 日期间隔："2024-01-01 到 2024-06-01"、"2024-01-01 ~ today"，同时给出工作日天数
+//!
+//! 工作日按周一至周五计算，不含法定节假日。输入无法解析时返回 None。
+
+use chrono::{Datelike, Duration, Local, Months, NaiveDate, Weekday};
+use regex::Regex;
+use serde::Serialize;
+use std::sync::LazyLock;
+
+/// 加减运算允许的最大数量，避免溢出或超长循环
+const MAX_AMOUNT: i64 = 100_000;
+
+static OFFSET_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)^(.+?)\s*([+-])\s*(\d+)\s*(个工作日|工作日|workdays?|天|日|days?|d|个星期|星期|周|weeks?|w|个月|月|months?|m|年|years?|y)$")
+        .unwrap()
+});
+
+static RANGE_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)^(.+?)\s*(?:到|至|~|\bto\b)\s*(.+)$").unwrap());
+
+static DATE_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^(\d{4})[-/.年](\d{1,2})[-/.月](\d{1,2})日?$").unwrap());
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DateCalcKind {
+    Date,
+    Interval,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DateCalcResult {
+    pub kind: DateCalcKind,
+    /// 可复制的结果：日期为 YYYY-MM-DD，间隔为天数
+    pub value: String,
+    /// 展示文本，如 "2024-02-15 星期四" 或 "152 天（108 个工作日）"
+    pub display: String,
+    /// 间隔中的工作日天数（仅间隔结果）
+    pub workdays: Option<i64>,
+}
+
+fn weekday_name(weekday: Weekday) -> &'static str {
+    match weekday {
+        Weekday::Mon => "星期一",
+        Weekday::Tue => "星期二",
+        Weekday::Wed => "星期三",
+        Weekday::Thu => "星期四",
+        Weekday::Fri => "星期五",
+        Weekday::Sat => "星期六",
+        Weekday::Sun => "星期日",
+    }
+}
+
+fn is_workday(date: NaiveDate) -> bool {
+    !matches!(date.weekday(), Weekday::Sat | Weekday::Sun)
+}
+
+fn parse_date(text: &str) -> Option<NaiveDate> {
+    let today = Local::now().date_naive();
+    let text = text.trim();
+    match text.to_lowercase().as_str() {
+        "今天" | "今日" | "today" | "now" => return Some(today),
+        "明天" | "tomorrow" => return Some(today + Duration::days(1)),
+        "后天" => return Some(today + Duration::days(2)),
+        "昨天" | "yesterday" => return Some(today - Duration::days(1)),
+        "前天" => return Some(today - Duration::days(2)),
+        _ => {}
+    }
+    let caps = DATE_RE.captures(text)?;
+    NaiveDate::from_ymd_opt(caps[1].parse().ok()?, caps[2].parse().ok()?, caps[3].parse().ok()?)
+}
+
+/// 从 date 起跳过周末移动 amount 个工作日（amount 可为负）
+fn add_workdays(mut date: NaiveDate, amount: i64) -> NaiveDate {
+    let step = if amount >= 0 { 1 } else { -1 };
+    let mut remaining = amount.abs();
+    while remaining > 0 {
+        date += Duration::days(step);
+        if is_workday(date) {
+            remaining -= 1;
+        }
+    }
+    date
+}
+
+/// start（不含）到 end（含）之间的工作日天数，end 早于 start 时为负
+fn count_workdays(start: NaiveDate, end: NaiveDate) -> i64 {
+    let (from, to, sign) = if end >= start { (start, end, 1) } else { (end, start, -1) };
+    let days = (to - from).num_days();
+    let full_weeks = days / 7;
+    let mut count = full_weeks * 5;
+    let mut date = from + Duration::days(full_weeks * 7);
+    while date < to {
+        date += Duration::days(1);
+        if is_workday(date) {
+            count += 1;
+        }
+    }
+    count * sign
+}
+
+fn apply_offset(base: NaiveDate, negative: bool, amount: i64, unit: &str) -> Option<NaiveDate> {
+    let signed = if negative { -amount } else { amount };
+    let months = |n: i64| {
+        let n = Months::new(n.unsigned_abs() as u32);
+        if negative {
+            base.checked_sub_months(n)
+        } else {
+            base.checked_add_months(n)
+        }
+    };
+    match unit.to_lowercase().as_str() {
+        "个工作日" | "工作日" | "workday" | "workdays" => Some(add_workdays(base, signed)),
+        "天" | "日" | "day" | "days" | "d" => base.checked_add_signed(Duration::days(signed)),
+        "个星期" | "星期" | "周" | "week" | "weeks" | "w" => {
+            base.checked_add_signed(Duration::weeks(signed))
+        }
+        "个月" | "月" | "month" | "months" | "m" => months(amount),
+        "年" | "year" | "years" | "y" => months(amount.checked_mul(12)?),
+        _ => None,
+    }
+}
+
+/// 解析并计算日期表达式，无法识别时返回 None
+pub fn evaluate(input: &str) -> Option<DateCalcResult> {
+    let input = input.trim();
+    if input.is_empty() {
+        return None;
+    }
+
+    if let Some(caps) = OFFSET_RE.captures(input) {
+        let base = parse_date(&caps[1])?;
+        let amount: i64 = caps[3].parse().ok().filter(|n| *n <= MAX_AMOUNT)?;
+        let date = apply_offset(base, &caps[2] == "-", amount, &caps[4])?;
+        return Some(DateCalcResult {
+            kind: DateCalcKind::Date,
+            value: date.format("%Y-%m-%d").to_string(),
+            display: format!("{} {}", date.format("%Y-%m-%d"), weekday_name(date.weekday())),
+            workdays: None,
+        });
+    }
+
+    if let Some(caps) = RANGE_RE.captures(input) {
+        let start = parse_date(&caps[1])?;
+        let end = parse_date(&caps[2])?;
+        let days = (end - start).num_days();
+        let workdays = count_workdays(start, end);
+        return Some(DateCalcResult {
+            kind: DateCalcKind::Interval,
+            value: days.to_string(),
+            display: format!("{} 天（{} 个工作日）", days, workdays),
+            workdays: Some(workdays),
+        });
+    }
+
+    None
+}
@@ -1,10 +1,26 @@
 use rusqlite::{Connection, OpenFlags};
+use std::collections::HashMap;
 use std::fs;
+use std::panic::{self, AssertUnwindSafe};
 use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{LazyLock, Mutex};
+use std::thread;
 
 const DB_NAME: &str = "re-fast.db";
 const LEGACY_DB_NAME: &str = "data.db";
 
+/// Maximum number of queued write jobs committed in one transaction.
+const MAX_WRITE_BATCH: usize = 64;
+
+/// A write job runs on the writer thread and returns a callback that receives the
+/// batch commit result once the surrounding transaction has finished.
+type WriteJob = Box<dyn FnOnce(&Connection) -> Box<dyn FnOnce(Result<(), String>) + Send> + Send>;
+
+/// One writer thread per database, keyed by app data directory.
+static WRITERS: LazyLock<Mutex<HashMap<PathBuf, Sender<WriteJob>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
 /// Database file path under the app data directory (new name).
 pub fn get_db_path(app_data_dir: &Path) -> PathBuf {
     app_data_dir.join(DB_NAME)
@@ -85,6 +101,137 @@ pub fn get_readonly_connection(app_data_dir: &Path) -> Result<Connection, String
     Ok(conn)
 }
 
+/// Get (or start) the dedicated writer thread for this database.
+fn writer_sender(app_data_dir: &Path) -> Result<Sender<WriteJob>, String> {
+    let mut writers = WRITERS
+        .lock()
+        .map_err(|e| format!("Failed to lock database writers: {}", e))?;
+    if let Some(sender) = writers.get(app_data_dir) {
+        return Ok(sender.clone());
+    }
+
+    let conn = get_connection(app_data_dir)?;
+    let (sender, receiver) = mpsc::channel::<WriteJob>();
+    thread::Builder::new()
+        .name("sqlite-writer".to_string())
+        .spawn(move || run_writer(conn, receiver))
+        .map_err(|e| format!("Failed to start database writer thread: {}", e))?;
+
+    writers.insert(app_data_dir.to_path_buf(), sender.clone());
+    Ok(sender)
+}
+
+/// Writer loop: drain queued jobs and commit them together, each job isolated by a savepoint.
+fn run_writer(conn: Connection, receiver: Receiver<WriteJob>) {
+    while let Ok(first) = receiver.recv() {
+        let mut batch = vec![first];
+        while batch.len() < MAX_WRITE_BATCH {
+            match receiver.try_recv() {
+                Ok(job) => batch.push(job),
+                Err(_) => break,
+            }
+        }
+
+        let in_transaction = conn.execute_batch("BEGIN IMMEDIATE").is_ok();
+        let callbacks: Vec<_> = batch.into_iter().map(|job| job(&conn)).collect();
+
+        let commit = if in_transaction {
+            conn.execute_batch("COMMIT").map_err(|e| {
+                let _ = conn.execute_batch("ROLLBACK");
+                format!("Failed to commit database writes: {}", e)
+            })
+        } else {
+            Ok(())
+        };
+
+        for callback in callbacks {
+            callback(commit.clone());
+        }
+    }
+}
+
+fn run_in_savepoint<T>(
+    conn: &Connection,
+    f: impl FnOnce(&Connection) -> Result<T, String>,
+) -> Result<T, String> {
+    conn.execute_batch("SAVEPOINT write_job")
+        .map_err(|e| format!("Failed to create savepoint: {}", e))?;
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| f(conn)))
+        .unwrap_or_else(|_| Err("Database write job panicked".to_string()));
+
+    let finish = if result.is_ok() {
+        "RELEASE write_job"
+    } else {
+        "ROLLBACK TO write_job; RELEASE write_job"
+    };
+    if let Err(e) = conn.execute_batch(finish) {
+        eprintln!("[DB] Failed to finish savepoint: {}", e);
+    }
+    result
+}
+
+/// Run a write operation on the dedicated writer thread and wait for it to be committed.
+///
+/// All writes go through a single connection so the clipboard monitor and UI commands no
+/// longer contend for the write lock; reads should use `get_readonly_connection`.
+/// Must not be called from inside another write job (it would wait on itself).
+pub fn execute_write<T, F>(app_data_dir: &Path, f: F) -> Result<T, String>
+where
+    T: Send + 'static,
+    F: FnOnce(&Connection) -> Result<T, String> + Send + 'static,
+{
+    let (reply_sender, reply_receiver) = mpsc::channel();
+    let job: WriteJob = Box::new(move |conn| {
+        let result = run_in_savepoint(conn, f);
+        Box::new(move |commit: Result<(), String>| {
+            let _ = reply_sender.send(commit.and(result));
+        })
+    });
+
+    writer_sender(app_data_dir)?
+        .send(job)
+        .map_err(|_| "Database writer thread has stopped".to_string())?;
+    reply_receiver
+        .recv()
+        .map_err(|_| "Database writer thread has stopped".to_string())?
+}
+
+/// 按当前 rowid 重建剪贴板 FTS 索引（VACUUM 后需要调用）；索引不存在时忽略
+pub fn rebuild_clipboard_fts(conn: &Connection) {
+    if let Err(e) = conn.execute_batch(
+        "DELETE FROM clipboard_fts;
+         INSERT INTO clipboard_fts (rowid, content) SELECT rowid, content FROM clipboard_history;",
+    ) {
+        eprintln!("[DB] Failed to rebuild clipboard FTS index: {}", e);
+    }
+}
+
+/// Wait until every write queued so far has been committed, then checkpoint the WAL.
+///
+/// Used by the shutdown flow so pending writes are not lost when the process exits.
+pub fn flush_writes(app_data_dir: &Path) -> Result<(), String> {
+    // The writer handles jobs in order, so an empty job completes after all earlier ones
+    execute_write(app_data_dir, |_| Ok(()))?;
+    let conn = get_connection(app_data_dir)?;
+    conn.execute_batch("PRAGMA wal_checkpoint(TRUNCATE);")
+        .map_err(|e| format!("Failed to checkpoint database: {}", e))
+}
+
+/// Run a blocking database (or file) operation on the async runtime's blocking pool.
+///
+/// Commands should await this instead of calling `execute_write` / opening connections
+/// directly, so slow queries never block the Tauri IPC thread.
+pub async fn run_blocking<T, F>(f: F) -> Result<T, String>
+where
+    T: Send + 'static,
+    F: FnOnce() -> Result<T, String> + Send + 'static,
+{
+    tauri::async_runtime::spawn_blocking(f)
+        .await
+        .map_err(|e| format!("Database task join error: {}", e))?
+}
+
 fn run_migrations(conn: &Connection) -> Result<(), String> {
     conn.execute_batch(
         r#"
@@ -153,6 +300,84 @@ fn run_migrations(conn: &Connection) -> Result<(), String> {
         );
         CREATE INDEX IF NOT EXISTS idx_plugin_usage_last_opened ON plugin_usage(last_opened);
 
+        CREATE TABLE IF NOT EXISTS query_history (
+            query TEXT PRIMARY KEY,
+            use_count INTEGER NOT NULL,
+            last_used INTEGER NOT NULL
+        );
+
+        CREATE TABLE IF NOT EXISTS result_weights (
+            key TEXT PRIMARY KEY,
+            result_type TEXT NOT NULL,
+            open_count INTEGER NOT NULL,
+            last_opened INTEGER NOT NULL
+        );
+
+        CREATE TABLE IF NOT EXISTS form_templates (
+            id TEXT PRIMARY KEY,
+            name TEXT NOT NULL,
+            fields TEXT NOT NULL,
+            submit INTEGER NOT NULL DEFAULT 0,
+            created_at INTEGER NOT NULL,
+            updated_at INTEGER NOT NULL
+        );
+
+        CREATE TABLE IF NOT EXISTS notes (
+            id TEXT PRIMARY KEY,
+            title TEXT NOT NULL,
+            content TEXT NOT NULL,
+            created_at INTEGER NOT NULL,
+            updated_at INTEGER NOT NULL
+        );
+        CREATE INDEX IF NOT EXISTS idx_notes_updated_at ON notes(updated_at);
+
+        -- 笔记全文索引（trigram 分词，支持中文子串匹配），通过触发器与 notes 保持同步
+        CREATE VIRTUAL TABLE IF NOT EXISTS notes_fts USING fts5(
+            title, content, content='notes', content_rowid='rowid', tokenize='trigram'
+        );
+        CREATE TRIGGER IF NOT EXISTS notes_fts_insert AFTER INSERT ON notes BEGIN
+            INSERT INTO notes_fts(rowid, title, content) VALUES (new.rowid, new.title, new.content);
+        END;
+        CREATE TRIGGER IF NOT EXISTS notes_fts_delete AFTER DELETE ON notes BEGIN
+            INSERT INTO notes_fts(notes_fts, rowid, title, content) VALUES ('delete', old.rowid, old.title, old.content);
+        END;
+        CREATE TRIGGER IF NOT EXISTS notes_fts_update AFTER UPDATE ON notes BEGIN
+            INSERT INTO notes_fts(notes_fts, rowid, title, content) VALUES ('delete', old.rowid, old.title, old.content);
+            INSERT INTO notes_fts(rowid, title, content) VALUES (new.rowid, new.title, new.content);
+        END;
+
+        CREATE TABLE IF NOT EXISTS playlists (
+            id TEXT PRIMARY KEY,
+            name TEXT NOT NULL,
+            items TEXT NOT NULL,
+            on_error TEXT NOT NULL,
+            max_retries INTEGER NOT NULL DEFAULT 0,
+            created_at INTEGER NOT NULL,
+            updated_at INTEGER NOT NULL
+        );
+
+        CREATE TABLE IF NOT EXISTS pinned_results (
+            key TEXT PRIMARY KEY,
+            result_type TEXT NOT NULL,
+            title TEXT NOT NULL,
+            path TEXT,
+            icon TEXT,
+            sort_order INTEGER NOT NULL,
+            pinned_at INTEGER NOT NULL
+        );
+
+        CREATE TABLE IF NOT EXISTS color_history (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            hex TEXT NOT NULL UNIQUE,
+            r INTEGER NOT NULL,
+            g INTEGER NOT NULL,
+            b INTEGER NOT NULL,
+            note TEXT,
+            is_favorite INTEGER NOT NULL DEFAULT 0,
+            created_at INTEGER NOT NULL
+        );
+        CREATE INDEX IF NOT EXISTS idx_color_history_created_at ON color_history(created_at);
+
         CREATE TABLE IF NOT EXISTS clipboard_history (
             id TEXT PRIMARY KEY,
             content TEXT NOT NULL,
@@ -163,6 +388,32 @@ fn run_migrations(conn: &Connection) -> Result<(), String> {
         CREATE INDEX IF NOT EXISTS idx_clipboard_history_created_at ON clipboard_history(created_at);
         CREATE INDEX IF NOT EXISTS idx_clipboard_history_is_favorite ON clipboard_history(is_favorite);
 
+        CREATE TABLE IF NOT EXISTS clipboard_tags (
+            item_id TEXT NOT NULL REFERENCES clipboard_history(id) ON DELETE CASCADE,
+            tag TEXT NOT NULL,
+            PRIMARY KEY (item_id, tag)
+        );
+        CREATE INDEX IF NOT EXISTS idx_clipboard_tags_tag ON clipboard_tags(tag);
+
+        CREATE TABLE IF NOT EXISTS clipboard_boards (
+            id TEXT PRIMARY KEY,
+            name TEXT NOT NULL UNIQUE,
+            sort_order INTEGER NOT NULL,
+            created_at INTEGER NOT NULL
+        );
+
+        CREATE TABLE IF NOT EXISTS clipboard_board_items (
+            item_id TEXT PRIMARY KEY REFERENCES clipboard_history(id) ON DELETE CASCADE,
+            board_id TEXT NOT NULL REFERENCES clipboard_boards(id) ON DELETE CASCADE,
+            position INTEGER NOT NULL
+        );
+        CREATE INDEX IF NOT EXISTS idx_clipboard_board_items_board ON clipboard_board_items(board_id, position);
+
+        CREATE TABLE IF NOT EXISTS clipboard_deleted (
+            content_hash TEXT PRIMARY KEY,
+            deleted_at INTEGER NOT NULL
+        );
+
         CREATE TABLE IF NOT EXISTS word_records (
             id TEXT PRIMARY KEY,
             word TEXT NOT NULL,
@@ -214,6 +465,129 @@ fn run_migrations(conn: &Connection) -> Result<(), String> {
         .map_err(|e| format!("Failed to add ai_explanation column: {}", e))?;
     }
 
+    // Migration: Add truncated column to clipboard_history if it doesn't exist
+    let clipboard_truncated_exists = conn
+        .prepare("SELECT truncated FROM clipboard_history LIMIT 1")
+        .is_ok();
+
+    if !clipboard_truncated_exists {
+        conn.execute(
+            "ALTER TABLE clipboard_history ADD COLUMN truncated INTEGER NOT NULL DEFAULT 0",
+            [],
+        )
+        .map_err(|e| format!("Failed to add truncated column: {}", e))?;
+    }
+
+    // Migration: Add html_content / rtf_content columns to clipboard_history if they don't exist
+    let clipboard_rich_exists = conn
+        .prepare("SELECT html_content, rtf_content FROM clipboard_history LIMIT 1")
+        .is_ok();
+
+    if !clipboard_rich_exists {
+        conn.execute_batch(
+            "ALTER TABLE clipboard_history ADD COLUMN html_content TEXT;
+             ALTER TABLE clipboard_history ADD COLUMN rtf_content TEXT;",
+        )
+        .map_err(|e| format!("Failed to add rich format columns: {}", e))?;
+    }
+
+    // Migration: Add content_file column to clipboard_history if it doesn't exist
+    let clipboard_content_file_exists = conn
+        .prepare("SELECT content_file FROM clipboard_history LIMIT 1")
+        .is_ok();
+
+    if !clipboard_content_file_exists {
+        conn.execute(
+            "ALTER TABLE clipboard_history ADD COLUMN content_file TEXT",
+            [],
+        )
+        .map_err(|e| format!("Failed to add content_file column: {}", e))?;
+    }
+
+    // Migration: Add source_app / source_title columns to clipboard_history if they don't exist
+    let clipboard_source_exists = conn
+        .prepare("SELECT source_app, source_title FROM clipboard_history LIMIT 1")
+        .is_ok();
+
+    if !clipboard_source_exists {
+        conn.execute_batch(
+            "ALTER TABLE clipboard_history ADD COLUMN source_app TEXT;
+             ALTER TABLE clipboard_history ADD COLUMN source_title TEXT;",
+        )
+        .map_err(|e| format!("Failed to add clipboard source columns: {}", e))?;
+    }
+
+    // Migration: Add ocr_text column to clipboard_history if it doesn't exist
+    // NULL 表示图片尚未识别，空字符串表示已识别但没有文字
+    let clipboard_ocr_exists = conn
+        .prepare("SELECT ocr_text FROM clipboard_history LIMIT 1")
+        .is_ok();
+
+    if !clipboard_ocr_exists {
+        conn.execute(
+            "ALTER TABLE clipboard_history ADD COLUMN ocr_text TEXT",
+            [],
+        )
+        .map_err(|e| format!("Failed to add ocr_text column: {}", e))?;
+    }
+
+    // Migration: Add abbreviation column to clipboard_history if it doesn't exist
+    // 收藏条目的片段缩写，输入缩写时自动展开为条目内容
+    let clipboard_abbreviation_exists = conn
+        .prepare("SELECT abbreviation FROM clipboard_history LIMIT 1")
+        .is_ok();
+
+    if !clipboard_abbreviation_exists {
+        conn.execute_batch(
+            "ALTER TABLE clipboard_history ADD COLUMN abbreviation TEXT;
+             CREATE UNIQUE INDEX IF NOT EXISTS idx_clipboard_abbreviation
+                 ON clipboard_history(abbreviation) WHERE abbreviation IS NOT NULL;",
+        )
+        .map_err(|e| format!("Failed to add abbreviation column: {}", e))?;
+    }
+
+    // Migration: Add content_hash column to clipboard_history if it doesn't exist
+    // 按内容哈希去重，避免比较完整内容；旧条目由后台任务回填
+    let clipboard_content_hash_exists = conn
+        .prepare("SELECT content_hash FROM clipboard_history LIMIT 1")
+        .is_ok();
+
+    if !clipboard_content_hash_exists {
+        conn.execute_batch(
+            "ALTER TABLE clipboard_history ADD COLUMN content_hash TEXT;
+             CREATE INDEX IF NOT EXISTS idx_clipboard_content_hash ON clipboard_history(content_hash);",
+        )
+        .map_err(|e| format!("Failed to add content_hash column: {}", e))?;
+    }
+
+    // Migration: Add expires_at column to clipboard_history if it doesn't exist
+    // 条目定时清除的到期时间（Unix 秒），由 clipboard_expiry 调度
+    let clipboard_expires_at_exists = conn
+        .prepare("SELECT expires_at FROM clipboard_history LIMIT 1")
+        .is_ok();
+
+    if !clipboard_expires_at_exists {
+        conn.execute_batch(
+            "ALTER TABLE clipboard_history ADD COLUMN expires_at INTEGER;
+             CREATE INDEX IF NOT EXISTS idx_clipboard_expires_at
+                 ON clipboard_history(expires_at) WHERE expires_at IS NOT NULL;",
+        )
+        .map_err(|e| format!("Failed to add expires_at column: {}", e))?;
+    }
+
+    // Migration: Add confirm_before_run column to shortcuts if it doesn't exist
+    let shortcut_confirm_exists = conn
+        .prepare("SELECT confirm_before_run FROM shortcuts LIMIT 1")
+        .is_ok();
+
+    if !shortcut_confirm_exists {
+        conn.execute(
+            "ALTER TABLE shortcuts ADD COLUMN confirm_before_run INTEGER NOT NULL DEFAULT 0",
+            [],
+        )
+        .map_err(|e| format!("Failed to add confirm_before_run column: {}", e))?;
+    }
+
     // Migration: Remove source_lang and target_lang columns if they exist
     // SQLite doesn't support DROP COLUMN, so we need to recreate the table
     let old_columns_exist = conn
@@ -266,6 +640,41 @@ fn run_migrations(conn: &Connection) -> Result<(), String> {
         .map_err(|e| format!("Failed to migrate word_records table: {}", e))?;
     }
 
+    // Migration: FTS5 index for clipboard_history, kept in sync by triggers
+    // trigram 分词按 3 字符子串建索引，中日韩文本无需分词也能做子串匹配
+    // 索引 rowid 与 clipboard_history 的 rowid 对应；VACUUM 可能重排 rowid，需先清空或重建索引
+    let clipboard_fts_exists: bool = conn
+        .query_row(
+            "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = 'clipboard_fts'",
+            [],
+            |row| row.get::<_, i64>(0),
+        )
+        .map(|count| count > 0)
+        .unwrap_or(false);
+
+    if !clipboard_fts_exists {
+        // FTS5 不可用时只记录日志，搜索会回退到 LIKE
+        if let Err(e) = conn.execute_batch(
+            r#"
+            CREATE VIRTUAL TABLE clipboard_fts USING fts5(content, tokenize = 'trigram');
+
+            CREATE TRIGGER IF NOT EXISTS clipboard_fts_insert AFTER INSERT ON clipboard_history BEGIN
+                INSERT INTO clipboard_fts (rowid, content) VALUES (new.rowid, new.content);
+            END;
+            CREATE TRIGGER IF NOT EXISTS clipboard_fts_delete AFTER DELETE ON clipboard_history BEGIN
+                DELETE FROM clipboard_fts WHERE rowid = old.rowid;
+            END;
+            CREATE TRIGGER IF NOT EXISTS clipboard_fts_update AFTER UPDATE OF content ON clipboard_history BEGIN
+                UPDATE clipboard_fts SET content = new.content WHERE rowid = old.rowid;
+            END;
+
+            INSERT INTO clipboard_fts (rowid, content) SELECT rowid, content FROM clipboard_history;
+            "#,
+        ) {
+            eprintln!("[DB] Failed to create clipboard FTS index: {}", e);
+        }
+    }
+
     Ok(())
 }
 
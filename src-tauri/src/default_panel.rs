@@ -0,0 +1,91 @@
+//! 启动器空查询默认面板
+//!
+//! 按设置中的分区顺序一次性聚合固定项、最近启动、剪贴板最新条目与今日提醒，
+//! 减少前端打开启动器时的多次 IPC。未启用的分区返回 None。
+
+use crate::open_history::OpenHistoryItem;
+use crate::pinned_results::PinnedResult;
+use crate::reminders::Reminder;
+use crate::settings::{self, DefaultPanelSection};
+use chrono::{Local, TimeZone};
+use serde::Serialize;
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DefaultPanel {
+    /// 分区展示顺序
+    pub sections: Vec<DefaultPanelSection>,
+    pub pinned: Option<Vec<PinnedResult>>,
+    /// 最近启动（按最后打开时间降序）
+    pub recent_launches: Option<Vec<OpenHistoryItem>>,
+    pub clipboard: Option<Vec<crate::clipboard::ClipboardItem>>,
+    /// 今天结束前到期的提醒（包括已过期未完成的）
+    pub reminders: Option<Vec<Reminder>>,
+}
+
+/// 今天结束时刻（本地时间次日零点）的 Unix 秒
+fn end_of_today() -> u64 {
+    let tomorrow = Local::now().date_naive() + chrono::Duration::days(1);
+    tomorrow
+        .and_hms_opt(0, 0, 0)
+        .and_then(|t| Local.from_local_datetime(&t).earliest())
+        .map(|t| t.timestamp().max(0) as u64)
+        .unwrap_or(u64::MAX)
+}
+
+/// 聚合默认面板数据，单个分区读取失败时记录日志并返回空列表，不影响其他分区
+pub fn load(app_data_dir: &PathBuf) -> Result<DefaultPanel, String> {
+    let config = settings::load_settings(app_data_dir)?.default_panel;
+    let mut panel = DefaultPanel {
+        sections: config.sections.clone(),
+        pinned: None,
+        recent_launches: None,
+        clipboard: None,
+        reminders: None,
+    };
+
+    for section in &config.sections {
+        match section {
+            DefaultPanelSection::Pinned => {
+                panel.pinned = Some(
+                    crate::pinned_results::get_pinned_results(app_data_dir).unwrap_or_else(|e| {
+                        eprintln!("[DefaultPanel] Failed to load pinned results: {}", e);
+                        Vec::new()
+                    }),
+                );
+            }
+            DefaultPanelSection::RecentLaunches => {
+                let mut items: Vec<OpenHistoryItem> =
+                    match crate::open_history::get_all_history_items(app_data_dir) {
+                        Ok(items) => items.into_values().collect(),
+                        Err(e) => {
+                            eprintln!("[DefaultPanel] Failed to load open history: {}", e);
+                            Vec::new()
+                        }
+                    };
+                items.sort_by(|a, b| b.last_opened.cmp(&a.last_opened));
+                items.truncate(config.recent_limit);
+                panel.recent_launches = Some(items);
+            }
+            DefaultPanelSection::Clipboard => {
+                panel.clipboard = Some(
+                    crate::clipboard::get_recent_clipboard_items(config.clipboard_limit, app_data_dir)
+                        .unwrap_or_else(|e| {
+                            eprintln!("[DefaultPanel] Failed to load clipboard items: {}", e);
+                            Vec::new()
+                        }),
+                );
+            }
+            DefaultPanelSection::Reminders => {
+                let end = end_of_today();
+                panel.reminders = Some(
+                    crate::reminders::list()
+                        .into_iter()
+                        .filter(|r| r.due_at < end)
+                        .collect(),
+                );
+            }
+        }
+    }
+    Ok(panel)
+}
@@ -0,0 +1,104 @@
+//! 勿扰模式
+//!
+//! 手动开启或处于配置的勿扰时段时，暂停 Toast 通知并推迟提醒弹窗，搜索等核心功能不受影响。
+//! 手动状态只保存在内存中，时段配置来自 Settings.quiet_periods。
+
+use crate::settings::QuietPeriod;
+use chrono::{Local, NaiveTime, Timelike};
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{LazyLock, Mutex};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+
+static MANUAL: AtomicBool = AtomicBool::new(false);
+static LAST_ACTIVE: AtomicBool = AtomicBool::new(false);
+static QUIET_PERIODS: LazyLock<Mutex<Vec<QuietPeriod>>> = LazyLock::new(|| Mutex::new(Vec::new()));
+
+/// 检查时段边界的间隔
+const CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DndStatus {
+    /// 当前是否处于勿扰状态
+    pub active: bool,
+    /// 是否手动开启
+    pub manual: bool,
+    /// 是否处于配置的勿扰时段
+    pub scheduled: bool,
+}
+
+fn parse_time(value: &str) -> Option<NaiveTime> {
+    NaiveTime::parse_from_str(value.trim(), "%H:%M").ok()
+}
+
+fn in_period(period: &QuietPeriod, now: NaiveTime) -> bool {
+    let (Some(start), Some(end)) = (parse_time(&period.start), parse_time(&period.end)) else {
+        return false;
+    };
+    if start <= end {
+        now >= start && now < end
+    } else {
+        // 跨午夜，如 22:00-07:00
+        now >= start || now < end
+    }
+}
+
+fn is_scheduled() -> bool {
+    let now = Local::now().time();
+    let now = NaiveTime::from_hms_opt(now.hour(), now.minute(), 0).unwrap_or(now);
+    QUIET_PERIODS
+        .lock()
+        .map(|periods| periods.iter().any(|p| p.enabled && in_period(p, now)))
+        .unwrap_or(false)
+}
+
+/// 更新勿扰时段配置（启动与保存设置时调用）
+pub fn set_quiet_periods(periods: Vec<QuietPeriod>) {
+    if let Ok(mut current) = QUIET_PERIODS.lock() {
+        *current = periods;
+    }
+}
+
+/// 当前是否处于勿扰状态
+pub fn is_active() -> bool {
+    MANUAL.load(Ordering::SeqCst) || is_scheduled()
+}
+
+pub fn status() -> DndStatus {
+    let manual = MANUAL.load(Ordering::SeqCst);
+    let scheduled = is_scheduled();
+    DndStatus {
+        active: manual || scheduled,
+        manual,
+        scheduled,
+    }
+}
+
+/// 检查状态是否变化，变化时推送 "dnd-changed"
+pub fn refresh(app: &AppHandle) {
+    let current = status();
+    if LAST_ACTIVE.swap(current.active, Ordering::SeqCst) != current.active {
+        eprintln!("[DND] Do not disturb {}", if current.active { "on" } else { "off" });
+    }
+    let _ = app.emit("dnd-changed", current);
+}
+
+/// 手动开关勿扰模式
+pub fn set_manual(app: &AppHandle, enabled: bool) -> DndStatus {
+    MANUAL.store(enabled, Ordering::SeqCst);
+    refresh(app);
+    status()
+}
+
+/// 后台定时检查时段边界，进入或离开勿扰时段时通知托盘与前端
+pub fn start_watcher(app: AppHandle) {
+    LAST_ACTIVE.store(is_active(), Ordering::SeqCst);
+    std::thread::spawn(move || loop {
+        std::thread::sleep(CHECK_INTERVAL);
+        let active = is_active();
+        if LAST_ACTIVE.load(Ordering::SeqCst) != active {
+            refresh(&app);
+        }
+    });
+}
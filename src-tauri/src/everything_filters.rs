@@ -48,3 +48,89 @@ pub fn save_custom_filters(app_data_dir: &Path, filters: &[CustomFilter]) -> Res
     Ok(())
 }
 
+
+/// 命名搜索作用域：只在这些根路径下搜索
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SearchScope {
+    pub id: String,
+    pub name: String,
+    pub paths: Vec<String>,
+}
+
+/// 已保存的作用域方案及当前启用的方案
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct SearchScopeConfig {
+    #[serde(default)]
+    pub active_id: Option<String>,
+    #[serde(default)]
+    pub scopes: Vec<SearchScope>,
+}
+
+/// 加载搜索作用域方案
+pub fn load_scopes(app_data_dir: &Path) -> Result<SearchScopeConfig, String> {
+    let conn = db::get_connection(app_data_dir)?;
+
+    let value: Option<String> = conn
+        .query_row(
+            "SELECT value FROM settings WHERE key = 'everything_scopes' LIMIT 1",
+            [],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(|e| format!("Failed to load search scopes from database: {}", e))?;
+
+    if let Some(json) = value {
+        serde_json::from_str(&json)
+            .map_err(|e| format!("Failed to parse search scopes from database: {}", e))
+    } else {
+        Ok(SearchScopeConfig::default())
+    }
+}
+
+/// 保存搜索作用域方案
+pub fn save_scopes(app_data_dir: &Path, config: &SearchScopeConfig) -> Result<(), String> {
+    let conn = db::get_connection(app_data_dir)?;
+
+    let config_json = serde_json::to_string(config)
+        .map_err(|e| format!("Failed to serialize search scopes: {}", e))?;
+
+    conn.execute(
+        "INSERT INTO settings (key, value) VALUES ('everything_scopes', ?1)
+         ON CONFLICT(key) DO UPDATE SET value = excluded.value",
+        params![config_json],
+    )
+    .map_err(|e| format!("Failed to save search scopes to database: {}", e))?;
+
+    Ok(())
+}
+
+/// 当前启用方案的根路径，未启用时为空
+pub fn active_scope_paths(app_data_dir: &Path) -> Vec<String> {
+    let Ok(config) = load_scopes(app_data_dir) else {
+        return Vec::new();
+    };
+    let Some(active_id) = config.active_id else {
+        return Vec::new();
+    };
+    config
+        .scopes
+        .into_iter()
+        .find(|scope| scope.id == active_id)
+        .map(|scope| scope.paths)
+        .unwrap_or_default()
+}
+
+/// 把根路径列表转换为 Everything 的 path: 过滤，如 <path:"D:\work\"|path:"E:\docs\">
+pub fn scope_query(paths: &[String]) -> Option<String> {
+    let terms: Vec<String> = paths
+        .iter()
+        .map(|p| p.trim().trim_matches('"').trim_end_matches(['\\', '/']))
+        .filter(|p| !p.is_empty())
+        .map(|p| format!("path:\"{}\\\"", p.replace('/', "\\")))
+        .collect();
+    match terms.len() {
+        0 => None,
+        1 => terms.into_iter().next(),
+        _ => Some(format!("<{}>", terms.join("|"))),
+    }
+}
@@ -10,6 +10,84 @@ pub struct EverythingResult {
     pub is_folder: Option<bool>,
 }
 
+/// 排序字段，对应 Everything IPC 的 sort_type
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum EverythingSortKey {
+    Name,
+    Path,
+    Size,
+    /// 按扩展名
+    Type,
+    DateModified,
+}
+
+/// 由 Everything 完成排序，客户端不再对全部结果排序
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EverythingSort {
+    pub key: EverythingSortKey,
+    pub descending: bool,
+}
+
+impl EverythingSort {
+    /// 解析前端传入的排序参数（key: name/path/size/type/date_modified，order: asc/desc，默认降序）
+    pub fn parse(key: &str, order: Option<&str>) -> Option<Self> {
+        let key = match key {
+            "name" => EverythingSortKey::Name,
+            "path" => EverythingSortKey::Path,
+            "size" => EverythingSortKey::Size,
+            "type" | "extension" => EverythingSortKey::Type,
+            "date_modified" | "dateModified" | "modified" => EverythingSortKey::DateModified,
+            _ => return None,
+        };
+        Some(Self {
+            key,
+            descending: order != Some("asc"),
+        })
+    }
+
+    /// EVERYTHING_IPC_SORT_* 常量
+    pub fn ipc_sort_type(&self) -> u32 {
+        let ascending = match self.key {
+            EverythingSortKey::Name => 1,
+            EverythingSortKey::Path => 3,
+            EverythingSortKey::Size => 5,
+            EverythingSortKey::Type => 7,
+            EverythingSortKey::DateModified => 13,
+        };
+        if self.descending {
+            ascending + 1
+        } else {
+            ascending
+        }
+    }
+}
+
+/// 文件夹优先：稳定地把文件夹排到文件前面，组内保持 Everything 返回的顺序
+pub fn sort_folders_first(results: &mut [EverythingResult]) {
+    results.sort_by_key(|r| !r.is_folder.unwrap_or(false));
+}
+
+/// 把 "folder：" / "文件夹:" 等快捷写法统一为 Everything 的 folder: 语法
+pub fn normalize_folder_shortcut(query: &str) -> String {
+    const SHORTCUTS: [&str; 3] = ["folder：", "文件夹:", "文件夹："];
+    // 没有快捷写法时原样返回，保留引号内的连续空白
+    if !SHORTCUTS.iter().any(|s| query.contains(s)) {
+        return query.to_string();
+    }
+    query
+        .split_whitespace()
+        .map(|word| {
+            SHORTCUTS
+                .iter()
+                .find_map(|prefix| word.strip_prefix(prefix))
+                .map(|rest| format!("folder:{}", rest))
+                .unwrap_or_else(|| word.to_string())
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct EverythingSearchResponse {
     pub results: Vec<EverythingResult>,
@@ -66,6 +144,38 @@ impl fmt::Display for EverythingError {
     }
 }
 
+/// Everything 安装检测状态
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum EverythingInstallState {
+    /// 已安装且 IPC 可用
+    Ready,
+    /// 未安装
+    NotInstalled,
+    /// 已安装但未运行
+    NotRunning,
+    /// Lite 版（不支持 IPC）
+    Lite,
+    /// 正在运行但 IPC 不可用（被禁用）
+    IpcDisabled,
+}
+
+/// Everything 安装检测结果
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct EverythingInstallation {
+    pub state: EverythingInstallState,
+    /// 找到的 Everything.exe 路径
+    pub exe_path: Option<String>,
+    /// 是否安装了 Everything 服务
+    pub service_installed: bool,
+    /// Everything 服务是否在运行
+    pub service_running: bool,
+    /// Everything 进程是否在运行
+    pub process_running: bool,
+    /// 修复建议码：INSTALL_EVERYTHING / START_EVERYTHING / START_SERVICE / INSTALL_FULL_VERSION / ENABLE_IPC
+    pub suggestions: Vec<String>,
+}
+
 #[cfg(target_os = "windows")]
 pub mod windows {
     use super::*;
@@ -90,6 +200,15 @@ pub mod windows {
     const EVERYTHING_IPC_COPYDATAQUERYW: usize = 2; // Unicode 查询命令（必须使用 2，不是 0x10001）
     const EVERYTHING_IPC_REPLY: u32 = 2;
     const COPYDATA_QUERYCOMPLETE: u32 = 0x804E; // 新协议必须使用 0x804E
+    // Query2 协议（支持服务端排序并可请求大小/修改时间），回复用独立的消息值以区分解析方式
+    const EVERYTHING_IPC_COPYDATA_QUERY2W: usize = 18;
+    const COPYDATA_QUERY2COMPLETE: u32 = 0x804F;
+
+    // Query2 请求字段，回复数据按位从低到高依次排列
+    const EVERYTHING_IPC_QUERY2_REQUEST_NAME: u32 = 0x00000001;
+    const EVERYTHING_IPC_QUERY2_REQUEST_PATH: u32 = 0x00000002;
+    const EVERYTHING_IPC_QUERY2_REQUEST_SIZE: u32 = 0x00000010;
+    const EVERYTHING_IPC_QUERY2_REQUEST_DATE_MODIFIED: u32 = 0x00000040;
 
     // Everything IPC 搜索标志
     const EVERYTHING_IPC_REGEX: u32 = 0x00000001;
@@ -112,6 +231,48 @@ pub mod windows {
                                      // 注意：结构体后面紧跟着 UTF-16 字符串，没有额外的对齐
     }
 
+    // Query2 查询结构体（EVERYTHING_IPC_QUERY2），search_string 紧随其后
+    #[repr(C)]
+    struct EverythingIpcQuery2 {
+        reply_hwnd: u32,
+        reply_copydata_message: u32,
+        search_flags: u32,
+        offset: u32,
+        max_results: u32,
+        request_flags: u32,
+        sort_type: u32,
+    }
+
+    // Query2 回复头（EVERYTHING_IPC_LIST2），20 字节，items 紧随其后
+    #[repr(C)]
+    #[derive(Debug, Clone, Copy)]
+    struct EverythingIpcList2 {
+        totitems: u32,
+        numitems: u32,
+        offset: u32,
+        request_flags: u32,
+        sort_type: u32,
+    }
+
+    // Query2 回复条目（EVERYTHING_IPC_ITEM2），data_offset 相对 list 起始地址
+    #[repr(C)]
+    #[derive(Debug, Clone, Copy)]
+    struct EverythingIpcItem2 {
+        flags: u32,
+        data_offset: u32,
+    }
+
+    /// IPC 回复中的单个条目
+    struct IpcItem {
+        path: String,
+        flags: u32,
+        size: Option<u64>,
+        date_modified: Option<String>,
+    }
+
+    /// (结果列表, 总条数, 当前页条数, 当前页偏移量)
+    type IpcReply = Result<(Vec<IpcItem>, u32, u32, u32), EverythingError>;
+
     // Everything IPC 回复结构体（根据官方头文件 everything_ipc.h）
     // 对应 EVERYTHING_IPC_LISTW 结构体
     // 总大小：28 字节（7 * DWORD）
@@ -154,7 +315,7 @@ pub mod windows {
     static WINDOW_SENDERS: OnceLock<
         Arc<
             Mutex<
-                HashMap<HWND, mpsc::Sender<Result<(Vec<(String, u32)>, u32, u32, u32), EverythingError>>>,
+                HashMap<HWND, mpsc::Sender<IpcReply>>,
             >,
         >,
     > = OnceLock::new();
@@ -296,7 +457,7 @@ pub mod windows {
     }
 
     fn get_window_senders() -> &'static Arc<
-        Mutex<HashMap<HWND, mpsc::Sender<Result<(Vec<(String, u32)>, u32, u32, u32), EverythingError>>>>,
+        Mutex<HashMap<HWND, mpsc::Sender<IpcReply>>>,
     > {
         WINDOW_SENDERS.get_or_init(|| Arc::new(Mutex::new(HashMap::new())))
     }
@@ -344,13 +505,18 @@ pub mod windows {
                 // Everything 回复时，dwData 通常是 EVERYTHING_IPC_REPLY (2)
                 // 新协议也可能使用我们发送的 reply_copydata_message 值
                 // 为了兼容性，我们检查多种可能
+                let is_query2_reply = cds.dwData == COPYDATA_QUERY2COMPLETE as usize;
                 let is_reply = cds.dwData == EVERYTHING_IPC_REPLY as usize
                     || cds.dwData == COPYDATA_QUERYCOMPLETE as usize
                     || cds.dwData == 0x804E; // 兼容新协议可能的回复值
 
-                if is_reply {
-                    // 解析结果（现在返回四元组：结果列表(路径, flags), 总条数, 当前页条数, 当前页偏移量）
-                    let result = parse_ipc_reply(&cds);
+                if is_reply || is_query2_reply {
+                    // 解析结果（现在返回四元组：结果列表, 总条数, 当前页条数, 当前页偏移量）
+                    let result = if is_query2_reply {
+                        parse_ipc_reply2(&cds)
+                    } else {
+                        parse_ipc_reply(&cds)
+                    };
                     match &result {
                         Ok((paths_with_flags, tot, num, _off)) => {
                             // 只在批次数量很大或出错时输出详细日志，减少日志噪音
@@ -401,7 +567,7 @@ pub mod windows {
     /// Everything IPC 查询句柄，用于管理消息循环和结果接收
     struct EverythingIpcHandle {
         reply_hwnd: HWND,
-        result_receiver: mpsc::Receiver<Result<(Vec<(String, u32)>, u32, u32, u32), EverythingError>>,
+        result_receiver: mpsc::Receiver<IpcReply>,
     }
 
     impl EverythingIpcHandle {
@@ -579,11 +745,119 @@ pub mod windows {
         Some(read_u16_string(str_ptr, max_chars))
     }
 
+    /// 读取 Query2 数据区中以 DWORD 长度开头的 UTF-16 字符串，返回字符串和之后的偏移
+    unsafe fn read_len_prefixed_string(base: *const u8, offset: usize, data_size: usize) -> Option<(String, usize)> {
+        if offset + 4 > data_size {
+            return None;
+        }
+        let len = ptr::read_unaligned(base.add(offset) as *const u32) as usize;
+        let text_start = offset + 4;
+        // 文本后还有一个结尾 0
+        let text_end = text_start + (len + 1) * 2;
+        if text_end > data_size {
+            return None;
+        }
+        let mut chars = Vec::with_capacity(len);
+        for i in 0..len {
+            chars.push(ptr::read_unaligned(base.add(text_start + i * 2) as *const u16));
+        }
+        Some((String::from_utf16_lossy(&chars), text_end))
+    }
+
+    /// FILETIME（1601 年起的 100ns 计数）转换为本地时间字符串
+    fn filetime_to_string(filetime: u64) -> Option<String> {
+        const UNIX_EPOCH_OFFSET_SECS: i64 = 11_644_473_600;
+        if filetime == 0 || filetime == u64::MAX {
+            return None;
+        }
+        let secs = (filetime / 10_000_000) as i64 - UNIX_EPOCH_OFFSET_SECS;
+        chrono::DateTime::from_timestamp(secs, 0).map(|dt| {
+            dt.with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M:%S")
+                .to_string()
+        })
+    }
+
+    /// 解析 Everything Query2 回复（EVERYTHING_IPC_LIST2）
+    fn parse_ipc_reply2(cds: &COPYDATASTRUCT) -> IpcReply {
+        let header_size = std::mem::size_of::<EverythingIpcList2>();
+        let item_size = std::mem::size_of::<EverythingIpcItem2>();
+        let data_size = cds.cbData as usize;
+        if data_size < header_size {
+            return Err(EverythingError::IpcFailed("回复数据太短".to_string()));
+        }
+
+        unsafe {
+            let base = cds.lpData as *const u8;
+            let list = ptr::read_unaligned(base as *const EverythingIpcList2);
+            if header_size + list.numitems as usize * item_size > data_size {
+                return Err(EverythingError::IpcFailed(format!(
+                    "回复数据大小不足: {} 条结果，实际只有 {} 字节",
+                    list.numitems, data_size
+                )));
+            }
+
+            let mut results = Vec::with_capacity(list.numitems as usize);
+            for i in 0..list.numitems as usize {
+                let item = ptr::read_unaligned(base.add(header_size + i * item_size) as *const EverythingIpcItem2);
+                let mut cursor = item.data_offset as usize;
+
+                let mut name = String::new();
+                let mut path_part = String::new();
+                let mut size = None;
+                let mut date_modified = None;
+
+                if list.request_flags & EVERYTHING_IPC_QUERY2_REQUEST_NAME != 0 {
+                    let Some((text, next)) = read_len_prefixed_string(base, cursor, data_size) else { continue };
+                    name = text;
+                    cursor = next;
+                }
+                if list.request_flags & EVERYTHING_IPC_QUERY2_REQUEST_PATH != 0 {
+                    let Some((text, next)) = read_len_prefixed_string(base, cursor, data_size) else { continue };
+                    path_part = text;
+                    cursor = next;
+                }
+                if list.request_flags & EVERYTHING_IPC_QUERY2_REQUEST_SIZE != 0 && cursor + 8 <= data_size {
+                    let value = ptr::read_unaligned(base.add(cursor) as *const u64);
+                    // 文件夹大小未索引时为 -1
+                    size = (value != u64::MAX).then_some(value);
+                    cursor += 8;
+                }
+                if list.request_flags & EVERYTHING_IPC_QUERY2_REQUEST_DATE_MODIFIED != 0 && cursor + 8 <= data_size {
+                    date_modified = filetime_to_string(ptr::read_unaligned(base.add(cursor) as *const u64));
+                }
+
+                let path = if path_part.is_empty() {
+                    name
+                } else if name.is_empty() {
+                    path_part
+                } else {
+                    PathBuf::from(format!("{}\\", path_part.trim_end_matches('\\')))
+                        .join(&name)
+                        .to_string_lossy()
+                        .to_string()
+                };
+                if path.is_empty() {
+                    continue;
+                }
+
+                results.push(IpcItem {
+                    path,
+                    flags: item.flags,
+                    size,
+                    date_modified,
+                });
+            }
+
+            Ok((results, list.totitems, list.numitems, list.offset))
+        }
+    }
+
     /// 解析 Everything IPC 回复（官方协议）
-    /// 返回 (结果列表(路径, flags), 总条数, 当前页条数, 当前页偏移量)
+    /// 返回 (结果列表, 总条数, 当前页条数, 当前页偏移量)
     fn parse_ipc_reply(
         cds: &COPYDATASTRUCT,
-    ) -> Result<(Vec<(String, u32)>, u32, u32, u32), EverythingError> {
+    ) -> IpcReply {
         // 验证结构体大小（根据官方头文件，应该是 28 字节）
         let expected_list_size = 28u32; // 7 * DWORD = 28 字节
         let actual_list_size = std::mem::size_of::<EverythingIpcList>() as u32;
@@ -642,7 +916,7 @@ pub mod windows {
 
             // 如果 totitems 和 numitems 为 0，直接返回空结果
             if numitems == 0 {
-                return Ok((Vec::<(String, u32)>::new(), totitems, 0, offset));
+                return Ok((Vec::new(), totitems, 0, offset));
             }
 
             // 处理所有返回的 item（不再限制）
@@ -673,7 +947,7 @@ pub mod windows {
             // 使用宏定义：EVERYTHING_IPC_ITEMFILENAMEW(list,item) = (WCHAR *)((CHAR *)(list) + item->filename_offset)
             // 使用宏定义：EVERYTHING_IPC_ITEMPATHW(list,item) = (WCHAR *)((CHAR *)(list) + item->path_offset)
 
-            let mut results: Vec<(String, u32)> = Vec::new();
+            let mut results: Vec<IpcItem> = Vec::new();
             let mut skipped_count = 0;
             let mut invalid_offset_count = 0;
 
@@ -809,8 +1083,13 @@ pub mod windows {
 
                 // 只有当文件名或路径至少有一个有效时才添加结果
                 if !filename.is_empty() || !path_part.is_empty() {
-                    // 返回路径和 flags 的元组
-                    results.push((full_path, flags));
+                    // 旧协议不返回大小与修改时间
+                    results.push(IpcItem {
+                        path: full_path,
+                        flags,
+                        size: None,
+                        date_modified: None,
+                    });
                 } else {
                     skipped_count += 1;
                     // 性能优化：减少日志输出
@@ -895,23 +1174,131 @@ pub mod windows {
         find_everything_window_internal("check_everything_service_running").is_some()
     }
 
-    /// 查找 Everything.exe 主程序路径（用于启动）
-    pub fn find_everything_main_exe() -> Option<PathBuf> {
-        let everything_paths = [
+    /// Everything.exe 的常见安装路径
+    fn everything_exe_candidates() -> Vec<PathBuf> {
+        let mut candidates: Vec<PathBuf> = [
             r"C:\Program Files\Everything\Everything.exe",
             r"C:\Program Files (x86)\Everything\Everything.exe",
             r"C:\Tools\Everything\Everything.exe",
             r"C:\Everything\Everything.exe",
-        ];
+        ]
+        .iter()
+        .map(PathBuf::from)
+        .collect();
+
+        // 非 C 盘安装或便携安装到用户目录
+        for (var, sub) in [
+            ("ProgramFiles", r"Everything\Everything.exe"),
+            ("ProgramFiles(x86)", r"Everything\Everything.exe"),
+            ("ProgramW6432", r"Everything\Everything.exe"),
+            ("LOCALAPPDATA", r"Programs\Everything\Everything.exe"),
+        ] {
+            if let Ok(base) = std::env::var(var) {
+                let path = PathBuf::from(base).join(sub);
+                if !candidates.contains(&path) {
+                    candidates.push(path);
+                }
+            }
+        }
+
+        candidates
+    }
+
+    /// 查找 Everything.exe 主程序路径（用于启动）
+    pub fn find_everything_main_exe() -> Option<PathBuf> {
+        everything_exe_candidates().into_iter().find(|path| path.exists())
+    }
 
-        for path in &everything_paths {
-            let exe_path = PathBuf::from(path);
-            if exe_path.exists() {
-                return Some(exe_path);
+    /// 查询 Everything 服务状态，返回 (是否已安装, 是否在运行)
+    fn query_everything_service() -> (bool, bool) {
+        match std::process::Command::new("sc")
+            .args(&["query", "Everything"])
+            .creation_flags(0x08000000) // CREATE_NO_WINDOW
+            .output()
+        {
+            Ok(output) => {
+                // 服务不存在时 sc 返回 1060（失败）
+                let installed = output.status.success();
+                let stdout = String::from_utf8_lossy(&output.stdout);
+                (installed, installed && stdout.contains("RUNNING"))
             }
+            Err(_) => (false, false),
+        }
+    }
+
+    /// 检查 Everything.exe 进程是否在运行
+    fn is_everything_process_running() -> bool {
+        match std::process::Command::new("tasklist")
+            .args(&["/FI", "IMAGENAME eq Everything.exe", "/NH"])
+            .creation_flags(0x08000000) // CREATE_NO_WINDOW
+            .output()
+        {
+            Ok(output) => String::from_utf8_lossy(&output.stdout)
+                .to_lowercase()
+                .contains("everything.exe"),
+            Err(_) => false,
         }
+    }
+
+    /// 判断是否为 Lite 版（Lite 版版本资源中的描述带有 "Everything Lite"）
+    fn is_lite_build(exe_path: &PathBuf) -> bool {
+        if exe_path
+            .to_string_lossy()
+            .to_lowercase()
+            .contains("lite")
+        {
+            return true;
+        }
+        let Ok(bytes) = std::fs::read(exe_path) else {
+            return false;
+        };
+        let needle: Vec<u8> = "Everything Lite"
+            .encode_utf16()
+            .flat_map(|c| c.to_le_bytes())
+            .collect();
+        bytes.windows(needle.len()).any(|w| w == needle.as_slice())
+    }
+
+    /// 检测 Everything 安装情况，区分未安装/未运行/Lite 版/IPC 被禁用
+    pub fn detect_installation() -> EverythingInstallation {
+        let exe_path = find_everything_main_exe();
+        let (service_installed, service_running) = query_everything_service();
+        let process_running = is_everything_process_running();
+        let ipc_available = check_everything_service_running();
+
+        let (state, suggestions) = if ipc_available {
+            (EverythingInstallState::Ready, vec![])
+        } else if exe_path.is_none() && !process_running && !service_installed {
+            (
+                EverythingInstallState::NotInstalled,
+                vec!["INSTALL_EVERYTHING".to_string()],
+            )
+        } else if exe_path.as_ref().map(is_lite_build).unwrap_or(false) {
+            (
+                EverythingInstallState::Lite,
+                vec!["INSTALL_FULL_VERSION".to_string()],
+            )
+        } else if !process_running {
+            let mut suggestions = vec!["START_EVERYTHING".to_string()];
+            if service_installed && !service_running {
+                suggestions.push("START_SERVICE".to_string());
+            }
+            (EverythingInstallState::NotRunning, suggestions)
+        } else {
+            (
+                EverythingInstallState::IpcDisabled,
+                vec!["ENABLE_IPC".to_string()],
+            )
+        };
 
-        None
+        EverythingInstallation {
+            state,
+            exe_path: exe_path.map(|p| p.to_string_lossy().to_string()),
+            service_installed,
+            service_running,
+            process_running,
+            suggestions,
+        }
     }
 
     /// 获取 Everything 版本号
@@ -979,6 +1366,7 @@ pub mod windows {
         reply_hwnd: HWND,
         everything_hwnd: HWND,
         search_flags: u32, // 搜索标志（如全字匹配、大小写敏感等）
+        sort: Option<EverythingSort>, // 指定排序时使用 Query2 协议
     ) -> Result<(), EverythingError> {
         // 将查询字符串转换为 UTF-16（以双 0 结尾）
         let query_wide = wide_string(query);
@@ -990,8 +1378,12 @@ pub mod windows {
             log_debug!("[DEBUG] WARNING: Query string does not end with double null!");
         }
 
-        // 计算结构体大小（Everything 1.4+ QueryW 协议）
-        let base_size = std::mem::size_of::<EverythingIpcQueryW>();
+        // 计算结构体大小（Everything 1.4+ QueryW 协议，排序时为 Query2）
+        let base_size = if sort.is_some() {
+            std::mem::size_of::<EverythingIpcQuery2>()
+        } else {
+            std::mem::size_of::<EverythingIpcQueryW>()
+        };
         let string_size = query_wide.len() * std::mem::size_of::<u16>();
         let struct_size = base_size + string_size;
 
@@ -999,23 +1391,45 @@ pub mod windows {
         let mut query_data = vec![0u8; struct_size];
         let query_ptr = query_data.as_mut_ptr() as *mut EverythingIpcQueryW;
 
-        unsafe {
-            // 按照 Everything 1.4+ QueryW 协议顺序填充结构体
-            // 顺序：reply_hwnd, reply_copydata_message (0x804E), search_flags, reply_offset, max_results
-            (*query_ptr).reply_hwnd = reply_hwnd as u32; // HWND 转换为 u32
-            (*query_ptr).reply_copydata_message = COPYDATA_QUERYCOMPLETE; // 必须填 0x804E
-            (*query_ptr).search_flags = search_flags; // 使用传入的搜索标志
-            (*query_ptr).reply_offset = offset; // 使用传入的 offset 参数
-            (*query_ptr).max_results = max_results;
+        if let Some(sort) = sort {
+            let query2_ptr = query_data.as_mut_ptr() as *mut EverythingIpcQuery2;
+            unsafe {
+                (*query2_ptr).reply_hwnd = reply_hwnd as u32;
+                (*query2_ptr).reply_copydata_message = COPYDATA_QUERY2COMPLETE;
+                (*query2_ptr).search_flags = search_flags;
+                (*query2_ptr).offset = offset;
+                (*query2_ptr).max_results = max_results;
+                (*query2_ptr).request_flags = EVERYTHING_IPC_QUERY2_REQUEST_NAME
+                    | EVERYTHING_IPC_QUERY2_REQUEST_PATH
+                    | EVERYTHING_IPC_QUERY2_REQUEST_SIZE
+                    | EVERYTHING_IPC_QUERY2_REQUEST_DATE_MODIFIED;
+                (*query2_ptr).sort_type = sort.ipc_sort_type();
+            }
+        } else {
+            unsafe {
+                // 按照 Everything 1.4+ QueryW 协议顺序填充结构体
+                // 顺序：reply_hwnd, reply_copydata_message (0x804E), search_flags, reply_offset, max_results
+                (*query_ptr).reply_hwnd = reply_hwnd as u32; // HWND 转换为 u32
+                (*query_ptr).reply_copydata_message = COPYDATA_QUERYCOMPLETE; // 必须填 0x804E
+                (*query_ptr).search_flags = search_flags; // 使用传入的搜索标志
+                (*query_ptr).reply_offset = offset; // 使用传入的 offset 参数
+                (*query_ptr).max_results = max_results;
+            }
+        }
 
+        unsafe {
             // 复制查询字符串到结构体后面
             let search_string_ptr = (query_ptr as *mut u8).add(base_size) as *mut u16;
             ptr::copy_nonoverlapping(query_wide.as_ptr(), search_string_ptr, query_wide.len());
         }
 
-        // 创建 COPYDATASTRUCT（Everything 1.4+ 使用 QueryW 协议）
+        // 创建 COPYDATASTRUCT（Everything 1.4+ 使用 QueryW 协议，排序时使用 Query2）
         let mut cds = COPYDATASTRUCT {
-            dwData: EVERYTHING_IPC_COPYDATAQUERYW, // 关键！必须是 2 (EVERYTHING_IPC_COPYDATAQUERYW)
+            dwData: if sort.is_some() {
+                EVERYTHING_IPC_COPYDATA_QUERY2W
+            } else {
+                EVERYTHING_IPC_COPYDATAQUERYW // 关键！必须是 2 (EVERYTHING_IPC_COPYDATAQUERYW)
+            },
             cbData: struct_size as u32,
             lpData: query_data.as_mut_ptr() as *mut std::ffi::c_void,
         };
@@ -1152,17 +1566,74 @@ pub mod windows {
     /// # Arguments
     /// * `query` - 搜索查询字符串
     /// * `max_results` - 最大结果数量
+    /// * `timeout` - 每批次等待回复的超时；未拿到任何结果就超时时自动重试一次
+    /// * `sort` - 可选的排序方式，由 Everything 通过 Query2 协议完成排序
     /// * `cancelled` - 可选的取消标志，如果设置为 true，搜索将提前终止
     /// * `on_batch` - 可选的批次回调函数，每获取一批结果时调用
     pub fn search_files<F>(
         query: &str,
         max_results: usize,
         chunk_size: usize,
+        timeout: Duration,
+        sort: Option<EverythingSort>,
         cancelled: Option<&std::sync::Arc<std::sync::atomic::AtomicBool>>,
         mut on_batch: Option<F>,
     ) -> Result<EverythingSearchResponse, EverythingError>
     where
         F: FnMut(&[EverythingResult], u32, u32), // (batch_results, total_count, current_count)
+    {
+        let started = Instant::now();
+        let mut attempt = 1;
+        loop {
+            let attempt_started = Instant::now();
+            let result = search_files_once(
+                query,
+                max_results,
+                chunk_size,
+                timeout,
+                sort,
+                cancelled,
+                on_batch.as_mut(),
+            );
+            match &result {
+                Ok(resp) => eprintln!(
+                    "[Everything] Query '{}' finished in {:?} (attempt {}, {} results)",
+                    query,
+                    attempt_started.elapsed(),
+                    attempt,
+                    resp.results.len()
+                ),
+                Err(e) => eprintln!(
+                    "[Everything] Query '{}' failed in {:?} (attempt {}): {}",
+                    query,
+                    attempt_started.elapsed(),
+                    attempt,
+                    e
+                ),
+            }
+
+            if matches!(result, Err(EverythingError::Timeout)) && attempt < 2 {
+                attempt += 1;
+                continue;
+            }
+            if attempt > 1 {
+                eprintln!("[Everything] Query '{}' total time {:?}", query, started.elapsed());
+            }
+            return result;
+        }
+    }
+
+    fn search_files_once<F>(
+        query: &str,
+        max_results: usize,
+        chunk_size: usize,
+        timeout: Duration,
+        sort: Option<EverythingSort>,
+        cancelled: Option<&std::sync::Arc<std::sync::atomic::AtomicBool>>,
+        mut on_batch: Option<F>,
+    ) -> Result<EverythingSearchResponse, EverythingError>
+    where
+        F: FnMut(&[EverythingResult], u32, u32),
     {
         log_debug!("[DEBUG] ===== search_files called =====");
         log_debug!(
@@ -1187,9 +1658,6 @@ pub mod windows {
             EverythingError::ServiceNotRunning
         })?;
 
-        // 每批次的超时（略放宽，避免大结果集/高负载时过早超时）
-        let timeout = Duration::from_secs(12);
-
         // 归一化参数
         let target_max = max_results.max(1);
         let chunk = chunk_size.max(1);
@@ -1254,6 +1722,7 @@ pub mod windows {
                 ipc_handle.reply_hwnd,
                 everything_hwnd,
                 search_flags,
+                sort,
             )
             .map_err(|e| {
                 log_debug!("[DEBUG] ERROR: Failed to send search query: {:?}", e);
@@ -1263,9 +1732,7 @@ pub mod windows {
             // 等待回复
             // 性能优化：使用自适应休眠时间减少CPU占用，同时保持响应性
             let start = Instant::now();
-            let mut batch_result: Option<
-                Result<(Vec<(String, u32)>, u32, u32, u32), EverythingError>,
-            > = None;
+            let mut batch_result: Option<IpcReply> = None;
             let mut consecutive_empty_count = 0u32; // 连续空轮询计数
 
             loop {
@@ -1277,12 +1744,16 @@ pub mod windows {
                     }
                 }
 
-                // 检查超时
+                // 检查超时：已有部分结果时带着结果返回
                 if start.elapsed() > timeout {
                     log_debug!(
-                        "[DEBUG] ERROR: Timeout waiting for batch reply after {:?}",
+                        "[DEBUG] Timeout waiting for batch reply after {:?}",
                         start.elapsed()
                     );
+                    if !all_results.is_empty() {
+                        timeout_with_partial = true;
+                        break;
+                    }
                     return Err(EverythingError::Timeout);
                 }
 
@@ -1364,16 +1835,15 @@ pub mod windows {
 
             // 转换为 EverythingResult，限制每批
             let mut batch_results: Vec<EverythingResult> = Vec::new();
-            for (path, flags) in batch_paths_with_flags.iter().take(limit_for_batch) {
-                let path_buf = PathBuf::from(path);
+            for item in batch_paths_with_flags.into_iter().take(limit_for_batch) {
+                let path_buf = PathBuf::from(&item.path);
                 let name = path_buf
                     .file_name()
                     .and_then(|n| n.to_str())
                     .map(|s| s.to_string())
-                    .unwrap_or_else(|| path.clone());
+                    .unwrap_or_else(|| item.path.clone());
 
-                let size = None;
-                let date_modified = None;
+                let flags = item.flags;
                 let is_folder = if (flags & EVERYTHING_IPC_FOLDER) != 0
                     || (flags & EVERYTHING_IPC_DRIVE) != 0
                     || (flags & EVERYTHING_IPC_ROOT) != 0
@@ -1384,10 +1854,10 @@ pub mod windows {
                 };
 
                 batch_results.push(EverythingResult {
-                    path: path.clone(),
+                    path: item.path,
                     name,
-                    size,
-                    date_modified,
+                    size: item.size,
+                    date_modified: item.date_modified,
                     is_folder,
                 });
             }
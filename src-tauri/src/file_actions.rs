@@ -0,0 +1,570 @@
+//! 文件结果的快速操作：压缩为 zip、复制到、移动到
+//!
+//! 所有操作逐个处理输入路径，并通过回调报告进度，由命令层转发为前端事件
+
+use chrono::{DateTime, Datelike, Local, Timelike};
+use flate2::write::DeflateEncoder;
+use flate2::{Compression, Crc};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File};
+use std::io::{BufWriter, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+/// 目标位置已存在同名文件时的处理策略
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ConflictStrategy {
+    Overwrite,
+    Rename,
+    Skip,
+}
+
+impl Default for ConflictStrategy {
+    fn default() -> Self {
+        ConflictStrategy::Rename
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FileActionProgress {
+    pub action: String,
+    pub current: usize,
+    pub total: usize,
+    pub path: String,
+}
+
+#[derive(Debug, Clone, Serialize, Default)]
+pub struct FileActionResult {
+    pub succeeded: Vec<String>,
+    pub skipped: Vec<String>,
+    pub failed: Vec<FileActionFailure>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FileActionFailure {
+    pub path: String,
+    pub error: String,
+}
+
+/// 为冲突的目标路径生成 "name (1).ext" 形式的新路径
+fn next_available_path(target: &Path) -> PathBuf {
+    let parent = target.parent().map(Path::to_path_buf).unwrap_or_default();
+    let stem = target
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let ext = target
+        .extension()
+        .map(|e| format!(".{}", e.to_string_lossy()))
+        .unwrap_or_default();
+
+    let mut index = 1;
+    loop {
+        let candidate = parent.join(format!("{} ({}){}", stem, index, ext));
+        if !candidate.exists() {
+            return candidate;
+        }
+        index += 1;
+    }
+}
+
+/// 根据冲突策略解析最终目标路径，返回 None 表示跳过
+fn resolve_target(target: PathBuf, strategy: ConflictStrategy) -> Result<Option<PathBuf>, String> {
+    if !target.exists() {
+        return Ok(Some(target));
+    }
+    match strategy {
+        ConflictStrategy::Skip => Ok(None),
+        ConflictStrategy::Rename => Ok(Some(next_available_path(&target))),
+        ConflictStrategy::Overwrite => {
+            if target.is_dir() {
+                fs::remove_dir_all(&target)
+            } else {
+                fs::remove_file(&target)
+            }
+            .map_err(|e| format!("Failed to remove existing target {}: {}", target.display(), e))?;
+            Ok(Some(target))
+        }
+    }
+}
+
+/// 在按冲突策略删除已有目标之前检查源与目标的关系，返回 false 表示跳过。
+///
+/// 目标就是源本身时只允许以重命名方式复制一份；目标在源内部或是源的上级目录时报错，
+/// 避免覆盖时先删掉源文件
+fn check_source_target(
+    action: &str,
+    src: &Path,
+    target: &Path,
+    strategy: ConflictStrategy,
+) -> Result<bool, String> {
+    let src = fs::canonicalize(src)
+        .map_err(|e| format!("Failed to resolve source {}: {}", src.display(), e))?;
+    // 已存在的目标同样解析，大小写不同或经由链接指向源时也能识别为同一路径
+    let target = fs::canonicalize(target).unwrap_or_else(|_| target.to_path_buf());
+    if target == src {
+        return match (action, strategy) {
+            ("copy", ConflictStrategy::Rename) => Ok(true),
+            ("copy", ConflictStrategy::Overwrite) => {
+                Err("Source and target are the same file".to_string())
+            }
+            // 移动到所在目录无需操作
+            _ => Ok(false),
+        };
+    }
+    if target.starts_with(&src) {
+        return Err("Cannot copy or move a folder into itself".to_string());
+    }
+    if src.starts_with(&target) {
+        return Err("Cannot overwrite a folder that contains the source".to_string());
+    }
+    Ok(true)
+}
+
+fn copy_recursive(src: &Path, dst: &Path) -> Result<(), String> {
+    if src.is_dir() {
+        fs::create_dir_all(dst)
+            .map_err(|e| format!("Failed to create directory {}: {}", dst.display(), e))?;
+        let entries = fs::read_dir(src)
+            .map_err(|e| format!("Failed to read directory {}: {}", src.display(), e))?;
+        for entry in entries {
+            let entry = entry.map_err(|e| format!("Failed to read directory entry: {}", e))?;
+            copy_recursive(&entry.path(), &dst.join(entry.file_name()))?;
+        }
+        Ok(())
+    } else {
+        fs::copy(src, dst)
+            .map(|_| ())
+            .map_err(|e| format!("Failed to copy {}: {}", src.display(), e))
+    }
+}
+
+fn transfer(
+    action: &str,
+    paths: &[String],
+    target_dir: &str,
+    strategy: ConflictStrategy,
+    on_progress: &dyn Fn(FileActionProgress),
+) -> Result<FileActionResult, String> {
+    let target_dir = Path::new(target_dir);
+    if !target_dir.is_dir() {
+        return Err(format!("Target directory does not exist: {}", target_dir.display()));
+    }
+    let target_dir = fs::canonicalize(target_dir)
+        .map_err(|e| format!("Failed to resolve target directory {}: {}", target_dir.display(), e))?;
+
+    let mut result = FileActionResult::default();
+    let total = paths.len();
+
+    for (index, path) in paths.iter().enumerate() {
+        on_progress(FileActionProgress {
+            action: action.to_string(),
+            current: index + 1,
+            total,
+            path: path.clone(),
+        });
+
+        let src = Path::new(path);
+        let file_name = match src.file_name() {
+            Some(name) => name,
+            None => {
+                result.failed.push(FileActionFailure {
+                    path: path.clone(),
+                    error: "Invalid source path".to_string(),
+                });
+                continue;
+            }
+        };
+
+        let target = target_dir.join(file_name);
+        let outcome = check_source_target(action, src, &target, strategy).and_then(|proceed| {
+            if !proceed {
+                return Ok(false);
+            }
+            let Some(target) = resolve_target(target, strategy)? else {
+                return Ok(false);
+            };
+            if action == "move" {
+                // 同盘直接重命名，跨盘回退为复制后删除
+                if fs::rename(src, &target).is_err() {
+                    copy_recursive(src, &target)?;
+                    if src.is_dir() {
+                        fs::remove_dir_all(src)
+                    } else {
+                        fs::remove_file(src)
+                    }
+                    .map_err(|e| format!("Failed to remove source {}: {}", src.display(), e))?;
+                }
+            } else {
+                copy_recursive(src, &target)?;
+            }
+            Ok(true)
+        });
+
+        match outcome {
+            Ok(true) => result.succeeded.push(path.clone()),
+            Ok(false) => result.skipped.push(path.clone()),
+            Err(error) => result.failed.push(FileActionFailure {
+                path: path.clone(),
+                error,
+            }),
+        }
+    }
+
+    Ok(result)
+}
+
+/// 复制文件/文件夹到目标目录
+pub fn copy_to(
+    paths: &[String],
+    target_dir: &str,
+    strategy: ConflictStrategy,
+    on_progress: &dyn Fn(FileActionProgress),
+) -> Result<FileActionResult, String> {
+    transfer("copy", paths, target_dir, strategy, on_progress)
+}
+
+/// 移动文件/文件夹到目标目录
+pub fn move_to(
+    paths: &[String],
+    target_dir: &str,
+    strategy: ConflictStrategy,
+    on_progress: &dyn Fn(FileActionProgress),
+) -> Result<FileActionResult, String> {
+    transfer("move", paths, target_dir, strategy, on_progress)
+}
+
+struct ZipEntry {
+    name: String,
+    crc: u32,
+    compressed_size: u32,
+    size: u32,
+    method: u16,
+    dos_time: u16,
+    dos_date: u16,
+    offset: u32,
+    is_dir: bool,
+}
+
+fn dos_datetime(path: &Path) -> (u16, u16) {
+    let modified = fs::metadata(path)
+        .and_then(|m| m.modified())
+        .map(DateTime::<Local>::from)
+        .unwrap_or_else(|_| Local::now());
+    let year = modified.year().clamp(1980, 2107) as u16;
+    let time = ((modified.hour() as u16) << 11)
+        | ((modified.minute() as u16) << 5)
+        | (modified.second() as u16 / 2);
+    let date = ((year - 1980) << 9) | ((modified.month() as u16) << 5) | modified.day() as u16;
+    (time, date)
+}
+
+fn to_u32(value: u64, what: &str) -> Result<u32, String> {
+    u32::try_from(value).map_err(|_| format!("{} exceeds the 4GB zip limit", what))
+}
+
+/// 最小化的 zip 写入器：逐个文件流式 deflate，写完后回填 CRC 与大小
+struct ZipWriter {
+    file: BufWriter<File>,
+    entries: Vec<ZipEntry>,
+}
+
+impl ZipWriter {
+    fn create(path: &Path) -> Result<Self, String> {
+        let file = File::create(path)
+            .map_err(|e| format!("Failed to create zip file {}: {}", path.display(), e))?;
+        Ok(Self {
+            file: BufWriter::new(file),
+            entries: Vec::new(),
+        })
+    }
+
+    fn position(&mut self) -> Result<u64, String> {
+        self.file
+            .stream_position()
+            .map_err(|e| format!("Failed to get zip position: {}", e))
+    }
+
+    fn write_local_header(&mut self, entry: &ZipEntry) -> Result<(), String> {
+        let mut header = Vec::with_capacity(30 + entry.name.len());
+        header.extend_from_slice(&0x04034b50u32.to_le_bytes());
+        header.extend_from_slice(&20u16.to_le_bytes());
+        header.extend_from_slice(&0x0800u16.to_le_bytes()); // UTF-8 文件名
+        header.extend_from_slice(&entry.method.to_le_bytes());
+        header.extend_from_slice(&entry.dos_time.to_le_bytes());
+        header.extend_from_slice(&entry.dos_date.to_le_bytes());
+        header.extend_from_slice(&entry.crc.to_le_bytes());
+        header.extend_from_slice(&entry.compressed_size.to_le_bytes());
+        header.extend_from_slice(&entry.size.to_le_bytes());
+        header.extend_from_slice(&(entry.name.len() as u16).to_le_bytes());
+        header.extend_from_slice(&0u16.to_le_bytes());
+        header.extend_from_slice(entry.name.as_bytes());
+        self.file
+            .write_all(&header)
+            .map_err(|e| format!("Failed to write zip header: {}", e))
+    }
+
+    fn add_dir(&mut self, name: String, source: &Path) -> Result<(), String> {
+        let (dos_time, dos_date) = dos_datetime(source);
+        let offset = to_u32(self.position()?, "Archive")?;
+        let entry = ZipEntry {
+            name,
+            crc: 0,
+            compressed_size: 0,
+            size: 0,
+            method: 0,
+            dos_time,
+            dos_date,
+            offset,
+            is_dir: true,
+        };
+        self.write_local_header(&entry)?;
+        self.entries.push(entry);
+        Ok(())
+    }
+
+    fn add_file(&mut self, name: String, source: &Path) -> Result<(), String> {
+        let (dos_time, dos_date) = dos_datetime(source);
+        let offset = self.position()?;
+        let mut entry = ZipEntry {
+            name,
+            crc: 0,
+            compressed_size: 0,
+            size: 0,
+            method: 8,
+            dos_time,
+            dos_date,
+            offset: to_u32(offset, "Archive")?,
+            is_dir: false,
+        };
+        self.write_local_header(&entry)?;
+        let data_start = self.position()?;
+
+        let mut input = File::open(source)
+            .map_err(|e| format!("Failed to open {}: {}", source.display(), e))?;
+        let mut crc = Crc::new();
+        let mut encoder = DeflateEncoder::new(&mut self.file, Compression::default());
+        let mut buffer = vec![0u8; 64 * 1024];
+        loop {
+            let read = input
+                .read(&mut buffer)
+                .map_err(|e| format!("Failed to read {}: {}", source.display(), e))?;
+            if read == 0 {
+                break;
+            }
+            crc.update(&buffer[..read]);
+            encoder
+                .write_all(&buffer[..read])
+                .map_err(|e| format!("Failed to compress {}: {}", source.display(), e))?;
+        }
+        encoder
+            .finish()
+            .map_err(|e| format!("Failed to finish compression: {}", e))?;
+
+        let data_end = self.position()?;
+        entry.crc = crc.sum();
+        entry.size = to_u32(crc.amount() as u64, "File")?;
+        entry.compressed_size = to_u32(data_end - data_start, "Compressed file")?;
+
+        // 回填本地文件头中的 CRC 与大小（偏移 14 字节处）
+        self.file
+            .seek(SeekFrom::Start(offset + 14))
+            .and_then(|_| self.file.write_all(&entry.crc.to_le_bytes()))
+            .and_then(|_| self.file.write_all(&entry.compressed_size.to_le_bytes()))
+            .and_then(|_| self.file.write_all(&entry.size.to_le_bytes()))
+            .and_then(|_| self.file.seek(SeekFrom::Start(data_end)).map(|_| ()))
+            .map_err(|e| format!("Failed to update zip header: {}", e))?;
+
+        self.entries.push(entry);
+        Ok(())
+    }
+
+    fn finish(mut self) -> Result<(), String> {
+        let cd_start = self.position()?;
+        let mut directory = Vec::new();
+        for entry in &self.entries {
+            directory.extend_from_slice(&0x02014b50u32.to_le_bytes());
+            directory.extend_from_slice(&20u16.to_le_bytes());
+            directory.extend_from_slice(&20u16.to_le_bytes());
+            directory.extend_from_slice(&0x0800u16.to_le_bytes());
+            directory.extend_from_slice(&entry.method.to_le_bytes());
+            directory.extend_from_slice(&entry.dos_time.to_le_bytes());
+            directory.extend_from_slice(&entry.dos_date.to_le_bytes());
+            directory.extend_from_slice(&entry.crc.to_le_bytes());
+            directory.extend_from_slice(&entry.compressed_size.to_le_bytes());
+            directory.extend_from_slice(&entry.size.to_le_bytes());
+            directory.extend_from_slice(&(entry.name.len() as u16).to_le_bytes());
+            directory.extend_from_slice(&0u16.to_le_bytes()); // extra
+            directory.extend_from_slice(&0u16.to_le_bytes()); // comment
+            directory.extend_from_slice(&0u16.to_le_bytes()); // disk
+            directory.extend_from_slice(&0u16.to_le_bytes()); // internal attrs
+            let external: u32 = if entry.is_dir { 0x10 } else { 0 };
+            directory.extend_from_slice(&external.to_le_bytes());
+            directory.extend_from_slice(&entry.offset.to_le_bytes());
+            directory.extend_from_slice(entry.name.as_bytes());
+        }
+
+        let count = u16::try_from(self.entries.len())
+            .map_err(|_| "Too many entries for a zip archive".to_string())?;
+        let mut end = Vec::with_capacity(22);
+        end.extend_from_slice(&0x06054b50u32.to_le_bytes());
+        end.extend_from_slice(&0u16.to_le_bytes());
+        end.extend_from_slice(&0u16.to_le_bytes());
+        end.extend_from_slice(&count.to_le_bytes());
+        end.extend_from_slice(&count.to_le_bytes());
+        end.extend_from_slice(&to_u32(directory.len() as u64, "Central directory")?.to_le_bytes());
+        end.extend_from_slice(&to_u32(cd_start, "Archive")?.to_le_bytes());
+        end.extend_from_slice(&0u16.to_le_bytes());
+
+        self.file
+            .write_all(&directory)
+            .and_then(|_| self.file.write_all(&end))
+            .and_then(|_| self.file.flush())
+            .map_err(|e| format!("Failed to write zip directory: {}", e))
+    }
+}
+
+fn collect_zip_entries(path: &Path, name: String, out: &mut Vec<(String, PathBuf, bool)>) -> Result<(), String> {
+    if path.is_dir() {
+        out.push((format!("{}/", name), path.to_path_buf(), true));
+        let entries = fs::read_dir(path)
+            .map_err(|e| format!("Failed to read directory {}: {}", path.display(), e))?;
+        for entry in entries {
+            let entry = entry.map_err(|e| format!("Failed to read directory entry: {}", e))?;
+            let child_name = format!("{}/{}", name, entry.file_name().to_string_lossy());
+            collect_zip_entries(&entry.path(), child_name, out)?;
+        }
+    } else {
+        out.push((name, path.to_path_buf(), false));
+    }
+    Ok(())
+}
+
+/// 在按冲突策略删除已有 zip 之前检查它与待压缩源的关系。
+///
+/// 目标就是某个源时改为重命名，目标是源的上级目录时报错，避免覆盖时先删掉还没读取的源
+fn zip_target_strategy(
+    paths: &[String],
+    zip_path: &Path,
+    strategy: ConflictStrategy,
+) -> Result<ConflictStrategy, String> {
+    if strategy != ConflictStrategy::Overwrite || !zip_path.exists() {
+        return Ok(strategy);
+    }
+    let target = fs::canonicalize(zip_path)
+        .map_err(|e| format!("Failed to resolve target {}: {}", zip_path.display(), e))?;
+    let mut strategy = strategy;
+    for path in paths {
+        let src = fs::canonicalize(path)
+            .map_err(|e| format!("Failed to resolve source {}: {}", path, e))?;
+        if src == target {
+            strategy = ConflictStrategy::Rename;
+        } else if src.starts_with(&target) {
+            return Err("Cannot overwrite a folder that contains the source".to_string());
+        }
+    }
+    Ok(strategy)
+}
+
+/// 把一组文件/文件夹压缩为 zip，返回实际写入的 zip 路径
+pub fn compress_to_zip(
+    paths: &[String],
+    zip_path: &str,
+    strategy: ConflictStrategy,
+    on_progress: &dyn Fn(FileActionProgress),
+) -> Result<Option<String>, String> {
+    if paths.is_empty() {
+        return Err("No files to compress".to_string());
+    }
+    let strategy = zip_target_strategy(paths, Path::new(zip_path), strategy)?;
+    let Some(zip_path) = resolve_target(PathBuf::from(zip_path), strategy)? else {
+        return Ok(None);
+    };
+
+    let mut entries = Vec::new();
+    for path in paths {
+        let src = Path::new(path);
+        let name = src
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .ok_or_else(|| format!("Invalid source path: {}", path))?;
+        collect_zip_entries(src, name, &mut entries)?;
+    }
+
+    let mut writer = ZipWriter::create(&zip_path)?;
+    let total = entries.len();
+    for (index, (name, source, is_dir)) in entries.into_iter().enumerate() {
+        on_progress(FileActionProgress {
+            action: "compress".to_string(),
+            current: index + 1,
+            total,
+            path: source.to_string_lossy().to_string(),
+        });
+        let added = if is_dir {
+            writer.add_dir(name, &source)
+        } else {
+            writer.add_file(name, &source)
+        };
+        if let Err(e) = added {
+            drop(writer);
+            let _ = fs::remove_file(&zip_path);
+            return Err(e);
+        }
+    }
+    writer.finish()?;
+
+    Ok(Some(zip_path.to_string_lossy().to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_overwrite_into_own_folder_keeps_source() {
+        let dir = std::env::temp_dir().join(format!("refast-file-actions-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("folder")).unwrap();
+        let file = dir.join("note.txt");
+        fs::write(&file, "keep me").unwrap();
+        let paths = vec![
+            file.to_string_lossy().to_string(),
+            dir.join("folder").to_string_lossy().to_string(),
+        ];
+        let target = dir.to_string_lossy().to_string();
+
+        let copied = copy_to(&paths, &target, ConflictStrategy::Overwrite, &|_| {}).unwrap();
+        assert_eq!(copied.failed.len(), 2);
+        let moved = move_to(&paths, &target, ConflictStrategy::Overwrite, &|_| {}).unwrap();
+        assert_eq!(moved.skipped.len(), 2);
+        let into_self = move_to(&paths[1..], &paths[1], ConflictStrategy::Overwrite, &|_| {}).unwrap();
+        assert_eq!(into_self.failed.len(), 1);
+
+        assert_eq!(fs::read_to_string(&file).unwrap(), "keep me");
+        assert!(dir.join("folder").is_dir());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_overwrite_zip_named_like_source_keeps_source() {
+        let dir = std::env::temp_dir().join(format!("refast-file-actions-zip-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let archive = dir.join("archive.zip");
+        fs::write(&archive, "keep me").unwrap();
+        let paths = vec![archive.to_string_lossy().to_string()];
+        let target = archive.to_string_lossy().to_string();
+
+        let written = compress_to_zip(&paths, &target, ConflictStrategy::Overwrite, &|_| {})
+            .unwrap()
+            .unwrap();
+        assert_ne!(Path::new(&written), archive.as_path());
+        assert!(Path::new(&written).is_file());
+        assert_eq!(fs::read_to_string(&archive).unwrap(), "keep me");
+
+        let into_parent = dir.to_string_lossy().to_string();
+        assert!(compress_to_zip(&paths, &into_parent, ConflictStrategy::Overwrite, &|_| {}).is_err());
+        assert_eq!(fs::read_to_string(&archive).unwrap(), "keep me");
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,171 @@
+//! 前台窗口激活与恢复
+//!
+//! Windows 的前台锁定会导致 show + set_focus 后输入焦点仍留在原窗口，
+//! 这里通过 AttachThreadInput 与 ALT 键技巧可靠地激活启动器，
+//! 并在启动器隐藏后把焦点还给之前的前台窗口。
+
+#[cfg(target_os = "windows")]
+pub mod windows {
+    use std::sync::atomic::{AtomicIsize, Ordering};
+    use windows_sys::Win32::Foundation::HWND;
+    use windows_sys::Win32::System::Threading::{AttachThreadInput, GetCurrentThreadId};
+    use windows_sys::Win32::UI::Input::KeyboardAndMouse::{keybd_event, KEYEVENTF_KEYUP, VK_MENU};
+    use windows_sys::Win32::UI::WindowsAndMessaging::{
+        BringWindowToTop, GetForegroundWindow, GetWindowThreadProcessId, IsIconic, IsWindow,
+        SetForegroundWindow, ShowWindow, SW_RESTORE,
+    };
+
+    /// 启动器显示前的前台窗口
+    static PREVIOUS_FOREGROUND: AtomicIsize = AtomicIsize::new(0);
+
+    /// 窗口是否属于当前进程
+    fn is_own_window(hwnd: HWND) -> bool {
+        crate::process_info::windows::get_window_process_id(hwnd) == Some(std::process::id())
+    }
+
+    /// 记录当前前台窗口（属于本进程的窗口不记录）
+    pub fn remember_previous() {
+        let hwnd = unsafe { GetForegroundWindow() };
+        if hwnd != 0 && !is_own_window(hwnd) {
+            PREVIOUS_FOREGROUND.store(hwnd, Ordering::SeqCst);
+        }
+    }
+
+    /// 强制将窗口切换到前台，返回是否成功
+    pub fn force_foreground(hwnd: HWND) -> bool {
+        unsafe {
+            if hwnd == 0 || IsWindow(hwnd) == 0 {
+                return false;
+            }
+            if IsIconic(hwnd) != 0 {
+                ShowWindow(hwnd, SW_RESTORE);
+            }
+
+            let foreground = GetForegroundWindow();
+            if foreground == hwnd {
+                return true;
+            }
+
+            // 1. 挂接到当前前台线程的输入队列，绕过前台锁定
+            let current_thread = GetCurrentThreadId();
+            let foreground_thread = if foreground != 0 {
+                GetWindowThreadProcessId(foreground, std::ptr::null_mut())
+            } else {
+                0
+            };
+            let attached = foreground_thread != 0
+                && foreground_thread != current_thread
+                && AttachThreadInput(current_thread, foreground_thread, 1) != 0;
+
+            BringWindowToTop(hwnd);
+            SetForegroundWindow(hwnd);
+
+            if attached {
+                AttachThreadInput(current_thread, foreground_thread, 0);
+            }
+
+            if GetForegroundWindow() == hwnd {
+                return true;
+            }
+
+            // 2. ALT 键技巧：模拟一次按键使本进程获得前台切换权限
+            keybd_event(VK_MENU as u8, 0, 0, 0);
+            keybd_event(VK_MENU as u8, 0, KEYEVENTF_KEYUP, 0);
+            SetForegroundWindow(hwnd);
+
+            GetForegroundWindow() == hwnd
+        }
+    }
+
+    /// 激活 tauri 窗口
+    pub fn activate(window: &tauri::WebviewWindow) -> bool {
+        match window.hwnd() {
+            Ok(hwnd) => force_foreground(hwnd.0 as HWND),
+            Err(e) => {
+                eprintln!("[Foreground] Failed to get window handle: {}", e);
+                false
+            }
+        }
+    }
+
+    /// 前台窗口文本光标下沿的屏幕坐标，没有文本光标时取鼠标位置
+    pub fn caret_or_cursor_position() -> Option<(i32, i32)> {
+        use windows_sys::Win32::Foundation::POINT;
+        use windows_sys::Win32::Graphics::Gdi::ClientToScreen;
+        use windows_sys::Win32::UI::WindowsAndMessaging::{GetCursorPos, GetGUIThreadInfo, GUITHREADINFO};
+
+        unsafe {
+            let foreground = GetForegroundWindow();
+            if foreground != 0 {
+                let thread = GetWindowThreadProcessId(foreground, std::ptr::null_mut());
+                let mut info: GUITHREADINFO = std::mem::zeroed();
+                info.cbSize = std::mem::size_of::<GUITHREADINFO>() as u32;
+                if GetGUIThreadInfo(thread, &mut info) != 0 && info.hwndCaret != 0 {
+                    let mut point = POINT {
+                        x: info.rcCaret.left,
+                        y: info.rcCaret.bottom,
+                    };
+                    if ClientToScreen(info.hwndCaret, &mut point) != 0 {
+                        return Some((point.x, point.y));
+                    }
+                }
+            }
+
+            let mut point = POINT { x: 0, y: 0 };
+            (GetCursorPos(&mut point) != 0).then_some((point.x, point.y))
+        }
+    }
+
+    /// 包含该点（或离它最近）的显示器工作区：(left, top, right, bottom)
+    pub fn work_area_at(x: i32, y: i32) -> Option<(i32, i32, i32, i32)> {
+        use windows_sys::Win32::Foundation::POINT;
+        use windows_sys::Win32::Graphics::Gdi::{
+            GetMonitorInfoW, MonitorFromPoint, MONITORINFO, MONITOR_DEFAULTTONEAREST,
+        };
+
+        unsafe {
+            let monitor = MonitorFromPoint(POINT { x, y }, MONITOR_DEFAULTTONEAREST);
+            let mut info: MONITORINFO = std::mem::zeroed();
+            info.cbSize = std::mem::size_of::<MONITORINFO>() as u32;
+            if monitor == 0 || GetMonitorInfoW(monitor, &mut info) == 0 {
+                return None;
+            }
+            let area = info.rcWork;
+            Some((area.left, area.top, area.right, area.bottom))
+        }
+    }
+
+    /// 恢复启动器显示前的前台窗口
+    pub fn restore_previous() {
+        let previous = PREVIOUS_FOREGROUND.swap(0, Ordering::SeqCst);
+        if previous != 0 {
+            force_foreground(previous);
+        }
+    }
+}
+
+/// 显示窗口并可靠地获取输入焦点，同时记录之前的前台窗口
+pub fn show_and_activate(window: &tauri::WebviewWindow) {
+    #[cfg(target_os = "windows")]
+    windows::remember_previous();
+
+    let _ = window.show();
+    let _ = window.set_focus();
+
+    #[cfg(target_os = "windows")]
+    windows::activate(window);
+}
+
+/// 隐藏窗口并恢复之前的前台窗口
+/// 仅当窗口隐藏前持有焦点时恢复，避免抢走刚启动程序的焦点
+pub fn hide_and_restore(window: &tauri::WebviewWindow) {
+    #[cfg(target_os = "windows")]
+    let was_foreground = window.is_focused().unwrap_or(false);
+
+    let _ = window.hide();
+
+    #[cfg(target_os = "windows")]
+    if was_foreground {
+        windows::restore_previous();
+    }
+}
@@ -0,0 +1,151 @@
+//! 表单填充模板
+//!
+//! 把一组字段（姓名/电话/地址等）保存为模板，填充时依次输入各字段并用 Tab 切换到下一个输入框
+
+use crate::db;
+use rusqlite::{params, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// 每个字段输入后等待目标程序处理的时间
+const FIELD_DELAY: Duration = Duration::from_millis(60);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FormField {
+    pub label: String,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FormTemplate {
+    pub id: String,
+    pub name: String,
+    pub fields: Vec<FormField>,
+    /// 最后一个字段输入后是否按回车提交
+    #[serde(default)]
+    pub submit: bool,
+    pub created_at: u64,
+    pub updated_at: u64,
+}
+
+fn now_ts() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+const TEMPLATE_COLUMNS: &str = "id, name, fields, submit, created_at, updated_at";
+
+fn row_to_template(row: &rusqlite::Row) -> rusqlite::Result<FormTemplate> {
+    let fields_json: String = row.get(2)?;
+    Ok(FormTemplate {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        fields: serde_json::from_str(&fields_json).unwrap_or_default(),
+        submit: row.get::<_, i64>(3)? != 0,
+        created_at: row.get::<_, i64>(4)? as u64,
+        updated_at: row.get::<_, i64>(5)? as u64,
+    })
+}
+
+pub fn get_all_templates(app_data_dir: &Path) -> Result<Vec<FormTemplate>, String> {
+    let conn = db::get_connection(app_data_dir)?;
+    let mut stmt = conn
+        .prepare(&format!("SELECT {} FROM form_templates ORDER BY updated_at DESC", TEMPLATE_COLUMNS))
+        .map_err(|e| format!("Failed to prepare form templates query: {}", e))?;
+    let rows = stmt
+        .query_map([], row_to_template)
+        .map_err(|e| format!("Failed to iterate form templates: {}", e))?;
+
+    let mut items = Vec::new();
+    for row in rows {
+        items.push(row.map_err(|e| format!("Failed to read form template row: {}", e))?);
+    }
+    Ok(items)
+}
+
+pub fn get_template(id: &str, app_data_dir: &Path) -> Result<Option<FormTemplate>, String> {
+    let conn = db::get_connection(app_data_dir)?;
+    conn.query_row(
+        &format!("SELECT {} FROM form_templates WHERE id = ?1", TEMPLATE_COLUMNS),
+        params![id],
+        row_to_template,
+    )
+    .optional()
+    .map_err(|e| format!("Failed to load form template: {}", e))
+}
+
+/// 新建或更新模板（id 为空时新建）
+pub fn save_template(
+    id: Option<String>,
+    name: String,
+    fields: Vec<FormField>,
+    submit: bool,
+    app_data_dir: &Path,
+) -> Result<FormTemplate, String> {
+    if fields.is_empty() {
+        return Err("模板至少需要一个字段".to_string());
+    }
+    let now = now_ts();
+    let created_at = match &id {
+        Some(id) => get_template(id, app_data_dir)?
+            .map(|t| t.created_at)
+            .unwrap_or(now),
+        None => now,
+    };
+    let template = FormTemplate {
+        id: id.unwrap_or_else(|| format!("form-{}-{:04x}", now, rand::random::<u16>())),
+        name,
+        fields,
+        submit,
+        created_at,
+        updated_at: now,
+    };
+
+    let fields_json = serde_json::to_string(&template.fields)
+        .map_err(|e| format!("Failed to serialize form fields: {}", e))?;
+    let conn = db::get_connection(app_data_dir)?;
+    conn.execute(
+        "INSERT OR REPLACE INTO form_templates (id, name, fields, submit, created_at, updated_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        params![
+            template.id,
+            template.name,
+            fields_json,
+            template.submit as i64,
+            template.created_at as i64,
+            template.updated_at as i64
+        ],
+    )
+    .map_err(|e| format!("Failed to save form template: {}", e))?;
+
+    Ok(template)
+}
+
+pub fn delete_template(id: &str, app_data_dir: &Path) -> Result<(), String> {
+    let conn = db::get_connection(app_data_dir)?;
+    conn.execute("DELETE FROM form_templates WHERE id = ?1", params![id])
+        .map_err(|e| format!("Failed to delete form template: {}", e))?;
+    Ok(())
+}
+
+/// 向当前焦点控件依次输入各字段，字段之间按 Tab 切换
+pub fn fill(template: &FormTemplate) -> Result<(), String> {
+    use crate::text_input::windows as input;
+
+    for (index, field) in template.fields.iter().enumerate() {
+        if index > 0 {
+            input::press_key(input::VK_TAB)?;
+            std::thread::sleep(FIELD_DELAY);
+        }
+        input::type_text(&field.value)
+            .map_err(|e| format!("Failed to fill field {}: {}", field.label, e))?;
+        std::thread::sleep(FIELD_DELAY);
+    }
+    if template.submit {
+        input::press_key(input::VK_RETURN)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,65 @@
+//! 基于 QueryPerformanceCounter 的高精度时钟，用于录制时间戳与回放调度
+
+use std::time::Duration;
+
+/// 剩余时间低于该值时改为自旋等待，避开系统调度粒度（约 1~15ms）
+const SPIN_THRESHOLD_US: u64 = 2000;
+
+#[cfg(target_os = "windows")]
+mod windows {
+    use std::sync::OnceLock;
+    use windows_sys::Win32::System::Performance::{
+        QueryPerformanceCounter, QueryPerformanceFrequency,
+    };
+
+    static FREQUENCY: OnceLock<u64> = OnceLock::new();
+
+    fn frequency() -> u64 {
+        *FREQUENCY.get_or_init(|| {
+            let mut freq: i64 = 0;
+            unsafe {
+                QueryPerformanceFrequency(&mut freq);
+            }
+            (freq as u64).max(1)
+        })
+    }
+
+    /// 当前 QPC 计数换算为微秒
+    pub fn now_us() -> u64 {
+        let mut counter: i64 = 0;
+        unsafe {
+            QueryPerformanceCounter(&mut counter);
+        }
+        let counter = counter as u64 as u128;
+        (counter * 1_000_000 / frequency() as u128) as u64
+    }
+}
+
+#[cfg(target_os = "windows")]
+pub use windows::now_us;
+
+/// 单调时钟的微秒读数（非 Windows 平台以进程内 Instant 为基准）
+#[cfg(not(target_os = "windows"))]
+pub fn now_us() -> u64 {
+    use std::sync::OnceLock;
+    use std::time::Instant;
+
+    static ORIGIN: OnceLock<Instant> = OnceLock::new();
+    ORIGIN.get_or_init(Instant::now).elapsed().as_micros() as u64
+}
+
+/// 等待到 now_us() 达到 target_us：先粗粒度 sleep，最后 2ms 内自旋
+pub fn sleep_until_us(target_us: u64) {
+    loop {
+        let now = now_us();
+        if now >= target_us {
+            return;
+        }
+        let remaining = target_us - now;
+        if remaining > SPIN_THRESHOLD_US {
+            std::thread::sleep(Duration::from_micros(remaining - SPIN_THRESHOLD_US));
+        } else {
+            std::thread::yield_now();
+        }
+    }
+}
@@ -0,0 +1,177 @@
+//! 钩子线程守护
+//!
+//! 钩子/热键线程 panic 后自动重启（指数退避），连续失败达到上限时标记为降级，
+//! 通知前端并建议重启应用，避免热键功能静默失效。
+//!
+//! 钩子回调是 `extern "system"` 函数，panic 从中展开会直接终止进程，守护线程无法捕获。
+//! 回调需要用 `catch_callback_panic` 包裹：就地捕获后让线程退出消息循环，再由线程体调用
+//! `resume_callback_panic` 重新抛出，交给 `spawn_supervised` 重启。
+
+use serde::Serialize;
+use std::collections::HashMap;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{LazyLock, Mutex, OnceLock};
+use std::thread;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
+
+/// 连续失败达到该次数后不再重启
+const MAX_CONSECUTIVE_FAILURES: u32 = 5;
+/// 首次重启前的等待时间，之后每次翻倍
+const BASE_BACKOFF: Duration = Duration::from_millis(500);
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+/// 单次运行超过该时长视为已恢复，连续失败计数清零
+const STABLE_RUN: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, Serialize)]
+pub struct HookHealth {
+    pub name: String,
+    pub running: bool,
+    pub restarts: u32,
+    pub consecutive_failures: u32,
+    /// 连续失败过多已停止重启，需要重启应用
+    pub degraded: bool,
+    pub last_error: Option<String>,
+}
+
+static HEALTH: LazyLock<Mutex<HashMap<&'static str, HookHealth>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+static APP_HANDLE: OnceLock<AppHandle> = OnceLock::new();
+
+/// 回调中捕获、尚未交还给线程体的 panic 信息
+static PENDING_PANICS: LazyLock<Mutex<HashMap<&'static str, String>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// 设置用于上报降级状态的 AppHandle（启动时调用）
+pub fn set_app_handle(app: AppHandle) {
+    let _ = APP_HANDLE.set(app);
+}
+
+/// 所有受守护线程的健康状态
+pub fn get_health() -> Vec<HookHealth> {
+    let mut list: Vec<HookHealth> = HEALTH
+        .lock()
+        .map(|h| h.values().cloned().collect())
+        .unwrap_or_default();
+    list.sort_by(|a, b| a.name.cmp(&b.name));
+    list
+}
+
+fn update(name: &'static str, f: impl FnOnce(&mut HookHealth)) -> Option<HookHealth> {
+    let mut health = HEALTH.lock().ok()?;
+    let entry = health.entry(name).or_insert_with(|| HookHealth {
+        name: name.to_string(),
+        running: false,
+        restarts: 0,
+        consecutive_failures: 0,
+        degraded: false,
+        last_error: None,
+    });
+    f(entry);
+    Some(entry.clone())
+}
+
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+fn report_degraded(health: &HookHealth) {
+    eprintln!(
+        "[HookSupervisor] {} degraded after {} consecutive failures",
+        health.name, health.consecutive_failures
+    );
+    if let Some(app) = APP_HANDLE.get() {
+        let _ = app.emit("hook-degraded", health);
+        let _ = crate::toast::show_toast(app, "热键功能已降级", "后台钩子多次异常退出，建议重启 ReFast");
+    }
+}
+
+/// 在钩子/窗口回调中运行 body，panic 时返回 None 并让当前线程退出消息循环
+pub fn catch_callback_panic<R>(name: &'static str, body: impl FnOnce() -> R) -> Option<R> {
+    match panic::catch_unwind(AssertUnwindSafe(body)) {
+        Ok(result) => Some(result),
+        Err(payload) => {
+            let message = panic_message(payload.as_ref());
+            eprintln!("[HookSupervisor] {} callback panicked: {}", name, message);
+            if let Ok(mut pending) = PENDING_PANICS.lock() {
+                pending.insert(name, message);
+            }
+            // 回调运行在安装钩子/创建窗口的线程上，WM_QUIT 会结束该线程的消息循环
+            #[cfg(target_os = "windows")]
+            unsafe {
+                windows_sys::Win32::UI::WindowsAndMessaging::PostQuitMessage(0);
+            }
+            None
+        }
+    }
+}
+
+/// 消息循环结束后调用：回调中发生过 panic 时在线程体内重新抛出，由守护线程按退避重启
+pub fn resume_callback_panic(name: &'static str) {
+    let message = PENDING_PANICS
+        .lock()
+        .ok()
+        .and_then(|mut pending| pending.remove(name));
+    if let Some(message) = message {
+        panic!("{}", message);
+    }
+}
+
+/// 启动受守护的线程：body 正常返回视为主动退出，panic 则按指数退避重启
+pub fn spawn_supervised<F>(name: &'static str, body: F) -> thread::JoinHandle<()>
+where
+    F: Fn() + Send + 'static,
+{
+    thread::spawn(move || {
+        let mut consecutive_failures = 0u32;
+        loop {
+            update(name, |h| h.running = true);
+            let started = Instant::now();
+
+            match panic::catch_unwind(AssertUnwindSafe(&body)) {
+                Ok(()) => {
+                    update(name, |h| h.running = false);
+                    return;
+                }
+                Err(payload) => {
+                    let message = panic_message(payload.as_ref());
+                    if started.elapsed() >= STABLE_RUN {
+                        consecutive_failures = 0;
+                    }
+                    consecutive_failures += 1;
+                    eprintln!(
+                        "[HookSupervisor] {} panicked ({}/{}): {}",
+                        name, consecutive_failures, MAX_CONSECUTIVE_FAILURES, message
+                    );
+
+                    let degraded = consecutive_failures >= MAX_CONSECUTIVE_FAILURES;
+                    let health = update(name, |h| {
+                        h.running = false;
+                        h.consecutive_failures = consecutive_failures;
+                        h.last_error = Some(message);
+                        h.degraded = degraded;
+                    });
+                    if degraded {
+                        if let Some(health) = health {
+                            report_degraded(&health);
+                        }
+                        return;
+                    }
+
+                    let backoff = BASE_BACKOFF
+                        .saturating_mul(1 << (consecutive_failures - 1).min(16))
+                        .min(MAX_BACKOFF);
+                    thread::sleep(backoff);
+                    update(name, |h| h.restarts += 1);
+                }
+            }
+        }
+    })
+}
@@ -15,6 +15,12 @@ pub mod windows {
     static RECORDING_STATE: std::sync::OnceLock<Arc<Mutex<crate::recording::RecordingState>>> =
         std::sync::OnceLock::new();
 
+    /// 记录当前接收输入的前台进程，写入录制元信息
+    fn track_foreground(state: &mut crate::recording::RecordingState) {
+        let hwnd = crate::process_info::windows::get_foreground_window();
+        state.note_foreground(hwnd, || crate::process_info::windows::get_window_process_name(hwnd));
+    }
+
     unsafe extern "system" fn mouse_hook_proc(
         n_code: i32,
         w_param: WPARAM,
@@ -23,9 +29,8 @@ pub mod windows {
         if n_code >= 0 {
             if let Some(state) = RECORDING_STATE.get() {
                 if let Ok(mut state) = state.lock() {
-                    if state.is_recording {
-                        let start = state.start_instant.unwrap();
-                        let time_offset_ms = start.elapsed().as_millis() as u64;
+                    if state.is_capturing() {
+                        let time_offset_us = state.get_time_offset_us().unwrap_or(0);
 
                         // l_param points to MSLLHOOKSTRUCT, extract position from it
                         let mut x = None;
@@ -73,11 +78,16 @@ pub mod windows {
                         };
 
                         if let Some(event_type) = event_type {
+                            if matches!(event_type, EventType::MouseDown { .. }) {
+                                track_foreground(&mut state);
+                            }
                             state.add_event(RecordedEvent {
                                 event_type,
                                 x,
                                 y,
-                                time_offset_ms,
+                                time_offset_ms: time_offset_us / 1000,
+                                time_offset_us,
+                                note: None,
                             });
                         }
                     }
@@ -97,9 +107,8 @@ pub mod windows {
         if n_code >= 0 {
             if let Some(state) = RECORDING_STATE.get() {
                 if let Ok(mut state) = state.lock() {
-                    if state.is_recording {
-                        let start = state.start_instant.unwrap();
-                        let time_offset_ms = start.elapsed().as_millis() as u64;
+                    if state.is_capturing() {
+                        let time_offset_us = state.get_time_offset_us().unwrap_or(0);
 
                         // l_param points to KBDLLHOOKSTRUCT
                         // Extract virtual key code from the structure
@@ -118,11 +127,16 @@ pub mod windows {
                         };
 
                         if let Some(event_type) = event_type {
+                            if matches!(event_type, EventType::KeyDown { .. }) {
+                                track_foreground(&mut state);
+                            }
                             state.add_event(RecordedEvent {
                                 event_type,
                                 x: None,
                                 y: None,
-                                time_offset_ms,
+                                time_offset_ms: time_offset_us / 1000,
+                                time_offset_us,
+                                note: None,
                             });
                         }
                     }
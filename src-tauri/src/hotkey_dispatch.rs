@@ -0,0 +1,56 @@
+//! 热键分发层的重复触发抑制与启动器切换意图
+//!
+//! 快速连按（如两轮双击 Ctrl）会在窗口尚未完成显示时再次触发，按实际可见性切换会
+//! 导致刚显示就被隐藏。这里对同一热键在抑制窗口内的重复触发直接丢弃，并在窗口状态
+//! 尚未稳定时以最近一次用户意图（显示/隐藏）作为切换依据。
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+
+/// 最近一次意图在该时长内视为窗口仍在切换中，以意图代替实际可见性
+const INTENT_SETTLE: Duration = Duration::from_millis(500);
+
+static SUPPRESS_MS: AtomicU64 = AtomicU64::new(300);
+static LAST_TRIGGER: LazyLock<Mutex<HashMap<String, Instant>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+/// 最近一次启动器切换意图（true 为显示）及其时间
+static LAUNCHER_INTENT: Mutex<Option<(bool, Instant)>> = Mutex::new(None);
+
+/// 更新抑制窗口（启动与保存设置时调用），0 表示不抑制
+pub fn set_suppress_ms(ms: u64) {
+    SUPPRESS_MS.store(ms, Ordering::SeqCst);
+}
+
+/// 同一热键距上次被分发不足抑制窗口时返回 false
+pub fn should_dispatch(hotkey_id: &str) -> bool {
+    let window = Duration::from_millis(SUPPRESS_MS.load(Ordering::SeqCst));
+    let now = Instant::now();
+    let Ok(mut last) = LAST_TRIGGER.lock() else {
+        return true;
+    };
+    if let Some(previous) = last.get(hotkey_id) {
+        if now.duration_since(*previous) < window {
+            eprintln!("[HotkeyDispatch] Suppressed repeated trigger of {}", hotkey_id);
+            return false;
+        }
+    }
+    last.insert(hotkey_id.to_string(), now);
+    true
+}
+
+/// 根据最近一次意图与窗口实际可见性决定本次切换是否显示启动器，并记录新意图
+pub fn launcher_toggle_intent(visible: bool) -> bool {
+    let now = Instant::now();
+    let Ok(mut intent) = LAUNCHER_INTENT.lock() else {
+        return !visible;
+    };
+    let current = match *intent {
+        Some((shown, at)) if now.duration_since(at) < INTENT_SETTLE => shown,
+        _ => visible,
+    };
+    let show = !current;
+    *intent = Some((show, now));
+    show
+}
@@ -156,6 +156,8 @@ pub mod windows {
     
     // 自定义消息：更新热键
     const WM_UPDATE_HOTKEY: u32 = windows_sys::Win32::UI::WindowsAndMessaging::WM_APP + 1;
+    // 自定义消息：挂起/恢复键盘钩子（wParam 非零表示挂起），用于隐身模式
+    const WM_SET_HOOKS_SUSPENDED: u32 = windows_sys::Win32::UI::WindowsAndMessaging::WM_APP + 2;
 
     // 存储当前的快捷键配置和窗口句柄
     struct HotkeyState {
@@ -195,8 +197,14 @@ pub mod windows {
         }
     }
 
-    // 键盘钩子回调函数：检测重复修饰键（使用企业微信的实现方式）
+    // 键盘钩子回调函数：panic 不能从 extern "system" 函数展开，捕获后交给守护线程重启
     unsafe extern "system" fn keyboard_hook_proc(nCode: i32, wParam: WPARAM, lParam: LPARAM) -> LRESULT {
+        crate::hook_supervisor::catch_callback_panic("hotkey", || keyboard_hook(nCode, wParam, lParam))
+            .unwrap_or_else(|| CallNextHookEx(0, nCode, wParam, lParam))
+    }
+
+    // 检测重复修饰键（使用企业微信的实现方式）
+    unsafe fn keyboard_hook(nCode: i32, wParam: WPARAM, lParam: LPARAM) -> LRESULT {
         use windows_sys::Win32::UI::WindowsAndMessaging::{PostMessageW, HHOOK, KBDLLHOOKSTRUCT};
         
         // 如果 nCode < 0，必须调用 CallNextHookEx
@@ -211,9 +219,9 @@ pub mod windows {
         let is_keyup = wParam == WM_KEYUP as WPARAM || wParam == WM_SYSKEYUP as WPARAM;
         
         // 获取全局状态
-        let global_state = HOTKEY_STATE.lock().unwrap();
+        let global_state = HOTKEY_STATE.lock().unwrap_or_else(|e| e.into_inner());
         if let Some(state) = global_state.as_ref() {
-            let mut state_guard = state.lock().unwrap();
+            let mut state_guard = state.lock().unwrap_or_else(|e| e.into_inner());
             
             // 检查是否是重复修饰键模式
             if state_guard.is_double_modifier {
@@ -486,11 +494,14 @@ pub mod windows {
 
         // 保存到全局状态
         {
-            let mut global_state = HOTKEY_STATE.lock().unwrap();
+            let mut global_state = HOTKEY_STATE.lock().unwrap_or_else(|e| e.into_inner());
             *global_state = Some(state.clone());
         }
 
-        let handle = thread::spawn(move || {
+        // 线程 panic 后由守护线程按指数退避重启
+        let handle = crate::hook_supervisor::spawn_supervised("hotkey", move || {
+            // 上次异常退出时已清除全局状态，重启时重新登记
+            *HOTKEY_STATE.lock().unwrap_or_else(|e| e.into_inner()) = Some(state.clone());
             unsafe {
                 use std::ffi::OsStr;
                 use std::os::windows::ffi::OsStrExt;
@@ -504,6 +515,8 @@ pub mod windows {
                     .encode_wide()
                     .chain(Some(0))
                     .collect();
+                // 清理上次异常退出遗留的窗口类
+                let _ = UnregisterClassW(class_name.as_ptr(), 0);
 
                 let wc = WNDCLASSW {
                     style: 0,
@@ -524,10 +537,17 @@ pub mod windows {
                     return;
                 }
 
+                // 之后无论正常退出、提前返回还是 panic 展开，都由它释放钩子、窗口与 sender
+                let mut resources = HotkeyThreadResources {
+                    state: state.clone(),
+                    class_name,
+                    hwnd: 0,
+                };
+
                 // Create a hidden window
                 let hwnd = CreateWindowExW(
                     0,
-                    class_name.as_ptr(),
+                    resources.class_name.as_ptr(),
                     std::ptr::null(),
                     WS_OVERLAPPED,
                     CW_USEDEFAULT,
@@ -542,18 +562,18 @@ pub mod windows {
 
                 if hwnd == 0 {
                     eprintln!("Failed to create hotkey window");
-                    let _ = UnregisterClassW(class_name.as_ptr(), 0);
                     return;
                 }
+                resources.hwnd = hwnd;
 
                 // 更新状态中的 hwnd
                 {
-                    let mut state_guard = state.lock().unwrap();
+                    let mut state_guard = state.lock().unwrap_or_else(|e| e.into_inner());
                     state_guard.hwnd = Some(hwnd);
                 }
 
                 // Store sender in window user data
-                let sender_ptr = Box::into_raw(Box::new(sender));
+                let sender_ptr = Box::into_raw(Box::new(sender.clone()));
                 windows_sys::Win32::UI::WindowsAndMessaging::SetWindowLongPtrW(
                     hwnd,
                     windows_sys::Win32::UI::WindowsAndMessaging::GWLP_USERDATA,
@@ -561,14 +581,14 @@ pub mod windows {
                 );
 
                 // Register hotkey or install keyboard hook
-                let state_clone = state.clone();
                 let (mods, vk_code, is_double) = {
-                    let state_guard = state_clone.lock().unwrap();
+                    let state_guard = state.lock().unwrap_or_else(|e| e.into_inner());
                     (state_guard.modifiers, state_guard.vk, state_guard.is_double_modifier)
                 };
 
                 // 对于重复修饰键（如双击 Alt），使用键盘钩子而不是 RegisterHotKey
-                if is_double {
+                // 隐身模式下暂不安装，退出隐身模式时由 WM_SET_HOOKS_SUSPENDED 重新安装
+                if is_double && !crate::stealth::is_enabled() {
                     log_hotkey!("[Hotkey] Initial setup: Double modifier hotkey detected (modifiers={:x}, vk={:x}), using keyboard hook", mods, vk_code);
                     
                     // 安装键盘钩子
@@ -583,37 +603,19 @@ pub mod windows {
                     use windows_sys::Win32::UI::WindowsAndMessaging::HHOOK;
                     if hook == HHOOK::default() {
                         log_hotkey!("[Hotkey] Error: Failed to install keyboard hook during initialization");
-                        // Free the sender pointer before cleanup
-                        let sender_ptr = windows_sys::Win32::UI::WindowsAndMessaging::GetWindowLongPtrW(
-                            hwnd,
-                            windows_sys::Win32::UI::WindowsAndMessaging::GWLP_USERDATA,
-                        ) as *mut mpsc::Sender<()>;
-                        if !sender_ptr.is_null() {
-                            let _ = Box::from_raw(sender_ptr);
-                        }
-                        let _ = UnregisterClassW(class_name.as_ptr(), 0);
                         return;
                     }
                     
                     // 保存钩子句柄
-                    let mut state_guard = state.lock().unwrap();
+                    let mut state_guard = state.lock().unwrap_or_else(|e| e.into_inner());
                     state_guard.hook = Some(hook);
                     log_hotkey!("[Hotkey] Initial setup: Keyboard hook installed successfully, hook={:?}, hwnd={:?}", hook, hwnd);
-                } else {
+                } else if !is_double {
                     // 对于非重复修饰键，使用 RegisterHotKey
                     let result = RegisterHotKey(hwnd, HOTKEY_ID, mods, vk_code);
 
                     if result == 0 {
                         eprintln!("Failed to register global hotkey");
-                        // Free the sender pointer before cleanup
-                        let sender_ptr = windows_sys::Win32::UI::WindowsAndMessaging::GetWindowLongPtrW(
-                            hwnd,
-                            windows_sys::Win32::UI::WindowsAndMessaging::GWLP_USERDATA,
-                        ) as *mut mpsc::Sender<()>;
-                        if !sender_ptr.is_null() {
-                            let _ = Box::from_raw(sender_ptr);
-                        }
-                        let _ = UnregisterClassW(class_name.as_ptr(), 0);
                         return;
                     }
                     
@@ -649,42 +651,68 @@ pub mod windows {
                     DispatchMessageW(&msg);
                 }
 
-                // Cleanup
-                // 卸载键盘钩子（如果存在）
+                // 回调中发生的 panic 在这里重新抛出，resources 在展开时完成清理
+                crate::hook_supervisor::resume_callback_panic("hotkey");
+            }
+        });
+
+        Ok(handle)
+    }
+
+    /// 热键线程持有的钩子、窗口与 sender，线程体结束（包括 panic 展开）时统一释放，
+    /// 守护线程重启时不会叠加旧钩子或泄漏窗口
+    struct HotkeyThreadResources {
+        state: Arc<Mutex<HotkeyState>>,
+        class_name: Vec<u16>,
+        hwnd: HWND,
+    }
+
+    impl Drop for HotkeyThreadResources {
+        fn drop(&mut self) {
+            unsafe {
+                use windows_sys::Win32::UI::WindowsAndMessaging::UnregisterClassW;
+
                 {
-                    let global_state = HOTKEY_STATE.lock().unwrap();
-                    if let Some(state) = global_state.as_ref() {
-                        let mut state_guard = state.lock().unwrap();
-                        if let Some(hook) = state_guard.hook {
-                            UnhookWindowsHookEx(hook);
-                            state_guard.hook = None;
-                        }
+                    let mut state_guard = self.state.lock().unwrap_or_else(|e| e.into_inner());
+                    if let Some(hook) = state_guard.hook.take() {
+                        UnhookWindowsHookEx(hook);
                     }
+                    state_guard.hwnd = None;
+                    state_guard.last_keyup_time = None;
+                    state_guard.waiting_for_second = false;
+                    state_guard.other_key_pressed = false;
                 }
-                
-                // 取消注册热键（如果已注册）
-                let _ = UnregisterHotKey(hwnd, HOTKEY_ID);
 
-                // Free the sender pointer
-                let sender_ptr = windows_sys::Win32::UI::WindowsAndMessaging::GetWindowLongPtrW(
-                    hwnd,
-                    windows_sys::Win32::UI::WindowsAndMessaging::GWLP_USERDATA,
-                ) as *mut mpsc::Sender<()>;
-                if !sender_ptr.is_null() {
-                    let _ = Box::from_raw(sender_ptr);
+                if self.hwnd != 0 {
+                    let _ = UnregisterHotKey(self.hwnd, HOTKEY_ID);
+                    free_window_sender::<mpsc::Sender<()>>(self.hwnd);
+                    destroy_thread_window(self.hwnd);
                 }
 
                 // 清除全局状态
-                {
-                    let mut global_state = HOTKEY_STATE.lock().unwrap();
-                    *global_state = None;
-                }
+                *HOTKEY_STATE.lock().unwrap_or_else(|e| e.into_inner()) = None;
 
-                let _ = UnregisterClassW(class_name.as_ptr(), 0);
+                let _ = UnregisterClassW(self.class_name.as_ptr(), 0);
             }
-        });
+        }
+    }
 
-        Ok(handle)
+    /// 取出保存在窗口 GWLP_USERDATA 中的 sender 并释放
+    unsafe fn free_window_sender<T>(hwnd: HWND) {
+        use windows_sys::Win32::UI::WindowsAndMessaging::{SetWindowLongPtrW, GWLP_USERDATA};
+        let sender_ptr = SetWindowLongPtrW(hwnd, GWLP_USERDATA, 0) as *mut T;
+        if !sender_ptr.is_null() {
+            drop(Box::from_raw(sender_ptr));
+        }
+    }
+
+    /// 销毁线程的隐藏窗口，并丢弃 WM_DESTROY 投递的 WM_QUIT 等残留消息，
+    /// 避免守护线程在同一线程上重启后消息循环立即退出
+    unsafe fn destroy_thread_window(hwnd: HWND) {
+        use windows_sys::Win32::UI::WindowsAndMessaging::{DestroyWindow, PeekMessageW, PM_REMOVE};
+        DestroyWindow(hwnd);
+        let mut msg: MSG = std::mem::zeroed();
+        while PeekMessageW(&mut msg, 0, 0, 0, PM_REMOVE) != 0 {}
     }
 
     // 更新快捷键配置
@@ -698,9 +726,9 @@ pub mod windows {
         const MAX_RETRIES: u32 = 40; // 40 * 50ms = 2秒
         
         loop {
-            let global_state = HOTKEY_STATE.lock().unwrap();
+            let global_state = HOTKEY_STATE.lock().unwrap_or_else(|e| e.into_inner());
             if let Some(state) = global_state.as_ref() {
-                let state_guard = state.lock().unwrap();
+                let state_guard = state.lock().unwrap_or_else(|e| e.into_inner());
                 
                 // 如果 hwnd 还没有设置，等待并重试
                 if state_guard.hwnd.is_none() {
@@ -729,7 +757,7 @@ pub mod windows {
                 // 更新配置（在发送消息前更新，窗口线程会读取）
                 drop(state_guard);
                 {
-                    let mut state_guard = state.lock().unwrap();
+                    let mut state_guard = state.lock().unwrap_or_else(|e| e.into_inner());
                     state_guard.modifiers = modifiers;
                     state_guard.vk = vk;
                     state_guard.is_double_modifier = is_double;
@@ -772,6 +800,16 @@ pub mod windows {
         msg: u32,
         wparam: WPARAM,
         lparam: LPARAM,
+    ) -> LRESULT {
+        crate::hook_supervisor::catch_callback_panic("hotkey", || hotkey_wnd_message(hwnd, msg, wparam, lparam))
+            .unwrap_or(0)
+    }
+
+    unsafe fn hotkey_wnd_message(
+        hwnd: HWND,
+        msg: u32,
+        wparam: WPARAM,
+        lparam: LPARAM,
     ) -> LRESULT {
         use windows_sys::Win32::UI::WindowsAndMessaging::{
             DefWindowProcW, PostQuitMessage, WM_DESTROY, WM_HOTKEY,
@@ -779,6 +817,37 @@ pub mod windows {
         use windows_sys::Win32::Foundation::GetLastError;
 
         match msg {
+            WM_SET_HOOKS_SUSPENDED => {
+                // 钩子必须在拥有消息循环的线程中安装/卸载
+                let suspended = wparam != 0;
+                let global_state = HOTKEY_STATE.lock().unwrap_or_else(|e| e.into_inner());
+                if let Some(state) = global_state.as_ref() {
+                    let mut state_guard = state.lock().unwrap_or_else(|e| e.into_inner());
+                    if suspended {
+                        if let Some(hook) = state_guard.hook.take() {
+                            UnhookWindowsHookEx(hook);
+                        }
+                        state_guard.last_keyup_time = None;
+                        state_guard.waiting_for_second = false;
+                        state_guard.other_key_pressed = false;
+                    } else if state_guard.is_double_modifier && state_guard.hook.is_none() {
+                        use windows_sys::Win32::Foundation::HINSTANCE;
+                        use windows_sys::Win32::UI::WindowsAndMessaging::HHOOK;
+                        let hook = SetWindowsHookExW(
+                            WH_KEYBOARD_LL,
+                            keyboard_hook_proc,
+                            HINSTANCE::default(),
+                            0,
+                        );
+                        if hook != HHOOK::default() {
+                            state_guard.hook = Some(hook);
+                        } else {
+                            eprintln!("[Hotkey] Failed to reinstall keyboard hook after resume");
+                        }
+                    }
+                }
+                0
+            }
             WM_UPDATE_HOTKEY => {
                 // 在窗口线程中执行热键更新操作
                 // wParam: modifiers | (is_double << 16), lParam: vk
@@ -800,9 +869,9 @@ pub mod windows {
                 
                 // 更新全局状态
                 {
-                    let global_state = HOTKEY_STATE.lock().unwrap();
+                    let global_state = HOTKEY_STATE.lock().unwrap_or_else(|e| e.into_inner());
                     if let Some(state) = global_state.as_ref() {
-                        let mut state_guard = state.lock().unwrap();
+                        let mut state_guard = state.lock().unwrap_or_else(|e| e.into_inner());
                         state_guard.modifiers = modifiers;
                         state_guard.vk = vk;
                         state_guard.is_double_modifier = is_double;
@@ -815,9 +884,9 @@ pub mod windows {
                     
                     // 先卸载旧的钩子（如果存在）
                     {
-                        let global_state = HOTKEY_STATE.lock().unwrap();
+                        let global_state = HOTKEY_STATE.lock().unwrap_or_else(|e| e.into_inner());
                         if let Some(state) = global_state.as_ref() {
-                            let mut state_guard = state.lock().unwrap();
+                            let mut state_guard = state.lock().unwrap_or_else(|e| e.into_inner());
                             if let Some(old_hook) = state_guard.hook {
                                 UnhookWindowsHookEx(old_hook);
                                 state_guard.hook = None;
@@ -844,9 +913,9 @@ pub mod windows {
                     }
                     
                     // 保存钩子句柄和窗口句柄
-                    let global_state = HOTKEY_STATE.lock().unwrap();
+                    let global_state = HOTKEY_STATE.lock().unwrap_or_else(|e| e.into_inner());
                     if let Some(state) = global_state.as_ref() {
-                        let mut state_guard = state.lock().unwrap();
+                        let mut state_guard = state.lock().unwrap_or_else(|e| e.into_inner());
                         state_guard.hook = Some(hook);
                         state_guard.hwnd = Some(hwnd); // 确保 hwnd 已设置
                         log_hotkey!("[Hotkey] Window thread: Keyboard hook installed successfully, hook={:?}, hwnd={:?}, modifiers={:x}, vk={:x}, is_double_modifier={}", 
@@ -858,9 +927,9 @@ pub mod windows {
                 
                 // 对于非重复修饰键，先卸载钩子（如果存在）
                 {
-                    let global_state = HOTKEY_STATE.lock().unwrap();
+                    let global_state = HOTKEY_STATE.lock().unwrap_or_else(|e| e.into_inner());
                     if let Some(state) = global_state.as_ref() {
-                        let mut state_guard = state.lock().unwrap();
+                        let mut state_guard = state.lock().unwrap_or_else(|e| e.into_inner());
                         if let Some(old_hook) = state_guard.hook {
                             UnhookWindowsHookEx(old_hook);
                             state_guard.hook = None;
@@ -909,9 +978,9 @@ pub mod windows {
             WM_DESTROY => {
                 // 卸载键盘钩子（如果存在）
                 {
-                    let global_state = HOTKEY_STATE.lock().unwrap();
+                    let global_state = HOTKEY_STATE.lock().unwrap_or_else(|e| e.into_inner());
                     if let Some(state) = global_state.as_ref() {
-                        let mut state_guard = state.lock().unwrap();
+                        let mut state_guard = state.lock().unwrap_or_else(|e| e.into_inner());
                         if let Some(hook) = state_guard.hook {
                             UnhookWindowsHookEx(hook);
                             state_guard.hook = None;
@@ -955,19 +1024,25 @@ pub mod windows {
     /// 设置启动器窗口的 HWND
     pub fn set_launcher_hwnd(hwnd: HWND) {
         let manager = MULTI_HOTKEY_MANAGER.clone();
-        let mut launcher_hwnd_guard = manager.launcher_hwnd.lock().unwrap();
+        let mut launcher_hwnd_guard = manager.launcher_hwnd.lock().unwrap_or_else(|e| e.into_inner());
         *launcher_hwnd_guard = Some(hwnd);
     }
     
     /// 设置全局 sender（在启动监听器时调用）
     pub fn set_global_sender(sender: mpsc::Sender<String>) {
         let manager = MULTI_HOTKEY_MANAGER.clone();
-        let mut sender_guard = manager.sender.lock().unwrap();
+        let mut sender_guard = manager.sender.lock().unwrap_or_else(|e| e.into_inner());
         *sender_guard = Some(sender);
     }
     
-    // 全局键盘钩子回调 - 检查所有已注册的快捷键
+    // 全局键盘钩子回调，panic 时放行按键并交给守护线程重启
     unsafe extern "system" fn global_keyboard_hook_proc(nCode: i32, wParam: WPARAM, lParam: LPARAM) -> LRESULT {
+        crate::hook_supervisor::catch_callback_panic("multi_hotkey", || global_keyboard_hook(nCode, wParam, lParam))
+            .unwrap_or_else(|| CallNextHookEx(0, nCode, wParam, lParam))
+    }
+
+    // 检查所有已注册的快捷键
+    unsafe fn global_keyboard_hook(nCode: i32, wParam: WPARAM, lParam: LPARAM) -> LRESULT {
         use windows_sys::Win32::UI::WindowsAndMessaging::KBDLLHOOKSTRUCT;
         use windows_sys::Win32::UI::WindowsAndMessaging::GetForegroundWindow;
         
@@ -985,7 +1060,7 @@ pub mod windows {
         // 检查当前活动窗口是否是启动器窗口
         // 如果是启动器窗口，允许事件正常传播，不拦截插件快捷键
         let manager = MULTI_HOTKEY_MANAGER.clone();
-        let launcher_hwnd_guard = manager.launcher_hwnd.lock().unwrap();
+        let launcher_hwnd_guard = manager.launcher_hwnd.lock().unwrap_or_else(|e| e.into_inner());
         if let Some(launcher_hwnd) = *launcher_hwnd_guard {
             if launcher_hwnd != 0 {
                 let foreground_hwnd = GetForegroundWindow();
@@ -1021,6 +1096,14 @@ pub mod windows {
                 modifiers.push("Meta".to_string());
             }
         }
+
+        // 连续粘贴：检测到 Ctrl+V 时切换到下一条（不拦截按键，由目标程序正常粘贴）
+        if vk_code == 0x56
+            && modifiers.iter().any(|m| m == "Ctrl")
+            && !modifiers.iter().any(|m| m == "Alt" || m == "Meta")
+        {
+            crate::clipboard::on_paste_detected();
+        }
         
         // 转换虚拟键码为键名
         let key_name_opt: Option<String> = match vk_code {
@@ -1064,10 +1147,10 @@ pub mod windows {
         
         // 检查是否匹配任何已注册的快捷键
         let manager = MULTI_HOTKEY_MANAGER.clone();
-        let hotkeys_guard = manager.hotkeys.lock().unwrap();
-        let sender_guard = manager.sender.lock().unwrap();
-        let mut last_triggered_guard = manager.last_triggered.lock().unwrap();
-        let mut last_hotkey_triggered_guard = manager.last_hotkey_triggered.lock().unwrap();
+        let hotkeys_guard = manager.hotkeys.lock().unwrap_or_else(|e| e.into_inner());
+        let sender_guard = manager.sender.lock().unwrap_or_else(|e| e.into_inner());
+        let mut last_triggered_guard = manager.last_triggered.lock().unwrap_or_else(|e| e.into_inner());
+        let mut last_hotkey_triggered_guard = manager.last_hotkey_triggered.lock().unwrap_or_else(|e| e.into_inner());
         
         // 构建当前快捷键的唯一标识（用于防抖）
         let mut pressed_modifiers_sorted = modifiers.clone();
@@ -1151,7 +1234,8 @@ pub mod windows {
         
         let manager = MULTI_HOTKEY_MANAGER.clone();
         
-        let handle = thread::spawn(move || {
+        // 线程 panic 后由守护线程按指数退避重启
+        let handle = crate::hook_supervisor::spawn_supervised("multi_hotkey", move || {
             unsafe {
                 use std::ffi::OsStr;
                 use std::os::windows::ffi::OsStrExt;
@@ -1166,6 +1250,8 @@ pub mod windows {
                     .encode_wide()
                     .chain(Some(0))
                     .collect();
+                // 清理上次异常退出遗留的窗口类
+                let _ = UnregisterClassW(class_name.as_ptr(), 0);
                 
                 let wc = WNDCLASSW {
                     style: 0,
@@ -1185,11 +1271,18 @@ pub mod windows {
                     eprintln!("[MultiHotkey] Failed to register window class");
                     return;
                 }
+
+                // 之后无论正常退出、提前返回还是 panic 展开，都由它卸载钩子并销毁窗口
+                let mut resources = MultiHotkeyThreadResources {
+                    manager: manager.clone(),
+                    class_name,
+                    hwnd: 0,
+                };
                 
                 // 创建隐藏窗口
                 let hwnd = CreateWindowExW(
                     0,
-                    class_name.as_ptr(),
+                    resources.class_name.as_ptr(),
                     std::ptr::null(),
                     WS_OVERLAPPED,
                     CW_USEDEFAULT,
@@ -1204,33 +1297,32 @@ pub mod windows {
                 
                 if hwnd == 0 {
                     eprintln!("[MultiHotkey] Failed to create hotkey window");
-                    let _ = UnregisterClassW(class_name.as_ptr(), 0);
                     return;
                 }
+                resources.hwnd = hwnd;
                 
                 // 保存窗口句柄
                 {
-                    let mut hwnd_guard = manager.hwnd.lock().unwrap();
+                    let mut hwnd_guard = manager.hwnd.lock().unwrap_or_else(|e| e.into_inner());
                     *hwnd_guard = Some(hwnd);
                 }
                 
-                // 安装全局键盘钩子
-                let hook = SetWindowsHookExW(
-                    WH_KEYBOARD_LL,
-                    global_keyboard_hook_proc,
-                    HINSTANCE::default(),
-                    0,
-                );
-                
-                if hook == windows_sys::Win32::UI::WindowsAndMessaging::HHOOK::default() {
-                    eprintln!("[MultiHotkey] Failed to install keyboard hook");
-                    let _ = UnregisterClassW(class_name.as_ptr(), 0);
-                    return;
-                }
-                
-                // 保存钩子句柄
-                {
-                    let mut hook_guard = manager.hook.lock().unwrap();
+                // 安装全局键盘钩子；隐身模式下暂不安装，退出隐身模式时由 WM_SET_HOOKS_SUSPENDED 重新安装
+                if !crate::stealth::is_enabled() {
+                    let hook = SetWindowsHookExW(
+                        WH_KEYBOARD_LL,
+                        global_keyboard_hook_proc,
+                        HINSTANCE::default(),
+                        0,
+                    );
+                    
+                    if hook == windows_sys::Win32::UI::WindowsAndMessaging::HHOOK::default() {
+                        eprintln!("[MultiHotkey] Failed to install keyboard hook");
+                        return;
+                    }
+                    
+                    // 保存钩子句柄
+                    let mut hook_guard = manager.hook.lock().unwrap_or_else(|e| e.into_inner());
                     *hook_guard = Some(hook);
                 }
                 
@@ -1259,22 +1351,38 @@ pub mod windows {
                     TranslateMessage(&msg);
                     DispatchMessageW(&msg);
                 }
-                
-                // 清理
-                {
-                    let mut hook_guard = manager.hook.lock().unwrap();
-                    if let Some(h) = *hook_guard {
-                        UnhookWindowsHookEx(h);
-                        *hook_guard = None;
-                    }
-                }
-                
-                let _ = UnregisterClassW(class_name.as_ptr(), 0);
+
+                // 回调中发生的 panic 在这里重新抛出，resources 在展开时完成清理
+                crate::hook_supervisor::resume_callback_panic("multi_hotkey");
             }
         });
         
         Ok(handle)
     }
+
+    /// 插件快捷键线程持有的钩子与窗口，线程体结束（包括 panic 展开）时统一释放
+    struct MultiHotkeyThreadResources {
+        manager: Arc<MultiHotkeyManager>,
+        class_name: Vec<u16>,
+        hwnd: HWND,
+    }
+
+    impl Drop for MultiHotkeyThreadResources {
+        fn drop(&mut self) {
+            unsafe {
+                use windows_sys::Win32::UI::WindowsAndMessaging::UnregisterClassW;
+
+                if let Some(hook) = self.manager.hook.lock().unwrap_or_else(|e| e.into_inner()).take() {
+                    UnhookWindowsHookEx(hook);
+                }
+                if self.hwnd != 0 {
+                    *self.manager.hwnd.lock().unwrap_or_else(|e| e.into_inner()) = None;
+                    destroy_thread_window(self.hwnd);
+                }
+                let _ = UnregisterClassW(self.class_name.as_ptr(), 0);
+            }
+        }
+    }
     
     /// 注册插件快捷键
     pub fn register_plugin_hotkey(
@@ -1282,7 +1390,7 @@ pub mod windows {
         config: crate::settings::HotkeyConfig,
     ) -> Result<(), String> {
         let manager = MULTI_HOTKEY_MANAGER.clone();
-        let mut hotkeys_guard = manager.hotkeys.lock().unwrap();
+        let mut hotkeys_guard = manager.hotkeys.lock().unwrap_or_else(|e| e.into_inner());
         hotkeys_guard.insert(plugin_id.clone(), config);
         Ok(())
     }
@@ -1290,7 +1398,7 @@ pub mod windows {
     /// 取消注册插件快捷键
     pub fn unregister_plugin_hotkey(plugin_id: &str) -> Result<(), String> {
         let manager = MULTI_HOTKEY_MANAGER.clone();
-        let mut hotkeys_guard = manager.hotkeys.lock().unwrap();
+        let mut hotkeys_guard = manager.hotkeys.lock().unwrap_or_else(|e| e.into_inner());
         hotkeys_guard.remove(plugin_id);
         Ok(())
     }
@@ -1301,7 +1409,7 @@ pub mod windows {
         
         // 卸载键盘钩子
         {
-            let mut hook_guard = manager.hook.lock().unwrap();
+            let mut hook_guard = manager.hook.lock().unwrap_or_else(|e| e.into_inner());
             if let Some(h) = *hook_guard {
                 unsafe {
                     use windows_sys::Win32::UI::WindowsAndMessaging::UnhookWindowsHookEx;
@@ -1314,19 +1422,43 @@ pub mod windows {
         
         // 清理窗口句柄
         {
-            let mut hwnd_guard = manager.hwnd.lock().unwrap();
+            let mut hwnd_guard = manager.hwnd.lock().unwrap_or_else(|e| e.into_inner());
             *hwnd_guard = None;
         }
         
         // 清空快捷键注册
         {
-            let mut hotkeys_guard = manager.hotkeys.lock().unwrap();
+            let mut hotkeys_guard = manager.hotkeys.lock().unwrap_or_else(|e| e.into_inner());
             hotkeys_guard.clear();
         }
         
         eprintln!("[MultiHotkey] All hotkeys cleaned up");
     }
     
+    /// 挂起或恢复所有低层键盘钩子（隐身模式）
+    /// 通过 PostMessage 让各自的窗口线程执行卸载/重装，满足钩子的线程亲和性要求
+    pub fn set_hooks_suspended(suspended: bool) -> Result<(), String> {
+        use windows_sys::Win32::UI::WindowsAndMessaging::PostMessageW;
+        let wparam: WPARAM = if suspended { 1 } else { 0 };
+
+        let launcher_hwnd = {
+            let global_state = HOTKEY_STATE.lock().unwrap_or_else(|e| e.into_inner());
+            global_state
+                .as_ref()
+                .and_then(|state| state.lock().unwrap_or_else(|e| e.into_inner()).hwnd)
+        };
+        let multi_hwnd = *MULTI_HOTKEY_MANAGER.hwnd.lock().unwrap_or_else(|e| e.into_inner());
+
+        for hwnd in [launcher_hwnd, multi_hwnd].into_iter().flatten() {
+            unsafe {
+                if PostMessageW(hwnd, WM_SET_HOOKS_SUSPENDED, wparam, 0) == 0 {
+                    return Err("发送钩子挂起消息失败".to_string());
+                }
+            }
+        }
+        Ok(())
+    }
+    
     /// 更新所有插件快捷键
     pub fn update_plugin_hotkeys(
         hotkeys: std::collections::HashMap<String, crate::settings::HotkeyConfig>,
@@ -1362,7 +1494,7 @@ pub mod windows {
         }
         
         let manager = MULTI_HOTKEY_MANAGER.clone();
-        let mut hotkeys_guard = manager.hotkeys.lock().unwrap();
+        let mut hotkeys_guard = manager.hotkeys.lock().unwrap_or_else(|e| e.into_inner());
         hotkeys_guard.clear();
         
         for (plugin_id, config) in hotkeys {
@@ -1373,6 +1505,16 @@ pub mod windows {
     }
     
     unsafe extern "system" fn multi_hotkey_wnd_proc(
+        hwnd: HWND,
+        msg: u32,
+        wparam: WPARAM,
+        lparam: LPARAM,
+    ) -> LRESULT {
+        crate::hook_supervisor::catch_callback_panic("multi_hotkey", || multi_hotkey_wnd_message(hwnd, msg, wparam, lparam))
+            .unwrap_or(0)
+    }
+
+    unsafe fn multi_hotkey_wnd_message(
         _hwnd: HWND,
         msg: u32,
         _wparam: WPARAM,
@@ -1381,6 +1523,29 @@ pub mod windows {
         use windows_sys::Win32::UI::WindowsAndMessaging::{DefWindowProcW, PostQuitMessage, WM_DESTROY};
         
         match msg {
+            WM_SET_HOOKS_SUSPENDED => {
+                let manager = MULTI_HOTKEY_MANAGER.clone();
+                let mut hook_guard = manager.hook.lock().unwrap_or_else(|e| e.into_inner());
+                if _wparam != 0 {
+                    if let Some(h) = hook_guard.take() {
+                        UnhookWindowsHookEx(h);
+                    }
+                } else if hook_guard.is_none() {
+                    use windows_sys::Win32::Foundation::HINSTANCE;
+                    let hook = SetWindowsHookExW(
+                        WH_KEYBOARD_LL,
+                        global_keyboard_hook_proc,
+                        HINSTANCE::default(),
+                        0,
+                    );
+                    if hook != windows_sys::Win32::UI::WindowsAndMessaging::HHOOK::default() {
+                        *hook_guard = Some(hook);
+                    } else {
+                        eprintln!("[MultiHotkey] Failed to reinstall keyboard hook after resume");
+                    }
+                }
+                0
+            }
             WM_DESTROY => {
                 PostQuitMessage(0);
                 0
@@ -1437,4 +1602,8 @@ pub mod windows {
     pub fn cleanup_hotkeys() {
         // No-op on non-Windows platforms
     }
+    
+    pub fn set_hooks_suspended(_suspended: bool) -> Result<(), String> {
+        Ok(())
+    }
 }
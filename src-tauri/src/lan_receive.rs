@@ -0,0 +1,341 @@
+//! 局域网剪贴板接收
+//!
+//! 手动开启后在局域网端口上提供一个简单网页，手机扫码打开后输入或选择文本文件，
+//! 提交的文本直接写入电脑剪贴板并记入历史。每次开启生成新的随机令牌，
+//! 只有带正确令牌的请求会被接受。
+
+use crate::clipboard::{self, ClipboardSource, RichFormats};
+use crate::qrcode::QrCode;
+use serde::Serialize;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{IpAddr, SocketAddr, TcpListener, TcpStream, UdpSocket};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+
+/// 单次提交的文本上限
+const MAX_BODY_BYTES: usize = 1024 * 1024;
+/// 请求头上限
+const MAX_HEADER_BYTES: usize = 16 * 1024;
+
+struct Server {
+    url: String,
+    port: u16,
+    stop: Arc<AtomicBool>,
+}
+
+static SERVER: Mutex<Option<Server>> = Mutex::new(None);
+
+/// 接收服务状态，qr_code 为配对地址的二维码（data URL）
+#[derive(Debug, Clone, Serialize)]
+pub struct LanReceiveStatus {
+    pub running: bool,
+    pub url: Option<String>,
+    pub qr_code: Option<String>,
+}
+
+/// 获取当前状态
+pub fn status() -> Result<LanReceiveStatus, String> {
+    let server = SERVER.lock().unwrap();
+    match server.as_ref() {
+        Some(server) => running_status(&server.url),
+        None => Ok(LanReceiveStatus {
+            running: false,
+            url: None,
+            qr_code: None,
+        }),
+    }
+}
+
+fn running_status(url: &str) -> Result<LanReceiveStatus, String> {
+    Ok(LanReceiveStatus {
+        running: true,
+        url: Some(url.to_string()),
+        qr_code: Some(QrCode::encode_text(url)?.to_data_url(8, 4)?),
+    })
+}
+
+/// 开启接收服务，port 为 0 时使用随机端口；已开启时直接返回当前状态
+pub fn start(app: AppHandle, port: u16) -> Result<LanReceiveStatus, String> {
+    let mut server = SERVER.lock().unwrap();
+    if let Some(server) = server.as_ref() {
+        return running_status(&server.url);
+    }
+
+    let ip = local_ip().ok_or_else(|| "未检测到局域网地址".to_string())?;
+    let listener = TcpListener::bind(("0.0.0.0", port))
+        .map_err(|e| format!("Failed to bind LAN receive port: {}", e))?;
+    let port = listener
+        .local_addr()
+        .map_err(|e| format!("Failed to get LAN receive address: {}", e))?
+        .port();
+    let token = format!("{:032x}", rand::random::<u128>());
+    let url = format!("http://{}/?token={}", SocketAddr::new(ip, port), token);
+    let stop = Arc::new(AtomicBool::new(false));
+
+    let stop_flag = stop.clone();
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            if stop_flag.load(Ordering::SeqCst) {
+                break;
+            }
+            let Ok(stream) = stream else { continue };
+            let app = app.clone();
+            let token = token.clone();
+            std::thread::spawn(move || {
+                if let Err(e) = handle_connection(&app, stream, &token) {
+                    eprintln!("[LanReceive] {}", e);
+                }
+            });
+        }
+        eprintln!("[LanReceive] Stopped");
+    });
+
+    eprintln!("[LanReceive] Listening on port {}", port);
+    let status = running_status(&url)?;
+    *server = Some(Server { url, port, stop });
+    Ok(status)
+}
+
+/// 关闭接收服务
+pub fn stop() {
+    if let Some(server) = SERVER.lock().unwrap().take() {
+        server.stop.store(true, Ordering::SeqCst);
+        // 连接一次唤醒阻塞在 accept 上的线程
+        let addr = SocketAddr::from(([127, 0, 0, 1], server.port));
+        let _ = TcpStream::connect_timeout(&addr, Duration::from_secs(1));
+    }
+}
+
+/// 本机局域网地址：UDP connect 只选路由不发包
+fn local_ip() -> Option<IpAddr> {
+    let socket = UdpSocket::bind("0.0.0.0:0").ok()?;
+    socket.connect("8.8.8.8:80").ok()?;
+    let ip = socket.local_addr().ok()?.ip();
+    (!ip.is_loopback() && !ip.is_unspecified()).then_some(ip)
+}
+
+struct Request {
+    method: String,
+    path: String,
+    query_token: Option<String>,
+    body: Vec<u8>,
+}
+
+fn handle_connection(app: &AppHandle, stream: TcpStream, token: &str) -> Result<(), String> {
+    let peer = stream
+        .peer_addr()
+        .map(|addr| addr.ip().to_string())
+        .unwrap_or_default();
+    let _ = stream.set_read_timeout(Some(Duration::from_secs(10)));
+    let mut writer = stream
+        .try_clone()
+        .map_err(|e| format!("Failed to clone stream: {}", e))?;
+
+    let request = match read_request(stream) {
+        Ok(request) => request,
+        Err((status, message)) => return respond_text(&mut writer, status, &message),
+    };
+    if !request
+        .query_token
+        .as_deref()
+        .is_some_and(|t| constant_time_eq(t.as_bytes(), token.as_bytes()))
+    {
+        eprintln!(
+            "[LanReceive] Rejected request from {} with invalid token",
+            peer
+        );
+        return respond_text(&mut writer, "403 Forbidden", "令牌无效，请重新扫码");
+    }
+
+    match (request.method.as_str(), request.path.as_str()) {
+        ("GET", "/") => respond(&mut writer, "200 OK", "text/html; charset=utf-8", PAGE_HTML),
+        ("POST", "/send") => {
+            let text = match String::from_utf8(request.body) {
+                Ok(text) if !text.trim().is_empty() => text,
+                Ok(_) => return respond_text(&mut writer, "400 Bad Request", "内容为空"),
+                Err(_) => return respond_text(&mut writer, "400 Bad Request", "仅支持 UTF-8 文本"),
+            };
+            match deliver(app, text, &peer) {
+                Ok(()) => respond_text(&mut writer, "200 OK", "已发送到电脑"),
+                Err(e) => {
+                    eprintln!("[LanReceive] Failed to deliver text: {}", e);
+                    respond_text(&mut writer, "500 Internal Server Error", &e)
+                }
+            }
+        }
+        _ => respond_text(&mut writer, "404 Not Found", "Not Found"),
+    }
+}
+
+fn read_request(stream: TcpStream) -> Result<Request, (&'static str, String)> {
+    let bad_request = |msg: &str| ("400 Bad Request", msg.to_string());
+    let mut reader = BufReader::new(stream.take((MAX_HEADER_BYTES + MAX_BODY_BYTES) as u64));
+
+    let mut request_line = String::new();
+    reader
+        .read_line(&mut request_line)
+        .map_err(|_| bad_request("Invalid request"))?;
+    let mut parts = request_line.split_whitespace();
+    let method = parts
+        .next()
+        .ok_or_else(|| bad_request("Invalid request"))?
+        .to_string();
+    let target = parts.next().ok_or_else(|| bad_request("Invalid request"))?;
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let query_token = query
+        .split('&')
+        .find_map(|pair| pair.strip_prefix("token="))
+        .map(str::to_string);
+
+    let mut content_length = 0usize;
+    let mut header_bytes = 0usize;
+    loop {
+        let mut line = String::new();
+        let n = reader
+            .read_line(&mut line)
+            .map_err(|_| bad_request("Invalid headers"))?;
+        header_bytes += n;
+        if header_bytes > MAX_HEADER_BYTES {
+            return Err((
+                "431 Request Header Fields Too Large",
+                "Headers too large".to_string(),
+            ));
+        }
+        let line = line.trim_end();
+        if n == 0 || line.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("content-length") {
+                content_length = value
+                    .trim()
+                    .parse()
+                    .map_err(|_| bad_request("Invalid Content-Length"))?;
+            }
+        }
+    }
+    if content_length > MAX_BODY_BYTES {
+        return Err(("413 Payload Too Large", "内容超过 1 MB".to_string()));
+    }
+
+    let mut body = vec![0u8; content_length];
+    reader
+        .read_exact(&mut body)
+        .map_err(|_| bad_request("Incomplete body"))?;
+    Ok(Request {
+        method,
+        path: path.to_string(),
+        query_token,
+        body,
+    })
+}
+
+fn respond(
+    stream: &mut TcpStream,
+    status: &str,
+    content_type: &str,
+    body: &str,
+) -> Result<(), String> {
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nCache-Control: no-store\r\nConnection: close\r\n\r\n{}",
+        status,
+        content_type,
+        body.len(),
+        body
+    );
+    stream
+        .write_all(response.as_bytes())
+        .map_err(|e| format!("Failed to write response: {}", e))
+}
+
+fn respond_text(stream: &mut TcpStream, status: &str, body: &str) -> Result<(), String> {
+    respond(stream, status, "text/plain; charset=utf-8", body)
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// 写入剪贴板与历史，并提示用户
+fn deliver(app: &AppHandle, text: String, peer: &str) -> Result<(), String> {
+    #[cfg(target_os = "windows")]
+    clipboard::monitor::set_clipboard_text(&text)?;
+
+    let app_data_dir = crate::commands::get_app_data_dir(app)?;
+    let item = clipboard::add_clipboard_item_rich(
+        text,
+        "text".to_string(),
+        false,
+        RichFormats::default(),
+        ClipboardSource {
+            app: Some("局域网".to_string()),
+            title: Some(peer.to_string()),
+        },
+        &app_data_dir,
+    )?;
+    eprintln!(
+        "[LanReceive] Received {} chars from {}",
+        item.content.chars().count(),
+        peer
+    );
+
+    let preview: String = item.content.chars().take(60).collect();
+    let _ = crate::toast::show_toast(app, "收到手机发送的文本", &preview);
+    let _ = app.emit("lan-clipboard-received", &item);
+    Ok(())
+}
+
+const PAGE_HTML: &str = r#"<!DOCTYPE html>
+<html lang="zh-CN">
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+<title>发送到电脑剪贴板</title>
+<style>
+body { font-family: -apple-system, "Segoe UI", sans-serif; margin: 0; padding: 16px; background: #f5f5f5; }
+h1 { font-size: 18px; }
+textarea { width: 100%; box-sizing: border-box; height: 40vh; padding: 8px; font-size: 16px; border: 1px solid #ccc; border-radius: 6px; }
+.row { display: flex; gap: 8px; margin-top: 12px; }
+button, label { flex: 1; padding: 12px; font-size: 16px; border: none; border-radius: 6px; text-align: center; }
+button { background: #2563eb; color: #fff; }
+label { background: #e5e7eb; }
+#status { margin-top: 12px; color: #555; }
+</style>
+</head>
+<body>
+<h1>发送到电脑剪贴板</h1>
+<textarea id="text" placeholder="输入或粘贴要发送的文本"></textarea>
+<div class="row">
+<label>选择文本文件<input id="file" type="file" accept="text/*,.txt,.md,.json,.csv" hidden></label>
+<button id="send">发送</button>
+</div>
+<div id="status"></div>
+<script>
+const token = new URLSearchParams(location.search).get("token") || "";
+const text = document.getElementById("text");
+const status = document.getElementById("status");
+document.getElementById("file").addEventListener("change", async (e) => {
+  const file = e.target.files[0];
+  if (file) text.value = await file.text();
+});
+document.getElementById("send").addEventListener("click", async () => {
+  if (!text.value.trim()) { status.textContent = "内容为空"; return; }
+  status.textContent = "发送中…";
+  try {
+    const res = await fetch("/send?token=" + encodeURIComponent(token), {
+      method: "POST",
+      headers: { "Content-Type": "text/plain; charset=utf-8" },
+      body: text.value,
+    });
+    status.textContent = await res.text();
+    if (res.ok) text.value = "";
+  } catch (err) {
+    status.textContent = "发送失败：" + err;
+  }
+});
+</script>
+</body>
+</html>
+"#;
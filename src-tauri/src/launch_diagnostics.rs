@@ -0,0 +1,153 @@
+//! 启动失败诊断
+//!
+//! launch_application 失败时收集路径状态、权限需求与系统错误码，生成结构化诊断和建议动作
+
+use serde::Serialize;
+use std::fs;
+use std::io::Read;
+use std::path::Path;
+
+/// 读取可执行文件时的大小上限，超过则跳过清单检查
+const MAX_MANIFEST_SCAN_BYTES: u64 = 64 * 1024 * 1024;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LaunchDiagnostics {
+    /// 原始错误信息
+    pub message: String,
+    pub path: String,
+    pub path_exists: bool,
+    /// 快捷方式解析出的目标路径
+    pub target_path: Option<String>,
+    pub target_exists: Option<bool>,
+    pub requires_admin: bool,
+    pub blocked_by_antivirus: bool,
+    pub error_code: Option<i32>,
+    pub error_description: Option<String>,
+    /// 建议动作代码，前端据此展示按钮/提示
+    pub suggestions: Vec<String>,
+}
+
+/// 常见 Win32 错误码翻译
+pub fn translate_error_code(code: i32) -> Option<&'static str> {
+    Some(match code {
+        2 => "系统找不到指定的文件",
+        3 => "系统找不到指定的路径",
+        5 => "拒绝访问",
+        32 => "文件正被另一个进程使用",
+        193 => "不是有效的 Win32 应用程序",
+        216 => "程序与当前 Windows 版本不兼容",
+        225 => "文件包含病毒或潜在有害软件，已被安全软件拦截",
+        226 => "文件包含病毒，已被安全软件删除",
+        740 => "请求的操作需要提升权限（管理员）",
+        1155 => "没有与此文件关联的应用程序",
+        1223 => "操作已被用户取消",
+        1260 => "组策略阻止了此程序运行",
+        4551 => "应用程序控制策略阻止了此文件",
+        _ => return None,
+    })
+}
+
+/// 检查可执行文件内嵌清单是否要求管理员权限
+fn manifest_requires_admin(path: &Path) -> bool {
+    let is_exe = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.eq_ignore_ascii_case("exe"))
+        .unwrap_or(false);
+    if !is_exe {
+        return false;
+    }
+    let Ok(file) = fs::File::open(path) else {
+        return false;
+    };
+    let mut data = Vec::new();
+    if file.take(MAX_MANIFEST_SCAN_BYTES).read_to_end(&mut data).is_err() {
+        return false;
+    }
+    let needle = b"requireAdministrator";
+    data.windows(needle.len()).any(|window| window == needle)
+}
+
+/// 根据路径与错误码生成诊断信息
+pub fn diagnose(path: &str, message: &str, error_code: Option<i32>) -> LaunchDiagnostics {
+    let path_buf = Path::new(path.trim());
+    let path_exists = path_buf.exists();
+
+    let is_lnk = path_buf
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.eq_ignore_ascii_case("lnk"))
+        .unwrap_or(false);
+
+    #[cfg(target_os = "windows")]
+    let target_path = if is_lnk && path_exists {
+        crate::app_search::windows::parse_lnk_file(path_buf)
+            .ok()
+            .map(|info| info.path)
+    } else {
+        None
+    };
+    #[cfg(not(target_os = "windows"))]
+    let target_path: Option<String> = {
+        let _ = is_lnk;
+        None
+    };
+
+    let target_exists = target_path.as_ref().map(|t| Path::new(t).exists());
+    let executable = target_path.as_deref().map(Path::new).unwrap_or(path_buf);
+
+    let requires_admin = error_code == Some(740) || manifest_requires_admin(executable);
+    let blocked_by_antivirus = matches!(error_code, Some(225) | Some(226));
+
+    let mut suggestions = Vec::new();
+    if !path_exists {
+        suggestions.push("rescan_applications".to_string());
+        suggestions.push("remove_from_index".to_string());
+    } else if target_exists == Some(false) {
+        suggestions.push("fix_shortcut_target".to_string());
+        suggestions.push("remove_from_index".to_string());
+    }
+    if requires_admin {
+        suggestions.push("run_as_admin".to_string());
+    }
+    if blocked_by_antivirus {
+        suggestions.push("check_antivirus_quarantine".to_string());
+    }
+    match error_code {
+        Some(1155) => suggestions.push("choose_default_app".to_string()),
+        Some(1260) | Some(4551) => suggestions.push("contact_administrator".to_string()),
+        Some(32) => suggestions.push("retry_later".to_string()),
+        _ => {}
+    }
+    if suggestions.is_empty() {
+        suggestions.push("open_file_location".to_string());
+    }
+
+    let diagnostics = LaunchDiagnostics {
+        message: message.to_string(),
+        path: path.to_string(),
+        path_exists,
+        target_path,
+        target_exists,
+        requires_admin,
+        blocked_by_antivirus,
+        error_code,
+        error_description: error_code.and_then(translate_error_code).map(|s| s.to_string()),
+        suggestions,
+    };
+
+    crate::log!(
+        "Launch",
+        "启动失败: path={}, exists={}, target={:?}, target_exists={:?}, admin={}, antivirus={}, code={:?}, message={}",
+        diagnostics.path,
+        diagnostics.path_exists,
+        diagnostics.target_path,
+        diagnostics.target_exists,
+        diagnostics.requires_admin,
+        diagnostics.blocked_by_antivirus,
+        diagnostics.error_code,
+        diagnostics.message
+    );
+
+    diagnostics
+}
@@ -4,6 +4,7 @@
 use std::fs::{File, OpenOptions};
 use std::io::Write;
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicU32, Ordering};
 use std::sync::{Arc, Mutex};
 use std::sync::OnceLock;
 
@@ -129,12 +130,28 @@ pub fn init_log_file_early() {
     write_log("Logger", "日志系统已初始化");
 }
 
+/// 生成关联 ID（8 位十六进制），在命令入口生成，同一次搜索/回放的日志带上同一个 ID
+pub fn new_trace_id() -> String {
+    static COUNTER: AtomicU32 = AtomicU32::new(0);
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.subsec_nanos() ^ (d.as_secs() as u32).rotate_left(16))
+        .unwrap_or_default();
+    let seq = COUNTER.fetch_add(1, Ordering::Relaxed);
+    format!("{:08x}", nanos ^ seq.wrapping_mul(0x9E37_79B9))
+}
+
 /// 写入日志到文件
 /// 
 /// # Arguments
 /// * `module` - 模块名称（如 "IconExtract", "Everything", "Hotkey" 等）
 /// * `msg` - 日志消息
 pub fn write_log(module: &str, msg: &str) {
+    write_log_traced(module, None, msg);
+}
+
+/// 写入带关联 ID 的日志，格式为 `[时间] [模块] [trace:ID] 消息`
+pub fn write_log_traced(module: &str, trace_id: Option<&str>, msg: &str) {
     // 确保使用当前日期的日志文件（如果日期变化了会自动切换）
     ensure_current_log_file();
 
@@ -166,7 +183,10 @@ pub fn write_log(module: &str, msg: &str) {
             match state_guard.file.as_mut() {
                 Some(file) => {
                     let timestamp = chrono::Local::now().format("%H:%M:%S%.3f");
-                    let log_msg = format!("[{}] [{}] {}\n", timestamp, module, msg);
+                    let log_msg = match trace_id {
+                        Some(id) => format!("[{}] [{}] [trace:{}] {}\n", timestamp, module, id, msg),
+                        None => format!("[{}] [{}] {}\n", timestamp, module, msg),
+                    };
                     match file.write_all(log_msg.as_bytes()) {
                         Ok(_) => {
                             if let Err(e) = file.flush() {
@@ -191,16 +211,90 @@ pub fn write_log(module: &str, msg: &str) {
     }
 }
 
-/// 日志宏，支持格式化字符串
+/// 日志文件中的一条记录
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct LogEntry {
+    pub time: String,
+    pub module: String,
+    pub trace_id: Option<String>,
+    pub message: String,
+}
+
+/// 解析一行日志，不符合 `[时间] [模块] ...` 格式时返回 None（如多行消息的后续行）
+fn parse_log_line(line: &str) -> Option<LogEntry> {
+    let rest = line.strip_prefix('[')?;
+    let (time, rest) = rest.split_once("] [")?;
+    let (module, rest) = rest.split_once(']')?;
+    let rest = rest.strip_prefix(' ').unwrap_or(rest);
+    let (trace_id, message) = match rest.strip_prefix("[trace:").and_then(|r| r.split_once(']')) {
+        Some((id, message)) => (Some(id.to_string()), message.strip_prefix(' ').unwrap_or(message)),
+        None => (None, rest),
+    };
+    Some(LogEntry {
+        time: time.to_string(),
+        module: module.to_string(),
+        trace_id,
+        message: message.to_string(),
+    })
+}
+
+/// 读取某天（YYYYMMDD，默认今天）的日志，可按关联 ID 与模块过滤，返回最近的 limit 条
+pub fn read_logs(
+    date: Option<&str>,
+    trace_id: Option<&str>,
+    module: Option<&str>,
+    limit: usize,
+) -> Result<Vec<LogEntry>, String> {
+    let date = match date {
+        Some(d) if d.len() == 8 && d.chars().all(|c| c.is_ascii_digit()) => d.to_string(),
+        Some(d) => return Err(format!("Invalid log date: {}", d)),
+        None => chrono::Local::now().format("%Y%m%d").to_string(),
+    };
+    let log_path = get_log_dir().join(format!("everything-ipc-{}.log", date));
+    if !log_path.exists() {
+        return Ok(Vec::new());
+    }
+    let bytes = std::fs::read(&log_path).map_err(|e| format!("Failed to read log file: {}", e))?;
+    let content = String::from_utf8_lossy(&bytes);
+
+    let mut entries: Vec<LogEntry> = Vec::new();
+    for line in content.lines() {
+        match parse_log_line(line) {
+            Some(entry) => entries.push(entry),
+            None => {
+                if let Some(last) = entries.last_mut() {
+                    last.message.push('\n');
+                    last.message.push_str(line);
+                }
+            }
+        }
+    }
+
+    let mut filtered: Vec<LogEntry> = entries
+        .into_iter()
+        .filter(|e| trace_id.is_none() || e.trace_id.as_deref() == trace_id)
+        .filter(|e| module.map(|m| e.module.eq_ignore_ascii_case(m)).unwrap_or(true))
+        .collect();
+    if filtered.len() > limit {
+        filtered.drain(..filtered.len() - limit);
+    }
+    Ok(filtered)
+}
+
+/// 日志宏，支持格式化字符串，可用 `trace: id` 前缀附带关联 ID
 /// 
 /// # 使用示例
 /// ```rust
 /// use crate::logger::log;
 /// log!("IconExtract", "开始提取图标: {}", file_path);
 /// log!("Everything", "搜索查询: {}", query);
+/// log!(trace: trace_id, "Replay", "开始回放: {}", path);
 /// ```
 #[macro_export]
 macro_rules! log {
+    (trace: $trace:expr, $module:expr, $($arg:tt)*) => {
+        crate::logger::write_log_traced($module, Some(::std::convert::AsRef::<str>::as_ref(&$trace)), &format!($($arg)*));
+    };
     ($module:expr, $($arg:tt)*) => {
         crate::logger::write_log($module, &format!($($arg)*));
     };
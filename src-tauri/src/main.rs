@@ -1,39 +1,88 @@
 // Prevents additional console window on Windows in release, DO NOT REMOVE!!
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
+mod app_names;
 mod app_search;
+mod asset_protocol;
+mod cli_args;
+mod color;
+mod color_history;
+mod color_palette;
 mod commands;
+mod content_search;
 mod error;
 mod everything_search;
 mod everything_filters;
 mod file_history;
+mod foreground;
+mod form_templates;
+mod hook_supervisor;
+mod hires_clock;
 mod hooks;
 mod hotkey;
+mod hotkey_dispatch;
 mod hotkey_handler;
+mod launch_diagnostics;
 // mod keyboard_hook; // 已不再需要，hotkey_handler 已支持双击修饰键
+mod date_calc;
+mod default_panel;
 mod db;
 mod logger;
+mod pinned_results;
+mod playlists;
 mod plugin_usage;
+mod result_weights;
+mod profile;
+mod qrcode;
+mod process_info;
 mod memos;
+mod mouse_buttons;
+mod notes;
 mod open_history;
 mod recording;
+mod recording_schema;
+mod reminders;
 mod replay;
 mod settings;
+mod shutdown;
 mod shortcuts;
+mod snippet_expander;
+mod stealth;
+mod text_input;
+mod running_apps;
+mod privacy;
+mod suggest;
+mod tool_providers;
+mod dnd;
+mod toast;
 mod system_folders_search;
 mod window_config;
 mod clipboard;
+mod clipboard_actions;
+mod clipboard_alert;
+mod clipboard_archive;
+mod clipboard_crypto;
+mod clipboard_image;
+mod clipboard_ocr;
+mod clipboard_expiry;
+mod clipboard_transform;
+mod clipboard_sensitive;
+mod clipboard_sync;
+mod lan_receive;
 mod word_records;
 mod file_watcher;
+mod file_actions;
 mod markdown_recent_files;
+mod package_manager;
 
 use crate::commands::get_app_data_dir;
 use commands::*;
 use tauri::tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent};
 use tauri::{
-    menu::{Menu, MenuItem},
+    menu::{CheckMenuItem, Menu, MenuItem},
     Manager,
     Emitter,
+    Listener,
 };
 use std::sync::{Arc, Mutex};
 
@@ -170,6 +219,7 @@ fn cleanup_lock_file() {
     // 删除锁文件
     let lock_file_path = get_lock_file_path();
     let _ = std::fs::remove_file(&lock_file_path);
+    cli_args::cleanup(&cli_args::endpoint_file_path(&lock_file_path));
 }
 
 /// 设置 launcher 窗口位置（居中但稍微偏上）
@@ -226,29 +276,85 @@ fn set_launcher_window_position(window: &tauri::WebviewWindow, app_data_dir: &st
 }
 
 fn main() {
+    // 选择数据目录 Profile（--profile）
+    profile::init(profile::parse_args(std::env::args().skip(1)));
+
+    // 解析命令行参数（--play / --search）
+    let cli_command = match cli_args::parse(std::env::args().skip(1)) {
+        Ok(command) => command,
+        Err(e) => {
+            eprintln!("[CLI] {}", e);
+            None
+        }
+    };
+
     // 检查单实例
     if !check_single_instance() {
-        // 已有实例在运行，退出
+        // 已有实例在运行，将命令行命令转发给它后退出
+        if let Some(command) = cli_command {
+            let endpoint_file = cli_args::endpoint_file_path(&get_lock_file_path());
+            if let Err(e) = cli_args::forward(&endpoint_file, command) {
+                eprintln!("[CLI] {}", e);
+            }
+        }
         std::process::exit(0);
     }
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_dialog::init())
-        .setup(|app| {
+        .register_uri_scheme_protocol(asset_protocol::SCHEME, asset_protocol::handle)
+        .setup(move |app| {
+            // 注册 AUMID，通知按钮回调归属到本应用
+            toast::register_app_user_model_id(app.handle());
+            // 钩子线程连续崩溃时通过 AppHandle 向前端报告降级
+            hook_supervisor::set_app_handle(app.handle().clone());
+
             // Create system tray menu
             let app_center = MenuItem::with_id(app, "app_center", "应用中心", true, None::<&str>)?;
             let open_logs = MenuItem::with_id(app, "open_logs", "打开日志文件夹", true, None::<&str>)?;
+            let stealth_mode = CheckMenuItem::with_id(app, "stealth_mode", "隐身模式", true, stealth::is_enabled(), None::<&str>)?;
+            // 勿扰时段与热键抑制窗口来自设置，启动时加载
+            if let Ok(dir) = get_app_data_dir(app.handle()) {
+                let loaded = settings::load_settings(&dir).unwrap_or_default();
+                dnd::set_quiet_periods(loaded.quiet_periods);
+                hotkey_dispatch::set_suppress_ms(loaded.hotkey_suppress_ms);
+                clipboard::apply_monitoring_enabled(app.handle(), loaded.clipboard_monitoring);
+            }
+            let dnd_mode = CheckMenuItem::with_id(app, "dnd_mode", "勿扰模式", true, dnd::is_active(), None::<&str>)?;
+            let clipboard_paused = CheckMenuItem::with_id(app, "clipboard_paused", "暂停剪贴板记录", true, !clipboard::is_monitoring_enabled(), None::<&str>)?;
             let restart = MenuItem::with_id(app, "restart", "重启程序", true, None::<&str>)?;
             let quit = MenuItem::with_id(app, "quit", "退出", true, None::<&str>)?;
 
-            let menu = Menu::with_items(app, &[&app_center, &open_logs, &restart, &quit])?;
+            let menu = Menu::with_items(app, &[&app_center, &open_logs, &stealth_mode, &dnd_mode, &clipboard_paused, &restart, &quit])?;
+
+            // 非默认 Profile 时在托盘菜单顶部显示当前 Profile
+            let tray_tooltip = match profile::current() {
+                Some(name) => {
+                    let profile_item = MenuItem::with_id(app, "profile", format!("Profile: {}", name), false, None::<&str>)?;
+                    menu.prepend(&profile_item)?;
+                    format!("ReFast [{}]", name)
+                }
+                None => "ReFast".to_string(),
+            };
+
+            // 隐身模式状态变化时（托盘或命令触发）同步托盘勾选状态
+            let stealth_mode_item = stealth_mode.clone();
+            app.listen("stealth-mode-changed", move |_event| {
+                let _ = stealth_mode_item.set_checked(stealth::is_enabled());
+            });
+
+            // 剪贴板记录状态变化时（托盘、命令或设置触发）同步托盘勾选状态
+            let clipboard_paused_item = clipboard_paused.clone();
+            app.listen("clipboard-monitoring-changed", move |_event| {
+                let _ = clipboard_paused_item.set_checked(!clipboard::is_monitoring_enabled());
+            });
 
             // Create tray icon - use default window icon (which loads from tauri.conf.json)
             // 禁用左键点击显示菜单，左键只用于切换启动器窗口
             let mut tray_builder = TrayIconBuilder::new()
                 .menu(&menu)
-                .tooltip("ReFast")
+                .tooltip(if dnd::is_active() { format!("{} (勿扰)", tray_tooltip) } else { tray_tooltip.clone() })
                 .show_menu_on_left_click(false);
 
             // Use default window icon (loaded from tauri.conf.json icons/icon.ico)
@@ -273,7 +379,7 @@ fn main() {
 
             let app_data_dir_clone1 = app_data_dir.clone();
 
-            let _tray = tray_builder
+            let tray = tray_builder
                 .on_tray_icon_event(move |tray, event| {
                     if let TrayIconEvent::Click {
                         button: MouseButton::Left,
@@ -284,12 +390,11 @@ fn main() {
                         // Left click - toggle launcher window
                         if let Some(window) = tray.app_handle().get_webview_window("launcher") {
                             let _ = window.is_visible().map(|visible| {
-                                if visible {
-                                    let _ = window.hide();
+                                if !hotkey_dispatch::launcher_toggle_intent(visible) {
+                                    foreground::hide_and_restore(&window);
                                 } else {
                                     set_launcher_window_position(&window, &app_data_dir_clone1);
-                                    let _ = window.show();
-                                    let _ = window.set_focus();
+                                    foreground::show_and_activate(&window);
                                 }
                             });
                         }
@@ -350,30 +455,49 @@ fn main() {
                             }
                         }
                     }
-                    "restart" => {
-                        // 清理快捷键钩子
-                        #[cfg(target_os = "windows")]
-                        {
-                            hotkey_handler::windows::cleanup_hotkeys();
+                    "dnd_mode" => {
+                        // 勿扰时段内手动关闭无效，勾选状态由 dnd-changed 同步
+                        let target = !dnd::status().manual;
+                        dnd::set_manual(app, target);
+                    }
+                    "stealth_mode" => {
+                        let target = !stealth::is_enabled();
+                        if let Err(e) = stealth::set_enabled(target) {
+                            eprintln!("Failed to toggle stealth mode: {}", e);
                         }
-                        // 清理锁文件，以便重启后新实例可以正常启动
-                        cleanup_lock_file();
-                        app.restart();
+                        let _ = app.emit("stealth-mode-changed", stealth::is_enabled());
                     }
-                    "quit" => {
-                        // 清理快捷键钩子
-                        #[cfg(target_os = "windows")]
-                        {
-                            hotkey_handler::windows::cleanup_hotkeys();
+                    "clipboard_paused" => {
+                        let target = !clipboard::is_monitoring_enabled();
+                        let result = get_app_data_dir(app)
+                            .and_then(|dir| clipboard::set_monitoring_enabled(app, target, &dir));
+                        if let Err(e) = result {
+                            eprintln!("Failed to toggle clipboard monitoring: {}", e);
                         }
-                        // 清理锁文件
-                        cleanup_lock_file();
-                        app.exit(0);
+                        // 失败时也要把勾选状态恢复为实际状态
+                        let _ = app.emit("clipboard-monitoring-changed", clipboard::is_monitoring_enabled());
+                    }
+                    "restart" => {
+                        // 统一清理（含锁文件，以便重启后新实例可以正常启动）
+                        shutdown::restart(app);
+                    }
+                    "quit" => {
+                        shutdown::exit(app);
                     }
                     _ => {}
                 })
                 .build(app)?;
 
+            // 勿扰状态变化时（手动切换或进入/离开时段）同步托盘勾选与提示文字
+            let dnd_mode_item = dnd_mode.clone();
+            app.listen("dnd-changed", move |_event| {
+                let active = dnd::is_active();
+                let _ = dnd_mode_item.set_checked(active);
+                let tooltip = if active { format!("{} (勿扰)", tray_tooltip) } else { tray_tooltip.clone() };
+                let _ = tray.set_tooltip(Some(tooltip));
+            });
+            dnd::start_watcher(app.handle().clone());
+
             // Ensure launcher window has no decorations
             if let Some(window) = app.get_webview_window("launcher") {
                 let _ = window.set_decorations(false);
@@ -406,6 +530,10 @@ fn main() {
                         let app_handle_clone = app_handle.clone();
                         std::thread::spawn(move || {
                             while let Ok(_) = rx.recv() {
+                                // 抑制窗口内的重复触发（如快速两轮双击 Ctrl）直接丢弃
+                                if !hotkey_dispatch::should_dispatch("launcher") {
+                                    continue;
+                                }
                                 // Hotkey pressed - toggle launcher window
                                 // Small delay to ensure window operations are ready
                                 std::thread::sleep(Duration::from_millis(50));
@@ -414,12 +542,12 @@ fn main() {
                                     app_handle_clone.get_webview_window("launcher")
                                 {
                                     let _ = window.is_visible().map(|visible| {
-                                        if visible {
-                                            let _ = window.hide();
+                                        // 窗口仍在切换中时以最近一次意图为准
+                                        if !hotkey_dispatch::launcher_toggle_intent(visible) {
+                                            foreground::hide_and_restore(&window);
                                         } else {
                                             set_launcher_window_position(&window, &app_data_dir_hotkey);
-                                            let _ = window.show();
-                                            let _ = window.set_focus();
+                                            foreground::show_and_activate(&window);
                                         }
                                     });
                                 }
@@ -498,6 +626,9 @@ fn main() {
                         let app_data_dir_hotkey = app_data_dir.clone();
                         std::thread::spawn(move || {
                             while let Ok(hotkey_id) = rx_plugin.recv() {
+                                if !hotkey_dispatch::should_dispatch(&hotkey_id) {
+                                    continue;
+                                }
                                 // 检查是否是应用中心快捷键
                                 if hotkey_id == "app_center" {
                                     // 打开应用中心窗口
@@ -508,6 +639,43 @@ fn main() {
                                             eprintln!("[Main] Failed to show app center via hotkey: {}", e);
                                         }
                                     });
+                                } else if hotkey_id == commands::color_picker::COLOR_PICK_HOTKEY_ID {
+                                    // 直达取色，取完复制并 Toast 提示
+                                    let app_handle_color = app_handle_plugin.clone();
+                                    tauri::async_runtime::spawn(async move {
+                                        if let Err(e) = commands::pick_color_and_copy(app_handle_color).await {
+                                            eprintln!("[Main] Failed to pick color via hotkey: {}", e);
+                                        }
+                                    });
+                                } else if hotkey_id == commands::screen_ruler::SCREEN_RULER_HOTKEY_ID {
+                                    // 屏幕标尺，测量完复制并 Toast 提示
+                                    let app_handle_ruler = app_handle_plugin.clone();
+                                    tauri::async_runtime::spawn(async move {
+                                        if let Err(e) = commands::measure_screen_and_copy(app_handle_ruler).await {
+                                            eprintln!("[Main] Failed to measure screen via hotkey: {}", e);
+                                        }
+                                    });
+                                } else if hotkey_id == commands::PASTE_STACK_HOTKEY_ID {
+                                    // 连续粘贴下一条（模拟 Ctrl+V 需在阻塞线程中执行）
+                                    std::thread::spawn(|| {
+                                        if let Err(e) = commands::paste_next_from_stack() {
+                                            eprintln!("[Main] Failed to paste from stack via hotkey: {}", e);
+                                        }
+                                    });
+                                } else if hotkey_id == commands::CLIPBOARD_POPUP_HOTKEY_ID {
+                                    let app_handle_popup = app_handle_plugin.clone();
+                                    tauri::async_runtime::spawn(async move {
+                                        if let Err(e) = commands::show_clipboard_window(app_handle_popup, Some(true)).await {
+                                            eprintln!("[Main] Failed to show clipboard popup via hotkey: {}", e);
+                                        }
+                                    });
+                                } else if hotkey_id == commands::notes::NOTES_HOTKEY_ID {
+                                    let app_handle_notes = app_handle_plugin.clone();
+                                    tauri::async_runtime::spawn(async move {
+                                        if let Err(e) = commands::show_notes_window(app_handle_notes, None).await {
+                                            eprintln!("[Main] Failed to show notes window via hotkey: {}", e);
+                                        }
+                                    });
                                 } else if hotkey_id.starts_with("app:") {
                                     // 提取应用路径
                                     let app_path = hotkey_id.strip_prefix("app:").unwrap_or(&hotkey_id);
@@ -567,6 +735,56 @@ fn main() {
                                     }
                                 }
                                 
+                                // 注册直达取色快捷键
+                                if let Some(ref color_pick_hotkey) = settings.color_pick_hotkey {
+                                    if let Err(e) = hotkey_handler::windows::register_plugin_hotkey(
+                                        commands::color_picker::COLOR_PICK_HOTKEY_ID.to_string(),
+                                        color_pick_hotkey.clone(),
+                                    ) {
+                                        eprintln!("[Main] Failed to register color pick hotkey: {}", e);
+                                    }
+                                }
+
+                                // 注册屏幕标尺快捷键
+                                if let Some(ref ruler_hotkey) = settings.screen_ruler_hotkey {
+                                    if let Err(e) = hotkey_handler::windows::register_plugin_hotkey(
+                                        commands::screen_ruler::SCREEN_RULER_HOTKEY_ID.to_string(),
+                                        ruler_hotkey.clone(),
+                                    ) {
+                                        eprintln!("[Main] Failed to register screen ruler hotkey: {}", e);
+                                    }
+                                }
+
+                                // 注册连续粘贴快捷键
+                                if let Some(ref paste_stack_hotkey) = settings.paste_stack_hotkey {
+                                    if let Err(e) = hotkey_handler::windows::register_plugin_hotkey(
+                                        commands::PASTE_STACK_HOTKEY_ID.to_string(),
+                                        paste_stack_hotkey.clone(),
+                                    ) {
+                                        eprintln!("[Main] Failed to register paste stack hotkey: {}", e);
+                                    }
+                                }
+
+                                // 注册剪贴板弹出面板快捷键
+                                if let Some(ref clipboard_popup_hotkey) = settings.clipboard_popup_hotkey {
+                                    if let Err(e) = hotkey_handler::windows::register_plugin_hotkey(
+                                        commands::CLIPBOARD_POPUP_HOTKEY_ID.to_string(),
+                                        clipboard_popup_hotkey.clone(),
+                                    ) {
+                                        eprintln!("[Main] Failed to register clipboard popup hotkey: {}", e);
+                                    }
+                                }
+
+                                // 注册快速笔记快捷键
+                                if let Some(ref notes_hotkey) = settings.notes_hotkey {
+                                    if let Err(e) = hotkey_handler::windows::register_plugin_hotkey(
+                                        commands::notes::NOTES_HOTKEY_ID.to_string(),
+                                        notes_hotkey.clone(),
+                                    ) {
+                                        eprintln!("[Main] Failed to register notes hotkey: {}", e);
+                                    }
+                                }
+                                
                                 // 注册应用快捷键（使用 "app:" 前缀）
                                 let mut all_hotkeys = std::collections::HashMap::new();
                                 for (app_path, hotkey) in settings.app_hotkeys.iter() {
@@ -662,32 +880,86 @@ fn main() {
                 }
             });
 
+            // 加载剪贴板加密密钥（需在剪贴板监控写入前完成）
+            clipboard_crypto::init(&app_data_dir);
+
             // Start clipboard monitor on Windows
             #[cfg(target_os = "windows")]
             {
                 let app_data_dir_clipboard = app_data_dir.clone();
-                if let Err(e) = clipboard::monitor::start_clipboard_monitor(app_data_dir_clipboard) {
+                if let Err(e) = clipboard::monitor::start_clipboard_monitor(app.handle().clone(), app_data_dir_clipboard) {
                     eprintln!("[Main] Failed to start clipboard monitor: {}", e);
                 } else {
                     eprintln!("[Main] Clipboard monitor started");
                 }
             }
 
+            // 按保留策略定期清理剪贴板历史
+            clipboard::start_retention_task(app_data_dir.clone());
+
+            // 敏感条目定时清除
+            clipboard_expiry::start(app_data_dir.clone());
+
+            // 后台识别图片条目中的文字
+            clipboard_ocr::start_ocr_task(app_data_dir.clone());
+
+            // WebDAV 剪贴板同步
+            clipboard_sync::start_sync_task(app.handle().clone(), app_data_dir.clone());
+
+            // 收藏条目的片段缩写展开
+            snippet_expander::init(app_data_dir.clone());
+
+            // 鼠标侧键动作
+            mouse_buttons::init(
+                app.handle().clone(),
+                settings::load_settings(&app_data_dir)
+                    .map(|s| s.mouse_side_buttons)
+                    .unwrap_or_default(),
+            );
+
             // 预热拾色器资源（后台线程，避免阻塞启动）
             // commands::color_picker::warmup_color_picker();  // 暂时屏蔽，待优化
 
+            // 启动命令行转发监听，并执行本实例自身的命令行命令
+            let endpoint_file = cli_args::endpoint_file_path(&get_lock_file_path());
+            if let Err(e) = cli_args::start_server(app.handle().clone(), endpoint_file) {
+                eprintln!("[CLI] {}", e);
+            }
+            if let Some(command) = cli_command {
+                let app_handle_cli = app.handle().clone();
+                std::thread::spawn(move || {
+                    // 等待启动器窗口初始化完成
+                    std::thread::sleep(std::time::Duration::from_millis(800));
+                    cli_args::execute(&app_handle_cli, command);
+                });
+            }
+
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
             get_recording_status,
             start_recording,
             stop_recording,
+            pause_recording,
+            resume_recording,
+            get_recording_paused,
+            create_playlist,
+            list_playlists,
+            delete_playlist,
+            play_playlist,
+            stop_playlist,
             list_recordings,
             delete_recording,
             play_recording,
+            check_recording_screen,
+            get_recording_schema,
+            validate_recording,
             stop_playback,
+            get_replay_breakpoint,
             get_playback_status,
             get_playback_progress,
+            get_playback_step_note,
+            set_recording_step_note,
             scan_applications,
             rescan_applications,
             test_uwp_apps_scan,
@@ -695,6 +967,12 @@ fn main() {
             search_system_folders,
             populate_app_icons,
             launch_application,
+            suggest,
+            record_search_query,
+            run_tool_query,
+            evaluate_date_expression,
+            get_running_apps,
+            activate_running_app,
             remove_app_from_index,
             debug_app_icon,
             extract_icon_from_path,
@@ -711,8 +989,17 @@ fn main() {
             is_everything_available,
             get_everything_status,
             get_everything_path,
+            detect_everything_installation,
+            search_file_contents,
+            cancel_file_content_search,
+            search_packages,
+            install_package,
+            cancel_package_install,
+            get_current_profile,
+            get_hook_health,
             get_everything_version,
             get_everything_log_file_path,
+            read_logs,
             get_file_preview,
             purge_file_history,
             delete_file_history_by_range,
@@ -739,6 +1026,8 @@ fn main() {
             add_shortcut,
             update_shortcut,
             delete_shortcut,
+            import_shortcuts_from_folder,
+            launch_shortcut,
             get_all_file_history,
             delete_file_history,
             update_file_history_name,
@@ -748,11 +1037,24 @@ fn main() {
             update_memo,
             delete_memo,
             search_memos,
+            get_all_notes,
+            save_note,
+            delete_note,
+            search_notes,
+            show_notes_window,
+            get_notes_hotkey,
+            save_notes_hotkey,
             get_all_word_records,
             add_word_record,
             update_word_record,
             delete_word_record,
             search_word_records,
+            compress_to_zip,
+            copy_to,
+            move_to,
+            get_favorite_target_dirs,
+            add_favorite_target_dir,
+            remove_favorite_target_dir,
             show_shortcuts_config,
             show_main_window,
             open_url,
@@ -763,6 +1065,8 @@ fn main() {
             update_open_history_remark,
             record_plugin_usage,
             get_plugin_usage,
+            record_result_action,
+            get_result_weights,
             show_memo_window,
             show_plugin_list_window,
             show_json_formatter_window,
@@ -771,6 +1075,25 @@ fn main() {
             show_hex_converter_window,
             // show_color_picker_window,  // 暂时屏蔽，待优化
             // pick_color_from_screen,     // 暂时屏蔽，待优化
+            pick_color_and_copy,
+            get_color_pick_hotkey,
+            save_color_pick_hotkey,
+            measure_screen,
+            measure_screen_and_copy,
+            get_screen_ruler_hotkey,
+            save_screen_ruler_hotkey,
+            ocr_screen_region,
+            load_image_for_picking,
+            pick_color_from_image,
+            extract_dominant_colors,
+            simulate_color_blindness,
+            export_colors,
+            import_colors,
+            list_color_history,
+            add_color_to_history,
+            delete_color,
+            favorite_color,
+            set_color_note,
             show_file_toolbox_window,
             show_calculator_pad_window,
             show_everything_search_window,
@@ -791,6 +1114,9 @@ fn main() {
             save_settings,
             get_everything_custom_filters,
             save_everything_custom_filters,
+            get_everything_scopes,
+            save_everything_scopes,
+            set_active_everything_scope,
             is_startup_enabled,
             set_startup_enabled,
             get_hotkey_config,
@@ -809,17 +1135,98 @@ fn main() {
             download_update,
             install_update,
             quit_app,
+            set_stealth_mode,
+            get_stealth_mode,
+            set_dnd_mode,
+            set_clipboard_monitoring,
+            get_clipboard_monitoring,
+            get_dnd_status,
             get_all_clipboard_items,
+            list_clipboard_items,
+            get_clipboard_item_content,
+            get_clipboard_item_qr_code,
+            transform_clipboard_item,
+            export_clipboard_history,
+            import_clipboard_history,
+            get_clipboard_sync_status,
+            sync_clipboard_now,
+            test_clipboard_sync,
+            recompress_clipboard_images,
+            get_clipboard_encryption_status,
+            enable_clipboard_encryption,
+            disable_clipboard_encryption,
+            unlock_clipboard_encryption,
+            start_lan_receive,
+            stop_lan_receive,
+            get_lan_receive_status,
             add_clipboard_item,
             update_clipboard_item,
             toggle_favorite_clipboard_item,
+            set_clipboard_abbreviation,
             delete_clipboard_item,
+            batch_delete_clipboard_items,
+            batch_favorite_clipboard_items,
+            delete_clipboard_items,
+            favorite_clipboard_items,
             clear_clipboard_history,
+            clear_clipboard_except_recent,
+            set_clipboard_item_expiry,
+            wipe_all_data,
+            get_clipboard_blocked_sources,
+            unblock_clipboard_source,
             search_clipboard_items,
+            add_clipboard_tag,
+            batch_add_clipboard_tag,
+            remove_clipboard_tag,
+            list_clipboard_tags,
+            list_clipboard_boards,
+            create_clipboard_board,
+            rename_clipboard_board,
+            delete_clipboard_board,
+            reorder_clipboard_boards,
+            move_clipboard_item_to_board,
+            reorder_clipboard_board_items,
+            get_clipboard_board_items,
+            search_clipboard_items_regex,
+            get_clipboard_stats,
+            get_clipboard_open_stats,
+            merge_clipboard_items,
+            get_pinned_results,
+            get_default_panel,
+            pin_result,
+            unpin_result,
+            reorder_pinned_results,
+            get_form_templates,
+            save_form_template,
+            delete_form_template,
+            fill_form_template,
+            create_reminder,
+            list_reminders,
+            cancel_reminder,
             show_clipboard_window,
             get_clipboard_image_data,
             copy_image_to_clipboard,
+            copy_files_to_clipboard,
+            paste_clipboard_item_with_format,
+            paste_clipboard_item_as_plain_text,
+            paste_to_previous_window,
+            start_paste_stack,
+            cancel_paste_stack,
+            get_paste_stack_status,
+            paste_next_from_stack,
+            get_paste_stack_hotkey,
+            save_paste_stack_hotkey,
+            get_clipboard_popup_hotkey,
+            save_clipboard_popup_hotkey,
+            get_clipboard_item_actions,
+            run_clipboard_action,
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|app, event| {
+            // 兜底：未经 shutdown::exit 的退出（如系统注销）也执行清理
+            if let tauri::RunEvent::Exit = event {
+                shutdown::prepare(app);
+            }
+        });
 }
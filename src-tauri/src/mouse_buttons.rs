@@ -0,0 +1,272 @@
+//! 鼠标侧键动作
+//!
+//! 低层鼠标钩子捕获侧键（XBUTTON1/2），按设置执行显示启动器、打开剪贴板面板、后退 / 前进等动作。
+//! 绑定了动作的侧键会被拦截（按下与抬起都不再传给前台程序），未绑定或前台进程在禁用列表中时原样放行。
+//! 模拟输入的侧键不参与处理。隐身模式下卸载钩子，退出隐身模式后重新安装。
+
+use crate::settings::{MouseButtonAction, MouseSideButtonSettings};
+use std::sync::{LazyLock, Mutex, OnceLock};
+use tauri::AppHandle;
+
+static CONFIG: LazyLock<Mutex<MouseSideButtonSettings>> =
+    LazyLock::new(|| Mutex::new(MouseSideButtonSettings::default()));
+
+static APP_HANDLE: OnceLock<AppHandle> = OnceLock::new();
+
+/// 启动时调用：加载设置，绑定了动作时安装鼠标钩子
+pub fn init(app: AppHandle, settings: MouseSideButtonSettings) {
+    let _ = APP_HANDLE.set(app);
+    apply(settings);
+}
+
+/// 设置变更后更新侧键绑定
+pub fn apply(settings: MouseSideButtonSettings) {
+    let has_action = settings.xbutton1 != MouseButtonAction::None
+        || settings.xbutton2 != MouseButtonAction::None;
+    *CONFIG.lock().unwrap() = settings;
+    if has_action {
+        self::windows::ensure_hook();
+    }
+}
+
+/// 进入 / 退出隐身模式时卸载或重新安装鼠标钩子
+pub fn set_hook_suspended(suspended: bool) {
+    self::windows::set_hook_suspended(suspended);
+}
+
+/// 侧键（1 或 2）在当前前台进程下应执行的动作，None 表示放行
+fn action_for(button: u16, foreground_process: Option<&str>) -> Option<MouseButtonAction> {
+    let config = CONFIG.lock().ok()?;
+    let action = match button {
+        1 => config.xbutton1,
+        2 => config.xbutton2,
+        _ => return None,
+    };
+    if action == MouseButtonAction::None {
+        return None;
+    }
+    if let Some(process) = foreground_process {
+        if config
+            .disabled_processes
+            .iter()
+            .any(|disabled| disabled.trim().eq_ignore_ascii_case(process))
+        {
+            return None;
+        }
+    }
+    Some(action)
+}
+
+/// 执行侧键动作（在工作线程中执行）
+fn run_action(action: MouseButtonAction) -> Result<(), String> {
+    let app = APP_HANDLE
+        .get()
+        .ok_or_else(|| "Mouse buttons not initialized".to_string())?
+        .clone();
+    match action {
+        MouseButtonAction::None => Ok(()),
+        MouseButtonAction::ShowLauncher => crate::commands::toggle_launcher(app),
+        MouseButtonAction::ClipboardPanel | MouseButtonAction::ClipboardPopup => {
+            let popup = action == MouseButtonAction::ClipboardPopup;
+            tauri::async_runtime::block_on(crate::commands::show_clipboard_window(app, Some(popup)))
+        }
+        MouseButtonAction::Back => self::windows::press_browser_key(false),
+        MouseButtonAction::Forward => self::windows::press_browser_key(true),
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod windows {
+    use crate::settings::MouseButtonAction;
+    use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+    use std::sync::mpsc;
+    use std::sync::{Mutex, OnceLock};
+    use windows_sys::Win32::Foundation::{HWND, LPARAM, LRESULT, WPARAM};
+    use windows_sys::Win32::System::Threading::GetCurrentThreadId;
+    use windows_sys::Win32::UI::WindowsAndMessaging::{
+        CallNextHookEx, DispatchMessageW, GetMessageW, PeekMessageW, PostThreadMessageW,
+        SetWindowsHookExW, TranslateMessage, UnhookWindowsHookEx, HHOOK, LLMHF_INJECTED, MSG,
+        MSLLHOOKSTRUCT, PM_NOREMOVE, WH_MOUSE_LL, WM_APP, WM_XBUTTONDOWN, WM_XBUTTONUP,
+    };
+
+    const VK_BROWSER_BACK: u16 = 0xA6;
+    const VK_BROWSER_FORWARD: u16 = 0xA7;
+
+    /// 投递到钩子线程的消息：wParam 非 0 时卸载钩子，为 0 时重新安装
+    const WM_SET_HOOK_SUSPENDED: u32 = WM_APP + 1;
+
+    static HOOK_STARTED: AtomicBool = AtomicBool::new(false);
+    /// 钩子线程 ID，线程未运行时为 0
+    static HOOK_THREAD_ID: AtomicU32 = AtomicU32::new(0);
+    static SENDER: OnceLock<Mutex<mpsc::Sender<MouseButtonAction>>> = OnceLock::new();
+    /// 被拦截的按下事件，对应的抬起事件也需要拦截
+    static SWALLOWED: [AtomicBool; 2] = [AtomicBool::new(false), AtomicBool::new(false)];
+    /// 最近一次查询的前台窗口及其进程名（钩子回调需尽快返回）
+    static FOREGROUND_CACHE: Mutex<(HWND, Option<String>)> = Mutex::new((0, None));
+
+    fn foreground_process() -> Option<String> {
+        let hwnd = crate::process_info::windows::get_foreground_window();
+        let mut cache = FOREGROUND_CACHE.lock().ok()?;
+        if cache.0 != hwnd {
+            *cache = (
+                hwnd,
+                crate::process_info::windows::get_window_process_name(hwnd),
+            );
+        }
+        cache.1.clone()
+    }
+
+    unsafe extern "system" fn mouse_proc(n_code: i32, w_param: WPARAM, l_param: LPARAM) -> LRESULT {
+        let message = w_param as u32;
+        if n_code >= 0 && (message == WM_XBUTTONDOWN || message == WM_XBUTTONUP) {
+            let hook_struct = &*(l_param as *const MSLLHOOKSTRUCT);
+            let button = (hook_struct.mouseData >> 16) as u16;
+            if hook_struct.flags & LLMHF_INJECTED == 0 && (button == 1 || button == 2) {
+                // panic 不能从 extern "system" 函数展开，捕获后放行按键并交给守护线程重启
+                let swallow = crate::hook_supervisor::catch_callback_panic("mouse_buttons", || {
+                    handle_button(message, button)
+                });
+                if swallow == Some(true) {
+                    return 1;
+                }
+            }
+        }
+        CallNextHookEx(0, n_code, w_param, l_param)
+    }
+
+    /// 处理侧键事件，返回是否拦截
+    fn handle_button(message: u32, button: u16) -> bool {
+        let swallowed = &SWALLOWED[button as usize - 1];
+        if message != WM_XBUTTONDOWN {
+            // 进入隐身模式前拦截了按下事件时，抬起事件也要拦截
+            return swallowed.swap(false, Ordering::SeqCst);
+        }
+        // 卸载消息送达前的侧键同样放行
+        if crate::stealth::is_enabled() {
+            return false;
+        }
+        let process = foreground_process();
+        let Some(action) = super::action_for(button, process.as_deref()) else {
+            return false;
+        };
+        swallowed.store(true, Ordering::SeqCst);
+        if let Some(sender) = SENDER.get() {
+            let _ = sender.lock().unwrap_or_else(|e| e.into_inner()).send(action);
+        }
+        true
+    }
+
+    /// 发送浏览器后退 / 前进键
+    pub fn press_browser_key(forward: bool) -> Result<(), String> {
+        let vk = if forward {
+            VK_BROWSER_FORWARD
+        } else {
+            VK_BROWSER_BACK
+        };
+        crate::text_input::windows::press_key(vk)
+    }
+
+    /// 首次需要时启动钩子线程与动作工作线程（钩子回调中不能阻塞）
+    pub fn ensure_hook() {
+        if HOOK_STARTED.swap(true, Ordering::SeqCst) {
+            return;
+        }
+
+        let (sender, receiver) = mpsc::channel::<MouseButtonAction>();
+        let _ = SENDER.set(Mutex::new(sender));
+        std::thread::spawn(move || {
+            for action in receiver {
+                if let Err(e) = super::run_action(action) {
+                    eprintln!("[MouseButtons] Failed to run {:?}: {}", action, e);
+                }
+            }
+        });
+
+        crate::hook_supervisor::spawn_supervised("mouse_buttons", || unsafe {
+            // 先创建线程消息队列，之后投递的挂起消息才不会丢失
+            let mut msg: MSG = std::mem::zeroed();
+            PeekMessageW(&mut msg, 0, 0, 0, PM_NOREMOVE);
+            let mut hook = HookThread { hook: 0 };
+            HOOK_THREAD_ID.store(GetCurrentThreadId(), Ordering::SeqCst);
+
+            // 隐身模式下暂不安装，退出隐身模式时由 WM_SET_HOOK_SUSPENDED 安装
+            if !crate::stealth::is_enabled() && !hook.install() {
+                return;
+            }
+
+            while GetMessageW(&mut msg, 0, 0, 0) > 0 {
+                if msg.hwnd == 0 && msg.message == WM_SET_HOOK_SUSPENDED {
+                    if msg.wParam != 0 {
+                        hook.uninstall();
+                    } else {
+                        hook.install();
+                    }
+                    continue;
+                }
+                TranslateMessage(&msg);
+                DispatchMessageW(&msg);
+            }
+            drop(hook);
+            crate::hook_supervisor::resume_callback_panic("mouse_buttons");
+        });
+    }
+
+    /// 钩子线程持有的钩子，线程体结束（包括 panic 展开）时卸载，守护线程重启时不会叠加旧钩子
+    struct HookThread {
+        hook: HHOOK,
+    }
+
+    impl HookThread {
+        unsafe fn install(&mut self) -> bool {
+            if self.hook != 0 {
+                return true;
+            }
+            self.hook = SetWindowsHookExW(WH_MOUSE_LL, Some(mouse_proc), 0, 0);
+            if self.hook == 0 {
+                eprintln!("[MouseButtons] Failed to install mouse hook");
+                return false;
+            }
+            eprintln!("[MouseButtons] Mouse hook installed");
+            true
+        }
+
+        unsafe fn uninstall(&mut self) {
+            if self.hook != 0 {
+                UnhookWindowsHookEx(self.hook);
+                self.hook = 0;
+                eprintln!("[MouseButtons] Mouse hook removed");
+            }
+        }
+    }
+
+    impl Drop for HookThread {
+        fn drop(&mut self) {
+            HOOK_THREAD_ID.store(0, Ordering::SeqCst);
+            unsafe { self.uninstall() };
+        }
+    }
+
+    /// 通知钩子线程卸载或重新安装钩子，钩子尚未启动时无需处理
+    pub fn set_hook_suspended(suspended: bool) {
+        let thread_id = HOOK_THREAD_ID.load(Ordering::SeqCst);
+        if thread_id == 0 {
+            return;
+        }
+        unsafe {
+            if PostThreadMessageW(thread_id, WM_SET_HOOK_SUSPENDED, suspended as WPARAM, 0) == 0 {
+                eprintln!("[MouseButtons] Failed to post hook suspend message");
+            }
+        }
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+mod windows {
+    pub fn press_browser_key(_forward: bool) -> Result<(), String> {
+        Err("Mouse side buttons are only supported on Windows".to_string())
+    }
+
+    pub fn ensure_hook() {}
+
+    pub fn set_hook_suspended(_suspended: bool) {}
+}
@@ -0,0 +1,192 @@
+//! 快速笔记（Scratchpad）
+//!
+//! 笔记以 Markdown 原文存储在 SQLite，notes_fts 提供全文搜索，供便签窗口与启动器使用
+
+use crate::db;
+use rusqlite::{params, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// trigram 分词要求关键字至少 3 个字符，更短时退化为 LIKE 匹配
+const FTS_MIN_QUERY_CHARS: usize = 3;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NoteItem {
+    pub id: String,
+    pub title: String,
+    /// Markdown 原文
+    pub content: String,
+    pub created_at: u64,
+    pub updated_at: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct NoteSearchHit {
+    #[serde(flatten)]
+    pub note: NoteItem,
+    /// 命中片段，关键字以 <mark></mark> 包裹
+    pub snippet: String,
+}
+
+fn now_ts() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+const NOTE_COLUMNS: &str = "id, title, content, created_at, updated_at";
+
+fn row_to_note(row: &rusqlite::Row) -> rusqlite::Result<NoteItem> {
+    Ok(NoteItem {
+        id: row.get(0)?,
+        title: row.get(1)?,
+        content: row.get(2)?,
+        created_at: row.get::<_, i64>(3)? as u64,
+        updated_at: row.get::<_, i64>(4)? as u64,
+    })
+}
+
+/// 未指定标题时取 Markdown 第一行非空文本（去掉标题符号）
+fn derive_title(content: &str) -> String {
+    content
+        .lines()
+        .map(|line| line.trim().trim_start_matches('#').trim())
+        .find(|line| !line.is_empty())
+        .map(|line| line.chars().take(50).collect())
+        .unwrap_or_else(|| "未命名笔记".to_string())
+}
+
+pub fn get_all_notes(app_data_dir: &Path) -> Result<Vec<NoteItem>, String> {
+    let conn = db::get_connection(app_data_dir)?;
+    let mut stmt = conn
+        .prepare(&format!("SELECT {} FROM notes ORDER BY updated_at DESC", NOTE_COLUMNS))
+        .map_err(|e| format!("Failed to prepare notes query: {}", e))?;
+    let rows = stmt
+        .query_map([], row_to_note)
+        .map_err(|e| format!("Failed to iterate notes: {}", e))?;
+
+    let mut items = Vec::new();
+    for row in rows {
+        items.push(row.map_err(|e| format!("Failed to read note row: {}", e))?);
+    }
+    Ok(items)
+}
+
+pub fn get_note(id: &str, app_data_dir: &Path) -> Result<Option<NoteItem>, String> {
+    let conn = db::get_connection(app_data_dir)?;
+    conn.query_row(
+        &format!("SELECT {} FROM notes WHERE id = ?1", NOTE_COLUMNS),
+        params![id],
+        row_to_note,
+    )
+    .optional()
+    .map_err(|e| format!("Failed to load note: {}", e))
+}
+
+/// 保存笔记（id 为空时新建），便签窗口自动保存时调用
+pub fn save_note(
+    id: Option<String>,
+    title: Option<String>,
+    content: String,
+    app_data_dir: &Path,
+) -> Result<NoteItem, String> {
+    let now = now_ts();
+    let title = title
+        .filter(|t| !t.trim().is_empty())
+        .unwrap_or_else(|| derive_title(&content));
+
+    let conn = db::get_connection(app_data_dir)?;
+    if let Some(id) = id {
+        if let Some(mut note) = get_note(&id, app_data_dir)? {
+            note.title = title;
+            note.content = content;
+            note.updated_at = now;
+            conn.execute(
+                "UPDATE notes SET title = ?1, content = ?2, updated_at = ?3 WHERE id = ?4",
+                params![note.title, note.content, note.updated_at as i64, note.id],
+            )
+            .map_err(|e| format!("Failed to update note: {}", e))?;
+            return Ok(note);
+        }
+    }
+
+    let note = NoteItem {
+        id: format!("note-{}-{:04x}", now, rand::random::<u16>()),
+        title,
+        content,
+        created_at: now,
+        updated_at: now,
+    };
+    conn.execute(
+        "INSERT INTO notes (id, title, content, created_at, updated_at) VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![note.id, note.title, note.content, note.created_at as i64, note.updated_at as i64],
+    )
+    .map_err(|e| format!("Failed to insert note: {}", e))?;
+    Ok(note)
+}
+
+pub fn delete_note(id: &str, app_data_dir: &Path) -> Result<(), String> {
+    let conn = db::get_connection(app_data_dir)?;
+    conn.execute("DELETE FROM notes WHERE id = ?1", params![id])
+        .map_err(|e| format!("Failed to delete note: {}", e))?;
+    Ok(())
+}
+
+/// 搜索笔记标题与内容，结果按相关度排序
+pub fn search_notes(query: &str, limit: usize, app_data_dir: &Path) -> Result<Vec<NoteSearchHit>, String> {
+    let query = query.trim();
+    if query.is_empty() {
+        return Ok(Vec::new());
+    }
+    let conn = db::get_connection(app_data_dir)?;
+
+    if query.chars().count() < FTS_MIN_QUERY_CHARS {
+        let like = format!("%{}%", query.to_lowercase());
+        let mut stmt = conn
+            .prepare(&format!(
+                "SELECT {} FROM notes WHERE lower(title) LIKE ?1 OR lower(content) LIKE ?1
+                 ORDER BY updated_at DESC LIMIT ?2",
+                NOTE_COLUMNS
+            ))
+            .map_err(|e| format!("Failed to prepare note search: {}", e))?;
+        let rows = stmt
+            .query_map(params![like, limit as i64], row_to_note)
+            .map_err(|e| format!("Failed to iterate note search: {}", e))?;
+
+        let mut items = Vec::new();
+        for row in rows {
+            let note = row.map_err(|e| format!("Failed to read note row: {}", e))?;
+            let snippet = note.content.chars().take(80).collect();
+            items.push(NoteSearchHit { note, snippet });
+        }
+        return Ok(items);
+    }
+
+    // 整体作为短语匹配，避免用户输入被解析为 FTS 语法
+    let phrase = format!("\"{}\"", query.replace('"', "\"\""));
+    let mut stmt = conn
+        .prepare(
+            "SELECT n.id, n.title, n.content, n.created_at, n.updated_at,
+                    snippet(notes_fts, -1, '<mark>', '</mark>', '…', 16)
+             FROM notes_fts JOIN notes n ON n.rowid = notes_fts.rowid
+             WHERE notes_fts MATCH ?1
+             ORDER BY bm25(notes_fts, 5.0, 1.0) LIMIT ?2",
+        )
+        .map_err(|e| format!("Failed to prepare note search: {}", e))?;
+    let rows = stmt
+        .query_map(params![phrase, limit as i64], |row| {
+            Ok(NoteSearchHit {
+                note: row_to_note(row)?,
+                snippet: row.get(5)?,
+            })
+        })
+        .map_err(|e| format!("Failed to iterate note search: {}", e))?;
+
+    let mut items = Vec::new();
+    for row in rows {
+        items.push(row.map_err(|e| format!("Failed to read note row: {}", e))?);
+    }
+    Ok(items)
+}
@@ -0,0 +1,455 @@
+//! 启动器中的软件安装（winget / scoop）
+//!
+//! 输入 "install nodejs" 时并行调用 winget search 与 scoop search，解析表格输出合并为结果列表；
+//! 选择结果后在后台进程中安装，逐行读取输出解析进度。winget 直接以参数列表启动，
+//! scoop 通过 `powershell -File scoop.ps1` 调用，均不经过命令行拼接。
+
+use serde::{Deserialize, Serialize};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+/// 每个来源最多返回的结果数
+const MAX_RESULTS_PER_SOURCE: usize = 20;
+const INSTALL_KEYWORD: &str = "install";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PackageSource {
+    Winget,
+    Scoop,
+}
+
+impl PackageSource {
+    pub fn label(self) -> &'static str {
+        match self {
+            PackageSource::Winget => "winget",
+            PackageSource::Scoop => "scoop",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PackageInfo {
+    pub source: PackageSource,
+    /// 安装时使用的标识（winget 为包 ID，scoop 为 "bucket/name"）
+    pub id: String,
+    pub name: String,
+    pub version: String,
+    /// winget 的来源（winget / msstore）或 scoop 的 bucket
+    pub repository: String,
+}
+
+/// 安装过程中的一行输出，percent 在能识别进度时给出（0-100）
+#[derive(Debug, Clone, Serialize)]
+pub struct InstallProgress {
+    pub line: String,
+    pub percent: Option<f32>,
+}
+
+/// 解析启动器输入 "install xxx"，返回要搜索的关键字
+pub fn parse_install_query(query: &str) -> Option<&str> {
+    let query = query.trim();
+    let (keyword, args) = query.split_once(char::is_whitespace)?;
+    if !keyword.eq_ignore_ascii_case(INSTALL_KEYWORD) {
+        return None;
+    }
+    let args = args.trim();
+    (!args.is_empty()).then_some(args)
+}
+
+/// 包标识只允许常见字符，避免被当作命令行选项或路径
+pub fn is_valid_package_id(id: &str) -> bool {
+    !id.is_empty()
+        && !id.starts_with('-')
+        && id.len() <= 256
+        && id
+            .chars()
+            .all(|c| c.is_alphanumeric() || "._+-/@".contains(c))
+}
+
+/// 同时搜索 winget 与 scoop，两者都不可用时返回错误
+pub fn search(keyword: &str) -> Result<Vec<PackageInfo>, String> {
+    let (winget, scoop) = std::thread::scope(|scope| {
+        let winget = scope.spawn(|| search_winget(keyword));
+        let scoop = scope.spawn(|| search_scoop(keyword));
+        (
+            winget
+                .join()
+                .unwrap_or_else(|_| Err("winget search panicked".to_string())),
+            scoop
+                .join()
+                .unwrap_or_else(|_| Err("scoop search panicked".to_string())),
+        )
+    });
+
+    let mut results = Vec::new();
+    let mut errors = Vec::new();
+    for (source, result) in [
+        (PackageSource::Winget, winget),
+        (PackageSource::Scoop, scoop),
+    ] {
+        match result {
+            Ok(items) => results.extend(items.into_iter().take(MAX_RESULTS_PER_SOURCE)),
+            Err(e) => {
+                eprintln!("[PackageManager] {} search failed: {}", source.label(), e);
+                errors.push(e);
+            }
+        }
+    }
+    if results.is_empty() && errors.len() == 2 {
+        return Err(errors.join("; "));
+    }
+    Ok(results)
+}
+
+fn search_winget(keyword: &str) -> Result<Vec<PackageInfo>, String> {
+    let output = hidden_command("winget")
+        .args([
+            "search",
+            "--query",
+            keyword,
+            "--accept-source-agreements",
+            "--disable-interactivity",
+        ])
+        .output()
+        .map_err(|e| format!("Failed to run winget: {}", e))?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    // 没有匹配时 winget 以非零状态退出且不输出表格；表头随系统语言变化，按列顺序读取：
+    // 名称、ID、版本、[匹配]、源
+    Ok(parse_table(&stdout)
+        .into_iter()
+        .filter_map(|row| {
+            let id = row.at(1)?.to_string();
+            let repository = if row.values.len() >= 4 {
+                row.at(row.values.len() - 1)
+            } else {
+                None
+            };
+            Some(PackageInfo {
+                source: PackageSource::Winget,
+                name: row.at(0).unwrap_or(&id).to_string(),
+                version: row.at(2).unwrap_or("").to_string(),
+                repository: repository.unwrap_or("winget").to_string(),
+                id,
+            })
+        })
+        .filter(|p| is_valid_package_id(&p.id))
+        .collect())
+}
+
+fn search_scoop(keyword: &str) -> Result<Vec<PackageInfo>, String> {
+    let script = scoop_script().ok_or_else(|| "Scoop is not installed".to_string())?;
+    let output = scoop_command(&script)
+        .args(["search", keyword])
+        .output()
+        .map_err(|e| format!("Failed to run scoop: {}", e))?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    let rows = parse_table(&stdout);
+    let packages: Vec<PackageInfo> = if rows.is_empty() {
+        parse_scoop_legacy(&stdout)
+    } else {
+        rows.into_iter()
+            .filter_map(|row| {
+                let name = row.get("Name")?.to_string();
+                let bucket = row.get("Source").unwrap_or("").to_string();
+                Some(PackageInfo {
+                    source: PackageSource::Scoop,
+                    id: scoop_id(&bucket, &name),
+                    version: row.get("Version").unwrap_or("").to_string(),
+                    repository: bucket,
+                    name,
+                })
+            })
+            .collect()
+    };
+    Ok(packages
+        .into_iter()
+        .filter(|p| is_valid_package_id(&p.id))
+        .collect())
+}
+
+fn scoop_id(bucket: &str, name: &str) -> String {
+    if bucket.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}/{}", bucket, name)
+    }
+}
+
+/// 旧版 scoop 的输出：
+/// ```text
+/// 'main' bucket:
+///     nodejs (20.1.0)
+/// ```
+fn parse_scoop_legacy(output: &str) -> Vec<PackageInfo> {
+    let mut bucket = String::new();
+    let mut packages = Vec::new();
+    for line in output.lines() {
+        let trimmed = line.trim();
+        if let Some(name) = trimmed
+            .strip_prefix('\'')
+            .and_then(|rest| rest.strip_suffix("' bucket:"))
+        {
+            bucket = name.to_string();
+        } else if line.starts_with(char::is_whitespace) && !trimmed.is_empty() {
+            let (name, version) = match trimmed.split_once(" (") {
+                Some((name, rest)) => (name, rest.split(')').next().unwrap_or("")),
+                None => (trimmed, ""),
+            };
+            packages.push(PackageInfo {
+                source: PackageSource::Scoop,
+                id: scoop_id(&bucket, name),
+                name: name.to_string(),
+                version: version.to_string(),
+                repository: bucket.clone(),
+            });
+        }
+    }
+    packages
+}
+
+/// 解析 winget / PowerShell 的表格输出：表头下一行为 "----" 分隔线，按表头列起始位置切分每一行
+fn parse_table(output: &str) -> Vec<TableRow> {
+    // 进度动画以 \r 覆盖同一行，只保留最后一段
+    let lines: Vec<&str> = output
+        .lines()
+        .map(|line| line.rsplit('\r').next().unwrap_or(line))
+        .collect();
+    let Some(separator) = lines.iter().position(|line| {
+        line.trim_start().starts_with("---") && line.trim().chars().all(|c| c == '-' || c == ' ')
+    }) else {
+        return Vec::new();
+    };
+    let Some(header) = separator.checked_sub(1).map(|i| lines[i]) else {
+        return Vec::new();
+    };
+
+    // 列起始位置按显示宽度计算（中文名称占两列）
+    let header_chars: Vec<char> = header.chars().collect();
+    let mut columns: Vec<(String, usize)> = Vec::new();
+    let mut index = 0;
+    while index < header_chars.len() {
+        if header_chars[index].is_whitespace() {
+            index += 1;
+            continue;
+        }
+        let start = index;
+        while index < header_chars.len() && !header_chars[index].is_whitespace() {
+            index += 1;
+        }
+        let name: String = header_chars[start..index].iter().collect();
+        columns.push((name, display_width(&header_chars[..start])));
+    }
+    if columns.is_empty() {
+        return Vec::new();
+    }
+
+    lines[separator + 1..]
+        .iter()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let chars: Vec<char> = line.chars().collect();
+            let values = columns
+                .iter()
+                .enumerate()
+                .map(|(i, (name, start))| {
+                    let end = columns.get(i + 1).map(|(_, s)| *s).unwrap_or(usize::MAX);
+                    (
+                        name.clone(),
+                        slice_by_width(&chars, *start, end).trim().to_string(),
+                    )
+                })
+                .collect();
+            TableRow { values }
+        })
+        .collect()
+}
+
+struct TableRow {
+    values: Vec<(String, String)>,
+}
+
+impl TableRow {
+    fn get(&self, column: &str) -> Option<&str> {
+        self.values
+            .iter()
+            .find(|(name, _)| name == column)
+            .map(|(_, value)| value.as_str())
+            .filter(|value| !value.is_empty())
+    }
+
+    fn at(&self, index: usize) -> Option<&str> {
+        self.values
+            .get(index)
+            .map(|(_, value)| value.as_str())
+            .filter(|value| !value.is_empty())
+    }
+}
+
+fn char_width(c: char) -> usize {
+    // 粗略判断全角字符
+    match c as u32 {
+        0x1100..=0x115F
+        | 0x2E80..=0xA4CF
+        | 0xAC00..=0xD7A3
+        | 0xF900..=0xFAFF
+        | 0xFE30..=0xFE4F
+        | 0xFF00..=0xFF60
+        | 0xFFE0..=0xFFE6 => 2,
+        _ => 1,
+    }
+}
+
+fn display_width(chars: &[char]) -> usize {
+    chars.iter().map(|&c| char_width(c)).sum()
+}
+
+fn slice_by_width(chars: &[char], start: usize, end: usize) -> String {
+    let mut width = 0;
+    let mut result = String::new();
+    for &c in chars {
+        if width >= start && width < end {
+            result.push(c);
+        }
+        width += char_width(c);
+        if width >= end {
+            break;
+        }
+    }
+    result
+}
+
+/// scoop.ps1 的路径：优先 SCOOP 环境变量，其次默认安装目录
+fn scoop_script() -> Option<PathBuf> {
+    let root = std::env::var_os("SCOOP").map(PathBuf::from).or_else(|| {
+        std::env::var_os("USERPROFILE").map(|home| PathBuf::from(home).join("scoop"))
+    })?;
+    let script = root.join("shims").join("scoop.ps1");
+    script.is_file().then_some(script)
+}
+
+fn scoop_command(script: &Path) -> Command {
+    let mut cmd = hidden_command("powershell");
+    cmd.args([
+        "-NoProfile",
+        "-NonInteractive",
+        "-ExecutionPolicy",
+        "Bypass",
+        "-File",
+    ])
+    .arg(script);
+    cmd
+}
+
+fn hidden_command(program: &str) -> Command {
+    #[allow(unused_mut)]
+    let mut cmd = Command::new(program);
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        cmd.creation_flags(0x08000000); // CREATE_NO_WINDOW
+    }
+    cmd
+}
+
+/// 安装命令（未启动）
+pub fn install_command(source: PackageSource, id: &str) -> Result<Command, String> {
+    if !is_valid_package_id(id) {
+        return Err(format!("Invalid package id: {}", id));
+    }
+    let mut cmd = match source {
+        PackageSource::Winget => {
+            let mut cmd = hidden_command("winget");
+            cmd.args([
+                "install",
+                "--id",
+                id,
+                "--exact",
+                "--silent",
+                "--accept-package-agreements",
+                "--accept-source-agreements",
+                "--disable-interactivity",
+            ]);
+            cmd
+        }
+        PackageSource::Scoop => {
+            let script = scoop_script().ok_or_else(|| "Scoop is not installed".to_string())?;
+            let mut cmd = scoop_command(&script);
+            cmd.args(["install", id]);
+            cmd
+        }
+    };
+    cmd.stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    Ok(cmd)
+}
+
+/// 逐行读取输出（\r 与 \n 都视为换行，进度条会反复覆盖同一行），相同的连续行只回调一次
+pub fn read_progress<R: Read>(mut reader: R, mut on_progress: impl FnMut(InstallProgress)) {
+    let mut buffer = [0u8; 4096];
+    let mut pending: Vec<u8> = Vec::new();
+    let mut last_line = String::new();
+    let mut emit = |bytes: &[u8], last_line: &mut String| {
+        let line = String::from_utf8_lossy(bytes).trim().to_string();
+        if line.is_empty() || line == *last_line {
+            return;
+        }
+        *last_line = line.clone();
+        let percent = parse_percent(&line);
+        on_progress(InstallProgress { line, percent });
+    };
+    loop {
+        let read = match reader.read(&mut buffer) {
+            Ok(0) | Err(_) => break,
+            Ok(n) => n,
+        };
+        for &b in &buffer[..read] {
+            if b == b'\r' || b == b'\n' {
+                emit(&pending, &mut last_line);
+                pending.clear();
+            } else {
+                pending.push(b);
+            }
+        }
+    }
+    emit(&pending, &mut last_line);
+}
+
+/// 识别 "45%" 或 "10.0 MB / 25.3 MB" 形式的进度
+fn parse_percent(line: &str) -> Option<f32> {
+    if let Some(pos) = line.rfind('%') {
+        let digits: String = line[..pos]
+            .chars()
+            .rev()
+            .take_while(|c| c.is_ascii_digit() || *c == '.')
+            .collect::<Vec<_>>()
+            .into_iter()
+            .rev()
+            .collect();
+        if let Ok(value) = digits.parse::<f32>() {
+            return Some(value.clamp(0.0, 100.0));
+        }
+    }
+
+    let (done, total) = line.split_once(" / ")?;
+    let number = |text: &str| -> Option<f64> {
+        let mut parts = text.split_whitespace();
+        let value = parts.next()?.parse::<f64>().ok()?;
+        let scale = match parts.next()?.to_ascii_uppercase().as_str() {
+            "B" => 1.0,
+            "KB" => 1024.0,
+            "MB" => 1024.0 * 1024.0,
+            "GB" => 1024.0 * 1024.0 * 1024.0,
+            _ => return None,
+        };
+        Some(value * scale)
+    };
+    // 已下载量前面可能是进度条字符，只取最后两段（数值与单位）
+    let done: Vec<&str> = done.split_whitespace().collect();
+    let done = number(&done[done.len().saturating_sub(2)..].join(" "))?;
+    let total = number(total)?;
+    (total > 0.0).then(|| ((done / total * 100.0) as f32).clamp(0.0, 100.0))
+}
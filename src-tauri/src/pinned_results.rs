@@ -0,0 +1,148 @@
+//! 启动器结果固定（Pin）
+//!
+//! 固定项显示在空查询默认列表顶部，按 sort_order 排序并持久化到 SQLite
+
+use crate::db;
+use rusqlite::{params, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PinnedResult {
+    /// 结果的唯一标识（路径或 ID）
+    pub key: String,
+    /// 结果类型，如 "app" / "file" / "plugin" / "url"
+    pub result_type: String,
+    pub title: String,
+    #[serde(default)]
+    pub path: Option<String>,
+    #[serde(default)]
+    pub icon: Option<String>,
+    #[serde(default)]
+    pub sort_order: i64,
+    #[serde(default)]
+    pub pinned_at: u64,
+}
+
+fn now_ts() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// 获取所有固定项（按 sort_order 排序）
+pub fn get_pinned_results(app_data_dir: &Path) -> Result<Vec<PinnedResult>, String> {
+    let conn = db::get_connection(app_data_dir)?;
+    let mut stmt = conn
+        .prepare(
+            "SELECT key, result_type, title, path, icon, sort_order, pinned_at
+             FROM pinned_results ORDER BY sort_order ASC, pinned_at ASC",
+        )
+        .map_err(|e| format!("Failed to prepare pinned results query: {}", e))?;
+
+    let rows = stmt
+        .query_map([], |row| {
+            Ok(PinnedResult {
+                key: row.get(0)?,
+                result_type: row.get(1)?,
+                title: row.get(2)?,
+                path: row.get(3)?,
+                icon: row.get(4)?,
+                sort_order: row.get(5)?,
+                pinned_at: row.get::<_, i64>(6)? as u64,
+            })
+        })
+        .map_err(|e| format!("Failed to iterate pinned results: {}", e))?;
+
+    let mut items = Vec::new();
+    for row in rows {
+        items.push(row.map_err(|e| format!("Failed to read pinned result row: {}", e))?);
+    }
+    Ok(items)
+}
+
+/// 固定结果；已固定时只更新显示信息，保留原有位置
+pub fn pin_result(mut item: PinnedResult, app_data_dir: &Path) -> Result<PinnedResult, String> {
+    let conn = db::get_connection(app_data_dir)?;
+
+    let existing: Option<(i64, i64)> = conn
+        .query_row(
+            "SELECT sort_order, pinned_at FROM pinned_results WHERE key = ?1",
+            params![item.key],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .optional()
+        .map_err(|e| format!("Failed to load pinned result: {}", e))?;
+
+    match existing {
+        Some((sort_order, pinned_at)) => {
+            item.sort_order = sort_order;
+            item.pinned_at = pinned_at as u64;
+        }
+        None => {
+            // 新固定项追加到末尾
+            let max_order: Option<i64> = conn
+                .query_row("SELECT MAX(sort_order) FROM pinned_results", [], |row| row.get(0))
+                .map_err(|e| format!("Failed to query pinned order: {}", e))?;
+            item.sort_order = max_order.map(|o| o + 1).unwrap_or(0);
+            item.pinned_at = now_ts();
+        }
+    }
+
+    conn.execute(
+        "INSERT OR REPLACE INTO pinned_results (key, result_type, title, path, icon, sort_order, pinned_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        params![
+            item.key,
+            item.result_type,
+            item.title,
+            item.path,
+            item.icon,
+            item.sort_order,
+            item.pinned_at as i64
+        ],
+    )
+    .map_err(|e| format!("Failed to pin result: {}", e))?;
+
+    Ok(item)
+}
+
+/// 取消固定
+pub fn unpin_result(key: &str, app_data_dir: &Path) -> Result<(), String> {
+    let conn = db::get_connection(app_data_dir)?;
+    conn.execute("DELETE FROM pinned_results WHERE key = ?1", params![key])
+        .map_err(|e| format!("Failed to unpin result: {}", e))?;
+    Ok(())
+}
+
+/// 按给定 key 顺序重排固定项，未列出的固定项保持相对顺序排在后面
+pub fn reorder_pinned_results(keys: &[String], app_data_dir: &Path) -> Result<(), String> {
+    let current = get_pinned_results(app_data_dir)?;
+    let mut ordered: Vec<&str> = keys
+        .iter()
+        .filter(|k| current.iter().any(|item| &item.key == *k))
+        .map(|k| k.as_str())
+        .collect();
+    for item in &current {
+        if !ordered.contains(&item.key.as_str()) {
+            ordered.push(&item.key);
+        }
+    }
+
+    let mut conn = db::get_connection(app_data_dir)?;
+    let tx = conn
+        .transaction()
+        .map_err(|e| format!("Failed to start pinned results transaction: {}", e))?;
+    for (index, key) in ordered.iter().enumerate() {
+        tx.execute(
+            "UPDATE pinned_results SET sort_order = ?1 WHERE key = ?2",
+            params![index as i64, key],
+        )
+        .map_err(|e| format!("Failed to update pinned order: {}", e))?;
+    }
+    tx.commit()
+        .map_err(|e| format!("Failed to commit pinned order: {}", e))?;
+    Ok(())
+}
@@ -0,0 +1,178 @@
+//! 回放播放列表
+//!
+//! 把多个录制编排为顺序执行的列表，每项可设置重复次数、间隔与速度
+
+use crate::db;
+use rusqlite::{params, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+fn default_repeat() -> u32 {
+    1
+}
+
+fn default_speed() -> f32 {
+    1.0
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlaylistItem {
+    /// 录制文件路径（如 "recordings/recording_xxx.json"）
+    pub recording: String,
+    #[serde(default = "default_repeat")]
+    pub repeat: u32,
+    /// 每次回放结束后的等待时间
+    #[serde(default)]
+    pub interval_ms: u64,
+    #[serde(default = "default_speed")]
+    pub speed: f32,
+}
+
+/// 某项回放失败时的处理策略
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OnError {
+    /// 停止整个播放列表
+    #[default]
+    Stop,
+    /// 跳过该项继续执行后续项
+    Skip,
+}
+
+impl OnError {
+    fn as_str(&self) -> &'static str {
+        match self {
+            OnError::Stop => "stop",
+            OnError::Skip => "skip",
+        }
+    }
+
+    fn parse(value: &str) -> Self {
+        match value {
+            "skip" => OnError::Skip,
+            _ => OnError::Stop,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Playlist {
+    pub id: String,
+    pub name: String,
+    pub items: Vec<PlaylistItem>,
+    #[serde(default)]
+    pub on_error: OnError,
+    /// 失败后应用策略前的重试次数
+    #[serde(default)]
+    pub max_retries: u32,
+    pub created_at: u64,
+    pub updated_at: u64,
+}
+
+fn now_ts() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+fn row_to_playlist(row: &rusqlite::Row) -> rusqlite::Result<Playlist> {
+    let items_json: String = row.get(2)?;
+    let on_error: String = row.get(3)?;
+    Ok(Playlist {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        items: serde_json::from_str(&items_json).unwrap_or_default(),
+        on_error: OnError::parse(&on_error),
+        max_retries: row.get::<_, i64>(4)? as u32,
+        created_at: row.get::<_, i64>(5)? as u64,
+        updated_at: row.get::<_, i64>(6)? as u64,
+    })
+}
+
+const PLAYLIST_COLUMNS: &str = "id, name, items, on_error, max_retries, created_at, updated_at";
+
+pub fn get_all_playlists(app_data_dir: &Path) -> Result<Vec<Playlist>, String> {
+    let conn = db::get_connection(app_data_dir)?;
+    let mut stmt = conn
+        .prepare(&format!("SELECT {} FROM playlists ORDER BY updated_at DESC", PLAYLIST_COLUMNS))
+        .map_err(|e| format!("Failed to prepare playlists query: {}", e))?;
+    let rows = stmt
+        .query_map([], row_to_playlist)
+        .map_err(|e| format!("Failed to iterate playlists: {}", e))?;
+
+    let mut items = Vec::new();
+    for row in rows {
+        items.push(row.map_err(|e| format!("Failed to read playlist row: {}", e))?);
+    }
+    Ok(items)
+}
+
+pub fn get_playlist(id: &str, app_data_dir: &Path) -> Result<Option<Playlist>, String> {
+    let conn = db::get_connection(app_data_dir)?;
+    conn.query_row(
+        &format!("SELECT {} FROM playlists WHERE id = ?1", PLAYLIST_COLUMNS),
+        params![id],
+        row_to_playlist,
+    )
+    .optional()
+    .map_err(|e| format!("Failed to load playlist: {}", e))
+}
+
+/// 新建或更新播放列表（id 为空时新建）
+pub fn save_playlist(
+    id: Option<String>,
+    name: String,
+    items: Vec<PlaylistItem>,
+    on_error: OnError,
+    max_retries: u32,
+    app_data_dir: &Path,
+) -> Result<Playlist, String> {
+    if items.is_empty() {
+        return Err("播放列表至少需要一个录制".to_string());
+    }
+    let now = now_ts();
+    let created_at = match &id {
+        Some(id) => get_playlist(id, app_data_dir)?
+            .map(|p| p.created_at)
+            .unwrap_or(now),
+        None => now,
+    };
+    let playlist = Playlist {
+        id: id.unwrap_or_else(|| format!("playlist-{}", now)),
+        name,
+        items,
+        on_error,
+        max_retries,
+        created_at,
+        updated_at: now,
+    };
+
+    let items_json = serde_json::to_string(&playlist.items)
+        .map_err(|e| format!("Failed to serialize playlist items: {}", e))?;
+    let conn = db::get_connection(app_data_dir)?;
+    conn.execute(
+        "INSERT OR REPLACE INTO playlists (id, name, items, on_error, max_retries, created_at, updated_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        params![
+            playlist.id,
+            playlist.name,
+            items_json,
+            playlist.on_error.as_str(),
+            playlist.max_retries as i64,
+            playlist.created_at as i64,
+            playlist.updated_at as i64
+        ],
+    )
+    .map_err(|e| format!("Failed to save playlist: {}", e))?;
+
+    Ok(playlist)
+}
+
+pub fn delete_playlist(id: &str, app_data_dir: &Path) -> Result<(), String> {
+    let conn = db::get_connection(app_data_dir)?;
+    conn.execute("DELETE FROM playlists WHERE id = ?1", params![id])
+        .map_err(|e| format!("Failed to delete playlist: {}", e))?;
+    Ok(())
+}
@@ -0,0 +1,145 @@
+//! 隐私擦除
+//!
+//! 一次性清除剪贴板历史、搜索历史、日志、录制与使用统计。文件先以零覆写再删除，
+//! 数据库记录在 secure_delete 下删除并 VACUUM，避免残留在空闲页或 WAL 中。
+
+use crate::db;
+use serde::Serialize;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// 执行擦除前必须输入的确认短语
+pub const WIPE_CONFIRM_PHRASE: &str = "清空所有数据";
+
+/// 被清空的数据表（剪贴板历史与各类使用统计）
+const WIPED_TABLES: &[&str] = &[
+    "clipboard_history",
+    "clipboard_deleted",
+    "open_history",
+    "file_history",
+    "plugin_usage",
+    "result_weights",
+    "query_history",
+];
+
+/// 被擦除的数据目录（相对于应用数据目录）
+const WIPED_DIRS: &[&str] = &["clipboard_images", "clipboard_blobs", "recordings"];
+
+/// 迁移到数据库前遗留的历史文件
+const LEGACY_FILES: &[&str] = &["open_history.json", "file_history.json"];
+
+#[derive(Debug, Clone, Serialize, Default)]
+pub struct WipeReport {
+    pub rows_deleted: usize,
+    pub files_wiped: usize,
+    /// 未能删除的文件（例如正被占用的当天日志）
+    pub failed_files: Vec<String>,
+}
+
+/// 以零覆写文件内容后删除
+fn shred_file(path: &Path) -> Result<(), String> {
+    let len = fs::metadata(path)
+        .map_err(|e| format!("Failed to read file metadata: {}", e))?
+        .len();
+    {
+        let mut file = fs::OpenOptions::new()
+            .write(true)
+            .open(path)
+            .map_err(|e| format!("Failed to open file for overwrite: {}", e))?;
+        let zeros = [0u8; 64 * 1024];
+        let mut remaining = len;
+        while remaining > 0 {
+            let chunk = remaining.min(zeros.len() as u64) as usize;
+            file.write_all(&zeros[..chunk])
+                .map_err(|e| format!("Failed to overwrite file: {}", e))?;
+            remaining -= chunk as u64;
+        }
+        file.sync_all()
+            .map_err(|e| format!("Failed to flush file: {}", e))?;
+    }
+    fs::remove_file(path).map_err(|e| format!("Failed to delete file: {}", e))
+}
+
+/// 擦除单个文件并把结果记入报告
+fn shred_into_report(path: &Path, report: &mut WipeReport) {
+    match shred_file(path) {
+        Ok(()) => report.files_wiped += 1,
+        Err(e) => {
+            eprintln!("[Privacy] {}: {}", path.display(), e);
+            report.failed_files.push(path.to_string_lossy().to_string());
+        }
+    }
+}
+
+/// 递归覆写并删除目录下的所有文件
+fn shred_dir(dir: &Path, report: &mut WipeReport) {
+    let Ok(entries) = fs::read_dir(dir) else { return };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            shred_dir(&path, report);
+            let _ = fs::remove_dir(&path);
+        } else {
+            shred_into_report(&path, report);
+        }
+    }
+}
+
+fn wipe_database(app_data_dir: &PathBuf) -> Result<usize, String> {
+    let rows = db::execute_write(app_data_dir, |conn| {
+        conn.pragma_update(None, "secure_delete", true)
+            .map_err(|e| format!("Failed to enable secure_delete: {}", e))?;
+        let mut rows = 0;
+        for table in WIPED_TABLES {
+            rows += conn
+                .execute(&format!("DELETE FROM {}", table), [])
+                .map_err(|e| format!("Failed to clear {}: {}", table, e))?;
+        }
+        Ok(rows)
+    })?;
+
+    // VACUUM 不能在事务中执行，使用独立连接；先截断 WAL 以清除旧页
+    let conn = db::get_connection(app_data_dir)?;
+    conn.execute_batch("PRAGMA wal_checkpoint(TRUNCATE); VACUUM;")
+        .map_err(|e| format!("Failed to vacuum database: {}", e))?;
+    Ok(rows)
+}
+
+/// 擦除所有隐私数据，确认短语不匹配时拒绝执行
+pub fn wipe_all_data(app_data_dir: &PathBuf, confirm_phrase: &str) -> Result<WipeReport, String> {
+    if confirm_phrase.trim() != WIPE_CONFIRM_PHRASE {
+        return Err(format!(
+            "Confirmation phrase mismatch: type \"{}\" to wipe all data",
+            WIPE_CONFIRM_PHRASE
+        ));
+    }
+
+    let mut report = WipeReport {
+        rows_deleted: wipe_database(app_data_dir)?,
+        ..Default::default()
+    };
+    // 清空内存中的历史缓存，避免之后被写回数据库
+    crate::open_history::lock_history()?.clear();
+    crate::file_history::lock_history_write()?.clear();
+    crate::suggest::clear_history_cache();
+
+    for name in LEGACY_FILES {
+        let path = app_data_dir.join(name);
+        if path.exists() {
+            shred_into_report(&path, &mut report);
+        }
+    }
+    for dir in WIPED_DIRS {
+        shred_dir(&app_data_dir.join(dir), &mut report);
+    }
+    shred_dir(&crate::logger::get_log_dir(), &mut report);
+
+    eprintln!(
+        "[Privacy] Wiped {} rows and {} files ({} failed)",
+        report.rows_deleted,
+        report.files_wiped,
+        report.failed_files.len()
+    );
+    Ok(report)
+}
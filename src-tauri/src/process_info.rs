@@ -0,0 +1,99 @@
+//! 进程信息工具
+//!
+//! 根据窗口句柄查询所属进程的可执行文件名，供录制、剪贴板来源等功能使用
+
+#[cfg(target_os = "windows")]
+pub mod windows {
+    use std::ffi::OsString;
+    use std::os::windows::ffi::OsStringExt;
+    use std::path::Path;
+    use windows_sys::Win32::Foundation::{CloseHandle, HWND};
+    use windows_sys::Win32::System::Threading::{
+        OpenProcess, QueryFullProcessImageNameW, PROCESS_NAME_WIN32,
+        PROCESS_QUERY_LIMITED_INFORMATION,
+    };
+    use windows_sys::Win32::UI::WindowsAndMessaging::{
+        GetForegroundWindow, GetWindowTextLengthW, GetWindowTextW, GetWindowThreadProcessId,
+    };
+
+    /// 获取进程的完整可执行文件路径
+    pub fn get_process_path(pid: u32) -> Option<String> {
+        if pid == 0 {
+            return None;
+        }
+        unsafe {
+            let handle = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, 0, pid);
+            if handle == 0 {
+                return None;
+            }
+            let mut buffer = [0u16; 1024];
+            let mut size = buffer.len() as u32;
+            let ok = QueryFullProcessImageNameW(handle, PROCESS_NAME_WIN32, buffer.as_mut_ptr(), &mut size);
+            CloseHandle(handle);
+            if ok == 0 {
+                return None;
+            }
+            Some(OsString::from_wide(&buffer[..size as usize]).to_string_lossy().to_string())
+        }
+    }
+
+    /// 获取窗口所属进程的 PID
+    pub fn get_window_process_id(hwnd: HWND) -> Option<u32> {
+        if hwnd == 0 {
+            return None;
+        }
+        let mut pid = 0u32;
+        unsafe {
+            GetWindowThreadProcessId(hwnd, &mut pid);
+        }
+        if pid == 0 { None } else { Some(pid) }
+    }
+
+    /// 获取窗口所属进程的可执行文件名（如 "chrome.exe"）
+    pub fn get_window_process_name(hwnd: HWND) -> Option<String> {
+        let path = get_process_path(get_window_process_id(hwnd)?)?;
+        Path::new(&path)
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+    }
+
+    /// 获取窗口标题，无标题时返回 None
+    pub fn get_window_title(hwnd: HWND) -> Option<String> {
+        if hwnd == 0 {
+            return None;
+        }
+        unsafe {
+            let len = GetWindowTextLengthW(hwnd);
+            if len <= 0 {
+                return None;
+            }
+            let mut buffer = vec![0u16; len as usize + 1];
+            let copied = GetWindowTextW(hwnd, buffer.as_mut_ptr(), buffer.len() as i32);
+            if copied <= 0 {
+                return None;
+            }
+            Some(OsString::from_wide(&buffer[..copied as usize]).to_string_lossy().to_string())
+        }
+    }
+
+    /// 获取当前前台窗口句柄
+    pub fn get_foreground_window() -> HWND {
+        unsafe { GetForegroundWindow() }
+    }
+
+    /// 获取当前前台窗口所属进程的可执行文件名
+    pub fn get_foreground_process_name() -> Option<String> {
+        get_window_process_name(get_foreground_window())
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+pub mod windows {
+    pub fn get_process_path(_pid: u32) -> Option<String> {
+        None
+    }
+
+    pub fn get_foreground_process_name() -> Option<String> {
+        None
+    }
+}
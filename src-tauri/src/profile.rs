@@ -0,0 +1,62 @@
+//! 多数据目录 Profile 支持
+//!
+//! 通过 `--profile work` 启动参数选择 Profile，不同 Profile 使用
+//! `<app_data_dir>/profiles/<name>` 作为独立的数据目录（数据库、配置、快捷指令）。
+
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+static PROFILE: OnceLock<Option<String>> = OnceLock::new();
+
+/// Profile 名称只允许字母、数字、`-` 与 `_`，防止路径穿越
+fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.len() <= 64
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// 从命令行参数中解析 Profile（支持 `--profile work` 与 `--profile=work`）
+pub fn parse_args<I: IntoIterator<Item = String>>(args: I) -> Option<String> {
+    let mut iter = args.into_iter();
+    while let Some(arg) = iter.next() {
+        let name = if arg == "--profile" {
+            iter.next()
+        } else if let Some(value) = arg.strip_prefix("--profile=") {
+            Some(value.to_string())
+        } else {
+            continue;
+        };
+        return match name {
+            Some(name) if is_valid_name(name.trim()) => Some(name.trim().to_string()),
+            Some(name) => {
+                eprintln!("[Profile] Invalid profile name ignored: {}", name);
+                None
+            }
+            None => None,
+        };
+    }
+    None
+}
+
+/// 初始化当前 Profile（启动时调用一次）
+pub fn init(profile: Option<String>) {
+    if let Some(name) = &profile {
+        eprintln!("[Profile] Using profile: {}", name);
+    }
+    let _ = PROFILE.set(profile);
+}
+
+/// 当前 Profile 名称，默认 Profile 返回 None
+pub fn current() -> Option<&'static str> {
+    PROFILE.get().and_then(|p| p.as_deref())
+}
+
+/// 将基础数据目录映射到当前 Profile 的数据目录
+pub fn apply(base: PathBuf) -> PathBuf {
+    match current() {
+        Some(name) => base.join("profiles").join(name),
+        None => base,
+    }
+}
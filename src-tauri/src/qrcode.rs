@@ -0,0 +1,568 @@
+//! 二维码生成
+//!
+//! 字节模式、纠错等级 M，按内容长度自动选择版本（1-40），选择惩罚分最低的掩码，
+//! 输出模块矩阵或 PNG 图片。
+
+use base64::{engine::general_purpose, Engine as _};
+
+/// 纠错等级 M 每块的纠错码字数（下标为版本号）
+const ECC_CODEWORDS_PER_BLOCK: [usize; 41] = [
+    0, 10, 16, 26, 18, 24, 16, 18, 22, 22, 26, 30, 22, 22, 24, 24, 28, 28, 26, 26, 26, 26, 28, 28,
+    28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28,
+];
+
+/// 纠错等级 M 的纠错块数（下标为版本号）
+const NUM_ERROR_CORRECTION_BLOCKS: [usize; 41] = [
+    0, 1, 1, 1, 2, 2, 4, 4, 4, 5, 5, 5, 8, 9, 9, 10, 10, 11, 13, 14, 16, 17, 17, 18, 20, 21, 23,
+    25, 26, 28, 29, 31, 33, 35, 37, 38, 40, 43, 45, 47, 49,
+];
+
+/// 纠错等级 M 的格式信息编码
+const ECC_FORMAT_BITS: u32 = 0;
+
+/// 二维码模块矩阵，true 表示深色
+pub struct QrCode {
+    size: usize,
+    modules: Vec<Vec<bool>>,
+    is_function: Vec<Vec<bool>>,
+}
+
+impl QrCode {
+    /// 以字节模式编码文本
+    pub fn encode_text(text: &str) -> Result<QrCode, String> {
+        Self::encode_text_with_mask(text, None)
+    }
+
+    /// mask 为 None 时选择惩罚分最低的掩码
+    fn encode_text_with_mask(text: &str, mask: Option<u32>) -> Result<QrCode, String> {
+        let data = text.as_bytes();
+        let version = (1..=40)
+            .find(|&ver| 4 + char_count_bits(ver) + data.len() * 8 <= num_data_codewords(ver) * 8)
+            .ok_or_else(|| "内容过长，无法生成二维码".to_string())?;
+
+        // 模式指示符、字符数、数据，再补终止符与填充字节
+        let mut bits = BitBuffer::default();
+        bits.append(0b0100, 4);
+        bits.append(data.len() as u32, char_count_bits(version));
+        for &b in data {
+            bits.append(b as u32, 8);
+        }
+        let capacity = num_data_codewords(version) * 8;
+        bits.append(0, (capacity - bits.0.len()).min(4));
+        bits.append(0, (8 - bits.0.len() % 8) % 8);
+        for pad in [0xEC, 0x11].iter().cycle() {
+            if bits.0.len() >= capacity {
+                break;
+            }
+            bits.append(*pad, 8);
+        }
+
+        let mut codewords = vec![0u8; bits.0.len() / 8];
+        for (i, &bit) in bits.0.iter().enumerate() {
+            codewords[i >> 3] |= (bit as u8) << (7 - (i & 7));
+        }
+
+        let size = version * 4 + 17;
+        let mut qr = QrCode {
+            size,
+            modules: vec![vec![false; size]; size],
+            is_function: vec![vec![false; size]; size],
+        };
+        qr.draw_function_patterns(version);
+        qr.draw_codewords(&add_ecc_and_interleave(&codewords, version));
+
+        let best_mask = match mask {
+            Some(mask) => mask,
+            None => {
+                let mut best_mask = 0;
+                let mut best_penalty = usize::MAX;
+                for mask in 0..8 {
+                    qr.apply_mask(mask);
+                    qr.draw_format_bits(mask);
+                    let penalty = qr.penalty_score();
+                    if penalty < best_penalty {
+                        best_mask = mask;
+                        best_penalty = penalty;
+                    }
+                    qr.apply_mask(mask); // 异或两次即撤销
+                }
+                best_mask
+            }
+        };
+        qr.apply_mask(best_mask);
+        qr.draw_format_bits(best_mask);
+        Ok(qr)
+    }
+
+    /// 渲染为灰度 PNG，scale 为每个模块的像素数，border 为四周留白的模块数
+    pub fn to_png(&self, scale: usize, border: usize) -> Result<Vec<u8>, String> {
+        let scale = scale.max(1);
+        let dim = (self.size + border * 2) * scale;
+        let mut pixels = vec![255u8; dim * dim];
+        for y in 0..self.size {
+            for x in 0..self.size {
+                if !self.modules[y][x] {
+                    continue;
+                }
+                for dy in 0..scale {
+                    let row = ((y + border) * scale + dy) * dim;
+                    let start = row + (x + border) * scale;
+                    pixels[start..start + scale].fill(0);
+                }
+            }
+        }
+
+        let mut output = Vec::new();
+        let mut encoder = png::Encoder::new(&mut output, dim as u32, dim as u32);
+        encoder.set_color(png::ColorType::Grayscale);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder
+            .write_header()
+            .map_err(|e| format!("Failed to write PNG header: {}", e))?;
+        writer
+            .write_image_data(&pixels)
+            .map_err(|e| format!("Failed to write PNG data: {}", e))?;
+        writer
+            .finish()
+            .map_err(|e| format!("Failed to finish PNG: {}", e))?;
+        Ok(output)
+    }
+
+    /// 渲染为 data:image/png;base64 URL
+    pub fn to_data_url(&self, scale: usize, border: usize) -> Result<String, String> {
+        Ok(format!(
+            "data:image/png;base64,{}",
+            general_purpose::STANDARD.encode(self.to_png(scale, border)?)
+        ))
+    }
+
+    fn set_function(&mut self, x: usize, y: usize, dark: bool) {
+        self.modules[y][x] = dark;
+        self.is_function[y][x] = true;
+    }
+
+    fn draw_function_patterns(&mut self, version: usize) {
+        let size = self.size;
+        for i in 0..size {
+            self.set_function(6, i, i % 2 == 0);
+            self.set_function(i, 6, i % 2 == 0);
+        }
+
+        for (cx, cy) in [(3, 3), (size - 4, 3), (3, size - 4)] {
+            for dy in -4i32..=4 {
+                for dx in -4i32..=4 {
+                    let (x, y) = (cx as i32 + dx, cy as i32 + dy);
+                    if x < 0 || y < 0 || x >= size as i32 || y >= size as i32 {
+                        continue;
+                    }
+                    let dist = dx.abs().max(dy.abs());
+                    self.set_function(x as usize, y as usize, dist != 2 && dist != 4);
+                }
+            }
+        }
+
+        let positions = alignment_positions(version, size);
+        let last = positions.len().saturating_sub(1);
+        for (i, &ax) in positions.iter().enumerate() {
+            for (j, &ay) in positions.iter().enumerate() {
+                // 与定位图案重叠的三个角不绘制
+                if (i == 0 && j == 0) || (i == 0 && j == last) || (i == last && j == 0) {
+                    continue;
+                }
+                for dy in -2i32..=2 {
+                    for dx in -2i32..=2 {
+                        let dist = dx.abs().max(dy.abs());
+                        self.set_function(
+                            (ax as i32 + dx) as usize,
+                            (ay as i32 + dy) as usize,
+                            dist != 1,
+                        );
+                    }
+                }
+            }
+        }
+
+        // 先用占位值标记格式信息区域，选定掩码后再写入
+        self.draw_format_bits(0);
+
+        if version >= 7 {
+            let mut rem = version as u32;
+            for _ in 0..12 {
+                rem = (rem << 1) ^ ((rem >> 11) * 0x1F25);
+            }
+            let bits = ((version as u32) << 12) | rem;
+            for i in 0..18 {
+                let dark = (bits >> i) & 1 != 0;
+                let a = size - 11 + i % 3;
+                let b = i / 3;
+                self.set_function(a, b, dark);
+                self.set_function(b, a, dark);
+            }
+        }
+    }
+
+    fn draw_format_bits(&mut self, mask: u32) {
+        let data = (ECC_FORMAT_BITS << 3) | mask;
+        let mut rem = data;
+        for _ in 0..10 {
+            rem = (rem << 1) ^ ((rem >> 9) * 0x537);
+        }
+        let bits = ((data << 10) | rem) ^ 0x5412;
+        let bit = |i: usize| (bits >> i) & 1 != 0;
+        let size = self.size;
+
+        for i in 0..=5 {
+            self.set_function(8, i, bit(i));
+        }
+        self.set_function(8, 7, bit(6));
+        self.set_function(8, 8, bit(7));
+        self.set_function(7, 8, bit(8));
+        for i in 9..15 {
+            self.set_function(14 - i, 8, bit(i));
+        }
+
+        for i in 0..8 {
+            self.set_function(size - 1 - i, 8, bit(i));
+        }
+        for i in 8..15 {
+            self.set_function(8, size - 15 + i, bit(i));
+        }
+        self.set_function(8, size - 8, true);
+    }
+
+    fn draw_codewords(&mut self, data: &[u8]) {
+        let size = self.size;
+        let mut i = 0;
+        let mut right = size as i32 - 1;
+        while right >= 1 {
+            if right == 6 {
+                right = 5;
+            }
+            for vert in 0..size {
+                for j in 0..2 {
+                    let x = (right - j) as usize;
+                    let upward = (right + 1) & 2 == 0;
+                    let y = if upward { size - 1 - vert } else { vert };
+                    if !self.is_function[y][x] && i < data.len() * 8 {
+                        self.modules[y][x] = (data[i >> 3] >> (7 - (i & 7))) & 1 != 0;
+                        i += 1;
+                    }
+                }
+            }
+            right -= 2;
+        }
+    }
+
+    fn apply_mask(&mut self, mask: u32) {
+        for y in 0..self.size {
+            for x in 0..self.size {
+                let invert = match mask {
+                    0 => (x + y) % 2 == 0,
+                    1 => y % 2 == 0,
+                    2 => x % 3 == 0,
+                    3 => (x + y) % 3 == 0,
+                    4 => (x / 3 + y / 2) % 2 == 0,
+                    5 => x * y % 2 + x * y % 3 == 0,
+                    6 => (x * y % 2 + x * y % 3) % 2 == 0,
+                    _ => ((x + y) % 2 + x * y % 3) % 2 == 0,
+                };
+                if invert && !self.is_function[y][x] {
+                    self.modules[y][x] = !self.modules[y][x];
+                }
+            }
+        }
+    }
+
+    /// 简化的掩码惩罚分：连续同色、2x2 同色块与深浅比例（不计类定位图案）
+    fn penalty_score(&self) -> usize {
+        let size = self.size;
+        let mut score = 0;
+        let run_penalty = |line: &mut dyn Iterator<Item = bool>| {
+            let mut penalty = 0;
+            let mut prev = None;
+            let mut run = 0;
+            for dark in line {
+                if Some(dark) == prev {
+                    run += 1;
+                } else {
+                    if run >= 5 {
+                        penalty += run - 2;
+                    }
+                    prev = Some(dark);
+                    run = 1;
+                }
+            }
+            if run >= 5 {
+                penalty += run - 2;
+            }
+            penalty
+        };
+        for i in 0..size {
+            score += run_penalty(&mut (0..size).map(|x| self.modules[i][x]));
+            score += run_penalty(&mut (0..size).map(|y| self.modules[y][i]));
+        }
+
+        for y in 0..size - 1 {
+            for x in 0..size - 1 {
+                let c = self.modules[y][x];
+                if c == self.modules[y][x + 1]
+                    && c == self.modules[y + 1][x]
+                    && c == self.modules[y + 1][x + 1]
+                {
+                    score += 3;
+                }
+            }
+        }
+
+        let dark = self.modules.iter().flatten().filter(|&&m| m).count();
+        let total = size * size;
+        let deviation = (dark * 20).abs_diff(total * 10);
+        score += deviation.div_ceil(total).saturating_sub(1) * 10;
+        score
+    }
+}
+
+#[derive(Default)]
+struct BitBuffer(Vec<bool>);
+
+impl BitBuffer {
+    fn append(&mut self, value: u32, len: usize) {
+        for i in (0..len).rev() {
+            self.0.push((value >> i) & 1 != 0);
+        }
+    }
+}
+
+fn char_count_bits(version: usize) -> usize {
+    if version <= 9 {
+        8
+    } else {
+        16
+    }
+}
+
+fn num_raw_data_modules(version: usize) -> usize {
+    let mut result = (16 * version + 128) * version + 64;
+    if version >= 2 {
+        let num_align = version / 7 + 2;
+        result -= (25 * num_align - 10) * num_align - 55;
+        if version >= 7 {
+            result -= 36;
+        }
+    }
+    result
+}
+
+fn num_data_codewords(version: usize) -> usize {
+    num_raw_data_modules(version) / 8
+        - ECC_CODEWORDS_PER_BLOCK[version] * NUM_ERROR_CORRECTION_BLOCKS[version]
+}
+
+fn alignment_positions(version: usize, size: usize) -> Vec<usize> {
+    if version == 1 {
+        return Vec::new();
+    }
+    let num_align = version / 7 + 2;
+    let step = if version == 32 {
+        26
+    } else {
+        (version * 4 + num_align * 2 + 1) / (num_align * 2 - 2) * 2
+    };
+    let mut result = vec![6];
+    let mut pos = size - 7;
+    for _ in 0..num_align - 1 {
+        result.insert(1, pos);
+        pos -= step;
+    }
+    result
+}
+
+/// 分块计算 Reed-Solomon 纠错码并交错排列
+fn add_ecc_and_interleave(data: &[u8], version: usize) -> Vec<u8> {
+    let num_blocks = NUM_ERROR_CORRECTION_BLOCKS[version];
+    let block_ecc_len = ECC_CODEWORDS_PER_BLOCK[version];
+    let raw_codewords = num_raw_data_modules(version) / 8;
+    let num_short_blocks = num_blocks - raw_codewords % num_blocks;
+    let short_block_len = raw_codewords / num_blocks;
+
+    let divisor = reed_solomon_divisor(block_ecc_len);
+    let mut blocks = Vec::with_capacity(num_blocks);
+    let mut k = 0;
+    for i in 0..num_blocks {
+        let len = short_block_len - block_ecc_len + usize::from(i >= num_short_blocks);
+        let mut block = data[k..k + len].to_vec();
+        k += len;
+        let ecc = reed_solomon_remainder(&block, &divisor);
+        if i < num_short_blocks {
+            block.push(0);
+        }
+        block.extend(ecc);
+        blocks.push(block);
+    }
+
+    let mut result = Vec::with_capacity(raw_codewords);
+    for i in 0..blocks[0].len() {
+        for (j, block) in blocks.iter().enumerate() {
+            // 短块的占位字节不输出
+            if i != short_block_len - block_ecc_len || j >= num_short_blocks {
+                result.push(block[i]);
+            }
+        }
+    }
+    result
+}
+
+fn reed_solomon_divisor(degree: usize) -> Vec<u8> {
+    let mut result = vec![0u8; degree];
+    result[degree - 1] = 1;
+    let mut root = 1u8;
+    for _ in 0..degree {
+        for j in 0..degree {
+            result[j] = gf_multiply(result[j], root);
+            if j + 1 < degree {
+                result[j] ^= result[j + 1];
+            }
+        }
+        root = gf_multiply(root, 0x02);
+    }
+    result
+}
+
+fn reed_solomon_remainder(data: &[u8], divisor: &[u8]) -> Vec<u8> {
+    let mut result = vec![0u8; divisor.len()];
+    for &b in data {
+        let factor = b ^ result.remove(0);
+        result.push(0);
+        for (r, &coef) in result.iter_mut().zip(divisor) {
+            *r ^= gf_multiply(coef, factor);
+        }
+    }
+    result
+}
+
+/// GF(2^8) 乘法（本原多项式 0x11D）
+fn gf_multiply(x: u8, y: u8) -> u8 {
+    let mut z: u32 = 0;
+    for i in (0..8).rev() {
+        z = (z << 1) ^ ((z >> 7) * 0x11D);
+        z ^= ((y as u32 >> i) & 1) * x as u32;
+    }
+    z as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VERSION_7_TEXT: &str = "http://192.168.1.23:53317/?token=0123456789abcdef0123456789abcdef\
+                                  &name=ReFast-Desktop&device=Windows-11-Workstation";
+
+    /// 参考实现（kazuhikoarase/qrcode-generator）对 "REFAST-01" 以掩码 2 生成的版本 1 矩阵
+    const VERSION_1_MASK_2: [&str; 21] = [
+        "#######..#.#..#######",
+        "#.....#..#..#.#.....#",
+        "#.###.#.#.###.#.###.#",
+        "#.###.#.#.....#.###.#",
+        "#.###.#.###.#.#.###.#",
+        "#.....#.##.#..#.....#",
+        "#######.#.#.#.#######",
+        "........#.#..........",
+        "#.#####....#..#####..",
+        "#.##.......##...###..",
+        ".#.####...#.#..#.###.",
+        "######..#.###....##.#",
+        "..##..#.###.###.#.#..",
+        "........###.#..#####.",
+        "#######...##.#.#...#.",
+        "#.....#.#.#....#.###.",
+        "#.###.#.##.#......#.#",
+        "#.###.#.#######...#..",
+        "#.###.#.###.#.#..##..",
+        "#.....#..######.###..",
+        "#######.###.#..#.###.",
+    ];
+
+    /// 参考实现对 VERSION_7_TEXT 以掩码 5 生成的版本 7 矩阵，含版本信息区域
+    const VERSION_7_MASK_5: [&str; 45] = [
+        "#######..###.#.#.#..#.#...#..#####..#.#######",
+        "#.....#.#.#.#......#.#..#.#.....##.#..#.....#",
+        "#.###.#.#.##.#.#.##.##.###..#.####.#..#.###.#",
+        "#.###.#.#..########..#..#.#.#..##..##.#.###.#",
+        "#.###.#...##.##.#..#######.#.####.###.#.###.#",
+        "#.....#..#.#..###.#.#...#.#....###....#.....#",
+        "#######.#.#.#.#.#.#.#.#.#.#.#.#.#.#.#.#######",
+        "........#.....#..##.#...#.###.###.##.........",
+        "#.....#.#..###....#.#####.##.#.#...#.##..###.",
+        "#...#..#.#..###########.#.##.####.#.#.#..#.#.",
+        ".###.###.###.....#...###.###.#....###.###..#.",
+        ".#.#.#....#.#..###.###.....##.#.##.##...#.#..",
+        ".###..#.###.####..##.#....#.##..##...#..#..#.",
+        "...#....###..#.#...##......#.###....#....###.",
+        ".#....######.###..#.#...####......#####...##.",
+        "..##...#####..#..####.#.#..#.#.#.#.###...####",
+        "###.#.#.##.#..#####.#...###........#..#....##",
+        "######.#.#..#......#.#.#.#.#..#.##..##..###.#",
+        "##....##.#.#.#...##....##...#...#.#.##..#...#",
+        ".####...###..#..##......#.###.#.#...#########",
+        "..#######..#...#.##.######.#.#.#....#####....",
+        "#.###...#.#..##.....#...###..###..###...###..",
+        "..#.#.#.##..######..#.#.#.#....#..#.#.#.#.##.",
+        "###.#...#.#..###...##...##.####.#...#...####.",
+        "#..######.##....#...#####.#####.##..#####...#",
+        "#.#.#....#.####...###.###...#.#.#..####..#..#",
+        ".#.#.##.#....###....#..#.###...#.##.##...###.",
+        "..#.#..######...#..#....#.#......###.#..#.##.",
+        "...####.#..#..#.#..###.#..#..#.#..#..##.##...",
+        "..#..#..#.####.#..#..##.#....###.....##..####",
+        "#.###.#.###.#.#.#.##..###...#..#.....####.###",
+        "##..#...#...#...##......##..#.###..##.#..###.",
+        ".#....#..##.#.#.#.#.#........##..##.#..##..#.",
+        "#..#...#.#...#..#.##...#.##.#.#####..#.##....",
+        "....#.#..#.#.....##.#.##..#......##....#####.",
+        ".####..#..#########.#.#####.####...########.#",
+        "#..##.##...#..##..#######...###.#.########.##",
+        "........#.#.#.#.#..##...##.#.###...##...###.#",
+        "#######..##.##.#.#.##.#.#.#....######.#.#.##.",
+        "#.....#...##..#..##.#...#.##.#..##.##...####.",
+        "#.###.#....###.###..########.###.#########..#",
+        "#.###.#..#..###.#..#####....####...#....#.###",
+        "#.###.#.....#.###.#.###......##.#...#.#####.#",
+        "#.....#..##.#.#.#..#####...##.###.##.#..#.#..",
+        "#######.#......##.#...#.###..#...#.##.#..#.#.",
+    ];
+
+    fn render(qr: &QrCode) -> Vec<String> {
+        qr.modules
+            .iter()
+            .map(|row| row.iter().map(|&dark| if dark { '#' } else { '.' }).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_version_1_matches_reference_matrix() {
+        let qr = QrCode::encode_text_with_mask("REFAST-01", Some(2)).unwrap();
+        assert_eq!(render(&qr), VERSION_1_MASK_2);
+    }
+
+    #[test]
+    fn test_version_7_matches_reference_matrix() {
+        let qr = QrCode::encode_text_with_mask(VERSION_7_TEXT, Some(5)).unwrap();
+        assert_eq!(render(&qr), VERSION_7_MASK_5);
+    }
+
+    #[test]
+    fn test_reed_solomon_known_answer() {
+        // ISO/IEC 18004 示例 "HELLO WORLD"（1-M）的数据码字与纠错码字
+        let data = [32, 91, 11, 120, 209, 114, 220, 77, 67, 64, 236, 17, 236, 17, 236, 17];
+        let ecc = reed_solomon_remainder(&data, &reed_solomon_divisor(10));
+        assert_eq!(ecc, [196, 35, 39, 119, 235, 215, 231, 226, 93, 23]);
+    }
+
+    #[test]
+    fn test_version_selection_by_length() {
+        assert_eq!(QrCode::encode_text("REFAST-01").unwrap().size, 21);
+        assert_eq!(QrCode::encode_text(VERSION_7_TEXT).unwrap().size, 45);
+        assert_eq!(QrCode::encode_text(&"a".repeat(2331)).unwrap().size, 177);
+        assert!(QrCode::encode_text(&"a".repeat(2332)).is_err());
+    }
+}
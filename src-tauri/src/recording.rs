@@ -1,5 +1,5 @@
+use crate::hires_clock;
 use serde::{Deserialize, Serialize};
-use std::time::Instant;
 
 #[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
 pub enum MouseButton {
@@ -24,43 +24,183 @@ pub struct RecordedEvent {
     pub x: Option<i32>,
     pub y: Option<i32>,
     pub time_offset_ms: u64,
+    /// 微秒级相对时间（旧录制文件没有该字段，为 0）
+    #[serde(default)]
+    pub time_offset_us: u64,
+    /// 步骤注释，在编辑器中填写，回放时显示在状态条与报告中
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+impl RecordedEvent {
+    /// 微秒级时间偏移，旧录制文件回退为 time_offset_ms 换算
+    pub fn offset_us(&self) -> u64 {
+        if self.time_offset_us > 0 {
+            self.time_offset_us
+        } else {
+            self.time_offset_ms.saturating_mul(1000)
+        }
+    }
+}
+
+/// 主屏分辨率与 DPI，录制时保存，回放前用于检查坐标是否会点偏
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScreenInfo {
+    pub width: i32,
+    pub height: i32,
+    pub dpi: u32,
+}
+
+impl ScreenInfo {
+    /// 当前主屏信息
+    #[cfg(target_os = "windows")]
+    pub fn current() -> Option<Self> {
+        use windows_sys::Win32::Graphics::Gdi::{GetDC, GetDeviceCaps, ReleaseDC, LOGPIXELSX};
+        use windows_sys::Win32::UI::WindowsAndMessaging::{
+            GetSystemMetrics, SM_CXSCREEN, SM_CYSCREEN,
+        };
+
+        unsafe {
+            let width = GetSystemMetrics(SM_CXSCREEN);
+            let height = GetSystemMetrics(SM_CYSCREEN);
+            if width <= 0 || height <= 0 {
+                return None;
+            }
+            let dc = GetDC(0);
+            let dpi = if dc != 0 {
+                let dpi = GetDeviceCaps(dc, LOGPIXELSX);
+                ReleaseDC(0, dc);
+                dpi.max(0) as u32
+            } else {
+                96
+            };
+            Some(Self { width, height, dpi })
+        }
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    pub fn current() -> Option<Self> {
+        None
+    }
+
+    /// 把录制屏幕上的坐标映射到当前屏幕的缩放系数 (x, y)
+    pub fn scale_to(&self, current: &ScreenInfo) -> (f64, f64) {
+        (
+            current.width as f64 / self.width.max(1) as f64,
+            current.height as f64 / self.height.max(1) as f64,
+        )
+    }
 }
 
 pub struct RecordingState {
-    pub start_instant: Option<Instant>,
+    /// 录制开始时的 QPC 微秒读数
+    pub start_us: Option<u64>,
     pub events: Vec<RecordedEvent>,
     pub is_recording: bool,
+    /// 暂停期间的输入不被记录
+    pub is_paused: bool,
+    /// 录制期间接收过输入的前台进程（按首次出现顺序）
+    pub target_apps: Vec<String>,
+    last_foreground: isize,
+    paused_at_us: Option<u64>,
+    /// 已结束的暂停累计时长（微秒），从时间轴中扣除
+    paused_total_us: u64,
 }
 
 impl RecordingState {
     pub fn new() -> Self {
         Self {
-            start_instant: None,
+            start_us: None,
             events: Vec::new(),
             is_recording: false,
+            is_paused: false,
+            target_apps: Vec::new(),
+            last_foreground: 0,
+            paused_at_us: None,
+            paused_total_us: 0,
         }
     }
 
     pub fn start(&mut self) {
-        self.start_instant = Some(Instant::now());
+        self.start_us = Some(hires_clock::now_us());
         self.events.clear();
+        self.target_apps.clear();
+        self.last_foreground = 0;
+        self.is_paused = false;
+        self.paused_at_us = None;
+        self.paused_total_us = 0;
         self.is_recording = true;
     }
 
     pub fn stop(&mut self) {
+        // 暂停中停止时先结束暂停
+        self.resume();
         self.is_recording = false;
-        self.start_instant = None;
+        self.start_us = None;
+    }
+
+    /// 暂停录制，返回状态是否发生变化
+    pub fn pause(&mut self) -> bool {
+        if !self.is_recording || self.is_paused {
+            return false;
+        }
+        self.is_paused = true;
+        self.paused_at_us = Some(hires_clock::now_us());
+        true
+    }
+
+    /// 继续录制，返回状态是否发生变化
+    pub fn resume(&mut self) -> bool {
+        if !self.is_paused {
+            return false;
+        }
+        if let Some(paused_at) = self.paused_at_us.take() {
+            self.paused_total_us += hires_clock::now_us().saturating_sub(paused_at);
+        }
+        self.is_paused = false;
+        true
+    }
+
+    /// 是否正在采集输入（录制中且未暂停）
+    pub fn is_capturing(&self) -> bool {
+        self.is_recording && !self.is_paused
     }
 
     pub fn add_event(&mut self, event: RecordedEvent) {
-        if self.is_recording {
+        if self.is_capturing() {
             self.events.push(event);
         }
     }
 
+    /// 记录前台窗口，仅在前台窗口变化时才解析进程名
+    pub fn note_foreground(&mut self, hwnd: isize, resolve: impl FnOnce() -> Option<String>) {
+        if !self.is_capturing() || hwnd == 0 || hwnd == self.last_foreground {
+            return;
+        }
+        self.last_foreground = hwnd;
+        if let Some(name) = resolve() {
+            if !self.target_apps.iter().any(|app| app.eq_ignore_ascii_case(&name)) {
+                self.target_apps.push(name);
+            }
+        }
+    }
+
+    /// 距录制开始的时长（微秒），已扣除暂停时长
+    pub fn get_time_offset_us(&self) -> Option<u64> {
+        self.start_us.map(|start| {
+            let now = hires_clock::now_us();
+            let current_pause = self
+                .paused_at_us
+                .map(|p| now.saturating_sub(p))
+                .unwrap_or(0);
+            now.saturating_sub(start)
+                .saturating_sub(self.paused_total_us + current_pause)
+        })
+    }
+
+    /// 距录制开始的时长（毫秒），已扣除暂停时长
     pub fn get_time_offset_ms(&self) -> Option<u64> {
-        self.start_instant
-            .map(|start| start.elapsed().as_millis() as u64)
+        self.get_time_offset_us().map(|us| us / 1000)
     }
 }
 
@@ -77,4 +217,9 @@ pub struct RecordingMeta {
     pub duration_ms: u64,
     pub event_count: usize,
     pub created_at: String,
+    #[serde(default)]
+    pub target_apps: Vec<String>,
+    /// 录制时的屏幕信息（旧录制文件没有）
+    #[serde(default)]
+    pub screen: Option<ScreenInfo>,
 }
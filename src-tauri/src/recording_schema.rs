@@ -0,0 +1,386 @@
+//! 录制文件格式的 JSON Schema 与校验
+//!
+//! 导入第三方或手写的录制脚本前按内置 schema 检查结构，返回带行号的错误列表，
+//! 避免回放到一半才因字段缺失或类型错误而中断。校验规则与 `RECORDING_SCHEMA` 保持一致。
+
+use crate::recording::RecordedEvent;
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+/// 录制文件的 JSON Schema（draft-07）
+pub const RECORDING_SCHEMA: &str = r##"{
+  "$schema": "http://json-schema.org/draft-07/schema#",
+  "title": "ReFast recording",
+  "type": "object",
+  "required": ["events", "duration_ms", "created_at"],
+  "properties": {
+    "events": { "type": "array", "items": { "$ref": "#/definitions/event" } },
+    "duration_ms": { "type": "integer", "minimum": 0 },
+    "created_at": { "type": "string" },
+    "target_apps": { "type": "array", "items": { "type": "string" } },
+    "screen": {
+      "type": ["object", "null"],
+      "required": ["width", "height", "dpi"],
+      "properties": {
+        "width": { "type": "integer", "minimum": 1 },
+        "height": { "type": "integer", "minimum": 1 },
+        "dpi": { "type": "integer", "minimum": 1 }
+      }
+    }
+  },
+  "definitions": {
+    "button": { "enum": ["Left", "Right", "Middle"] },
+    "event": {
+      "type": "object",
+      "required": ["event_type", "time_offset_ms"],
+      "properties": {
+        "event_type": {
+          "oneOf": [
+            { "const": "MouseMove" },
+            {
+              "type": "object",
+              "minProperties": 1,
+              "maxProperties": 1,
+              "properties": {
+                "MouseDown": { "type": "object", "required": ["button"], "properties": { "button": { "$ref": "#/definitions/button" } } },
+                "MouseUp": { "type": "object", "required": ["button"], "properties": { "button": { "$ref": "#/definitions/button" } } },
+                "MouseWheel": { "type": "object", "required": ["delta"], "properties": { "delta": { "type": "integer" } } },
+                "KeyDown": { "type": "object", "required": ["vk_code"], "properties": { "vk_code": { "type": "integer", "minimum": 1, "maximum": 255 } } },
+                "KeyUp": { "type": "object", "required": ["vk_code"], "properties": { "vk_code": { "type": "integer", "minimum": 1, "maximum": 255 } } }
+              },
+              "additionalProperties": false
+            }
+          ]
+        },
+        "x": { "type": ["integer", "null"] },
+        "y": { "type": ["integer", "null"] },
+        "time_offset_ms": { "type": "integer", "minimum": 0 },
+        "time_offset_us": { "type": "integer", "minimum": 0 },
+        "note": { "type": ["string", "null"] }
+      }
+    }
+  }
+}"##;
+
+/// 单条校验错误，line / column 从 1 开始，指向出错的值（无法定位时指向文件开头）
+#[derive(Debug, Clone, Serialize)]
+pub struct ValidationIssue {
+    pub line: usize,
+    pub column: usize,
+    /// 出错位置，如 "events[3].event_type"
+    pub path: String,
+    pub message: String,
+}
+
+/// 内置 schema 的 JSON 值
+pub fn schema() -> Value {
+    serde_json::from_str(RECORDING_SCHEMA).expect("built-in recording schema is valid JSON")
+}
+
+/// 校验录制文件内容，结构正确时返回空列表
+pub fn validate(content: &str) -> Vec<ValidationIssue> {
+    let root: Value = match serde_json::from_str(content) {
+        Ok(value) => value,
+        Err(e) => {
+            return vec![ValidationIssue {
+                line: e.line().max(1),
+                column: e.column().max(1),
+                path: String::new(),
+                message: format!("Invalid JSON: {}", e),
+            }]
+        }
+    };
+
+    let positions = Positions::scan(content);
+    let mut issues = Vec::new();
+    let mut report = |offset: usize, path: String, message: String| {
+        let (line, column) = line_column(content, offset);
+        issues.push(ValidationIssue {
+            line,
+            column,
+            path,
+            message,
+        });
+    };
+
+    let Some(root) = root.as_object() else {
+        report(
+            0,
+            String::new(),
+            "Recording must be a JSON object".to_string(),
+        );
+        return issues;
+    };
+    let key_offset = |key: &str| {
+        positions
+            .keys
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, o)| *o)
+            .unwrap_or(0)
+    };
+
+    match root.get("duration_ms") {
+        None => report(0, "duration_ms".into(), "Missing required field".into()),
+        Some(v) if v.as_u64().is_none() => report(
+            key_offset("duration_ms"),
+            "duration_ms".into(),
+            "Must be a non-negative integer".into(),
+        ),
+        _ => {}
+    }
+    match root.get("created_at") {
+        None => report(0, "created_at".into(), "Missing required field".into()),
+        Some(v) if !v.is_string() => report(
+            key_offset("created_at"),
+            "created_at".into(),
+            "Must be a string".into(),
+        ),
+        _ => {}
+    }
+    if let Some(apps) = root.get("target_apps") {
+        if !apps
+            .as_array()
+            .is_some_and(|list| list.iter().all(Value::is_string))
+        {
+            report(
+                key_offset("target_apps"),
+                "target_apps".into(),
+                "Must be an array of strings".into(),
+            );
+        }
+    }
+    if let Some(screen) = root.get("screen") {
+        if !screen.is_null() {
+            if let Some(message) = check_screen(screen) {
+                report(key_offset("screen"), "screen".into(), message);
+            }
+        }
+    }
+
+    match root.get("events") {
+        None => report(0, "events".into(), "Missing required field".into()),
+        Some(Value::Array(events)) => {
+            for (index, event) in events.iter().enumerate() {
+                let offset = positions
+                    .events
+                    .get(index)
+                    .copied()
+                    .unwrap_or_else(|| key_offset("events"));
+                for (field, message) in check_event(event) {
+                    let path = if field.is_empty() {
+                        format!("events[{}]", index)
+                    } else {
+                        format!("events[{}].{}", index, field)
+                    };
+                    report(offset, path, message);
+                }
+            }
+        }
+        Some(_) => report(
+            key_offset("events"),
+            "events".into(),
+            "Must be an array".into(),
+        ),
+    }
+
+    issues
+}
+
+fn check_screen(screen: &Value) -> Option<String> {
+    let Some(screen) = screen.as_object() else {
+        return Some("Must be an object or null".to_string());
+    };
+    for field in ["width", "height", "dpi"] {
+        if !screen
+            .get(field)
+            .and_then(Value::as_u64)
+            .is_some_and(|v| (1..=i32::MAX as u64).contains(&v))
+        {
+            return Some(format!("'{}' must be a positive integer", field));
+        }
+    }
+    None
+}
+
+/// 检查单个事件，返回 (字段, 错误信息)，字段为空表示事件本身
+fn check_event(event: &Value) -> Vec<(String, String)> {
+    let mut issues = Vec::new();
+    let Some(object) = event.as_object() else {
+        issues.push((String::new(), "Event must be an object".to_string()));
+        return issues;
+    };
+
+    match object.get("event_type") {
+        None => issues.push((
+            "event_type".to_string(),
+            "Missing required field".to_string(),
+        )),
+        Some(event_type) => {
+            if let Err(message) = check_event_type(event_type) {
+                issues.push(("event_type".to_string(), message));
+            }
+        }
+    }
+    match object.get("time_offset_ms") {
+        None => issues.push((
+            "time_offset_ms".to_string(),
+            "Missing required field".to_string(),
+        )),
+        Some(v) if v.as_u64().is_none() => issues.push((
+            "time_offset_ms".to_string(),
+            "Must be a non-negative integer".to_string(),
+        )),
+        _ => {}
+    }
+    if object
+        .get("time_offset_us")
+        .is_some_and(|v| v.as_u64().is_none())
+    {
+        issues.push((
+            "time_offset_us".to_string(),
+            "Must be a non-negative integer".to_string(),
+        ));
+    }
+    for field in ["x", "y"] {
+        if let Some(v) = object.get(field) {
+            if !v.is_null() && !v.as_i64().is_some_and(|n| i32::try_from(n).is_ok()) {
+                issues.push((
+                    field.to_string(),
+                    "Must be a 32-bit integer or null".to_string(),
+                ));
+            }
+        }
+    }
+    if object
+        .get("note")
+        .is_some_and(|v| !v.is_null() && !v.is_string())
+    {
+        issues.push(("note".to_string(), "Must be a string or null".to_string()));
+    }
+
+    // 兜底：规则之外的问题以实际反序列化结果为准
+    if issues.is_empty() {
+        if let Err(e) = serde_json::from_value::<RecordedEvent>(event.clone()) {
+            issues.push((String::new(), e.to_string()));
+        }
+    }
+    issues
+}
+
+fn check_event_type(event_type: &Value) -> Result<(), String> {
+    const EXPECTED: &str =
+        "Expected \"MouseMove\" or one of MouseDown / MouseUp / MouseWheel / KeyDown / KeyUp";
+    if event_type.as_str() == Some("MouseMove") {
+        return Ok(());
+    }
+    let Some(object) = event_type.as_object().filter(|o| o.len() == 1) else {
+        return Err(EXPECTED.to_string());
+    };
+    let (kind, payload) = object.iter().next().unwrap();
+    let payload: &Map<String, Value> = payload
+        .as_object()
+        .ok_or_else(|| format!("{} must be an object", kind))?;
+    match kind.as_str() {
+        "MouseDown" | "MouseUp" => match payload.get("button").and_then(Value::as_str) {
+            Some("Left" | "Right" | "Middle") => Ok(()),
+            _ => Err(format!(
+                "{}.button must be \"Left\", \"Right\" or \"Middle\"",
+                kind
+            )),
+        },
+        "MouseWheel" => match payload.get("delta").and_then(Value::as_i64) {
+            Some(delta) if i32::try_from(delta).is_ok() => Ok(()),
+            _ => Err("MouseWheel.delta must be a 32-bit integer".to_string()),
+        },
+        "KeyDown" | "KeyUp" => match payload.get("vk_code").and_then(Value::as_u64) {
+            Some(1..=255) => Ok(()),
+            _ => Err(format!(
+                "{}.vk_code must be a virtual-key code between 1 and 255",
+                kind
+            )),
+        },
+        _ => Err(EXPECTED.to_string()),
+    }
+}
+
+/// 字节偏移转为行列（从 1 开始，列按字符计）
+fn line_column(content: &str, offset: usize) -> (usize, usize) {
+    let before = &content[..offset.min(content.len())];
+    let line = before.matches('\n').count() + 1;
+    let column = before
+        .rsplit('\n')
+        .next()
+        .map(|s| s.chars().count())
+        .unwrap_or(0)
+        + 1;
+    (line, column)
+}
+
+/// 顶层字段与 events 数组元素在原文中的起始偏移
+#[derive(Default)]
+struct Positions {
+    keys: Vec<(String, usize)>,
+    events: Vec<usize>,
+}
+
+impl Positions {
+    /// 只在 JSON 已解析成功后调用：逐字符跟踪嵌套深度与字符串，记录位置
+    fn scan(content: &str) -> Self {
+        let mut positions = Positions::default();
+        let bytes = content.as_bytes();
+        let mut depth = 0usize;
+        let mut in_events = false;
+        let mut expect_value = false;
+        let mut i = 0;
+        while i < bytes.len() {
+            let b = bytes[i];
+            if b.is_ascii_whitespace() {
+                i += 1;
+                continue;
+            }
+            // events 数组中每个元素的起始位置
+            if in_events && depth == 2 && expect_value && b != b']' {
+                positions.events.push(i);
+                expect_value = false;
+            }
+            match b {
+                b'"' => {
+                    let start = i;
+                    i += 1;
+                    while i < bytes.len() && bytes[i] != b'"' {
+                        i += if bytes[i] == b'\\' { 2 } else { 1 };
+                    }
+                    // 顶层对象的键：下一个非空白字符是冒号
+                    if depth == 1 {
+                        let rest = content.get(i + 1..).unwrap_or("").trim_start();
+                        if rest.starts_with(':') {
+                            let key = content[start + 1..i].to_string();
+                            positions.keys.push((key, start));
+                        }
+                    }
+                }
+                b'{' | b'[' => {
+                    depth += 1;
+                    if depth == 2 && b == b'[' {
+                        in_events = positions
+                            .keys
+                            .last()
+                            .is_some_and(|(key, _)| key == "events");
+                        expect_value = in_events;
+                    }
+                }
+                b'}' | b']' => {
+                    if depth == 2 && b == b']' {
+                        in_events = false;
+                    }
+                    depth = depth.saturating_sub(1);
+                }
+                b',' if in_events && depth == 2 => expect_value = true,
+                _ => {}
+            }
+            i += 1;
+        }
+        positions
+    }
+}
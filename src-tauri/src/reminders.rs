@@ -0,0 +1,149 @@
+//! 定时提醒
+//!
+//! 到点后弹出带"稍后提醒"/"完成"按钮的 Toast，点击"稍后提醒"再提醒 10 分钟。
+//! 提醒仅保存在内存中，应用退出后失效。
+
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+
+/// 稍后提醒的间隔
+const SNOOZE_MINUTES: u64 = 10;
+/// 勿扰期间到期的提醒每隔多久重新检查一次
+const DND_RETRY_SECS: u64 = 60;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Reminder {
+    pub id: String,
+    pub title: String,
+    pub body: String,
+    /// 下次提醒时间（Unix 秒）
+    pub due_at: u64,
+}
+
+static REMINDERS: LazyLock<Mutex<HashMap<String, Reminder>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+fn now_ts() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// 创建提醒，delay_secs 秒后触发
+pub fn schedule(app: &AppHandle, title: String, body: String, delay_secs: u64) -> Reminder {
+    let reminder = Reminder {
+        id: format!("reminder-{}-{:04x}", now_ts(), rand::random::<u16>()),
+        title,
+        body,
+        due_at: now_ts() + delay_secs,
+    };
+    if let Ok(mut reminders) = REMINDERS.lock() {
+        reminders.insert(reminder.id.clone(), reminder.clone());
+    }
+    spawn_timer(app.clone(), reminder.id.clone(), reminder.due_at);
+    reminder
+}
+
+/// 列出未完成的提醒（按触发时间排序）
+pub fn list() -> Vec<Reminder> {
+    let mut reminders: Vec<Reminder> = REMINDERS
+        .lock()
+        .map(|r| r.values().cloned().collect())
+        .unwrap_or_default();
+    reminders.sort_by_key(|r| r.due_at);
+    reminders
+}
+
+/// 取消/完成提醒
+pub fn cancel(id: &str) -> bool {
+    REMINDERS
+        .lock()
+        .map(|mut r| r.remove(id).is_some())
+        .unwrap_or(false)
+}
+
+/// 稍后提醒：把触发时间推迟 SNOOZE_MINUTES 分钟
+pub fn snooze(app: &AppHandle, id: &str) -> Option<Reminder> {
+    let reminder = {
+        let mut reminders = REMINDERS.lock().ok()?;
+        let reminder = reminders.get_mut(id)?;
+        reminder.due_at = now_ts() + SNOOZE_MINUTES * 60;
+        reminder.clone()
+    };
+    spawn_timer(app.clone(), reminder.id.clone(), reminder.due_at);
+    Some(reminder)
+}
+
+/// 等到 due_at 后弹出通知；期间提醒被取消或改期（due_at 变化）则放弃
+fn spawn_timer(app: AppHandle, id: String, due_at: u64) {
+    std::thread::spawn(move || {
+        std::thread::sleep(Duration::from_secs(due_at.saturating_sub(now_ts())));
+
+        let reminder = match REMINDERS.lock() {
+            Ok(reminders) => match reminders.get(&id) {
+                Some(r) if r.due_at == due_at => r.clone(),
+                _ => return,
+            },
+            Err(_) => return,
+        };
+        fire(&app, &reminder);
+    });
+}
+
+fn fire(app: &AppHandle, reminder: &Reminder) {
+    // 勿扰期间推迟提醒，结束后再弹出
+    if crate::dnd::is_active() {
+        let due_at = now_ts() + DND_RETRY_SECS;
+        if let Ok(mut reminders) = REMINDERS.lock() {
+            if let Some(r) = reminders.get_mut(&reminder.id) {
+                r.due_at = due_at;
+            }
+        }
+        spawn_timer(app.clone(), reminder.id.clone(), due_at);
+        return;
+    }
+
+    let _ = app.emit("reminder-fired", reminder);
+
+    let actions = [
+        crate::toast::ToastAction {
+            label: format!("{} 分钟后提醒", SNOOZE_MINUTES),
+            argument: format!("snooze:{}", reminder.id),
+        },
+        crate::toast::ToastAction {
+            label: "完成".to_string(),
+            argument: format!("done:{}", reminder.id),
+        },
+    ];
+    let app_handle = app.clone();
+    let result = crate::toast::show_toast_with_actions(
+        app,
+        &reminder.id,
+        &reminder.title,
+        &reminder.body,
+        &actions,
+        move |argument| handle_toast_action(&app_handle, &argument),
+    );
+    if let Err(e) = result {
+        eprintln!("[Reminders] Failed to show reminder toast: {}", e);
+    }
+}
+
+/// 处理 Toast 按钮回调（参数格式为 "snooze:<id>" / "done:<id>"）
+fn handle_toast_action(app: &AppHandle, argument: &str) {
+    match argument.split_once(':') {
+        Some(("snooze", id)) => {
+            snooze(app, id);
+        }
+        Some(("done", id)) => {
+            cancel(id);
+        }
+        // 点击通知正文：保留提醒，等待用户在应用内处理
+        _ => {}
+    }
+    let _ = app.emit("reminders-changed", ());
+}
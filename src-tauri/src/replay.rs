@@ -1,13 +1,267 @@
-use crate::recording::{EventType, MouseButton, RecordedEvent};
+use crate::recording::{EventType, MouseButton, RecordedEvent, ScreenInfo};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
 use serde_json;
 use std::fs;
 use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// 回放拟人化配置：随机化键盘事件间隔，并在注入失败时重试；smooth_move 控制鼠标轨迹平滑
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct HumanizeOptions {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_min_key_interval_ms")]
+    pub min_key_interval_ms: u64,
+    #[serde(default = "default_max_key_interval_ms")]
+    pub max_key_interval_ms: u64,
+    #[serde(default = "default_max_retries")]
+    pub max_retries: u32,
+    #[serde(default)]
+    pub smooth_move: SmoothMoveOptions,
+}
+
+/// 鼠标轨迹形状
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum MovePath {
+    Linear,
+    /// 三次贝塞尔曲线，控制点随机偏离直线，轨迹略带弧度
+    #[default]
+    Bezier,
+}
+
+/// 鼠标移动的速度曲线
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum MoveEasing {
+    Linear,
+    #[default]
+    EaseInOut,
+    EaseOut,
+}
+
+impl MoveEasing {
+    fn apply(self, t: f64) -> f64 {
+        match self {
+            MoveEasing::Linear => t,
+            MoveEasing::EaseInOut => t * t * (3.0 - 2.0 * t),
+            MoveEasing::EaseOut => 1.0 - (1.0 - t).powi(3),
+        }
+    }
+}
+
+/// 鼠标轨迹平滑：光标与目标点距离超过 min_distance 时插值移动过去，避免瞬移点击
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SmoothMoveOptions {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    pub path: MovePath,
+    #[serde(default)]
+    pub easing: MoveEasing,
+    /// 移动最长耗时（毫秒），短距离按比例缩短
+    #[serde(default = "default_move_duration_ms")]
+    pub duration_ms: u64,
+    #[serde(default = "default_min_move_distance")]
+    pub min_distance: u32,
+}
+
+fn default_move_duration_ms() -> u64 {
+    250
+}
+
+fn default_min_move_distance() -> u32 {
+    40
+}
+
+/// 插值步进间隔
+const SMOOTH_MOVE_STEP_US: u64 = 8_000;
+/// 达到最长耗时的移动距离（像素）
+const SMOOTH_MOVE_FULL_DISTANCE: f64 = 800.0;
+
+impl Default for SmoothMoveOptions {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            path: MovePath::default(),
+            easing: MoveEasing::default(),
+            duration_ms: default_move_duration_ms(),
+            min_distance: default_min_move_distance(),
+        }
+    }
+}
+
+impl SmoothMoveOptions {
+    /// 从 from 移动到 to 所需时间（微秒），未启用或距离太近时为 0
+    pub fn duration_us(&self, from: (i32, i32), to: (i32, i32)) -> u64 {
+        if !self.enabled {
+            return 0;
+        }
+        let distance = distance(from, to);
+        if distance < self.min_distance as f64 {
+            return 0;
+        }
+        let ratio = (distance / SMOOTH_MOVE_FULL_DISTANCE).clamp(0.3, 1.0);
+        (self.duration_ms.min(2_000) as f64 * 1000.0 * ratio) as u64
+    }
+
+    /// 生成插值轨迹点（不含起点，含终点）
+    pub fn path_points(&self, from: (i32, i32), to: (i32, i32), duration_us: u64) -> Vec<(i32, i32)> {
+        let steps = (duration_us / SMOOTH_MOVE_STEP_US).max(1) as usize;
+        let (x0, y0) = (from.0 as f64, from.1 as f64);
+        let (x3, y3) = (to.0 as f64, to.1 as f64);
+
+        // 控制点沿法线方向随机偏移，偏移量为距离的 5%~20%
+        let (c1, c2) = match self.path {
+            MovePath::Linear => ((x0, y0), (x3, y3)),
+            MovePath::Bezier => {
+                let len = distance(from, to).max(1.0);
+                let (nx, ny) = (-(y3 - y0) / len, (x3 - x0) / len);
+                let mut rng = rand::thread_rng();
+                let sign = if rng.gen_bool(0.5) { 1.0 } else { -1.0 };
+                let o1 = len * rng.gen_range(0.05..0.2) * sign;
+                let o2 = len * rng.gen_range(0.05..0.2) * sign;
+                (
+                    (x0 + (x3 - x0) / 3.0 + nx * o1, y0 + (y3 - y0) / 3.0 + ny * o1),
+                    (x0 + (x3 - x0) * 2.0 / 3.0 + nx * o2, y0 + (y3 - y0) * 2.0 / 3.0 + ny * o2),
+                )
+            }
+        };
+
+        (1..=steps)
+            .map(|i| {
+                let t = self.easing.apply(i as f64 / steps as f64);
+                let u = 1.0 - t;
+                let x = u * u * u * x0 + 3.0 * u * u * t * c1.0 + 3.0 * u * t * t * c2.0 + t * t * t * x3;
+                let y = u * u * u * y0 + 3.0 * u * u * t * c1.1 + 3.0 * u * t * t * c2.1 + t * t * t * y3;
+                (x.round() as i32, y.round() as i32)
+            })
+            .collect()
+    }
+}
+
+fn distance(from: (i32, i32), to: (i32, i32)) -> f64 {
+    let dx = (to.0 - from.0) as f64;
+    let dy = (to.1 - from.1) as f64;
+    (dx * dx + dy * dy).sqrt()
+}
+
+fn default_min_key_interval_ms() -> u64 {
+    40
+}
+
+fn default_max_key_interval_ms() -> u64 {
+    160
+}
+
+fn default_max_retries() -> u32 {
+    2
+}
+
+impl Default for HumanizeOptions {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            min_key_interval_ms: default_min_key_interval_ms(),
+            max_key_interval_ms: default_max_key_interval_ms(),
+            max_retries: default_max_retries(),
+            smooth_move: SmoothMoveOptions::default(),
+        }
+    }
+}
+
+impl HumanizeOptions {
+    /// 计算键盘事件的实际等待时间：在配置范围内随机取值，但不短于录制时的原始间隔
+    pub fn key_delay_ms(&self, recorded_delay_ms: u64) -> u64 {
+        if !self.enabled {
+            return recorded_delay_ms;
+        }
+        let min = self.min_key_interval_ms.min(self.max_key_interval_ms);
+        let max = self.min_key_interval_ms.max(self.max_key_interval_ms);
+        let jitter = rand::thread_rng().gen_range(min..=max);
+        recorded_delay_ms.max(jitter)
+    }
+
+    /// 注入失败时的重试次数（未启用时不重试）
+    pub fn retries(&self) -> u32 {
+        if self.enabled {
+            self.max_retries.min(10)
+        } else {
+            0
+        }
+    }
+}
+
+/// 断点文件名（位于应用数据目录）
+const BREAKPOINT_FILE: &str = "replay_breakpoint.json";
+
+/// 最近一次中途停止的回放断点，用于从中断的步骤继续回放
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ReplayBreakpoint {
+    /// 录制文件名（不含 "recordings/" 前缀）
+    pub recording: String,
+    /// 下一个待执行事件的序号
+    pub index: usize,
+    /// 录制的事件总数
+    pub total: usize,
+    /// 停止时间（Unix 秒）
+    pub stopped_at: u64,
+}
+
+/// 读取断点，不存在或已损坏时返回 None
+pub fn load_breakpoint(app_data_dir: &Path) -> Option<ReplayBreakpoint> {
+    let content = fs::read_to_string(app_data_dir.join(BREAKPOINT_FILE)).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+pub fn save_breakpoint(app_data_dir: &Path, recording: &str, index: usize, total: usize) -> Result<(), String> {
+    let breakpoint = ReplayBreakpoint {
+        recording: recording.to_string(),
+        index,
+        total,
+        stopped_at: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs(),
+    };
+    let json = serde_json::to_string_pretty(&breakpoint)
+        .map_err(|e| format!("Failed to serialize replay breakpoint: {}", e))?;
+    fs::write(app_data_dir.join(BREAKPOINT_FILE), json)
+        .map_err(|e| format!("Failed to save replay breakpoint: {}", e))
+}
+
+/// 清除断点；recording 不为 None 时只在断点属于该录制时清除
+pub fn clear_breakpoint(app_data_dir: &Path, recording: Option<&str>) {
+    if let Some(recording) = recording {
+        if !load_breakpoint(app_data_dir).is_some_and(|b| b.recording == recording) {
+            return;
+        }
+    }
+    let _ = fs::remove_file(app_data_dir.join(BREAKPOINT_FILE));
+}
 
 pub struct ReplayState {
     pub is_playing: bool,
     pub current_events: Vec<RecordedEvent>,
     pub current_index: usize,
+    /// 最近一次停止时下一个待执行事件的序号
+    pub stopped_index: usize,
     pub speed_multiplier: f32,
+    /// 上次回放是否完整播放结束（用户中止时为 false）
+    pub finished: bool,
+    /// 当前录制文件保存的屏幕信息
+    pub recorded_screen: Option<ScreenInfo>,
+    /// 最近一次执行到的带注释步骤
+    pub current_note: Option<StepNote>,
+}
+
+/// 回放中当前步骤的注释
+#[derive(Debug, Clone, Serialize)]
+pub struct StepNote {
+    /// 事件在录制中的序号
+    pub index: usize,
+    pub note: String,
 }
 
 impl ReplayState {
@@ -16,7 +270,11 @@ impl ReplayState {
             is_playing: false,
             current_events: Vec::new(),
             current_index: 0,
+            stopped_index: 0,
             speed_multiplier: 1.0,
+            finished: false,
+            recorded_screen: None,
+            current_note: None,
         }
     }
 
@@ -36,18 +294,32 @@ impl ReplayState {
             .map(|v| serde_json::from_value(v.clone()))
             .collect::<Result<Vec<_>, _>>()
             .map_err(|e| format!("Failed to parse events: {}", e))?;
+        self.recorded_screen = serde_json::from_value::<Option<ScreenInfo>>(json["screen"].clone())
+            .ok()
+            .flatten();
 
         self.current_index = 0;
         Ok(())
     }
 
     pub fn start(&mut self, speed: f32) {
+        self.start_at(speed, 0);
+    }
+
+    /// 从指定事件序号开始回放（用于从断点继续）
+    pub fn start_at(&mut self, speed: f32, index: usize) {
         self.is_playing = true;
-        self.current_index = 0;
+        self.finished = false;
+        self.current_index = index.min(self.current_events.len());
+        self.stopped_index = 0;
         self.speed_multiplier = speed;
+        self.current_note = None;
     }
 
     pub fn stop(&mut self) {
+        if self.is_playing {
+            self.stopped_index = self.current_index;
+        }
         self.is_playing = false;
         self.current_index = 0;
     }
@@ -62,6 +334,12 @@ impl ReplayState {
     pub fn get_next_event(&mut self) -> Option<RecordedEvent> {
         if self.current_index < self.current_events.len() {
             let event = self.current_events[self.current_index].clone();
+            if let Some(note) = event.note.as_ref().filter(|n| !n.trim().is_empty()) {
+                self.current_note = Some(StepNote {
+                    index: self.current_index,
+                    note: note.clone(),
+                });
+            }
             self.current_index += 1;
             Some(event)
         } else {
@@ -69,6 +347,61 @@ impl ReplayState {
         }
     }
 
+    /// 当前光标位置
+    pub fn cursor_pos() -> Option<(i32, i32)> {
+        #[cfg(target_os = "windows")]
+        {
+            use windows_sys::Win32::Foundation::POINT;
+            use windows_sys::Win32::UI::WindowsAndMessaging::GetCursorPos;
+
+            let mut point = POINT { x: 0, y: 0 };
+            if unsafe { GetCursorPos(&mut point) } != 0 {
+                return Some((point.x, point.y));
+            }
+        }
+        None
+    }
+
+    /// 沿插值轨迹把光标平滑移动到目标点，在 duration_us 内均匀步进
+    pub fn smooth_move(from: (i32, i32), to: (i32, i32), duration_us: u64, options: &SmoothMoveOptions) {
+        #[cfg(target_os = "windows")]
+        {
+            use windows_sys::Win32::UI::WindowsAndMessaging::SetCursorPos;
+
+            let points = options.path_points(from, to, duration_us);
+            let step_us = duration_us / points.len().max(1) as u64;
+            let mut due_us = crate::hires_clock::now_us();
+            for (x, y) in points {
+                due_us += step_us;
+                crate::hires_clock::sleep_until_us(due_us);
+                unsafe {
+                    SetCursorPos(x, y);
+                }
+            }
+        }
+
+        #[cfg(not(target_os = "windows"))]
+        {
+            let _ = (from, to, duration_us, options);
+        }
+    }
+
+    /// 执行事件，失败时按给定次数重试（用于拟人化键入时避免丢字）
+    pub fn execute_event_with_retry(event: &RecordedEvent, retries: u32) -> Result<(), String> {
+        let mut attempt = 0;
+        loop {
+            match Self::execute_event(event) {
+                Ok(_) => return Ok(()),
+                Err(e) if attempt < retries => {
+                    attempt += 1;
+                    eprintln!("Retrying event (attempt {}): {}", attempt, e);
+                    std::thread::sleep(std::time::Duration::from_millis(10 * attempt as u64));
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
     pub fn execute_event(event: &RecordedEvent) -> Result<(), String> {
         #[cfg(target_os = "windows")]
         {
@@ -0,0 +1,102 @@
+//! 搜索结果打开计数权重
+//!
+//! 任意 provider（文件/书签/剪贴板/插件等）的结果执行成功后按 result key 回写打开次数，
+//! 排序时按次数与最近打开时间计算权重，让常用项逐渐排到前面
+
+use crate::db;
+use rusqlite::params;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// 权重半衰期：超过该天数未打开，权重减半
+const HALF_LIFE_DAYS: f64 = 30.0;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResultWeight {
+    pub key: String,
+    pub result_type: String,
+    pub open_count: u64,
+    pub last_opened: u64,
+    pub score: f64,
+}
+
+fn now_ts() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// 打开次数取对数避免高频项无限领先，再按最近打开时间衰减
+fn weight_score(open_count: u64, last_opened: u64, now: u64) -> f64 {
+    let age_days = now.saturating_sub(last_opened) as f64 / 86400.0;
+    (1.0 + open_count as f64).ln() * 0.5f64.powf(age_days / HALF_LIFE_DAYS)
+}
+
+fn row_to_weight(row: &rusqlite::Row, now: u64) -> rusqlite::Result<ResultWeight> {
+    let open_count = row.get::<_, i64>(2)? as u64;
+    let last_opened = row.get::<_, i64>(3)? as u64;
+    Ok(ResultWeight {
+        key: row.get(0)?,
+        result_type: row.get(1)?,
+        open_count,
+        last_opened,
+        score: weight_score(open_count, last_opened, now),
+    })
+}
+
+/// 记录一次成功执行
+pub fn record_result_open(key: String, result_type: String, app_data_dir: &Path) -> Result<ResultWeight, String> {
+    let conn = db::get_connection(app_data_dir)?;
+    let ts = now_ts();
+
+    conn.execute(
+        r#"
+        INSERT INTO result_weights (key, result_type, open_count, last_opened)
+        VALUES (?1, ?2, 1, ?3)
+        ON CONFLICT(key) DO UPDATE SET
+            open_count = result_weights.open_count + 1,
+            last_opened = excluded.last_opened,
+            result_type = excluded.result_type
+        "#,
+        params![key, result_type, ts as i64],
+    )
+    .map_err(|e| format!("Failed to record result open: {}", e))?;
+
+    conn.query_row(
+        "SELECT key, result_type, open_count, last_opened FROM result_weights WHERE key = ?1",
+        params![key],
+        |row| row_to_weight(row, ts),
+    )
+    .map_err(|e| format!("Failed to fetch result weight: {}", e))
+}
+
+/// 查询指定结果的权重，未记录过的 key 不返回
+pub fn get_result_weights(keys: &[String], app_data_dir: &Path) -> Result<HashMap<String, f64>, String> {
+    if keys.is_empty() {
+        return Ok(HashMap::new());
+    }
+    let conn = db::get_connection(app_data_dir)?;
+    let now = now_ts();
+    let placeholders = vec!["?"; keys.len()].join(", ");
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT key, result_type, open_count, last_opened FROM result_weights WHERE key IN ({})",
+            placeholders
+        ))
+        .map_err(|e| format!("Failed to prepare result weights query: {}", e))?;
+
+    let rows = stmt
+        .query_map(rusqlite::params_from_iter(keys.iter()), |row| row_to_weight(row, now))
+        .map_err(|e| format!("Failed to iterate result weights: {}", e))?;
+
+    let mut weights = HashMap::new();
+    for row in rows {
+        let weight = row.map_err(|e| format!("Failed to read result weight row: {}", e))?;
+        weights.insert(weight.key, weight.score);
+    }
+    Ok(weights)
+}
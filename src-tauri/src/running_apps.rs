@@ -0,0 +1,146 @@
+//! 应用运行状态
+//!
+//! 枚举顶层窗口并按进程路径与应用索引条目匹配，用于在搜索结果中标记"运行中"，
+//! 以及把默认动作从启动改为切换到已打开的窗口。枚举结果短暂缓存，按需刷新。
+
+use serde::Serialize;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RunningApp {
+    pub pid: u32,
+    /// 该进程的窗口数量
+    pub window_count: usize,
+}
+
+#[cfg(target_os = "windows")]
+pub mod windows {
+    use super::RunningApp;
+    use std::collections::HashMap;
+    use std::path::Path;
+    use std::sync::{LazyLock, Mutex};
+    use std::time::{Duration, Instant};
+    use windows_sys::Win32::Foundation::{BOOL, HWND, LPARAM};
+    use windows_sys::Win32::UI::WindowsAndMessaging::{
+        EnumWindows, GetWindow, GetWindowTextLengthW, IsWindowVisible, GW_OWNER,
+    };
+
+    /// 窗口枚举结果的缓存时间
+    const SNAPSHOT_TTL: Duration = Duration::from_secs(2);
+
+    /// 小写的可执行文件路径 -> (pid, 该进程的窗口列表，按 Z 序)
+    type Snapshot = HashMap<String, (u32, Vec<HWND>)>;
+
+    static SNAPSHOT: LazyLock<Mutex<Option<(Instant, Snapshot)>>> = LazyLock::new(|| Mutex::new(None));
+    /// .lnk 路径 -> 目标可执行文件路径（小写）
+    static LNK_TARGETS: LazyLock<Mutex<HashMap<String, Option<String>>>> =
+        LazyLock::new(|| Mutex::new(HashMap::new()));
+
+    unsafe extern "system" fn collect_window(hwnd: HWND, lparam: LPARAM) -> BOOL {
+        let windows = &mut *(lparam as *mut Vec<HWND>);
+        // 只统计可见、无所有者且有标题的主窗口
+        if IsWindowVisible(hwnd) != 0 && GetWindow(hwnd, GW_OWNER) == 0 && GetWindowTextLengthW(hwnd) > 0 {
+            windows.push(hwnd);
+        }
+        1
+    }
+
+    fn build_snapshot() -> Snapshot {
+        let mut windows: Vec<HWND> = Vec::new();
+        unsafe {
+            EnumWindows(Some(collect_window), &mut windows as *mut Vec<HWND> as LPARAM);
+        }
+
+        let own_pid = std::process::id();
+        let mut snapshot = Snapshot::new();
+        let mut paths_by_pid: HashMap<u32, Option<String>> = HashMap::new();
+        for hwnd in windows {
+            let Some(pid) = crate::process_info::windows::get_window_process_id(hwnd) else { continue };
+            if pid == own_pid {
+                continue;
+            }
+            let path = paths_by_pid
+                .entry(pid)
+                .or_insert_with(|| crate::process_info::windows::get_process_path(pid).map(|p| p.to_lowercase()));
+            if let Some(path) = path {
+                snapshot.entry(path.clone()).or_insert_with(|| (pid, Vec::new())).1.push(hwnd);
+            }
+        }
+        snapshot
+    }
+
+    fn with_snapshot<T>(f: impl FnOnce(&Snapshot) -> T) -> T {
+        let mut guard = SNAPSHOT.lock().unwrap_or_else(|e| e.into_inner());
+        let fresh = matches!(&*guard, Some((at, _)) if at.elapsed() < SNAPSHOT_TTL);
+        if !fresh {
+            *guard = Some((Instant::now(), build_snapshot()));
+        }
+        f(&guard.as_ref().expect("snapshot initialized").1)
+    }
+
+    /// 应用索引路径对应的可执行文件路径（.lnk 解析目标，结果缓存）
+    fn resolve_exe(app_path: &str) -> Option<String> {
+        if !app_path.to_lowercase().ends_with(".lnk") {
+            return Some(app_path.to_lowercase());
+        }
+        let mut targets = LNK_TARGETS.lock().unwrap_or_else(|e| e.into_inner());
+        targets
+            .entry(app_path.to_string())
+            .or_insert_with(|| {
+                crate::app_search::windows::get_lnk_all_paths(Path::new(app_path))
+                    .and_then(|(_, target)| target)
+                    .filter(|t| t.to_lowercase().ends_with(".exe"))
+                    .map(|t| t.to_lowercase())
+            })
+            .clone()
+    }
+
+    /// 查询一组应用的运行状态，只返回正在运行的条目
+    pub fn get_running(app_paths: &[String]) -> HashMap<String, RunningApp> {
+        let exes: Vec<(String, Option<String>)> =
+            app_paths.iter().map(|p| (p.clone(), resolve_exe(p))).collect();
+        with_snapshot(|snapshot| {
+            exes.into_iter()
+                .filter_map(|(app_path, exe)| {
+                    let (pid, windows) = snapshot.get(&exe?)?;
+                    Some((
+                        app_path,
+                        RunningApp {
+                            pid: *pid,
+                            window_count: windows.len(),
+                        },
+                    ))
+                })
+                .collect()
+        })
+    }
+
+    /// 切换到应用最上层的窗口，应用未运行时返回 false
+    pub fn activate(app_path: &str) -> bool {
+        let Some(exe) = resolve_exe(app_path) else {
+            return false;
+        };
+        // 切换前强制刷新，避免使用已关闭的窗口句柄
+        if let Ok(mut guard) = SNAPSHOT.lock() {
+            *guard = None;
+        }
+        let hwnd = with_snapshot(|snapshot| snapshot.get(&exe).and_then(|(_, w)| w.first().copied()));
+        match hwnd {
+            Some(hwnd) => crate::foreground::windows::force_foreground(hwnd),
+            None => false,
+        }
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+pub mod windows {
+    use super::RunningApp;
+    use std::collections::HashMap;
+
+    pub fn get_running(_app_paths: &[String]) -> HashMap<String, RunningApp> {
+        HashMap::new()
+    }
+
+    pub fn activate(_app_path: &str) -> bool {
+        false
+    }
+}
@@ -14,6 +14,30 @@ pub struct Settings {
     pub hotkey: Option<HotkeyConfig>,
     #[serde(default)]
     pub app_center_hotkey: Option<HotkeyConfig>,
+    /// 直接屏幕取色的全局快捷键（不打开拾色器窗口）
+    #[serde(default)]
+    pub color_pick_hotkey: Option<HotkeyConfig>,
+    /// 取色结果复制格式：hex / hex_upper / rgb / hsl / hsv / cmyk / css_var
+    #[serde(default = "default_color_copy_format")]
+    pub color_copy_format: String,
+    /// 屏幕取色时右键取消取色（默认仅屏蔽右键）
+    #[serde(default)]
+    pub color_pick_right_click_cancels: bool,
+    /// 唤出快速笔记窗口的全局快捷键
+    #[serde(default)]
+    pub notes_hotkey: Option<HotkeyConfig>,
+    /// 进入屏幕标尺的全局快捷键
+    #[serde(default)]
+    pub screen_ruler_hotkey: Option<HotkeyConfig>,
+    /// 连续粘贴下一条的全局快捷键
+    #[serde(default)]
+    pub paste_stack_hotkey: Option<HotkeyConfig>,
+    /// 在光标附近弹出剪贴板历史面板的全局快捷键
+    #[serde(default)]
+    pub clipboard_popup_hotkey: Option<HotkeyConfig>,
+    /// 同一热键重复触发的抑制窗口（毫秒），0 表示不抑制
+    #[serde(default = "default_hotkey_suppress_ms")]
+    pub hotkey_suppress_ms: u64,
     #[serde(default)]
     pub plugin_hotkeys: HashMap<String, HotkeyConfig>,
     #[serde(default)]
@@ -30,20 +54,428 @@ pub struct Settings {
     pub ignored_update_version: Option<String>,
     #[serde(default = "default_clipboard_max_items")]
     pub clipboard_max_items: u32,
+    /// 非收藏条目的最长保留天数（0 表示不限制）
+    #[serde(default)]
+    pub clipboard_max_age_days: u32,
+    /// 剪贴板历史的最大总大小（MB），超出时删除最旧的非收藏条目（0 表示不限制）
+    #[serde(default)]
+    pub clipboard_max_total_mb: u32,
+    /// 剪贴板文本超过该大小（KB）时只保存摘要并标记 truncated
+    #[serde(default = "default_clipboard_max_text_kb")]
+    pub clipboard_max_text_kb: u32,
+    /// 超大文本保存的摘要大小（KB）
+    #[serde(default = "default_clipboard_truncated_keep_kb")]
+    pub clipboard_truncated_keep_kb: u32,
+    /// 文本超过该大小（KB）时完整内容保存为文件，表中只保留预览（0 表示不启用）
+    #[serde(default = "default_clipboard_external_threshold_kb")]
+    pub clipboard_external_threshold_kb: u32,
+    /// 复制的文本是颜色值（#RRGGBB / rgb()）时记录为颜色条目，历史中渲染色块
+    #[serde(default)]
+    pub clipboard_detect_colors: bool,
+    /// 不记录这些进程写入的剪贴板内容（可执行文件名，忽略大小写，可省略 .exe）
+    #[serde(default = "default_clipboard_ignored_processes")]
+    pub clipboard_ignored_processes: Vec<String>,
+    /// 文本入库前的规范化规则
+    #[serde(default)]
+    pub clipboard_normalize: ClipboardNormalizeSettings,
+    /// 剪贴板图片的存储格式、质量与分辨率上限
+    #[serde(default)]
+    pub clipboard_image: ClipboardImageSettings,
+    /// 后台识别图片条目中的文字，识别结果可被搜索
+    #[serde(default = "default_true")]
+    pub clipboard_image_ocr: bool,
+    /// 捕获新剪贴板内容时在通知区域显示前 50 字摘要（有频率限制，敏感内容不提示）
+    #[serde(default)]
+    pub clipboard_capture_toast: bool,
+    /// 记录剪贴板历史，关闭时暂停记录新内容（如处理账号密码时）
+    #[serde(default = "default_true")]
+    pub clipboard_monitoring: bool,
+    /// 敏感条目（像密码 / 密钥，或来自密码管理器）入库后多少分钟自动删除并清空系统剪贴板（0 表示不自动清除）
+    #[serde(default)]
+    pub clipboard_sensitive_expire_minutes: u32,
+    /// 视为密码管理器的进程名，来自这些程序的内容按敏感条目处理
+    #[serde(default = "default_clipboard_password_managers")]
+    pub clipboard_password_managers: Vec<String>,
+    /// 复制内容命中敏感词 / 正则时提醒，可选不写入历史
+    #[serde(default)]
+    pub clipboard_alert: ClipboardAlertSettings,
+    /// 在任意程序中输入收藏条目的缩写时自动展开为条目内容
+    #[serde(default = "default_true")]
+    pub clipboard_snippet_expansion: bool,
+    /// 启动器空查询时展示的默认面板
+    #[serde(default)]
+    pub default_panel: DefaultPanelSettings,
+    /// 通过 WebDAV 在多台电脑间同步剪贴板历史
+    #[serde(default)]
+    pub clipboard_sync: ClipboardSyncSettings,
+    /// 鼠标侧键（XBUTTON1/2）绑定的动作
+    #[serde(default)]
+    pub mouse_side_buttons: MouseSideButtonSettings,
+    /// 同一来源在 clipboard_spam_window_secs 秒内写入剪贴板达到该次数时临时屏蔽（0 表示不检测）
+    #[serde(default = "default_clipboard_spam_threshold")]
+    pub clipboard_spam_threshold: u32,
+    #[serde(default = "default_clipboard_spam_window_secs")]
+    pub clipboard_spam_window_secs: u64,
+    /// 屏蔽时长（秒）
+    #[serde(default = "default_clipboard_spam_block_secs")]
+    pub clipboard_spam_block_secs: u64,
+    /// Everything 每批次查询超时（毫秒），超时无结果时自动重试一次
+    #[serde(default = "default_everything_timeout_ms")]
+    pub everything_timeout_ms: u64,
+    /// Everything 结果中文件夹排在文件前面（搜索时未指定 foldersFirst 时使用）
+    #[serde(default)]
+    pub everything_folders_first: bool,
     #[serde(default = "default_translation_tab_order")]
     pub translation_tab_order: Vec<String>,
     #[serde(default = "default_search_engines")]
     pub search_engines: Vec<SearchEngineConfig>,
+    #[serde(default)]
+    pub file_action_favorite_dirs: Vec<String>,
+    /// 启动器搜索同义词表：键与值互为同义词，如 {"chrome": ["谷歌浏览器", "gc"]}
+    #[serde(default)]
+    pub synonyms: HashMap<String, Vec<String>>,
+    /// 勿扰时段：时段内不弹出通知与提醒
+    #[serde(default)]
+    pub quiet_periods: Vec<QuietPeriod>,
+    /// 启动器 "pwd" 生成密码使用的字符集
+    #[serde(default)]
+    pub password_generator: PasswordGeneratorSettings,
+    /// 剪贴板条目自定义动作：内容匹配正则时出现在条目右键菜单
+    #[serde(default)]
+    pub clipboard_actions: Vec<ClipboardAction>,
+}
+
+/// 剪贴板条目自定义动作，调用外部程序处理条目内容
+///
+/// args 中可使用占位符：{content} 整个条目、{match} 正则匹配的文本、{1}..{9} 捕获组
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ClipboardAction {
+    pub id: String,
+    pub name: String,
+    pub pattern: String,
+    pub program: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// 通过 STDIN 传入整个条目内容
+    #[serde(default)]
+    pub use_stdin: bool,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+}
+
+/// 剪贴板文本入库前的规范化规则，默认全部关闭
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct ClipboardNormalizeSettings {
+    /// 去除首尾空白
+    #[serde(default)]
+    pub trim: bool,
+    /// 统一换行为 \n（\r\n、\r 均转换）
+    #[serde(default)]
+    pub unify_line_endings: bool,
+    /// 去除每行行尾空白
+    #[serde(default)]
+    pub trim_line_ends: bool,
+    /// 制表符转换为该数量的空格（0 表示不转换）
+    #[serde(default)]
+    pub tab_width: u32,
+}
+
+/// 剪贴板敏感词告警规则
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ClipboardAlertRule {
+    /// 敏感词（忽略大小写的包含匹配）或正则表达式
+    pub pattern: String,
+    /// pattern 按正则表达式匹配
+    #[serde(default)]
+    pub regex: bool,
+}
+
+/// 剪贴板敏感词告警设置，默认关闭
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct ClipboardAlertSettings {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    pub rules: Vec<ClipboardAlertRule>,
+    /// 命中时不写入剪贴板历史
+    #[serde(default)]
+    pub block_storage: bool,
+}
+
+/// 剪贴板图片存储格式
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ClipboardImageFormat {
+    #[default]
+    Png,
+    Jpeg,
+    /// 需要系统安装 WebP 编码器，不可用时回退为 PNG
+    Webp,
+}
+
+/// 剪贴板图片存储设置
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ClipboardImageSettings {
+    #[serde(default)]
+    pub format: ClipboardImageFormat,
+    /// JPEG / WebP 质量（1-100）
+    #[serde(default = "default_clipboard_image_quality")]
+    pub quality: u8,
+    /// 宽或高超过该值时按比例缩小（0 表示不限制）
+    #[serde(default = "default_clipboard_image_max_dimension")]
+    pub max_dimension: u32,
+}
+
+impl Default for ClipboardImageSettings {
+    fn default() -> Self {
+        Self {
+            format: ClipboardImageFormat::default(),
+            quality: default_clipboard_image_quality(),
+            max_dimension: default_clipboard_image_max_dimension(),
+        }
+    }
+}
+
+fn default_clipboard_image_quality() -> u8 {
+    85
+}
+
+fn default_clipboard_image_max_dimension() -> u32 {
+    3840
+}
+
+/// 启动器空查询时默认面板的分区
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DefaultPanelSection {
+    /// 固定项
+    Pinned,
+    /// 最近启动
+    RecentLaunches,
+    /// 剪贴板最新条目
+    Clipboard,
+    /// 今日提醒
+    Reminders,
+}
+
+/// 启动器空查询默认面板：按 sections 顺序展示，不在列表中的分区不展示
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct DefaultPanelSettings {
+    #[serde(default = "default_panel_sections")]
+    pub sections: Vec<DefaultPanelSection>,
+    /// 最近启动显示条数
+    #[serde(default = "default_panel_recent_limit")]
+    pub recent_limit: usize,
+    /// 剪贴板显示条数
+    #[serde(default = "default_panel_clipboard_limit")]
+    pub clipboard_limit: usize,
+}
+
+impl Default for DefaultPanelSettings {
+    fn default() -> Self {
+        Self {
+            sections: default_panel_sections(),
+            recent_limit: default_panel_recent_limit(),
+            clipboard_limit: default_panel_clipboard_limit(),
+        }
+    }
+}
+
+fn default_panel_sections() -> Vec<DefaultPanelSection> {
+    vec![
+        DefaultPanelSection::Pinned,
+        DefaultPanelSection::RecentLaunches,
+        DefaultPanelSection::Clipboard,
+        DefaultPanelSection::Reminders,
+    ]
+}
+
+fn default_panel_recent_limit() -> usize {
+    8
+}
+
+fn default_panel_clipboard_limit() -> usize {
+    5
+}
+
+/// 剪贴板 WebDAV 同步设置，url 为服务器上用于同步的目录
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ClipboardSyncSettings {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    pub url: String,
+    #[serde(default)]
+    pub username: String,
+    #[serde(default)]
+    pub password: String,
+    /// 本机在同步目录中的名称，为空时使用计算机名
+    #[serde(default)]
+    pub device_name: String,
+    /// 自动同步间隔（分钟，0 表示只手动同步）
+    #[serde(default = "default_clipboard_sync_interval_minutes")]
+    pub interval_minutes: u32,
+    /// 每次上传的最近条目数
+    #[serde(default = "default_clipboard_sync_max_items")]
+    pub max_items: usize,
+    /// 同步口令：启用剪贴板加密时用它加密上传的数据，各设备需设置相同的口令
+    #[serde(default)]
+    pub passphrase: String,
+}
+
+impl Default for ClipboardSyncSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            url: String::new(),
+            username: String::new(),
+            password: String::new(),
+            device_name: String::new(),
+            interval_minutes: default_clipboard_sync_interval_minutes(),
+            max_items: default_clipboard_sync_max_items(),
+            passphrase: String::new(),
+        }
+    }
+}
+
+fn default_clipboard_sync_interval_minutes() -> u32 {
+    5
+}
+
+fn default_clipboard_sync_max_items() -> usize {
+    200
+}
+
+/// 鼠标侧键动作，none 表示不拦截（保持系统默认的后退 / 前进）
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum MouseButtonAction {
+    #[default]
+    None,
+    ShowLauncher,
+    ClipboardPanel,
+    ClipboardPopup,
+    Back,
+    Forward,
+}
+
+/// 鼠标侧键设置，xbutton1 通常是“后退”键，xbutton2 通常是“前进”键
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct MouseSideButtonSettings {
+    #[serde(default)]
+    pub xbutton1: MouseButtonAction,
+    #[serde(default)]
+    pub xbutton2: MouseButtonAction,
+    /// 前台为这些进程（如 "chrome.exe"，不区分大小写）时不拦截侧键
+    #[serde(default)]
+    pub disabled_processes: Vec<String>,
+}
+
+/// 随机密码字符集配置，symbols 为空表示不含符号
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PasswordGeneratorSettings {
+    #[serde(default = "default_true")]
+    pub lowercase: bool,
+    #[serde(default = "default_true")]
+    pub uppercase: bool,
+    #[serde(default = "default_true")]
+    pub digits: bool,
+    #[serde(default = "default_password_symbols")]
+    pub symbols: String,
+    /// 排除易混淆字符（0O1lI|）
+    #[serde(default)]
+    pub exclude_ambiguous: bool,
+}
+
+impl Default for PasswordGeneratorSettings {
+    fn default() -> Self {
+        Self {
+            lowercase: true,
+            uppercase: true,
+            digits: true,
+            symbols: default_password_symbols(),
+            exclude_ambiguous: false,
+        }
+    }
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_password_symbols() -> String {
+    "!@#$%^&*-_=+?".to_string()
+}
+
+/// 勿扰时段，start/end 为 "HH:MM"，end 早于 start 时表示跨午夜
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct QuietPeriod {
+    pub start: String,
+    pub end: String,
+    #[serde(default = "default_quiet_period_enabled")]
+    pub enabled: bool,
+}
+
+fn default_quiet_period_enabled() -> bool {
+    true
+}
+
+fn default_hotkey_suppress_ms() -> u64 {
+    300
 }
 
 fn default_clipboard_max_items() -> u32 {
     100
 }
 
+fn default_clipboard_max_text_kb() -> u32 {
+    1024
+}
+
+fn default_clipboard_truncated_keep_kb() -> u32 {
+    64
+}
+
+fn default_clipboard_external_threshold_kb() -> u32 {
+    256
+}
+
+fn default_clipboard_ignored_processes() -> Vec<String> {
+    ["KeePass.exe", "KeePassXC.exe", "1Password.exe", "Bitwarden.exe"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect()
+}
+
+/// 默认与忽略列表相同：从忽略列表中移除后，这些程序复制的内容会被记录并按敏感条目处理
+fn default_clipboard_password_managers() -> Vec<String> {
+    default_clipboard_ignored_processes()
+}
+
+fn default_clipboard_spam_threshold() -> u32 {
+    10
+}
+
+fn default_clipboard_spam_window_secs() -> u64 {
+    10
+}
+
+fn default_clipboard_spam_block_secs() -> u64 {
+    300
+}
+
+fn default_everything_timeout_ms() -> u64 {
+    2000
+}
+
 fn default_result_style() -> String {
     "skeuomorphic".to_string()
 }
 
+fn default_color_copy_format() -> String {
+    "hex".to_string()
+}
+
 fn default_close_on_blur() -> bool {
     true
 }
@@ -63,6 +495,14 @@ impl Default for Settings {
             startup_enabled: false,
             hotkey: None,
             app_center_hotkey: None,
+            color_pick_hotkey: None,
+            color_copy_format: default_color_copy_format(),
+            color_pick_right_click_cancels: false,
+            notes_hotkey: None,
+            screen_ruler_hotkey: None,
+            paste_stack_hotkey: None,
+            clipboard_popup_hotkey: None,
+            hotkey_suppress_ms: default_hotkey_suppress_ms(),
             plugin_hotkeys: HashMap::new(),
             app_hotkeys: HashMap::new(),
             close_on_blur: default_close_on_blur(),
@@ -71,8 +511,37 @@ impl Default for Settings {
             last_update_check_time: None,
             ignored_update_version: None,
             clipboard_max_items: default_clipboard_max_items(),
+            clipboard_max_age_days: 0,
+            clipboard_max_total_mb: 0,
+            clipboard_max_text_kb: default_clipboard_max_text_kb(),
+            clipboard_truncated_keep_kb: default_clipboard_truncated_keep_kb(),
+            clipboard_external_threshold_kb: default_clipboard_external_threshold_kb(),
+            clipboard_detect_colors: false,
+            clipboard_ignored_processes: default_clipboard_ignored_processes(),
+            clipboard_normalize: ClipboardNormalizeSettings::default(),
+            clipboard_image: ClipboardImageSettings::default(),
+            clipboard_image_ocr: true,
+            clipboard_capture_toast: false,
+            clipboard_monitoring: true,
+            clipboard_sensitive_expire_minutes: 0,
+            clipboard_password_managers: default_clipboard_password_managers(),
+            clipboard_alert: ClipboardAlertSettings::default(),
+            clipboard_snippet_expansion: true,
+            default_panel: DefaultPanelSettings::default(),
+            clipboard_sync: ClipboardSyncSettings::default(),
+            mouse_side_buttons: MouseSideButtonSettings::default(),
+            clipboard_spam_threshold: default_clipboard_spam_threshold(),
+            clipboard_spam_window_secs: default_clipboard_spam_window_secs(),
+            clipboard_spam_block_secs: default_clipboard_spam_block_secs(),
+            everything_timeout_ms: default_everything_timeout_ms(),
+            everything_folders_first: false,
             translation_tab_order: default_translation_tab_order(),
             search_engines: default_search_engines(),
+            file_action_favorite_dirs: Vec::new(),
+            synonyms: HashMap::new(),
+            quiet_periods: Vec::new(),
+            password_generator: PasswordGeneratorSettings::default(),
+            clipboard_actions: Vec::new(),
         }
     }
 }
@@ -1,4 +1,5 @@
 use crate::db;
+use regex::Regex;
 use rusqlite::params;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -14,6 +15,17 @@ pub struct ShortcutItem {
     pub icon: Option<String>, // Optional icon path or base64 data
     pub created_at: u64,      // Unix timestamp
     pub updated_at: u64,      // Unix timestamp
+    #[serde(default)]
+    pub confirm_before_run: bool, // Ask for confirmation before launching
+}
+
+/// Result of a shortcut launch request
+#[derive(Serialize, Debug, Clone)]
+pub struct ShortcutLaunchResult {
+    pub launched: bool,
+    pub requires_confirmation: bool,
+    /// Set when the target was detected as dangerous; confirmation is then forced
+    pub danger_reason: Option<String>,
 }
 
 static SHORTCUTS: LazyLock<Arc<Mutex<HashMap<String, ShortcutItem>>>> =
@@ -42,6 +54,7 @@ pub fn add_shortcut(
     name: String,
     path: String,
     icon: Option<String>,
+    confirm_before_run: bool,
     app_data_dir: &Path,
 ) -> Result<ShortcutItem, String> {
     use std::time::{SystemTime, UNIX_EPOCH};
@@ -61,6 +74,7 @@ pub fn add_shortcut(
         icon,
         created_at: timestamp,
         updated_at: timestamp,
+        confirm_before_run,
     };
 
     let mut state = SHORTCUTS.lock().map_err(|e| e.to_string())?;
@@ -77,6 +91,7 @@ pub fn update_shortcut(
     name: Option<String>,
     path: Option<String>,
     icon: Option<String>,
+    confirm_before_run: Option<bool>,
     app_data_dir: &Path,
 ) -> Result<ShortcutItem, String> {
     use std::time::{SystemTime, UNIX_EPOCH};
@@ -101,6 +116,9 @@ pub fn update_shortcut(
     if let Some(icon) = icon {
         shortcut.icon = Some(icon);
     }
+    if let Some(confirm_before_run) = confirm_before_run {
+        shortcut.confirm_before_run = confirm_before_run;
+    }
     shortcut.updated_at = timestamp;
 
     let shortcut_clone = shortcut.clone();
@@ -125,6 +143,322 @@ pub fn delete_shortcut(id: String, app_data_dir: &Path) -> Result<(), String> {
     Ok(())
 }
 
+pub fn get_shortcut(id: &str) -> Option<ShortcutItem> {
+    SHORTCUTS.lock().ok()?.get(id).cloned()
+}
+
+/// File types that can be turned into shortcuts by a bulk import
+const IMPORTABLE_EXTENSIONS: &[&str] = &["lnk", "exe", "url"];
+
+/// Folders are scanned at most this deep when importing recursively
+const MAX_IMPORT_DEPTH: usize = 5;
+
+/// Result of a bulk shortcut import
+#[derive(Serialize, Debug, Clone, Default)]
+pub struct ShortcutImportResult {
+    pub imported: Vec<ShortcutItem>,
+    /// Files skipped because an existing shortcut already points at the same target
+    pub duplicates: Vec<String>,
+}
+
+fn is_importable(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .map(|e| IMPORTABLE_EXTENSIONS.contains(&e.to_lowercase().as_str()))
+        .unwrap_or(false)
+}
+
+fn collect_importable(dir: &Path, recursive: bool, depth: usize, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    let mut entries: Vec<PathBuf> = entries.filter_map(|e| e.ok()).map(|e| e.path()).collect();
+    entries.sort();
+    for path in entries {
+        if path.is_dir() {
+            if recursive && depth < MAX_IMPORT_DEPTH {
+                collect_importable(&path, recursive, depth + 1, files);
+            }
+        } else if is_importable(&path) {
+            files.push(path);
+        }
+    }
+}
+
+/// Keys used to detect duplicates: the file itself and, for .lnk files, the target it points to
+fn dedupe_keys(path: &Path) -> Vec<String> {
+    let mut keys = vec![path.to_string_lossy().to_lowercase()];
+    #[cfg(target_os = "windows")]
+    {
+        let is_lnk = path
+            .extension()
+            .map(|e| e.eq_ignore_ascii_case("lnk"))
+            .unwrap_or(false);
+        if is_lnk {
+            if let Some((_, Some(target))) = crate::app_search::windows::get_lnk_all_paths(path) {
+                if !target.is_empty() {
+                    keys.push(target.to_lowercase());
+                }
+            }
+        }
+    }
+    keys
+}
+
+fn extract_import_icon(path: &Path) -> Option<String> {
+    #[cfg(target_os = "windows")]
+    {
+        let ext = path.extension()?.to_str()?.to_lowercase();
+        match ext.as_str() {
+            "lnk" => crate::app_search::windows::extract_lnk_icon_base64(path),
+            "url" => crate::app_search::windows::extract_url_icon_base64(path),
+            _ => crate::app_search::windows::extract_icon_base64(path),
+        }
+    }
+    #[cfg(not(target_os = "windows"))]
+    {
+        let _ = path;
+        None
+    }
+}
+
+/// Create shortcuts from dropped files and folders (.lnk / .exe / .url).
+/// Names come from the file name, icons are extracted from the file, and
+/// targets that already have a shortcut are skipped.
+pub fn import_shortcuts(
+    paths: Vec<String>,
+    recursive: bool,
+    app_data_dir: &Path,
+) -> Result<ShortcutImportResult, String> {
+    use std::collections::HashSet;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    let mut files = Vec::new();
+    for path in paths.iter().map(PathBuf::from) {
+        if path.is_dir() {
+            collect_importable(&path, recursive, 0, &mut files);
+        } else if path.is_file() && is_importable(&path) {
+            files.push(path);
+        }
+    }
+
+    let mut known: HashSet<String> = get_all_shortcuts()
+        .iter()
+        .flat_map(|s| dedupe_keys(Path::new(&s.path)))
+        .collect();
+
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_err(|e| format!("Failed to get timestamp: {}", e))?
+        .as_secs();
+
+    let mut result = ShortcutImportResult::default();
+    let mut new_items = Vec::new();
+    for file in files {
+        let keys = dedupe_keys(&file);
+        let path = file.to_string_lossy().to_string();
+        if keys.iter().any(|k| known.contains(k)) {
+            result.duplicates.push(path);
+            continue;
+        }
+        known.extend(keys);
+
+        let name = file
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_else(|| path.clone());
+        new_items.push(ShortcutItem {
+            id: String::new(),
+            icon: extract_import_icon(&file),
+            name,
+            path,
+            created_at: timestamp,
+            updated_at: timestamp,
+            confirm_before_run: false,
+        });
+    }
+
+    if new_items.is_empty() {
+        return Ok(result);
+    }
+
+    let mut state = SHORTCUTS.lock().map_err(|e| e.to_string())?;
+    for mut item in new_items {
+        // Same ID scheme as add_shortcut, with a suffix when several files share a name
+        let base_id = format!("{}_{}", item.name.replace(" ", "_"), timestamp);
+        let mut id = base_id.clone();
+        let mut suffix = 1;
+        while state.contains_key(&id) {
+            suffix += 1;
+            id = format!("{}_{}", base_id, suffix);
+        }
+        item.id = id.clone();
+        state.insert(id, item.clone());
+        result.imported.push(item);
+    }
+    drop(state);
+
+    save_shortcuts(app_data_dir)?;
+
+    Ok(result)
+}
+
+/// Script extensions whose content is scanned for dangerous commands
+const SCRIPT_EXTENSIONS: &[&str] = &["bat", "cmd", "ps1", "vbs", "js", "wsf", "sh"];
+
+/// Only the beginning of a script is scanned to keep the check cheap
+const MAX_SCRIPT_SCAN_BYTES: u64 = 256 * 1024;
+
+/// A delete command at a command start (line start or after `&`, `|`, `;`)
+/// whose first non-switch argument is a system drive path
+static DELETE_COMMAND: LazyLock<Regex> = LazyLock::new(|| {
+    let system_drive = std::env::var("SystemDrive").unwrap_or_else(|_| "C:".to_string());
+    Regex::new(&format!(
+        r#"(?i)(?:^|[&|;])\s*@?\s*(?:(?:cmd|powershell|pwsh)(?:\.exe)?(?:\s+[-/][a-z]+)*\s+)?["']?(?:del|erase|rd|rmdir|remove-item|rm)(?:\.exe)?(?:\s+(?:/[a-z]+|-{{1,2}}[a-z]+))*\s+["']?(?:{}[\\/]|%systemdrive%|%systemroot%|%windir%|\$env:systemdrive|\$env:windir)"#,
+        regex::escape(&system_drive)
+    ))
+    .unwrap()
+});
+
+static FORMAT_COMMAND: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)\b(format(\.com)?\s+[a-z]:|diskpart\b|cipher\s+/w)").unwrap()
+});
+
+/// Whether a shortcut pointing at `path` asks for confirmation before running
+fn confirm_before_run_for(path: &str) -> bool {
+    let target = path.trim().trim_matches('"');
+    let state = SHORTCUTS.lock().unwrap_or_else(|e| e.into_inner());
+    state.values().any(|shortcut| {
+        shortcut.confirm_before_run
+            && shortcut.path.trim().trim_matches('"').eq_ignore_ascii_case(target)
+    })
+}
+
+/// Shared launch routine for shortcuts and search results.
+/// Dangerous targets and targets of shortcuts marked confirm_before_run only run with `confirmed = true`.
+pub fn launch_checked(path: &str, confirmed: bool) -> Result<ShortcutLaunchResult, String> {
+    let danger_reason = detect_danger(path);
+    let requires_confirmation = danger_reason.is_some() || confirm_before_run_for(path);
+    if requires_confirmation && !confirmed {
+        return Ok(ShortcutLaunchResult {
+            launched: false,
+            requires_confirmation,
+            danger_reason,
+        });
+    }
+
+    if let Some(ref reason) = danger_reason {
+        eprintln!("[Shortcuts] Launching confirmed dangerous target {}: {}", path, reason);
+    }
+    crate::file_history::launch_file(path)?;
+
+    Ok(ShortcutLaunchResult {
+        launched: true,
+        requires_confirmation,
+        danger_reason,
+    })
+}
+
+/// Split a launch string into its target and the arguments after it.
+/// A string naming an existing path has no arguments.
+fn split_launch_target(path: &str) -> (&str, Option<&str>) {
+    let trimmed = path.trim();
+    let (target, rest) = if let Some(quoted) = trimmed.strip_prefix('"') {
+        match quoted.find('"') {
+            Some(end) => (&quoted[..end], &quoted[end + 1..]),
+            None => (quoted, ""),
+        }
+    } else if Path::new(trimmed).exists() {
+        (trimmed, "")
+    } else {
+        trimmed.split_once(char::is_whitespace).unwrap_or((trimmed, ""))
+    };
+    let rest = rest.trim();
+    (target, (!rest.is_empty()).then_some(rest))
+}
+
+/// Command line of a .lnk shortcut (target file stem followed by its arguments), if it has arguments
+#[cfg(target_os = "windows")]
+fn lnk_command_line(lnk_path: &Path) -> Option<String> {
+    use std::os::windows::process::CommandExt;
+
+    let path_str = lnk_path.to_string_lossy().replace('\'', "''");
+    let ps_command = format!(
+        r#"$shortcut = (New-Object -ComObject WScript.Shell).CreateShortcut('{}'); $shortcut.TargetPath; $shortcut.Arguments"#,
+        path_str
+    );
+    let output = std::process::Command::new("powershell")
+        .args(["-NoProfile", "-ExecutionPolicy", "Bypass", "-Command", &ps_command])
+        .creation_flags(0x08000000) // CREATE_NO_WINDOW
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut lines = stdout.lines();
+    let target = lines.next()?.trim().to_string();
+    let arguments = lines.next().unwrap_or("").trim();
+    if arguments.is_empty() {
+        return None;
+    }
+    Some(format!("{} {}", command_name(&target), arguments))
+}
+
+/// File stem of a launch target, so `C:\Windows\System32\cmd.exe` reads as `cmd`
+fn command_name(target: &str) -> String {
+    Path::new(target.trim_matches('"'))
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_else(|| target.to_string())
+}
+
+/// Detect commands that delete files on the system drive or wipe disks.
+/// Only script contents and launch/shortcut arguments are scanned, never the bare path,
+/// so file names such as `rd-notes.txt` don't trigger a confirmation.
+/// Returns a human readable reason when the target must be confirmed.
+pub fn detect_danger(path: &str) -> Option<String> {
+    let (target, arguments) = split_launch_target(path);
+    let target = Path::new(target);
+
+    let mut sources = Vec::new();
+    if let Some(arguments) = arguments {
+        sources.push(format!("{} {}", command_name(&target.to_string_lossy()), arguments));
+    }
+    let extension = target
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_lowercase())
+        .unwrap_or_default();
+    if SCRIPT_EXTENSIONS.contains(&extension.as_str()) {
+        if let Ok(file) = fs::File::open(target) {
+            use std::io::Read;
+            let mut buffer = Vec::new();
+            if file.take(MAX_SCRIPT_SCAN_BYTES).read_to_end(&mut buffer).is_ok() {
+                sources.push(String::from_utf8_lossy(&buffer).to_string());
+            }
+        }
+    }
+    #[cfg(target_os = "windows")]
+    {
+        if extension == "lnk" {
+            sources.extend(lnk_command_line(target));
+        }
+    }
+
+    for source in &sources {
+        for line in source.lines() {
+            if FORMAT_COMMAND.is_match(line) {
+                return Some(format!("包含格式化/擦除磁盘命令: {}", line.trim()));
+            }
+            if DELETE_COMMAND.is_match(line) {
+                return Some(format!("包含删除系统盘文件的命令: {}", line.trim()));
+            }
+        }
+    }
+    None
+}
+
 fn load_shortcuts_into(
     state: &mut HashMap<String, ShortcutItem>,
     app_data_dir: &Path,
@@ -134,7 +468,7 @@ fn load_shortcuts_into(
 
     let mut stmt = conn
         .prepare(
-            "SELECT id, name, path, icon, created_at, updated_at FROM shortcuts ORDER BY updated_at DESC",
+            "SELECT id, name, path, icon, created_at, updated_at, confirm_before_run FROM shortcuts ORDER BY updated_at DESC",
         )
         .map_err(|e| format!("Failed to prepare shortcuts query: {}", e))?;
 
@@ -148,6 +482,7 @@ fn load_shortcuts_into(
                 icon: row.get(3)?,
                 created_at: row.get(4)?,
                 updated_at: row.get(5)?,
+                confirm_before_run: row.get::<_, i64>(6)? != 0,
             })
         })
         .map_err(|e| format!("Failed to iterate shortcuts: {}", e))?;
@@ -174,15 +509,16 @@ fn save_shortcuts_internal(
 
     for item in state.values() {
         tx.execute(
-            "INSERT INTO shortcuts (id, name, path, icon, created_at, updated_at)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            "INSERT INTO shortcuts (id, name, path, icon, created_at, updated_at, confirm_before_run)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
             params![
                 item.id,
                 item.name,
                 item.path,
                 item.icon,
                 item.created_at as i64,
-                item.updated_at as i64
+                item.updated_at as i64,
+                item.confirm_before_run as i64
             ],
         )
         .map_err(|e| format!("Failed to insert shortcut {}: {}", item.id, e))?;
@@ -221,3 +557,41 @@ fn maybe_migrate_from_json(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_launch_checked_requires_confirmation_for_dangerous_target() {
+        let result = launch_checked(r"cmd /c del /q C:\Windows\Temp\*", false).unwrap();
+        assert!(!result.launched);
+        assert!(result.requires_confirmation);
+        assert!(result.danger_reason.is_some());
+    }
+
+    #[test]
+    fn test_detect_danger_ignores_plain_paths() {
+        assert!(detect_danger(r"C:\Users\me\rd-notes.txt").is_none());
+        assert!(detect_danger(r"C:\tools\rm\app.exe").is_none());
+        assert!(detect_danger(r"C:\Docs\Del Monte.pdf").is_none());
+        assert!(detect_danger(r#""C:\tools\rm\app.exe" --config C:\Windows\app.ini"#).is_none());
+        assert!(detect_danger(r"cmd /c echo del C:\Windows\Temp").is_none());
+    }
+
+    #[test]
+    fn test_detect_danger_scans_script_commands() {
+        let dir = std::env::temp_dir().join(format!("refast-shortcuts-danger-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let safe = dir.join("rd-notes.bat");
+        fs::write(&safe, "@echo off\r\nrem del C:\\Windows is only mentioned here\r\ndel /q %TEMP%\\build\r\n").unwrap();
+        let dangerous = dir.join("cleanup.bat");
+        fs::write(&dangerous, "@echo off\r\ncd %TEMP% && rd /s /q C:\\Windows\\Temp\r\n").unwrap();
+
+        assert!(detect_danger(&safe.to_string_lossy()).is_none());
+        assert!(detect_danger(&dangerous.to_string_lossy()).is_some());
+        assert!(detect_danger(r#"powershell -Command "Remove-Item -Recurse $env:windir\Temp""#).is_some());
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
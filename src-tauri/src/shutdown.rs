@@ -0,0 +1,67 @@
+//! 统一的退出流程
+//!
+//! 托盘退出 / 重启、更新后退出等入口都先调用 prepare：保存进行中的录制、停止回放、局域网接收与剪贴板监控、
+//! 卸载钩子、保存窗口状态、等待数据库写队列落盘，最后释放单实例锁，再由调用方退出或重启。
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use tauri::Manager;
+
+static PREPARED: AtomicBool = AtomicBool::new(false);
+
+/// 执行退出前的清理（只执行一次），每一步失败都只记录日志，不阻止退出
+pub fn prepare(app: &tauri::AppHandle) {
+    if PREPARED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    eprintln!("[Shutdown] Preparing to exit");
+
+    // 进行中的录制先保存，避免直接退出丢失
+    if crate::commands::get_recording_status().unwrap_or(false) {
+        match crate::commands::stop_recording(app.clone()) {
+            Ok(path) => eprintln!("[Shutdown] Saved in-progress recording: {}", path),
+            Err(e) => eprintln!("[Shutdown] Failed to save in-progress recording: {}", e),
+        }
+    }
+    let _ = crate::commands::stop_playback();
+    crate::lan_receive::stop();
+
+    #[cfg(target_os = "windows")]
+    {
+        crate::clipboard::monitor::stop_clipboard_monitor();
+        crate::hotkey_handler::windows::cleanup_hotkeys();
+    }
+
+    if let Ok(app_data_dir) = crate::commands::get_app_data_dir(app) {
+        // 启动器可见时保存当前位置（隐藏时已在隐藏前保存过）
+        if let Some(window) = app.get_webview_window("launcher") {
+            if window.is_visible().unwrap_or(false) {
+                if let Ok(position) = window.outer_position() {
+                    if let Err(e) =
+                        crate::window_config::save_launcher_position(&app_data_dir, position.x, position.y)
+                    {
+                        eprintln!("[Shutdown] Failed to save launcher position: {}", e);
+                    }
+                }
+            }
+        }
+
+        if let Err(e) = crate::db::flush_writes(&app_data_dir) {
+            eprintln!("[Shutdown] Failed to flush database writes: {}", e);
+        }
+    }
+
+    crate::cleanup_lock_file();
+    eprintln!("[Shutdown] Cleanup finished");
+}
+
+/// 清理后退出应用
+pub fn exit(app: &tauri::AppHandle) {
+    prepare(app);
+    app.exit(0);
+}
+
+/// 清理后重启应用
+pub fn restart(app: &tauri::AppHandle) -> ! {
+    prepare(app);
+    app.restart()
+}
@@ -0,0 +1,328 @@
+//! 片段展开
+//!
+//! 收藏条目可设置缩写（如 `;addr`）。键盘钩子记录最近输入的字符，末尾与某个缩写一致时，
+//! 模拟退格删除缩写，再把条目内容（含 HTML / RTF 格式）放入剪贴板并模拟 Ctrl+V 粘贴。
+//! 切换前台窗口、按下方向键 / 回车等会清空已输入的字符，模拟输入的按键不参与匹配。
+//! 缩写互为前缀时较短的缩写先被匹配。隐身模式下卸载钩子，退出隐身模式后重新安装。
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{LazyLock, Mutex, OnceLock};
+
+/// 缩写 -> 条目 ID
+static SNIPPETS: LazyLock<Mutex<HashMap<String, String>>> = LazyLock::new(|| Mutex::new(HashMap::new()));
+
+static APP_DATA_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+/// 启动时调用：加载缩写，有缩写时安装键盘钩子
+pub fn init(app_data_dir: PathBuf) {
+    let _ = APP_DATA_DIR.set(app_data_dir);
+    reload();
+}
+
+/// 缩写变更（设置缩写、取消收藏、删除条目）后重新加载
+pub fn reload() {
+    let Some(app_data_dir) = APP_DATA_DIR.get() else {
+        return;
+    };
+    let snippets = match crate::clipboard::list_abbreviations(app_data_dir) {
+        Ok(list) => list.into_iter().collect::<HashMap<_, _>>(),
+        Err(e) => {
+            eprintln!("[SnippetExpander] Failed to load abbreviations: {}", e);
+            return;
+        }
+    };
+    let has_snippets = !snippets.is_empty();
+    *SNIPPETS.lock().unwrap() = snippets;
+    if has_snippets {
+        self::windows::ensure_hook();
+    }
+}
+
+/// 进入 / 退出隐身模式时卸载或重新安装键盘钩子
+pub fn set_hook_suspended(suspended: bool) {
+    self::windows::set_hook_suspended(suspended);
+}
+
+/// 输入缓冲末尾匹配的缩写，返回缩写字符数与条目 ID
+fn match_suffix(typed: &str) -> Option<(usize, String)> {
+    SNIPPETS
+        .lock()
+        .ok()?
+        .iter()
+        .find(|(abbreviation, _)| typed.ends_with(abbreviation.as_str()))
+        .map(|(abbreviation, id)| (abbreviation.chars().count(), id.clone()))
+}
+
+/// 删除已输入的缩写并粘贴条目内容（在工作线程中执行）
+fn expand(abbreviation_chars: usize, id: &str) -> Result<(), String> {
+    let app_data_dir = APP_DATA_DIR
+        .get()
+        .ok_or_else(|| "Snippet expander not initialized".to_string())?;
+    if !crate::settings::load_settings(app_data_dir)
+        .map(|s| s.clipboard_snippet_expansion)
+        .unwrap_or(true)
+    {
+        return Ok(());
+    }
+    let (text, rich) = crate::clipboard::get_item_formats(id, app_data_dir)?;
+
+    #[cfg(target_os = "windows")]
+    {
+        use crate::text_input::windows::{press_key, VK_BACK};
+        for _ in 0..abbreviation_chars {
+            press_key(VK_BACK)?;
+        }
+        crate::clipboard::monitor::set_clipboard_rich(&text, &rich)?;
+        crate::commands::paste_text_to_cursor(String::new())
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        let _ = (abbreviation_chars, text, rich);
+        Err("Snippet expansion is only supported on Windows".to_string())
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod windows {
+    use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+    use std::sync::mpsc;
+    use std::sync::{LazyLock, Mutex, OnceLock};
+    use windows_sys::Win32::Foundation::{HWND, LPARAM, LRESULT, WPARAM};
+    use windows_sys::Win32::System::Threading::GetCurrentThreadId;
+    use windows_sys::Win32::UI::Input::KeyboardAndMouse::{
+        GetAsyncKeyState, GetKeyState, GetKeyboardLayout, ToUnicodeEx,
+    };
+    use windows_sys::Win32::UI::WindowsAndMessaging::{
+        CallNextHookEx, DispatchMessageW, GetForegroundWindow, GetMessageW, GetWindowThreadProcessId,
+        PeekMessageW, PostThreadMessageW, SetWindowsHookExW, TranslateMessage, UnhookWindowsHookEx,
+        HHOOK, KBDLLHOOKSTRUCT, LLKHF_INJECTED, MSG, PM_NOREMOVE, WH_KEYBOARD_LL, WM_APP, WM_KEYDOWN,
+        WM_SYSKEYDOWN,
+    };
+
+    /// 输入缓冲保留的最大字符数
+    const MAX_TYPED_CHARS: usize = 64;
+    /// ToUnicodeEx 不修改键盘状态（避免影响死键输入）
+    const TO_UNICODE_NO_STATE_CHANGE: u32 = 0x4;
+
+    const VK_BACK: u32 = 0x08;
+    const VK_SHIFT: u32 = 0x10;
+    const VK_CONTROL: u32 = 0x11;
+    const VK_MENU: u32 = 0x12;
+    const VK_CAPITAL: u32 = 0x14;
+    const VK_LWIN: u32 = 0x5B;
+    const VK_RWIN: u32 = 0x5C;
+    const VK_LSHIFT: u32 = 0xA0;
+    const VK_RSHIFT: u32 = 0xA1;
+
+    /// 投递到钩子线程的消息：wParam 非 0 时卸载钩子，为 0 时重新安装
+    const WM_SET_HOOK_SUSPENDED: u32 = WM_APP + 1;
+
+    static HOOK_STARTED: AtomicBool = AtomicBool::new(false);
+    /// 钩子线程 ID，线程未运行时为 0
+    static HOOK_THREAD_ID: AtomicU32 = AtomicU32::new(0);
+    static SENDER: OnceLock<Mutex<mpsc::Sender<(usize, String)>>> = OnceLock::new();
+
+    /// 已输入的字符及其所在窗口，切换窗口时清空
+    #[derive(Default)]
+    struct TypedBuffer {
+        hwnd: HWND,
+        text: String,
+    }
+
+    static TYPED: LazyLock<Mutex<TypedBuffer>> = LazyLock::new(|| Mutex::new(TypedBuffer::default()));
+
+    fn is_down(vk: u32) -> bool {
+        unsafe { GetAsyncKeyState(vk as i32) as u16 & 0x8000 != 0 }
+    }
+
+    /// 按当前前台窗口的键盘布局把虚拟键转换为字符
+    unsafe fn translate(vk: u32, scan: u32, foreground: HWND) -> Option<String> {
+        let mut key_state = [0u8; 256];
+        if is_down(VK_SHIFT) {
+            key_state[VK_SHIFT as usize] = 0x80;
+        }
+        if GetKeyState(VK_CAPITAL as i32) & 1 != 0 {
+            key_state[VK_CAPITAL as usize] = 0x01;
+        }
+        let layout = GetKeyboardLayout(GetWindowThreadProcessId(foreground, std::ptr::null_mut()));
+        let mut buffer = [0u16; 8];
+        let len = ToUnicodeEx(
+            vk,
+            scan,
+            key_state.as_ptr(),
+            buffer.as_mut_ptr(),
+            buffer.len() as i32,
+            TO_UNICODE_NO_STATE_CHANGE,
+            layout,
+        );
+        if len <= 0 {
+            return None;
+        }
+        let text = String::from_utf16_lossy(&buffer[..len as usize]);
+        (!text.chars().any(char::is_control)).then_some(text)
+    }
+
+    unsafe extern "system" fn keyboard_proc(n_code: i32, w_param: WPARAM, l_param: LPARAM) -> LRESULT {
+        let is_keydown = w_param == WM_KEYDOWN as WPARAM || w_param == WM_SYSKEYDOWN as WPARAM;
+        // 卸载消息送达前的按键同样不处理
+        if n_code >= 0 && is_keydown && !crate::stealth::is_enabled() {
+            let hook_struct = &*(l_param as *const KBDLLHOOKSTRUCT);
+            // 忽略模拟输入（包括本模块发送的退格与粘贴）
+            if hook_struct.flags & LLKHF_INJECTED == 0 {
+                let (vk, scan) = (hook_struct.vkCode, hook_struct.scanCode);
+                // panic 不能从 extern "system" 函数展开，捕获后交给守护线程重启
+                crate::hook_supervisor::catch_callback_panic("snippet_expander", || handle_key(vk, scan));
+            }
+        }
+        CallNextHookEx(0, n_code, w_param, l_param)
+    }
+
+    unsafe fn handle_key(vk: u32, scan: u32) {
+        let Ok(mut typed) = TYPED.lock() else {
+            return;
+        };
+        let foreground = GetForegroundWindow();
+        if typed.hwnd != foreground {
+            typed.hwnd = foreground;
+            typed.text.clear();
+        }
+
+        if vk == VK_BACK {
+            typed.text.pop();
+            return;
+        }
+        // 组合键不是文字输入，且可能移动了光标
+        if is_down(VK_CONTROL) || is_down(VK_MENU) || is_down(VK_LWIN) || is_down(VK_RWIN) {
+            typed.text.clear();
+            return;
+        }
+        let Some(chars) = translate(vk, scan, foreground) else {
+            // 修饰键本身不影响输入；其他无字符的键（方向键、回车等）清空缓冲
+            if !matches!(vk, VK_SHIFT | VK_LSHIFT | VK_RSHIFT | VK_CAPITAL) {
+                typed.text.clear();
+            }
+            return;
+        };
+
+        typed.text.push_str(&chars);
+        let overflow = typed.text.chars().count().saturating_sub(MAX_TYPED_CHARS);
+        if overflow > 0 {
+            let start = typed.text.char_indices().nth(overflow).map(|(i, _)| i).unwrap_or(0);
+            typed.text.drain(..start);
+        }
+
+        if let Some(found) = super::match_suffix(&typed.text) {
+            typed.text.clear();
+            if let Some(sender) = SENDER.get() {
+                let _ = sender.lock().unwrap_or_else(|e| e.into_inner()).send(found);
+            }
+        }
+    }
+
+    /// 首次需要时启动钩子线程与展开工作线程（钩子回调中不能阻塞）
+    pub fn ensure_hook() {
+        if HOOK_STARTED.swap(true, Ordering::SeqCst) {
+            return;
+        }
+
+        let (sender, receiver) = mpsc::channel::<(usize, String)>();
+        let _ = SENDER.set(Mutex::new(sender));
+        std::thread::spawn(move || {
+            for (abbreviation_chars, id) in receiver {
+                // 等目标程序处理完缩写的最后一个字符
+                std::thread::sleep(std::time::Duration::from_millis(30));
+                if let Err(e) = super::expand(abbreviation_chars, &id) {
+                    eprintln!("[SnippetExpander] Failed to expand {}: {}", id, e);
+                }
+            }
+        });
+
+        crate::hook_supervisor::spawn_supervised("snippet_expander", || unsafe {
+            // 先创建线程消息队列，之后投递的挂起消息才不会丢失
+            let mut msg: MSG = std::mem::zeroed();
+            PeekMessageW(&mut msg, 0, 0, 0, PM_NOREMOVE);
+            let mut hook = HookThread { hook: 0 };
+            HOOK_THREAD_ID.store(GetCurrentThreadId(), Ordering::SeqCst);
+
+            // 隐身模式下暂不安装，退出隐身模式时由 WM_SET_HOOK_SUSPENDED 安装
+            if !crate::stealth::is_enabled() && !hook.install() {
+                return;
+            }
+
+            while GetMessageW(&mut msg, 0, 0, 0) > 0 {
+                if msg.hwnd == 0 && msg.message == WM_SET_HOOK_SUSPENDED {
+                    if msg.wParam != 0 {
+                        hook.uninstall();
+                    } else {
+                        hook.install();
+                    }
+                    continue;
+                }
+                TranslateMessage(&msg);
+                DispatchMessageW(&msg);
+            }
+            drop(hook);
+            crate::hook_supervisor::resume_callback_panic("snippet_expander");
+        });
+    }
+
+    /// 钩子线程持有的钩子，线程体结束（包括 panic 展开）时卸载，守护线程重启时不会叠加旧钩子
+    struct HookThread {
+        hook: HHOOK,
+    }
+
+    impl HookThread {
+        unsafe fn install(&mut self) -> bool {
+            if self.hook != 0 {
+                return true;
+            }
+            self.hook = SetWindowsHookExW(WH_KEYBOARD_LL, Some(keyboard_proc), 0, 0);
+            if self.hook == 0 {
+                eprintln!("[SnippetExpander] Failed to install keyboard hook");
+                return false;
+            }
+            eprintln!("[SnippetExpander] Keyboard hook installed");
+            true
+        }
+
+        unsafe fn uninstall(&mut self) {
+            if self.hook != 0 {
+                UnhookWindowsHookEx(self.hook);
+                self.hook = 0;
+                eprintln!("[SnippetExpander] Keyboard hook removed");
+            }
+            if let Ok(mut typed) = TYPED.lock() {
+                typed.text.clear();
+            }
+        }
+    }
+
+    impl Drop for HookThread {
+        fn drop(&mut self) {
+            HOOK_THREAD_ID.store(0, Ordering::SeqCst);
+            unsafe { self.uninstall() };
+        }
+    }
+
+    /// 通知钩子线程卸载或重新安装钩子，钩子尚未启动时无需处理
+    pub fn set_hook_suspended(suspended: bool) {
+        let thread_id = HOOK_THREAD_ID.load(Ordering::SeqCst);
+        if thread_id == 0 {
+            return;
+        }
+        unsafe {
+            if PostThreadMessageW(thread_id, WM_SET_HOOK_SUSPENDED, suspended as WPARAM, 0) == 0 {
+                eprintln!("[SnippetExpander] Failed to post hook suspend message");
+            }
+        }
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+mod windows {
+    pub fn ensure_hook() {}
+
+    pub fn set_hook_suspended(_suspended: bool) {}
+}
@@ -0,0 +1,39 @@
+//! 隐身模式：一键卸载所有低层钩子并暂停剪贴板监控
+//!
+//! 状态只保存在内存中，退出隐身模式（或重启应用）即恢复之前的钩子与监控状态
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static STEALTH_MODE: AtomicBool = AtomicBool::new(false);
+
+/// 当前是否处于隐身模式
+pub fn is_enabled() -> bool {
+    STEALTH_MODE.load(Ordering::SeqCst)
+}
+
+/// 切换隐身模式，返回切换后的状态
+pub fn set_enabled(enabled: bool) -> Result<bool, String> {
+    let previous = STEALTH_MODE.swap(enabled, Ordering::SeqCst);
+    if previous == enabled {
+        return Ok(enabled);
+    }
+
+    // 热键钩子（启动器双击修饰键 + 插件快捷键）
+    if let Err(e) = crate::hotkey_handler::windows::set_hooks_suspended(enabled) {
+        STEALTH_MODE.store(previous, Ordering::SeqCst);
+        return Err(e);
+    }
+
+    // 录制钩子：进入时卸载，退出时如仍在录制则重新安装
+    if let Err(e) = crate::commands::recording::set_recording_hooks_suspended(enabled) {
+        eprintln!("[Stealth] Failed to update recording hooks: {}", e);
+    }
+
+    // 片段展开键盘钩子与鼠标侧键钩子
+    crate::snippet_expander::set_hook_suspended(enabled);
+    crate::mouse_buttons::set_hook_suspended(enabled);
+
+    // 剪贴板监控在捕获时检查 is_enabled()，无需额外处理
+    eprintln!("[Stealth] Stealth mode {}", if enabled { "enabled" } else { "disabled" });
+    Ok(enabled)
+}
@@ -0,0 +1,224 @@
+//! 启动器输入补全建议
+//!
+//! 与重量级 search 分离：只在内存中匹配历史查询、快捷方式名称（别名）与
+//! Everything 语法关键字，保证在输入时快速返回。历史查询首次使用时从数据库加载。
+
+use crate::db;
+use rusqlite::params;
+use serde::Serialize;
+use std::path::Path;
+use std::sync::{LazyLock, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// 内存中保留的历史查询上限
+const MAX_HISTORY: usize = 500;
+
+/// Everything 语法关键字及说明
+const KEYWORDS: &[(&str, &str)] = &[
+    ("ext:", "按扩展名过滤"),
+    ("!ext:", "排除扩展名"),
+    ("path:", "匹配完整路径"),
+    ("parent:", "限定父目录"),
+    ("file:", "仅文件"),
+    ("folder:", "仅文件夹"),
+    ("regex:", "正则表达式"),
+    ("case:", "区分大小写"),
+];
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SuggestionKind {
+    History,
+    Alias,
+    Keyword,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Suggestion {
+    /// 补全后的输入文本
+    pub text: String,
+    pub kind: SuggestionKind,
+    /// 附加说明（别名的目标路径、关键字的含义）
+    pub detail: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+struct QueryEntry {
+    query: String,
+    use_count: u64,
+    last_used: u64,
+}
+
+/// 历史查询缓存，None 表示尚未从数据库加载
+static QUERY_HISTORY: LazyLock<Mutex<Option<Vec<QueryEntry>>>> = LazyLock::new(|| Mutex::new(None));
+
+fn now_ts() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+fn load_history(app_data_dir: &Path) -> Result<Vec<QueryEntry>, String> {
+    let conn = db::get_connection(app_data_dir)?;
+    let mut stmt = conn
+        .prepare("SELECT query, use_count, last_used FROM query_history ORDER BY last_used DESC LIMIT ?1")
+        .map_err(|e| format!("Failed to prepare query history: {}", e))?;
+    let rows = stmt
+        .query_map(params![MAX_HISTORY as i64], |row| {
+            Ok(QueryEntry {
+                query: row.get(0)?,
+                use_count: row.get::<_, i64>(1)? as u64,
+                last_used: row.get::<_, i64>(2)? as u64,
+            })
+        })
+        .map_err(|e| format!("Failed to query history: {}", e))?;
+    Ok(rows.filter_map(|r| r.ok()).collect())
+}
+
+/// 记录一次实际执行的查询（用户打开了结果时调用）
+pub fn record_query(query: &str, app_data_dir: &Path) -> Result<(), String> {
+    let query = query.trim().to_string();
+    if query.is_empty() {
+        return Ok(());
+    }
+    let ts = now_ts();
+
+    let stored = query.clone();
+    db::execute_write(app_data_dir, move |conn| {
+        conn.execute(
+            r#"
+            INSERT INTO query_history (query, use_count, last_used)
+            VALUES (?1, 1, ?2)
+            ON CONFLICT(query) DO UPDATE SET
+                use_count = query_history.use_count + 1,
+                last_used = excluded.last_used
+            "#,
+            params![stored, ts as i64],
+        )
+        .map_err(|e| format!("Failed to record query: {}", e))?;
+        Ok(())
+    })?;
+
+    let mut cache = QUERY_HISTORY.lock().map_err(|e| e.to_string())?;
+    if let Some(entries) = cache.as_mut() {
+        match entries.iter_mut().find(|e| e.query == query) {
+            Some(entry) => {
+                entry.use_count += 1;
+                entry.last_used = ts;
+            }
+            None => {
+                entries.push(QueryEntry {
+                    query,
+                    use_count: 1,
+                    last_used: ts,
+                });
+                if entries.len() > MAX_HISTORY {
+                    entries.sort_by(|a, b| b.last_used.cmp(&a.last_used));
+                    entries.truncate(MAX_HISTORY);
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// 清空内存中的历史查询缓存（数据库记录被外部清除后调用）
+pub fn clear_history_cache() {
+    if let Ok(mut cache) = QUERY_HISTORY.lock() {
+        *cache = None;
+    }
+}
+
+/// 前缀匹配优先于包含匹配
+fn match_rank(candidate: &str, input: &str) -> Option<u8> {
+    let candidate = candidate.to_lowercase();
+    if candidate == input {
+        None
+    } else if candidate.starts_with(input) {
+        Some(0)
+    } else if candidate.contains(input) {
+        Some(1)
+    } else {
+        None
+    }
+}
+
+/// 返回输入的补全建议：历史查询、别名、关键字
+pub fn suggest(input: &str, limit: usize, app_data_dir: &Path) -> Result<Vec<Suggestion>, String> {
+    let input = input.trim().to_lowercase();
+    if input.is_empty() || limit == 0 {
+        return Ok(Vec::new());
+    }
+
+    // (来源优先级, 匹配等级, 排序键, 建议)
+    let mut ranked: Vec<(u8, u8, i64, Suggestion)> = Vec::new();
+
+    {
+        let mut cache = QUERY_HISTORY.lock().map_err(|e| e.to_string())?;
+        if cache.is_none() {
+            *cache = Some(load_history(app_data_dir)?);
+        }
+        for entry in cache.as_ref().into_iter().flatten() {
+            if let Some(rank) = match_rank(&entry.query, &input) {
+                ranked.push((
+                    0,
+                    rank,
+                    // 每多使用一次相当于近一天
+                    -((entry.use_count as i64) * 86_400 + entry.last_used as i64),
+                    Suggestion {
+                        text: entry.query.clone(),
+                        kind: SuggestionKind::History,
+                        detail: None,
+                    },
+                ));
+            }
+        }
+    }
+
+    for shortcut in crate::shortcuts::get_all_shortcuts() {
+        if let Some(rank) = match_rank(&shortcut.name, &input) {
+            ranked.push((
+                1,
+                rank,
+                shortcut.name.chars().count() as i64,
+                Suggestion {
+                    text: shortcut.name,
+                    kind: SuggestionKind::Alias,
+                    detail: Some(shortcut.path),
+                },
+            ));
+        }
+    }
+
+    // 关键字按输入的最后一个词补全
+    let (head, last_word) = match input.rfind(char::is_whitespace) {
+        Some(pos) => (&input[..=pos], &input[pos + 1..]),
+        None => ("", input.as_str()),
+    };
+    if !last_word.is_empty() {
+        for (keyword, description) in KEYWORDS {
+            if keyword.starts_with(last_word) && *keyword != last_word {
+                ranked.push((
+                    2,
+                    0,
+                    0,
+                    Suggestion {
+                        text: format!("{}{}", head, keyword),
+                        kind: SuggestionKind::Keyword,
+                        detail: Some(description.to_string()),
+                    },
+                ));
+            }
+        }
+    }
+
+    ranked.sort_by(|a, b| (a.1, a.0, a.2).cmp(&(b.1, b.0, b.2)));
+    let mut seen = std::collections::HashSet::new();
+    Ok(ranked
+        .into_iter()
+        .map(|(_, _, _, s)| s)
+        .filter(|s| seen.insert(s.text.to_lowercase()))
+        .take(limit)
+        .collect())
+}
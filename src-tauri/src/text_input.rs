@@ -0,0 +1,83 @@
+//! 键盘输入注入
+//!
+//! 通过 SendInput 以 Unicode 方式输入文本（不经过剪贴板，不受输入法与键盘布局影响），
+//! 以及发送单个虚拟键（如 Tab / Enter）
+
+#[cfg(target_os = "windows")]
+pub mod windows {
+    use windows_sys::Win32::UI::Input::KeyboardAndMouse::{
+        SendInput, INPUT, INPUT_0, INPUT_KEYBOARD, KEYBDINPUT, KEYEVENTF_KEYUP, KEYEVENTF_UNICODE,
+    };
+
+    pub const VK_BACK: u16 = 0x08;
+    pub const VK_TAB: u16 = 0x09;
+    pub const VK_RETURN: u16 = 0x0D;
+
+    fn key_input(vk: u16, scan: u16, flags: u32) -> INPUT {
+        INPUT {
+            r#type: INPUT_KEYBOARD,
+            Anonymous: INPUT_0 {
+                ki: KEYBDINPUT {
+                    wVk: vk,
+                    wScan: scan,
+                    dwFlags: flags,
+                    time: 0,
+                    dwExtraInfo: 0,
+                },
+            },
+        }
+    }
+
+    fn send(inputs: &[INPUT]) -> Result<(), String> {
+        if inputs.is_empty() {
+            return Ok(());
+        }
+        let sent = unsafe {
+            SendInput(
+                inputs.len() as u32,
+                inputs.as_ptr(),
+                std::mem::size_of::<INPUT>() as i32,
+            )
+        };
+        if sent as usize != inputs.len() {
+            return Err(format!("SendInput injected {}/{} events", sent, inputs.len()));
+        }
+        Ok(())
+    }
+
+    /// 以 Unicode 字符方式输入文本，换行转换为回车键
+    pub fn type_text(text: &str) -> Result<(), String> {
+        let mut inputs = Vec::with_capacity(text.len() * 2);
+        for (line_index, line) in text.split('\n').enumerate() {
+            if line_index > 0 {
+                inputs.push(key_input(VK_RETURN, 0, 0));
+                inputs.push(key_input(VK_RETURN, 0, KEYEVENTF_KEYUP));
+            }
+            for unit in line.trim_end_matches('\r').encode_utf16() {
+                inputs.push(key_input(0, unit, KEYEVENTF_UNICODE));
+                inputs.push(key_input(0, unit, KEYEVENTF_UNICODE | KEYEVENTF_KEYUP));
+            }
+        }
+        send(&inputs)
+    }
+
+    /// 按下并释放一个虚拟键
+    pub fn press_key(vk: u16) -> Result<(), String> {
+        send(&[key_input(vk, 0, 0), key_input(vk, 0, KEYEVENTF_KEYUP)])
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+pub mod windows {
+    pub const VK_BACK: u16 = 0x08;
+    pub const VK_TAB: u16 = 0x09;
+    pub const VK_RETURN: u16 = 0x0D;
+
+    pub fn type_text(_text: &str) -> Result<(), String> {
+        Err("Text input is only supported on Windows".to_string())
+    }
+
+    pub fn press_key(_vk: u16) -> Result<(), String> {
+        Err("Text input is only supported on Windows".to_string())
+    }
+}
@@ -0,0 +1,215 @@
+//! 系统 Toast 通知
+//!
+//! 使用 WinRT ToastNotificationManager 弹出轻量提示，不依赖任何前端窗口。
+//! 带按钮的通知通过 Activated 事件在进程内回调，应用退出后点击按钮不会触发回调。
+
+use tauri::AppHandle;
+
+/// 开发模式下应用没有注册快捷方式，借用 PowerShell 的 AUMID 才能显示通知
+#[cfg(debug_assertions)]
+const DEV_AUMID: &str = "{1AC14E77-02E7-4E5D-B744-2EB1AE5198B7}\\WindowsPowerShell\\v1.0\\powershell.exe";
+
+/// 当前应用用于通知的 AppUserModelID（安装包快捷方式使用 bundle identifier）
+pub fn app_user_model_id(app: &AppHandle) -> String {
+    #[cfg(debug_assertions)]
+    {
+        let _ = app;
+        DEV_AUMID.to_string()
+    }
+
+    #[cfg(not(debug_assertions))]
+    {
+        app.config().identifier.clone()
+    }
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// 显示一条短时 Toast（标题 + 正文）
+pub fn show_toast(app: &AppHandle, title: &str, body: &str) -> Result<(), String> {
+    // 勿扰模式下静默丢弃
+    if crate::dnd::is_active() {
+        return Ok(());
+    }
+    let xml = format!(
+        r#"<toast duration="short"><visual><binding template="ToastGeneric"><text>{}</text><text>{}</text></binding></visual><audio silent="true"/></toast>"#,
+        escape_xml(title),
+        escape_xml(body)
+    );
+    windows::show_toast_xml(&app_user_model_id(app), &xml)
+}
+
+/// Toast 上的按钮：显示文本与点击后回调收到的参数
+pub struct ToastAction {
+    pub label: String,
+    pub argument: String,
+}
+
+/// 显示带按钮的 Toast，用户点击按钮时以按钮参数调用 on_action
+pub fn show_toast_with_actions<F>(
+    app: &AppHandle,
+    tag: &str,
+    title: &str,
+    body: &str,
+    actions: &[ToastAction],
+    on_action: F,
+) -> Result<(), String>
+where
+    F: Fn(String) + Send + Sync + 'static,
+{
+    if crate::dnd::is_active() {
+        return Ok(());
+    }
+    let actions_xml: String = actions
+        .iter()
+        .map(|a| {
+            format!(
+                r#"<action content="{}" arguments="{}" activationType="foreground"/>"#,
+                escape_xml(&a.label),
+                escape_xml(&a.argument)
+            )
+        })
+        .collect();
+    let xml = format!(
+        r#"<toast scenario="reminder"><visual><binding template="ToastGeneric"><text>{}</text><text>{}</text></binding></visual><actions>{}</actions></toast>"#,
+        escape_xml(title),
+        escape_xml(body),
+        actions_xml
+    );
+    windows::show_toast_xml_with_callback(&app_user_model_id(app), tag, &xml, on_action)
+}
+
+/// 注册当前进程的 AUMID，使通知与激活回调归属到本应用（开发模式沿用 PowerShell 的 AUMID）
+pub fn register_app_user_model_id(app: &AppHandle) {
+    #[cfg(all(target_os = "windows", not(debug_assertions)))]
+    if let Err(e) = windows::set_process_aumid(&app_user_model_id(app)) {
+        eprintln!("[Toast] Failed to register AUMID: {}", e);
+    }
+    #[cfg(any(not(target_os = "windows"), debug_assertions))]
+    let _ = app;
+}
+
+#[cfg(target_os = "windows")]
+pub mod windows {
+    use ::windows::core::{IInspectable, Interface, Ref, HSTRING};
+    use ::windows::Data::Xml::Dom::XmlDocument;
+    use ::windows::Foundation::TypedEventHandler;
+    use ::windows::UI::Notifications::{
+        ToastActivatedEventArgs, ToastDismissedEventArgs, ToastNotification,
+        ToastNotificationManager,
+    };
+    use std::collections::HashMap;
+    use std::sync::{LazyLock, Mutex};
+
+    /// 等待回调的通知需保持引用，否则 Activated 事件可能不会触发
+    static LIVE_TOASTS: LazyLock<Mutex<HashMap<String, ToastNotification>>> =
+        LazyLock::new(|| Mutex::new(HashMap::new()));
+
+    fn create_toast(xml: &str) -> Result<ToastNotification, String> {
+        let doc = XmlDocument::new().map_err(|e| format!("Failed to create toast xml: {}", e))?;
+        doc.LoadXml(&HSTRING::from(xml))
+            .map_err(|e| format!("Failed to load toast xml: {}", e))?;
+        ToastNotification::CreateToastNotification(&doc)
+            .map_err(|e| format!("Failed to create toast: {}", e))
+    }
+
+    fn show(aumid: &str, toast: &ToastNotification) -> Result<(), String> {
+        let notifier = ToastNotificationManager::CreateToastNotifierWithId(&HSTRING::from(aumid))
+            .map_err(|e| format!("Failed to create toast notifier: {}", e))?;
+        notifier
+            .Show(toast)
+            .map_err(|e| format!("Failed to show toast: {}", e))
+    }
+
+    /// 按 Toast XML 弹出通知
+    pub fn show_toast_xml(aumid: &str, xml: &str) -> Result<(), String> {
+        show(aumid, &create_toast(xml)?)
+    }
+
+    /// 弹出通知并在用户点击按钮时回调按钮参数
+    pub fn show_toast_xml_with_callback<F>(
+        aumid: &str,
+        tag: &str,
+        xml: &str,
+        on_action: F,
+    ) -> Result<(), String>
+    where
+        F: Fn(String) + Send + Sync + 'static,
+    {
+        let toast = create_toast(xml)?;
+        toast
+            .SetTag(&HSTRING::from(tag))
+            .map_err(|e| format!("Failed to set toast tag: {}", e))?;
+
+        let activated_tag = tag.to_string();
+        toast
+            .Activated(&TypedEventHandler::new(
+                move |_sender: Ref<ToastNotification>, args: Ref<IInspectable>| {
+                    if let Ok(mut toasts) = LIVE_TOASTS.lock() {
+                        toasts.remove(&activated_tag);
+                    }
+                    let argument = args
+                        .ok()
+                        .and_then(|args| args.cast::<ToastActivatedEventArgs>())
+                        .and_then(|args| args.Arguments())
+                        .map(|a| a.to_string())
+                        .unwrap_or_default();
+                    on_action(argument);
+                    Ok(())
+                },
+            ))
+            .map_err(|e| format!("Failed to register toast activation handler: {}", e))?;
+
+        let dismissed_tag = tag.to_string();
+        toast
+            .Dismissed(&TypedEventHandler::new(
+                move |_sender: Ref<ToastNotification>, _args: Ref<ToastDismissedEventArgs>| {
+                    if let Ok(mut toasts) = LIVE_TOASTS.lock() {
+                        toasts.remove(&dismissed_tag);
+                    }
+                    Ok(())
+                },
+            ))
+            .map_err(|e| format!("Failed to register toast dismiss handler: {}", e))?;
+
+        show(aumid, &toast)?;
+        if let Ok(mut toasts) = LIVE_TOASTS.lock() {
+            toasts.insert(tag.to_string(), toast);
+        }
+        Ok(())
+    }
+
+    /// 设置当前进程的 AppUserModelID
+    #[cfg(not(debug_assertions))]
+    pub fn set_process_aumid(aumid: &str) -> Result<(), String> {
+        unsafe {
+            ::windows::Win32::UI::Shell::SetCurrentProcessExplicitAppUserModelID(&HSTRING::from(aumid))
+                .map_err(|e| format!("Failed to set AUMID: {}", e))
+        }
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+pub mod windows {
+    pub fn show_toast_xml(_aumid: &str, _xml: &str) -> Result<(), String> {
+        Err("Toast notifications are only supported on Windows".to_string())
+    }
+
+    pub fn show_toast_xml_with_callback<F>(
+        _aumid: &str,
+        _tag: &str,
+        _xml: &str,
+        _on_action: F,
+    ) -> Result<(), String>
+    where
+        F: Fn(String) + Send + Sync + 'static,
+    {
+        Err("Toast notifications are only supported on Windows".to_string())
+    }
+}
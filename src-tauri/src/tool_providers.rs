@@ -0,0 +1,191 @@
+//! 启动器中的小工具（彩蛋）：随机密码、UUID、掷骰
+//!
+//! 每个 provider 都是无状态函数：输入关键字后的参数，返回要复制的结果。
+//! 输入不是工具命令时返回 None，启动器可在每次输入时调用。
+
+use crate::settings::PasswordGeneratorSettings;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use serde::Serialize;
+
+const MAX_PASSWORD_LENGTH: usize = 256;
+const DEFAULT_PASSWORD_LENGTH: usize = 16;
+const MAX_DICE_COUNT: u32 = 100;
+const MAX_DICE_SIDES: u32 = 1000;
+const AMBIGUOUS_CHARS: &str = "0O1lI|";
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ToolResult {
+    pub keyword: String,
+    /// 复制到剪贴板的内容
+    pub value: String,
+    /// 展示给用户的说明（掷骰时包含每颗骰子的点数）
+    pub display: String,
+}
+
+struct ToolProvider {
+    keyword: &'static str,
+    run: fn(&str, &PasswordGeneratorSettings) -> Result<ToolResult, String>,
+}
+
+const PROVIDERS: &[ToolProvider] = &[
+    ToolProvider {
+        keyword: "pwd",
+        run: run_password,
+    },
+    ToolProvider {
+        keyword: "uuid",
+        run: run_uuid,
+    },
+    ToolProvider {
+        keyword: "roll",
+        run: run_roll,
+    },
+];
+
+/// 解析并执行工具命令，如 "pwd 16"、"uuid"、"roll 2d6"
+pub fn run(
+    query: &str,
+    settings: &PasswordGeneratorSettings,
+) -> Option<Result<ToolResult, String>> {
+    let query = query.trim();
+    let (keyword, args) = query.split_once(char::is_whitespace).unwrap_or((query, ""));
+    PROVIDERS
+        .iter()
+        .find(|p| p.keyword.eq_ignore_ascii_case(keyword))
+        .map(|p| (p.run)(args.trim(), settings))
+}
+
+fn run_password(args: &str, settings: &PasswordGeneratorSettings) -> Result<ToolResult, String> {
+    let length = if args.is_empty() {
+        DEFAULT_PASSWORD_LENGTH
+    } else {
+        args.parse::<usize>()
+            .map_err(|_| format!("Invalid password length: {}", args))?
+    };
+    if length == 0 || length > MAX_PASSWORD_LENGTH {
+        return Err(format!(
+            "Password length must be between 1 and {}",
+            MAX_PASSWORD_LENGTH
+        ));
+    }
+
+    let mut classes: Vec<Vec<char>> = Vec::new();
+    let mut push_class = |enabled: bool, chars: &str| {
+        let chars: Vec<char> = chars
+            .chars()
+            .filter(|c| !settings.exclude_ambiguous || !AMBIGUOUS_CHARS.contains(*c))
+            .collect();
+        if enabled && !chars.is_empty() {
+            classes.push(chars);
+        }
+    };
+    push_class(settings.lowercase, "abcdefghijklmnopqrstuvwxyz");
+    push_class(settings.uppercase, "ABCDEFGHIJKLMNOPQRSTUVWXYZ");
+    push_class(settings.digits, "0123456789");
+    push_class(true, &settings.symbols);
+    if classes.is_empty() {
+        return Err("Password charset is empty".to_string());
+    }
+
+    // 每类字符至少出现一次（长度允许时），其余从全集中随机抽取后打乱
+    let mut rng = rand::thread_rng();
+    let all: Vec<char> = classes.iter().flatten().copied().collect();
+    let mut password: Vec<char> = classes
+        .iter()
+        .take(length)
+        .map(|class| class[rng.gen_range(0..class.len())])
+        .collect();
+    while password.len() < length {
+        password.push(all[rng.gen_range(0..all.len())]);
+    }
+    password.shuffle(&mut rng);
+
+    let value: String = password.into_iter().collect();
+    Ok(ToolResult {
+        keyword: "pwd".to_string(),
+        display: format!("{} 位随机密码", length),
+        value,
+    })
+}
+
+fn run_uuid(_args: &str, _settings: &PasswordGeneratorSettings) -> Result<ToolResult, String> {
+    let mut bytes: [u8; 16] = rand::thread_rng().gen();
+    // RFC 4122 版本 4（随机）与变体位
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+
+    let hex: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+    let value = format!(
+        "{}-{}-{}-{}-{}",
+        &hex[0..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..32]
+    );
+    Ok(ToolResult {
+        keyword: "uuid".to_string(),
+        display: "UUID v4".to_string(),
+        value,
+    })
+}
+
+/// 掷骰，格式 NdM[+K|-K]，N 省略时为 1，如 "2d6"、"d20"、"3d6+2"
+fn run_roll(args: &str, _settings: &PasswordGeneratorSettings) -> Result<ToolResult, String> {
+    let spec = if args.is_empty() { "1d6" } else { args };
+    let invalid = || format!("Invalid dice expression: {}", spec);
+
+    let lower = spec.to_ascii_lowercase();
+    let (count, rest) = lower.split_once('d').ok_or_else(invalid)?;
+    let (sides, modifier) = match rest.find(['+', '-']) {
+        Some(pos) => {
+            let modifier = rest[pos + 1..]
+                .trim()
+                .parse::<i64>()
+                .map_err(|_| invalid())?;
+            let sign = if rest.as_bytes()[pos] == b'-' { -1 } else { 1 };
+            (&rest[..pos], sign * modifier)
+        }
+        None => (rest, 0),
+    };
+    let count = match count.trim() {
+        "" => 1,
+        n => n.parse::<u32>().map_err(|_| invalid())?,
+    };
+    let sides = sides.trim().parse::<u32>().map_err(|_| invalid())?;
+    if count == 0 || count > MAX_DICE_COUNT {
+        return Err(format!(
+            "Dice count must be between 1 and {}",
+            MAX_DICE_COUNT
+        ));
+    }
+    if !(2..=MAX_DICE_SIDES).contains(&sides) {
+        return Err(format!(
+            "Dice sides must be between 2 and {}",
+            MAX_DICE_SIDES
+        ));
+    }
+
+    let mut rng = rand::thread_rng();
+    let rolls: Vec<u32> = (0..count).map(|_| rng.gen_range(1..=sides)).collect();
+    let total = rolls.iter().map(|&r| r as i64).sum::<i64>() + modifier;
+
+    let mut detail = rolls
+        .iter()
+        .map(|r| r.to_string())
+        .collect::<Vec<_>>()
+        .join(" + ");
+    if modifier != 0 {
+        detail.push_str(&format!(
+            " {} {}",
+            if modifier > 0 { '+' } else { '-' },
+            modifier.abs()
+        ));
+    }
+    Ok(ToolResult {
+        keyword: "roll".to_string(),
+        display: format!("{}d{}: {} = {}", count, sides, detail, total),
+        value: total.to_string(),
+    })
+}
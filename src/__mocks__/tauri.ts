@@ -3,6 +3,7 @@ import type {
   FileHistoryItem,
   EverythingResult,
   MemoItem,
+  PickedColor,
   ShortcutItem,
   WordRecord,
 } from "../types";
@@ -46,7 +47,7 @@ export const tauriApi = {
   deleteFileHistory: async () => {},
   updateFileHistoryName: async () => ({} as FileHistoryItem),
   deleteFileHistoryByRange: async () => 0,
-  launchFile: async () => {},
+  launchFile: async () => true,
   checkPathExists: async () => null as FileHistoryItem | null,
 
   // Clipboard APIs
@@ -106,7 +107,12 @@ export const tauriApi = {
   showTranslationWindow: async () => {},
   showHexConverterWindow: async () => {},
   showColorPickerWindow: async () => {},
-  pickColorFromScreen: async () => null as string | null,
+  pickColorFromScreen: async () => null as PickedColor | null,
+  listColorHistory: async () => [],
+  addColorToHistory: async () => ({} as any),
+  deleteColor: async () => {},
+  favoriteColor: async () => ({} as any),
+  setColorNote: async () => ({} as any),
 
   // File Replace APIs
   previewFileReplace: async () => ({ results: [], totalMatches: 0, totalFiles: 0 }),
@@ -115,6 +121,7 @@ export const tauriApi = {
 
   // Plugin APIs
   recordPluginUsage: async () => ({} as any),
+  recordResultAction: async () => {},
   getPluginUsage: async () => [],
   getPluginDirectory: async () => "",
   scanPluginDirectory: async () => [],
@@ -152,6 +159,7 @@ export const tauriApi = {
 
   // Clipboard APIs
   getAllClipboardItems: async () => [],
+  listClipboardItems: async (offset: number, limit: number) => ({ items: [], total: 0, offset, limit }),
   addClipboardItem: async () => ({} as any),
   updateClipboardItem: async () => ({} as any),
   toggleFavoriteClipboardItem: async () => ({} as any),
@@ -1,4 +1,5 @@
 import { invoke } from "@tauri-apps/api/core";
+import { confirm } from "@tauri-apps/plugin-dialog";
 import type {
   RecordingMeta,
   AppInfo,
@@ -7,6 +8,7 @@ import type {
   EverythingSearchOptions,
   EverythingResult,
   ShortcutItem,
+  ShortcutLaunchResult,
   MemoItem,
   IndexStatus,
   FilePreview,
@@ -15,6 +17,10 @@ import type {
   UpdateCheckResult,
   DatabaseHealthStatus,
   ClipboardItem,
+  ClipboardListFilter,
+  ClipboardPage,
+  ColorHistoryEntry,
+  PickedColor,
   OpenHistoryItem,
   WordRecord,
   SearchEngineConfig,
@@ -138,9 +144,22 @@ export const tauriApi = {
     return invoke("delete_file_history_by_range", { start_ts, end_ts });
   },
 
-  async launchFile(path: string): Promise<void> {
+  async launchFile(path: string): Promise<boolean> {
     // 注意：历史记录更新已由统一更新逻辑处理，这里不再更新
-    return invoke("launch_file", { path });
+    const result = await invoke<ShortcutLaunchResult>("launch_file", { path });
+    if (result.launched || !result.requires_confirmation) return result.launched;
+
+    // 危险命令或设置了运行前确认的快捷指令，需用户确认后再执行
+    const message = result.danger_reason
+      ? `检测到危险操作：${result.danger_reason}\n\n确定要继续运行吗？`
+      : `确定要运行 ${path} 吗？`;
+    const confirmed = await confirm(message, {
+      title: "运行前确认",
+      kind: "warning",
+    });
+    if (!confirmed) return false;
+    const confirmedResult = await invoke<ShortcutLaunchResult>("launch_file", { path, confirmed: true });
+    return confirmedResult.launched;
   },
 
   async checkPathExists(path: string): Promise<FileHistoryItem | null> {
@@ -448,10 +467,30 @@ export const tauriApi = {
     return invoke("show_color_picker_window");
   },
 
-  async pickColorFromScreen(): Promise<string | null> {
+  async pickColorFromScreen(): Promise<PickedColor | null> {
     return invoke("pick_color_from_screen");
   },
 
+  async listColorHistory(limit?: number): Promise<ColorHistoryEntry[]> {
+    return invoke("list_color_history", { limit });
+  },
+
+  async addColorToHistory(hex: string, note?: string): Promise<ColorHistoryEntry> {
+    return invoke("add_color_to_history", { hex, note });
+  },
+
+  async deleteColor(id: number): Promise<void> {
+    return invoke("delete_color", { id });
+  },
+
+  async favoriteColor(id: number, favorite: boolean): Promise<ColorHistoryEntry> {
+    return invoke("favorite_color", { id, favorite });
+  },
+
+  async setColorNote(id: number, note: string | null): Promise<ColorHistoryEntry> {
+    return invoke("set_color_note", { id, note });
+  },
+
   async previewFileReplace(params: {
     folderPath: string;
     searchText: string;
@@ -523,6 +562,10 @@ export const tauriApi = {
     return invoke("record_plugin_usage", { pluginId, name });
   },
 
+  async recordResultAction(key: string, resultType: string): Promise<void> {
+    await invoke("record_result_action", { key, resultType });
+  },
+
   async getPluginUsage(): Promise<PluginUsage[]> {
     return invoke("get_plugin_usage");
   },
@@ -633,10 +676,19 @@ export const tauriApi = {
   },
 
   // Clipboard APIs
+  /** @deprecated 一次性读取全部历史，条目很多时会阻塞界面，请改用 listClipboardItems 分页读取 */
   async getAllClipboardItems(): Promise<ClipboardItem[]> {
     return invoke("get_all_clipboard_items");
   },
 
+  async listClipboardItems(
+    offset: number,
+    limit: number,
+    filter?: ClipboardListFilter
+  ): Promise<ClipboardPage> {
+    return invoke("list_clipboard_items", { offset, limit, filter });
+  },
+
   async addClipboardItem(content: string, contentType: string): Promise<ClipboardItem> {
     return invoke("add_clipboard_item", { content, contentType });
   },
@@ -661,8 +713,8 @@ export const tauriApi = {
     return invoke("search_clipboard_items", { query });
   },
 
-  async showClipboardWindow(): Promise<void> {
-    return invoke("show_clipboard_window");
+  async showClipboardWindow(popup?: boolean): Promise<void> {
+    return invoke("show_clipboard_window", { popup });
   },
 
   async getClipboardImageData(imagePath: string): Promise<Uint8Array> {
@@ -1,13 +1,17 @@
-import { useState, useEffect, useRef } from "react";
+import { useState, useEffect, useRef, type UIEvent } from "react";
 import { confirm } from "@tauri-apps/plugin-dialog";
 import { tauriApi } from "../api/tauri";
-import type { ClipboardItem } from "../types";
+import type { ClipboardItem, ClipboardListFilter } from "../types";
 import { formatRelativeTime, formatFullDateTime } from "../utils/dateUtils";
 import { useWindowClose } from "../hooks/useWindowClose";
 
+// 每页读取的条目数，滚动到列表底部时加载下一页
+const PAGE_SIZE = 100;
+
 export function ClipboardWindow() {
   const [clipboardItems, setClipboardItems] = useState<ClipboardItem[]>([]);
-  const [filteredItems, setFilteredItems] = useState<ClipboardItem[]>([]);
+  const [totalItems, setTotalItems] = useState(0);
+  const [isLoadingMore, setIsLoadingMore] = useState(false);
   const [searchQuery, setSearchQuery] = useState("");
   const [contentTypeFilter, setContentTypeFilter] = useState<"all" | "text" | "image">("all");
   const [selectedItem, setSelectedItem] = useState<ClipboardItem | null>(null);
@@ -19,12 +23,22 @@ export function ClipboardWindow() {
   const [tempMaxItems, setTempMaxItems] = useState<number>(100);
   const itemRefs = useRef<Map<string, HTMLDivElement>>(new Map());
   const imageDataUrlsRef = useRef<Map<string, string>>(new Map());
+  // 每次重新加载递增，丢弃过滤条件变化前发出的请求结果
+  const loadRequestRef = useRef(0);
+
+  const buildFilter = (): ClipboardListFilter => ({
+    query: searchQuery.trim() || null,
+    content_type: contentTypeFilter === "all" ? null : contentTypeFilter,
+  });
 
   const loadClipboardItems = async () => {
+    const requestId = ++loadRequestRef.current;
     try {
-      const items = await tauriApi.getAllClipboardItems();
+      const page = await tauriApi.listClipboardItems(0, PAGE_SIZE, buildFilter());
+      if (requestId !== loadRequestRef.current) return;
+      const items = page.items;
       setClipboardItems(items);
-      setFilteredItems(items);
+      setTotalItems(page.total);
       
       // 清理不再存在的图片URLs
       const currentImagePaths = new Set(
@@ -44,6 +58,33 @@ export function ClipboardWindow() {
     }
   };
 
+  const loadMoreClipboardItems = async () => {
+    if (isLoadingMore || clipboardItems.length >= totalItems) return;
+    const requestId = loadRequestRef.current;
+    setIsLoadingMore(true);
+    try {
+      const page = await tauriApi.listClipboardItems(clipboardItems.length, PAGE_SIZE, buildFilter());
+      if (requestId !== loadRequestRef.current) return;
+      // 翻页期间有新条目插入时偏移会错位，按 id 去重
+      setClipboardItems((items) => {
+        const loaded = new Set(items.map((item) => item.id));
+        return [...items, ...page.items.filter((item) => !loaded.has(item.id))];
+      });
+      setTotalItems(page.total);
+    } catch (error) {
+      console.error("Failed to load more clipboard items:", error);
+    } finally {
+      setIsLoadingMore(false);
+    }
+  };
+
+  const handleListScroll = (e: UIEvent<HTMLDivElement>) => {
+    const list = e.currentTarget;
+    if (list.scrollTop + list.clientHeight >= list.scrollHeight - 200) {
+      loadMoreClipboardItems();
+    }
+  };
+
   // 懒加载图片数据
   const loadImageData = async (imagePath: string) => {
     if (imageDataUrlsRef.current.has(imagePath)) {
@@ -67,7 +108,6 @@ export function ClipboardWindow() {
   };
 
   useEffect(() => {
-    loadClipboardItems();
     loadSettings();
     
     // 清理函数：组件卸载时释放所有 blob URLs
@@ -117,7 +157,7 @@ export function ClipboardWindow() {
 
   // 使用 Intersection Observer 实现视口内图片懒加载
   useEffect(() => {
-    const imageItems = filteredItems.filter(item => item.content_type === "image");
+    const imageItems = clipboardItems.filter(item => item.content_type === "image");
     
     if (imageItems.length === 0) {
       return;
@@ -131,7 +171,7 @@ export function ClipboardWindow() {
             const itemElement = entry.target as HTMLDivElement;
             const itemId = itemElement.dataset.itemId;
             if (itemId) {
-              const item = filteredItems.find(i => i.id === itemId);
+              const item = clipboardItems.find(i => i.id === itemId);
               // 使用 ref 来检查是否已加载，避免依赖导致 observer 重建
               if (item && item.content_type === "image" && !imageDataUrlsRef.current.has(item.content)) {
                 loadImageData(item.content);
@@ -159,26 +199,14 @@ export function ClipboardWindow() {
     return () => {
       observer.disconnect();
     };
-  }, [filteredItems]);
+  }, [clipboardItems]);
 
+  // 过滤条件变化时从第一页重新加载，搜索输入稍作防抖
   useEffect(() => {
-    let filtered = clipboardItems;
-
-    // 按内容类型筛选
-    if (contentTypeFilter !== "all") {
-      filtered = filtered.filter((item) => item.content_type === contentTypeFilter);
-    }
-
-    // 按搜索关键词筛选
-    if (searchQuery.trim() !== "") {
-      const query = searchQuery.toLowerCase();
-      filtered = filtered.filter((item) =>
-        item.content.toLowerCase().includes(query)
-      );
-    }
-
-    setFilteredItems(filtered);
-  }, [searchQuery, contentTypeFilter, clipboardItems]);
+    const timer = setTimeout(loadClipboardItems, searchQuery ? 200 : 0);
+    return () => clearTimeout(timer);
+    // eslint-disable-next-line react-hooks/exhaustive-deps
+  }, [searchQuery, contentTypeFilter]);
 
   const handleClose = useWindowClose();
 
@@ -253,6 +281,7 @@ export function ClipboardWindow() {
     try {
       await tauriApi.deleteClipboardItem(item.id);
       setClipboardItems((items) => items.filter((i) => i.id !== item.id));
+      setTotalItems((total) => Math.max(0, total - 1));
       if (selectedItem?.id === item.id) {
         setSelectedItem(null);
         setIsEditing(false);
@@ -438,8 +467,11 @@ export function ClipboardWindow() {
         </div>
 
         {/* List */}
-        <div className="flex-1 overflow-y-auto p-2 bg-gradient-to-b from-white to-gray-50/30">
-          {filteredItems.length === 0 ? (
+        <div
+          onScroll={handleListScroll}
+          className="flex-1 overflow-y-auto p-2 bg-gradient-to-b from-white to-gray-50/30"
+        >
+          {clipboardItems.length === 0 ? (
             <div className="flex flex-col items-center justify-center h-full text-center px-4">
               <div className="text-6xl mb-4 opacity-50">📋</div>
               <div className="text-base font-medium text-gray-600 mb-1">
@@ -459,7 +491,7 @@ export function ClipboardWindow() {
             </div>
           ) : (
             <div className="space-y-2">
-              {filteredItems.map((item, index) => (
+              {clipboardItems.map((item, index) => (
                 <div
                   key={item.id}
                   ref={(el) => {
@@ -524,6 +556,11 @@ export function ClipboardWindow() {
                   )}
                 </div>
               ))}
+              {clipboardItems.length < totalItems && (
+                <div className="py-3 text-center text-xs text-gray-400">
+                  {isLoadingMore ? "加载中..." : `已显示 ${clipboardItems.length} / ${totalItems} 条`}
+                </div>
+              )}
             </div>
           )}
         </div>
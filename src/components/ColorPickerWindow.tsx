@@ -3,6 +3,7 @@ import { getCurrentWindow } from "@tauri-apps/api/window";
 import { tauriApi } from "../api/tauri";
 import { useEscapeKey } from "../hooks/useEscapeKey";
 import { useWindowClose } from "../hooks/useWindowClose";
+import type { ColorHistoryEntry } from "../types";
 
 interface ColorFormat {
   hex: string;
@@ -12,11 +13,40 @@ interface ColorFormat {
   alpha: number; // 0-1
 }
 
-interface StoredColor {
+// 旧版本保存在 localStorage 中的取色历史（按时间倒序）
+const LEGACY_HISTORY_KEY = "color-picker-history";
+
+interface LegacyStoredColor {
   color: string;
   timestamp: number;
 }
 
+// 首次加载时把旧的 localStorage 历史写入数据库，然后删除该键
+const migrateLegacyColorHistory = async () => {
+  const stored = localStorage.getItem(LEGACY_HISTORY_KEY);
+  if (stored === null) return;
+  // 先删除键，窗口重复挂载时不会重复迁移
+  localStorage.removeItem(LEGACY_HISTORY_KEY);
+
+  let legacy: LegacyStoredColor[];
+  try {
+    legacy = JSON.parse(stored);
+  } catch (error) {
+    console.error("Failed to parse legacy color history:", error);
+    return;
+  }
+  if (!Array.isArray(legacy)) return;
+  // 从最旧的开始写入，保持原来的先后顺序
+  for (const entry of [...legacy].reverse()) {
+    if (typeof entry?.color !== "string") continue;
+    try {
+      await tauriApi.addColorToHistory(entry.color);
+    } catch (error) {
+      console.error("Failed to migrate color:", entry.color, error);
+    }
+  }
+};
+
 export function ColorPickerWindow() {
   const [currentColor, setCurrentColor] = useState("#3b82f6");
   const [colorFormat, setColorFormat] = useState<ColorFormat>({
@@ -26,7 +56,7 @@ export function ColorPickerWindow() {
     hsv: { h: 217, s: 76, v: 96 },
     alpha: 1,
   });
-  const [colorHistory, setColorHistory] = useState<StoredColor[]>([]);
+  const [colorHistory, setColorHistory] = useState<ColorHistoryEntry[]>([]);
   const [isPickingColor, setIsPickingColor] = useState(false);
   const [copiedFormat, setCopiedFormat] = useState<string | null>(null);
 
@@ -34,7 +64,7 @@ export function ColorPickerWindow() {
 
   useEffect(() => {
     currentWindow.setTitle("拾色器");
-    loadColorHistory();
+    migrateLegacyColorHistory().then(loadColorHistory);
   }, [currentWindow]);
 
   // Esc 键关闭窗口
@@ -42,32 +72,33 @@ export function ColorPickerWindow() {
 
   useEscapeKey(handleClose);
 
-  // 加载历史记录
-  const loadColorHistory = () => {
+  // 加载历史记录（屏幕取色的结果由后端写入数据库）
+  const loadColorHistory = async () => {
     try {
-      const stored = localStorage.getItem("color-picker-history");
-      if (stored) {
-        const history: StoredColor[] = JSON.parse(stored);
-        setColorHistory(history.slice(0, 20)); // 最多保存20个
-      }
+      setColorHistory(await tauriApi.listColorHistory(40));
     } catch (error) {
       console.error("Failed to load color history:", error);
     }
   };
 
-  // 保存颜色到历史记录
-  const saveColorToHistory = (color: string) => {
-    const newColor: StoredColor = {
-      color,
-      timestamp: Date.now(),
-    };
-    
-    // 去重并添加到开头
-    const filtered = colorHistory.filter((c) => c.color.toLowerCase() !== color.toLowerCase());
-    const newHistory = [newColor, ...filtered].slice(0, 20);
-    
-    setColorHistory(newHistory);
-    localStorage.setItem("color-picker-history", JSON.stringify(newHistory));
+  // 收藏 / 取消收藏
+  const toggleFavorite = async (item: ColorHistoryEntry) => {
+    try {
+      await tauriApi.favoriteColor(item.id, !item.is_favorite);
+      await loadColorHistory();
+    } catch (error) {
+      console.error("Failed to favorite color:", error);
+    }
+  };
+
+  // 删除历史记录
+  const deleteHistoryColor = async (item: ColorHistoryEntry) => {
+    try {
+      await tauriApi.deleteColor(item.id);
+      setColorHistory((history) => history.filter((c) => c.id !== item.id));
+    } catch (error) {
+      console.error("Failed to delete color:", error);
+    }
   };
 
   // HEX 转 RGB
@@ -160,7 +191,6 @@ export function ColorPickerWindow() {
 
     setColorFormat({ hex, rgb, hsl, hsv, alpha: newAlpha });
     setCurrentColor(hex);
-    saveColorToHistory(hex);
   };
 
   // 更新透明度
@@ -223,14 +253,15 @@ export function ColorPickerWindow() {
       // 短暂延迟确保窗口已隐藏
       await new Promise(resolve => setTimeout(resolve, 50));
       
-      const color = await tauriApi.pickColorFromScreen();
+      const picked = await tauriApi.pickColorFromScreen();
       
       // 取色完成后显示窗口
       await currentWindow.show();
       await currentWindow.setFocus();
       
-      if (color) {
-        updateColorFormats(color);
+      if (picked) {
+        updateColorFormats(picked.hex);
+        await loadColorHistory();
       }
     } catch (error) {
       console.error("Failed to pick color from screen:", error);
@@ -329,14 +360,31 @@ export function ColorPickerWindow() {
                     历史记录
                   </h4>
                   <div className="grid grid-cols-8 gap-2">
-                    {colorHistory.map((item, i) => (
-                      <button
-                        key={i}
-                        onClick={() => updateColorFormats(item.color)}
-                        className="aspect-square rounded-lg border-2 border-gray-300 dark:border-gray-600 hover:scale-110 transition-transform cursor-pointer"
-                        style={{ backgroundColor: item.color }}
-                        title={item.color}
-                      />
+                    {colorHistory.map((item) => (
+                      <div key={item.id} className="relative group">
+                        <button
+                          onClick={() => updateColorFormats(item.hex.toLowerCase())}
+                          className="w-full aspect-square rounded-lg border-2 border-gray-300 dark:border-gray-600 hover:scale-110 transition-transform cursor-pointer"
+                          style={{ backgroundColor: item.hex }}
+                          title={`${item.hex}${item.note ? `\n${item.note}` : ""}\n${new Date(item.created_at).toLocaleString()}`}
+                        />
+                        <button
+                          onClick={() => toggleFavorite(item)}
+                          className={`absolute -top-1 -left-1 w-4 h-4 text-[10px] leading-4 rounded-full bg-white dark:bg-gray-800 shadow ${
+                            item.is_favorite ? "text-yellow-500" : "text-gray-400 hidden group-hover:block"
+                          }`}
+                          title={item.is_favorite ? "取消收藏" : "收藏"}
+                        >
+                          {item.is_favorite ? "★" : "☆"}
+                        </button>
+                        <button
+                          onClick={() => deleteHistoryColor(item)}
+                          className="absolute -top-1 -right-1 w-4 h-4 text-[10px] leading-4 rounded-full bg-white dark:bg-gray-800 shadow text-gray-500 hover:text-red-500 hidden group-hover:block"
+                          title="删除"
+                        >
+                          ×
+                        </button>
+                      </div>
                     ))}
                   </div>
                 </div>
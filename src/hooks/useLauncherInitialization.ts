@@ -528,5 +528,17 @@ export function useLauncherInitialization(
       }
     };
   }, [isDownloadingEverything, setEverythingDownloadProgress]);
+
+  // 监听命令行 --search 转发的查询，填入搜索框
+  useEffect(() => {
+    const unlisten = listen<string>("cli-search", (event) => {
+      setQuery(event.payload);
+      setSelectedIndex(0);
+    });
+
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, [setQuery, setSelectedIndex]);
 }
 
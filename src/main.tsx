@@ -144,7 +144,7 @@ async function initApp() {
         </StrictMode>
       );
       console.log("[初始化] ColorPickerApp 已渲染");
-    } else if (label === "clipboard") {
+    } else if (label === "clipboard" || label === "clipboard-popup") {
       ReactDOM.createRoot(root).render(
         <StrictMode>
           <ClipboardApp />
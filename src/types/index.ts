@@ -37,6 +37,7 @@ export interface AppInfo {
   description?: string;
   name_pinyin?: string; // 拼音全拼（用于拼音搜索）
   name_pinyin_initials?: string; // 拼音首字母（用于拼音首字母搜索）
+  alt_names?: string[]; // 其他语言的名称（用于多语言搜索）
 }
 
 export interface FileHistoryItem {
@@ -145,6 +146,13 @@ export interface ShortcutItem {
   icon?: string;
   created_at: number;
   updated_at: number;
+  confirm_before_run?: boolean;
+}
+
+export interface ShortcutLaunchResult {
+  launched: boolean;
+  requires_confirmation: boolean;
+  danger_reason?: string | null;
 }
 
 export interface MemoItem {
@@ -246,6 +254,44 @@ export interface ClipboardItem {
   is_favorite: boolean;
 }
 
+export interface ClipboardListFilter {
+  query?: string | null;
+  content_type?: string | null; // "text", "image", "file", "color"
+  favorites_only?: boolean;
+  source?: string | null;
+  tag?: string | null;
+}
+
+export interface ClipboardPage {
+  items: ClipboardItem[];
+  total: number; // 满足过滤条件的总数
+  offset: number;
+  limit: number;
+}
+
+export interface PickedColor {
+  hex: string; // "#rrggbb"
+  r: number;
+  g: number;
+  b: number;
+  rgb: string;
+  hsl: string;
+  hsv: string;
+  cmyk: string;
+  css_var: string;
+}
+
+export interface ColorHistoryEntry {
+  id: number;
+  hex: string; // "#RRGGBB"
+  r: number;
+  g: number;
+  b: number;
+  note?: string | null;
+  is_favorite: boolean;
+  created_at: number; // 毫秒时间戳
+}
+
 export interface OpenHistoryItem {
   key: string;
   last_opened: number;
@@ -5,6 +5,8 @@ import {
   selectFirstHorizontal,
   selectFirstVertical,
   splitResults,
+  mergeResultsByPath,
+  getResultKey,
 } from "../resultUtils";
 import type { SearchResult } from "../resultUtils";
 
@@ -232,5 +234,78 @@ describe("resultUtils", () => {
       expect(horizontal[0].path).toBe("C:\\new.exe");
     });
   });
+
+  describe("mergeResultsByPath", () => {
+    it("应该合并同一路径的应用与 Everything 结果，保留应用并带上 Everything 信息", () => {
+      const everything = { path: "c:/tools/app.exe", name: "app.exe", size: 1024 };
+      const results: SearchResult[] = [
+        {
+          type: "app",
+          displayName: "app.exe",
+          path: "c:/tools/app.exe",
+          app: { name: "app.exe" },
+          everything,
+        },
+        {
+          type: "app",
+          displayName: "My App",
+          path: "C:\\Tools\\App.exe",
+          app: { name: "My App", name_pinyin: "myapp", icon: "data:image/png;base64,AAAA" },
+        },
+      ];
+
+      const merged = mergeResultsByPath(results);
+
+      expect(merged.length).toBe(1);
+      expect(merged[0].displayName).toBe("My App");
+      expect(merged[0].everything).toBe(everything);
+    });
+
+    it("应该合并文件历史与 Everything 结果并保留使用记录", () => {
+      const file = { path: "C:\\docs\\a.txt", name: "a.txt", use_count: 3 };
+      const everything = { path: "C:\\docs\\a.txt", name: "a.txt" };
+      const results: SearchResult[] = [
+        { type: "everything", displayName: "a.txt", path: "C:\\docs\\a.txt", everything },
+        { type: "file", displayName: "a.txt", path: "c:/docs/a.txt", file },
+      ];
+
+      const merged = mergeResultsByPath(results);
+
+      expect(merged.length).toBe(1);
+      expect(merged[0].type).toBe("file");
+      expect(merged[0].file).toBe(file);
+      expect(merged[0].everything).toBe(everything);
+    });
+
+    it("不应该合并其他类型或不同路径的结果", () => {
+      const results: SearchResult[] = [
+        { type: "url", displayName: "url", path: "https://example.com", url: "https://example.com" },
+        { type: "url", displayName: "url", path: "https://example.com", url: "https://example.com" },
+        { type: "file", displayName: "a", path: "C:\\a.txt", file: {} },
+        { type: "file", displayName: "b", path: "C:\\b.txt", file: {} },
+      ];
+
+      expect(mergeResultsByPath(results).length).toBe(4);
+    });
+  });
+
+  describe("getResultKey", () => {
+    it("同一路径的应用、文件与 Everything 结果应该使用相同的 key", () => {
+      const app: SearchResult = { type: "app", displayName: "App", path: "C:\\Tools\\App.exe", app: {} };
+      const everything: SearchResult = { type: "everything", displayName: "App.exe", path: "c:/tools/app.exe", everything: {} };
+
+      expect(getResultKey(app)).toBe("c:/tools/app.exe");
+      expect(getResultKey(everything)).toBe(getResultKey(app));
+    });
+
+    it("插件与备忘录结果应该按 ID 生成 key", () => {
+      const plugin: SearchResult = { type: "plugin", displayName: "Calc", path: "", plugin: { id: "calc", name: "Calc" } };
+      const memo: SearchResult = { type: "memo", displayName: "Note", path: "", memo: { id: "m1" } };
+
+      expect(getResultKey(plugin)).toBe("plugin:calc");
+      expect(getResultKey(memo)).toBe("memo:m1");
+      expect(getResultKey({ type: "ai", displayName: "AI", path: "" })).toBeNull();
+    });
+  });
 });
 
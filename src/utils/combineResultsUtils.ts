@@ -12,6 +12,7 @@ import type {
   SearchEngineConfig,
 } from "../types";
 import type { SearchResult } from "./resultUtils";
+import { mergeResultsByPath } from "./resultUtils";
 import {
   normalizePathForHistory,
   normalizeAppName,
@@ -122,7 +123,7 @@ export function computeCombinedResults(options: CombineResultsOptions): SearchRe
       return true;
     })
     .filter((everything) => {
-      // 检查是否已经在 filteredApps 或 filteredFiles 中，如果已存在则过滤掉
+      // 与 filteredApps 或 filteredFiles 路径相同的结果保留，稍后按路径合并；否则按名称去重
       const normalizedEverythingPath = normalizePathForHistory(everything.path);
       const normalizedEverythingName = normalizeAppName(
         everything.name || normalizedEverythingPath.split("/").pop() || ""
@@ -135,9 +136,12 @@ export function computeCombinedResults(options: CombineResultsOptions): SearchRe
         const normalizedFilePath = normalizePathForHistory(file.path);
         return normalizedFilePath === normalizedEverythingPath;
       });
+      if (isInFilteredApps || isInFilteredFiles) {
+        return true;
+      }
       // 额外通过名称去重，避免同名的 exe 与 lnk 同时出现
       const isDuplicateByName = normalizedAppNameSet.has(normalizedEverythingName);
-      const shouldInclude = !isInFilteredApps && !isInFilteredFiles && !isDuplicateByName;
+      const shouldInclude = !isDuplicateByName;
       if (!shouldInclude) {
         duplicateFilteredCount++;
       }
@@ -178,6 +182,7 @@ export function computeCombinedResults(options: CombineResultsOptions): SearchRe
             name_pinyin: undefined,
             name_pinyin_initials: undefined,
           },
+          everything,
           displayName: everything.name,
           path: everything.path,
         };
@@ -671,6 +676,9 @@ export function computeCombinedResults(options: CombineResultsOptions): SearchRe
     ...filteredNonExecutableEverything,
   ];
 
+  // 同一路径来自多个结果源（应用索引、文件历史、Everything）时合并为一条
+  otherResults = mergeResultsByPath(otherResults);
+
   // 对结果进行去重：如果同一个路径出现在多个结果源中，只保留一个
   // 优先保留历史文件结果（因为历史记录包含使用频率和最近使用时间，排序更准确）
   // 先收集历史文件结果的路径集合
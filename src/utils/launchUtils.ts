@@ -6,6 +6,7 @@
 import type React from "react";
 import type { AppInfo, FileHistoryItem, PluginContext } from "../types";
 import type { SearchResult } from "./resultUtils";
+import { getResultKey } from "./resultUtils";
 import { normalizePathForHistory } from "./launcherUtils";
 import { tauriApi } from "../api/tauri";
 import { trackEvent } from "../api/events";
@@ -52,6 +53,17 @@ export interface LaunchOptions {
   tauriApi: typeof tauriApi;
 }
 
+/**
+ * 结果执行成功后回写打开次数，让各类结果的常用项逐渐排到前面（失败不影响启动流程）
+ */
+export function recordResultAction(result: SearchResult): void {
+  const key = getResultKey(result);
+  if (!key) return;
+  void tauriApi.recordResultAction(key, result.type).catch((error) => {
+    console.warn(`[结果权重] ✗ 记录失败: ${key}`, error);
+  });
+}
+
 /**
  * 处理搜索结果启动
  */
@@ -221,6 +233,7 @@ export async function handleLaunch(options: LaunchOptions): Promise<void> {
     const pathLower = result.path?.toLowerCase() || "";
     if (/^https?:\/\//.test(pathLower)) {
       await tauriApi.openUrl(result.path);
+      recordResultAction(result);
       await hideLauncherAndResetState();
       return;
     }
@@ -233,18 +246,21 @@ export async function handleLaunch(options: LaunchOptions): Promise<void> {
       return;
     } else if (result.type === "url" && result.url) {
       await tauriApi.openUrl(result.url);
+      recordResultAction(result);
       // 注意：历史记录的更新已在开头统一处理
       await hideLauncherAndResetState();
       return;
     } else if (result.type === "search") {
       // 处理搜索类型：打开浏览器进行搜索
       await tauriApi.openUrl(result.path);
+      recordResultAction(result);
       await hideLauncherAndResetState();
       return;
     } else if (result.type === "email" && result.email) {
       // 复制邮箱地址到剪贴板
       try {
         await navigator.clipboard.writeText(result.email);
+        recordResultAction(result);
         // 显示成功提示，不隐藏启动器
         setSuccessMessage(`已复制邮箱地址：${result.email}`);
         // 3秒后自动关闭提示
@@ -263,6 +279,7 @@ export async function handleLaunch(options: LaunchOptions): Promise<void> {
 
       // 打开窗口（如果窗口已存在，会立即收到 ready 事件；如果是新窗口，会在组件挂载后收到 ready 事件）
       await tauriApi.showJsonFormatterWindow();
+      recordResultAction(result);
 
       // 关闭启动器
       await hideLauncherAndResetState();
@@ -297,6 +314,7 @@ export async function handleLaunch(options: LaunchOptions): Promise<void> {
         // 启动应用
         await tauriApi.launchApplication(result.app);
         trackEvent("app_launched", { name: result.app.name });
+        recordResultAction(result);
 
         // 注意：open_history 的更新已经在 handleLaunch 开头处理了，这里不需要重复更新
 
@@ -461,7 +479,9 @@ export async function handleLaunch(options: LaunchOptions): Promise<void> {
     } else if (result.type === "file" && result.file) {
       try {
         // 注意：历史记录的更新已在开头统一处理，launchFile 不再更新历史记录
-        await tauriApi.launchFile(result.file.path);
+        if (await tauriApi.launchFile(result.file.path)) {
+          recordResultAction(result);
+        }
 
         // 刷新文件历史缓存以同步后端更新后的数据（包括使用次数）
         void refreshFileHistoryCache()
@@ -518,13 +538,16 @@ export async function handleLaunch(options: LaunchOptions): Promise<void> {
         // 如果 Everything 返回的是以 http/https 开头的链接，作为 URL 处理，走浏览器打开
         if (everythingPath && /^https?:\/\//i.test(everythingPath)) {
           await tauriApi.openUrl(everythingPath);
+          recordResultAction(result);
           // 打开链接后直接隐藏启动器，不再走后续文件历史逻辑
           await hideLauncherAndResetState();
           return;
         }
 
         // 注意：历史记录的更新已在开头统一处理，launchFile 不再更新历史记录
-        await tauriApi.launchFile(everythingPath);
+        if (await tauriApi.launchFile(everythingPath)) {
+          recordResultAction(result);
+        }
 
         // 刷新文件历史缓存以确保与数据库同步
         void refreshFileHistoryCache()
@@ -560,6 +583,7 @@ export async function handleLaunch(options: LaunchOptions): Promise<void> {
       setMemoEditContent(result.memo.content);
       setIsEditingMemo(false);
       setIsMemoModalOpen(true);
+      recordResultAction(result);
       // 不关闭启动器，让用户查看/编辑备忘录
       return;
     } else if (result.type === "plugin" && result.plugin) {
@@ -582,6 +606,7 @@ export async function handleLaunch(options: LaunchOptions): Promise<void> {
       };
 
       await executePlugin(result.plugin.id, pluginContext);
+      recordResultAction(result);
       // 插件执行后清理状态
       setQuery("");
       setSelectedIndex(0);
@@ -10,6 +10,7 @@ import {
   shouldShowInHorizontal,
   getResultUsageInfo,
   calculateRelevanceScore,
+  isValidIcon,
 } from "./launcherUtils";
 
 // SearchResult 类型定义（与 LauncherWindow.tsx 中的定义保持一致）
@@ -28,6 +29,81 @@ export type SearchResult = {
   path: string;
 };
 
+/**
+ * 参与按路径合并的结果类型
+ */
+const MERGEABLE_RESULT_TYPES = new Set<SearchResult["type"]>(["app", "file", "everything"]);
+
+/**
+ * 结果的信息完整度：应用索引结果（带拼音）优先，其次是有图标、有使用记录的结果
+ */
+function getResultRichness(result: SearchResult): number {
+  let score = 0;
+  if (result.type === "app") score += 8;
+  if (result.app?.name_pinyin || result.app?.name_pinyin_initials) score += 4;
+  if (isValidIcon(result.app?.icon)) score += 2;
+  if (result.file) score += 1;
+  return score;
+}
+
+/**
+ * 按规范化路径合并应用、文件历史与 Everything 结果
+ * 同一路径出现多次时保留信息更全的一条（位置取首次出现处），
+ * 并补上另一条的 app / file / everything 数据，使合并后的结果具备各来源的动作与排序信息
+ */
+export function mergeResultsByPath(results: SearchResult[]): SearchResult[] {
+  const merged: SearchResult[] = [];
+  const indexByPath = new Map<string, number>();
+
+  for (const result of results) {
+    if (!MERGEABLE_RESULT_TYPES.has(result.type)) {
+      merged.push(result);
+      continue;
+    }
+
+    const key = normalizePathForHistory(result.path).replace(/\/+$/, "");
+    const index = indexByPath.get(key);
+    if (index === undefined) {
+      indexByPath.set(key, merged.length);
+      merged.push(result);
+      continue;
+    }
+
+    const existing = merged[index];
+    const [primary, secondary] =
+      getResultRichness(result) > getResultRichness(existing)
+        ? [result, existing]
+        : [existing, result];
+    // 保留的结果没有图标时沿用另一条的图标
+    const app =
+      primary.app && secondary.app && !isValidIcon(primary.app.icon) && isValidIcon(secondary.app.icon)
+        ? { ...primary.app, icon: secondary.app.icon }
+        : primary.app ?? secondary.app;
+    merged[index] = {
+      ...primary,
+      app,
+      file: primary.file ?? secondary.file,
+      everything: primary.everything ?? secondary.everything,
+    };
+  }
+
+  return merged;
+}
+
+/**
+ * 结果权重使用的唯一标识：路径类结果按规范化路径（与合并规则一致），其他结果按类型 + ID
+ */
+export function getResultKey(result: SearchResult): string | null {
+  if (result.type === "plugin" && result.plugin) return `plugin:${result.plugin.id}`;
+  if (result.type === "memo" && result.memo) return `memo:${result.memo.id}`;
+  if (result.type === "email" && result.email) return `email:${result.email}`;
+  if (!result.path) return null;
+  if (MERGEABLE_RESULT_TYPES.has(result.type)) {
+    return normalizePathForHistory(result.path).replace(/\/+$/, "");
+  }
+  return `${result.type}:${result.path}`;
+}
+
 /**
  * 清空所有结果状态
  */
@@ -170,7 +246,7 @@ export function splitResults(
       
       // 普通应用的去重逻辑
       // 规范化路径：统一使用正斜杠，转小写
-      const normalizedPath = result.path;
+      const normalizedPath = normalizePathForHistory(result.path);
       
       if (!normalizedPathMap.has(normalizedPath)) {
         // 路径不存在，直接添加